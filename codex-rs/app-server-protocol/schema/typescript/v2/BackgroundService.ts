@@ -0,0 +1,27 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { BackgroundServiceHealth } from "./BackgroundServiceHealth";
+import type { BackgroundServiceSource } from "./BackgroundServiceSource";
+import type { BackgroundServiceStatus } from "./BackgroundServiceStatus";
+
+/**
+ * A long-running service declared in config or started by the model.
+ */
+export type BackgroundService = { name: string, command: Array<string>, source: BackgroundServiceSource, status: BackgroundServiceStatus, 
+/**
+ * Result of the latest health check; null when the service has none.
+ */
+health: BackgroundServiceHealth | null, 
+/**
+ * Times the service was restarted after exiting on its own.
+ */
+restarts: number, 
+/**
+ * Exit code of the last run, once it has exited.
+ */
+exitCode: number | null, 
+/**
+ * Most recent log lines, oldest first.
+ */
+recentLogs: Array<string>, };
@@ -0,0 +1,10 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { McpServerPrompts } from "./McpServerPrompts";
+
+export type McpServerPromptListResponse = { 
+/**
+ * Servers that publish prompts, sorted by server name.
+ */
+data: Array<McpServerPrompts>, };
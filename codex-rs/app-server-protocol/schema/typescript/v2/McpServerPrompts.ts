@@ -0,0 +1,10 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Prompt } from "../Prompt";
+
+export type McpServerPrompts = { 
+/**
+ * Name of the MCP server that publishes these prompts.
+ */
+server: string, prompts: Array<Prompt>, };
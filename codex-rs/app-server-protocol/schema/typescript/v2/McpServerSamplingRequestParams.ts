@@ -0,0 +1,18 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { McpServerSamplingMessage } from "./McpServerSamplingMessage";
+
+/**
+ * An MCP server asked Codex to run a model completion with the thread's
+ * model provider. The completion only runs if the client approves.
+ */
+export type McpServerSamplingRequestParams = { threadId: string, 
+/**
+ * Active Codex turn when this request was observed, if app-server could correlate one.
+ */
+turnId: string | null, serverName: string, systemPrompt: string | null, messages: Array<McpServerSamplingMessage>, 
+/**
+ * Output token cap for the completion, after applying the server's configured limits.
+ */
+maxTokens: number, };
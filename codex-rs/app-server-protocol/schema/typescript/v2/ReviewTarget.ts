@@ -6,4 +6,8 @@ export type ReviewTarget = { "type": "uncommittedChanges" } | { "type": "baseBra
 /**
  * Optional human-readable label (e.g., commit subject) for UIs.
  */
+title: string | null, } | { "type": "commitRange", base: string, head: string, } | { "type": "patchFile", path: string, } | { "type": "diff", diff: string, 
+/**
+ * Optional human-readable label for UIs.
+ */
 title: string | null, } | { "type": "custom", instructions: string, };
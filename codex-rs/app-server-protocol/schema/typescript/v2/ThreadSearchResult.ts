@@ -0,0 +1,15 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Thread } from "./Thread";
+import type { ThreadSearchMatchKind } from "./ThreadSearchMatchKind";
+
+export type ThreadSearchResult = { thread: Thread, 
+/**
+ * The kind of content that matched best.
+ */
+matchKind: ThreadSearchMatchKind, 
+/**
+ * A short excerpt of the best matching content.
+ */
+snippet: string, };
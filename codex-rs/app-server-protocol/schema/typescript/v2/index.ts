@@ -24,6 +24,11 @@ export type { AppsDefaultConfig } from "./AppsDefaultConfig";
 export type { AppsListParams } from "./AppsListParams";
 export type { AppsListResponse } from "./AppsListResponse";
 export type { AskForApproval } from "./AskForApproval";
+export type { BackgroundService } from "./BackgroundService";
+export type { BackgroundServiceAction } from "./BackgroundServiceAction";
+export type { BackgroundServiceHealth } from "./BackgroundServiceHealth";
+export type { BackgroundServiceSource } from "./BackgroundServiceSource";
+export type { BackgroundServiceStatus } from "./BackgroundServiceStatus";
 export type { ByteRange } from "./ByteRange";
 export type { CancelLoginAccountParams } from "./CancelLoginAccountParams";
 export type { CancelLoginAccountResponse } from "./CancelLoginAccountResponse";
@@ -148,7 +153,17 @@ export type { McpServerElicitationRequestResponse } from "./McpServerElicitation
 export type { McpServerOauthLoginCompletedNotification } from "./McpServerOauthLoginCompletedNotification";
 export type { McpServerOauthLoginParams } from "./McpServerOauthLoginParams";
 export type { McpServerOauthLoginResponse } from "./McpServerOauthLoginResponse";
+export type { McpServerPromptGetParams } from "./McpServerPromptGetParams";
+export type { McpServerPromptGetResponse } from "./McpServerPromptGetResponse";
+export type { McpServerPromptListParams } from "./McpServerPromptListParams";
+export type { McpServerPromptListResponse } from "./McpServerPromptListResponse";
+export type { McpServerPrompts } from "./McpServerPrompts";
 export type { McpServerRefreshResponse } from "./McpServerRefreshResponse";
+export type { McpServerSamplingDecision } from "./McpServerSamplingDecision";
+export type { McpServerSamplingMessage } from "./McpServerSamplingMessage";
+export type { McpServerSamplingRequestParams } from "./McpServerSamplingRequestParams";
+export type { McpServerSamplingRequestResponse } from "./McpServerSamplingRequestResponse";
+export type { McpServerSamplingRole } from "./McpServerSamplingRole";
 export type { McpServerStatus } from "./McpServerStatus";
 export type { McpToolCallError } from "./McpToolCallError";
 export type { McpToolCallProgressNotification } from "./McpToolCallProgressNotification";
@@ -206,6 +221,7 @@ export type { ReviewDelivery } from "./ReviewDelivery";
 export type { ReviewStartParams } from "./ReviewStartParams";
 export type { ReviewStartResponse } from "./ReviewStartResponse";
 export type { ReviewTarget } from "./ReviewTarget";
+export type { RunBudgetKind } from "./RunBudgetKind";
 export type { SandboxMode } from "./SandboxMode";
 export type { SandboxPolicy } from "./SandboxPolicy";
 export type { SandboxWorkspaceWrite } from "./SandboxWorkspaceWrite";
@@ -264,6 +280,10 @@ export type { ThreadResumeParams } from "./ThreadResumeParams";
 export type { ThreadResumeResponse } from "./ThreadResumeResponse";
 export type { ThreadRollbackParams } from "./ThreadRollbackParams";
 export type { ThreadRollbackResponse } from "./ThreadRollbackResponse";
+export type { ThreadSearchMatchKind } from "./ThreadSearchMatchKind";
+export type { ThreadSearchParams } from "./ThreadSearchParams";
+export type { ThreadSearchResponse } from "./ThreadSearchResponse";
+export type { ThreadSearchResult } from "./ThreadSearchResult";
 export type { ThreadSetNameParams } from "./ThreadSetNameParams";
 export type { ThreadSetNameResponse } from "./ThreadSetNameResponse";
 export type { ThreadSortKey } from "./ThreadSortKey";
@@ -272,6 +272,16 @@ client_request_definitions! {
         params: v2::ThreadBackgroundTerminalsCleanParams,
         response: v2::ThreadBackgroundTerminalsCleanResponse,
     },
+    #[experimental("thread/backgroundTerminals/list")]
+    ThreadBackgroundTerminalsList => "thread/backgroundTerminals/list" {
+        params: v2::ThreadBackgroundTerminalsListParams,
+        response: v2::ThreadBackgroundTerminalsListResponse,
+    },
+    #[experimental("thread/backgroundTerminals/control")]
+    ThreadBackgroundTerminalsControl => "thread/backgroundTerminals/control" {
+        params: v2::ThreadBackgroundTerminalsControlParams,
+        response: v2::ThreadBackgroundTerminalsControlResponse,
+    },
     ThreadRollback => "thread/rollback" {
         params: v2::ThreadRollbackParams,
         response: v2::ThreadRollbackResponse,
@@ -280,6 +290,10 @@ client_request_definitions! {
         params: v2::ThreadListParams,
         response: v2::ThreadListResponse,
     },
+    ThreadSearch => "thread/search" {
+        params: v2::ThreadSearchParams,
+        response: v2::ThreadSearchResponse,
+    },
     ThreadLoadedList => "thread/loaded/list" {
         params: v2::ThreadLoadedListParams,
         response: v2::ThreadLoadedListResponse,
@@ -398,6 +412,16 @@ client_request_definitions! {
         response: v2::ListMcpServerStatusResponse,
     },
 
+    McpServerPromptList => "mcpServer/prompt/list" {
+        params: v2::McpServerPromptListParams,
+        response: v2::McpServerPromptListResponse,
+    },
+
+    McpServerPromptGet => "mcpServer/prompt/get" {
+        params: v2::McpServerPromptGetParams,
+        response: v2::McpServerPromptGetResponse,
+    },
+
     WindowsSandboxSetupStart => "windowsSandbox/setupStart" {
         params: v2::WindowsSandboxSetupStartParams,
         response: v2::WindowsSandboxSetupStartResponse,
@@ -733,6 +757,12 @@ server_request_definitions! {
         response: v2::McpServerElicitationRequestResponse,
     },
 
+    /// Request approval to run a model completion for an MCP server.
+    McpServerSamplingRequest => "mcpServer/sampling/request" {
+        params: v2::McpServerSamplingRequestParams,
+        response: v2::McpServerSamplingRequestResponse,
+    },
+
     /// Request approval for additional permissions from the user.
     PermissionsRequestApproval => "item/permissions/requestApproval" {
         params: v2::PermissionsRequestApprovalParams,
@@ -1196,6 +1226,50 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn serialize_mcp_server_sampling_request() -> Result<()> {
+        let params = v2::McpServerSamplingRequestParams {
+            thread_id: "thr_123".to_string(),
+            turn_id: None,
+            server_name: "docs".to_string(),
+            system_prompt: Some("Be brief.".to_string()),
+            messages: vec![v2::McpServerSamplingMessage {
+                role: v2::McpServerSamplingRole::User,
+                text: "Summarize this page.".to_string(),
+            }],
+            max_tokens: 256,
+        };
+        let request = ServerRequest::McpServerSamplingRequest {
+            request_id: RequestId::Integer(3),
+            params,
+        };
+
+        assert_eq!(
+            json!({
+                "method": "mcpServer/sampling/request",
+                "id": 3,
+                "params": {
+                    "threadId": "thr_123",
+                    "turnId": null,
+                    "serverName": "docs",
+                    "systemPrompt": "Be brief.",
+                    "messages": [{ "role": "user", "text": "Summarize this page." }],
+                    "maxTokens": 256
+                }
+            }),
+            serde_json::to_value(&request)?,
+        );
+        assert_eq!(
+            serde_json::from_value::<v2::McpServerSamplingRequestResponse>(
+                json!({ "decision": "approve" })
+            )?,
+            v2::McpServerSamplingRequestResponse {
+                decision: v2::McpServerSamplingDecision::Approve,
+            }
+        );
+        Ok(())
+    }
+
     #[test]
     fn serialize_get_account_rate_limits() -> Result<()> {
         let request = ClientRequest::GetAccountRateLimits {
@@ -1460,6 +1534,31 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn serialize_thread_background_terminals_control() -> Result<()> {
+        let request = ClientRequest::ThreadBackgroundTerminalsControl {
+            request_id: RequestId::Integer(8),
+            params: v2::ThreadBackgroundTerminalsControlParams {
+                thread_id: "thr_123".to_string(),
+                name: "db".to_string(),
+                action: v2::BackgroundServiceAction::Restart,
+            },
+        };
+        assert_eq!(
+            json!({
+                "method": "thread/backgroundTerminals/control",
+                "id": 8,
+                "params": {
+                    "threadId": "thr_123",
+                    "name": "db",
+                    "action": "restart"
+                }
+            }),
+            serde_json::to_value(&request)?,
+        );
+        Ok(())
+    }
+
     #[test]
     fn serialize_thread_realtime_start() -> Result<()> {
         let request = ClientRequest::ThreadRealtimeStart {
@@ -26,6 +26,7 @@ use codex_protocol::config_types::WebSearchMode;
 use codex_protocol::config_types::WebSearchToolConfig;
 use codex_protocol::items::AgentMessageContent as CoreAgentMessageContent;
 use codex_protocol::items::TurnItem as CoreTurnItem;
+use codex_protocol::mcp::Prompt as McpPrompt;
 use codex_protocol::mcp::Resource as McpResource;
 use codex_protocol::mcp::ResourceTemplate as McpResourceTemplate;
 use codex_protocol::mcp::Tool as McpTool;
@@ -159,6 +160,10 @@ pub enum CodexErrorInfo {
         #[ts(rename = "httpStatusCode")]
         http_status_code: Option<u16>,
     },
+    /// A run budget ran out and the turn was stopped before the agent finished.
+    RunBudgetExceeded {
+        budget: RunBudgetKind,
+    },
     Other,
 }
 
@@ -185,6 +190,9 @@ impl From<CoreCodexErrorInfo> for CodexErrorInfo {
             CoreCodexErrorInfo::ResponseTooManyFailedAttempts { http_status_code } => {
                 CodexErrorInfo::ResponseTooManyFailedAttempts { http_status_code }
             }
+            CoreCodexErrorInfo::RunBudgetExceeded { budget } => CodexErrorInfo::RunBudgetExceeded {
+                budget: budget.into(),
+            },
             CoreCodexErrorInfo::Other => CodexErrorInfo::Other,
         }
     }
@@ -300,6 +308,52 @@ v2_enum_from_core!(
     }
 );
 
+v2_enum_from_core!(
+    pub enum RunBudgetKind from codex_protocol::protocol::RunBudgetKind {
+        MaxTurns,
+        MaxOutputTokens,
+        MaxTotalTokens,
+        Timeout,
+        MaxToolCalls
+    }
+);
+
+v2_enum_from_core!(
+    pub enum BackgroundServiceSource from codex_protocol::protocol::BackgroundServiceSource {
+        Config, Model
+    }
+);
+
+v2_enum_from_core!(
+    pub enum BackgroundServiceStatus from codex_protocol::protocol::BackgroundServiceStatus {
+        Stopped, Running, Restarting, Exited
+    }
+);
+
+v2_enum_from_core!(
+    pub enum BackgroundServiceHealth from codex_protocol::protocol::BackgroundServiceHealth {
+        Pending, Healthy, Unhealthy
+    }
+);
+
+v2_enum_from_core!(
+    pub enum BackgroundServiceAction from codex_protocol::protocol::BackgroundServiceAction {
+        Start, Stop, Restart
+    }
+);
+
+v2_enum_from_core!(
+    pub enum McpServerSamplingRole from codex_protocol::approvals::McpSamplingRole {
+        User, Assistant
+    }
+);
+
+v2_enum_from_core!(
+    pub enum McpServerSamplingDecision from codex_protocol::approvals::McpSamplingDecision {
+        Approve, Decline
+    }
+);
+
 v2_enum_from_core!(
     pub enum ModelRerouteReason from CoreModelRerouteReason {
         HighRiskCyberActivity
@@ -308,7 +362,7 @@ v2_enum_from_core!(
 
 v2_enum_from_core!(
     pub enum HookEventName from CoreHookEventName {
-        SessionStart, Stop
+        SessionStart, UserPromptSubmit, PreToolUse, PostToolUse, Stop
     }
 );
 
@@ -1879,6 +1933,50 @@ pub struct ListMcpServerStatusResponse {
     pub next_cursor: Option<String>,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct McpServerPromptListParams {
+    pub thread_id: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct McpServerPrompts {
+    /// Name of the MCP server that publishes these prompts.
+    pub server: String,
+    pub prompts: Vec<McpPrompt>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct McpServerPromptListResponse {
+    /// Servers that publish prompts, sorted by server name.
+    pub data: Vec<McpServerPrompts>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct McpServerPromptGetParams {
+    pub thread_id: String,
+    pub server: String,
+    pub name: String,
+    #[serde(default)]
+    #[ts(optional = nullable)]
+    pub arguments: Option<HashMap<String, String>>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct McpServerPromptGetResponse {
+    /// The prompt's messages flattened to text, ready to send as user input.
+    pub text: String,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export_to = "v2/")]
@@ -2669,6 +2767,69 @@ pub struct ThreadBackgroundTerminalsCleanParams {
 #[ts(export_to = "v2/")]
 pub struct ThreadBackgroundTerminalsCleanResponse {}
 
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadBackgroundTerminalsListParams {
+    pub thread_id: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadBackgroundTerminalsListResponse {
+    pub services: Vec<BackgroundService>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadBackgroundTerminalsControlParams {
+    pub thread_id: String,
+    /// Name of the background service to control.
+    pub name: String,
+    pub action: BackgroundServiceAction,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadBackgroundTerminalsControlResponse {}
+
+/// A long-running service declared in config or started by the model.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct BackgroundService {
+    pub name: String,
+    pub command: Vec<String>,
+    pub source: BackgroundServiceSource,
+    pub status: BackgroundServiceStatus,
+    /// Result of the latest health check; null when the service has none.
+    pub health: Option<BackgroundServiceHealth>,
+    /// Times the service was restarted after exiting on its own.
+    pub restarts: u32,
+    /// Exit code of the last run, once it has exited.
+    pub exit_code: Option<i32>,
+    /// Most recent log lines, oldest first.
+    pub recent_logs: Vec<String>,
+}
+
+impl From<codex_protocol::protocol::BackgroundServiceSummary> for BackgroundService {
+    fn from(value: codex_protocol::protocol::BackgroundServiceSummary) -> Self {
+        Self {
+            name: value.name,
+            command: value.command,
+            source: value.source.into(),
+            status: value.status.into(),
+            health: value.health.map(Into::into),
+            restarts: value.restarts,
+            exit_code: value.exit_code,
+            recent_logs: value.recent_logs,
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export_to = "v2/")]
@@ -2763,6 +2924,59 @@ pub struct ThreadListResponse {
     pub next_cursor: Option<String>,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadSearchParams {
+    /// Words to search for in user messages, agent messages and command
+    /// output. Every word must appear in the matching content.
+    pub query: String,
+    /// Optional maximum number of threads to return; defaults to a reasonable
+    /// server-side value.
+    #[ts(optional = nullable)]
+    pub limit: Option<u32>,
+    /// Optional provider filter; when set, only sessions recorded under these
+    /// providers are returned. When present but empty, includes all providers.
+    #[ts(optional = nullable)]
+    pub model_providers: Option<Vec<String>>,
+    /// Optional source filter; when set, only sessions from these source kinds
+    /// are returned. When omitted or empty, defaults to interactive sources.
+    #[ts(optional = nullable)]
+    pub source_kinds: Option<Vec<ThreadSourceKind>>,
+    /// Optional archived filter; when set to true, only archived threads are searched.
+    /// If false or null, only non-archived threads are searched.
+    #[ts(optional = nullable)]
+    pub archived: Option<bool>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(rename_all = "camelCase", export_to = "v2/")]
+pub enum ThreadSearchMatchKind {
+    UserMessage,
+    AgentMessage,
+    CommandOutput,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadSearchResult {
+    pub thread: Thread,
+    /// The kind of content that matched best.
+    pub match_kind: ThreadSearchMatchKind,
+    /// A short excerpt of the best matching content.
+    pub snippet: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadSearchResponse {
+    /// Matching threads, most relevant first.
+    pub data: Vec<ThreadSearchResult>,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export_to = "v2/")]
@@ -3693,6 +3907,26 @@ pub enum ReviewTarget {
         title: Option<String>,
     },
 
+    /// Review the changes in `base..head` without checking either revision out.
+    #[serde(rename_all = "camelCase")]
+    #[ts(rename_all = "camelCase")]
+    CommitRange { base: String, head: String },
+
+    /// Review a `.patch`/`.diff` file. Relative paths resolve against the
+    /// session cwd; the patch is never applied.
+    #[serde(rename_all = "camelCase")]
+    #[ts(rename_all = "camelCase")]
+    PatchFile { path: PathBuf },
+
+    /// Review an inline unified diff, e.g. one piped on stdin.
+    #[serde(rename_all = "camelCase")]
+    #[ts(rename_all = "camelCase")]
+    Diff {
+        diff: String,
+        /// Optional human-readable label for UIs.
+        title: Option<String>,
+    },
+
     /// Arbitrary instructions, equivalent to the old free-form prompt.
     #[serde(rename_all = "camelCase")]
     #[ts(rename_all = "camelCase")]
@@ -5255,6 +5489,46 @@ impl From<rmcp::model::CreateElicitationResult> for McpServerElicitationRequestR
     }
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct McpServerSamplingMessage {
+    pub role: McpServerSamplingRole,
+    pub text: String,
+}
+
+impl From<codex_protocol::approvals::McpSamplingMessage> for McpServerSamplingMessage {
+    fn from(value: codex_protocol::approvals::McpSamplingMessage) -> Self {
+        Self {
+            role: value.role.into(),
+            text: value.text,
+        }
+    }
+}
+
+/// An MCP server asked Codex to run a model completion with the thread's
+/// model provider. The completion only runs if the client approves.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct McpServerSamplingRequestParams {
+    pub thread_id: String,
+    /// Active Codex turn when this request was observed, if app-server could correlate one.
+    pub turn_id: Option<String>,
+    pub server_name: String,
+    pub system_prompt: Option<String>,
+    pub messages: Vec<McpServerSamplingMessage>,
+    /// Output token cap for the completion, after applying the server's configured limits.
+    pub max_tokens: u32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct McpServerSamplingRequestResponse {
+    pub decision: McpServerSamplingDecision,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export_to = "v2/")]
@@ -7037,6 +7311,22 @@ mod tests {
         );
     }
 
+    #[test]
+    fn codex_error_info_serializes_run_budget_in_camel_case() {
+        let value = CodexErrorInfo::from(CoreCodexErrorInfo::RunBudgetExceeded {
+            budget: codex_protocol::protocol::RunBudgetKind::MaxToolCalls,
+        });
+
+        assert_eq!(
+            serde_json::to_value(value).unwrap(),
+            json!({
+                "runBudgetExceeded": {
+                    "budget": "maxToolCalls"
+                }
+            })
+        );
+    }
+
     #[test]
     fn dynamic_tool_response_serializes_content_items() {
         let value = serde_json::to_value(DynamicToolCallResponse {
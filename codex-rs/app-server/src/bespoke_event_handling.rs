@@ -52,6 +52,9 @@ use codex_app_server_protocol::JSONRPCErrorError;
 use codex_app_server_protocol::McpServerElicitationAction;
 use codex_app_server_protocol::McpServerElicitationRequestParams;
 use codex_app_server_protocol::McpServerElicitationRequestResponse;
+use codex_app_server_protocol::McpServerSamplingDecision;
+use codex_app_server_protocol::McpServerSamplingRequestParams;
+use codex_app_server_protocol::McpServerSamplingRequestResponse;
 use codex_app_server_protocol::McpToolCallError;
 use codex_app_server_protocol::McpToolCallResult;
 use codex_app_server_protocol::McpToolCallStatus;
@@ -689,6 +692,52 @@ pub(crate) async fn apply_bespoke_event_handling(
                 });
             }
         }
+        EventMsg::McpSamplingRequest(request) => {
+            if matches!(api_version, ApiVersion::V2) {
+                let permission_guard = thread_watch_manager
+                    .note_permission_requested(&conversation_id.to_string())
+                    .await;
+                let turn_id = match request.turn_id.clone() {
+                    Some(turn_id) => Some(turn_id),
+                    None => {
+                        let state = thread_state.lock().await;
+                        state.active_turn_snapshot().map(|turn| turn.id)
+                    }
+                };
+                let params = McpServerSamplingRequestParams {
+                    thread_id: conversation_id.to_string(),
+                    turn_id,
+                    server_name: request.server_name.clone(),
+                    system_prompt: request.system_prompt,
+                    messages: request.messages.into_iter().map(Into::into).collect(),
+                    max_tokens: request.max_tokens,
+                };
+                let (pending_request_id, rx) = outgoing
+                    .send_request(ServerRequestPayload::McpServerSamplingRequest(params))
+                    .await;
+                tokio::spawn(async move {
+                    on_mcp_server_sampling_response(
+                        request.server_name,
+                        request.id,
+                        pending_request_id,
+                        rx,
+                        conversation,
+                        thread_state,
+                        permission_guard,
+                    )
+                    .await;
+                });
+            } else if let Err(err) = conversation
+                .submit(Op::ResolveMcpSampling {
+                    server_name: request.server_name,
+                    request_id: request.id,
+                    decision: codex_protocol::approvals::McpSamplingDecision::Decline,
+                })
+                .await
+            {
+                error!("failed to submit ResolveMcpSampling: {err}");
+            }
+        }
         EventMsg::RequestPermissions(request) => {
             if matches!(api_version, ApiVersion::V2) {
                 let permission_guard = thread_watch_manager
@@ -2226,6 +2275,57 @@ fn mcp_server_elicitation_response_from_client_result(
     }
 }
 
+async fn on_mcp_server_sampling_response(
+    server_name: String,
+    request_id: codex_protocol::mcp::RequestId,
+    pending_request_id: RequestId,
+    receiver: oneshot::Receiver<ClientRequestResult>,
+    conversation: Arc<CodexThread>,
+    thread_state: Arc<Mutex<ThreadState>>,
+    permission_guard: ThreadWatchActiveGuard,
+) {
+    let response = receiver.await;
+    resolve_server_request_on_thread_listener(&thread_state, pending_request_id).await;
+    drop(permission_guard);
+    let decision = mcp_server_sampling_decision_from_client_result(response);
+
+    if let Err(err) = conversation
+        .submit(Op::ResolveMcpSampling {
+            server_name,
+            request_id,
+            decision: decision.to_core(),
+        })
+        .await
+    {
+        error!("failed to submit ResolveMcpSampling: {err}");
+    }
+}
+
+/// Anything other than an explicit approval declines the request, so a
+/// sampling completion never runs without the user's consent.
+fn mcp_server_sampling_decision_from_client_result(
+    response: std::result::Result<ClientRequestResult, oneshot::error::RecvError>,
+) -> McpServerSamplingDecision {
+    match response {
+        Ok(Ok(value)) => serde_json::from_value::<McpServerSamplingRequestResponse>(value)
+            .map(|response| response.decision)
+            .unwrap_or_else(|err| {
+                error!("failed to deserialize McpServerSamplingRequestResponse: {err}");
+                McpServerSamplingDecision::Decline
+            }),
+        Ok(Err(err)) => {
+            if !is_turn_transition_server_request_error(&err) {
+                error!("request failed with client error: {err:?}");
+            }
+            McpServerSamplingDecision::Decline
+        }
+        Err(err) => {
+            error!("request failed: {err:?}");
+            McpServerSamplingDecision::Decline
+        }
+    }
+}
+
 async fn on_request_permissions_response(
     call_id: String,
     requested_permissions: CoreRequestPermissionProfile,
@@ -2713,6 +2813,31 @@ mod tests {
         );
     }
 
+    #[test]
+    fn mcp_server_sampling_declines_unless_approved() {
+        assert_eq!(
+            mcp_server_sampling_decision_from_client_result(Ok(Ok(
+                serde_json::json!({ "decision": "approve" })
+            ))),
+            McpServerSamplingDecision::Approve
+        );
+        assert_eq!(
+            mcp_server_sampling_decision_from_client_result(Ok(Ok(
+                serde_json::json!({ "decision": "maybe" })
+            ))),
+            McpServerSamplingDecision::Decline
+        );
+        let error = JSONRPCErrorError {
+            code: -1,
+            message: "client request resolved because the turn state was changed".to_string(),
+            data: Some(serde_json::json!({ "reason": "turnTransition" })),
+        };
+        assert_eq!(
+            mcp_server_sampling_decision_from_client_result(Ok(Err(error))),
+            McpServerSamplingDecision::Decline
+        );
+    }
+
     #[test]
     fn request_permissions_turn_transition_error_is_ignored() {
         let error = JSONRPCErrorError {
@@ -75,6 +75,11 @@ use codex_app_server_protocol::LogoutAccountResponse;
 use codex_app_server_protocol::McpServerOauthLoginCompletedNotification;
 use codex_app_server_protocol::McpServerOauthLoginParams;
 use codex_app_server_protocol::McpServerOauthLoginResponse;
+use codex_app_server_protocol::McpServerPromptGetParams;
+use codex_app_server_protocol::McpServerPromptGetResponse;
+use codex_app_server_protocol::McpServerPromptListParams;
+use codex_app_server_protocol::McpServerPromptListResponse;
+use codex_app_server_protocol::McpServerPrompts;
 use codex_app_server_protocol::McpServerRefreshResponse;
 use codex_app_server_protocol::McpServerStatus;
 use codex_app_server_protocol::MockExperimentalMethodParams;
@@ -118,6 +123,10 @@ use codex_app_server_protocol::ThreadArchiveResponse;
 use codex_app_server_protocol::ThreadArchivedNotification;
 use codex_app_server_protocol::ThreadBackgroundTerminalsCleanParams;
 use codex_app_server_protocol::ThreadBackgroundTerminalsCleanResponse;
+use codex_app_server_protocol::ThreadBackgroundTerminalsControlParams;
+use codex_app_server_protocol::ThreadBackgroundTerminalsControlResponse;
+use codex_app_server_protocol::ThreadBackgroundTerminalsListParams;
+use codex_app_server_protocol::ThreadBackgroundTerminalsListResponse;
 use codex_app_server_protocol::ThreadClosedNotification;
 use codex_app_server_protocol::ThreadCompactStartParams;
 use codex_app_server_protocol::ThreadCompactStartResponse;
@@ -149,6 +158,10 @@ use codex_app_server_protocol::ThreadRealtimeStopResponse;
 use codex_app_server_protocol::ThreadResumeParams;
 use codex_app_server_protocol::ThreadResumeResponse;
 use codex_app_server_protocol::ThreadRollbackParams;
+use codex_app_server_protocol::ThreadSearchMatchKind;
+use codex_app_server_protocol::ThreadSearchParams;
+use codex_app_server_protocol::ThreadSearchResponse;
+use codex_app_server_protocol::ThreadSearchResult;
 use codex_app_server_protocol::ThreadSetNameParams;
 use codex_app_server_protocol::ThreadSetNameResponse;
 use codex_app_server_protocol::ThreadSortKey;
@@ -281,6 +294,7 @@ use codex_protocol::user_input::UserInput as CoreInputItem;
 use codex_rmcp_client::perform_oauth_login_return_url;
 use codex_state::StateRuntime;
 use codex_state::ThreadMetadataBuilder;
+use codex_state::ThreadSearchDocumentKind;
 use codex_state::log_db::LogDbLayer;
 use codex_utils_json_to_toml::json_to_toml;
 use codex_utils_pty::DEFAULT_OUTPUT_BYTES_CAP;
@@ -600,6 +614,31 @@ impl CodexMessageProcessor {
                     .filter(|t| !t.is_empty());
                 ApiReviewTarget::Commit { sha, title }
             }
+            ApiReviewTarget::CommitRange { base, head } => {
+                let base = base.trim().to_string();
+                let head = head.trim().to_string();
+                if base.is_empty() || head.is_empty() {
+                    return Err(invalid_request(
+                        "base and head must not be empty".to_string(),
+                    ));
+                }
+                ApiReviewTarget::CommitRange { base, head }
+            }
+            ApiReviewTarget::PatchFile { path } => {
+                if path.as_os_str().is_empty() {
+                    return Err(invalid_request("path must not be empty".to_string()));
+                }
+                ApiReviewTarget::PatchFile { path }
+            }
+            ApiReviewTarget::Diff { diff, title } => {
+                if diff.trim().is_empty() {
+                    return Err(invalid_request("diff must not be empty".to_string()));
+                }
+                let title = title
+                    .map(|t| t.trim().to_string())
+                    .filter(|t| !t.is_empty());
+                ApiReviewTarget::Diff { diff, title }
+            }
             ApiReviewTarget::Custom { instructions } => {
                 let trimmed = instructions.trim().to_string();
                 if trimmed.is_empty() {
@@ -617,6 +656,11 @@ impl CodexMessageProcessor {
             ApiReviewTarget::UncommittedChanges => CoreReviewTarget::UncommittedChanges,
             ApiReviewTarget::BaseBranch { branch } => CoreReviewTarget::BaseBranch { branch },
             ApiReviewTarget::Commit { sha, title } => CoreReviewTarget::Commit { sha, title },
+            ApiReviewTarget::CommitRange { base, head } => {
+                CoreReviewTarget::CommitRange { base, head }
+            }
+            ApiReviewTarget::PatchFile { path } => CoreReviewTarget::PatchFile { path },
+            ApiReviewTarget::Diff { diff, title } => CoreReviewTarget::Diff { diff, title },
             ApiReviewTarget::Custom { instructions } => CoreReviewTarget::Custom { instructions },
         };
 
@@ -701,6 +745,17 @@ impl CodexMessageProcessor {
                 )
                 .await;
             }
+            ClientRequest::ThreadBackgroundTerminalsList { request_id, params } => {
+                self.thread_background_terminals_list(to_connection_request_id(request_id), params)
+                    .await;
+            }
+            ClientRequest::ThreadBackgroundTerminalsControl { request_id, params } => {
+                self.thread_background_terminals_control(
+                    to_connection_request_id(request_id),
+                    params,
+                )
+                .await;
+            }
             ClientRequest::ThreadRollback { request_id, params } => {
                 self.thread_rollback(to_connection_request_id(request_id), params)
                     .await;
@@ -709,6 +764,10 @@ impl CodexMessageProcessor {
                 self.thread_list(to_connection_request_id(request_id), params)
                     .await;
             }
+            ClientRequest::ThreadSearch { request_id, params } => {
+                self.thread_search(to_connection_request_id(request_id), params)
+                    .await;
+            }
             ClientRequest::ThreadLoadedList { request_id, params } => {
                 self.thread_loaded_list(to_connection_request_id(request_id), params)
                     .await;
@@ -832,6 +891,14 @@ impl CodexMessageProcessor {
                 self.list_mcp_server_status(to_connection_request_id(request_id), params)
                     .await;
             }
+            ClientRequest::McpServerPromptList { request_id, params } => {
+                self.mcp_server_prompt_list(to_connection_request_id(request_id), params)
+                    .await;
+            }
+            ClientRequest::McpServerPromptGet { request_id, params } => {
+                self.mcp_server_prompt_get(to_connection_request_id(request_id), params)
+                    .await;
+            }
             ClientRequest::WindowsSandboxSetupStart { request_id, params } => {
                 self.windows_sandbox_setup_start(to_connection_request_id(request_id), params)
                     .await;
@@ -1752,6 +1819,7 @@ impl CodexMessageProcessor {
             sandbox_cwd.as_path(),
             &codex_linux_sandbox_exe,
             use_legacy_landlock,
+            self.config.permissions.resource_limits,
         ) {
             Ok(exec_request) => {
                 if let Err(error) = self
@@ -2942,6 +3010,131 @@ impl CodexMessageProcessor {
         }
     }
 
+    async fn thread_background_terminals_list(
+        &self,
+        request_id: ConnectionRequestId,
+        params: ThreadBackgroundTerminalsListParams,
+    ) {
+        let ThreadBackgroundTerminalsListParams { thread_id } = params;
+        let thread_id = match ThreadId::from_string(&thread_id) {
+            Ok(id) => id,
+            Err(err) => {
+                self.send_invalid_request_error(request_id, format!("invalid thread id: {err}"))
+                    .await;
+                return;
+            }
+        };
+
+        // Services outlive the session, so this works for threads that are not loaded.
+        let services = self
+            .thread_manager
+            .list_background_services(thread_id)
+            .into_iter()
+            .map(Into::into)
+            .collect();
+        self.outgoing
+            .send_response(
+                request_id,
+                ThreadBackgroundTerminalsListResponse { services },
+            )
+            .await;
+    }
+
+    async fn thread_background_terminals_control(
+        &self,
+        request_id: ConnectionRequestId,
+        params: ThreadBackgroundTerminalsControlParams,
+    ) {
+        let ThreadBackgroundTerminalsControlParams {
+            thread_id,
+            name,
+            action,
+        } = params;
+        let thread_id = match ThreadId::from_string(&thread_id) {
+            Ok(id) => id,
+            Err(err) => {
+                self.send_invalid_request_error(request_id, format!("invalid thread id: {err}"))
+                    .await;
+                return;
+            }
+        };
+
+        match self
+            .thread_manager
+            .control_background_service(thread_id, &name, action.to_core())
+        {
+            Ok(()) => {
+                self.outgoing
+                    .send_response(request_id, ThreadBackgroundTerminalsControlResponse {})
+                    .await;
+            }
+            Err(err) => {
+                self.send_invalid_request_error(request_id, err.to_string())
+                    .await;
+            }
+        }
+    }
+
+    async fn mcp_server_prompt_list(
+        &self,
+        request_id: ConnectionRequestId,
+        params: McpServerPromptListParams,
+    ) {
+        let McpServerPromptListParams { thread_id } = params;
+        let (_, thread) = match self.load_thread(&thread_id).await {
+            Ok(v) => v,
+            Err(error) => {
+                self.outgoing.send_error(request_id, error).await;
+                return;
+            }
+        };
+
+        let mut data: Vec<McpServerPrompts> = thread
+            .list_mcp_prompts()
+            .await
+            .into_iter()
+            .map(|(server, prompts)| McpServerPrompts { server, prompts })
+            .collect();
+        data.sort_by(|a, b| a.server.cmp(&b.server));
+        self.outgoing
+            .send_response(request_id, McpServerPromptListResponse { data })
+            .await;
+    }
+
+    async fn mcp_server_prompt_get(
+        &self,
+        request_id: ConnectionRequestId,
+        params: McpServerPromptGetParams,
+    ) {
+        let McpServerPromptGetParams {
+            thread_id,
+            server,
+            name,
+            arguments,
+        } = params;
+        let (_, thread) = match self.load_thread(&thread_id).await {
+            Ok(v) => v,
+            Err(error) => {
+                self.outgoing.send_error(request_id, error).await;
+                return;
+            }
+        };
+
+        match thread
+            .get_mcp_prompt(&server, &name, arguments.unwrap_or_default())
+            .await
+        {
+            Ok(text) => {
+                self.outgoing
+                    .send_response(request_id, McpServerPromptGetResponse { text })
+                    .await;
+            }
+            Err(err) => {
+                self.send_invalid_request_error(request_id, err).await;
+            }
+        }
+    }
+
     async fn thread_list(&self, request_id: ConnectionRequestId, params: ThreadListParams) {
         let ThreadListParams {
             cursor,
@@ -3023,6 +3216,115 @@ impl CodexMessageProcessor {
         self.outgoing.send_response(request_id, response).await;
     }
 
+    async fn thread_search(&self, request_id: ConnectionRequestId, params: ThreadSearchParams) {
+        let ThreadSearchParams {
+            query,
+            limit,
+            model_providers,
+            source_kinds,
+            archived,
+        } = params;
+
+        let limit = limit
+            .map(|value| value as usize)
+            .unwrap_or(THREAD_LIST_DEFAULT_LIMIT)
+            .clamp(1, THREAD_LIST_MAX_LIMIT);
+        let model_provider_filter = match model_providers {
+            Some(providers) => {
+                if providers.is_empty() {
+                    None
+                } else {
+                    Some(providers)
+                }
+            }
+            None => Some(vec![self.config.model_provider_id.clone()]),
+        };
+        let fallback_provider = self.config.model_provider_id.clone();
+        let (allowed_sources, source_kind_filter) = compute_source_filters(source_kinds);
+        let hits = match RolloutRecorder::search_threads(
+            &self.config,
+            query.as_str(),
+            limit,
+            allowed_sources.as_slice(),
+            model_provider_filter.as_deref(),
+            archived.unwrap_or(false),
+        )
+        .await
+        {
+            Ok(hits) => hits,
+            Err(err) => {
+                let error = JSONRPCErrorError {
+                    code: INTERNAL_ERROR_CODE,
+                    message: format!("failed to search threads: {err}"),
+                    data: None,
+                };
+                self.outgoing.send_error(request_id, error).await;
+                return;
+            }
+        };
+
+        let state_db_ctx = get_state_db(&self.config).await;
+        let mut results = Vec::with_capacity(hits.len());
+        let mut thread_ids = HashSet::with_capacity(hits.len());
+        let mut status_ids = Vec::with_capacity(hits.len());
+        for hit in hits {
+            let Some(summary) = summary_from_thread_list_item(
+                hit.item,
+                fallback_provider.as_str(),
+                state_db_ctx.as_ref(),
+            )
+            .await
+            else {
+                continue;
+            };
+            if !source_kind_filter
+                .as_ref()
+                .is_none_or(|filter| source_kind_matches(&summary.source, filter))
+            {
+                continue;
+            }
+            let conversation_id = summary.conversation_id;
+            thread_ids.insert(conversation_id);
+            let thread = summary_to_thread(summary);
+            status_ids.push(thread.id.clone());
+            results.push((
+                conversation_id,
+                ThreadSearchResult {
+                    thread,
+                    match_kind: thread_search_match_kind(hit.match_kind),
+                    snippet: hit.snippet,
+                },
+            ));
+        }
+
+        let names = match find_thread_names_by_ids(&self.config.codex_home, &thread_ids).await {
+            Ok(names) => names,
+            Err(err) => {
+                warn!("Failed to read thread names: {err}");
+                HashMap::new()
+            }
+        };
+
+        let statuses = self
+            .thread_watch_manager
+            .loaded_statuses_for_threads(status_ids)
+            .await;
+
+        let data = results
+            .into_iter()
+            .map(|(conversation_id, mut result)| {
+                result.thread.name = names.get(&conversation_id).cloned();
+                if let Some(status) = statuses.get(&result.thread.id) {
+                    result.thread.status = status.clone();
+                }
+                result
+            })
+            .collect();
+        self.outgoing
+            .send_response(request_id, ThreadSearchResponse { data })
+            .await;
+    }
+
     async fn thread_loaded_list(
         &self,
         request_id: ConnectionRequestId,
@@ -4997,6 +5299,7 @@ impl CodexMessageProcessor {
             }
         }
         self.finalize_thread_teardown(thread_id).await;
+        self.thread_manager.stop_background_services(thread_id);
 
         if state_db_ctx.is_none() {
             state_db_ctx = get_state_db(&self.config).await;
@@ -5016,6 +5319,11 @@ impl CodexMessageProcessor {
                     .mark_archived(thread_id, archived_path.as_path(), Utc::now())
                     .await;
             }
+            if let Err(err) =
+                codex_core::remove_thread_tool_outputs(&self.config.codex_home, thread_id).await
+            {
+                warn!("failed to remove saved tool outputs for thread {thread_id}: {err}");
+            }
             Ok(())
         }
         .await;
@@ -5950,6 +6258,14 @@ impl CodexMessageProcessor {
                         INVALID_REQUEST_ERROR_CODE,
                         "input must not be empty".to_string(),
                     ),
+                    SteerInputError::BlockedByHook(reason) => (
+                        INVALID_REQUEST_ERROR_CODE,
+                        format!("input blocked by UserPromptSubmit hook: {reason}"),
+                    ),
+                    SteerInputError::StoppedByHook(reason) => (
+                        INVALID_REQUEST_ERROR_CODE,
+                        format!("UserPromptSubmit hook stopped the turn: {reason}"),
+                    ),
                 };
                 let error = JSONRPCErrorError {
                     code,
@@ -8048,6 +8364,14 @@ fn build_thread_from_snapshot(
     }
 }
 
+fn thread_search_match_kind(kind: ThreadSearchDocumentKind) -> ThreadSearchMatchKind {
+    match kind {
+        ThreadSearchDocumentKind::UserMessage => ThreadSearchMatchKind::UserMessage,
+        ThreadSearchDocumentKind::AgentMessage => ThreadSearchMatchKind::AgentMessage,
+        ThreadSearchDocumentKind::CommandOutput => ThreadSearchMatchKind::CommandOutput,
+    }
+}
+
 pub(crate) fn summary_to_thread(summary: ConversationSummary) -> Thread {
     let ConversationSummary {
         conversation_id,
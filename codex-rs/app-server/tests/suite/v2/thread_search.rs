@@ -0,0 +1,112 @@
+use anyhow::Result;
+use app_test_support::McpProcess;
+use app_test_support::create_fake_rollout;
+use app_test_support::to_response;
+use codex_app_server_protocol::JSONRPCResponse;
+use codex_app_server_protocol::RequestId;
+use codex_app_server_protocol::ThreadListParams;
+use codex_app_server_protocol::ThreadListResponse;
+use codex_app_server_protocol::ThreadSearchMatchKind;
+use codex_app_server_protocol::ThreadSearchParams;
+use codex_app_server_protocol::ThreadSearchResponse;
+use pretty_assertions::assert_eq;
+use std::path::Path;
+use tempfile::TempDir;
+use tokio::time::timeout;
+
+const DEFAULT_READ_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
+fn create_sqlite_config(codex_home: &Path) -> std::io::Result<()> {
+    std::fs::write(
+        codex_home.join("config.toml"),
+        r#"
+model = "mock-model"
+approval_policy = "never"
+suppress_unstable_features_warning = true
+
+[features]
+sqlite = true
+"#,
+    )
+}
+
+async fn search_threads(mcp: &mut McpProcess, query: &str) -> Result<ThreadSearchResponse> {
+    let request_id = mcp
+        .send_thread_search_request(ThreadSearchParams {
+            query: query.to_string(),
+            limit: Some(10),
+            model_providers: Some(vec!["mock_provider".to_string()]),
+            source_kinds: None,
+            archived: None,
+        })
+        .await?;
+    let resp: JSONRPCResponse = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(request_id)),
+    )
+    .await??;
+    to_response::<ThreadSearchResponse>(resp)
+}
+
+#[tokio::test]
+async fn thread_search_matches_message_content() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    create_sqlite_config(codex_home.path())?;
+
+    let matching = create_fake_rollout(
+        codex_home.path(),
+        "2025-01-02T10-00-00",
+        "2025-01-02T10:00:00Z",
+        "why is the migration lock flaky on CI",
+        Some("mock_provider"),
+        None,
+    )?;
+    let _other = create_fake_rollout(
+        codex_home.path(),
+        "2025-01-02T11-00-00",
+        "2025-01-02T11:00:00Z",
+        "rename the config loader",
+        Some("mock_provider"),
+        None,
+    )?;
+
+    // Rollouts created on disk reach SQLite through backfill or `thread/list`
+    // read-repair; mark backfill complete and warm the DB with a listing.
+    let state_db =
+        codex_state::StateRuntime::init(codex_home.path().to_path_buf(), "mock_provider".into())
+            .await?;
+    state_db.mark_backfill_complete(None).await?;
+
+    let mut mcp = McpProcess::new(codex_home.path()).await?;
+    timeout(DEFAULT_READ_TIMEOUT, mcp.initialize()).await??;
+    let request_id = mcp
+        .send_thread_list_request(ThreadListParams {
+            cursor: None,
+            limit: Some(10),
+            sort_key: None,
+            model_providers: Some(vec!["mock_provider".to_string()]),
+            source_kinds: None,
+            archived: None,
+            cwd: None,
+            search_term: None,
+        })
+        .await?;
+    let resp: JSONRPCResponse = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(request_id)),
+    )
+    .await??;
+    let ThreadListResponse { data, .. } = to_response::<ThreadListResponse>(resp)?;
+    assert_eq!(data.len(), 2);
+
+    let ThreadSearchResponse { data } = search_threads(&mut mcp, "Migration flaky").await?;
+    assert_eq!(data.len(), 1);
+    assert_eq!(data[0].thread.id, matching);
+    assert_eq!(data[0].match_kind, ThreadSearchMatchKind::UserMessage);
+    assert_eq!(data[0].snippet, "why is the migration lock flaky on CI");
+
+    let ThreadSearchResponse { data } = search_threads(&mut mcp, "   ").await?;
+    assert_eq!(data, Vec::new());
+
+    Ok(())
+}
@@ -1,5 +1,6 @@
 use std::collections::HashMap;
 use std::path::Path;
+use std::path::PathBuf;
 use std::sync::LazyLock;
 
 use tree_sitter::Parser;
@@ -18,7 +19,7 @@ use crate::MaybeApplyPatchVerified;
 use crate::parser::Hunk;
 use crate::parser::ParseError;
 use crate::parser::parse_patch;
-use crate::unified_diff_from_chunks;
+use crate::unified_diff_from_read;
 use std::str::Utf8Error;
 use tree_sitter::LanguageError;
 
@@ -130,6 +131,22 @@ pub fn maybe_parse_apply_patch(argv: &[String]) -> MaybeApplyPatch {
 /// cwd must be an absolute path so that we can resolve relative paths in the
 /// patch.
 pub fn maybe_parse_apply_patch_verified(argv: &[String], cwd: &Path) -> MaybeApplyPatchVerified {
+    maybe_parse_apply_patch_verified_with_read_path(argv, cwd, &|path| Some(path.to_path_buf()))
+}
+
+/// Like [`maybe_parse_apply_patch_verified`], but reads the files the patch
+/// deletes or updates from `read_path(path)`. This lets callers whose commands
+/// see a different view of the filesystem, such as a copy-on-write workspace,
+/// verify the patch against that view. `None` means the file does not exist.
+pub fn maybe_parse_apply_patch_verified_with_read_path(
+    argv: &[String],
+    cwd: &Path,
+    read_path: &dyn Fn(&Path) -> Option<PathBuf>,
+) -> MaybeApplyPatchVerified {
+    let read_to_string = |path: &Path| match read_path(path) {
+        Some(read_path) => std::fs::read_to_string(read_path),
+        None => Err(std::io::ErrorKind::NotFound.into()),
+    };
     // Detect a raw patch body passed directly as the command or as the body of a shell
     // script. In these cases, report an explicit error rather than applying the patch.
     if let [body] = argv
@@ -168,7 +185,7 @@ pub fn maybe_parse_apply_patch_verified(argv: &[String], cwd: &Path) -> MaybeApp
                         changes.insert(path, ApplyPatchFileChange::Add { content: contents });
                     }
                     Hunk::DeleteFile { .. } => {
-                        let content = match std::fs::read_to_string(&path) {
+                        let content = match read_to_string(&path) {
                             Ok(content) => content,
                             Err(e) => {
                                 return MaybeApplyPatchVerified::CorrectnessError(
@@ -187,7 +204,7 @@ pub fn maybe_parse_apply_patch_verified(argv: &[String], cwd: &Path) -> MaybeApp
                         let ApplyPatchFileUpdate {
                             unified_diff,
                             content: contents,
-                        } = match unified_diff_from_chunks(&path, &chunks) {
+                        } = match unified_diff_from_read(&path, read_to_string(&path), &chunks) {
                             Ok(diff) => diff,
                             Err(e) => {
                                 return MaybeApplyPatchVerified::CorrectnessError(e);
@@ -810,4 +827,41 @@ PATCH"#,
             other => panic!("expected update change, got {other:?}"),
         }
     }
+
+    #[test]
+    fn test_verified_patch_reads_through_read_path() {
+        let session_dir = tempdir().unwrap();
+        let upper_dir = tempdir().unwrap();
+        fs::write(session_dir.path().join("source.txt"), "on disk\n").unwrap();
+        fs::write(upper_dir.path().join("source.txt"), "in overlay\n").unwrap();
+
+        let argv = vec![
+            "apply_patch".to_string(),
+            wrap_patch("*** Update File: source.txt\n@@\n-in overlay\n+updated"),
+        ];
+        let read_path = |path: &Path| {
+            path.strip_prefix(session_dir.path())
+                .ok()
+                .map(|relative| upper_dir.path().join(relative))
+        };
+
+        let result =
+            maybe_parse_apply_patch_verified_with_read_path(&argv, session_dir.path(), &read_path);
+
+        assert_eq!(
+            result,
+            MaybeApplyPatchVerified::Body(ApplyPatchAction {
+                changes: HashMap::from([(
+                    session_dir.path().join("source.txt"),
+                    ApplyPatchFileChange::Update {
+                        unified_diff: "@@ -1 +1 @@\n-in overlay\n+updated\n".to_string(),
+                        move_path: None,
+                        new_content: "updated\n".to_string(),
+                    },
+                )]),
+                patch: argv[1].clone(),
+                cwd: session_dir.path().to_path_buf(),
+            })
+        );
+    }
 }
@@ -18,6 +18,7 @@ use similar::TextDiff;
 use thiserror::Error;
 
 pub use invocation::maybe_parse_apply_patch_verified;
+pub use invocation::maybe_parse_apply_patch_verified_with_read_path;
 pub use standalone_executable::main;
 
 use crate::invocation::ExtractHeredocError;
@@ -349,7 +350,17 @@ fn derive_new_contents_from_chunks(
     path: &Path,
     chunks: &[UpdateFileChunk],
 ) -> std::result::Result<AppliedPatch, ApplyPatchError> {
-    let original_contents = match std::fs::read_to_string(path) {
+    derive_new_contents_from_read(path, std::fs::read_to_string(path), chunks)
+}
+
+/// Like [`derive_new_contents_from_chunks`], but with the original contents
+/// of `path` already read by the caller.
+fn derive_new_contents_from_read(
+    path: &Path,
+    read: std::io::Result<String>,
+    chunks: &[UpdateFileChunk],
+) -> std::result::Result<AppliedPatch, ApplyPatchError> {
+    let original_contents = match read {
         Ok(contents) => contents,
         Err(err) => {
             return Err(ApplyPatchError::IoError(IoError {
@@ -520,10 +531,26 @@ pub fn unified_diff_from_chunks_with_context(
     chunks: &[UpdateFileChunk],
     context: usize,
 ) -> std::result::Result<ApplyPatchFileUpdate, ApplyPatchError> {
-    let AppliedPatch {
+    applied_patch_to_update(derive_new_contents_from_chunks(path, chunks)?, context)
+}
+
+/// Like [`unified_diff_from_chunks`], but with the original contents of
+/// `path` already read by the caller.
+pub(crate) fn unified_diff_from_read(
+    path: &Path,
+    read: std::io::Result<String>,
+    chunks: &[UpdateFileChunk],
+) -> std::result::Result<ApplyPatchFileUpdate, ApplyPatchError> {
+    applied_patch_to_update(derive_new_contents_from_read(path, read, chunks)?, 1)
+}
+
+fn applied_patch_to_update(
+    AppliedPatch {
         original_contents,
         new_contents,
-    } = derive_new_contents_from_chunks(path, chunks)?;
+    }: AppliedPatch,
+    context: usize,
+) -> std::result::Result<ApplyPatchFileUpdate, ApplyPatchError> {
     let text_diff = TextDiff::from_lines(&original_contents, &new_contents);
     let unified_diff = text_diff.unified_diff().context_radius(context).to_string();
     Ok(ApplyPatchFileUpdate {
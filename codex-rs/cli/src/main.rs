@@ -39,10 +39,12 @@ mod app_cmd;
 #[cfg(target_os = "macos")]
 mod desktop_app;
 mod mcp_cmd;
+mod secrets_cmd;
 #[cfg(not(windows))]
 mod wsl_paths;
 
 use crate::mcp_cmd::McpCli;
+use crate::secrets_cmd::SecretsCli;
 
 use codex_core::config::Config;
 use codex_core::config::ConfigOverrides;
@@ -102,6 +104,9 @@ enum Subcommand {
     /// Start Codex as an MCP server (stdio).
     McpServer,
 
+    /// Manage secrets exposed to shell commands as environment variables.
+    Secrets(SecretsCli),
+
     /// [experimental] Run the app server or related tooling.
     AppServer(AppServerCommand),
 
@@ -204,6 +209,11 @@ struct ResumeCommand {
     #[arg(long = "all", default_value_t = false)]
     all: bool,
 
+    /// Open the picker with a full-text search of session contents (user
+    /// messages, agent messages and command output).
+    #[arg(long = "search", value_name = "QUERY", conflicts_with_all = ["session_id", "last"])]
+    search: Option<String>,
+
     #[clap(flatten)]
     config_overrides: TuiCli,
 }
@@ -602,6 +612,14 @@ async fn cli_main(arg0_paths: Arg0DispatchPaths) -> anyhow::Result<()> {
             prepend_config_flags(&mut mcp_cli.config_overrides, root_config_overrides.clone());
             mcp_cli.run().await?;
         }
+        Some(Subcommand::Secrets(mut secrets_cli)) => {
+            // Propagate any root-level config overrides (e.g. `-c key=value`).
+            prepend_config_flags(
+                &mut secrets_cli.config_overrides,
+                root_config_overrides.clone(),
+            );
+            secrets_cli.run().await?;
+        }
         Some(Subcommand::AppServer(app_server_cli)) => match app_server_cli.subcommand {
             None => {
                 let transport = app_server_cli.listen;
@@ -640,6 +658,7 @@ async fn cli_main(arg0_paths: Arg0DispatchPaths) -> anyhow::Result<()> {
             session_id,
             last,
             all,
+            search,
             config_overrides,
         })) => {
             interactive = finalize_resume_interactive(
@@ -648,6 +667,7 @@ async fn cli_main(arg0_paths: Arg0DispatchPaths) -> anyhow::Result<()> {
                 session_id,
                 last,
                 all,
+                search,
                 config_overrides,
             );
             let exit_info = run_interactive_tui(interactive, arg0_paths.clone()).await?;
@@ -995,6 +1015,7 @@ fn finalize_resume_interactive(
     session_id: Option<String>,
     last: bool,
     show_all: bool,
+    search: Option<String>,
     resume_cli: TuiCli,
 ) -> TuiCli {
     // Start with the parsed interactive CLI so resume shares the same
@@ -1004,6 +1025,7 @@ fn finalize_resume_interactive(
     interactive.resume_last = last;
     interactive.resume_session_id = resume_session_id;
     interactive.resume_show_all = show_all;
+    interactive.resume_search = search;
 
     // Merge resume-scoped flags and overrides with highest precedence.
     merge_interactive_cli_flags(&mut interactive, resume_cli);
@@ -1115,6 +1137,7 @@ mod tests {
             session_id,
             last,
             all,
+            search,
             config_overrides: resume_cli,
         }) = subcommand.expect("resume present")
         else {
@@ -1127,6 +1150,7 @@ mod tests {
             session_id,
             last,
             all,
+            search,
             resume_cli,
         )
     }
@@ -1313,6 +1337,24 @@ mod tests {
         assert!(!interactive.resume_show_all);
     }
 
+    #[test]
+    fn resume_search_flag_opens_picker_with_query() {
+        let interactive =
+            finalize_resume_from_args(["codex", "resume", "--search", "flaky migration"].as_ref());
+        assert!(interactive.resume_picker);
+        assert!(!interactive.resume_last);
+        assert_eq!(
+            interactive.resume_search.as_deref(),
+            Some("flaky migration")
+        );
+    }
+
+    #[test]
+    fn resume_search_conflicts_with_last() {
+        let result = MultitoolCli::try_parse_from(["codex", "resume", "--last", "--search", "x"]);
+        assert!(result.is_err());
+    }
+
     #[test]
     fn resume_all_flag_sets_show_all() {
         let interactive = finalize_resume_from_args(["codex", "resume", "--all"].as_ref());
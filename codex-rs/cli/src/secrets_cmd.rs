@@ -0,0 +1,228 @@
+use std::io::IsTerminal;
+use std::io::Read;
+use std::io::Write;
+
+use anyhow::Context;
+use anyhow::Result;
+use anyhow::bail;
+use codex_core::config::Config;
+use codex_secrets::SecretName;
+use codex_secrets::SecretScope;
+use codex_secrets::SecretsManager;
+use codex_secrets::environment_id_from_cwd;
+use codex_utils_cli::CliConfigOverrides;
+
+/// Subcommands:
+/// - `set`    — store a secret read from stdin
+/// - `list`   — list stored secret names (values are never printed)
+/// - `delete` — remove a stored secret
+///
+/// Secrets are scoped to the current environment (the git repo name, or a hash
+/// of the working directory) unless `--global` is passed. Names listed under
+/// `[secrets].expose` in config.toml are exported to shell tool runs.
+#[derive(Debug, clap::Parser)]
+pub struct SecretsCli {
+    #[clap(flatten)]
+    pub config_overrides: CliConfigOverrides,
+
+    #[command(subcommand)]
+    pub subcommand: SecretsSubcommand,
+}
+
+#[derive(Debug, clap::Subcommand)]
+pub enum SecretsSubcommand {
+    /// Store a secret. The value is read from stdin so it never appears in
+    /// shell history or the process list.
+    Set(SetArgs),
+    /// List stored secret names.
+    List(ListArgs),
+    /// Delete a stored secret.
+    Delete(DeleteArgs),
+}
+
+#[derive(Debug, clap::Args)]
+pub struct ScopeArgs {
+    /// Use the global scope instead of the current environment.
+    #[arg(long, default_value_t = false)]
+    pub global: bool,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct SetArgs {
+    /// Secret name (A-Z, 0-9 and `_`), also used as the environment variable name.
+    pub name: String,
+
+    #[command(flatten)]
+    pub scope: ScopeArgs,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct ListArgs {
+    /// Only list secrets in the global scope.
+    #[arg(long, default_value_t = false)]
+    pub global: bool,
+
+    /// Output the secret names as JSON.
+    #[arg(long)]
+    pub json: bool,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct DeleteArgs {
+    /// Secret name to delete.
+    pub name: String,
+
+    #[command(flatten)]
+    pub scope: ScopeArgs,
+}
+
+impl SecretsCli {
+    pub async fn run(self) -> Result<()> {
+        let SecretsCli {
+            config_overrides,
+            subcommand,
+        } = self;
+        let overrides = config_overrides
+            .parse_overrides()
+            .map_err(anyhow::Error::msg)?;
+        let config = Config::load_with_cli_overrides(overrides)
+            .await
+            .context("failed to load configuration")?;
+        let manager = SecretsManager::new(config.codex_home.clone(), config.secrets.backend);
+        let environment_scope = SecretScope::environment(environment_id_from_cwd(&config.cwd))?;
+
+        match subcommand {
+            SecretsSubcommand::Set(args) => {
+                let name = SecretName::new(&args.name)?;
+                let scope = resolve_scope(&args.scope, &environment_scope);
+                let value = read_secret_value(&name)?;
+                manager.set(&scope, &name, &value)?;
+                println!("Stored secret {name} ({}).", describe_scope(&scope));
+                if !config
+                    .secrets
+                    .expose
+                    .iter()
+                    .any(|exposed| exposed == name.as_str())
+                {
+                    println!(
+                        "Add \"{name}\" to `[secrets].expose` in config.toml to make it available to shell commands."
+                    );
+                }
+            }
+            SecretsSubcommand::List(args) => {
+                let scope_filter = args.global.then_some(SecretScope::Global);
+                let mut entries = manager.list(scope_filter.as_ref())?;
+                entries.sort_by(|a, b| {
+                    describe_scope(&a.scope)
+                        .cmp(&describe_scope(&b.scope))
+                        .then_with(|| a.name.cmp(&b.name))
+                });
+                if args.json {
+                    let json_entries: Vec<_> = entries
+                        .iter()
+                        .map(|entry| {
+                            serde_json::json!({
+                                "name": entry.name.as_str(),
+                                "scope": describe_scope(&entry.scope),
+                                "exposed": config
+                                    .secrets
+                                    .expose
+                                    .iter()
+                                    .any(|exposed| exposed == entry.name.as_str()),
+                            })
+                        })
+                        .collect();
+                    let output = serde_json::to_string_pretty(&json_entries)?;
+                    println!("{output}");
+                    return Ok(());
+                }
+                if entries.is_empty() {
+                    println!("No secrets stored yet. Try `codex secrets set NAME`.");
+                    return Ok(());
+                }
+                for entry in entries {
+                    let current = if entry.scope == environment_scope {
+                        " (current)"
+                    } else {
+                        ""
+                    };
+                    println!("{}\t{}{current}", entry.name, describe_scope(&entry.scope));
+                }
+            }
+            SecretsSubcommand::Delete(args) => {
+                let name = SecretName::new(&args.name)?;
+                let scope = resolve_scope(&args.scope, &environment_scope);
+                if manager.delete(&scope, &name)? {
+                    println!("Deleted secret {name} ({}).", describe_scope(&scope));
+                } else {
+                    bail!("No secret named {name} in {}.", describe_scope(&scope));
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn resolve_scope(args: &ScopeArgs, environment_scope: &SecretScope) -> SecretScope {
+    if args.global {
+        SecretScope::Global
+    } else {
+        environment_scope.clone()
+    }
+}
+
+fn describe_scope(scope: &SecretScope) -> String {
+    match scope {
+        SecretScope::Global => "global".to_string(),
+        SecretScope::Environment(environment_id) => format!("env:{environment_id}"),
+    }
+}
+
+fn read_secret_value(name: &SecretName) -> Result<String> {
+    let mut stdin = std::io::stdin();
+    let mut buffer = String::new();
+    if stdin.is_terminal() {
+        eprint!("Value for {name}: ");
+        std::io::stderr().flush()?;
+        stdin
+            .read_line(&mut buffer)
+            .context("failed to read secret value")?;
+    } else {
+        stdin
+            .read_to_string(&mut buffer)
+            .context("failed to read secret value from stdin")?;
+    }
+    let value = buffer.trim_end_matches(['\r', '\n']).to_string();
+    if value.is_empty() {
+        bail!("No value provided for {name}.");
+    }
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn set_parses_name_and_scope() {
+        let cli = SecretsCli::try_parse_from(["secrets", "set", "GITHUB_TOKEN", "--global"])
+            .expect("parse should succeed");
+        let SecretsSubcommand::Set(args) = cli.subcommand else {
+            panic!("expected set subcommand");
+        };
+        assert_eq!(args.name, "GITHUB_TOKEN");
+        assert!(args.scope.global);
+    }
+
+    #[test]
+    fn describe_scope_labels_environment() {
+        assert_eq!(describe_scope(&SecretScope::Global), "global");
+        assert_eq!(
+            describe_scope(&SecretScope::Environment("codex".to_string())),
+            "env:codex"
+        );
+    }
+}
@@ -0,0 +1,115 @@
+use crate::auth::AuthProvider;
+use crate::common::ResponseStream;
+use crate::common::ResponsesApiRequest;
+use crate::endpoint::responses::ResponsesOptions;
+use crate::endpoint::session::EndpointSession;
+use crate::error::ApiError;
+use crate::provider::Provider;
+use crate::requests::anthropic::build_anthropic_messages_body;
+use crate::requests::freeform::custom_tool_names;
+use crate::requests::headers::build_conversation_headers;
+use crate::requests::headers::insert_header;
+use crate::sse::anthropic::spawn_anthropic_messages_stream;
+use crate::telemetry::SseTelemetry;
+use codex_client::HttpTransport;
+use codex_client::RequestTelemetry;
+use http::HeaderValue;
+use http::Method;
+use std::sync::Arc;
+
+const ANTHROPIC_VERSION_HEADER: &str = "anthropic-version";
+const ANTHROPIC_VERSION: &str = "2023-06-01";
+const API_KEY_HEADER: &str = "x-api-key";
+
+/// Client for the Anthropic Messages streaming API (`POST /messages`).
+///
+/// Like [`crate::ChatCompletionsClient`], it accepts a [`ResponsesApiRequest`]
+/// and yields [`crate::ResponseEvent`]s. The provider's bearer token is sent
+/// as `x-api-key`, and `anthropic-version` defaults to [`ANTHROPIC_VERSION`]
+/// unless the provider headers already set it.
+pub struct AnthropicMessagesClient<T: HttpTransport, A: AuthProvider> {
+    session: EndpointSession<T, A>,
+    sse_telemetry: Option<Arc<dyn SseTelemetry>>,
+}
+
+impl<T: HttpTransport, A: AuthProvider> AnthropicMessagesClient<T, A> {
+    pub fn new(transport: T, provider: Provider, auth: A) -> Self {
+        Self {
+            session: EndpointSession::new(transport, provider, auth),
+            sse_telemetry: None,
+        }
+    }
+
+    pub fn with_telemetry(
+        self,
+        request: Option<Arc<dyn RequestTelemetry>>,
+        sse: Option<Arc<dyn SseTelemetry>>,
+    ) -> Self {
+        Self {
+            session: self.session.with_request_telemetry(request),
+            sse_telemetry: sse,
+        }
+    }
+
+    fn path() -> &'static str {
+        "messages"
+    }
+
+    /// Streams a message. Compression and `turn_state` in `options` are
+    /// ignored because Messages endpoints support neither.
+    pub async fn stream_request(
+        &self,
+        request: ResponsesApiRequest,
+        options: ResponsesOptions,
+    ) -> Result<ResponseStream, ApiError> {
+        let ResponsesOptions {
+            conversation_id,
+            extra_headers,
+            ..
+        } = options;
+
+        let body = build_anthropic_messages_body(&request);
+        let custom_tools = custom_tool_names(&request.tools);
+
+        let mut headers = extra_headers;
+        if let Some(ref conv_id) = conversation_id {
+            insert_header(&mut headers, "x-client-request-id", conv_id);
+        }
+        headers.extend(build_conversation_headers(conversation_id));
+
+        let stream_response = self
+            .session
+            .stream_with(Method::POST, Self::path(), headers, Some(body), |req| {
+                req.headers.insert(
+                    http::header::ACCEPT,
+                    HeaderValue::from_static("text/event-stream"),
+                );
+                if !req.headers.contains_key(ANTHROPIC_VERSION_HEADER) {
+                    req.headers.insert(
+                        ANTHROPIC_VERSION_HEADER,
+                        HeaderValue::from_static(ANTHROPIC_VERSION),
+                    );
+                }
+                let api_key = req
+                    .headers
+                    .get(http::header::AUTHORIZATION)
+                    .and_then(|value| value.to_str().ok())
+                    .and_then(|value| value.strip_prefix("Bearer "))
+                    .and_then(|token| HeaderValue::from_str(token).ok());
+                if let Some(api_key) = api_key
+                    && !req.headers.contains_key(API_KEY_HEADER)
+                {
+                    req.headers.remove(http::header::AUTHORIZATION);
+                    req.headers.insert(API_KEY_HEADER, api_key);
+                }
+            })
+            .await?;
+
+        Ok(spawn_anthropic_messages_stream(
+            stream_response,
+            custom_tools,
+            self.session.provider().stream_idle_timeout,
+            self.sse_telemetry.clone(),
+        ))
+    }
+}
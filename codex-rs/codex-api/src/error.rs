@@ -29,6 +29,8 @@ pub enum ApiError {
     InvalidRequest { message: String },
     #[error("server overloaded")]
     ServerOverloaded,
+    #[error("response stopped at max_output_tokens")]
+    MaxOutputTokens,
 }
 
 impl From<RateLimitError> for ApiError {
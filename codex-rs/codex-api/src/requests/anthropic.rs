@@ -0,0 +1,503 @@
+//! Translation of [`ResponsesApiRequest`] into an Anthropic Messages request body.
+//!
+//! Messages has no system role inside the conversation and requires tool
+//! calls and their results to live in content blocks, so the translation:
+//!
+//! - sends `instructions` as the top-level `system` prompt and folds
+//!   `developer`/`system` history messages into user turns;
+//! - turns function calls into `tool_use` blocks on the assistant turn and
+//!   call outputs into `tool_result` blocks on the following user turn;
+//! - replays reasoning items as signed `thinking` (or `redacted_thinking`)
+//!   blocks, which Anthropic requires when thinking is enabled;
+//! - merges consecutive messages from the same role into one turn;
+//! - marks the system prompt, the tool list and the latest user turn as
+//!   prompt-caching breakpoints.
+
+use crate::common::ResponsesApiRequest;
+use crate::requests::freeform::custom_tool_arguments;
+use crate::requests::freeform::function_tool_from_custom_tool;
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::FunctionCallOutputBody;
+use codex_protocol::models::FunctionCallOutputContentItem;
+use codex_protocol::models::ReasoningItemContent;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::openai_models::ReasoningEffort;
+use serde_json::Map;
+use serde_json::Value;
+use serde_json::json;
+use tracing::trace;
+
+/// Output token ceiling sent when the request does not set one. Messages
+/// requires `max_tokens`, and it must exceed the thinking budget.
+const DEFAULT_MAX_TOKENS: u64 = 32_000;
+
+/// Smallest thinking budget Messages accepts.
+const MIN_THINKING_BUDGET: u64 = 1_024;
+
+/// Builds the JSON body for `POST /messages`.
+pub fn build_anthropic_messages_body(request: &ResponsesApiRequest) -> Value {
+    let mut body = Map::new();
+    body.insert("model".to_string(), Value::String(request.model.clone()));
+    let max_tokens = request.max_output_tokens.unwrap_or(DEFAULT_MAX_TOKENS);
+    body.insert("max_tokens".to_string(), json!(max_tokens));
+    body.insert("stream".to_string(), Value::Bool(true));
+    if !request.instructions.is_empty() {
+        body.insert(
+            "system".to_string(),
+            json!([{
+                "type": "text",
+                "text": request.instructions,
+                "cache_control": ephemeral(),
+            }]),
+        );
+    }
+
+    let mut tools = request
+        .tools
+        .iter()
+        .filter_map(anthropic_tool_from_responses_tool)
+        .collect::<Vec<_>>();
+    if let Some(Value::Object(last)) = tools.last_mut() {
+        last.insert("cache_control".to_string(), ephemeral());
+    }
+    if !tools.is_empty() {
+        body.insert("tools".to_string(), Value::Array(tools));
+        body.insert(
+            "tool_choice".to_string(),
+            json!({
+                "type": "auto",
+                "disable_parallel_tool_use": !request.parallel_tool_calls,
+            }),
+        );
+    }
+
+    if let Some(budget_tokens) = request
+        .reasoning
+        .as_ref()
+        .and_then(|reasoning| reasoning.effort)
+        .and_then(thinking_budget)
+        .map(|budget| budget.min(max_tokens.saturating_sub(1)))
+        .filter(|budget| *budget >= MIN_THINKING_BUDGET)
+    {
+        body.insert(
+            "thinking".to_string(),
+            json!({ "type": "enabled", "budget_tokens": budget_tokens }),
+        );
+    }
+
+    body.insert(
+        "messages".to_string(),
+        Value::Array(build_messages(&request.input)),
+    );
+    Value::Object(body)
+}
+
+fn ephemeral() -> Value {
+    json!({ "type": "ephemeral" })
+}
+
+/// Maps a reasoning effort onto a thinking budget. `None` disables thinking.
+fn thinking_budget(effort: ReasoningEffort) -> Option<u64> {
+    match effort {
+        ReasoningEffort::None => None,
+        ReasoningEffort::Minimal => Some(MIN_THINKING_BUDGET),
+        ReasoningEffort::Low => Some(4_096),
+        ReasoningEffort::Medium => Some(10_000),
+        ReasoningEffort::High => Some(20_000),
+        ReasoningEffort::XHigh => Some(30_000),
+    }
+}
+
+fn build_messages(input: &[ResponseItem]) -> Vec<Value> {
+    let mut turns: Vec<(&'static str, Vec<Value>)> = Vec::new();
+    let mut push = |role: &'static str, block: Value| match turns.last_mut() {
+        Some((last_role, blocks)) if *last_role == role => blocks.push(block),
+        _ => turns.push((role, vec![block])),
+    };
+
+    for item in input {
+        match item {
+            ResponseItem::Message { role, content, .. } => {
+                let role = if role == "assistant" {
+                    "assistant"
+                } else {
+                    "user"
+                };
+                for item in content {
+                    if let Some(block) = content_block(item) {
+                        push(role, block);
+                    }
+                }
+            }
+            ResponseItem::Reasoning {
+                content,
+                encrypted_content,
+                ..
+            } => {
+                if let Some(block) = thinking_block(content.as_deref(), encrypted_content) {
+                    push("assistant", block);
+                }
+            }
+            ResponseItem::FunctionCall {
+                name,
+                arguments,
+                call_id,
+                ..
+            } => push(
+                "assistant",
+                json!({
+                    "type": "tool_use",
+                    "id": call_id,
+                    "name": name,
+                    "input": tool_use_input(arguments),
+                }),
+            ),
+            ResponseItem::CustomToolCall {
+                call_id,
+                name,
+                input,
+                ..
+            } => push(
+                "assistant",
+                json!({
+                    "type": "tool_use",
+                    "id": call_id,
+                    "name": name,
+                    "input": custom_tool_arguments(input),
+                }),
+            ),
+            ResponseItem::FunctionCallOutput { call_id, output }
+            | ResponseItem::CustomToolCallOutput { call_id, output } => {
+                let mut block = Map::new();
+                block.insert("type".to_string(), json!("tool_result"));
+                block.insert("tool_use_id".to_string(), json!(call_id));
+                block.insert("content".to_string(), tool_result_content(&output.body));
+                if output.success == Some(false) {
+                    block.insert("is_error".to_string(), Value::Bool(true));
+                }
+                push("user", Value::Object(block));
+            }
+            other => {
+                trace!("dropping item without an anthropic messages equivalent: {other:?}");
+            }
+        }
+    }
+
+    // Caching up to the latest user turn lets the next request reuse
+    // everything that came before it.
+    if let Some((_, blocks)) = turns.iter_mut().rev().find(|(role, _)| *role == "user")
+        && let Some(Value::Object(block)) = blocks.last_mut()
+    {
+        block.insert("cache_control".to_string(), ephemeral());
+    }
+
+    turns
+        .into_iter()
+        .map(|(role, content)| json!({ "role": role, "content": content }))
+        .collect()
+}
+
+fn content_block(item: &ContentItem) -> Option<Value> {
+    match item {
+        ContentItem::InputText { text } | ContentItem::OutputText { text } => {
+            if text.is_empty() {
+                // Messages rejects empty text blocks.
+                None
+            } else {
+                Some(json!({ "type": "text", "text": text }))
+            }
+        }
+        ContentItem::InputImage { image_url } => Some(image_block(image_url)),
+    }
+}
+
+fn image_block(image_url: &str) -> Value {
+    if let Some((media_type, data)) = image_url
+        .strip_prefix("data:")
+        .and_then(|rest| rest.split_once(";base64,"))
+    {
+        return json!({
+            "type": "image",
+            "source": { "type": "base64", "media_type": media_type, "data": data },
+        });
+    }
+    json!({
+        "type": "image",
+        "source": { "type": "url", "url": image_url },
+    })
+}
+
+fn thinking_block(
+    content: Option<&[ReasoningItemContent]>,
+    encrypted_content: &Option<String>,
+) -> Option<Value> {
+    // Thinking blocks can only be replayed with the signature Anthropic
+    // issued for them, which is kept in `encrypted_content`.
+    let signature = encrypted_content.as_ref()?;
+    let thinking = content
+        .unwrap_or_default()
+        .iter()
+        .map(|item| match item {
+            ReasoningItemContent::ReasoningText { text } | ReasoningItemContent::Text { text } => {
+                text.as_str()
+            }
+        })
+        .collect::<String>();
+    if thinking.is_empty() {
+        Some(json!({ "type": "redacted_thinking", "data": signature }))
+    } else {
+        Some(json!({ "type": "thinking", "thinking": thinking, "signature": signature }))
+    }
+}
+
+fn tool_use_input(arguments: &str) -> Value {
+    match serde_json::from_str::<Value>(arguments) {
+        Ok(value @ Value::Object(_)) => value,
+        _ => json!({}),
+    }
+}
+
+fn tool_result_content(body: &FunctionCallOutputBody) -> Value {
+    match body {
+        FunctionCallOutputBody::Text(text) => Value::String(text.clone()),
+        FunctionCallOutputBody::ContentItems(items) => Value::Array(
+            items
+                .iter()
+                .map(|item| match item {
+                    FunctionCallOutputContentItem::InputText { text } => {
+                        json!({ "type": "text", "text": text })
+                    }
+                    FunctionCallOutputContentItem::InputImage { image_url, .. } => {
+                        image_block(image_url)
+                    }
+                })
+                .collect(),
+        ),
+    }
+}
+
+/// Converts a Responses `function` tool into a Messages tool definition.
+/// Freeform tools are sent as tools taking a single `input` string. Hosted
+/// tools have no Messages equivalent and are skipped.
+fn anthropic_tool_from_responses_tool(tool: &Value) -> Option<Value> {
+    let function_tool;
+    let tool = match tool.get("type").and_then(Value::as_str) {
+        Some("function") => tool,
+        Some("custom") => {
+            function_tool = function_tool_from_custom_tool(tool)?;
+            &function_tool
+        }
+        _ => {
+            trace!("dropping tool without an anthropic messages equivalent: {tool}");
+            return None;
+        }
+    };
+
+    let mut definition = Map::new();
+    definition.insert("name".to_string(), tool.get("name")?.clone());
+    if let Some(description) = tool.get("description") {
+        definition.insert("description".to_string(), description.clone());
+    }
+    definition.insert(
+        "input_schema".to_string(),
+        tool.get("parameters")
+            .cloned()
+            .unwrap_or_else(|| json!({ "type": "object", "properties": {} })),
+    );
+    Some(Value::Object(definition))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::Reasoning;
+    use codex_protocol::models::FunctionCallOutputPayload;
+    use pretty_assertions::assert_eq;
+
+    fn user_text(text: &str) -> ResponseItem {
+        ResponseItem::Message {
+            id: None,
+            role: "user".to_string(),
+            content: vec![ContentItem::InputText {
+                text: text.to_string(),
+            }],
+            end_turn: None,
+            phase: None,
+        }
+    }
+
+    #[test]
+    fn translates_tool_round_trip_with_thinking_and_cache_breakpoints() {
+        let request = ResponsesApiRequest {
+            model: "claude-sonnet-4-5".to_string(),
+            instructions: "You are a coding agent.".to_string(),
+            previous_response_id: None,
+            input: vec![
+                ResponseItem::Message {
+                    id: None,
+                    role: "developer".to_string(),
+                    content: vec![ContentItem::InputText {
+                        text: "sandbox: read-only".to_string(),
+                    }],
+                    end_turn: None,
+                    phase: None,
+                },
+                user_text("list files"),
+                ResponseItem::Reasoning {
+                    id: String::new(),
+                    summary: Vec::new(),
+                    content: Some(vec![ReasoningItemContent::ReasoningText {
+                        text: "use ls".to_string(),
+                    }]),
+                    encrypted_content: Some("sig-1".to_string()),
+                },
+                ResponseItem::FunctionCall {
+                    id: None,
+                    name: "shell".to_string(),
+                    namespace: None,
+                    arguments: r#"{"command":["ls"]}"#.to_string(),
+                    call_id: "toolu_1".to_string(),
+                },
+                ResponseItem::FunctionCallOutput {
+                    call_id: "toolu_1".to_string(),
+                    output: FunctionCallOutputPayload {
+                        body: FunctionCallOutputBody::Text("permission denied".to_string()),
+                        success: Some(false),
+                    },
+                },
+            ],
+            tools: vec![
+                json!({
+                    "type": "function",
+                    "name": "shell",
+                    "description": "Runs a command",
+                    "strict": false,
+                    "parameters": {"type": "object", "properties": {}},
+                }),
+                json!({"type": "web_search"}),
+            ],
+            tool_choice: "auto".to_string(),
+            parallel_tool_calls: false,
+            reasoning: Some(Reasoning {
+                effort: Some(ReasoningEffort::Low),
+                summary: None,
+            }),
+            store: false,
+            stream: true,
+            include: Vec::new(),
+            service_tier: None,
+            prompt_cache_key: None,
+            text: None,
+            max_output_tokens: None,
+        };
+
+        let body = build_anthropic_messages_body(&request);
+
+        assert_eq!(
+            body,
+            json!({
+                "model": "claude-sonnet-4-5",
+                "max_tokens": 32000,
+                "stream": true,
+                "system": [{
+                    "type": "text",
+                    "text": "You are a coding agent.",
+                    "cache_control": {"type": "ephemeral"},
+                }],
+                "tools": [{
+                    "name": "shell",
+                    "description": "Runs a command",
+                    "input_schema": {"type": "object", "properties": {}},
+                    "cache_control": {"type": "ephemeral"},
+                }],
+                "tool_choice": {"type": "auto", "disable_parallel_tool_use": true},
+                "thinking": {"type": "enabled", "budget_tokens": 4096},
+                "messages": [
+                    {"role": "user", "content": [
+                        {"type": "text", "text": "sandbox: read-only"},
+                        {"type": "text", "text": "list files"},
+                    ]},
+                    {"role": "assistant", "content": [
+                        {"type": "thinking", "thinking": "use ls", "signature": "sig-1"},
+                        {"type": "tool_use", "id": "toolu_1", "name": "shell", "input": {"command": ["ls"]}},
+                    ]},
+                    {"role": "user", "content": [{
+                        "type": "tool_result",
+                        "tool_use_id": "toolu_1",
+                        "content": "permission denied",
+                        "is_error": true,
+                        "cache_control": {"type": "ephemeral"},
+                    }]},
+                ],
+            })
+        );
+    }
+
+    #[test]
+    fn max_output_tokens_caps_max_tokens_and_thinking_budget() {
+        let request = |max_output_tokens| ResponsesApiRequest {
+            model: "claude-sonnet-4-5".to_string(),
+            instructions: String::new(),
+            previous_response_id: None,
+            input: vec![user_text("hi")],
+            tools: Vec::new(),
+            tool_choice: "auto".to_string(),
+            parallel_tool_calls: true,
+            reasoning: Some(Reasoning {
+                effort: Some(ReasoningEffort::High),
+                summary: None,
+            }),
+            store: false,
+            stream: true,
+            include: Vec::new(),
+            service_tier: None,
+            prompt_cache_key: None,
+            text: None,
+            max_output_tokens: Some(max_output_tokens),
+        };
+
+        let body = build_anthropic_messages_body(&request(8_192));
+        assert_eq!(body["max_tokens"], json!(8_192));
+        assert_eq!(
+            body["thinking"],
+            json!({"type": "enabled", "budget_tokens": 8_191})
+        );
+
+        let body = build_anthropic_messages_body(&request(1_000));
+        assert_eq!(body["max_tokens"], json!(1_000));
+        assert_eq!(body.get("thinking"), None);
+    }
+
+    #[test]
+    fn data_url_images_become_base64_sources() {
+        assert_eq!(
+            image_block("data:image/png;base64,AAAA"),
+            json!({
+                "type": "image",
+                "source": {"type": "base64", "media_type": "image/png", "data": "AAAA"},
+            })
+        );
+    }
+
+    #[test]
+    fn unsigned_reasoning_is_not_replayed() {
+        let messages = build_messages(&[
+            user_text("hi"),
+            ResponseItem::Reasoning {
+                id: String::new(),
+                summary: Vec::new(),
+                content: Some(vec![ReasoningItemContent::ReasoningText {
+                    text: "from another provider".to_string(),
+                }]),
+                encrypted_content: None,
+            },
+        ]);
+
+        assert_eq!(
+            messages,
+            vec![json!({"role": "user", "content": [
+                {"type": "text", "text": "hi", "cache_control": {"type": "ephemeral"}},
+            ]})]
+        );
+    }
+}
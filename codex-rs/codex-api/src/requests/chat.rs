@@ -0,0 +1,509 @@
+//! Translation of [`ResponsesApiRequest`] into a Chat Completions request body.
+//!
+//! Codex keeps its history as Responses API items. Chat Completions servers
+//! only understand role-tagged messages, so the translation folds function
+//! calls into the preceding assistant message, turns call outputs into `tool`
+//! messages, and drops items that have no Chat Completions equivalent (web
+//! search calls, ghost snapshots, compaction markers, ...).
+
+use crate::common::ResponsesApiRequest;
+use crate::requests::freeform::custom_tool_arguments;
+use crate::requests::freeform::function_tool_from_custom_tool;
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::FunctionCallOutputBody;
+use codex_protocol::models::FunctionCallOutputContentItem;
+use codex_protocol::models::ReasoningItemContent;
+use codex_protocol::models::ReasoningItemReasoningSummary;
+use codex_protocol::models::ResponseItem;
+use serde_json::Map;
+use serde_json::Value;
+use serde_json::json;
+use tracing::trace;
+
+/// Builds the JSON body for `POST /chat/completions`.
+pub fn build_chat_completions_body(request: &ResponsesApiRequest) -> Value {
+    let mut body = Map::new();
+    body.insert("model".to_string(), Value::String(request.model.clone()));
+    body.insert(
+        "messages".to_string(),
+        Value::Array(build_messages(&request.instructions, &request.input)),
+    );
+    body.insert("stream".to_string(), Value::Bool(true));
+    body.insert(
+        "stream_options".to_string(),
+        json!({ "include_usage": true }),
+    );
+
+    let tools = request
+        .tools
+        .iter()
+        .filter_map(chat_tool_from_responses_tool)
+        .collect::<Vec<_>>();
+    if !tools.is_empty() {
+        body.insert("tools".to_string(), Value::Array(tools));
+        body.insert(
+            "tool_choice".to_string(),
+            Value::String(request.tool_choice.clone()),
+        );
+        body.insert(
+            "parallel_tool_calls".to_string(),
+            Value::Bool(request.parallel_tool_calls),
+        );
+    }
+
+    if let Some(max_output_tokens) = request.max_output_tokens {
+        body.insert("max_tokens".to_string(), json!(max_output_tokens));
+    }
+    if let Some(effort) = request
+        .reasoning
+        .as_ref()
+        .and_then(|reasoning| reasoning.effort)
+    {
+        body.insert("reasoning_effort".to_string(), json!(effort));
+    }
+    if let Some(format) = request.text.as_ref().and_then(|text| text.format.as_ref()) {
+        body.insert(
+            "response_format".to_string(),
+            json!({
+                "type": "json_schema",
+                "json_schema": {
+                    "name": format.name,
+                    "strict": format.strict,
+                    "schema": format.schema,
+                },
+            }),
+        );
+    }
+    if let Some(service_tier) = request.service_tier.as_ref() {
+        body.insert(
+            "service_tier".to_string(),
+            Value::String(service_tier.clone()),
+        );
+    }
+    if let Some(prompt_cache_key) = request.prompt_cache_key.as_ref() {
+        body.insert(
+            "prompt_cache_key".to_string(),
+            Value::String(prompt_cache_key.clone()),
+        );
+    }
+
+    Value::Object(body)
+}
+
+fn build_messages(instructions: &str, input: &[ResponseItem]) -> Vec<Value> {
+    let mut messages = Vec::new();
+    if !instructions.is_empty() {
+        messages.push(json!({ "role": "system", "content": instructions }));
+    }
+
+    // Reasoning is replayed on the next assistant message as
+    // `reasoning_content`, which is how vLLM and llama.cpp expose it.
+    let mut pending_reasoning: Option<String> = None;
+    for item in input {
+        match item {
+            ResponseItem::Message { role, content, .. } => {
+                let role = match role.as_str() {
+                    // Most Chat Completions servers reject the `developer` role.
+                    "developer" => "system",
+                    role => role,
+                };
+                let mut message = Map::new();
+                message.insert("role".to_string(), Value::String(role.to_string()));
+                message.insert("content".to_string(), message_content(role, content));
+                if role == "assistant"
+                    && let Some(reasoning) = pending_reasoning.take()
+                {
+                    message.insert("reasoning_content".to_string(), Value::String(reasoning));
+                }
+                messages.push(Value::Object(message));
+            }
+            ResponseItem::Reasoning {
+                summary, content, ..
+            } => {
+                if let Some(text) = reasoning_text(summary, content.as_deref()) {
+                    pending_reasoning = Some(match pending_reasoning.take() {
+                        Some(previous) => format!("{previous}\n{text}"),
+                        None => text,
+                    });
+                }
+            }
+            ResponseItem::FunctionCall {
+                name,
+                arguments,
+                call_id,
+                ..
+            } => push_tool_call(
+                &mut messages,
+                &mut pending_reasoning,
+                call_id,
+                name,
+                arguments.clone(),
+            ),
+            ResponseItem::CustomToolCall {
+                call_id,
+                name,
+                input,
+                ..
+            } => push_tool_call(
+                &mut messages,
+                &mut pending_reasoning,
+                call_id,
+                name,
+                custom_tool_arguments(input).to_string(),
+            ),
+            ResponseItem::FunctionCallOutput { call_id, output }
+            | ResponseItem::CustomToolCallOutput { call_id, output } => {
+                messages.push(json!({
+                    "role": "tool",
+                    "tool_call_id": call_id,
+                    "content": tool_output_content(&output.body),
+                }));
+            }
+            other => {
+                trace!("dropping item without a chat completions equivalent: {other:?}");
+            }
+        }
+    }
+    messages
+}
+
+fn message_content(role: &str, content: &[ContentItem]) -> Value {
+    let has_images = content
+        .iter()
+        .any(|item| matches!(item, ContentItem::InputImage { .. }));
+    if !has_images || role != "user" {
+        let text = content
+            .iter()
+            .filter_map(|item| match item {
+                ContentItem::InputText { text } | ContentItem::OutputText { text } => {
+                    Some(text.as_str())
+                }
+                ContentItem::InputImage { .. } => None,
+            })
+            .collect::<String>();
+        return Value::String(text);
+    }
+
+    Value::Array(
+        content
+            .iter()
+            .map(|item| match item {
+                ContentItem::InputText { text } | ContentItem::OutputText { text } => {
+                    json!({ "type": "text", "text": text })
+                }
+                ContentItem::InputImage { image_url } => {
+                    json!({ "type": "image_url", "image_url": { "url": image_url } })
+                }
+            })
+            .collect(),
+    )
+}
+
+fn reasoning_text(
+    summary: &[ReasoningItemReasoningSummary],
+    content: Option<&[ReasoningItemContent]>,
+) -> Option<String> {
+    let text = match content {
+        Some(content) if !content.is_empty() => content
+            .iter()
+            .map(|item| match item {
+                ReasoningItemContent::ReasoningText { text }
+                | ReasoningItemContent::Text { text } => text.as_str(),
+            })
+            .collect::<String>(),
+        _ => summary
+            .iter()
+            .map(|item| match item {
+                ReasoningItemReasoningSummary::SummaryText { text } => text.as_str(),
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+    };
+    (!text.is_empty()).then_some(text)
+}
+
+/// Appends a tool call to the trailing assistant message, or starts a new
+/// assistant message when the previous message came from someone else.
+fn push_tool_call(
+    messages: &mut Vec<Value>,
+    pending_reasoning: &mut Option<String>,
+    call_id: &str,
+    name: &str,
+    arguments: String,
+) {
+    let tool_call = json!({
+        "id": call_id,
+        "type": "function",
+        "function": { "name": name, "arguments": arguments },
+    });
+
+    if let Some(Value::Object(last)) = messages.last_mut()
+        && last.get("role").and_then(Value::as_str) == Some("assistant")
+    {
+        match last.get_mut("tool_calls") {
+            Some(Value::Array(tool_calls)) => tool_calls.push(tool_call),
+            _ => {
+                last.insert("tool_calls".to_string(), Value::Array(vec![tool_call]));
+            }
+        }
+        return;
+    }
+
+    let mut message = Map::new();
+    message.insert("role".to_string(), Value::String("assistant".to_string()));
+    message.insert("content".to_string(), Value::Null);
+    message.insert("tool_calls".to_string(), Value::Array(vec![tool_call]));
+    if let Some(reasoning) = pending_reasoning.take() {
+        message.insert("reasoning_content".to_string(), Value::String(reasoning));
+    }
+    messages.push(Value::Object(message));
+}
+
+fn tool_output_content(body: &FunctionCallOutputBody) -> Value {
+    match body {
+        FunctionCallOutputBody::Text(text) => Value::String(text.clone()),
+        FunctionCallOutputBody::ContentItems(items) => Value::Array(
+            items
+                .iter()
+                .map(|item| match item {
+                    FunctionCallOutputContentItem::InputText { text } => {
+                        json!({ "type": "text", "text": text })
+                    }
+                    FunctionCallOutputContentItem::InputImage { image_url, .. } => {
+                        json!({ "type": "image_url", "image_url": { "url": image_url } })
+                    }
+                })
+                .collect(),
+        ),
+    }
+}
+
+/// Converts a Responses `function` tool into the nested Chat Completions
+/// shape. Freeform tools are sent as functions taking a single `input`
+/// string. Hosted tools (web search, local shell, ...) have no Chat
+/// Completions equivalent and are skipped.
+fn chat_tool_from_responses_tool(tool: &Value) -> Option<Value> {
+    let function_tool;
+    let tool = match tool.get("type").and_then(Value::as_str) {
+        Some("function") => tool,
+        Some("custom") => {
+            function_tool = function_tool_from_custom_tool(tool)?;
+            &function_tool
+        }
+        _ => {
+            trace!("dropping tool without a chat completions equivalent: {tool}");
+            return None;
+        }
+    };
+
+    let mut function = Map::new();
+    for key in ["name", "description", "parameters", "strict"] {
+        if let Some(value) = tool.get(key) {
+            function.insert(key.to_string(), value.clone());
+        }
+    }
+    Some(json!({ "type": "function", "function": function }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::Reasoning;
+    use codex_protocol::models::FunctionCallOutputPayload;
+    use codex_protocol::openai_models::ReasoningEffort;
+    use pretty_assertions::assert_eq;
+
+    fn request(input: Vec<ResponseItem>, tools: Vec<Value>) -> ResponsesApiRequest {
+        ResponsesApiRequest {
+            model: "qwen3-coder".to_string(),
+            instructions: "be helpful".to_string(),
+            previous_response_id: None,
+            input,
+            tools,
+            tool_choice: "auto".to_string(),
+            parallel_tool_calls: true,
+            reasoning: Some(Reasoning {
+                effort: Some(ReasoningEffort::High),
+                summary: None,
+            }),
+            store: false,
+            stream: true,
+            include: Vec::new(),
+            service_tier: None,
+            prompt_cache_key: None,
+            text: None,
+            max_output_tokens: None,
+        }
+    }
+
+    #[test]
+    fn translates_history_and_tools() {
+        let input = vec![
+            ResponseItem::Message {
+                id: None,
+                role: "developer".to_string(),
+                content: vec![ContentItem::InputText {
+                    text: "sandbox: read-only".to_string(),
+                }],
+                end_turn: None,
+                phase: None,
+            },
+            ResponseItem::Message {
+                id: None,
+                role: "user".to_string(),
+                content: vec![ContentItem::InputText {
+                    text: "list files".to_string(),
+                }],
+                end_turn: None,
+                phase: None,
+            },
+            ResponseItem::Reasoning {
+                id: "rs_1".to_string(),
+                summary: Vec::new(),
+                content: Some(vec![ReasoningItemContent::ReasoningText {
+                    text: "use ls".to_string(),
+                }]),
+                encrypted_content: None,
+            },
+            ResponseItem::FunctionCall {
+                id: None,
+                name: "shell".to_string(),
+                namespace: None,
+                arguments: r#"{"command":["ls"]}"#.to_string(),
+                call_id: "call_1".to_string(),
+            },
+            ResponseItem::FunctionCall {
+                id: None,
+                name: "shell".to_string(),
+                namespace: None,
+                arguments: r#"{"command":["pwd"]}"#.to_string(),
+                call_id: "call_2".to_string(),
+            },
+            ResponseItem::FunctionCallOutput {
+                call_id: "call_1".to_string(),
+                output: FunctionCallOutputPayload::from_text("Cargo.toml".to_string()),
+            },
+            ResponseItem::FunctionCallOutput {
+                call_id: "call_2".to_string(),
+                output: FunctionCallOutputPayload::from_text("/repo".to_string()),
+            },
+        ];
+        let tools = vec![
+            json!({
+                "type": "function",
+                "name": "shell",
+                "description": "Runs a command",
+                "strict": false,
+                "parameters": {"type": "object", "properties": {}},
+            }),
+            json!({"type": "web_search"}),
+        ];
+
+        let body = build_chat_completions_body(&request(input, tools));
+
+        assert_eq!(
+            body,
+            json!({
+                "model": "qwen3-coder",
+                "messages": [
+                    {"role": "system", "content": "be helpful"},
+                    {"role": "system", "content": "sandbox: read-only"},
+                    {"role": "user", "content": "list files"},
+                    {
+                        "role": "assistant",
+                        "content": null,
+                        "reasoning_content": "use ls",
+                        "tool_calls": [
+                            {
+                                "id": "call_1",
+                                "type": "function",
+                                "function": {"name": "shell", "arguments": r#"{"command":["ls"]}"#},
+                            },
+                            {
+                                "id": "call_2",
+                                "type": "function",
+                                "function": {"name": "shell", "arguments": r#"{"command":["pwd"]}"#},
+                            },
+                        ],
+                    },
+                    {"role": "tool", "tool_call_id": "call_1", "content": "Cargo.toml"},
+                    {"role": "tool", "tool_call_id": "call_2", "content": "/repo"},
+                ],
+                "stream": true,
+                "stream_options": {"include_usage": true},
+                "tools": [{
+                    "type": "function",
+                    "function": {
+                        "name": "shell",
+                        "description": "Runs a command",
+                        "strict": false,
+                        "parameters": {"type": "object", "properties": {}},
+                    },
+                }],
+                "tool_choice": "auto",
+                "parallel_tool_calls": true,
+                "reasoning_effort": "high",
+            })
+        );
+    }
+
+    #[test]
+    fn user_images_use_content_parts() {
+        let input = vec![ResponseItem::Message {
+            id: None,
+            role: "user".to_string(),
+            content: vec![
+                ContentItem::InputText {
+                    text: "what is this?".to_string(),
+                },
+                ContentItem::InputImage {
+                    image_url: "data:image/png;base64,AAAA".to_string(),
+                },
+            ],
+            end_turn: None,
+            phase: None,
+        }];
+
+        let body = build_chat_completions_body(&request(input, Vec::new()));
+
+        assert_eq!(
+            body["messages"][1],
+            json!({
+                "role": "user",
+                "content": [
+                    {"type": "text", "text": "what is this?"},
+                    {"type": "image_url", "image_url": {"url": "data:image/png;base64,AAAA"}},
+                ],
+            })
+        );
+        assert_eq!(body.get("tools"), None);
+    }
+
+    #[test]
+    fn freeform_tools_are_sent_as_functions_with_an_input_string() {
+        let input = vec![ResponseItem::CustomToolCall {
+            id: None,
+            status: None,
+            call_id: "call_1".to_string(),
+            name: "apply_patch".to_string(),
+            input: "*** Begin Patch".to_string(),
+        }];
+        let tools = vec![json!({
+            "type": "custom",
+            "name": "apply_patch",
+            "description": "Edit files.",
+            "format": {"type": "text"},
+        })];
+
+        let body = build_chat_completions_body(&request(input, tools));
+
+        assert_eq!(
+            body["messages"][1]["tool_calls"][0]["function"],
+            json!({"name": "apply_patch", "arguments": r#"{"input":"*** Begin Patch"}"#})
+        );
+        assert_eq!(
+            body["tools"][0]["function"]["parameters"]["required"],
+            json!(["input"])
+        );
+    }
+}
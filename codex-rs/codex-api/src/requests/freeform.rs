@@ -0,0 +1,184 @@
+//! Freeform (`custom`) tools on wire APIs that only know function tools.
+//!
+//! Chat Completions and Anthropic Messages have no equivalent of the
+//! Responses `custom` tool type, so a freeform tool is declared as a function
+//! tool taking its raw input in a single string `input` parameter. Calls to
+//! such a tool are turned back into [`ResponseItem::CustomToolCall`]s, so the
+//! rest of Codex sees the same items the Responses API produces.
+
+use codex_protocol::models::ResponseItem;
+use serde_json::Map;
+use serde_json::Value;
+use serde_json::json;
+use std::collections::HashSet;
+
+const INPUT_PARAMETER: &str = "input";
+
+/// Names of the freeform tools in a Responses tool list.
+pub(crate) fn custom_tool_names(tools: &[Value]) -> HashSet<String> {
+    tools
+        .iter()
+        .filter(|tool| tool.get("type").and_then(Value::as_str) == Some("custom"))
+        .filter_map(|tool| tool.get("name").and_then(Value::as_str))
+        .map(str::to_string)
+        .collect()
+}
+
+/// Rewrites a Responses `custom` tool as a Responses `function` tool. A
+/// grammar, if the tool has one, is appended to the description so the model
+/// still knows what the input must look like.
+pub(crate) fn function_tool_from_custom_tool(tool: &Value) -> Option<Value> {
+    let name = tool.get("name")?.clone();
+    let mut description = tool
+        .get("description")
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .to_string();
+    if let Some(format) = tool.get("format")
+        && format.get("type").and_then(Value::as_str) == Some("grammar")
+        && let Some(definition) = format.get("definition").and_then(Value::as_str)
+    {
+        let syntax = format
+            .get("syntax")
+            .and_then(Value::as_str)
+            .unwrap_or("grammar");
+        description.push_str(&format!(
+            "\n\nThe `{INPUT_PARAMETER}` argument must match this {syntax} grammar:\n{definition}"
+        ));
+    }
+
+    let mut properties = Map::new();
+    properties.insert(
+        INPUT_PARAMETER.to_string(),
+        json!({ "type": "string", "description": "The raw tool input." }),
+    );
+    Some(json!({
+        "type": "function",
+        "name": name,
+        "description": description,
+        "parameters": {
+            "type": "object",
+            "properties": properties,
+            "required": [INPUT_PARAMETER],
+            "additionalProperties": false,
+        },
+    }))
+}
+
+/// The function-call arguments a freeform tool call is replayed with.
+pub(crate) fn custom_tool_arguments(input: &str) -> Value {
+    json!({ INPUT_PARAMETER: input })
+}
+
+/// Builds the output item for a streamed tool call: a
+/// [`ResponseItem::CustomToolCall`] when `name` is one of `custom_tools`,
+/// otherwise a [`ResponseItem::FunctionCall`].
+pub(crate) fn tool_call_item(
+    custom_tools: &HashSet<String>,
+    call_id: String,
+    name: String,
+    arguments: String,
+) -> ResponseItem {
+    if !custom_tools.contains(&name) {
+        return ResponseItem::FunctionCall {
+            id: None,
+            name,
+            namespace: None,
+            arguments: if arguments.is_empty() {
+                "{}".to_string()
+            } else {
+                arguments
+            },
+            call_id,
+        };
+    }
+
+    // Models occasionally skip the JSON wrapper; pass such input through.
+    let input = match serde_json::from_str::<Value>(&arguments) {
+        Ok(Value::Object(mut object)) => match object.remove(INPUT_PARAMETER) {
+            Some(Value::String(input)) => input,
+            _ => arguments,
+        },
+        _ => arguments,
+    };
+    ResponseItem::CustomToolCall {
+        id: None,
+        status: None,
+        call_id,
+        name,
+        input,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn custom_tool_becomes_function_with_input_parameter() {
+        let tool = json!({
+            "type": "custom",
+            "name": "apply_patch",
+            "description": "Edit files.",
+            "format": {
+                "type": "grammar",
+                "syntax": "lark",
+                "definition": "start: patch",
+            },
+        });
+
+        assert_eq!(
+            function_tool_from_custom_tool(&tool),
+            Some(json!({
+                "type": "function",
+                "name": "apply_patch",
+                "description": "Edit files.\n\nThe `input` argument must match this lark grammar:\nstart: patch",
+                "parameters": {
+                    "type": "object",
+                    "properties": {
+                        "input": { "type": "string", "description": "The raw tool input." },
+                    },
+                    "required": ["input"],
+                    "additionalProperties": false,
+                },
+            }))
+        );
+    }
+
+    #[test]
+    fn calls_to_custom_tools_become_custom_tool_calls() {
+        let custom_tools = HashSet::from(["apply_patch".to_string()]);
+
+        assert_eq!(
+            tool_call_item(
+                &custom_tools,
+                "call_1".to_string(),
+                "apply_patch".to_string(),
+                r#"{"input":"*** Begin Patch"}"#.to_string(),
+            ),
+            ResponseItem::CustomToolCall {
+                id: None,
+                status: None,
+                call_id: "call_1".to_string(),
+                name: "apply_patch".to_string(),
+                input: "*** Begin Patch".to_string(),
+            }
+        );
+        assert_eq!(
+            tool_call_item(
+                &custom_tools,
+                "call_2".to_string(),
+                "shell".to_string(),
+                String::new(),
+            ),
+            ResponseItem::FunctionCall {
+                id: None,
+                name: "shell".to_string(),
+                namespace: None,
+                arguments: "{}".to_string(),
+                call_id: "call_2".to_string(),
+            }
+        );
+    }
+}
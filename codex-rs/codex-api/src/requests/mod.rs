@@ -1,2 +1,5 @@
+pub mod anthropic;
+pub mod chat;
+pub(crate) mod freeform;
 pub(crate) mod headers;
 pub mod responses;
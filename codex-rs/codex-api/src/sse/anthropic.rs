@@ -0,0 +1,505 @@
+//! Streaming parser for Anthropic Messages SSE.
+//!
+//! Messages streams indexed content blocks (`text`, `thinking`,
+//! `redacted_thinking`, `tool_use`) between `content_block_start` and
+//! `content_block_stop`. Each block becomes one output item: text blocks are
+//! assistant messages, thinking blocks are reasoning items whose signature is
+//! kept in `encrypted_content` so they can be replayed, and `tool_use` blocks
+//! are function calls.
+
+use crate::common::ResponseEvent;
+use crate::common::ResponseStream;
+use crate::error::ApiError;
+use crate::requests::freeform::tool_call_item;
+use crate::telemetry::SseTelemetry;
+use codex_client::ByteStream;
+use codex_client::StreamResponse;
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::ReasoningItemContent;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::protocol::TokenUsage;
+use eventsource_stream::Eventsource;
+use futures::StreamExt;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::time::Instant;
+use tokio::time::timeout;
+use tracing::debug;
+use tracing::trace;
+
+/// Spawns the parser for a Messages stream. Calls to the tools named in
+/// `custom_tools` are reported as freeform tool calls.
+pub fn spawn_anthropic_messages_stream(
+    stream_response: StreamResponse,
+    custom_tools: HashSet<String>,
+    idle_timeout: Duration,
+    telemetry: Option<Arc<dyn SseTelemetry>>,
+) -> ResponseStream {
+    let (tx_event, rx_event) = mpsc::channel::<Result<ResponseEvent, ApiError>>(1600);
+    tokio::spawn(process_anthropic_sse(
+        stream_response.bytes,
+        custom_tools,
+        tx_event,
+        idle_timeout,
+        telemetry,
+    ));
+
+    ResponseStream { rx_event }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum AnthropicStreamEvent {
+    MessageStart {
+        message: AnthropicMessageStart,
+    },
+    ContentBlockStart {
+        index: usize,
+        content_block: AnthropicContentBlock,
+    },
+    ContentBlockDelta {
+        index: usize,
+        delta: AnthropicBlockDelta,
+    },
+    ContentBlockStop {
+        index: usize,
+    },
+    MessageDelta {
+        #[serde(default)]
+        usage: Option<AnthropicUsage>,
+    },
+    MessageStop,
+    Ping,
+    Error {
+        error: AnthropicError,
+    },
+}
+
+#[derive(Debug, Deserialize)]
+struct AnthropicMessageStart {
+    id: String,
+    #[serde(default)]
+    usage: Option<AnthropicUsage>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum AnthropicContentBlock {
+    Text {
+        #[serde(default)]
+        text: String,
+    },
+    Thinking {
+        #[serde(default)]
+        thinking: String,
+    },
+    RedactedThinking {
+        data: String,
+    },
+    ToolUse {
+        id: String,
+        name: String,
+    },
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum AnthropicBlockDelta {
+    TextDelta {
+        text: String,
+    },
+    ThinkingDelta {
+        thinking: String,
+    },
+    SignatureDelta {
+        signature: String,
+    },
+    InputJsonDelta {
+        partial_json: String,
+    },
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct AnthropicUsage {
+    #[serde(default)]
+    input_tokens: Option<i64>,
+    #[serde(default)]
+    cache_creation_input_tokens: Option<i64>,
+    #[serde(default)]
+    cache_read_input_tokens: Option<i64>,
+    #[serde(default)]
+    output_tokens: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AnthropicError {
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    message: String,
+}
+
+impl From<AnthropicError> for ApiError {
+    fn from(error: AnthropicError) -> Self {
+        match error.kind.as_str() {
+            "overloaded_error" => ApiError::ServerOverloaded,
+            "rate_limit_error" | "api_error" => ApiError::Retryable {
+                message: error.message,
+                delay: None,
+            },
+            "invalid_request_error" if error.message.contains("prompt is too long") => {
+                ApiError::ContextWindowExceeded
+            }
+            "invalid_request_error" => ApiError::InvalidRequest {
+                message: error.message,
+            },
+            _ => ApiError::Stream(format!("{}: {}", error.kind, error.message)),
+        }
+    }
+}
+
+#[derive(Debug)]
+enum PendingBlock {
+    Text(String),
+    Thinking {
+        text: String,
+        signature: String,
+    },
+    RedactedThinking(String),
+    ToolUse {
+        id: String,
+        name: String,
+        input: String,
+    },
+}
+
+impl PendingBlock {
+    fn into_item(self, custom_tools: &HashSet<String>) -> ResponseItem {
+        match self {
+            PendingBlock::Text(text) => assistant_message(text),
+            PendingBlock::Thinking { text, signature } => {
+                reasoning_item(text, (!signature.is_empty()).then_some(signature))
+            }
+            PendingBlock::RedactedThinking(data) => ResponseItem::Reasoning {
+                id: String::new(),
+                summary: Vec::new(),
+                content: None,
+                encrypted_content: Some(data),
+            },
+            PendingBlock::ToolUse { id, name, input } => {
+                tool_call_item(custom_tools, id, name, input)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct AnthropicStreamState {
+    custom_tools: HashSet<String>,
+    response_id: String,
+    blocks: HashMap<usize, PendingBlock>,
+    input_tokens: i64,
+    cached_input_tokens: i64,
+    output_tokens: i64,
+}
+
+impl AnthropicStreamState {
+    fn record_usage(&mut self, usage: AnthropicUsage) {
+        let cache_read = usage.cache_read_input_tokens.unwrap_or(0);
+        if let Some(input_tokens) = usage.input_tokens {
+            // Anthropic reports cache reads and writes separately from
+            // uncached input; Codex counts them all as input.
+            self.input_tokens =
+                input_tokens + cache_read + usage.cache_creation_input_tokens.unwrap_or(0);
+            self.cached_input_tokens = cache_read;
+        }
+        if let Some(output_tokens) = usage.output_tokens {
+            self.output_tokens = output_tokens;
+        }
+    }
+
+    fn token_usage(&self) -> TokenUsage {
+        TokenUsage {
+            input_tokens: self.input_tokens,
+            cached_input_tokens: self.cached_input_tokens,
+            output_tokens: self.output_tokens,
+            reasoning_output_tokens: 0,
+            total_tokens: self.input_tokens + self.output_tokens,
+        }
+    }
+
+    /// Applies one stream event and returns the events to forward.
+    fn apply(&mut self, event: AnthropicStreamEvent) -> Result<Vec<ResponseEvent>, ApiError> {
+        let mut events = Vec::new();
+        match event {
+            AnthropicStreamEvent::MessageStart { message } => {
+                self.response_id = message.id;
+                if let Some(usage) = message.usage {
+                    self.record_usage(usage);
+                }
+                events.push(ResponseEvent::Created);
+            }
+            AnthropicStreamEvent::ContentBlockStart {
+                index,
+                content_block,
+            } => {
+                let block = match content_block {
+                    AnthropicContentBlock::Text { text } => {
+                        events.push(ResponseEvent::OutputItemAdded(assistant_message(
+                            String::new(),
+                        )));
+                        if !text.is_empty() {
+                            events.push(ResponseEvent::OutputTextDelta(text.clone()));
+                        }
+                        PendingBlock::Text(text)
+                    }
+                    AnthropicContentBlock::Thinking { thinking } => {
+                        events.push(ResponseEvent::OutputItemAdded(reasoning_item(
+                            String::new(),
+                            None,
+                        )));
+                        PendingBlock::Thinking {
+                            text: thinking,
+                            signature: String::new(),
+                        }
+                    }
+                    AnthropicContentBlock::RedactedThinking { data } => {
+                        PendingBlock::RedactedThinking(data)
+                    }
+                    AnthropicContentBlock::ToolUse { id, name } => PendingBlock::ToolUse {
+                        id,
+                        name,
+                        input: String::new(),
+                    },
+                    AnthropicContentBlock::Unknown => {
+                        trace!("ignoring unknown anthropic content block at index {index}");
+                        return Ok(events);
+                    }
+                };
+                self.blocks.insert(index, block);
+            }
+            AnthropicStreamEvent::ContentBlockDelta { index, delta } => {
+                match (self.blocks.get_mut(&index), delta) {
+                    (
+                        Some(PendingBlock::Text(text)),
+                        AnthropicBlockDelta::TextDelta { text: delta },
+                    ) => {
+                        text.push_str(&delta);
+                        events.push(ResponseEvent::OutputTextDelta(delta));
+                    }
+                    (
+                        Some(PendingBlock::Thinking { text, .. }),
+                        AnthropicBlockDelta::ThinkingDelta { thinking },
+                    ) => {
+                        text.push_str(&thinking);
+                        events.push(ResponseEvent::ReasoningContentDelta {
+                            delta: thinking,
+                            content_index: 0,
+                        });
+                    }
+                    (
+                        Some(PendingBlock::Thinking { signature, .. }),
+                        AnthropicBlockDelta::SignatureDelta { signature: delta },
+                    ) => signature.push_str(&delta),
+                    (
+                        Some(PendingBlock::ToolUse { input, .. }),
+                        AnthropicBlockDelta::InputJsonDelta { partial_json },
+                    ) => input.push_str(&partial_json),
+                    (_, delta) => {
+                        trace!("ignoring anthropic delta for block {index}: {delta:?}");
+                    }
+                }
+            }
+            AnthropicStreamEvent::ContentBlockStop { index } => {
+                if let Some(block) = self.blocks.remove(&index) {
+                    events.push(ResponseEvent::OutputItemDone(
+                        block.into_item(&self.custom_tools),
+                    ));
+                }
+            }
+            AnthropicStreamEvent::MessageDelta { usage } => {
+                if let Some(usage) = usage {
+                    self.record_usage(usage);
+                }
+            }
+            AnthropicStreamEvent::MessageStop => {
+                events.push(ResponseEvent::Completed {
+                    response_id: self.response_id.clone(),
+                    token_usage: Some(self.token_usage()),
+                });
+            }
+            AnthropicStreamEvent::Ping => {}
+            AnthropicStreamEvent::Error { error } => return Err(error.into()),
+        }
+        Ok(events)
+    }
+}
+
+fn assistant_message(text: String) -> ResponseItem {
+    ResponseItem::Message {
+        id: None,
+        role: "assistant".to_string(),
+        content: vec![ContentItem::OutputText { text }],
+        end_turn: None,
+        phase: None,
+    }
+}
+
+fn reasoning_item(text: String, signature: Option<String>) -> ResponseItem {
+    ResponseItem::Reasoning {
+        id: String::new(),
+        summary: Vec::new(),
+        content: Some(if text.is_empty() {
+            Vec::new()
+        } else {
+            vec![ReasoningItemContent::ReasoningText { text }]
+        }),
+        encrypted_content: signature,
+    }
+}
+
+pub async fn process_anthropic_sse(
+    stream: ByteStream,
+    custom_tools: HashSet<String>,
+    tx_event: mpsc::Sender<Result<ResponseEvent, ApiError>>,
+    idle_timeout: Duration,
+    telemetry: Option<Arc<dyn SseTelemetry>>,
+) {
+    let mut stream = stream.eventsource();
+    let mut state = AnthropicStreamState {
+        custom_tools,
+        ..Default::default()
+    };
+
+    loop {
+        let start = Instant::now();
+        let response = timeout(idle_timeout, stream.next()).await;
+        if let Some(t) = telemetry.as_ref() {
+            t.on_sse_poll(&response, start.elapsed());
+        }
+        let sse = match response {
+            Ok(Some(Ok(sse))) => sse,
+            Ok(Some(Err(e))) => {
+                debug!("SSE Error: {e:#}");
+                let _ = tx_event.send(Err(ApiError::Stream(e.to_string()))).await;
+                return;
+            }
+            Ok(None) => {
+                let _ = tx_event
+                    .send(Err(ApiError::Stream(
+                        "stream closed before message_stop".into(),
+                    )))
+                    .await;
+                return;
+            }
+            Err(_) => {
+                let _ = tx_event
+                    .send(Err(ApiError::Stream("idle timeout waiting for SSE".into())))
+                    .await;
+                return;
+            }
+        };
+
+        trace!("SSE event: {}", &sse.data);
+
+        let event: AnthropicStreamEvent = match serde_json::from_str(&sse.data) {
+            Ok(event) => event,
+            Err(e) => {
+                debug!(
+                    "Failed to parse anthropic SSE event: {e}, data: {}",
+                    &sse.data
+                );
+                continue;
+            }
+        };
+
+        match state.apply(event) {
+            Ok(events) => {
+                for event in events {
+                    let is_completed = matches!(event, ResponseEvent::Completed { .. });
+                    if tx_event.send(Ok(event)).await.is_err() {
+                        return;
+                    }
+                    if is_completed {
+                        return;
+                    }
+                }
+            }
+            Err(error) => {
+                let _ = tx_event.send(Err(error)).await;
+                return;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_matches::assert_matches;
+    use codex_client::TransportError;
+    use futures::TryStreamExt;
+    use tokio_test::io::Builder as IoBuilder;
+    use tokio_util::io::ReaderStream;
+
+    async fn collect_events(body: &str) -> Vec<Result<ResponseEvent, ApiError>> {
+        let reader = IoBuilder::new().read(body.as_bytes()).build();
+        let stream =
+            ReaderStream::new(reader).map_err(|err| TransportError::Network(err.to_string()));
+        let (tx, mut rx) = mpsc::channel::<Result<ResponseEvent, ApiError>>(64);
+        tokio::spawn(process_anthropic_sse(
+            Box::pin(stream),
+            HashSet::from(["apply_patch".to_string()]),
+            tx,
+            Duration::from_millis(1000),
+            None,
+        ));
+
+        let mut events = Vec::new();
+        while let Some(ev) = rx.recv().await {
+            events.push(ev);
+        }
+        events
+    }
+
+    #[tokio::test]
+    async fn overloaded_error_event_maps_to_server_overloaded() {
+        let events = collect_events(concat!(
+            "event: error\n",
+            "data: {\"type\":\"error\",\"error\":{\"type\":\"overloaded_error\",\"message\":\"Overloaded\"}}\n\n",
+        ))
+        .await;
+
+        assert_matches!(events.last(), Some(Err(ApiError::ServerOverloaded)));
+    }
+
+    #[tokio::test]
+    async fn prompt_too_long_maps_to_context_window_exceeded() {
+        let events = collect_events(concat!(
+            "event: error\n",
+            "data: {\"type\":\"error\",\"error\":{\"type\":\"invalid_request_error\",\"message\":\"prompt is too long: 210000 tokens > 200000 maximum\"}}\n\n",
+        ))
+        .await;
+
+        assert_matches!(events.last(), Some(Err(ApiError::ContextWindowExceeded)));
+    }
+
+    #[tokio::test]
+    async fn stream_without_message_stop_is_an_error() {
+        let events = collect_events(concat!(
+            "event: message_start\n",
+            "data: {\"type\":\"message_start\",\"message\":{\"id\":\"msg_1\",\"usage\":{\"input_tokens\":1}}}\n\n",
+        ))
+        .await;
+
+        assert_matches!(events.last(), Some(Err(ApiError::Stream(_))));
+    }
+}
@@ -0,0 +1,511 @@
+//! Streaming parser for Chat Completions SSE.
+//!
+//! Chat Completions streams deltas for a single choice rather than discrete
+//! output items, so this module accumulates content, reasoning and tool-call
+//! fragments and re-emits them as the [`ResponseEvent`]s the Responses parser
+//! produces: `OutputItemAdded` and deltas while streaming, then one
+//! `OutputItemDone` per item and `Completed` once the server sends `[DONE]`.
+
+use crate::common::ResponseEvent;
+use crate::common::ResponseStream;
+use crate::error::ApiError;
+use crate::rate_limits::parse_all_rate_limits;
+use crate::requests::freeform::tool_call_item;
+use crate::telemetry::SseTelemetry;
+use codex_client::ByteStream;
+use codex_client::StreamResponse;
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::ReasoningItemContent;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::protocol::TokenUsage;
+use eventsource_stream::Eventsource;
+use futures::StreamExt;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::time::Instant;
+use tokio::time::timeout;
+use tracing::debug;
+use tracing::trace;
+
+const DONE_SENTINEL: &str = "[DONE]";
+
+/// Spawns the parser for a chat completions stream. Calls to the tools named
+/// in `custom_tools` are reported as freeform tool calls.
+pub fn spawn_chat_completions_stream(
+    stream_response: StreamResponse,
+    custom_tools: HashSet<String>,
+    idle_timeout: Duration,
+    telemetry: Option<Arc<dyn SseTelemetry>>,
+) -> ResponseStream {
+    let rate_limit_snapshots = parse_all_rate_limits(&stream_response.headers);
+    let (tx_event, rx_event) = mpsc::channel::<Result<ResponseEvent, ApiError>>(1600);
+    tokio::spawn(async move {
+        for snapshot in rate_limit_snapshots {
+            let _ = tx_event.send(Ok(ResponseEvent::RateLimits(snapshot))).await;
+        }
+        process_chat_sse(
+            stream_response.bytes,
+            custom_tools,
+            tx_event,
+            idle_timeout,
+            telemetry,
+        )
+        .await;
+    });
+
+    ResponseStream { rx_event }
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatCompletionChunk {
+    #[serde(default)]
+    id: Option<String>,
+    #[serde(default)]
+    choices: Vec<ChatChoice>,
+    #[serde(default)]
+    usage: Option<ChatUsage>,
+    #[serde(default)]
+    error: Option<ChatError>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatChoice {
+    #[serde(default)]
+    delta: Option<ChatDelta>,
+    #[serde(default)]
+    finish_reason: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct ChatDelta {
+    #[serde(default)]
+    content: Option<String>,
+    /// vLLM, llama.cpp and DeepSeek use `reasoning_content`; some
+    /// OpenRouter-style servers use `reasoning`.
+    #[serde(default, alias = "reasoning")]
+    reasoning_content: Option<String>,
+    #[serde(default)]
+    tool_calls: Vec<ChatToolCallDelta>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatToolCallDelta {
+    #[serde(default)]
+    index: Option<usize>,
+    #[serde(default)]
+    id: Option<String>,
+    #[serde(default)]
+    function: Option<ChatFunctionDelta>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatFunctionDelta {
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    arguments: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatUsage {
+    #[serde(default)]
+    prompt_tokens: i64,
+    #[serde(default)]
+    completion_tokens: i64,
+    #[serde(default)]
+    total_tokens: i64,
+    #[serde(default)]
+    prompt_tokens_details: Option<ChatPromptTokensDetails>,
+    #[serde(default)]
+    completion_tokens_details: Option<ChatCompletionTokensDetails>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatPromptTokensDetails {
+    #[serde(default)]
+    cached_tokens: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatCompletionTokensDetails {
+    #[serde(default)]
+    reasoning_tokens: Option<i64>,
+}
+
+impl From<ChatUsage> for TokenUsage {
+    fn from(val: ChatUsage) -> Self {
+        TokenUsage {
+            input_tokens: val.prompt_tokens,
+            cached_input_tokens: val
+                .prompt_tokens_details
+                .and_then(|d| d.cached_tokens)
+                .unwrap_or(0),
+            output_tokens: val.completion_tokens,
+            reasoning_output_tokens: val
+                .completion_tokens_details
+                .and_then(|d| d.reasoning_tokens)
+                .unwrap_or(0),
+            total_tokens: val.total_tokens,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatError {
+    #[serde(default)]
+    code: Option<serde_json::Value>,
+    #[serde(default)]
+    message: Option<String>,
+}
+
+impl From<ChatError> for ApiError {
+    fn from(error: ChatError) -> Self {
+        let code = error.code.as_ref().and_then(serde_json::Value::as_str);
+        match code {
+            Some("context_length_exceeded") => ApiError::ContextWindowExceeded,
+            Some("insufficient_quota") => ApiError::QuotaExceeded,
+            Some("server_is_overloaded") | Some("slow_down") => ApiError::ServerOverloaded,
+            _ => ApiError::Stream(
+                error
+                    .message
+                    .unwrap_or_else(|| "chat completions stream returned an error".to_string()),
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct PendingToolCall {
+    id: Option<String>,
+    name: String,
+    arguments: String,
+}
+
+/// Accumulated state for the single choice Codex requests.
+#[derive(Debug, Default)]
+struct ChatStreamState {
+    custom_tools: HashSet<String>,
+    response_id: Option<String>,
+    reasoning: Option<String>,
+    reasoning_done: bool,
+    text: Option<String>,
+    tool_calls: BTreeMap<usize, PendingToolCall>,
+    usage: Option<TokenUsage>,
+    finished: bool,
+}
+
+impl ChatStreamState {
+    fn apply_chunk(&mut self, chunk: ChatCompletionChunk) -> Vec<ResponseEvent> {
+        let mut events = Vec::new();
+        if self.response_id.is_none() {
+            self.response_id = chunk.id.filter(|id| !id.is_empty());
+        }
+        if let Some(usage) = chunk.usage {
+            self.usage = Some(usage.into());
+        }
+
+        for choice in chunk.choices {
+            let delta = choice.delta.unwrap_or_default();
+            if let Some(reasoning) = delta.reasoning_content.filter(|r| !r.is_empty())
+                && !self.reasoning_done
+            {
+                if self.reasoning.is_none() {
+                    events.push(ResponseEvent::OutputItemAdded(
+                        reasoning_item(String::new()),
+                    ));
+                }
+                self.reasoning
+                    .get_or_insert_with(String::new)
+                    .push_str(&reasoning);
+                events.push(ResponseEvent::ReasoningContentDelta {
+                    delta: reasoning,
+                    content_index: 0,
+                });
+            }
+            if let Some(content) = delta.content.filter(|c| !c.is_empty()) {
+                events.extend(self.finish_reasoning());
+                if self.text.is_none() {
+                    events.push(ResponseEvent::OutputItemAdded(assistant_message(
+                        String::new(),
+                    )));
+                }
+                self.text.get_or_insert_with(String::new).push_str(&content);
+                events.push(ResponseEvent::OutputTextDelta(content));
+            }
+            for (position, tool_call) in delta.tool_calls.into_iter().enumerate() {
+                let index = tool_call.index.unwrap_or(position);
+                let pending = self.tool_calls.entry(index).or_default();
+                if let Some(id) = tool_call.id.filter(|id| !id.is_empty()) {
+                    pending.id = Some(id);
+                }
+                if let Some(function) = tool_call.function {
+                    if let Some(name) = function.name {
+                        pending.name.push_str(&name);
+                    }
+                    if let Some(arguments) = function.arguments {
+                        pending.arguments.push_str(&arguments);
+                    }
+                }
+            }
+            if choice.finish_reason.is_some() {
+                self.finished = true;
+            }
+        }
+
+        events
+    }
+
+    fn finish_reasoning(&mut self) -> Option<ResponseEvent> {
+        if self.reasoning_done {
+            return None;
+        }
+        self.reasoning_done = true;
+        self.reasoning
+            .clone()
+            .map(|reasoning| ResponseEvent::OutputItemDone(reasoning_item(reasoning)))
+    }
+
+    fn into_completion_events(mut self) -> Vec<ResponseEvent> {
+        let mut events = Vec::new();
+        events.extend(self.finish_reasoning());
+        if let Some(text) = self.text.take() {
+            events.push(ResponseEvent::OutputItemDone(assistant_message(text)));
+        }
+        let response_id = self.response_id.unwrap_or_default();
+        for (index, tool_call) in std::mem::take(&mut self.tool_calls) {
+            let call_id = tool_call
+                .id
+                .unwrap_or_else(|| format!("call_{response_id}_{index}"));
+            events.push(ResponseEvent::OutputItemDone(tool_call_item(
+                &self.custom_tools,
+                call_id,
+                tool_call.name,
+                tool_call.arguments,
+            )));
+        }
+        events.push(ResponseEvent::Completed {
+            response_id,
+            token_usage: self.usage,
+        });
+        events
+    }
+}
+
+fn assistant_message(text: String) -> ResponseItem {
+    ResponseItem::Message {
+        id: None,
+        role: "assistant".to_string(),
+        content: vec![ContentItem::OutputText { text }],
+        end_turn: None,
+        phase: None,
+    }
+}
+
+fn reasoning_item(text: String) -> ResponseItem {
+    ResponseItem::Reasoning {
+        id: String::new(),
+        summary: Vec::new(),
+        content: Some(if text.is_empty() {
+            Vec::new()
+        } else {
+            vec![ReasoningItemContent::ReasoningText { text }]
+        }),
+        encrypted_content: None,
+    }
+}
+
+pub async fn process_chat_sse(
+    stream: ByteStream,
+    custom_tools: HashSet<String>,
+    tx_event: mpsc::Sender<Result<ResponseEvent, ApiError>>,
+    idle_timeout: Duration,
+    telemetry: Option<Arc<dyn SseTelemetry>>,
+) {
+    let mut stream = stream.eventsource();
+    let mut state = ChatStreamState {
+        custom_tools,
+        ..Default::default()
+    };
+    if tx_event.send(Ok(ResponseEvent::Created)).await.is_err() {
+        return;
+    }
+
+    loop {
+        let start = Instant::now();
+        let response = timeout(idle_timeout, stream.next()).await;
+        if let Some(t) = telemetry.as_ref() {
+            t.on_sse_poll(&response, start.elapsed());
+        }
+        let sse = match response {
+            Ok(Some(Ok(sse))) => sse,
+            Ok(Some(Err(e))) => {
+                debug!("SSE Error: {e:#}");
+                let _ = tx_event.send(Err(ApiError::Stream(e.to_string()))).await;
+                return;
+            }
+            Ok(None) => {
+                // Some servers close the stream after the final chunk without
+                // sending `[DONE]`; accept that once a finish reason arrived.
+                if state.finished {
+                    for event in state.into_completion_events() {
+                        if tx_event.send(Ok(event)).await.is_err() {
+                            return;
+                        }
+                    }
+                } else {
+                    let _ = tx_event
+                        .send(Err(ApiError::Stream(
+                            "stream closed before chat completion finished".into(),
+                        )))
+                        .await;
+                }
+                return;
+            }
+            Err(_) => {
+                let _ = tx_event
+                    .send(Err(ApiError::Stream("idle timeout waiting for SSE".into())))
+                    .await;
+                return;
+            }
+        };
+
+        trace!("SSE event: {}", &sse.data);
+
+        if sse.data.trim() == DONE_SENTINEL {
+            for event in state.into_completion_events() {
+                if tx_event.send(Ok(event)).await.is_err() {
+                    return;
+                }
+            }
+            return;
+        }
+
+        let chunk: ChatCompletionChunk = match serde_json::from_str(&sse.data) {
+            Ok(chunk) => chunk,
+            Err(e) => {
+                debug!(
+                    "Failed to parse chat completion chunk: {e}, data: {}",
+                    &sse.data
+                );
+                continue;
+            }
+        };
+        if let Some(error) = chunk.error {
+            let _ = tx_event.send(Err(error.into())).await;
+            return;
+        }
+
+        for event in state.apply_chunk(chunk) {
+            if tx_event.send(Ok(event)).await.is_err() {
+                return;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_matches::assert_matches;
+    use codex_client::TransportError;
+    use futures::TryStreamExt;
+    use pretty_assertions::assert_eq;
+    use tokio_test::io::Builder as IoBuilder;
+    use tokio_util::io::ReaderStream;
+
+    async fn collect_events(body: &str) -> Vec<Result<ResponseEvent, ApiError>> {
+        let reader = IoBuilder::new().read(body.as_bytes()).build();
+        let stream =
+            ReaderStream::new(reader).map_err(|err| TransportError::Network(err.to_string()));
+        let (tx, mut rx) = mpsc::channel::<Result<ResponseEvent, ApiError>>(64);
+        tokio::spawn(process_chat_sse(
+            Box::pin(stream),
+            HashSet::from(["apply_patch".to_string()]),
+            tx,
+            Duration::from_millis(1000),
+            None,
+        ));
+
+        let mut events = Vec::new();
+        while let Some(ev) = rx.recv().await {
+            events.push(ev);
+        }
+        events
+    }
+
+    #[tokio::test]
+    async fn closes_with_error_when_stream_ends_without_finish_reason() {
+        let events = collect_events(
+            "data: {\"id\":\"c1\",\"choices\":[{\"delta\":{\"content\":\"hi\"}}]}\n\n",
+        )
+        .await;
+
+        assert_matches!(events.last(), Some(Err(ApiError::Stream(_))));
+    }
+
+    #[tokio::test]
+    async fn context_length_error_chunk_maps_to_context_window_exceeded() {
+        let events = collect_events(
+            "data: {\"error\":{\"code\":\"context_length_exceeded\",\"message\":\"too long\"}}\n\n",
+        )
+        .await;
+
+        assert_matches!(events.last(), Some(Err(ApiError::ContextWindowExceeded)));
+    }
+
+    #[tokio::test]
+    async fn reasoning_is_closed_before_text_starts() {
+        let events = collect_events(concat!(
+            "data: {\"id\":\"c1\",\"choices\":[{\"delta\":{\"reasoning_content\":\"think\"}}]}\n\n",
+            "data: {\"id\":\"c1\",\"choices\":[{\"delta\":{\"content\":\"done\"},\"finish_reason\":\"stop\"}]}\n\n",
+        ))
+        .await;
+
+        let done_items = events
+            .into_iter()
+            .filter_map(|event| match event {
+                Ok(ResponseEvent::OutputItemDone(item)) => Some(item),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            done_items,
+            vec![
+                reasoning_item("think".to_string()),
+                assistant_message("done".to_string()),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn calls_to_freeform_tools_become_custom_tool_calls() {
+        let events = collect_events(concat!(
+            "data: {\"id\":\"c1\",\"choices\":[{\"delta\":{\"tool_calls\":[{\"index\":0,\"id\":\"call_1\",\"function\":{\"name\":\"apply_patch\",\"arguments\":\"{\\\"input\\\":\"}}]}}]}\n\n",
+            "data: {\"id\":\"c1\",\"choices\":[{\"delta\":{\"tool_calls\":[{\"index\":0,\"function\":{\"arguments\":\"\\\"*** Begin Patch\\\"}\"}}]},\"finish_reason\":\"tool_calls\"}]}\n\n",
+            "data: [DONE]\n\n",
+        ))
+        .await;
+
+        let done_items = events
+            .into_iter()
+            .filter_map(|event| match event {
+                Ok(ResponseEvent::OutputItemDone(item)) => Some(item),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            done_items,
+            vec![ResponseItem::CustomToolCall {
+                id: None,
+                status: None,
+                call_id: "call_1".to_string(),
+                name: "apply_patch".to_string(),
+                input: "*** Begin Patch".to_string(),
+            }]
+        );
+    }
+}
@@ -1,3 +1,5 @@
+pub mod anthropic;
+pub mod chat;
 pub mod responses;
 
 pub use responses::process_sse;
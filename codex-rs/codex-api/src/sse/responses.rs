@@ -305,6 +305,9 @@ pub fn process_responses_event(
                     .and_then(|details| details.get("reason"))
                     .and_then(Value::as_str)
             });
+            if reason == Some("max_output_tokens") {
+                return Err(ResponsesEventError::Api(ApiError::MaxOutputTokens));
+            }
             let reason = reason.unwrap_or("unknown");
             let message = format!("Incomplete response returned, reason: {reason}");
             return Err(ResponsesEventError::Api(ApiError::Stream(message)));
@@ -776,6 +779,35 @@ mod tests {
         assert_matches!(events[0], Err(ApiError::QuotaExceeded));
     }
 
+    #[tokio::test]
+    async fn incomplete_at_max_output_tokens_is_distinct_error() {
+        let delta = r#"{"type":"response.output_text.delta","delta":"partial"}"#;
+        let incomplete = r#"{"type":"response.incomplete","response":{"id":"resp_incomplete","status":"incomplete","incomplete_details":{"reason":"max_output_tokens"}}}"#;
+        let sse1 = format!("event: response.output_text.delta\ndata: {delta}\n\n");
+        let sse2 = format!("event: response.incomplete\ndata: {incomplete}\n\n");
+
+        let events = collect_events(&[sse1.as_bytes(), sse2.as_bytes()]).await;
+
+        assert_eq!(events.len(), 2);
+        assert_matches!(&events[0], Ok(ResponseEvent::OutputTextDelta(delta)) if delta == "partial");
+        assert_matches!(events[1], Err(ApiError::MaxOutputTokens));
+    }
+
+    #[tokio::test]
+    async fn incomplete_for_other_reasons_is_stream_error() {
+        let incomplete = r#"{"type":"response.incomplete","response":{"id":"resp_filtered","status":"incomplete","incomplete_details":{"reason":"content_filter"}}}"#;
+        let sse1 = format!("event: response.incomplete\ndata: {incomplete}\n\n");
+
+        let events = collect_events(&[sse1.as_bytes()]).await;
+
+        assert_eq!(events.len(), 1);
+        assert_matches!(
+            &events[0],
+            Err(ApiError::Stream(message))
+                if message == "Incomplete response returned, reason: content_filter"
+        );
+    }
+
     #[tokio::test]
     async fn invalid_prompt_without_type_is_invalid_request() {
         let raw_error = r#"{"type":"response.failed","sequence_number":3,"response":{"id":"resp_invalid_prompt_no_type","object":"response","created_at":1759771628,"status":"failed","background":false,"error":{"code":"invalid_prompt","message":"Invalid prompt: we've limited access to this content for safety reasons."},"incomplete_details":null}}"#;
@@ -0,0 +1,279 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use codex_api::AnthropicMessagesClient;
+use codex_api::AuthProvider;
+use codex_api::ResponseEvent;
+use codex_api::ResponsesApiRequest;
+use codex_api::ResponsesOptions;
+use codex_api::common::Reasoning;
+use codex_api::provider::Provider;
+use codex_api::provider::RetryConfig;
+use codex_client::ReqwestTransport;
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::ReasoningItemContent;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::openai_models::ReasoningEffort;
+use codex_protocol::protocol::TokenUsage;
+use futures::StreamExt;
+use http::HeaderMap;
+use http::HeaderValue;
+use pretty_assertions::assert_eq;
+use serde_json::Value;
+use serde_json::json;
+use wiremock::Mock;
+use wiremock::MockServer;
+use wiremock::ResponseTemplate;
+use wiremock::matchers::header;
+use wiremock::matchers::method;
+use wiremock::matchers::path;
+
+const TOOL_USE_FIXTURE: &str = include_str!("fixtures/anthropic_messages_tool_use.sse");
+
+#[derive(Clone, Default)]
+struct ApiKeyAuth;
+
+impl AuthProvider for ApiKeyAuth {
+    fn bearer_token(&self) -> Option<String> {
+        Some("sk-ant-test".to_string())
+    }
+}
+
+fn provider(base_url: &str, headers: HeaderMap) -> Provider {
+    Provider {
+        name: "gateway".to_string(),
+        base_url: base_url.to_string(),
+        query_params: None,
+        headers,
+        retry: RetryConfig {
+            max_attempts: 1,
+            base_delay: Duration::from_millis(1),
+            retry_429: false,
+            retry_5xx: false,
+            retry_transport: false,
+        },
+        stream_idle_timeout: Duration::from_secs(5),
+    }
+}
+
+fn request() -> ResponsesApiRequest {
+    ResponsesApiRequest {
+        model: "claude-sonnet-4-5".to_string(),
+        instructions: "You are a coding agent.".to_string(),
+        previous_response_id: None,
+        input: vec![ResponseItem::Message {
+            id: None,
+            role: "user".to_string(),
+            content: vec![ContentItem::InputText {
+                text: "list the repo".to_string(),
+            }],
+            end_turn: None,
+            phase: None,
+        }],
+        tools: vec![json!({
+            "type": "function",
+            "name": "shell",
+            "description": "Runs a shell command",
+            "strict": false,
+            "parameters": {
+                "type": "object",
+                "properties": {"command": {"type": "array", "items": {"type": "string"}}},
+                "required": ["command"],
+            },
+        })],
+        tool_choice: "auto".to_string(),
+        parallel_tool_calls: true,
+        reasoning: Some(Reasoning {
+            effort: Some(ReasoningEffort::Medium),
+            summary: None,
+        }),
+        store: false,
+        stream: true,
+        include: Vec::new(),
+        service_tier: None,
+        prompt_cache_key: None,
+        text: None,
+        max_output_tokens: None,
+    }
+}
+
+async fn stream_fixture(
+    server: &MockServer,
+    provider_headers: HeaderMap,
+) -> Result<Vec<ResponseEvent>> {
+    let transport = ReqwestTransport::new(reqwest::Client::new());
+    let client = AnthropicMessagesClient::new(
+        transport,
+        provider(&format!("{}/v1", server.uri()), provider_headers),
+        ApiKeyAuth,
+    );
+    let mut stream = client
+        .stream_request(request(), ResponsesOptions::default())
+        .await?;
+    let mut events = Vec::new();
+    while let Some(event) = stream.next().await {
+        events.push(event?);
+    }
+    Ok(events)
+}
+
+#[tokio::test]
+async fn fixture_stream_yields_reasoning_text_and_tool_call() -> Result<()> {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/messages"))
+        .and(header("x-api-key", "sk-ant-test"))
+        .and(header("anthropic-version", "2023-06-01"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_raw(TOOL_USE_FIXTURE, "text/event-stream"),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let events = stream_fixture(&server, HeaderMap::new()).await?;
+
+    let text_deltas = events
+        .iter()
+        .filter_map(|event| match event {
+            ResponseEvent::OutputTextDelta(delta) => Some(delta.as_str()),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(text_deltas, vec!["Listing the ", "repository."]);
+
+    let done_items = events
+        .iter()
+        .filter_map(|event| match event {
+            ResponseEvent::OutputItemDone(item) => Some(item.clone()),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        done_items,
+        vec![
+            ResponseItem::Reasoning {
+                id: String::new(),
+                summary: Vec::new(),
+                content: Some(vec![ReasoningItemContent::ReasoningText {
+                    text: "The user wants a file listing.".to_string(),
+                }]),
+                encrypted_content: Some("EqQBCgIYAhIM".to_string()),
+            },
+            ResponseItem::Message {
+                id: None,
+                role: "assistant".to_string(),
+                content: vec![ContentItem::OutputText {
+                    text: "Listing the repository.".to_string(),
+                }],
+                end_turn: None,
+                phase: None,
+            },
+            ResponseItem::FunctionCall {
+                id: None,
+                name: "shell".to_string(),
+                namespace: None,
+                arguments: r#"{"command": ["ls"]}"#.to_string(),
+                call_id: "toolu_01".to_string(),
+            },
+        ]
+    );
+
+    let Some(ResponseEvent::Completed {
+        response_id,
+        token_usage,
+    }) = events.last()
+    else {
+        panic!("expected completed event, got {events:?}");
+    };
+    assert_eq!(response_id, "msg_01");
+    assert_eq!(
+        token_usage,
+        &Some(TokenUsage {
+            input_tokens: 2060,
+            cached_input_tokens: 2048,
+            output_tokens: 87,
+            reasoning_output_tokens: 0,
+            total_tokens: 2147,
+        })
+    );
+
+    let received = server.received_requests().await.unwrap_or_default();
+    assert_eq!(received[0].headers.get("authorization"), None);
+    let body: Value = received[0].body_json()?;
+    assert_eq!(
+        body,
+        json!({
+            "model": "claude-sonnet-4-5",
+            "max_tokens": 32000,
+            "stream": true,
+            "system": [{
+                "type": "text",
+                "text": "You are a coding agent.",
+                "cache_control": {"type": "ephemeral"},
+            }],
+            "tools": [{
+                "name": "shell",
+                "description": "Runs a shell command",
+                "input_schema": {
+                    "type": "object",
+                    "properties": {"command": {"type": "array", "items": {"type": "string"}}},
+                    "required": ["command"],
+                },
+                "cache_control": {"type": "ephemeral"},
+            }],
+            "tool_choice": {"type": "auto", "disable_parallel_tool_use": false},
+            "thinking": {"type": "enabled", "budget_tokens": 10000},
+            "messages": [{
+                "role": "user",
+                "content": [{
+                    "type": "text",
+                    "text": "list the repo",
+                    "cache_control": {"type": "ephemeral"},
+                }],
+            }],
+        })
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn provider_headers_override_anthropic_version() -> Result<()> {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/messages"))
+        .and(header("anthropic-version", "2024-10-22"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_raw(TOOL_USE_FIXTURE, "text/event-stream"),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let mut headers = HeaderMap::new();
+    headers.insert("anthropic-version", HeaderValue::from_static("2024-10-22"));
+    let events = stream_fixture(&server, headers).await?;
+
+    assert!(matches!(
+        events.last(),
+        Some(ResponseEvent::Completed { .. })
+    ));
+    Ok(())
+}
+
+#[tokio::test]
+async fn overloaded_status_is_reported_before_streaming() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/messages"))
+        .respond_with(ResponseTemplate::new(529).set_body_json(json!({
+            "type": "error",
+            "error": {"type": "overloaded_error", "message": "Overloaded"},
+        })))
+        .mount(&server)
+        .await;
+
+    let result = stream_fixture(&server, HeaderMap::new()).await;
+
+    assert!(result.is_err());
+}
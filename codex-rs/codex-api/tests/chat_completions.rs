@@ -0,0 +1,323 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use codex_api::AuthProvider;
+use codex_api::ChatCompletionsClient;
+use codex_api::ResponseEvent;
+use codex_api::ResponsesApiRequest;
+use codex_api::ResponsesOptions;
+use codex_api::provider::Provider;
+use codex_api::provider::RetryConfig;
+use codex_client::ReqwestTransport;
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::protocol::TokenUsage;
+use futures::StreamExt;
+use http::HeaderMap;
+use pretty_assertions::assert_eq;
+use serde_json::Value;
+use serde_json::json;
+use wiremock::Mock;
+use wiremock::MockServer;
+use wiremock::ResponseTemplate;
+use wiremock::matchers::header;
+use wiremock::matchers::method;
+use wiremock::matchers::path;
+
+#[derive(Clone, Default)]
+struct DummyAuth;
+
+impl AuthProvider for DummyAuth {
+    fn bearer_token(&self) -> Option<String> {
+        Some("local-key".to_string())
+    }
+}
+
+fn provider(base_url: &str) -> Provider {
+    Provider {
+        name: "vllm".to_string(),
+        base_url: base_url.to_string(),
+        query_params: None,
+        headers: HeaderMap::new(),
+        retry: RetryConfig {
+            max_attempts: 1,
+            base_delay: Duration::from_millis(1),
+            retry_429: false,
+            retry_5xx: false,
+            retry_transport: false,
+        },
+        stream_idle_timeout: Duration::from_secs(5),
+    }
+}
+
+fn request() -> ResponsesApiRequest {
+    ResponsesApiRequest {
+        model: "qwen3-coder".to_string(),
+        instructions: "You are a coding agent.".to_string(),
+        previous_response_id: None,
+        input: vec![ResponseItem::Message {
+            id: None,
+            role: "user".to_string(),
+            content: vec![ContentItem::InputText {
+                text: "what is in this repo?".to_string(),
+            }],
+            end_turn: None,
+            phase: None,
+        }],
+        tools: vec![json!({
+            "type": "function",
+            "name": "shell",
+            "description": "Runs a shell command",
+            "strict": false,
+            "parameters": {
+                "type": "object",
+                "properties": {"command": {"type": "array", "items": {"type": "string"}}},
+                "required": ["command"],
+            },
+        })],
+        tool_choice: "auto".to_string(),
+        parallel_tool_calls: false,
+        reasoning: None,
+        store: false,
+        stream: true,
+        include: Vec::new(),
+        service_tier: None,
+        prompt_cache_key: None,
+        text: None,
+        max_output_tokens: None,
+    }
+}
+
+fn sse(chunks: &[Value]) -> String {
+    let mut body = String::new();
+    for chunk in chunks {
+        body.push_str(&format!("data: {chunk}\n\n"));
+    }
+    body.push_str("data: [DONE]\n\n");
+    body
+}
+
+async fn mount_sse(server: &MockServer, body: String) {
+    Mock::given(method("POST"))
+        .and(path("/v1/chat/completions"))
+        .and(header("authorization", "Bearer local-key"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("content-type", "text/event-stream")
+                .set_body_raw(body, "text/event-stream"),
+        )
+        .expect(1)
+        .mount(server)
+        .await;
+}
+
+async fn collect(
+    client: &ChatCompletionsClient<ReqwestTransport, DummyAuth>,
+) -> Result<Vec<ResponseEvent>> {
+    let mut stream = client
+        .stream_request(request(), ResponsesOptions::default())
+        .await?;
+    let mut events = Vec::new();
+    while let Some(event) = stream.next().await {
+        events.push(event?);
+    }
+    Ok(events)
+}
+
+fn client(server: &MockServer) -> ChatCompletionsClient<ReqwestTransport, DummyAuth> {
+    let transport = ReqwestTransport::new(reqwest::Client::new());
+    ChatCompletionsClient::new(
+        transport,
+        provider(&format!("{}/v1", server.uri())),
+        DummyAuth,
+    )
+}
+
+#[tokio::test]
+async fn streams_text_reasoning_and_usage() -> Result<()> {
+    let server = MockServer::start().await;
+    mount_sse(
+        &server,
+        sse(&[
+            json!({"id": "chatcmpl-1", "choices": [{"index": 0, "delta": {"role": "assistant", "reasoning_content": "Look at "}}]}),
+            json!({"id": "chatcmpl-1", "choices": [{"index": 0, "delta": {"reasoning_content": "the files."}}]}),
+            json!({"id": "chatcmpl-1", "choices": [{"index": 0, "delta": {"content": "A Rust "}}]}),
+            json!({"id": "chatcmpl-1", "choices": [{"index": 0, "delta": {"content": "workspace."}, "finish_reason": "stop"}]}),
+            json!({"id": "chatcmpl-1", "choices": [], "usage": {
+                "prompt_tokens": 120,
+                "completion_tokens": 30,
+                "total_tokens": 150,
+                "prompt_tokens_details": {"cached_tokens": 100},
+                "completion_tokens_details": {"reasoning_tokens": 12},
+            }}),
+        ]),
+    )
+    .await;
+
+    let events = collect(&client(&server)).await?;
+
+    let text_deltas = events
+        .iter()
+        .filter_map(|event| match event {
+            ResponseEvent::OutputTextDelta(delta) => Some(delta.as_str()),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(text_deltas, vec!["A Rust ", "workspace."]);
+
+    let reasoning_deltas = events
+        .iter()
+        .filter_map(|event| match event {
+            ResponseEvent::ReasoningContentDelta { delta, .. } => Some(delta.as_str()),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(reasoning_deltas, vec!["Look at ", "the files."]);
+
+    let done_items = events
+        .iter()
+        .filter_map(|event| match event {
+            ResponseEvent::OutputItemDone(item) => Some(item.clone()),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(done_items.len(), 2);
+    assert!(matches!(done_items[0], ResponseItem::Reasoning { .. }));
+    assert_eq!(
+        done_items[1],
+        ResponseItem::Message {
+            id: None,
+            role: "assistant".to_string(),
+            content: vec![ContentItem::OutputText {
+                text: "A Rust workspace.".to_string(),
+            }],
+            end_turn: None,
+            phase: None,
+        }
+    );
+
+    let Some(ResponseEvent::Completed {
+        response_id,
+        token_usage,
+    }) = events.last()
+    else {
+        panic!("expected completed event, got {events:?}");
+    };
+    assert_eq!(response_id, "chatcmpl-1");
+    assert_eq!(
+        token_usage,
+        &Some(TokenUsage {
+            input_tokens: 120,
+            cached_input_tokens: 100,
+            output_tokens: 30,
+            reasoning_output_tokens: 12,
+            total_tokens: 150,
+        })
+    );
+
+    let received = server.received_requests().await.unwrap_or_default();
+    let body: Value = received[0].body_json()?;
+    assert_eq!(
+        body,
+        json!({
+            "model": "qwen3-coder",
+            "messages": [
+                {"role": "system", "content": "You are a coding agent."},
+                {"role": "user", "content": "what is in this repo?"},
+            ],
+            "stream": true,
+            "stream_options": {"include_usage": true},
+            "tools": [{
+                "type": "function",
+                "function": {
+                    "name": "shell",
+                    "description": "Runs a shell command",
+                    "strict": false,
+                    "parameters": {
+                        "type": "object",
+                        "properties": {"command": {"type": "array", "items": {"type": "string"}}},
+                        "required": ["command"],
+                    },
+                },
+            }],
+            "tool_choice": "auto",
+            "parallel_tool_calls": false,
+        })
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn assembles_streamed_tool_call_deltas() -> Result<()> {
+    let server = MockServer::start().await;
+    mount_sse(
+        &server,
+        sse(&[
+            json!({"id": "chatcmpl-2", "choices": [{"index": 0, "delta": {"tool_calls": [
+                {"index": 0, "id": "call_a", "type": "function", "function": {"name": "shell", "arguments": ""}},
+            ]}}]}),
+            json!({"id": "chatcmpl-2", "choices": [{"index": 0, "delta": {"tool_calls": [
+                {"index": 0, "function": {"arguments": "{\"command\":"}},
+            ]}}]}),
+            json!({"id": "chatcmpl-2", "choices": [{"index": 0, "delta": {"tool_calls": [
+                {"index": 0, "function": {"arguments": "[\"ls\"]}"}},
+                {"index": 1, "id": "call_b", "type": "function", "function": {"name": "shell", "arguments": "{\"command\":[\"pwd\"]}"}},
+            ]}}]}),
+            json!({"id": "chatcmpl-2", "choices": [{"index": 0, "delta": {}, "finish_reason": "tool_calls"}]}),
+        ]),
+    )
+    .await;
+
+    let events = collect(&client(&server)).await?;
+
+    let done_items = events
+        .iter()
+        .filter_map(|event| match event {
+            ResponseEvent::OutputItemDone(item) => Some(item.clone()),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        done_items,
+        vec![
+            ResponseItem::FunctionCall {
+                id: None,
+                name: "shell".to_string(),
+                namespace: None,
+                arguments: r#"{"command":["ls"]}"#.to_string(),
+                call_id: "call_a".to_string(),
+            },
+            ResponseItem::FunctionCall {
+                id: None,
+                name: "shell".to_string(),
+                namespace: None,
+                arguments: r#"{"command":["pwd"]}"#.to_string(),
+                call_id: "call_b".to_string(),
+            },
+        ]
+    );
+    assert!(matches!(
+        events.last(),
+        Some(ResponseEvent::Completed {
+            token_usage: None,
+            ..
+        })
+    ));
+    Ok(())
+}
+
+#[tokio::test]
+async fn http_errors_surface_before_streaming() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/chat/completions"))
+        .respond_with(ResponseTemplate::new(400).set_body_string("model not found"))
+        .mount(&server)
+        .await;
+
+    let result = client(&server)
+        .stream_request(request(), ResponsesOptions::default())
+        .await;
+
+    assert!(result.is_err());
+}
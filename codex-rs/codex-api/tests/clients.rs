@@ -269,6 +269,7 @@ async fn streaming_client_retries_on_transport_error() -> Result<()> {
         service_tier: None,
         prompt_cache_key: None,
         text: None,
+        max_output_tokens: None,
     };
     let client = ResponsesClient::new(transport.clone(), provider, NoAuth);
 
@@ -312,6 +313,7 @@ async fn azure_default_store_attaches_ids_and_headers() -> Result<()> {
         service_tier: None,
         prompt_cache_key: None,
         text: None,
+        max_output_tokens: None,
     };
 
     let mut extra_headers = HeaderMap::new();
@@ -3,10 +3,12 @@ use crate::agent::guards::Guards;
 use crate::agent::role::DEFAULT_ROLE_NAME;
 use crate::agent::role::resolve_role_config;
 use crate::agent::status::is_final;
+use crate::config::types::RunBudgetConfig;
 use crate::error::CodexErr;
 use crate::error::Result as CodexResult;
 use crate::find_thread_path_by_id_str;
 use crate::rollout::RolloutRecorder;
+use crate::run_budget::RunBudget;
 use crate::session_prefix::format_subagent_context_line;
 use crate::session_prefix::format_subagent_notification_message;
 use crate::shell_snapshot::ShellSnapshot;
@@ -83,6 +85,11 @@ impl AgentControl {
         }
     }
 
+    /// The run budget shared by every thread of this user session.
+    pub(crate) fn run_budget(&self, limits: RunBudgetConfig) -> Arc<RunBudget> {
+        self.state.run_budget(limits)
+    }
+
     /// Spawn a new agent thread and submit the initial prompt.
     pub(crate) async fn spawn_agent(
         &self,
@@ -1,5 +1,7 @@
+use crate::config::types::RunBudgetConfig;
 use crate::error::CodexErr;
 use crate::error::Result;
+use crate::run_budget::RunBudget;
 use codex_protocol::ThreadId;
 use codex_protocol::protocol::SessionSource;
 use codex_protocol::protocol::SubAgentSource;
@@ -8,12 +10,14 @@ use std::collections::HashMap;
 use std::collections::HashSet;
 use std::sync::Arc;
 use std::sync::Mutex;
+use std::sync::OnceLock;
 use std::sync::atomic::AtomicUsize;
 use std::sync::atomic::Ordering;
 
 /// This structure is used to add some limits on the multi-agent capabilities for Codex. In
 /// the current implementation, it limits:
 /// * Total number of sub-agents (i.e. threads) per user session
+/// * The `[run_budget]` of the whole session, which every thread draws from
 ///
 /// This structure is shared by all agents in the same user session (because the `AgentControl`
 /// is).
@@ -21,6 +25,7 @@ use std::sync::atomic::Ordering;
 pub(crate) struct Guards {
     active_agents: Mutex<ActiveAgents>,
     total_count: AtomicUsize,
+    run_budget: OnceLock<Arc<RunBudget>>,
 }
 
 #[derive(Default)]
@@ -67,6 +72,15 @@ pub(crate) fn exceeds_thread_spawn_depth_limit(depth: i32, max_depth: i32) -> bo
 }
 
 impl Guards {
+    /// The session's run budget. The first thread to ask, i.e. the root
+    /// thread, sets the limits that its sub-agents share.
+    pub(crate) fn run_budget(&self, limits: RunBudgetConfig) -> Arc<RunBudget> {
+        Arc::clone(
+            self.run_budget
+                .get_or_init(|| Arc::new(RunBudget::new(limits))),
+        )
+    }
+
     pub(crate) fn reserve_spawn_slot(
         self: &Arc<Self>,
         max_threads: Option<usize>,
@@ -0,0 +1,746 @@
+//! Long-running processes (databases, dev servers, watchers) that belong to a
+//! thread rather than to a turn.
+//!
+//! Services are declared under `[services.<name>]` in config or started by the
+//! model with `service_start`. Each running service has a supervisor task that
+//! keeps a bounded tail of its output, runs its health check and restarts it
+//! according to its restart policy. The per-thread sets live in a
+//! [`BackgroundServiceRegistry`] owned by the thread manager, so a resumed
+//! thread finds the services it left running. Services are tracked apart from
+//! `exec_command` processes and do not count against
+//! `MAX_UNIFIED_EXEC_PROCESSES`.
+
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::collections::VecDeque;
+use std::net::Ipv4Addr;
+use std::net::SocketAddr;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::sync::Arc;
+use std::sync::Mutex as StdMutex;
+use std::sync::MutexGuard;
+use std::sync::PoisonError;
+use std::time::Duration;
+
+use codex_protocol::ThreadId;
+use codex_protocol::config_types::WindowsSandboxLevel;
+use codex_protocol::permissions::FileSystemSandboxPolicy;
+use codex_protocol::permissions::NetworkSandboxPolicy;
+use codex_protocol::permissions::ResourceLimits;
+use codex_protocol::protocol::BackgroundServiceAction;
+use codex_protocol::protocol::BackgroundServiceHealth;
+use codex_protocol::protocol::BackgroundServiceSource;
+use codex_protocol::protocol::BackgroundServiceStatus;
+use codex_protocol::protocol::BackgroundServiceSummary;
+use codex_protocol::protocol::SandboxPolicy;
+use codex_utils_pty::SpawnedProcess;
+use codex_utils_pty::combine_output_receivers;
+use codex_utils_pty::spawn_pipe_process_no_stdin;
+use tokio::sync::broadcast;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::oneshot;
+use tokio::sync::watch;
+use tokio::time::Instant;
+use tokio_util::sync::CancellationToken;
+
+use crate::config::types::ServiceConfig;
+use crate::config::types::ServiceHealthCheck;
+use crate::config::types::ServiceHealthProbe;
+use crate::config::types::ServiceRestartPolicy;
+use crate::exec::ExecExpiration;
+use crate::sandboxing::CommandSpec;
+use crate::sandboxing::ExecRequest;
+use crate::sandboxing::SandboxManager;
+use crate::sandboxing::SandboxPermissions;
+use crate::sandboxing::SandboxTransformError;
+use crate::sandboxing::SandboxTransformRequest;
+use crate::tools::sandboxing::SandboxablePreference;
+
+const MAX_LOG_LINES: usize = 1_000;
+const MAX_LOG_LINE_BYTES: usize = 4 * 1024;
+/// Log lines included in [`BackgroundServiceSummary::recent_logs`].
+const SUMMARY_LOG_LINES: usize = 20;
+const MIN_RESTART_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_RESTART_BACKOFF: Duration = Duration::from_secs(30);
+/// Runs shorter than this count towards [`MAX_QUICK_RESTARTS`].
+const STABLE_RUN: Duration = Duration::from_secs(30);
+/// Consecutive quick exits after which a crash-looping service is left exited.
+const MAX_QUICK_RESTARTS: u32 = 5;
+const HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum BackgroundServiceError {
+    #[error("no background service named `{0}`")]
+    UnknownService(String),
+    #[error("background service `{0}` is already running")]
+    AlreadyRunning(String),
+    #[error("background service `{0}` is not running")]
+    NotRunning(String),
+}
+
+/// What to run for a service and how to supervise it.
+#[derive(Debug, Clone)]
+pub(crate) struct ServiceSpec {
+    /// Command as declared; `program` and `args` may wrap it in a sandbox.
+    pub(crate) command: Vec<String>,
+    pub(crate) program: String,
+    pub(crate) args: Vec<String>,
+    pub(crate) cwd: PathBuf,
+    pub(crate) env: HashMap<String, String>,
+    pub(crate) arg0: Option<String>,
+    pub(crate) restart: ServiceRestartPolicy,
+    pub(crate) health_check: Option<ServiceHealthCheck>,
+    /// `arg0` for a health check command that the sandbox wrapped.
+    pub(crate) health_check_arg0: Option<String>,
+}
+
+/// The session's sandbox, which `[services.<name>]` tables run under just like
+/// the commands the model runs.
+pub(crate) struct ServiceSandbox<'a> {
+    pub(crate) policy: &'a SandboxPolicy,
+    pub(crate) file_system_policy: &'a FileSystemSandboxPolicy,
+    pub(crate) network_policy: NetworkSandboxPolicy,
+    pub(crate) enforce_managed_network: bool,
+    pub(crate) codex_linux_sandbox_exe: Option<&'a PathBuf>,
+    pub(crate) use_legacy_landlock: bool,
+    pub(crate) resource_limits: ResourceLimits,
+    pub(crate) windows_sandbox_level: WindowsSandboxLevel,
+}
+
+impl ServiceSandbox<'_> {
+    fn wrap(
+        &self,
+        command: &[String],
+        cwd: &Path,
+        env: HashMap<String, String>,
+        sandbox_policy_cwd: &Path,
+    ) -> Result<ExecRequest, SandboxTransformError> {
+        let manager = SandboxManager::new();
+        let sandbox = manager.select_initial(
+            self.file_system_policy,
+            self.network_policy,
+            SandboxablePreference::Auto,
+            self.windows_sandbox_level,
+            self.enforce_managed_network,
+        );
+        manager.transform(SandboxTransformRequest {
+            spec: CommandSpec {
+                program: command.first().cloned().unwrap_or_default(),
+                args: command.iter().skip(1).cloned().collect(),
+                cwd: cwd.to_path_buf(),
+                env,
+                expiration: ExecExpiration::DefaultTimeout,
+                sandbox_permissions: SandboxPermissions::UseDefault,
+                additional_permissions: None,
+                justification: None,
+            },
+            policy: self.policy,
+            file_system_policy: self.file_system_policy,
+            network_policy: self.network_policy,
+            sandbox,
+            enforce_managed_network: self.enforce_managed_network,
+            network: None,
+            sandbox_policy_cwd,
+            #[cfg(target_os = "macos")]
+            macos_seatbelt_profile_extensions: None,
+            codex_linux_sandbox_exe: self.codex_linux_sandbox_exe,
+            use_legacy_landlock: self.use_legacy_landlock,
+            resource_limits: self.resource_limits,
+            overlay_workspace: None,
+            glob_deny_matches: None,
+            windows_sandbox_level: self.windows_sandbox_level,
+        })
+    }
+}
+
+impl ServiceSpec {
+    /// Builds the spec for a `[services.<name>]` table. The service and its
+    /// health check command run in the session's sandbox.
+    pub(crate) fn from_config(
+        config: &ServiceConfig,
+        session_cwd: &Path,
+        mut env: HashMap<String, String>,
+        sandbox: &ServiceSandbox<'_>,
+    ) -> Result<Self, SandboxTransformError> {
+        env.extend(config.env.clone());
+        let cwd = match &config.cwd {
+            Some(cwd) => session_cwd.join(cwd),
+            None => session_cwd.to_path_buf(),
+        };
+        let mut health_check = config.health_check.clone();
+        let mut health_check_arg0 = None;
+        if let Some(ServiceHealthCheck {
+            probe: ServiceHealthProbe::Command(command),
+            ..
+        }) = health_check.as_mut()
+        {
+            let request = sandbox.wrap(command, &cwd, env.clone(), session_cwd)?;
+            *command = request.command;
+            health_check_arg0 = request.arg0;
+        }
+        let request = sandbox.wrap(&config.command, &cwd, env, session_cwd)?;
+        let mut spec = Self::from_exec_request(
+            config.command.clone(),
+            request,
+            config.restart,
+            health_check,
+        );
+        spec.health_check_arg0 = health_check_arg0;
+        Ok(spec)
+    }
+
+    /// Builds the spec for a service started by the model from its approved,
+    /// sandbox-wrapped request.
+    pub(crate) fn from_exec_request(
+        command: Vec<String>,
+        request: ExecRequest,
+        restart: ServiceRestartPolicy,
+        health_check: Option<ServiceHealthCheck>,
+    ) -> Self {
+        let mut argv = request.command.into_iter();
+        Self {
+            command,
+            program: argv.next().unwrap_or_default(),
+            args: argv.collect(),
+            cwd: request.cwd,
+            env: request.env,
+            arg0: request.arg0,
+            restart,
+            health_check,
+            health_check_arg0: None,
+        }
+    }
+}
+
+/// Service names end up in tool calls and slash commands, so keep them simple.
+pub(crate) fn is_valid_service_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// Background services for every thread, kept for as long as the thread
+/// manager lives so they survive a thread being unloaded and resumed.
+#[derive(Default)]
+pub(crate) struct BackgroundServiceRegistry {
+    threads: StdMutex<HashMap<ThreadId, Arc<BackgroundServices>>>,
+}
+
+impl BackgroundServiceRegistry {
+    pub(crate) fn for_thread(&self, thread_id: ThreadId) -> Arc<BackgroundServices> {
+        Arc::clone(lock(&self.threads).entry(thread_id).or_default())
+    }
+
+    pub(crate) fn get(&self, thread_id: ThreadId) -> Option<Arc<BackgroundServices>> {
+        lock(&self.threads).get(&thread_id).cloned()
+    }
+
+    /// Stops and forgets every service of `thread_id`.
+    pub(crate) fn remove_thread(&self, thread_id: ThreadId) {
+        let services = lock(&self.threads).remove(&thread_id);
+        if let Some(services) = services {
+            services.stop_all();
+        }
+    }
+}
+
+/// The services of a single thread.
+pub(crate) struct BackgroundServices {
+    services: StdMutex<BTreeMap<String, ServiceEntry>>,
+    updates: watch::Sender<()>,
+}
+
+struct ServiceEntry {
+    spec: Arc<ServiceSpec>,
+    source: BackgroundServiceSource,
+    state: Arc<StdMutex<ServiceState>>,
+    cancel: CancellationToken,
+}
+
+struct ServiceState {
+    status: BackgroundServiceStatus,
+    health: Option<BackgroundServiceHealth>,
+    restarts: u32,
+    exit_code: Option<i32>,
+    logs: VecDeque<String>,
+    partial_line: Vec<u8>,
+}
+
+impl Default for BackgroundServices {
+    fn default() -> Self {
+        let (updates, _) = watch::channel(());
+        Self {
+            services: StdMutex::new(BTreeMap::new()),
+            updates,
+        }
+    }
+}
+
+impl BackgroundServices {
+    /// Notifies whenever a service is added or changes status or health.
+    pub(crate) fn subscribe(&self) -> watch::Receiver<()> {
+        self.updates.subscribe()
+    }
+
+    /// Adds `name` unless a service with that name already exists, e.g. because
+    /// the thread was resumed. Returns whether the service was added.
+    pub(crate) fn define_if_absent(
+        &self,
+        name: &str,
+        spec: ServiceSpec,
+        source: BackgroundServiceSource,
+    ) -> bool {
+        let mut services = lock(&self.services);
+        if services.contains_key(name) {
+            return false;
+        }
+        services.insert(name.to_string(), ServiceEntry::new(spec, source));
+        drop(services);
+        self.updates.send_replace(());
+        true
+    }
+
+    /// Adds `name`, replacing a stopped or exited service with the same name.
+    pub(crate) fn define(
+        &self,
+        name: &str,
+        spec: ServiceSpec,
+        source: BackgroundServiceSource,
+    ) -> Result<(), BackgroundServiceError> {
+        let mut services = lock(&self.services);
+        if services.get(name).is_some_and(ServiceEntry::is_active) {
+            return Err(BackgroundServiceError::AlreadyRunning(name.to_string()));
+        }
+        services.insert(name.to_string(), ServiceEntry::new(spec, source));
+        drop(services);
+        self.updates.send_replace(());
+        Ok(())
+    }
+
+    pub(crate) fn is_defined(&self, name: &str) -> bool {
+        lock(&self.services).contains_key(name)
+    }
+
+    pub(crate) fn control(
+        &self,
+        name: &str,
+        action: BackgroundServiceAction,
+    ) -> Result<(), BackgroundServiceError> {
+        match action {
+            BackgroundServiceAction::Start => self.start(name),
+            BackgroundServiceAction::Stop => self.stop(name),
+            BackgroundServiceAction::Restart => {
+                match self.stop(name) {
+                    Ok(()) | Err(BackgroundServiceError::NotRunning(_)) => {}
+                    Err(err) => return Err(err),
+                }
+                self.start(name)
+            }
+        }
+    }
+
+    pub(crate) fn start(&self, name: &str) -> Result<(), BackgroundServiceError> {
+        let mut services = lock(&self.services);
+        let entry = services
+            .get_mut(name)
+            .ok_or_else(|| BackgroundServiceError::UnknownService(name.to_string()))?;
+        if entry.is_active() {
+            return Err(BackgroundServiceError::AlreadyRunning(name.to_string()));
+        }
+        entry.cancel = CancellationToken::new();
+        {
+            let mut state = lock(&entry.state);
+            state.status = BackgroundServiceStatus::Running;
+            state.health = entry
+                .spec
+                .health_check
+                .as_ref()
+                .map(|_| BackgroundServiceHealth::Pending);
+            state.restarts = 0;
+            state.exit_code = None;
+        }
+        let supervisor = Supervisor {
+            spec: Arc::clone(&entry.spec),
+            state: Arc::clone(&entry.state),
+            updates: self.updates.clone(),
+            cancel: entry.cancel.clone(),
+        };
+        drop(services);
+        self.updates.send_replace(());
+        tokio::spawn(supervisor.run());
+        Ok(())
+    }
+
+    pub(crate) fn stop(&self, name: &str) -> Result<(), BackgroundServiceError> {
+        let services = lock(&self.services);
+        let entry = services
+            .get(name)
+            .ok_or_else(|| BackgroundServiceError::UnknownService(name.to_string()))?;
+        if !entry.is_active() {
+            return Err(BackgroundServiceError::NotRunning(name.to_string()));
+        }
+        entry.stop();
+        drop(services);
+        self.updates.send_replace(());
+        Ok(())
+    }
+
+    pub(crate) fn stop_all(&self) {
+        let services = lock(&self.services);
+        let mut stopped_any = false;
+        for entry in services.values().filter(|entry| entry.is_active()) {
+            entry.stop();
+            stopped_any = true;
+        }
+        drop(services);
+        if stopped_any {
+            self.updates.send_replace(());
+        }
+    }
+
+    pub(crate) fn summaries(&self) -> Vec<BackgroundServiceSummary> {
+        lock(&self.services)
+            .iter()
+            .map(|(name, entry)| entry.summary(name))
+            .collect()
+    }
+
+    /// Returns the summary of `name` with up to `max_log_lines` log lines.
+    pub(crate) fn summary(
+        &self,
+        name: &str,
+        max_log_lines: usize,
+    ) -> Result<BackgroundServiceSummary, BackgroundServiceError> {
+        let services = lock(&self.services);
+        let entry = services
+            .get(name)
+            .ok_or_else(|| BackgroundServiceError::UnknownService(name.to_string()))?;
+        let mut summary = entry.summary(name);
+        summary.recent_logs = lock(&entry.state).recent_logs(max_log_lines);
+        Ok(summary)
+    }
+}
+
+impl Drop for BackgroundServices {
+    fn drop(&mut self) {
+        for entry in lock(&self.services).values() {
+            entry.cancel.cancel();
+        }
+    }
+}
+
+impl ServiceEntry {
+    fn new(spec: ServiceSpec, source: BackgroundServiceSource) -> Self {
+        let cancel = CancellationToken::new();
+        cancel.cancel();
+        Self {
+            spec: Arc::new(spec),
+            source,
+            state: Arc::new(StdMutex::new(ServiceState {
+                status: BackgroundServiceStatus::Stopped,
+                health: None,
+                restarts: 0,
+                exit_code: None,
+                logs: VecDeque::new(),
+                partial_line: Vec::new(),
+            })),
+            cancel,
+        }
+    }
+
+    fn is_active(&self) -> bool {
+        matches!(
+            lock(&self.state).status,
+            BackgroundServiceStatus::Running | BackgroundServiceStatus::Restarting
+        )
+    }
+
+    /// Cancels the supervisor. The state lock is held while cancelling so the
+    /// supervisor cannot overwrite `Stopped` afterwards.
+    fn stop(&self) {
+        let mut state = lock(&self.state);
+        self.cancel.cancel();
+        state.status = BackgroundServiceStatus::Stopped;
+        state.health = None;
+    }
+
+    fn summary(&self, name: &str) -> BackgroundServiceSummary {
+        let state = lock(&self.state);
+        BackgroundServiceSummary {
+            name: name.to_string(),
+            command: self.spec.command.clone(),
+            source: self.source,
+            status: state.status,
+            health: state.health,
+            restarts: state.restarts,
+            exit_code: state.exit_code,
+            recent_logs: state.recent_logs(SUMMARY_LOG_LINES),
+        }
+    }
+}
+
+impl ServiceState {
+    fn push_output(&mut self, chunk: &[u8]) {
+        for byte in chunk {
+            if *byte == b'\n' {
+                self.flush_partial_line();
+            } else {
+                self.partial_line.push(*byte);
+                if self.partial_line.len() >= MAX_LOG_LINE_BYTES {
+                    self.flush_partial_line();
+                }
+            }
+        }
+    }
+
+    fn flush_partial_line(&mut self) {
+        if self.partial_line.is_empty() {
+            return;
+        }
+        let line = String::from_utf8_lossy(&self.partial_line)
+            .trim_end_matches('\r')
+            .to_string();
+        self.partial_line.clear();
+        self.push_line(line);
+    }
+
+    fn push_line(&mut self, line: String) {
+        if self.logs.len() == MAX_LOG_LINES {
+            self.logs.pop_front();
+        }
+        self.logs.push_back(line);
+    }
+
+    fn recent_logs(&self, max_lines: usize) -> Vec<String> {
+        let skip = self.logs.len().saturating_sub(max_lines);
+        self.logs.iter().skip(skip).cloned().collect()
+    }
+}
+
+#[derive(Clone)]
+struct Supervisor {
+    spec: Arc<ServiceSpec>,
+    state: Arc<StdMutex<ServiceState>>,
+    updates: watch::Sender<()>,
+    cancel: CancellationToken,
+}
+
+impl Supervisor {
+    async fn run(self) {
+        let mut backoff = MIN_RESTART_BACKOFF;
+        let mut quick_exits = 0;
+        let mut first_run = true;
+        loop {
+            let started_at = Instant::now();
+            let spawned = spawn_pipe_process_no_stdin(
+                &self.spec.program,
+                &self.spec.args,
+                &self.spec.cwd,
+                &self.spec.env,
+                &self.spec.arg0,
+            )
+            .await;
+            let SpawnedProcess {
+                session: process,
+                stdout_rx,
+                stderr_rx,
+                exit_rx,
+            } = match spawned {
+                Ok(spawned) => spawned,
+                Err(err) => {
+                    self.update(|state| {
+                        state.push_line(format!("[codex] failed to start: {err}"));
+                        state.status = BackgroundServiceStatus::Exited;
+                        state.health = None;
+                        true
+                    });
+                    return;
+                }
+            };
+            let restarted = !first_run;
+            first_run = false;
+            let health_check = self.spec.health_check.clone();
+            let running = self.update(|state| {
+                if restarted {
+                    state.restarts += 1;
+                }
+                state.status = BackgroundServiceStatus::Running;
+                state.health = health_check
+                    .as_ref()
+                    .map(|_| BackgroundServiceHealth::Pending);
+                state.exit_code = None;
+                restarted
+            });
+            if !running {
+                process.terminate();
+                return;
+            }
+
+            let health_cancel = self.cancel.child_token();
+            if let Some(check) = health_check {
+                tokio::spawn(self.clone().check_health(check, health_cancel.clone()));
+            }
+            let output = combine_output_receivers(stdout_rx, stderr_rx);
+            let exit_code = self.wait_for_exit(output, exit_rx).await;
+            health_cancel.cancel();
+            let Some(exit_code) = exit_code else {
+                process.terminate();
+                return;
+            };
+            drop(process);
+
+            let should_restart = match self.spec.restart {
+                ServiceRestartPolicy::Always => true,
+                ServiceRestartPolicy::OnFailure => exit_code != 0,
+                ServiceRestartPolicy::Never => false,
+            };
+            if started_at.elapsed() >= STABLE_RUN {
+                quick_exits = 0;
+                backoff = MIN_RESTART_BACKOFF;
+            } else {
+                quick_exits += 1;
+            }
+            let give_up = should_restart && quick_exits > MAX_QUICK_RESTARTS;
+            let restarting = should_restart && !give_up;
+            let still_supervised = self.update(|state| {
+                state.flush_partial_line();
+                state.push_line(format!("[codex] exited with code {exit_code}"));
+                if give_up {
+                    state.push_line(format!(
+                        "[codex] exited {quick_exits} times within {}s of starting; not restarting",
+                        STABLE_RUN.as_secs()
+                    ));
+                }
+                state.status = if restarting {
+                    BackgroundServiceStatus::Restarting
+                } else {
+                    BackgroundServiceStatus::Exited
+                };
+                state.health = None;
+                state.exit_code = Some(exit_code);
+                true
+            });
+            if !still_supervised || !restarting {
+                return;
+            }
+
+            tokio::select! {
+                _ = self.cancel.cancelled() => return,
+                _ = tokio::time::sleep(backoff) => {}
+            }
+            backoff = (backoff * 2).min(MAX_RESTART_BACKOFF);
+        }
+    }
+
+    /// Collects output until the process exits. Returns `None` when the
+    /// service was stopped first.
+    async fn wait_for_exit(
+        &self,
+        mut output: broadcast::Receiver<Vec<u8>>,
+        mut exit_rx: oneshot::Receiver<i32>,
+    ) -> Option<i32> {
+        let mut output_open = true;
+        loop {
+            tokio::select! {
+                biased;
+                _ = self.cancel.cancelled() => return None,
+                chunk = output.recv(), if output_open => match chunk {
+                    Ok(chunk) => lock(&self.state).push_output(&chunk),
+                    Err(RecvError::Lagged(_)) => {}
+                    Err(RecvError::Closed) => output_open = false,
+                },
+                code = &mut exit_rx => {
+                    let mut state = lock(&self.state);
+                    while let Ok(chunk) = output.try_recv() {
+                        state.push_output(&chunk);
+                    }
+                    return Some(code.unwrap_or(-1));
+                }
+            }
+        }
+    }
+
+    async fn check_health(self, check: ServiceHealthCheck, cancel: CancellationToken) {
+        let mut interval =
+            tokio::time::interval_at(Instant::now() + check.interval, check.interval);
+        loop {
+            tokio::select! {
+                _ = cancel.cancelled() => return,
+                _ = interval.tick() => {}
+            }
+            let health = if probe(&check.probe, &self.spec).await {
+                BackgroundServiceHealth::Healthy
+            } else {
+                BackgroundServiceHealth::Unhealthy
+            };
+            if cancel.is_cancelled() {
+                return;
+            }
+            self.update(|state| state.health.replace(health) != Some(health));
+        }
+    }
+
+    /// Applies `update` unless the service has been stopped, notifying
+    /// subscribers when it returns `true`. Returns whether the service is
+    /// still supervised.
+    fn update(&self, update: impl FnOnce(&mut ServiceState) -> bool) -> bool {
+        let mut state = lock(&self.state);
+        if self.cancel.is_cancelled() {
+            return false;
+        }
+        let changed = update(&mut state);
+        drop(state);
+        if changed {
+            self.updates.send_replace(());
+        }
+        true
+    }
+}
+
+async fn probe(probe: &ServiceHealthProbe, spec: &ServiceSpec) -> bool {
+    match probe {
+        ServiceHealthProbe::Port(port) => {
+            let addr = SocketAddr::from((Ipv4Addr::LOCALHOST, *port));
+            tokio::task::spawn_blocking(move || {
+                std::net::TcpStream::connect_timeout(&addr, HEALTH_CHECK_TIMEOUT).is_ok()
+            })
+            .await
+            .unwrap_or(false)
+        }
+        ServiceHealthProbe::Command(command) => {
+            let Some((program, args)) = command.split_first() else {
+                return false;
+            };
+            let mut command = tokio::process::Command::new(program);
+            #[cfg(unix)]
+            if let Some(arg0) = &spec.health_check_arg0 {
+                command.arg0(arg0);
+            }
+            let status = command
+                .args(args)
+                .current_dir(&spec.cwd)
+                .env_clear()
+                .envs(&spec.env)
+                .stdin(Stdio::null())
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .kill_on_drop(true)
+                .status();
+            matches!(
+                tokio::time::timeout(HEALTH_CHECK_TIMEOUT, status).await,
+                Ok(Ok(status)) if status.success()
+            )
+        }
+    }
+}
+
+fn lock<T>(mutex: &StdMutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+#[cfg(test)]
+#[path = "background_services_tests.rs"]
+mod tests;
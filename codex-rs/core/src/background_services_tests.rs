@@ -0,0 +1,248 @@
+use super::*;
+use pretty_assertions::assert_eq;
+
+fn shell_service(script: &str, restart: ServiceRestartPolicy) -> ServiceSpec {
+    let command = vec!["sh".to_string(), "-c".to_string(), script.to_string()];
+    ServiceSpec {
+        program: command[0].clone(),
+        args: command[1..].to_vec(),
+        command,
+        cwd: std::env::temp_dir(),
+        env: std::env::vars().collect(),
+        arg0: None,
+        restart,
+        health_check: None,
+        health_check_arg0: None,
+    }
+}
+
+async fn wait_for_summary(
+    services: &BackgroundServices,
+    name: &str,
+    predicate: impl Fn(&BackgroundServiceSummary) -> bool,
+) -> BackgroundServiceSummary {
+    let mut updates = services.subscribe();
+    tokio::time::timeout(Duration::from_secs(10), async {
+        loop {
+            let summary = services
+                .summary(name, MAX_LOG_LINES)
+                .expect("service should exist");
+            if predicate(&summary) {
+                return summary;
+            }
+            updates.changed().await.expect("updates channel closed");
+        }
+    })
+    .await
+    .expect("timed out waiting for the service")
+}
+
+#[test]
+fn log_tail_splits_lines_and_keeps_the_most_recent() {
+    let mut state = ServiceEntry::new(
+        shell_service("true", ServiceRestartPolicy::Never),
+        BackgroundServiceSource::Config,
+    )
+    .state;
+    let state = Arc::get_mut(&mut state)
+        .expect("state is not shared")
+        .get_mut()
+        .expect("state is not poisoned");
+
+    state.push_output(b"first\r\nsec");
+    state.push_output(b"ond\nthi");
+    assert_eq!(state.recent_logs(10), vec!["first", "second"]);
+    state.flush_partial_line();
+    assert_eq!(state.recent_logs(2), vec!["second", "thi"]);
+
+    for line in 0..MAX_LOG_LINES {
+        state.push_line(line.to_string());
+    }
+    assert_eq!(state.logs.len(), MAX_LOG_LINES);
+    assert_eq!(state.logs.front().map(String::as_str), Some("0"));
+}
+
+#[tokio::test]
+#[cfg(unix)]
+async fn exited_service_keeps_exit_code_and_logs() {
+    let services = BackgroundServices::default();
+    assert!(services.define_if_absent(
+        "job",
+        shell_service("echo hello; exit 3", ServiceRestartPolicy::Never),
+        BackgroundServiceSource::Model,
+    ));
+    services.start("job").expect("start service");
+
+    let summary = wait_for_summary(&services, "job", |summary| {
+        summary.status == BackgroundServiceStatus::Exited
+    })
+    .await;
+    assert_eq!(summary.exit_code, Some(3));
+    assert_eq!(summary.restarts, 0);
+    assert_eq!(
+        summary.recent_logs,
+        vec![
+            "hello".to_string(),
+            "[codex] exited with code 3".to_string()
+        ]
+    );
+}
+
+#[tokio::test]
+#[cfg(unix)]
+async fn stop_and_start_control_a_running_service() {
+    let services = BackgroundServices::default();
+    services
+        .define(
+            "sleeper",
+            shell_service("sleep 30", ServiceRestartPolicy::OnFailure),
+            BackgroundServiceSource::Config,
+        )
+        .expect("define service");
+
+    assert_eq!(
+        services.stop("sleeper"),
+        Err(BackgroundServiceError::NotRunning("sleeper".to_string()))
+    );
+    services.start("sleeper").expect("start service");
+    assert_eq!(
+        services.start("sleeper"),
+        Err(BackgroundServiceError::AlreadyRunning(
+            "sleeper".to_string()
+        ))
+    );
+    assert_eq!(
+        services.define(
+            "sleeper",
+            shell_service("true", ServiceRestartPolicy::Never),
+            BackgroundServiceSource::Model,
+        ),
+        Err(BackgroundServiceError::AlreadyRunning(
+            "sleeper".to_string()
+        ))
+    );
+
+    services.stop("sleeper").expect("stop service");
+    let summary = services
+        .summary("sleeper", MAX_LOG_LINES)
+        .expect("service should exist");
+    assert_eq!(summary.status, BackgroundServiceStatus::Stopped);
+    assert_eq!(summary.exit_code, None);
+
+    services
+        .control("sleeper", BackgroundServiceAction::Restart)
+        .expect("restart service");
+    assert_eq!(
+        services
+            .summary("sleeper", 0)
+            .expect("service should exist")
+            .status,
+        BackgroundServiceStatus::Running
+    );
+    services.stop_all();
+    assert_eq!(
+        services.control("missing", BackgroundServiceAction::Start),
+        Err(BackgroundServiceError::UnknownService(
+            "missing".to_string()
+        ))
+    );
+}
+
+#[tokio::test]
+#[cfg(unix)]
+async fn always_policy_restarts_a_service_that_exits() {
+    let services = BackgroundServices::default();
+    services
+        .define(
+            "watcher",
+            shell_service("echo tick", ServiceRestartPolicy::Always),
+            BackgroundServiceSource::Config,
+        )
+        .expect("define service");
+    services.start("watcher").expect("start service");
+
+    let summary = wait_for_summary(&services, "watcher", |summary| summary.restarts >= 1).await;
+    assert!(summary.recent_logs.iter().any(|line| line == "tick"));
+    services.stop_all();
+}
+
+#[tokio::test]
+async fn registry_keeps_services_per_thread_until_removed() {
+    let registry = BackgroundServiceRegistry::default();
+    let thread_id = ThreadId::new();
+    registry.for_thread(thread_id).define_if_absent(
+        "db",
+        shell_service("true", ServiceRestartPolicy::Never),
+        BackgroundServiceSource::Config,
+    );
+
+    // A resumed session finds the same services.
+    let resumed = registry.for_thread(thread_id);
+    assert!(resumed.is_defined("db"));
+    assert!(!resumed.define_if_absent(
+        "db",
+        shell_service("false", ServiceRestartPolicy::Never),
+        BackgroundServiceSource::Config,
+    ));
+    assert!(registry.get(ThreadId::new()).is_none());
+
+    registry.remove_thread(thread_id);
+    assert!(registry.get(thread_id).is_none());
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn config_services_run_in_the_session_sandbox() {
+    let cwd = std::env::temp_dir();
+    let service = ServiceConfig {
+        command: vec!["sleep".to_string(), "60".to_string()],
+        cwd: None,
+        env: HashMap::new(),
+        autostart: false,
+        restart: ServiceRestartPolicy::Never,
+        health_check: Some(ServiceHealthCheck {
+            probe: ServiceHealthProbe::Command(vec!["true".to_string()]),
+            interval: Duration::from_secs(10),
+        }),
+    };
+    let policy = SandboxPolicy::new_read_only_policy();
+    let file_system_policy = FileSystemSandboxPolicy::from_legacy_sandbox_policy(&policy, &cwd);
+    let sandbox_exe = PathBuf::from("/usr/local/bin/codex-linux-sandbox");
+    let sandbox = ServiceSandbox {
+        policy: &policy,
+        file_system_policy: &file_system_policy,
+        network_policy: NetworkSandboxPolicy::from(&policy),
+        enforce_managed_network: false,
+        codex_linux_sandbox_exe: Some(&sandbox_exe),
+        use_legacy_landlock: false,
+        resource_limits: ResourceLimits::default(),
+        windows_sandbox_level: WindowsSandboxLevel::Disabled,
+    };
+
+    let spec = ServiceSpec::from_config(&service, &cwd, HashMap::new(), &sandbox)
+        .expect("sandbox the service");
+
+    assert_eq!(spec.command, service.command);
+    assert_eq!(spec.program, sandbox_exe.to_string_lossy());
+    assert!(
+        spec.args
+            .ends_with(&["sleep".to_string(), "60".to_string()])
+    );
+    assert_eq!(spec.arg0.as_deref(), Some("codex-linux-sandbox"));
+    let Some(ServiceHealthCheck {
+        probe: ServiceHealthProbe::Command(health_command),
+        ..
+    }) = &spec.health_check
+    else {
+        panic!("expected a command health check");
+    };
+    assert_eq!(
+        health_command.first().map(String::as_str),
+        Some(sandbox_exe.to_string_lossy().as_ref())
+    );
+    assert_eq!(health_command.last().map(String::as_str), Some("true"));
+    assert_eq!(
+        spec.health_check_arg0.as_deref(),
+        Some("codex-linux-sandbox")
+    );
+}
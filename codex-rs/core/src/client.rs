@@ -34,6 +34,8 @@ use crate::api_bridge::CoreAuthProvider;
 use crate::api_bridge::auth_provider_from_auth;
 use crate::api_bridge::map_api_error;
 use crate::auth::UnauthorizedRecovery;
+use codex_api::AnthropicMessagesClient as ApiAnthropicMessagesClient;
+use codex_api::ChatCompletionsClient as ApiChatCompletionsClient;
 use codex_api::CompactClient as ApiCompactClient;
 use codex_api::CompactionInput as ApiCompactionInput;
 use codex_api::MemoriesClient as ApiMemoriesClient;
@@ -458,7 +460,8 @@ impl ModelClient {
     /// If websockets are only enabled via model preference (no explicit feature flag), prefer the
     /// current v2 behavior.
     pub fn responses_websocket_enabled(&self, model_info: &ModelInfo) -> bool {
-        if !self.state.provider.supports_websockets
+        if self.state.provider.wire_api != WireApi::Responses
+            || !self.state.provider.supports_websockets
             || self.state.disable_websockets.load(Ordering::Relaxed)
         {
             return false;
@@ -629,6 +632,11 @@ impl ModelClientSession {
             },
             prompt_cache_key,
             text,
+            max_output_tokens: prompt.max_output_tokens.or(self
+                .client
+                .state
+                .provider
+                .max_output_tokens),
         };
         Ok(request)
     }
@@ -999,6 +1007,83 @@ impl ModelClientSession {
         }
     }
 
+    /// Streams a turn via a non-Responses HTTP wire API (Chat Completions or
+    /// Anthropic Messages).
+    ///
+    /// The request is built exactly like a Responses request and translated
+    /// by `codex-api`, so tools, reasoning effort and output schemas follow
+    /// the same model-info gating as the Responses path.
+    #[allow(clippy::too_many_arguments)]
+    async fn stream_translated_api(
+        &mut self,
+        wire_api: WireApi,
+        prompt: &Prompt,
+        model_info: &ModelInfo,
+        session_telemetry: &SessionTelemetry,
+        effort: Option<ReasoningEffortConfig>,
+        summary: ReasoningSummaryConfig,
+        service_tier: Option<ServiceTier>,
+        turn_metadata_header: Option<&str>,
+    ) -> Result<ResponseStream> {
+        let auth_manager = self.client.state.auth_manager.clone();
+        let mut auth_recovery = auth_manager
+            .as_ref()
+            .map(super::auth::AuthManager::unauthorized_recovery);
+        loop {
+            let client_setup = self.client.current_client_setup().await?;
+            let transport = ReqwestTransport::new(build_reqwest_client());
+            let (request_telemetry, sse_telemetry) =
+                Self::build_streaming_telemetry(session_telemetry);
+            // Neither Chat Completions servers nor Messages endpoints accept zstd bodies.
+            let options = self.build_responses_options(turn_metadata_header, Compression::None);
+            let request = self.build_responses_request(
+                &client_setup.api_provider,
+                prompt,
+                model_info,
+                effort,
+                summary,
+                service_tier,
+            )?;
+            let result = match wire_api {
+                WireApi::Anthropic => {
+                    ApiAnthropicMessagesClient::new(
+                        transport,
+                        client_setup.api_provider,
+                        client_setup.api_auth,
+                    )
+                    .with_telemetry(Some(request_telemetry), Some(sse_telemetry))
+                    .stream_request(request, options)
+                    .await
+                }
+                WireApi::Chat | WireApi::Responses => {
+                    ApiChatCompletionsClient::new(
+                        transport,
+                        client_setup.api_provider,
+                        client_setup.api_auth,
+                    )
+                    .with_telemetry(Some(request_telemetry), Some(sse_telemetry))
+                    .stream_request(request, options)
+                    .await
+                }
+            };
+
+            match result {
+                Ok(stream) => {
+                    let (stream, _last_response_rx) =
+                        map_response_stream(stream, session_telemetry.clone());
+                    return Ok(stream);
+                }
+                Err(ApiError::Transport(
+                    unauthorized_transport @ TransportError::Http { status, .. },
+                )) if status == StatusCode::UNAUTHORIZED => {
+                    handle_unauthorized(unauthorized_transport, &mut auth_recovery).await?;
+                    continue;
+                }
+                Err(err) => return Err(map_api_error(err)),
+            }
+        }
+    }
+
     /// Streams a turn via the Responses API over WebSocket transport.
     #[allow(clippy::too_many_arguments)]
     async fn stream_responses_websocket(
@@ -1157,9 +1242,9 @@ impl ModelClientSession {
     /// Streams a single model request within the current turn.
     ///
     /// The caller is responsible for passing per-turn settings explicitly (model selection,
-    /// reasoning settings, telemetry context, and turn metadata). This method will prefer the
-    /// Responses WebSocket transport when enabled and healthy, and will fall back to the HTTP
-    /// Responses API transport otherwise.
+    /// reasoning settings, telemetry context, and turn metadata). For Responses providers this
+    /// method will prefer the Responses WebSocket transport when enabled and healthy, and will
+    /// fall back to the HTTP Responses API transport otherwise.
     pub async fn stream(
         &mut self,
         prompt: &Prompt,
@@ -1205,6 +1290,19 @@ impl ModelClientSession {
                 )
                 .await
             }
+            wire_api @ (WireApi::Chat | WireApi::Anthropic) => {
+                self.stream_translated_api(
+                    wire_api,
+                    prompt,
+                    model_info,
+                    session_telemetry,
+                    effort,
+                    summary,
+                    service_tier,
+                    turn_metadata_header,
+                )
+                .await
+            }
         }
     }
 
@@ -1568,6 +1666,7 @@ mod tests {
             service_tier: None,
             prompt_cache_key: None,
             text: None,
+            max_output_tokens: None,
         }
     }
 
@@ -42,6 +42,10 @@ pub struct Prompt {
 
     /// Optional the output schema for the model's response.
     pub output_schema: Option<Value>,
+
+    /// Caps the response length for this prompt. Falls back to the
+    /// provider's `max_output_tokens` when unset.
+    pub max_output_tokens: Option<u64>,
 }
 
 impl Prompt {
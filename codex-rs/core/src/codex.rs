@@ -29,6 +29,7 @@ use crate::exec_policy::ExecPolicyManager;
 use crate::features::FEATURES;
 use crate::features::Feature;
 use crate::features::maybe_push_unstable_features_warning;
+use crate::hook_model_runner::SessionHookModelRunner;
 #[cfg(test)]
 use crate::models_manager::collaboration_mode_presets::CollaborationModesConfig;
 use crate::models_manager::manager::ModelsManager;
@@ -40,6 +41,7 @@ use crate::realtime_conversation::handle_audio as handle_realtime_conversation_a
 use crate::realtime_conversation::handle_close as handle_realtime_conversation_close;
 use crate::realtime_conversation::handle_start as handle_realtime_conversation_start;
 use crate::realtime_conversation::handle_text as handle_realtime_conversation_text;
+use crate::repo_index_updater::RepoIndexUpdater;
 use crate::rollout::session_index;
 use crate::stream_events_utils::HandleOutputCtx;
 use crate::stream_events_utils::handle_non_tool_response_item;
@@ -48,6 +50,7 @@ use crate::stream_events_utils::last_assistant_message_from_item;
 use crate::stream_events_utils::raw_assistant_output_text_from_item;
 use crate::stream_events_utils::record_completed_response_item;
 use crate::terminal;
+use crate::tool_secrets::ToolSecrets;
 use crate::truncate::TruncationPolicy;
 use crate::turn_metadata::TurnMetadataState;
 use crate::util::error_or_panic;
@@ -85,12 +88,16 @@ use codex_protocol::items::PlanItem;
 use codex_protocol::items::TurnItem;
 use codex_protocol::items::UserMessageItem;
 use codex_protocol::mcp::CallToolResult;
+use codex_protocol::mcp::Prompt as McpPrompt;
 use codex_protocol::models::BaseInstructions;
 use codex_protocol::models::PermissionProfile;
 use codex_protocol::models::format_allow_prefixes;
 use codex_protocol::openai_models::ModelInfo;
 use codex_protocol::permissions::FileSystemSandboxPolicy;
+use codex_protocol::permissions::GlobDenyMatches;
 use codex_protocol::permissions::NetworkSandboxPolicy;
+use codex_protocol::protocol::BackgroundServiceSource;
+use codex_protocol::protocol::BackgroundServicesUpdatedEvent;
 use codex_protocol::protocol::FileChange;
 use codex_protocol::protocol::HasLegacyEvent;
 use codex_protocol::protocol::ItemCompletedEvent;
@@ -123,6 +130,7 @@ use futures::future::BoxFuture;
 use futures::future::Shared;
 use futures::prelude::*;
 use futures::stream::FuturesOrdered;
+use rmcp::model::GetPromptRequestParams;
 use rmcp::model::ListResourceTemplatesResult;
 use rmcp::model::ListResourcesResult;
 use rmcp::model::PaginatedRequestParams;
@@ -132,6 +140,7 @@ use rmcp::model::RequestId;
 use serde_json;
 use serde_json::Value;
 use tokio::sync::Mutex;
+use tokio::sync::OnceCell;
 use tokio::sync::RwLock;
 use tokio::sync::oneshot;
 use tokio::sync::watch;
@@ -183,6 +192,8 @@ pub enum SteerInputError {
     NoActiveTurn(Vec<UserInput>),
     ExpectedTurnMismatch { expected: String, actual: String },
     EmptyInput,
+    BlockedByHook(String),
+    StoppedByHook(String),
 }
 
 /// Notes from the previous real user turn.
@@ -197,6 +208,10 @@ pub(crate) struct PreviousTurnSettings {
     pub(crate) realtime_active: Option<bool>,
 }
 
+use crate::background_services::BackgroundServiceRegistry;
+use crate::background_services::ServiceSandbox;
+use crate::background_services::ServiceSpec;
+use crate::exec_env::create_env;
 use crate::exec_policy::ExecPolicyUpdateError;
 use crate::feedback_tags;
 use crate::file_watcher::FileWatcher;
@@ -207,6 +222,8 @@ use crate::mcp::CODEX_APPS_MCP_SERVER_NAME;
 use crate::mcp::McpManager;
 use crate::mcp::auth::compute_auth_statuses;
 use crate::mcp::maybe_prompt_and_install_mcp_dependencies;
+use crate::mcp::resource_subscriptions::RESOURCE_UPDATE_READ_TIMEOUT;
+use crate::mcp::resource_subscriptions::resource_update_message;
 use crate::mcp::with_codex_apps_mcp;
 use crate::mcp_connection_manager::McpConnectionManager;
 use crate::mcp_connection_manager::codex_apps_tools_cache_key;
@@ -218,6 +235,7 @@ use crate::mentions::collect_explicit_app_ids;
 use crate::mentions::collect_explicit_plugin_mentions;
 use crate::mentions::collect_tool_mentions_from_messages;
 use crate::network_policy_decision::execpolicy_network_rule_amendment;
+use crate::overlay_workspace::OverlayWorkspace;
 use crate::plugins::PluginsManager;
 use crate::plugins::build_plugin_injections;
 use crate::project_doc::get_user_instructions;
@@ -263,6 +281,7 @@ use crate::rollout::RolloutRecorderParams;
 use crate::rollout::map_session_init_error;
 use crate::rollout::metadata;
 use crate::rollout::policy::EventPersistenceMode;
+use crate::run_budget::RunBudget;
 use crate::shell;
 use crate::shell_snapshot::ShellSnapshot;
 use crate::skills::SkillError;
@@ -286,6 +305,7 @@ use crate::tasks::RegularTask;
 use crate::tasks::ReviewTask;
 use crate::tasks::SessionTask;
 use crate::tasks::SessionTaskContext;
+use crate::tool_output_store::ToolOutputStore;
 use crate::tools::ToolRouter;
 use crate::tools::context::SharedTurnDiffTracker;
 use crate::tools::discoverable::DiscoverableTool;
@@ -360,6 +380,7 @@ pub(crate) struct CodexSpawnArgs {
     pub(crate) plugins_manager: Arc<PluginsManager>,
     pub(crate) mcp_manager: Arc<McpManager>,
     pub(crate) file_watcher: Arc<FileWatcher>,
+    pub(crate) background_service_registry: Arc<BackgroundServiceRegistry>,
     pub(crate) conversation_history: InitialHistory,
     pub(crate) session_source: SessionSource,
     pub(crate) agent_control: AgentControl,
@@ -410,6 +431,7 @@ impl Codex {
             plugins_manager,
             mcp_manager,
             file_watcher,
+            background_service_registry,
             conversation_history,
             session_source,
             agent_control,
@@ -599,6 +621,7 @@ impl Codex {
             plugins_manager,
             mcp_manager.clone(),
             file_watcher,
+            background_service_registry,
             agent_control,
         )
         .instrument(session_init_span)
@@ -817,6 +840,13 @@ pub(crate) struct TurnContext {
     pub(crate) turn_metadata_state: Arc<TurnMetadataState>,
     pub(crate) turn_skills: TurnSkillsContext,
     pub(crate) turn_timing_state: Arc<TurnTimingState>,
+    pub(crate) run_budget: Arc<RunBudget>,
+    /// Copy-on-write workspace that sandboxed commands write into, when the
+    /// `overlay_workspace` feature is enabled.
+    pub(crate) overlay_workspace: Option<Arc<OverlayWorkspace>>,
+    /// Filesystem glob deny matches handed to the Linux sandbox helper,
+    /// expanded at most once per turn.
+    pub(crate) glob_deny_matches: Arc<OnceCell<Option<GlobDenyMatches>>>,
 }
 impl TurnContext {
     pub(crate) fn model_context_window(&self) -> Option<i64> {
@@ -920,9 +950,50 @@ impl TurnContext {
             turn_metadata_state: self.turn_metadata_state.clone(),
             turn_skills: self.turn_skills.clone(),
             turn_timing_state: Arc::clone(&self.turn_timing_state),
+            run_budget: Arc::clone(&self.run_budget),
+            overlay_workspace: self.overlay_workspace.clone(),
+            glob_deny_matches: Arc::clone(&self.glob_deny_matches),
         }
     }
 
+    /// Expands the filesystem glob denies for the Linux sandbox helper once per
+    /// turn so that every command of the turn reuses the same walk. Returns
+    /// `None` when there is nothing to expand or the walk failed, in which
+    /// case the helper expands the globs itself.
+    pub(crate) async fn glob_deny_matches(&self) -> Option<&GlobDenyMatches> {
+        if !cfg!(target_os = "linux") || self.codex_linux_sandbox_exe.is_none() {
+            return None;
+        }
+        let glob_denies = self
+            .file_system_sandbox_policy
+            .glob_denies_with_cwd(&self.cwd);
+        if glob_denies.is_empty() {
+            return None;
+        }
+        self.glob_deny_matches
+            .get_or_init(|| async move {
+                match tokio::task::spawn_blocking(move || glob_denies.expand_existing_matches())
+                    .await
+                {
+                    Ok(expanded) => {
+                        if let Some(dir) = &expanded.truncated_under {
+                            warn!(
+                                "filesystem glob denies were only partly expanded, stopped under {}",
+                                dir.display()
+                            );
+                        }
+                        Some(expanded)
+                    }
+                    Err(err) => {
+                        warn!("failed to expand filesystem glob denies: {err}");
+                        None
+                    }
+                }
+            })
+            .await
+            .as_ref()
+    }
+
     pub(crate) fn resolve_path(&self, path: Option<String>) -> PathBuf {
         path.as_ref()
             .map(PathBuf::from)
@@ -1241,6 +1312,12 @@ impl Session {
                         };
                         sess.send_event_raw(event).await;
                     }
+                    Ok(FileWatcherEvent::RepoFilesChanged { paths }) => {
+                        let Some(sess) = weak_sess.upgrade() else {
+                            break;
+                        };
+                        sess.services.repo_index_updater.notify_paths(paths);
+                    }
                     Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
                     Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => continue,
                 }
@@ -1248,6 +1325,86 @@ impl Session {
         });
     }
 
+    /// Registers the `[services.<name>]` tables of `config`, autostarts them
+    /// for top-level threads, and reports service changes as
+    /// `BackgroundServicesUpdated` events. Does nothing unless the
+    /// `background_services` feature is enabled.
+    fn start_background_services(
+        self: &Arc<Self>,
+        config: &Config,
+        session_configuration: &SessionConfiguration,
+    ) {
+        if !config.features.enabled(Feature::BackgroundServices) {
+            return;
+        }
+        let services = &self.services.background_services;
+        if !matches!(
+            session_configuration.session_source,
+            SessionSource::SubAgent(_)
+        ) {
+            let env = create_env(
+                &config.permissions.shell_environment_policy,
+                Some(self.conversation_id),
+            );
+            let sandbox = ServiceSandbox {
+                policy: session_configuration.sandbox_policy.get(),
+                file_system_policy: &session_configuration.file_system_sandbox_policy,
+                network_policy: session_configuration.network_sandbox_policy,
+                enforce_managed_network: config
+                    .config_layer_stack
+                    .requirements_toml()
+                    .network
+                    .is_some(),
+                codex_linux_sandbox_exe: config.codex_linux_sandbox_exe.as_ref(),
+                use_legacy_landlock: config.features.use_legacy_landlock(),
+                resource_limits: config.permissions.resource_limits,
+                windows_sandbox_level: session_configuration.windows_sandbox_level,
+            };
+            for (name, service) in &config.services {
+                let spec = match ServiceSpec::from_config(
+                    service,
+                    &session_configuration.cwd,
+                    env.clone(),
+                    &sandbox,
+                ) {
+                    Ok(spec) => spec,
+                    Err(err) => {
+                        warn!("failed to sandbox background service `{name}`: {err}");
+                        continue;
+                    }
+                };
+                if services.define_if_absent(name, spec, BackgroundServiceSource::Config)
+                    && service.autostart
+                    && let Err(err) = services.start(name)
+                {
+                    warn!("failed to start background service `{name}`: {err}");
+                }
+            }
+        }
+
+        let mut updates = services.subscribe();
+        // Report services that already exist, including ones left running
+        // before the thread was resumed.
+        if !services.summaries().is_empty() {
+            updates.mark_changed();
+        }
+        let weak_sess = Arc::downgrade(self);
+        tokio::spawn(async move {
+            while updates.changed().await.is_ok() {
+                let Some(sess) = weak_sess.upgrade() else {
+                    break;
+                };
+                let event = Event {
+                    id: sess.next_internal_sub_id(),
+                    msg: EventMsg::BackgroundServicesUpdated(BackgroundServicesUpdatedEvent {
+                        services: sess.services.background_services.summaries(),
+                    }),
+                };
+                sess.send_event_raw(event).await;
+            }
+        });
+    }
+
     #[allow(clippy::too_many_arguments)]
     fn make_turn_context(
         auth_manager: Option<Arc<AuthManager>>,
@@ -1261,6 +1418,7 @@ impl Session {
         sub_id: String,
         js_repl: Arc<JsReplHandle>,
         skills_outcome: Arc<SkillLoadOutcome>,
+        run_budget: Arc<RunBudget>,
     ) -> TurnContext {
         let reasoning_effort = session_configuration.collaboration_mode.reasoning_effort();
         let reasoning_summary = session_configuration
@@ -1337,6 +1495,9 @@ impl Session {
             turn_metadata_state,
             turn_skills: TurnSkillsContext::new(skills_outcome),
             turn_timing_state: Arc::new(TurnTimingState::default()),
+            run_budget,
+            overlay_workspace: None,
+            glob_deny_matches: Arc::new(OnceCell::new()),
         }
     }
 
@@ -1355,6 +1516,7 @@ impl Session {
         plugins_manager: Arc<PluginsManager>,
         mcp_manager: Arc<McpManager>,
         file_watcher: Arc<FileWatcher>,
+        background_service_registry: Arc<BackgroundServiceRegistry>,
         agent_control: AgentControl,
     ) -> anyhow::Result<Arc<Self>> {
         debug!(
@@ -1514,6 +1676,39 @@ impl Session {
             });
         }
 
+        let overlay_workspace = if !config.features.enabled(Feature::OverlayWorkspace)
+            || matches!(
+                session_configuration.session_source,
+                SessionSource::SubAgent(_)
+            ) {
+            None
+        } else if config.features.use_legacy_landlock() {
+            post_session_configured_events.push(Event {
+                id: "".to_owned(),
+                msg: EventMsg::Warning(WarningEvent {
+                    message: "`overlay_workspace` needs the bubblewrap sandbox and is disabled while `use_legacy_landlock` is on.".to_string(),
+                }),
+            });
+            None
+        } else {
+            match OverlayWorkspace::create(
+                &config.codex_home,
+                conversation_id,
+                &session_configuration.cwd,
+            ) {
+                Ok(overlay) => Some(Arc::new(overlay)),
+                Err(err) => {
+                    post_session_configured_events.push(Event {
+                        id: "".to_owned(),
+                        msg: EventMsg::Warning(WarningEvent {
+                            message: format!("Copy-on-write workspace is disabled: {err}"),
+                        }),
+                    });
+                    None
+                }
+            }
+        };
+
         let auth = auth.as_ref();
         let auth_mode = auth.map(CodexAuth::auth_mode).map(TelemetryAuthMode::from);
         let account_id = auth.and_then(CodexAuth::get_account_id);
@@ -1724,6 +1919,17 @@ impl Session {
             plugins_manager: Arc::clone(&plugins_manager),
             mcp_manager: Arc::clone(&mcp_manager),
             file_watcher,
+            repo_index_updater: RepoIndexUpdater::new(
+                Arc::clone(&config),
+                SandboxState {
+                    sandbox_policy: session_configuration.sandbox_policy.get().clone(),
+                    codex_linux_sandbox_exe: config.codex_linux_sandbox_exe.clone(),
+                    sandbox_cwd: session_configuration.cwd.clone(),
+                    use_legacy_landlock: config.features.use_legacy_landlock(),
+                },
+            ),
+            tool_secrets: ToolSecrets::load(config.as_ref()).await,
+            run_budget: agent_control.run_budget(config.run_budget),
             agent_control,
             network_proxy,
             network_approval: Arc::clone(&network_approval),
@@ -1742,6 +1948,12 @@ impl Session {
             code_mode_service: crate::tools::code_mode::CodeModeService::new(
                 config.js_repl_node_path.clone(),
             ),
+            overlay_workspace,
+            tool_output_store: config
+                .features
+                .enabled(Feature::SpillToolOutput)
+                .then(|| ToolOutputStore::new(&config.codex_home, conversation_id)),
+            background_services: background_service_registry.for_thread(conversation_id),
         };
         let js_repl = Arc::new(JsReplHandle::with_node_path(
             config.js_repl_node_path.clone(),
@@ -1798,6 +2010,7 @@ impl Session {
 
         // Start the watcher after SessionConfigured so it cannot emit earlier events.
         sess.start_file_watcher_listener();
+        sess.start_background_services(&config, &session_configuration);
         // Construct sandbox_state before MCP startup so it can be sent to each
         // MCP server immediately after it becomes ready (avoiding blocking).
         let sandbox_state = SandboxState {
@@ -2213,8 +2426,11 @@ impl Session {
             match state.session_configuration.clone().apply(&updates) {
                 Ok(next) => {
                     let previous_cwd = state.session_configuration.cwd.clone();
-                    let sandbox_policy_changed =
-                        state.session_configuration.sandbox_policy != next.sandbox_policy;
+                    // MCP servers see the sandbox cwd (and the roots derived
+                    // from it), so a new cwd is reported like a new policy.
+                    let sandbox_policy_changed = state.session_configuration.sandbox_policy
+                        != next.sandbox_policy
+                        || previous_cwd != next.cwd;
                     let codex_home = next.codex_home.clone();
                     let session_source = next.session_source.clone();
                     state.session_configuration = next.clone();
@@ -2320,8 +2536,10 @@ impl Session {
             sub_id,
             Arc::clone(&self.js_repl),
             skills_outcome,
+            Arc::clone(&self.services.run_budget),
         );
         turn_context.realtime_active = self.conversation.running_state().await.is_some();
+        turn_context.overlay_workspace = self.services.overlay_workspace.clone();
 
         if let Some(final_schema) = final_output_json_schema {
             turn_context.final_output_json_schema = final_schema;
@@ -3548,6 +3766,7 @@ impl Session {
         token_usage: Option<&TokenUsage>,
     ) {
         if let Some(token_usage) = token_usage {
+            turn_context.run_budget.record_token_usage(token_usage);
             let mut state = self.state.lock().await;
             state.update_token_info_from_usage(token_usage, turn_context.model_context_window());
         }
@@ -3736,9 +3955,15 @@ impl Session {
 
     /// Inject additional user input into the currently active turn.
     ///
+    /// The input goes through the UserPromptSubmit hooks first, like the
+    /// input that started the turn. Input a hook blocks is dropped with
+    /// [`SteerInputError::BlockedByHook`]; when a hook stops processing, the
+    /// active turn is interrupted and [`SteerInputError::StoppedByHook`] is
+    /// returned.
+    ///
     /// Returns the active turn id when accepted.
     pub async fn steer_input(
-        &self,
+        self: &Arc<Self>,
         input: Vec<UserInput>,
         expected_turn_id: Option<&str>,
     ) -> Result<String, SteerInputError> {
@@ -3746,25 +3971,72 @@ impl Session {
             return Err(SteerInputError::EmptyInput);
         }
 
+        let turn_context = {
+            let active = self.active_turn.lock().await;
+            let Some((active_turn_id, task)) = active
+                .as_ref()
+                .and_then(|active_turn| active_turn.tasks.first())
+            else {
+                return Err(SteerInputError::NoActiveTurn(input));
+            };
+
+            if let Some(expected_turn_id) = expected_turn_id
+                && expected_turn_id != active_turn_id
+            {
+                return Err(SteerInputError::ExpectedTurnMismatch {
+                    expected: expected_turn_id.to_string(),
+                    actual: active_turn_id.clone(),
+                });
+            }
+            Arc::clone(&task.turn_context)
+        };
+
+        // Hooks run without holding the turn lock; they may take a while and
+        // can call back into the session.
+        let (input, additional_context) =
+            match run_user_prompt_submit_hooks(self, &turn_context, input).await {
+                UserPromptSubmitDecision::Continue {
+                    input,
+                    additional_context,
+                } => (input, additional_context),
+                UserPromptSubmitDecision::Blocked { reason } => {
+                    return Err(SteerInputError::BlockedByHook(reason));
+                }
+                UserPromptSubmitDecision::Stopped { reason } => {
+                    let still_active = self
+                        .active_turn
+                        .lock()
+                        .await
+                        .as_ref()
+                        .and_then(|active_turn| active_turn.tasks.first())
+                        .is_some_and(|(active_turn_id, _)| *active_turn_id == turn_context.sub_id);
+                    if still_active {
+                        self.abort_all_tasks(TurnAbortReason::Interrupted).await;
+                    }
+                    return Err(SteerInputError::StoppedByHook(reason));
+                }
+            };
+
         let mut active = self.active_turn.lock().await;
         let Some(active_turn) = active.as_mut() else {
             return Err(SteerInputError::NoActiveTurn(input));
         };
-
         let Some((active_turn_id, _)) = active_turn.tasks.first() else {
             return Err(SteerInputError::NoActiveTurn(input));
         };
-
-        if let Some(expected_turn_id) = expected_turn_id
-            && expected_turn_id != active_turn_id
-        {
-            return Err(SteerInputError::ExpectedTurnMismatch {
-                expected: expected_turn_id.to_string(),
-                actual: active_turn_id.clone(),
-            });
+        if *active_turn_id != turn_context.sub_id {
+            return Err(SteerInputError::NoActiveTurn(input));
         }
 
         let mut turn_state = active_turn.turn_state.lock().await;
+        if let Some(additional_context) = additional_context {
+            turn_state.push_pending_input(ResponseInputItem::Message {
+                role: "developer".to_string(),
+                content: vec![ContentItem::InputText {
+                    text: additional_context,
+                }],
+            });
+        }
         turn_state.push_pending_input(input.into());
         Ok(active_turn_id.clone())
     }
@@ -3848,6 +4120,106 @@ impl Session {
             .await
     }
 
+    pub async fn subscribe_mcp_resource(&self, server: &str, uri: &str) -> anyhow::Result<()> {
+        self.services
+            .mcp_connection_manager
+            .read()
+            .await
+            .subscribe_resource(server, uri)
+            .await
+    }
+
+    pub async fn unsubscribe_mcp_resource(&self, server: &str, uri: &str) -> anyhow::Result<()> {
+        self.services
+            .mcp_connection_manager
+            .read()
+            .await
+            .unsubscribe_resource(server, uri)
+            .await
+    }
+
+    /// Reads every subscribed MCP resource that changed since the last turn
+    /// and returns one context message per resource. The reads run
+    /// concurrently, each bounded by `RESOURCE_UPDATE_READ_TIMEOUT`; resources
+    /// that could not be read stay queued for the next turn.
+    async fn mcp_resource_update_items(&self) -> Vec<ResponseItem> {
+        let manager = self.services.mcp_connection_manager.read().await;
+        let mut updated = manager.take_updated_resources();
+        let manager = &*manager;
+        let reads = updated.keys().into_iter().map(|(server, uri)| async move {
+            let params = ReadResourceRequestParams {
+                meta: None,
+                uri: uri.clone(),
+            };
+            let result = match tokio::time::timeout(
+                RESOURCE_UPDATE_READ_TIMEOUT,
+                manager.read_resource(&server, params),
+            )
+            .await
+            {
+                Ok(result) => result.map_err(|err| format!("{err:#}")),
+                Err(_) => Err(format!(
+                    "timed out after {}s",
+                    RESOURCE_UPDATE_READ_TIMEOUT.as_secs()
+                )),
+            };
+            (server, uri, result)
+        });
+
+        let mut items = Vec::new();
+        for (server, uri, result) in futures::future::join_all(reads).await {
+            match result {
+                Ok(result) => {
+                    updated.mark_read(&server, &uri);
+                    items.push(resource_update_message(&server, &uri, result));
+                }
+                Err(err) => warn!(
+                    "failed to read updated MCP resource `{uri}` from `{server}`, retrying next turn: {err}"
+                ),
+            }
+        }
+        items
+    }
+
+    /// Returns the prompts published by MCP servers, keyed by server name.
+    pub async fn list_mcp_prompts(&self) -> HashMap<String, Vec<McpPrompt>> {
+        self.services
+            .mcp_connection_manager
+            .read()
+            .await
+            .list_all_prompts()
+            .await
+            .into_iter()
+            .map(|(server, prompts)| {
+                let prompts = crate::mcp::prompts::prompts_from_mcp(&server, prompts);
+                (server, prompts)
+            })
+            .collect()
+    }
+
+    /// Fetches an MCP prompt and flattens its messages to text.
+    pub async fn get_mcp_prompt(
+        &self,
+        server: &str,
+        prompt: &str,
+        arguments: HashMap<String, String>,
+    ) -> Result<String, String> {
+        let params = GetPromptRequestParams {
+            meta: None,
+            name: prompt.to_string(),
+            arguments: crate::mcp::prompts::prompt_arguments(arguments),
+        };
+        let result = self
+            .services
+            .mcp_connection_manager
+            .read()
+            .await
+            .get_prompt(server, params)
+            .await
+            .map_err(|err| format!("{err:#}"))?;
+        crate::mcp::prompts::prompt_result_text(result)
+    }
+
     pub async fn call_tool(
         &self,
         server: &str,
@@ -4051,6 +4423,11 @@ async fn submission_loop(sess: Arc<Session>, config: Arc<Config>, rx_sub: Receiv
                     handlers::clean_background_terminals(&sess).await;
                     false
                 }
+                Op::ControlBackgroundService { name, action } => {
+                    handlers::control_background_service(&sess, sub.id.clone(), &name, action)
+                        .await;
+                    false
+                }
                 Op::RealtimeConversationStart(params) => {
                     if let Err(err) =
                         handle_realtime_conversation_start(&sess, sub.id.clone(), params).await
@@ -4165,6 +4542,25 @@ async fn submission_loop(sess: Arc<Session>, config: Arc<Config>, rx_sub: Receiv
                     handlers::list_mcp_tools(&sess, &config, sub.id.clone()).await;
                     false
                 }
+                Op::ListMcpPrompts => {
+                    handlers::list_mcp_prompts(&sess, sub.id.clone()).await;
+                    false
+                }
+                Op::GetMcpPrompt {
+                    server_name,
+                    prompt_name,
+                    arguments,
+                } => {
+                    handlers::get_mcp_prompt(
+                        &sess,
+                        sub.id.clone(),
+                        server_name,
+                        prompt_name,
+                        arguments,
+                    )
+                    .await;
+                    false
+                }
                 Op::RefreshMcpServers { config } => {
                     handlers::refresh_mcp_servers(&sess, config).await;
                     false
@@ -4177,6 +4573,15 @@ async fn submission_loop(sess: Arc<Session>, config: Arc<Config>, rx_sub: Receiv
                     handlers::list_custom_prompts(&sess, sub.id.clone()).await;
                     false
                 }
+                Op::ExpandCustomPrompt {
+                    id,
+                    text,
+                    text_elements,
+                } => {
+                    handlers::expand_custom_prompt(&sess, sub.id.clone(), id, text, text_elements)
+                        .await;
+                    false
+                }
                 Op::ListSkills { cwds, force_reload } => {
                     handlers::list_skills(&sess, sub.id.clone(), cwds, force_reload).await;
                     false
@@ -4234,6 +4639,11 @@ async fn submission_loop(sess: Arc<Session>, config: Arc<Config>, rx_sub: Receiv
                     handlers::repo_index_refresh(&sess, sub.id.clone(), force_full).await;
                     false
                 }
+                Op::ResolveOverlayWorkspace { decision, paths } => {
+                    handlers::resolve_overlay_workspace(&sess, sub.id.clone(), decision, paths)
+                        .await;
+                    false
+                }
                 Op::ResolveElicitation {
                     server_name,
                     request_id,
@@ -4252,6 +4662,14 @@ async fn submission_loop(sess: Arc<Session>, config: Arc<Config>, rx_sub: Receiv
                     .await;
                     false
                 }
+                Op::ResolveMcpSampling {
+                    server_name,
+                    request_id,
+                    decision,
+                } => {
+                    handlers::resolve_mcp_sampling(&sess, server_name, request_id, decision).await;
+                    false
+                }
                 Op::Shutdown => handlers::shutdown(&sess, sub.id.clone()).await,
                 Op::Review { review_request } => {
                     handlers::review(&sess, &config, sub.id.clone(), review_request).await;
@@ -4302,21 +4720,28 @@ mod handlers {
     use crate::rollout::RolloutRecorder;
     use crate::rollout::session_index;
     use crate::tasks::CompactTask;
+    use crate::tasks::CustomPromptShellTask;
     use crate::tasks::RepoIndexRefreshTask;
     use crate::tasks::UndoTask;
     use crate::tasks::UserShellCommandMode;
     use crate::tasks::UserShellCommandTask;
     use crate::tasks::execute_user_shell_command;
+    use crate::tasks::expand_custom_prompt_shell;
     use codex_protocol::custom_prompts::CustomPrompt;
+    use codex_protocol::protocol::BackgroundServiceAction;
     use codex_protocol::protocol::CodexErrorInfo;
     use codex_protocol::protocol::ErrorEvent;
     use codex_protocol::protocol::Event;
     use codex_protocol::protocol::EventMsg;
+    use codex_protocol::protocol::GetMcpPromptResponseEvent;
     use codex_protocol::protocol::ListCustomPromptsResponseEvent;
+    use codex_protocol::protocol::ListMcpPromptsResponseEvent;
     use codex_protocol::protocol::ListRemoteSkillsResponseEvent;
     use codex_protocol::protocol::ListSkillsResponseEvent;
     use codex_protocol::protocol::McpServerRefreshConfig;
     use codex_protocol::protocol::Op;
+    use codex_protocol::protocol::OverlayWorkspaceChangesEvent;
+    use codex_protocol::protocol::OverlayWorkspaceDecision;
     use codex_protocol::protocol::RemoteSkillDownloadedEvent;
     use codex_protocol::protocol::RemoteSkillHazelnutScope;
     use codex_protocol::protocol::RemoteSkillProductSurface;
@@ -4333,15 +4758,18 @@ mod handlers {
     use codex_protocol::request_user_input::RequestUserInputResponse;
 
     use crate::context_manager::is_user_turn_boundary;
+    use codex_protocol::approvals::McpSamplingDecision;
     use codex_protocol::config_types::CollaborationMode;
     use codex_protocol::config_types::ModeKind;
     use codex_protocol::config_types::Settings;
     use codex_protocol::dynamic_tools::DynamicToolResponse;
     use codex_protocol::mcp::RequestId as ProtocolRequestId;
+    use codex_protocol::user_input::TextElement;
     use codex_protocol::user_input::UserInput;
     use codex_rmcp_client::ElicitationAction;
     use codex_rmcp_client::ElicitationResponse;
     use serde_json::Value;
+    use std::collections::HashMap;
     use std::path::PathBuf;
     use std::sync::Arc;
     use tracing::info;
@@ -4355,6 +4783,26 @@ mod handlers {
         sess.close_unified_exec_processes().await;
     }
 
+    /// State changes are reported by the background services listener; only
+    /// failures are reported here.
+    pub async fn control_background_service(
+        sess: &Arc<Session>,
+        sub_id: String,
+        name: &str,
+        action: BackgroundServiceAction,
+    ) {
+        if let Err(err) = sess.services.background_services.control(name, action) {
+            sess.send_event_raw(Event {
+                id: sub_id,
+                msg: EventMsg::Error(ErrorEvent {
+                    message: err.to_string(),
+                    codex_error_info: Some(CodexErrorInfo::BadRequest),
+                }),
+            })
+            .await;
+        }
+    }
+
     pub async fn override_turn_context(
         sess: &Session,
         sub_id: String,
@@ -4471,6 +4919,43 @@ mod handlers {
         .await;
     }
 
+    /// Runs the shell directives of an expanded custom prompt. Like `!cmd`,
+    /// this joins the active turn if there is one so it does not abort it.
+    pub async fn expand_custom_prompt(
+        sess: &Arc<Session>,
+        sub_id: String,
+        request_id: String,
+        text: String,
+        text_elements: Vec<TextElement>,
+    ) {
+        if let Some((turn_context, cancellation_token)) =
+            sess.active_turn_context_and_cancellation_token().await
+        {
+            let session = Arc::clone(sess);
+            tokio::spawn(async move {
+                expand_custom_prompt_shell(
+                    session,
+                    turn_context,
+                    request_id,
+                    text,
+                    text_elements,
+                    cancellation_token,
+                    UserShellCommandMode::ActiveTurnAuxiliary,
+                )
+                .await;
+            });
+            return;
+        }
+
+        let turn_context = sess.new_default_turn_with_sub_id(sub_id).await;
+        sess.spawn_task(
+            Arc::clone(&turn_context),
+            Vec::new(),
+            CustomPromptShellTask::new(request_id, text, text_elements),
+        )
+        .await;
+    }
+
     pub async fn repo_index_refresh(sess: &Arc<Session>, sub_id: String, force_full: bool) {
         let turn_context = sess.new_default_turn_with_sub_id(sub_id).await;
         sess.spawn_task(
@@ -4481,6 +4966,56 @@ mod handlers {
         .await;
     }
 
+    /// Accepts or rejects pending copy-on-write workspace changes, then reports
+    /// the changes that are still pending.
+    pub async fn resolve_overlay_workspace(
+        sess: &Arc<Session>,
+        sub_id: String,
+        decision: OverlayWorkspaceDecision,
+        paths: Option<Vec<PathBuf>>,
+    ) {
+        let error = |message: String| {
+            EventMsg::Error(ErrorEvent {
+                message,
+                codex_error_info: Some(CodexErrorInfo::BadRequest),
+            })
+        };
+        let Some(overlay_workspace) = sess.services.overlay_workspace.clone() else {
+            sess.send_event_raw(Event {
+                id: sub_id,
+                msg: error("Copy-on-write workspace is not enabled for this session.".to_string()),
+            })
+            .await;
+            return;
+        };
+        let has_active_turn = { sess.active_turn.lock().await.is_some() };
+        if has_active_turn {
+            sess.send_event_raw(Event {
+                id: sub_id,
+                msg: error(
+                    "Cannot resolve workspace changes while a turn is in progress.".to_string(),
+                ),
+            })
+            .await;
+            return;
+        }
+
+        let result = tokio::task::spawn_blocking(move || {
+            overlay_workspace.resolve(decision, paths.as_deref())?;
+            overlay_workspace.pending_file_changes()
+        })
+        .await
+        .map_err(std::io::Error::other)
+        .flatten();
+        let msg = match result {
+            Ok(changes) => {
+                EventMsg::OverlayWorkspaceChanges(OverlayWorkspaceChangesEvent { changes })
+            }
+            Err(err) => error(format!("Failed to resolve workspace changes: {err}")),
+        };
+        sess.send_event_raw(Event { id: sub_id, msg }).await;
+    }
+
     pub async fn resolve_elicitation(
         sess: &Arc<Session>,
         server_name: String,
@@ -4521,6 +5056,44 @@ mod handlers {
         }
     }
 
+    pub async fn resolve_mcp_sampling(
+        sess: &Arc<Session>,
+        server_name: String,
+        request_id: ProtocolRequestId,
+        decision: McpSamplingDecision,
+    ) {
+        let request_id = match request_id {
+            ProtocolRequestId::String(value) => {
+                rmcp::model::NumberOrString::String(std::sync::Arc::from(value))
+            }
+            ProtocolRequestId::Integer(value) => rmcp::model::NumberOrString::Number(value),
+        };
+        let Some(pending) = sess
+            .services
+            .mcp_connection_manager
+            .read()
+            .await
+            .take_sampling_request(server_name, request_id)
+            .await
+        else {
+            warn!("no pending MCP sampling request found");
+            return;
+        };
+        match decision {
+            McpSamplingDecision::Decline => pending.decline(),
+            McpSamplingDecision::Approve => {
+                let sess = Arc::clone(sess);
+                tokio::spawn(async move {
+                    let turn_context = sess.new_default_turn().await;
+                    let completion =
+                        crate::mcp::sampling::run_sampling_job(&sess, &turn_context, &pending.job)
+                            .await;
+                    pending.complete(completion);
+                });
+            }
+        }
+    }
+
     /// Propagate a user's exec approval decision to the session.
     /// Also optionally applies an execpolicy amendment.
     pub async fn exec_approval(
@@ -4675,13 +5248,42 @@ mod handlers {
         sess.send_event_raw(event).await;
     }
 
+    pub async fn list_mcp_prompts(sess: &Session, sub_id: String) {
+        let prompts = sess.list_mcp_prompts().await;
+        let event = Event {
+            id: sub_id,
+            msg: EventMsg::ListMcpPromptsResponse(ListMcpPromptsResponseEvent { prompts }),
+        };
+        sess.send_event_raw(event).await;
+    }
+
+    pub async fn get_mcp_prompt(
+        sess: &Session,
+        sub_id: String,
+        server_name: String,
+        prompt_name: String,
+        arguments: HashMap<String, String>,
+    ) {
+        let result = sess
+            .get_mcp_prompt(&server_name, &prompt_name, arguments)
+            .await;
+        let event = Event {
+            id: sub_id,
+            msg: EventMsg::GetMcpPromptResponse(GetMcpPromptResponseEvent {
+                server_name,
+                prompt_name,
+                result,
+            }),
+        };
+        sess.send_event_raw(event).await;
+    }
+
     pub async fn list_custom_prompts(sess: &Session, sub_id: String) {
+        let config = sess.get_config().await;
+        let dirs =
+            crate::custom_prompts::prompt_dirs(&config.config_layer_stack, &config.codex_home);
         let custom_prompts: Vec<CustomPrompt> =
-            if let Some(dir) = crate::custom_prompts::default_prompts_dir() {
-                crate::custom_prompts::discover_prompts_in(&dir).await
-            } else {
-                Vec::new()
-            };
+            crate::custom_prompts::discover_prompts_in_dirs(&dirs).await;
 
         let event = Event {
             id: sub_id,
@@ -5276,6 +5878,9 @@ async fn spawn_review_thread(
         turn_metadata_state,
         turn_skills: TurnSkillsContext::new(parent_turn_context.turn_skills.outcome.clone()),
         turn_timing_state: Arc::new(TurnTimingState::default()),
+        run_budget: Arc::clone(&parent_turn_context.run_budget),
+        overlay_workspace: parent_turn_context.overlay_workspace.clone(),
+        glob_deny_matches: Arc::new(OnceCell::new()),
     };
 
     // Seed the child task with the review prompt as the initial user message.
@@ -5354,6 +5959,92 @@ fn errors_to_info(errors: &[SkillError]) -> Vec<SkillErrorInfo> {
         .collect()
 }
 
+/// Maps the turn approval policy onto the Claude-style `permission_mode` that
+/// hooks receive on stdin.
+pub(crate) fn hook_permission_mode(approval_policy: AskForApproval) -> String {
+    match approval_policy {
+        AskForApproval::Never => "bypassPermissions",
+        AskForApproval::UnlessTrusted
+        | AskForApproval::OnFailure
+        | AskForApproval::OnRequest
+        | AskForApproval::Granular(_) => "default",
+    }
+    .to_string()
+}
+
+/// Runs the UserPromptSubmit hooks over `input` and reports them as hook
+/// events on `turn_context`. Returns `None` when a hook blocked or stopped the
+/// prompt, otherwise the input (possibly rewritten by a hook) and any context
+/// the hooks asked to add.
+/// What the `UserPromptSubmit` hooks decided about a prompt.
+enum UserPromptSubmitDecision {
+    Continue {
+        input: Vec<UserInput>,
+        additional_context: Option<String>,
+    },
+    Blocked {
+        reason: String,
+    },
+    Stopped {
+        reason: String,
+    },
+}
+
+async fn run_user_prompt_submit_hooks(
+    sess: &Arc<Session>,
+    turn_context: &Arc<TurnContext>,
+    input: Vec<UserInput>,
+) -> UserPromptSubmitDecision {
+    let request = codex_hooks::UserPromptSubmitRequest {
+        session_id: sess.conversation_id,
+        turn_id: turn_context.sub_id.clone(),
+        cwd: turn_context.cwd.clone(),
+        transcript_path: sess.current_rollout_path().await,
+        model: turn_context.model_info.slug.clone(),
+        permission_mode: hook_permission_mode(turn_context.approval_policy.value()),
+        input: input.clone(),
+    };
+    for run in sess.hooks().preview_user_prompt_submit(&request) {
+        sess.send_event(
+            turn_context,
+            EventMsg::HookStarted(crate::protocol::HookStartedEvent {
+                turn_id: Some(turn_context.sub_id.clone()),
+                run,
+            }),
+        )
+        .await;
+    }
+    let outcome = sess
+        .hooks()
+        .run_user_prompt_submit(
+            request,
+            &SessionHookModelRunner::new(Arc::clone(sess), Arc::clone(turn_context)),
+        )
+        .await;
+    for completed in outcome.hook_events {
+        sess.send_event(turn_context, EventMsg::HookCompleted(completed))
+            .await;
+    }
+    if outcome.should_stop {
+        return UserPromptSubmitDecision::Stopped {
+            reason: outcome
+                .stop_reason
+                .unwrap_or_else(|| "no reason given".to_string()),
+        };
+    }
+    if outcome.should_block {
+        return UserPromptSubmitDecision::Blocked {
+            reason: outcome
+                .block_reason
+                .unwrap_or_else(|| "no reason given".to_string()),
+        };
+    }
+    UserPromptSubmitDecision::Continue {
+        input: outcome.updated_input.unwrap_or(input),
+        additional_context: outcome.additional_context,
+    }
+}
+
 /// Takes a user message as input and runs a loop where, at each sampling request, the model
 /// replies with either:
 ///
@@ -5388,6 +6079,18 @@ pub(crate) async fn run_turn(
         collaboration_mode_kind: turn_context.collaboration_mode.mode,
     });
     sess.send_event(&turn_context, event).await;
+    turn_context.run_budget.start();
+
+    // UserPromptSubmit hooks see the prompt before anything is recorded, so a
+    // blocked prompt never reaches the model or the transcript.
+    let UserPromptSubmitDecision::Continue {
+        input,
+        additional_context,
+    } = run_user_prompt_submit_hooks(&sess, &turn_context, input).await
+    else {
+        return None;
+    };
+
     // TODO(ccunningham): Pre-turn compaction runs before context updates and the
     // new user message are recorded. Estimate pending incoming items (context
     // diffs/full reinjection + user input) and trigger compaction preemptively
@@ -5569,6 +6272,16 @@ pub(crate) async fn run_turn(
         sess.record_conversation_items(&turn_context, &plugin_items)
             .await;
     }
+    let mcp_resource_items = sess.mcp_resource_update_items().await;
+    if !mcp_resource_items.is_empty() {
+        sess.record_conversation_items(&turn_context, &mcp_resource_items)
+            .await;
+    }
+    if let Some(additional_context) = additional_context {
+        let developer_message: ResponseItem = DeveloperInstructions::new(additional_context).into();
+        sess.record_conversation_items(&turn_context, std::slice::from_ref(&developer_message))
+            .await;
+    }
 
     sess.maybe_start_ghost_snapshot(Arc::clone(&turn_context), cancellation_token.child_token())
         .await;
@@ -5587,20 +6300,12 @@ pub(crate) async fn run_turn(
 
     loop {
         if let Some(session_start_source) = sess.take_pending_session_start_source().await {
-            let session_start_permission_mode = match turn_context.approval_policy.value() {
-                AskForApproval::Never => "bypassPermissions",
-                AskForApproval::UnlessTrusted
-                | AskForApproval::OnFailure
-                | AskForApproval::OnRequest
-                | AskForApproval::Granular(_) => "default",
-            }
-            .to_string();
             let session_start_request = codex_hooks::SessionStartRequest {
                 session_id: sess.conversation_id,
                 cwd: turn_context.cwd.clone(),
                 transcript_path: sess.current_rollout_path().await,
                 model: turn_context.model_info.slug.clone(),
-                permission_mode: session_start_permission_mode,
+                permission_mode: hook_permission_mode(turn_context.approval_policy.value()),
                 source: session_start_source,
             };
             for run in sess.hooks().preview_session_start(&session_start_request) {
@@ -5615,7 +6320,11 @@ pub(crate) async fn run_turn(
             }
             let session_start_outcome = sess
                 .hooks()
-                .run_session_start(session_start_request, Some(turn_context.sub_id.clone()))
+                .run_session_start(
+                    session_start_request,
+                    Some(turn_context.sub_id.clone()),
+                    &SessionHookModelRunner::new(Arc::clone(&sess), Arc::clone(&turn_context)),
+                )
                 .await;
             for completed in session_start_outcome.hook_events {
                 sess.send_event(&turn_context, EventMsg::HookCompleted(completed))
@@ -5684,20 +6393,47 @@ pub(crate) async fn run_turn(
             .map(|user_message| user_message.message())
             .collect::<Vec<String>>();
         let turn_metadata_header = turn_context.turn_metadata_state.current_header_value();
-        match run_sampling_request(
-            Arc::clone(&sess),
-            Arc::clone(&turn_context),
-            Arc::clone(&turn_diff_tracker),
-            &mut client_session,
-            turn_metadata_header.as_deref(),
-            sampling_request_input,
-            &turn_enabled_connectors,
-            skills_outcome,
-            &mut server_model_warning_emitted_for_turn,
-            cancellation_token.child_token(),
+        turn_context.run_budget.record_sampling_request();
+        let sampling_cancellation_token = cancellation_token.child_token();
+        let sampling_request_result = run_within_run_timeout(
+            &turn_context,
+            &sampling_cancellation_token,
+            run_sampling_request(
+                Arc::clone(&sess),
+                Arc::clone(&turn_context),
+                Arc::clone(&turn_diff_tracker),
+                &mut client_session,
+                turn_metadata_header.as_deref(),
+                sampling_request_input,
+                &turn_enabled_connectors,
+                skills_outcome,
+                &mut server_model_warning_emitted_for_turn,
+                sampling_cancellation_token.clone(),
+            ),
         )
-        .await
-        {
+        .await;
+
+        // Budgets only stop the agent from doing more work: a request that
+        // finished the turn, or one the user interrupted, is left alone.
+        let may_stop_for_budget = match &sampling_request_result {
+            Ok(output) => output.needs_follow_up,
+            Err(CodexErr::TurnAborted) => !cancellation_token.is_cancelled(),
+            Err(_) => false,
+        };
+        if may_stop_for_budget && let Some(budget) = turn_context.run_budget.exhausted() {
+            if let Ok(output) = sampling_request_result {
+                last_agent_message = output.last_agent_message;
+            }
+            info!(turn_id = %turn_context.sub_id, budget = budget.as_str(), "run budget exhausted");
+            sess.send_event(
+                &turn_context,
+                EventMsg::Error(turn_context.run_budget.exhausted_error(budget)),
+            )
+            .await;
+            break;
+        }
+
+        match sampling_request_result {
             Ok(sampling_request_output) => {
                 let SamplingRequestResult {
                     needs_follow_up,
@@ -5736,21 +6472,13 @@ pub(crate) async fn run_turn(
 
                 if !needs_follow_up {
                     last_agent_message = sampling_request_last_agent_message;
-                    let stop_hook_permission_mode = match turn_context.approval_policy.value() {
-                        AskForApproval::Never => "bypassPermissions",
-                        AskForApproval::UnlessTrusted
-                        | AskForApproval::OnFailure
-                        | AskForApproval::OnRequest
-                        | AskForApproval::Granular(_) => "default",
-                    }
-                    .to_string();
                     let stop_request = codex_hooks::StopRequest {
                         session_id: sess.conversation_id,
                         turn_id: turn_context.sub_id.clone(),
                         cwd: turn_context.cwd.clone(),
                         transcript_path: sess.current_rollout_path().await,
                         model: turn_context.model_info.slug.clone(),
-                        permission_mode: stop_hook_permission_mode,
+                        permission_mode: hook_permission_mode(turn_context.approval_policy.value()),
                         stop_hook_active,
                         last_assistant_message: last_agent_message.clone(),
                     };
@@ -5764,7 +6492,16 @@ pub(crate) async fn run_turn(
                         )
                         .await;
                     }
-                    let stop_outcome = sess.hooks().run_stop(stop_request).await;
+                    let stop_outcome = sess
+                        .hooks()
+                        .run_stop(
+                            stop_request,
+                            &SessionHookModelRunner::new(
+                                Arc::clone(&sess),
+                                Arc::clone(&turn_context),
+                            ),
+                        )
+                        .await;
                     for completed in stop_outcome.hook_events {
                         sess.send_event(&turn_context, EventMsg::HookCompleted(completed))
                             .await;
@@ -5882,6 +6619,27 @@ pub(crate) async fn run_turn(
     last_agent_message
 }
 
+/// Runs one sampling request until it completes or the run's
+/// `[run_budget]` timeout elapses. On timeout the request is cancelled and
+/// awaited so in-flight tool calls still record their output.
+async fn run_within_run_timeout<T>(
+    turn_context: &TurnContext,
+    cancellation_token: &CancellationToken,
+    request: impl Future<Output = T>,
+) -> T {
+    let Some(deadline) = turn_context.run_budget.deadline() else {
+        return request.await;
+    };
+    tokio::pin!(request);
+    tokio::select! {
+        output = &mut request => output,
+        () = tokio::time::sleep_until(deadline.into()) => {
+            cancellation_token.cancel();
+            request.await
+        }
+    }
+}
+
 async fn run_pre_sampling_compact(
     sess: &Arc<Session>,
     turn_context: &Arc<TurnContext>,
@@ -6142,6 +6900,7 @@ fn build_prompt(
         base_instructions,
         personality: turn_context.personality,
         output_schema: turn_context.final_output_json_schema.clone(),
+        max_output_tokens: None,
     }
 }
 #[allow(clippy::too_many_arguments)]
@@ -6631,6 +7390,7 @@ fn realtime_text_for_event(msg: &EventMsg) -> Option<String> {
         | EventMsg::DynamicToolCallRequest(_)
         | EventMsg::DynamicToolCallResponse(_)
         | EventMsg::ElicitationRequest(_)
+        | EventMsg::McpSamplingRequest(_)
         | EventMsg::ApplyPatchApprovalRequest(_)
         | EventMsg::DeprecationNotice(_)
         | EventMsg::BackgroundEvent(_)
@@ -6638,9 +7398,14 @@ fn realtime_text_for_event(msg: &EventMsg) -> Option<String> {
         | EventMsg::UndoCompleted(_)
         | EventMsg::StreamError(_)
         | EventMsg::TurnDiff(_)
+        | EventMsg::OverlayWorkspaceChanges(_)
+        | EventMsg::BackgroundServicesUpdated(_)
         | EventMsg::GetHistoryEntryResponse(_)
         | EventMsg::McpListToolsResponse(_)
+        | EventMsg::ListMcpPromptsResponse(_)
+        | EventMsg::GetMcpPromptResponse(_)
         | EventMsg::ListCustomPromptsResponse(_)
+        | EventMsg::ExpandCustomPromptResponse(_)
         | EventMsg::ListSkillsResponse(_)
         | EventMsg::ListRemoteSkillsResponse(_)
         | EventMsg::RemoteSkillDownloaded(_)
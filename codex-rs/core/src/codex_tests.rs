@@ -1,5 +1,6 @@
 use super::*;
 use crate::CodexAuth;
+use crate::background_services::BackgroundServices;
 use crate::config::ConfigBuilder;
 use crate::config::test_config;
 use crate::config_loader::ConfigLayerStack;
@@ -2027,6 +2028,7 @@ async fn session_new_fails_when_zsh_fork_enabled_without_zsh_path() {
         plugins_manager,
         mcp_manager,
         Arc::new(FileWatcher::noop()),
+        Arc::new(BackgroundServiceRegistry::default()),
         AgentControl::default(),
     )
     .await;
@@ -2153,6 +2155,9 @@ pub(crate) async fn make_session_and_context() -> (Session, TurnContext) {
         plugins_manager,
         mcp_manager,
         file_watcher,
+        repo_index_updater: RepoIndexUpdater::disabled(config.cwd.clone()),
+        tool_secrets: ToolSecrets::default(),
+        run_budget: agent_control.run_budget(config.run_budget),
         agent_control,
         network_proxy: None,
         network_approval: Arc::clone(&network_approval),
@@ -2171,6 +2176,9 @@ pub(crate) async fn make_session_and_context() -> (Session, TurnContext) {
         code_mode_service: crate::tools::code_mode::CodeModeService::new(
             config.js_repl_node_path.clone(),
         ),
+        overlay_workspace: None,
+        tool_output_store: None,
+        background_services: Arc::new(BackgroundServices::default()),
     };
     let js_repl = Arc::new(JsReplHandle::with_node_path(
         config.js_repl_node_path.clone(),
@@ -2190,6 +2198,7 @@ pub(crate) async fn make_session_and_context() -> (Session, TurnContext) {
         "turn_id".to_string(),
         Arc::clone(&js_repl),
         skills_outcome,
+        Arc::clone(&services.run_budget),
     );
 
     let session = Session {
@@ -2795,6 +2804,9 @@ pub(crate) async fn make_session_and_context_with_dynamic_tools_and_rx(
         plugins_manager,
         mcp_manager,
         file_watcher,
+        repo_index_updater: RepoIndexUpdater::disabled(config.cwd.clone()),
+        tool_secrets: ToolSecrets::default(),
+        run_budget: agent_control.run_budget(config.run_budget),
         agent_control,
         network_proxy: None,
         network_approval: Arc::clone(&network_approval),
@@ -2813,6 +2825,9 @@ pub(crate) async fn make_session_and_context_with_dynamic_tools_and_rx(
         code_mode_service: crate::tools::code_mode::CodeModeService::new(
             config.js_repl_node_path.clone(),
         ),
+        overlay_workspace: None,
+        tool_output_store: None,
+        background_services: Arc::new(BackgroundServices::default()),
     };
     let js_repl = Arc::new(JsReplHandle::with_node_path(
         config.js_repl_node_path.clone(),
@@ -2832,6 +2847,7 @@ pub(crate) async fn make_session_and_context_with_dynamic_tools_and_rx(
         "turn_id".to_string(),
         Arc::clone(&js_repl),
         skills_outcome,
+        Arc::clone(&services.run_budget),
     ));
 
     let session = Arc::new(Session {
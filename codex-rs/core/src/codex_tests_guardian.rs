@@ -376,6 +376,7 @@ async fn guardian_subagent_does_not_inherit_parent_exec_policy_rules() {
         plugins_manager,
         mcp_manager,
         file_watcher,
+        background_service_registry: Arc::new(BackgroundServiceRegistry::default()),
         conversation_history: InitialHistory::New,
         session_source: SessionSource::SubAgent(SubAgentSource::Other(
             GUARDIAN_SUBAGENT_NAME.to_string(),
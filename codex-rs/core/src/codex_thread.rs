@@ -11,6 +11,7 @@ use crate::protocol::Op;
 use crate::protocol::Submission;
 use codex_protocol::config_types::Personality;
 use codex_protocol::config_types::ServiceTier;
+use codex_protocol::mcp::Prompt as McpPrompt;
 use codex_protocol::models::ContentItem;
 use codex_protocol::models::ResponseInputItem;
 use codex_protocol::models::ResponseItem;
@@ -21,6 +22,7 @@ use codex_protocol::protocol::SessionSource;
 use codex_protocol::protocol::TokenUsage;
 use codex_protocol::protocol::W3cTraceContext;
 use codex_protocol::user_input::UserInput;
+use std::collections::HashMap;
 use std::path::PathBuf;
 use tokio::sync::Mutex;
 use tokio::sync::watch;
@@ -145,6 +147,24 @@ impl CodexThread {
             .await;
     }
 
+    /// Prompts published by the thread's MCP servers, keyed by server name.
+    pub async fn list_mcp_prompts(&self) -> HashMap<String, Vec<McpPrompt>> {
+        self.codex.session.list_mcp_prompts().await
+    }
+
+    /// Fetches an MCP prompt and returns its messages flattened to text.
+    pub async fn get_mcp_prompt(
+        &self,
+        server: &str,
+        prompt: &str,
+        arguments: HashMap<String, String>,
+    ) -> Result<String, String> {
+        self.codex
+            .session
+            .get_mcp_prompt(server, prompt, arguments)
+            .await
+    }
+
     pub fn rollout_path(&self) -> Option<PathBuf> {
         self.rollout_path.clone()
     }
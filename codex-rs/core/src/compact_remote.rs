@@ -112,6 +112,7 @@ async fn run_remote_compact_task_inner_impl(
         base_instructions,
         personality: turn_context.personality,
         output_schema: None,
+        max_output_tokens: None,
     };
 
     let mut new_history = sess
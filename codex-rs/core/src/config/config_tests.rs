@@ -10,6 +10,12 @@ use crate::config::types::MemoriesToml;
 use crate::config::types::ModelAvailabilityNuxConfig;
 use crate::config::types::NotificationMethod;
 use crate::config::types::Notifications;
+use crate::config::types::RunBudgetConfig;
+use crate::config::types::RunBudgetToml;
+use crate::config::types::SecretsBackendKind;
+use crate::config::types::SecretsConfig;
+use crate::config::types::SecretsToml;
+use crate::config::types::ServiceRestartPolicy;
 use crate::config_loader::RequirementSource;
 use crate::features::Feature;
 use assert_matches::assert_matches;
@@ -20,6 +26,7 @@ use codex_protocol::permissions::FileSystemSandboxEntry;
 use codex_protocol::permissions::FileSystemSandboxPolicy;
 use codex_protocol::permissions::FileSystemSpecialPath;
 use codex_protocol::permissions::NetworkSandboxPolicy;
+use codex_protocol::permissions::ResourceLimits;
 use serde::Deserialize;
 use tempfile::tempdir;
 
@@ -50,6 +57,8 @@ fn stdio_mcp(command: &str) -> McpServerConfig {
         disabled_tools: None,
         scopes: None,
         oauth_resource: None,
+        sampling: None,
+        sandbox: None,
     }
 }
 
@@ -70,6 +79,8 @@ fn http_mcp(url: &str) -> McpServerConfig {
         disabled_tools: None,
         scopes: None,
         oauth_resource: None,
+        sampling: None,
+        sandbox: None,
     }
 }
 
@@ -158,6 +169,166 @@ consolidation_model = "gpt-5"
     );
 }
 
+#[test]
+fn parses_secrets_config() {
+    let cfg: ConfigToml = toml::from_str(
+        r#"
+[secrets]
+backend = "local"
+expose = ["GITHUB_TOKEN", "NPM_TOKEN"]
+"#,
+    )
+    .expect("TOML deserialization should succeed");
+    assert_eq!(
+        Some(SecretsToml {
+            backend: Some(SecretsBackendKind::Local),
+            expose: Some(vec!["GITHUB_TOKEN".to_string(), "NPM_TOKEN".to_string()]),
+        }),
+        cfg.secrets
+    );
+
+    let config = Config::load_from_base_config_with_overrides(
+        cfg,
+        ConfigOverrides::default(),
+        tempdir().expect("tempdir").path().to_path_buf(),
+    )
+    .expect("load config from secrets settings");
+    assert_eq!(
+        config.secrets,
+        SecretsConfig {
+            backend: SecretsBackendKind::Local,
+            expose: vec!["GITHUB_TOKEN".to_string(), "NPM_TOKEN".to_string()],
+        }
+    );
+}
+
+#[test]
+fn parses_run_budget_config() {
+    let cfg: ConfigToml = toml::from_str(
+        r#"
+[run_budget]
+max_turns = 20
+max_total_tokens = 500000
+timeout_seconds = 600
+"#,
+    )
+    .expect("TOML deserialization should succeed");
+    assert_eq!(
+        Some(RunBudgetToml {
+            max_turns: Some(20),
+            max_output_tokens: None,
+            max_total_tokens: Some(500_000),
+            timeout_seconds: Some(600),
+            max_tool_calls: None,
+        }),
+        cfg.run_budget
+    );
+
+    let config = Config::load_from_base_config_with_overrides(
+        cfg,
+        ConfigOverrides::default(),
+        tempdir().expect("tempdir").path().to_path_buf(),
+    )
+    .expect("load config from run budget settings");
+    assert_eq!(
+        config.run_budget,
+        RunBudgetConfig {
+            max_turns: Some(20),
+            max_output_tokens: None,
+            max_total_tokens: Some(500_000),
+            timeout: Some(Duration::from_secs(600)),
+            max_tool_calls: None,
+        }
+    );
+}
+
+#[test]
+fn parses_services_config() {
+    let cfg: ConfigToml = toml::from_str(
+        r#"
+[services.db]
+command = ["postgres", "-D", "data"]
+restart = "always"
+health_check = { port = 5432 }
+
+[services.web]
+command = ["npm", "run", "dev"]
+cwd = "frontend"
+autostart = true
+health_check = { command = ["curl", "-sf", "http://localhost:3000"], interval_seconds = 30 }
+"#,
+    )
+    .expect("TOML deserialization should succeed");
+
+    let config = Config::load_from_base_config_with_overrides(
+        cfg,
+        ConfigOverrides::default(),
+        tempdir().expect("tempdir").path().to_path_buf(),
+    )
+    .expect("load config from services settings");
+    assert_eq!(
+        config.services,
+        BTreeMap::from([
+            (
+                "db".to_string(),
+                ServiceConfig {
+                    command: vec!["postgres".to_string(), "-D".to_string(), "data".to_string()],
+                    cwd: None,
+                    env: HashMap::new(),
+                    autostart: false,
+                    restart: ServiceRestartPolicy::Always,
+                    health_check: Some(ServiceHealthCheck {
+                        probe: ServiceHealthProbe::Port(5432),
+                        interval: Duration::from_secs(10),
+                    }),
+                },
+            ),
+            (
+                "web".to_string(),
+                ServiceConfig {
+                    command: vec!["npm".to_string(), "run".to_string(), "dev".to_string()],
+                    cwd: Some(PathBuf::from("frontend")),
+                    env: HashMap::new(),
+                    autostart: true,
+                    restart: ServiceRestartPolicy::OnFailure,
+                    health_check: Some(ServiceHealthCheck {
+                        probe: ServiceHealthProbe::Command(vec![
+                            "curl".to_string(),
+                            "-sf".to_string(),
+                            "http://localhost:3000".to_string(),
+                        ]),
+                        interval: Duration::from_secs(30),
+                    }),
+                },
+            ),
+        ])
+    );
+}
+
+#[test]
+fn rejects_service_health_check_with_both_probes() {
+    let cfg: ConfigToml = toml::from_str(
+        r#"
+[services.db]
+command = ["postgres"]
+health_check = { command = ["pg_isready"], port = 5432 }
+"#,
+    )
+    .expect("TOML deserialization should succeed");
+
+    let err = Config::load_from_base_config_with_overrides(
+        cfg,
+        ConfigOverrides::default(),
+        tempdir().expect("tempdir").path().to_path_buf(),
+    )
+    .expect_err("expected an invalid health check");
+    assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    assert_eq!(
+        err.to_string(),
+        "services.db.health_check must set exactly one of `command` or `port`"
+    );
+}
+
 #[test]
 fn parses_bundled_skills_config() {
     let cfg: ConfigToml = toml::from_str(
@@ -319,6 +490,7 @@ allowed_domains = ["openai.com"]
                         allow_unix_sockets: None,
                         allow_local_binding: None,
                     }),
+                    resources: None,
                 },
             )]),
         }
@@ -350,6 +522,7 @@ fn permissions_profiles_network_populates_runtime_network_proxy_spec() -> std::i
                             enable_socks5: Some(false),
                             ..Default::default()
                         }),
+                        resources: None,
                     },
                 )]),
             }),
@@ -395,6 +568,7 @@ fn permissions_profiles_network_disabled_by_default_does_not_start_proxy() -> st
                             allowed_domains: Some(vec!["openai.com".to_string()]),
                             ..Default::default()
                         }),
+                        resources: None,
                     },
                 )]),
             }),
@@ -440,6 +614,7 @@ fn default_permissions_profile_populates_runtime_sandbox_policy() -> std::io::Re
                         ]),
                     }),
                     network: None,
+                    resources: None,
                 },
             )]),
         }),
@@ -526,6 +701,7 @@ fn permissions_profiles_require_default_permissions() -> std::io::Result<()> {
                             )]),
                         }),
                         network: None,
+                        resources: None,
                     },
                 )]),
             }),
@@ -568,6 +744,7 @@ fn permissions_profiles_reject_writes_outside_workspace_root() -> std::io::Resul
                             )]),
                         }),
                         network: None,
+                        resources: None,
                     },
                 )]),
             }),
@@ -613,6 +790,7 @@ fn permissions_profiles_reject_nested_entries_for_non_project_roots() -> std::io
                             )]),
                         }),
                         network: None,
+                        resources: None,
                     },
                 )]),
             }),
@@ -665,6 +843,7 @@ fn permissions_profiles_allow_unknown_special_paths() -> std::io::Result<()> {
             )]),
         }),
         network: None,
+        resources: None,
     })?;
 
     assert_eq!(
@@ -696,6 +875,119 @@ fn permissions_profiles_allow_unknown_special_paths() -> std::io::Result<()> {
     Ok(())
 }
 
+#[test]
+fn permissions_profiles_compile_resource_limits() -> std::io::Result<()> {
+    let config = load_workspace_permission_profile(PermissionProfileToml {
+        filesystem: None,
+        network: None,
+        resources: Some(ResourceLimitsToml {
+            memory_max_mb: Some(2048),
+            cpu_max_percent: Some(150),
+            pids_max: Some(256),
+            wall_time_seconds: None,
+        }),
+    })?;
+
+    assert_eq!(
+        config.permissions.resource_limits,
+        ResourceLimits {
+            memory_max_bytes: Some(2048 * 1024 * 1024),
+            cpu_max_percent: Some(150),
+            pids_max: Some(256),
+            wall_time_seconds: None,
+        }
+    );
+    Ok(())
+}
+
+#[test]
+fn permissions_profiles_reject_zero_resource_limits() {
+    let err = load_workspace_permission_profile(PermissionProfileToml {
+        filesystem: None,
+        network: None,
+        resources: Some(ResourceLimitsToml {
+            pids_max: Some(0),
+            ..Default::default()
+        }),
+    })
+    .expect_err("zero resource limits should be rejected");
+
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+    assert_eq!(
+        err.to_string(),
+        "resource limit `pids_max` must be greater than zero"
+    );
+}
+
+#[test]
+fn permissions_profiles_compile_glob_deny_entries() -> std::io::Result<()> {
+    let config = load_workspace_permission_profile(PermissionProfileToml {
+        filesystem: Some(FilesystemPermissionsToml {
+            entries: BTreeMap::from([
+                (
+                    "**/.env*".to_string(),
+                    FilesystemPermissionToml::Access(FileSystemAccessMode::None),
+                ),
+                (
+                    ":project_roots".to_string(),
+                    FilesystemPermissionToml::Scoped(BTreeMap::from([
+                        (".".to_string(), FileSystemAccessMode::Write),
+                        ("**/*.pem".to_string(), FileSystemAccessMode::None),
+                    ])),
+                ),
+            ]),
+        }),
+        network: None,
+        resources: None,
+    })?;
+
+    assert_eq!(
+        config.permissions.file_system_sandbox_policy,
+        FileSystemSandboxPolicy::restricted(vec![
+            FileSystemSandboxEntry {
+                path: FileSystemPath::Glob {
+                    pattern: "**/.env*".to_string(),
+                },
+                access: FileSystemAccessMode::None,
+            },
+            FileSystemSandboxEntry {
+                path: FileSystemPath::Glob {
+                    pattern: "**/*.pem".to_string(),
+                },
+                access: FileSystemAccessMode::None,
+            },
+            FileSystemSandboxEntry {
+                path: FileSystemPath::Special {
+                    value: FileSystemSpecialPath::project_roots(None),
+                },
+                access: FileSystemAccessMode::Write,
+            },
+        ])
+    );
+    Ok(())
+}
+
+#[test]
+fn permissions_profiles_reject_glob_entries_that_grant_access() {
+    let err = load_workspace_permission_profile(PermissionProfileToml {
+        filesystem: Some(FilesystemPermissionsToml {
+            entries: BTreeMap::from([(
+                "**/*.rs".to_string(),
+                FilesystemPermissionToml::Access(FileSystemAccessMode::Read),
+            )]),
+        }),
+        network: None,
+        resources: None,
+    })
+    .expect_err("glob entries must deny access");
+
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+    assert_eq!(
+        err.to_string(),
+        "filesystem glob `**/*.rs` only supports `none` access"
+    );
+}
+
 #[test]
 fn permissions_profiles_allow_unknown_special_paths_with_nested_entries() -> std::io::Result<()> {
     let config = load_workspace_permission_profile(PermissionProfileToml {
@@ -709,6 +1001,7 @@ fn permissions_profiles_allow_unknown_special_paths_with_nested_entries() -> std
             )]),
         }),
         network: None,
+        resources: None,
     })?;
 
     assert_eq!(
@@ -735,6 +1028,7 @@ fn permissions_profiles_allow_missing_filesystem_with_warning() -> std::io::Resu
     let config = load_workspace_permission_profile(PermissionProfileToml {
         filesystem: None,
         network: None,
+        resources: None,
     })?;
 
     assert_eq!(
@@ -768,6 +1062,7 @@ fn permissions_profiles_allow_empty_filesystem_with_warning() -> std::io::Result
             entries: BTreeMap::new(),
         }),
         network: None,
+        resources: None,
     })?;
 
     assert_eq!(
@@ -807,6 +1102,7 @@ fn permissions_profiles_reject_project_root_parent_traversal() -> std::io::Resul
                             )]),
                         }),
                         network: None,
+                        resources: None,
                     },
                 )]),
             }),
@@ -851,6 +1147,7 @@ fn permissions_profiles_allow_network_enablement() -> std::io::Result<()> {
                             enabled: Some(true),
                             ..Default::default()
                         }),
+                        resources: None,
                     },
                 )]),
             }),
@@ -1829,6 +2126,8 @@ async fn replace_mcp_servers_round_trips_entries() -> anyhow::Result<()> {
             disabled_tools: None,
             scopes: None,
             oauth_resource: None,
+            sampling: None,
+            sandbox: None,
         },
     );
 
@@ -1985,6 +2284,8 @@ async fn replace_mcp_servers_serializes_env_sorted() -> anyhow::Result<()> {
             disabled_tools: None,
             scopes: None,
             oauth_resource: None,
+            sampling: None,
+            sandbox: None,
         },
     )]);
 
@@ -2057,6 +2358,8 @@ async fn replace_mcp_servers_serializes_env_vars() -> anyhow::Result<()> {
             disabled_tools: None,
             scopes: None,
             oauth_resource: None,
+            sampling: None,
+            sandbox: None,
         },
     )]);
 
@@ -2109,6 +2412,8 @@ async fn replace_mcp_servers_serializes_cwd() -> anyhow::Result<()> {
             disabled_tools: None,
             scopes: None,
             oauth_resource: None,
+            sampling: None,
+            sandbox: None,
         },
     )]);
 
@@ -2159,6 +2464,8 @@ async fn replace_mcp_servers_streamable_http_serializes_bearer_token() -> anyhow
             disabled_tools: None,
             scopes: None,
             oauth_resource: None,
+            sampling: None,
+            sandbox: None,
         },
     )]);
 
@@ -2225,6 +2532,8 @@ async fn replace_mcp_servers_streamable_http_serializes_custom_headers() -> anyh
             disabled_tools: None,
             scopes: None,
             oauth_resource: None,
+            sampling: None,
+            sandbox: None,
         },
     )]);
     apply_blocking(
@@ -2303,6 +2612,8 @@ async fn replace_mcp_servers_streamable_http_removes_optional_sections() -> anyh
             disabled_tools: None,
             scopes: None,
             oauth_resource: None,
+            sampling: None,
+            sandbox: None,
         },
     )]);
 
@@ -2334,6 +2645,8 @@ async fn replace_mcp_servers_streamable_http_removes_optional_sections() -> anyh
             disabled_tools: None,
             scopes: None,
             oauth_resource: None,
+            sampling: None,
+            sandbox: None,
         },
     );
     apply_blocking(
@@ -2400,6 +2713,8 @@ async fn replace_mcp_servers_streamable_http_isolates_headers_between_servers()
                 disabled_tools: None,
                 scopes: None,
                 oauth_resource: None,
+                sampling: None,
+                sandbox: None,
             },
         ),
         (
@@ -2421,6 +2736,8 @@ async fn replace_mcp_servers_streamable_http_isolates_headers_between_servers()
                 disabled_tools: None,
                 scopes: None,
                 oauth_resource: None,
+                sampling: None,
+                sandbox: None,
             },
         ),
     ]);
@@ -2505,6 +2822,8 @@ async fn replace_mcp_servers_serializes_disabled_flag() -> anyhow::Result<()> {
             disabled_tools: None,
             scopes: None,
             oauth_resource: None,
+            sampling: None,
+            sandbox: None,
         },
     )]);
 
@@ -2551,6 +2870,8 @@ async fn replace_mcp_servers_serializes_required_flag() -> anyhow::Result<()> {
             disabled_tools: None,
             scopes: None,
             oauth_resource: None,
+            sampling: None,
+            sandbox: None,
         },
     )]);
 
@@ -2597,6 +2918,8 @@ async fn replace_mcp_servers_serializes_tool_filters() -> anyhow::Result<()> {
             disabled_tools: Some(vec!["blocked".to_string()]),
             scopes: None,
             oauth_resource: None,
+            sampling: None,
+            sandbox: None,
         },
     )]);
 
@@ -2647,6 +2970,8 @@ async fn replace_mcp_servers_streamable_http_serializes_oauth_resource() -> anyh
             disabled_tools: None,
             scopes: None,
             oauth_resource: Some("https://resource.example.com".to_string()),
+            sampling: None,
+            sandbox: None,
         },
     )]);
 
@@ -4012,6 +4337,7 @@ model_verbosity = "high"
         request_max_retries: Some(4),
         stream_max_retries: Some(10),
         stream_idle_timeout_ms: Some(300_000),
+        max_output_tokens: None,
         requires_openai_auth: false,
         supports_websockets: false,
     };
@@ -4083,6 +4409,7 @@ fn test_precedence_fixture_with_o3_profile() -> std::io::Result<()> {
                 shell_environment_policy: ShellEnvironmentPolicy::default(),
                 windows_sandbox_mode: None,
                 macos_seatbelt_profile_extensions: None,
+                resource_limits: ResourceLimits::default(),
             },
             enforce_residency: Constrained::allow_any(None),
             user_instructions: None,
@@ -4101,6 +4428,9 @@ fn test_precedence_fixture_with_o3_profile() -> std::io::Result<()> {
             agent_max_depth: DEFAULT_AGENT_MAX_DEPTH,
             agent_roles: BTreeMap::new(),
             memories: MemoriesConfig::default(),
+            secrets: SecretsConfig::default(),
+            run_budget: RunBudgetConfig::default(),
+            services: BTreeMap::new(),
             agent_job_max_runtime_seconds: DEFAULT_AGENT_JOB_MAX_RUNTIME_SECONDS,
             codex_home: fixture.codex_home(),
             sqlite_home: fixture.codex_home(),
@@ -4221,6 +4551,7 @@ fn test_precedence_fixture_with_gpt3_profile() -> std::io::Result<()> {
             shell_environment_policy: ShellEnvironmentPolicy::default(),
             windows_sandbox_mode: None,
             macos_seatbelt_profile_extensions: None,
+            resource_limits: ResourceLimits::default(),
         },
         enforce_residency: Constrained::allow_any(None),
         user_instructions: None,
@@ -4239,6 +4570,9 @@ fn test_precedence_fixture_with_gpt3_profile() -> std::io::Result<()> {
         agent_max_depth: DEFAULT_AGENT_MAX_DEPTH,
         agent_roles: BTreeMap::new(),
         memories: MemoriesConfig::default(),
+        secrets: SecretsConfig::default(),
+        run_budget: RunBudgetConfig::default(),
+        services: BTreeMap::new(),
         agent_job_max_runtime_seconds: DEFAULT_AGENT_JOB_MAX_RUNTIME_SECONDS,
         codex_home: fixture.codex_home(),
         sqlite_home: fixture.codex_home(),
@@ -4357,6 +4691,7 @@ fn test_precedence_fixture_with_zdr_profile() -> std::io::Result<()> {
             shell_environment_policy: ShellEnvironmentPolicy::default(),
             windows_sandbox_mode: None,
             macos_seatbelt_profile_extensions: None,
+            resource_limits: ResourceLimits::default(),
         },
         enforce_residency: Constrained::allow_any(None),
         user_instructions: None,
@@ -4375,6 +4710,9 @@ fn test_precedence_fixture_with_zdr_profile() -> std::io::Result<()> {
         agent_max_depth: DEFAULT_AGENT_MAX_DEPTH,
         agent_roles: BTreeMap::new(),
         memories: MemoriesConfig::default(),
+        secrets: SecretsConfig::default(),
+        run_budget: RunBudgetConfig::default(),
+        services: BTreeMap::new(),
         agent_job_max_runtime_seconds: DEFAULT_AGENT_JOB_MAX_RUNTIME_SECONDS,
         codex_home: fixture.codex_home(),
         sqlite_home: fixture.codex_home(),
@@ -4479,6 +4817,7 @@ fn test_precedence_fixture_with_gpt5_profile() -> std::io::Result<()> {
             shell_environment_policy: ShellEnvironmentPolicy::default(),
             windows_sandbox_mode: None,
             macos_seatbelt_profile_extensions: None,
+            resource_limits: ResourceLimits::default(),
         },
         enforce_residency: Constrained::allow_any(None),
         user_instructions: None,
@@ -4497,6 +4836,9 @@ fn test_precedence_fixture_with_gpt5_profile() -> std::io::Result<()> {
         agent_max_depth: DEFAULT_AGENT_MAX_DEPTH,
         agent_roles: BTreeMap::new(),
         memories: MemoriesConfig::default(),
+        secrets: SecretsConfig::default(),
+        run_budget: RunBudgetConfig::default(),
+        services: BTreeMap::new(),
         agent_job_max_runtime_seconds: DEFAULT_AGENT_JOB_MAX_RUNTIME_SECONDS,
         codex_home: fixture.codex_home(),
         sqlite_home: fixture.codex_home(),
@@ -103,6 +103,7 @@ pub fn model_availability_nux_count_edits(shown_count: &HashMap<String, u32>) ->
 // TODO(jif) move to a dedicated file
 mod document_helpers {
     use crate::config::types::McpServerConfig;
+    use crate::config::types::McpServerSandboxPermissions;
     use crate::config::types::McpServerTransportConfig;
     use toml_edit::Array as TomlArray;
     use toml_edit::InlineTable;
@@ -225,6 +226,57 @@ mod document_helpers {
         {
             entry["oauth_resource"] = value(resource.clone());
         }
+        if let Some(sampling) = &config.sampling {
+            let mut table = TomlTable::new();
+            table.set_implicit(false);
+            table.insert("enabled", value(sampling.enabled));
+            table.insert("max_tokens", value(i64::from(sampling.max_tokens)));
+            if let Some(max_total_tokens) = sampling.max_total_tokens {
+                table.insert(
+                    "max_total_tokens",
+                    value(i64::try_from(max_total_tokens).unwrap_or(i64::MAX)),
+                );
+            }
+            entry["sampling"] = TomlItem::Table(table);
+        }
+        if let Some(sandbox) = &config.sandbox {
+            let mut table = TomlTable::new();
+            table.set_implicit(false);
+            let permissions = match sandbox.permissions {
+                McpServerSandboxPermissions::ReadOnly => "read-only",
+                McpServerSandboxPermissions::WorkspaceWrite => "workspace-write",
+            };
+            table.insert("permissions", value(permissions));
+            if !sandbox.writable_roots.is_empty() {
+                table.insert(
+                    "writable_roots",
+                    array_from_iter(
+                        sandbox
+                            .writable_roots
+                            .iter()
+                            .map(|root| root.to_string_lossy().into_owned()),
+                    ),
+                );
+            }
+            if !sandbox.readable_roots.is_empty() {
+                table.insert(
+                    "readable_roots",
+                    array_from_iter(
+                        sandbox
+                            .readable_roots
+                            .iter()
+                            .map(|root| root.to_string_lossy().into_owned()),
+                    ),
+                );
+            }
+            if !sandbox.allowed_domains.is_empty() {
+                table.insert(
+                    "allowed_domains",
+                    array_from_iter(sandbox.allowed_domains.iter().cloned()),
+                );
+            }
+            entry["sandbox"] = TomlItem::Table(table);
+        }
 
         entry
     }
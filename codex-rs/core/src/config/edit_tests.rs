@@ -1,6 +1,10 @@
 use super::*;
+use crate::config::types::McpServerSamplingConfig;
+use crate::config::types::McpServerSandboxConfig;
+use crate::config::types::McpServerSandboxPermissions;
 use crate::config::types::McpServerTransportConfig;
 use codex_protocol::openai_models::ReasoningEffort;
+use codex_utils_absolute_path::AbsolutePathBuf;
 use pretty_assertions::assert_eq;
 #[cfg(unix)]
 use std::os::unix::fs::symlink;
@@ -561,6 +565,21 @@ fn blocking_replace_mcp_servers_round_trips() {
             disabled_tools: None,
             scopes: None,
             oauth_resource: None,
+            sampling: Some(McpServerSamplingConfig {
+                enabled: true,
+                max_tokens: 512,
+                max_total_tokens: Some(4096),
+            }),
+            sandbox: Some(McpServerSandboxConfig {
+                permissions: McpServerSandboxPermissions::WorkspaceWrite,
+                writable_roots: vec![
+                    AbsolutePathBuf::from_absolute_path("/var/cache/cmd").expect("absolute path"),
+                ],
+                readable_roots: vec![
+                    AbsolutePathBuf::from_absolute_path("/opt/cmd").expect("absolute path"),
+                ],
+                allowed_domains: vec!["api.example.com".to_string()],
+            }),
         },
     );
 
@@ -586,6 +605,8 @@ fn blocking_replace_mcp_servers_round_trips() {
             disabled_tools: Some(vec!["forbidden".to_string()]),
             scopes: None,
             oauth_resource: Some("https://resource.example.com".to_string()),
+            sampling: None,
+            sandbox: None,
         },
     );
 
@@ -618,6 +639,17 @@ enabled_tools = [\"one\", \"two\"]
 [mcp_servers.stdio.env]
 A = \"1\"
 B = \"2\"
+
+[mcp_servers.stdio.sampling]
+enabled = true
+max_tokens = 512
+max_total_tokens = 4096
+
+[mcp_servers.stdio.sandbox]
+permissions = \"workspace-write\"
+writable_roots = [\"/var/cache/cmd\"]
+readable_roots = [\"/opt/cmd\"]
+allowed_domains = [\"api.example.com\"]
 ";
     assert_eq!(raw, expected);
 }
@@ -655,6 +687,8 @@ foo = { command = "cmd" }
             disabled_tools: None,
             scopes: None,
             oauth_resource: None,
+            sampling: None,
+            sandbox: None,
         },
     );
 
@@ -700,6 +734,8 @@ foo = { command = "cmd" } # keep me
             disabled_tools: None,
             scopes: None,
             oauth_resource: None,
+            sampling: None,
+            sandbox: None,
         },
     );
 
@@ -744,6 +780,8 @@ foo = { command = "cmd", args = ["--flag"] } # keep me
             disabled_tools: None,
             scopes: None,
             oauth_resource: None,
+            sampling: None,
+            sandbox: None,
         },
     );
 
@@ -789,6 +827,8 @@ foo = { command = "cmd" }
             disabled_tools: None,
             scopes: None,
             oauth_resource: None,
+            sampling: None,
+            sandbox: None,
         },
     );
 
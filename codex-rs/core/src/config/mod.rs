@@ -1,4 +1,5 @@
 use crate::auth::AuthCredentialsStoreMode;
+use crate::background_services::is_valid_service_name;
 use crate::config::edit::ConfigEdit;
 use crate::config::edit::ConfigEditsBuilder;
 use crate::config::types::AppsConfigToml;
@@ -19,7 +20,15 @@ use crate::config::types::OtelExporterKind;
 use crate::config::types::PluginConfig;
 use crate::config::types::QueryProjectIndex;
 use crate::config::types::QueryProjectIndexQdrant;
+use crate::config::types::RunBudgetConfig;
+use crate::config::types::RunBudgetToml;
 use crate::config::types::SandboxWorkspaceWrite;
+use crate::config::types::SecretsConfig;
+use crate::config::types::SecretsToml;
+use crate::config::types::ServiceConfig;
+use crate::config::types::ServiceHealthCheck;
+use crate::config::types::ServiceHealthProbe;
+use crate::config::types::ServiceToml;
 use crate::config::types::ShellEnvironmentPolicy;
 use crate::config::types::ShellEnvironmentPolicyToml;
 use crate::config::types::SkillsConfig;
@@ -79,6 +88,7 @@ use codex_protocol::openai_models::ModelsResponse;
 use codex_protocol::openai_models::ReasoningEffort;
 use codex_protocol::permissions::FileSystemSandboxPolicy;
 use codex_protocol::permissions::NetworkSandboxPolicy;
+use codex_protocol::permissions::ResourceLimits;
 use codex_rmcp_client::OAuthCredentialsStoreMode;
 use codex_utils_absolute_path::AbsolutePathBuf;
 use codex_utils_absolute_path::AbsolutePathBufGuard;
@@ -92,8 +102,10 @@ use std::collections::HashMap;
 use std::io::ErrorKind;
 use std::path::Path;
 use std::path::PathBuf;
+use std::time::Duration;
 
 use crate::config::permissions::compile_permission_profile;
+use crate::config::permissions::compile_resource_limits;
 use crate::config::permissions::network_proxy_config_from_profile_network;
 use crate::config::profile::ConfigProfile;
 use codex_network_proxy::NetworkProxyConfig;
@@ -122,6 +134,7 @@ pub use permissions::FilesystemPermissionsToml;
 pub use permissions::NetworkToml;
 pub use permissions::PermissionProfileToml;
 pub use permissions::PermissionsToml;
+pub use permissions::ResourceLimitsToml;
 pub(crate) use permissions::resolve_permission_profile;
 pub use service::ConfigService;
 pub use service::ConfigServiceError;
@@ -194,6 +207,9 @@ pub struct Permissions {
     /// Optional macOS seatbelt extension profile used to extend default
     /// seatbelt permissions when running under seatbelt.
     pub macos_seatbelt_profile_extensions: Option<MacOsSeatbeltProfileExtensions>,
+    /// Per-command resource limits from the active permissions profile,
+    /// enforced by the Linux sandbox helper.
+    pub resource_limits: ResourceLimits,
 }
 
 /// Application configuration loaded from disk and merged with overrides.
@@ -387,6 +403,15 @@ pub struct Config {
     /// Memories subsystem settings.
     pub memories: MemoriesConfig,
 
+    /// Stored secrets exposed to shell tool runs.
+    pub secrets: SecretsConfig,
+
+    /// Limits that stop a run early (`[run_budget]`).
+    pub run_budget: RunBudgetConfig,
+
+    /// Long-running services declared under `[services.<name>]`.
+    pub services: BTreeMap<String, ServiceConfig>,
+
     /// Directory containing all Codex state (defaults to `~/.codex` but can be
     /// overridden by the `CODEX_HOME` environment variable).
     pub codex_home: PathBuf,
@@ -1278,6 +1303,17 @@ pub struct ConfigToml {
     /// Memories subsystem settings.
     pub memories: Option<MemoriesToml>,
 
+    /// Stored secrets exposed to shell tool runs.
+    pub secrets: Option<SecretsToml>,
+
+    /// Limits that stop a run early, mainly for unattended `codex exec` runs.
+    pub run_budget: Option<RunBudgetToml>,
+
+    /// Long-running services (databases, dev servers, watchers) managed per
+    /// thread, keyed by service name.
+    #[serde(default)]
+    pub services: BTreeMap<String, ServiceToml>,
+
     /// User-level skill config entries keyed by SKILL.md path.
     pub skills: Option<SkillsConfig>,
 
@@ -1821,6 +1857,65 @@ fn resolve_web_search_config(
     }
 }
 
+pub(crate) const DEFAULT_SERVICE_HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Validates `[services.<name>]` tables and applies their defaults.
+fn resolve_services(
+    services: &BTreeMap<String, ServiceToml>,
+) -> std::io::Result<BTreeMap<String, ServiceConfig>> {
+    let invalid = |message: String| std::io::Error::new(std::io::ErrorKind::InvalidInput, message);
+    services
+        .iter()
+        .map(|(name, service)| {
+            if !is_valid_service_name(name) {
+                return Err(invalid(format!(
+                    "services.{name}: service names may only contain letters, digits, `-` and `_`"
+                )));
+            }
+            if service.command.is_empty() {
+                return Err(invalid(format!("services.{name}.command must not be empty")));
+            }
+            let health_check = match &service.health_check {
+                None => None,
+                Some(check) => {
+                    let probe = match (&check.command, check.port) {
+                        (Some(command), None) if !command.is_empty() => {
+                            ServiceHealthProbe::Command(command.clone())
+                        }
+                        (None, Some(port)) => ServiceHealthProbe::Port(port),
+                        _ => {
+                            return Err(invalid(format!(
+                                "services.{name}.health_check must set exactly one of `command` or `port`"
+                            )));
+                        }
+                    };
+                    let interval = check
+                        .interval_seconds
+                        .map(Duration::from_secs)
+                        .unwrap_or(DEFAULT_SERVICE_HEALTH_CHECK_INTERVAL);
+                    if interval.is_zero() {
+                        return Err(invalid(format!(
+                            "services.{name}.health_check.interval_seconds must be at least 1"
+                        )));
+                    }
+                    Some(ServiceHealthCheck { probe, interval })
+                }
+            };
+            Ok((
+                name.clone(),
+                ServiceConfig {
+                    command: service.command.clone(),
+                    cwd: service.cwd.clone(),
+                    env: service.env.clone().unwrap_or_default(),
+                    autostart: service.autostart.unwrap_or(false),
+                    restart: service.restart.unwrap_or_default(),
+                    health_check,
+                },
+            ))
+        })
+        .collect()
+}
+
 pub(crate) fn resolve_web_search_mode_for_turn(
     web_search_mode: &Constrained<WebSearchMode>,
     sandbox_policy: &SandboxPolicy,
@@ -2015,6 +2110,7 @@ impl Config {
             sandbox_policy,
             file_system_sandbox_policy,
             network_sandbox_policy,
+            resource_limits,
         ) = if profiles_are_active {
             let permissions = cfg.permissions.as_ref().ok_or_else(|| {
                 std::io::Error::new(
@@ -2031,6 +2127,7 @@ impl Config {
             let profile = resolve_permission_profile(permissions, default_permissions)?;
             let configured_network_proxy_config =
                 network_proxy_config_from_profile_network(profile.network.as_ref());
+            let resource_limits = compile_resource_limits(profile)?;
             let (mut file_system_sandbox_policy, network_sandbox_policy) =
                 compile_permission_profile(
                     permissions,
@@ -2052,6 +2149,7 @@ impl Config {
                 sandbox_policy,
                 file_system_sandbox_policy,
                 network_sandbox_policy,
+                resource_limits,
             )
         } else {
             let configured_network_proxy_config = NetworkProxyConfig::default();
@@ -2077,6 +2175,7 @@ impl Config {
                 sandbox_policy,
                 file_system_sandbox_policy,
                 network_sandbox_policy,
+                ResourceLimits::default(),
             )
         };
         let approval_policy_was_explicit = approval_policy_override.is_some()
@@ -2109,6 +2208,7 @@ impl Config {
 
         let agent_roles =
             agent_roles::load_agent_roles(&cfg, &config_layer_stack, &mut startup_warnings)?;
+        let services = resolve_services(&cfg.services)?;
 
         let mut model_providers = built_in_model_providers();
         // Merge user-defined providers into the built-in list.
@@ -2301,6 +2401,7 @@ impl Config {
             QueryProjectIndex {
                 backend: raw.backend,
                 auto_warm: raw.auto_warm,
+                auto_update: raw.auto_update,
                 require_embeddings: raw.require_embeddings,
                 embedding_model: raw.embedding_model.and_then(|model| {
                     let trimmed = model.trim();
@@ -2460,6 +2561,7 @@ impl Config {
                 shell_environment_policy,
                 windows_sandbox_mode,
                 macos_seatbelt_profile_extensions: None,
+                resource_limits,
             },
             enforce_residency: enforce_residency.value,
             notify: cfg.notify,
@@ -2498,6 +2600,9 @@ impl Config {
             agent_max_depth,
             agent_roles,
             memories: cfg.memories.unwrap_or_default().into(),
+            secrets: cfg.secrets.unwrap_or_default().into(),
+            run_budget: cfg.run_budget.unwrap_or_default().into(),
+            services,
             agent_job_max_runtime_seconds,
             codex_home,
             sqlite_home,
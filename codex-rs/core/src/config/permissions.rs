@@ -13,6 +13,8 @@ use codex_protocol::permissions::FileSystemSandboxEntry;
 use codex_protocol::permissions::FileSystemSandboxPolicy;
 use codex_protocol::permissions::FileSystemSpecialPath;
 use codex_protocol::permissions::NetworkSandboxPolicy;
+use codex_protocol::permissions::ResourceLimits;
+use codex_protocol::permissions::is_glob_pattern;
 use codex_utils_absolute_path::AbsolutePathBuf;
 use schemars::JsonSchema;
 use serde::Deserialize;
@@ -35,6 +37,7 @@ impl PermissionsToml {
 pub struct PermissionProfileToml {
     pub filesystem: Option<FilesystemPermissionsToml>,
     pub network: Option<NetworkToml>,
+    pub resources: Option<ResourceLimitsToml>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, JsonSchema)]
@@ -75,6 +78,60 @@ pub struct NetworkToml {
     pub allow_local_binding: Option<bool>,
 }
 
+/// Per-command resource limits for sandboxed commands on Linux.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct ResourceLimitsToml {
+    /// Maximum memory per command, in MiB.
+    pub memory_max_mb: Option<u64>,
+    /// CPU quota as a percentage of one core, so `200` allows two full cores.
+    pub cpu_max_percent: Option<u64>,
+    /// Maximum number of processes and threads per command.
+    pub pids_max: Option<u64>,
+    /// Wall-clock limit per command, in seconds.
+    pub wall_time_seconds: Option<u64>,
+}
+
+impl ResourceLimitsToml {
+    pub(crate) fn to_resource_limits(self) -> io::Result<ResourceLimits> {
+        let Self {
+            memory_max_mb,
+            cpu_max_percent,
+            pids_max,
+            wall_time_seconds,
+        } = self;
+        for (key, value) in [
+            ("memory_max_mb", memory_max_mb),
+            ("cpu_max_percent", cpu_max_percent),
+            ("pids_max", pids_max),
+            ("wall_time_seconds", wall_time_seconds),
+        ] {
+            if value == Some(0) {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("resource limit `{key}` must be greater than zero"),
+                ));
+            }
+        }
+        let memory_max_bytes = memory_max_mb
+            .map(|memory_max_mb| {
+                memory_max_mb.checked_mul(1024 * 1024).ok_or_else(|| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        format!("resource limit `memory_max_mb` is too large: {memory_max_mb}"),
+                    )
+                })
+            })
+            .transpose()?;
+        Ok(ResourceLimits {
+            memory_max_bytes,
+            cpu_max_percent,
+            pids_max,
+            wall_time_seconds,
+        })
+    }
+}
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
 #[serde(rename_all = "lowercase")]
 enum NetworkModeSchema {
@@ -156,6 +213,17 @@ pub(crate) fn resolve_permission_profile<'a>(
     })
 }
 
+/// Resource limits declared by a permissions profile; empty when unset.
+pub(crate) fn compile_resource_limits(
+    profile: &PermissionProfileToml,
+) -> io::Result<ResourceLimits> {
+    profile
+        .resources
+        .map(ResourceLimitsToml::to_resource_limits)
+        .transpose()
+        .map(Option::unwrap_or_default)
+}
+
 pub(crate) fn compile_permission_profile(
     permissions: &PermissionsToml,
     profile_name: &str,
@@ -208,22 +276,46 @@ fn compile_filesystem_permission(
     startup_warnings: &mut Vec<String>,
 ) -> io::Result<()> {
     match permission {
-        FilesystemPermissionToml::Access(access) => entries.push(FileSystemSandboxEntry {
-            path: compile_filesystem_path(path, startup_warnings)?,
-            access: *access,
-        }),
+        FilesystemPermissionToml::Access(access) => entries.push(filesystem_entry(
+            compile_filesystem_path(path, startup_warnings)?,
+            *access,
+        )?),
         FilesystemPermissionToml::Scoped(scoped_entries) => {
             for (subpath, access) in scoped_entries {
-                entries.push(FileSystemSandboxEntry {
-                    path: compile_scoped_filesystem_path(path, subpath, startup_warnings)?,
-                    access: *access,
-                });
+                entries.push(filesystem_entry(
+                    compile_scoped_filesystem_path(path, subpath, startup_warnings)?,
+                    *access,
+                )?);
             }
         }
     }
     Ok(())
 }
 
+fn filesystem_entry(
+    path: FileSystemPath,
+    access: FileSystemAccessMode,
+) -> io::Result<FileSystemSandboxEntry> {
+    if let FileSystemPath::Glob { pattern } = &path {
+        if access != FileSystemAccessMode::None {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("filesystem glob `{pattern}` only supports `none` access"),
+            ));
+        }
+        globset::GlobBuilder::new(pattern)
+            .literal_separator(true)
+            .build()
+            .map_err(|err| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("invalid filesystem glob `{pattern}`: {err}"),
+                )
+            })?;
+    }
+    Ok(FileSystemSandboxEntry { path, access })
+}
+
 fn compile_filesystem_path(
     path: &str,
     startup_warnings: &mut Vec<String>,
@@ -233,6 +325,17 @@ fn compile_filesystem_path(
         return Ok(FileSystemPath::Special { value: special });
     }
 
+    if is_glob_pattern(path) {
+        // Relative globs are matched below the cwd, so `**/.env*` covers the
+        // whole workspace.
+        if !Path::new(path).is_absolute() {
+            parse_relative_subpath(path)?;
+        }
+        return Ok(FileSystemPath::Glob {
+            pattern: path.to_string(),
+        });
+    }
+
     let path = parse_absolute_path(path)?;
     Ok(FileSystemPath::Path { path })
 }
@@ -248,6 +351,17 @@ fn compile_scoped_filesystem_path(
 
     if let Some(special) = parse_special_path(path) {
         let subpath = parse_relative_subpath(subpath)?;
+        if is_glob_pattern(&subpath.to_string_lossy()) {
+            return match special {
+                FileSystemSpecialPath::ProjectRoots { .. } => Ok(FileSystemPath::Glob {
+                    pattern: subpath.to_string_lossy().into_owned(),
+                }),
+                _ => Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("filesystem path `{path}` does not support glob entries"),
+                )),
+            };
+        }
         let special = match special {
             FileSystemSpecialPath::ProjectRoots { .. } => Ok(FileSystemPath::Special {
                 value: FileSystemSpecialPath::project_roots(Some(subpath)),
@@ -268,6 +382,16 @@ fn compile_scoped_filesystem_path(
 
     let subpath = parse_relative_subpath(subpath)?;
     let base = parse_absolute_path(path)?;
+    if is_glob_pattern(&subpath.to_string_lossy()) {
+        let base = globset::escape(&base.as_path().to_string_lossy());
+        return Ok(FileSystemPath::Glob {
+            pattern: format!(
+                "{}/{}",
+                base.trim_end_matches('/'),
+                subpath.to_string_lossy()
+            ),
+        });
+    }
     let path = AbsolutePathBuf::resolve_path_against_base(&subpath, base.as_path())?;
     Ok(FileSystemPath::Path { path })
 }
@@ -9,6 +9,7 @@ pub use codex_protocol::config_types::ModeKind;
 pub use codex_protocol::config_types::Personality;
 pub use codex_protocol::config_types::ServiceTier;
 pub use codex_protocol::config_types::WebSearchMode;
+pub use codex_secrets::SecretsBackendKind;
 use codex_utils_absolute_path::AbsolutePathBuf;
 use std::collections::BTreeMap;
 use std::collections::HashMap;
@@ -104,6 +105,89 @@ pub struct McpServerConfig {
     /// Optional OAuth resource parameter to include during MCP login (RFC 8707).
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub oauth_resource: Option<String>,
+
+    /// Opt-in settings for `sampling/createMessage` requests from this server.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sampling: Option<McpServerSamplingConfig>,
+
+    /// Runs a stdio server inside the Codex sandbox instead of with the
+    /// user's full privileges.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sandbox: Option<McpServerSandboxConfig>,
+}
+
+pub const DEFAULT_MCP_SAMPLING_MAX_TOKENS: u32 = 1024;
+
+/// Lets an MCP server ask Codex for model completions via
+/// `sampling/createMessage`. Each request still needs user approval.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct McpServerSamplingConfig {
+    /// When `true`, Codex advertises the sampling capability to this server.
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Upper bound on `maxTokens` for a single sampling request. Larger
+    /// requests are clamped to this value.
+    #[serde(default = "default_mcp_sampling_max_tokens")]
+    pub max_tokens: u32,
+
+    /// Total output tokens this server may consume through sampling in one
+    /// session. Unlimited when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_total_tokens: Option<u64>,
+}
+
+impl Default for McpServerSamplingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_tokens: DEFAULT_MCP_SAMPLING_MAX_TOKENS,
+            max_total_tokens: None,
+        }
+    }
+}
+
+const fn default_mcp_sampling_max_tokens() -> u32 {
+    DEFAULT_MCP_SAMPLING_MAX_TOKENS
+}
+
+/// Sandbox for a stdio MCP server. The server is spawned through the same
+/// platform sandbox as shell commands, and its network traffic goes through a
+/// Codex network proxy that only admits `allowed_domains`.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct McpServerSandboxConfig {
+    /// Filesystem access for the server. Defaults to `read-only`.
+    #[serde(default)]
+    pub permissions: McpServerSandboxPermissions,
+
+    /// Extra directories the server may write to. Requires
+    /// `permissions = "workspace-write"`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub writable_roots: Vec<AbsolutePathBuf>,
+
+    /// Extra directories the server may read. Reads are otherwise limited to
+    /// its working directory, the directory of its executable and the system
+    /// directories needed to run programs.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub readable_roots: Vec<AbsolutePathBuf>,
+
+    /// Domains the server may reach. The server has no network access when
+    /// this is empty.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allowed_domains: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum McpServerSandboxPermissions {
+    /// The server can write nowhere.
+    #[default]
+    ReadOnly,
+    /// The server can also write to its working directory and
+    /// `writable_roots`.
+    WorkspaceWrite,
 }
 
 // Raw MCP config shape used for deserialization and JSON Schema generation.
@@ -150,6 +234,10 @@ pub(crate) struct RawMcpServerConfig {
     pub scopes: Option<Vec<String>>,
     #[serde(default)]
     pub oauth_resource: Option<String>,
+    #[serde(default)]
+    pub sampling: Option<McpServerSamplingConfig>,
+    #[serde(default)]
+    pub sandbox: Option<McpServerSandboxConfig>,
 }
 
 impl<'de> Deserialize<'de> for McpServerConfig {
@@ -174,6 +262,16 @@ impl<'de> Deserialize<'de> for McpServerConfig {
         let disabled_tools = raw.disabled_tools.clone();
         let scopes = raw.scopes.clone();
         let oauth_resource = raw.oauth_resource.clone();
+        let sampling = raw.sampling;
+        let sandbox = raw.sandbox.take();
+        if let Some(sandbox) = sandbox.as_ref()
+            && sandbox.permissions == McpServerSandboxPermissions::ReadOnly
+            && !sandbox.writable_roots.is_empty()
+        {
+            return Err(SerdeError::custom(
+                "sandbox.writable_roots requires sandbox.permissions = \"workspace-write\"",
+            ));
+        }
 
         fn throw_if_set<E, T>(transport: &str, field: &str, value: Option<&T>) -> Result<(), E>
         where
@@ -211,6 +309,7 @@ impl<'de> Deserialize<'de> for McpServerConfig {
             throw_if_set("streamable_http", "env_vars", raw.env_vars.as_ref())?;
             throw_if_set("streamable_http", "cwd", raw.cwd.as_ref())?;
             throw_if_set("streamable_http", "bearer_token", raw.bearer_token.as_ref())?;
+            throw_if_set("streamable_http", "sandbox", sandbox.as_ref())?;
             McpServerTransportConfig::StreamableHttp {
                 url,
                 bearer_token_env_var: raw.bearer_token_env_var.clone(),
@@ -232,6 +331,8 @@ impl<'de> Deserialize<'de> for McpServerConfig {
             disabled_tools,
             scopes,
             oauth_resource,
+            sampling,
+            sandbox,
         })
     }
 }
@@ -461,6 +562,141 @@ impl From<MemoriesToml> for MemoriesConfig {
     }
 }
 
+/// Secrets settings loaded from config.toml.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct SecretsToml {
+    /// Where secrets managed by `codex secrets` are stored.
+    pub backend: Option<SecretsBackendKind>,
+    /// Names of stored secrets to export as environment variables of the same
+    /// name to `shell` and `exec_command` tool runs. Values are resolved from
+    /// the current environment scope first, then the global scope, and are
+    /// scrubbed from tool output before it reaches the model.
+    pub expose: Option<Vec<String>>,
+}
+
+/// Effective secrets settings after defaults are applied.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct SecretsConfig {
+    pub backend: SecretsBackendKind,
+    pub expose: Vec<String>,
+}
+
+impl From<SecretsToml> for SecretsConfig {
+    fn from(toml: SecretsToml) -> Self {
+        Self {
+            backend: toml.backend.unwrap_or_default(),
+            expose: toml.expose.unwrap_or_default(),
+        }
+    }
+}
+
+/// Limits that stop a run before the agent finishes, mainly for unattended
+/// `codex exec` runs. Sub-agents count against the budget of the session that
+/// spawned them. Every limit is unset by default.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct RunBudgetToml {
+    /// Maximum number of model requests in a run.
+    pub max_turns: Option<u64>,
+    /// Maximum number of output tokens (including reasoning) in a run.
+    pub max_output_tokens: Option<u64>,
+    /// Maximum number of input plus output tokens in a run.
+    pub max_total_tokens: Option<u64>,
+    /// Maximum wall-clock time of a run, in seconds.
+    pub timeout_seconds: Option<u64>,
+    /// Maximum number of tool calls in a run.
+    pub max_tool_calls: Option<u64>,
+}
+
+/// Effective run budget; `None` means unlimited.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct RunBudgetConfig {
+    pub max_turns: Option<u64>,
+    pub max_output_tokens: Option<u64>,
+    pub max_total_tokens: Option<u64>,
+    pub timeout: Option<Duration>,
+    pub max_tool_calls: Option<u64>,
+}
+
+impl From<RunBudgetToml> for RunBudgetConfig {
+    fn from(toml: RunBudgetToml) -> Self {
+        Self {
+            max_turns: toml.max_turns,
+            max_output_tokens: toml.max_output_tokens,
+            max_total_tokens: toml.max_total_tokens,
+            timeout: toml.timeout_seconds.map(Duration::from_secs),
+            max_tool_calls: toml.max_tool_calls,
+        }
+    }
+}
+
+/// A long-running process declared under `[services.<name>]`, such as a
+/// database, dev server or file watcher.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct ServiceToml {
+    /// Program and arguments to run.
+    pub command: Vec<String>,
+    /// Working directory, relative to the session cwd. Defaults to the session cwd.
+    pub cwd: Option<PathBuf>,
+    /// Extra environment variables for the service.
+    pub env: Option<HashMap<String, String>>,
+    /// Start the service when a thread starts. Defaults to `false`.
+    pub autostart: Option<bool>,
+    /// When to restart the service after it exits. Defaults to `on-failure`.
+    pub restart: Option<ServiceRestartPolicy>,
+    /// How to tell whether the running service is healthy.
+    pub health_check: Option<ServiceHealthCheckToml>,
+}
+
+/// Set exactly one of `command` or `port`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct ServiceHealthCheckToml {
+    /// Command that exits with status 0 while the service is healthy.
+    pub command: Option<Vec<String>>,
+    /// Local TCP port that accepts connections while the service is healthy.
+    pub port: Option<u16>,
+    /// Seconds between checks. Defaults to 10.
+    pub interval_seconds: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum ServiceRestartPolicy {
+    /// Restart only when the service exits with a non-zero status or a signal.
+    #[default]
+    OnFailure,
+    /// Restart whenever the service exits.
+    Always,
+    /// Never restart the service.
+    Never,
+}
+
+/// Effective settings for a declared service.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServiceConfig {
+    pub command: Vec<String>,
+    pub cwd: Option<PathBuf>,
+    pub env: HashMap<String, String>,
+    pub autostart: bool,
+    pub restart: ServiceRestartPolicy,
+    pub health_check: Option<ServiceHealthCheck>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServiceHealthCheck {
+    pub probe: ServiceHealthProbe,
+    pub interval: Duration,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ServiceHealthProbe {
+    Command(Vec<String>),
+    Port(u16),
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum AppToolApproval {
@@ -752,6 +988,11 @@ pub struct QueryProjectIndex {
     #[serde(default = "default_true")]
     pub auto_warm: bool,
 
+    /// Whether to keep an existing index in sync with working-tree edits
+    /// during a session. Defaults to `true`.
+    #[serde(default = "default_true")]
+    pub auto_update: bool,
+
     /// If `true`, indexing/search requests fail when embeddings are unavailable.
     /// Defaults to `false`.
     #[serde(default)]
@@ -775,6 +1016,7 @@ impl Default for QueryProjectIndex {
         Self {
             backend: QueryProjectIndexBackend::default(),
             auto_warm: true,
+            auto_update: true,
             require_embeddings: false,
             embedding_model: None,
             file_globs: Vec::new(),
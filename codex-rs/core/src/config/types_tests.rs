@@ -243,6 +243,74 @@ fn deserialize_server_config_with_tool_filters() {
     assert_eq!(cfg.disabled_tools, Some(vec!["blocked".to_string()]));
 }
 
+#[test]
+fn deserialize_stdio_server_config_with_sandbox() {
+    let cfg: McpServerConfig = toml::from_str(
+        r#"
+            command = "echo"
+
+            [sandbox]
+            permissions = "workspace-write"
+            writable_roots = ["/var/cache/echo"]
+            readable_roots = ["/opt/echo"]
+            allowed_domains = ["api.example.com"]
+        "#,
+    )
+    .expect("should deserialize sandbox config");
+
+    assert_eq!(
+        cfg.sandbox,
+        Some(McpServerSandboxConfig {
+            permissions: McpServerSandboxPermissions::WorkspaceWrite,
+            writable_roots: vec![
+                AbsolutePathBuf::from_absolute_path("/var/cache/echo").expect("absolute path")
+            ],
+            readable_roots: vec![
+                AbsolutePathBuf::from_absolute_path("/opt/echo").expect("absolute path")
+            ],
+            allowed_domains: vec!["api.example.com".to_string()],
+        })
+    );
+
+    let cfg: McpServerConfig = toml::from_str(
+        r#"
+            command = "echo"
+            sandbox = {}
+        "#,
+    )
+    .expect("should deserialize empty sandbox config");
+    assert_eq!(cfg.sandbox, Some(McpServerSandboxConfig::default()));
+}
+
+#[test]
+fn deserialize_rejects_invalid_sandbox_config() {
+    let err = toml::from_str::<McpServerConfig>(
+        r#"
+            url = "https://example.com"
+            sandbox = {}
+        "#,
+    )
+    .expect_err("should reject sandbox for http transport");
+    assert!(
+        err.to_string()
+            .contains("sandbox is not supported for streamable_http"),
+        "unexpected error: {err}"
+    );
+
+    let err = toml::from_str::<McpServerConfig>(
+        r#"
+            command = "echo"
+            sandbox = { writable_roots = ["/var/cache/echo"] }
+        "#,
+    )
+    .expect_err("should reject writable roots for a read-only sandbox");
+    assert!(
+        err.to_string()
+            .contains("sandbox.writable_roots requires sandbox.permissions = \"workspace-write\""),
+        "unexpected error: {err}"
+    );
+}
+
 #[test]
 fn deserialize_rejects_command_and_url() {
     toml::from_str::<McpServerConfig>(
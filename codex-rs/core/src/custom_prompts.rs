@@ -1,15 +1,78 @@
+use crate::config_loader::ConfigLayerStack;
+use crate::config_loader::ConfigLayerStackOrdering;
+use codex_app_server_protocol::ConfigLayerSource;
 use codex_protocol::custom_prompts::CustomPrompt;
+use codex_protocol::custom_prompts::CustomPromptArgument;
+use codex_protocol::user_input::ByteRange;
+use codex_protocol::user_input::TextElement;
 use std::collections::HashSet;
 use std::path::Path;
 use std::path::PathBuf;
 use tokio::fs;
+use tracing::warn;
+
+/// Prompts directory inside `$CODEX_HOME` and inside a project's `.codex/`.
+const PROMPTS_DIR_NAME: &str = "prompts";
+
+/// How deeply `{{include path}}` directives may nest.
+const MAX_INCLUDE_DEPTH: usize = 8;
 
 /// Return the default prompts directory: `$CODEX_HOME/prompts`.
 /// If `CODEX_HOME` cannot be resolved, returns `None`.
 pub fn default_prompts_dir() -> Option<PathBuf> {
     crate::config::find_codex_home()
         .ok()
-        .map(|home| home.join("prompts"))
+        .map(|home| home.join(PROMPTS_DIR_NAME))
+}
+
+/// A directory to search for prompts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PromptDir {
+    pub path: PathBuf,
+    /// `{{include path}}` directives may only read files under this
+    /// directory: the project for `.codex/prompts`, otherwise `path` itself.
+    pub include_root: PathBuf,
+}
+
+/// Return the directories to search for prompts, highest precedence first:
+/// `.codex/prompts` for each enabled project layer (innermost first), then
+/// `$CODEX_HOME/prompts`. Disabled layers, such as untrusted projects, are
+/// skipped.
+pub fn prompt_dirs(config_layer_stack: &ConfigLayerStack, codex_home: &Path) -> Vec<PromptDir> {
+    let mut dirs: Vec<PromptDir> = config_layer_stack
+        .get_layers(ConfigLayerStackOrdering::HighestPrecedenceFirst, false)
+        .into_iter()
+        .filter(|layer| matches!(layer.name, ConfigLayerSource::Project { .. }))
+        .filter_map(|layer| layer.config_folder())
+        .map(|folder| {
+            let folder = folder.as_path();
+            PromptDir {
+                path: folder.join(PROMPTS_DIR_NAME),
+                include_root: folder.parent().unwrap_or(folder).to_path_buf(),
+            }
+        })
+        .collect();
+    let user_prompts = codex_home.join(PROMPTS_DIR_NAME);
+    dirs.push(PromptDir {
+        path: user_prompts.clone(),
+        include_root: user_prompts,
+    });
+    dirs
+}
+
+/// Discover prompts across `dirs`, which are ordered highest precedence first.
+/// When several directories define a prompt with the same name, the first one
+/// wins. Returns entries sorted by name.
+pub async fn discover_prompts_in_dirs(dirs: &[PromptDir]) -> Vec<CustomPrompt> {
+    let mut seen: HashSet<String> = HashSet::new();
+    let mut out: Vec<CustomPrompt> = Vec::new();
+    for dir in dirs {
+        let found = discover_prompts(&dir.path, &dir.include_root, &seen).await;
+        seen.extend(found.iter().map(|prompt| prompt.name.clone()));
+        out.extend(found);
+    }
+    out.sort_by(|a, b| a.name.cmp(&b.name));
+    out
 }
 
 /// Discover prompt files in the given directory, returning entries sorted by name.
@@ -24,6 +87,17 @@ pub async fn discover_prompts_in_excluding(
     dir: &Path,
     exclude: &HashSet<String>,
 ) -> Vec<CustomPrompt> {
+    discover_prompts(dir, dir, exclude).await
+}
+
+async fn discover_prompts(
+    dir: &Path,
+    include_root: &Path,
+    exclude: &HashSet<String>,
+) -> Vec<CustomPrompt> {
+    let include_root = fs::canonicalize(include_root)
+        .await
+        .unwrap_or_else(|_| include_root.to_path_buf());
     let mut out: Vec<CustomPrompt> = Vec::new();
     let mut entries = match fs::read_dir(dir).await {
         Ok(entries) => entries,
@@ -62,36 +136,49 @@ pub async fn discover_prompts_in_excluding(
             Ok(s) => s,
             Err(_) => continue,
         };
-        let (description, argument_hint, body) = parse_frontmatter(&content);
+        let (frontmatter, body) = parse_frontmatter(&content);
+        let mut include_stack = vec![std::fs::canonicalize(&path).unwrap_or_else(|_| path.clone())];
+        let body = expand_includes(&body, dir, &include_root, &mut include_stack);
         out.push(CustomPrompt {
             name,
             path,
             content: body,
-            description,
-            argument_hint,
+            description: frontmatter.description,
+            argument_hint: frontmatter.argument_hint,
+            arguments: frontmatter.arguments,
         });
     }
     out.sort_by(|a, b| a.name.cmp(&b.name));
     out
 }
 
+/// Metadata read from a prompt's frontmatter.
+#[derive(Debug, Default, PartialEq)]
+struct PromptFrontmatter {
+    description: Option<String>,
+    argument_hint: Option<String>,
+    arguments: Vec<CustomPromptArgument>,
+}
+
 /// Parse optional YAML-like frontmatter at the beginning of `content`.
 /// Supported keys:
 /// - `description`: short description shown in the slash popup
 /// - `argument-hint` or `argument_hint`: brief hint string shown after the description
-///   Returns (description, argument_hint, body_without_frontmatter).
-fn parse_frontmatter(content: &str) -> (Option<String>, Option<String>, String) {
+/// - `required-args` / `required_args` and `optional-args` / `optional_args`:
+///   comma-separated names of the prompt's `$NAME` arguments
+///
+/// Returns the frontmatter and the body without it.
+fn parse_frontmatter(content: &str) -> (PromptFrontmatter, String) {
     let mut segments = content.split_inclusive('\n');
     let Some(first_segment) = segments.next() else {
-        return (None, None, String::new());
+        return (PromptFrontmatter::default(), String::new());
     };
     let first_line = first_segment.trim_end_matches(['\r', '\n']);
     if first_line.trim() != "---" {
-        return (None, None, content.to_string());
+        return (PromptFrontmatter::default(), content.to_string());
     }
 
-    let mut desc: Option<String> = None;
-    let mut hint: Option<String> = None;
+    let mut frontmatter = PromptFrontmatter::default();
     let mut frontmatter_closed = false;
     let mut consumed = first_segment.len();
 
@@ -122,8 +209,14 @@ fn parse_frontmatter(content: &str) -> (Option<String>, Option<String>, String)
                 }
             }
             match key.as_str() {
-                "description" => desc = Some(val),
-                "argument-hint" | "argument_hint" => hint = Some(val),
+                "description" => frontmatter.description = Some(val),
+                "argument-hint" | "argument_hint" => frontmatter.argument_hint = Some(val),
+                "required-args" | "required_args" => {
+                    push_arguments(&mut frontmatter.arguments, &val, true);
+                }
+                "optional-args" | "optional_args" => {
+                    push_arguments(&mut frontmatter.arguments, &val, false);
+                }
                 _ => {}
             }
         }
@@ -133,7 +226,7 @@ fn parse_frontmatter(content: &str) -> (Option<String>, Option<String>, String)
 
     if !frontmatter_closed {
         // Unterminated frontmatter: treat input as-is.
-        return (None, None, content.to_string());
+        return (PromptFrontmatter::default(), content.to_string());
     }
 
     let body = if consumed >= content.len() {
@@ -141,7 +234,169 @@ fn parse_frontmatter(content: &str) -> (Option<String>, Option<String>, String)
     } else {
         content[consumed..].to_string()
     };
-    (desc, hint, body)
+    (frontmatter, body)
+}
+
+/// Append the comma-separated argument names in `list`, accepting `$NAME` or
+/// `NAME`. A name declared twice keeps its first declaration.
+fn push_arguments(arguments: &mut Vec<CustomPromptArgument>, list: &str, required: bool) {
+    for name in list.split(',') {
+        let name = name.trim().trim_start_matches('$');
+        if name.is_empty() || arguments.iter().any(|arg| arg.name == name) {
+            continue;
+        }
+        arguments.push(CustomPromptArgument {
+            name: name.to_string(),
+            required,
+        });
+    }
+}
+
+/// Replace every `{{include path}}` in `content` with the contents of `path`,
+/// resolved against `base_dir` when relative. Included files may include
+/// others; their own frontmatter is dropped. A directive that cannot be
+/// resolved, or that points outside the canonical `include_root`, is left in
+/// place.
+fn expand_includes(
+    content: &str,
+    base_dir: &Path,
+    include_root: &Path,
+    include_stack: &mut Vec<PathBuf>,
+) -> String {
+    replace_directives(content, |directive| {
+        let target = include_target(directive)?;
+        let path = base_dir.join(target);
+        let canonical = match std::fs::canonicalize(&path) {
+            Ok(canonical) => canonical,
+            Err(err) => {
+                warn!("failed to read prompt include {}: {err}", path.display());
+                return None;
+            }
+        };
+        if !canonical.starts_with(include_root) {
+            warn!(
+                "skipping prompt include of {} outside {}",
+                path.display(),
+                include_root.display()
+            );
+            return None;
+        }
+        if include_stack.len() > MAX_INCLUDE_DEPTH || include_stack.contains(&canonical) {
+            warn!("skipping recursive prompt include of {}", path.display());
+            return None;
+        }
+        let included = match std::fs::read_to_string(&canonical) {
+            Ok(included) => included,
+            Err(err) => {
+                warn!("failed to read prompt include {}: {err}", path.display());
+                return None;
+            }
+        };
+        let (_, body) = parse_frontmatter(&included);
+        let include_dir = canonical.parent().unwrap_or(base_dir).to_path_buf();
+        include_stack.push(canonical);
+        let expanded = expand_includes(&body, &include_dir, include_root, include_stack);
+        include_stack.pop();
+        Some(expanded)
+    })
+}
+
+fn include_target(directive: &str) -> Option<&str> {
+    let rest = directive.strip_prefix("include")?;
+    if !rest.starts_with(char::is_whitespace) {
+        return None;
+    }
+    let target = rest.trim();
+    (!target.is_empty()).then_some(target)
+}
+
+/// Return the commands of the `{{shell: cmd}}` directives in `text`, in order.
+pub fn shell_commands(text: &str) -> Vec<String> {
+    let mut commands = Vec::new();
+    replace_directives(text, |directive| {
+        if let Some(command) = shell_command(directive) {
+            commands.push(command.to_string());
+        }
+        None
+    });
+    commands
+}
+
+/// Replace each `{{shell: cmd}}` directive in `text` with the next entry of
+/// `outputs`, which holds the output of the commands from [`shell_commands`].
+///
+/// `text_elements` are moved to where their text ends up in the result;
+/// elements that overlap a replaced directive are dropped.
+pub fn replace_shell_commands(
+    text: &str,
+    text_elements: &[TextElement],
+    outputs: Vec<String>,
+) -> (String, Vec<TextElement>) {
+    let mut outputs = outputs.into_iter();
+    let (replaced_text, replaced) = replace_directives_with_ranges(text, |directive| {
+        shell_command(directive)?;
+        outputs.next()
+    });
+    let text_elements = text_elements
+        .iter()
+        .filter_map(|element| {
+            let range = element.byte_range;
+            let mut removed = 0;
+            let mut added = 0;
+            for (directive, replacement_len) in &replaced {
+                if directive.end <= range.start {
+                    removed += directive.end - directive.start;
+                    added += replacement_len;
+                } else if directive.start < range.end {
+                    return None;
+                }
+            }
+            Some(element.map_range(|range| ByteRange {
+                start: range.start - removed + added,
+                end: range.end - removed + added,
+            }))
+        })
+        .collect();
+    (replaced_text, text_elements)
+}
+
+fn shell_command(directive: &str) -> Option<&str> {
+    let command = directive.strip_prefix("shell:")?.trim();
+    (!command.is_empty()).then_some(command)
+}
+
+/// Call `replace` with the trimmed inside of every `{{...}}` directive in
+/// `text`, substituting the directive when it returns `Some`.
+fn replace_directives(text: &str, replace: impl FnMut(&str) -> Option<String>) -> String {
+    replace_directives_with_ranges(text, replace).0
+}
+
+/// Like [`replace_directives`], also returning the byte range in `text` of
+/// each substituted directive with the length of its replacement.
+fn replace_directives_with_ranges(
+    text: &str,
+    mut replace: impl FnMut(&str) -> Option<String>,
+) -> (String, Vec<(ByteRange, usize)>) {
+    let mut out = String::with_capacity(text.len());
+    let mut replaced = Vec::new();
+    let mut offset = 0;
+    while let Some(start) = text[offset..].find("{{").map(|start| offset + start) {
+        let Some(len) = text[start + 2..].find("}}") else {
+            break;
+        };
+        let end = start + 2 + len + 2;
+        out.push_str(&text[offset..start]);
+        match replace(text[start + 2..end - 2].trim()) {
+            Some(replacement) => {
+                replaced.push((ByteRange { start, end }, replacement.len()));
+                out.push_str(&replacement);
+            }
+            None => out.push_str(&text[start..end]),
+        }
+        offset = end;
+    }
+    out.push_str(&text[offset..]);
+    (out, replaced)
 }
 
 #[cfg(test)]
@@ -1,4 +1,9 @@
 use super::*;
+use crate::config_loader::ConfigLayerEntry;
+use crate::config_loader::ConfigRequirements;
+use crate::config_loader::ConfigRequirementsToml;
+use codex_utils_absolute_path::AbsolutePathBuf;
+use pretty_assertions::assert_eq;
 use std::fs;
 use tempfile::tempdir;
 
@@ -88,8 +93,217 @@ async fn parses_frontmatter_and_strips_from_body() {
 #[test]
 fn parse_frontmatter_preserves_body_newlines() {
     let content = "---\r\ndescription: \"Line endings\"\r\nargument_hint: \"[arg]\"\r\n---\r\nFirst line\r\nSecond line\r\n";
-    let (desc, hint, body) = parse_frontmatter(content);
-    assert_eq!(desc.as_deref(), Some("Line endings"));
-    assert_eq!(hint.as_deref(), Some("[arg]"));
+    let (frontmatter, body) = parse_frontmatter(content);
+    assert_eq!(frontmatter.description.as_deref(), Some("Line endings"));
+    assert_eq!(frontmatter.argument_hint.as_deref(), Some("[arg]"));
     assert_eq!(body, "First line\r\nSecond line\r\n");
 }
+
+#[test]
+fn parse_frontmatter_reads_named_arguments() {
+    let content =
+        "---\nrequired-args: FILE, $TARGET\noptional_args: FOCUS, FILE\n---\nReview $FILE";
+    let (frontmatter, body) = parse_frontmatter(content);
+    assert_eq!(
+        frontmatter.arguments,
+        vec![
+            CustomPromptArgument {
+                name: "FILE".to_string(),
+                required: true,
+            },
+            CustomPromptArgument {
+                name: "TARGET".to_string(),
+                required: true,
+            },
+            CustomPromptArgument {
+                name: "FOCUS".to_string(),
+                required: false,
+            },
+        ]
+    );
+    assert_eq!(body, "Review $FILE");
+}
+
+#[tokio::test]
+async fn expands_includes_relative_to_the_prompt() {
+    let tmp = tempdir().expect("create TempDir");
+    let dir = tmp.path();
+    fs::create_dir(dir.join("shared")).unwrap();
+    fs::write(
+        dir.join("shared/style.md"),
+        "---\ndescription: ignored\n---\nStyle {{include footer.md}}",
+    )
+    .unwrap();
+    fs::write(dir.join("shared/footer.md"), "footer").unwrap();
+    fs::write(
+        dir.join("review.md"),
+        "Review.\n{{include shared/style.md}}\n{{include missing.md}} {{shell: date}}",
+    )
+    .unwrap();
+
+    let found = discover_prompts_in(dir).await;
+    let review = found
+        .iter()
+        .find(|prompt| prompt.name == "review")
+        .expect("review prompt");
+    assert_eq!(
+        review.content,
+        "Review.\nStyle footer\n{{include missing.md}} {{shell: date}}"
+    );
+}
+
+#[tokio::test]
+async fn stops_recursive_includes() {
+    let tmp = tempdir().expect("create TempDir");
+    let dir = tmp.path();
+    fs::write(dir.join("loop.md"), "loop {{include loop.md}}").unwrap();
+
+    let found = discover_prompts_in(dir).await;
+    assert_eq!(found[0].content, "loop {{include loop.md}}");
+}
+
+#[tokio::test]
+async fn includes_must_stay_inside_the_include_root() {
+    let tmp = tempdir().expect("create TempDir");
+    let repo = tmp.path().join("repo");
+    let dir = repo.join(".codex/prompts");
+    fs::create_dir_all(&dir).unwrap();
+    fs::create_dir_all(repo.join("docs")).unwrap();
+    fs::write(repo.join("docs/style.md"), "repo style").unwrap();
+    let secret = tmp.path().join("secret.md");
+    fs::write(&secret, "secret").unwrap();
+    let content = format!(
+        "{{{{include ../../docs/style.md}}}} {{{{include ../../../secret.md}}}} {{{{include {}}}}}",
+        secret.display()
+    );
+    fs::write(dir.join("review.md"), &content).unwrap();
+
+    let found = discover_prompts(&dir, &repo, &HashSet::new()).await;
+    assert_eq!(
+        found[0].content,
+        format!(
+            "repo style {{{{include ../../../secret.md}}}} {{{{include {}}}}}",
+            secret.display()
+        )
+    );
+
+    // Prompts found on their own may only include files from their directory.
+    let found = discover_prompts_in(&dir).await;
+    assert_eq!(found[0].content, content);
+}
+
+#[test]
+fn shell_commands_are_listed_and_replaced_in_order() {
+    let text = "Status:\n{{shell: git status --short}}\nLog: {{ shell:git log -1 }} {{other}}";
+    assert_eq!(
+        shell_commands(text),
+        vec!["git status --short".to_string(), "git log -1".to_string()]
+    );
+    assert_eq!(
+        replace_shell_commands(text, &[], vec!["M a.rs".to_string(), "abc fix".to_string()]),
+        (
+            "Status:\nM a.rs\nLog: abc fix {{other}}".to_string(),
+            Vec::new()
+        )
+    );
+}
+
+#[test]
+fn replacing_shell_commands_moves_text_elements() {
+    let text = "[Image #1] {{shell: date}} [Image #2] {{shell: pwd}}";
+    let element = |start, end, placeholder: &str| {
+        TextElement::new(ByteRange { start, end }, Some(placeholder.to_string()))
+    };
+    let (replaced, elements) = replace_shell_commands(
+        text,
+        &[
+            element(0, 10, "[Image #1]"),
+            element(27, 37, "[Image #2]"),
+            element(13, 18, "shell"),
+        ],
+        vec!["Mon".to_string(), "/home/me/project".to_string()],
+    );
+
+    assert_eq!(replaced, "[Image #1] Mon [Image #2] /home/me/project");
+    assert_eq!(
+        elements,
+        vec![element(0, 10, "[Image #1]"), element(15, 25, "[Image #2]")]
+    );
+    assert_eq!(&replaced[15..25], "[Image #2]");
+}
+
+#[tokio::test]
+async fn project_prompts_override_user_prompts() -> std::io::Result<()> {
+    let tmp = tempdir()?;
+    let codex_home = tmp.path().join("home");
+    let repo = tmp.path().join("repo");
+    let nested = repo.join("nested");
+    for dir in [
+        codex_home.join("prompts"),
+        repo.join(".codex/prompts"),
+        nested.join(".codex/prompts"),
+    ] {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(codex_home.join("prompts/review.md"), "user review")?;
+    fs::write(codex_home.join("prompts/personal.md"), "personal")?;
+    fs::write(repo.join(".codex/prompts/review.md"), "repo review")?;
+    fs::write(repo.join(".codex/prompts/deploy.md"), "repo deploy")?;
+    fs::write(nested.join(".codex/prompts/deploy.md"), "nested deploy")?;
+
+    let project_layer = |dir: &Path| -> std::io::Result<ConfigLayerEntry> {
+        Ok(ConfigLayerEntry::new(
+            ConfigLayerSource::Project {
+                dot_codex_folder: AbsolutePathBuf::from_absolute_path(dir.join(".codex"))?,
+            },
+            toml::Value::Table(toml::map::Map::new()),
+        ))
+    };
+    let stack = ConfigLayerStack::new(
+        vec![
+            ConfigLayerEntry::new(
+                ConfigLayerSource::User {
+                    file: AbsolutePathBuf::from_absolute_path(codex_home.join("config.toml"))?,
+                },
+                toml::Value::Table(toml::map::Map::new()),
+            ),
+            project_layer(&repo)?,
+            project_layer(&nested)?,
+        ],
+        ConfigRequirements::default(),
+        ConfigRequirementsToml::default(),
+    )?;
+
+    let dirs = prompt_dirs(&stack, &codex_home);
+    assert_eq!(
+        dirs,
+        vec![
+            PromptDir {
+                path: nested.join(".codex/prompts"),
+                include_root: nested.clone(),
+            },
+            PromptDir {
+                path: repo.join(".codex/prompts"),
+                include_root: repo.clone(),
+            },
+            PromptDir {
+                path: codex_home.join("prompts"),
+                include_root: codex_home.join("prompts"),
+            },
+        ]
+    );
+    let found: Vec<(String, String)> = discover_prompts_in_dirs(&dirs)
+        .await
+        .into_iter()
+        .map(|prompt| (prompt.name, prompt.content))
+        .collect();
+    assert_eq!(
+        found,
+        vec![
+            ("deploy".to_string(), "nested deploy".to_string()),
+            ("personal".to_string(), "personal".to_string()),
+            ("review".to_string(), "repo review".to_string()),
+        ]
+    );
+    Ok(())
+}
@@ -120,6 +120,11 @@ pub enum CodexErr {
     #[error("Selected model is at capacity. Please try a different model.")]
     ServerOverloaded,
 
+    /// The response stopped at the request's `max_output_tokens` before the
+    /// model finished. Retrying would stop at the same limit.
+    #[error("the model's response stopped at its max_output_tokens limit")]
+    MaxOutputTokens,
+
     #[error("{0}")]
     ResponseStreamFailed(ResponseStreamFailed),
 
@@ -213,7 +218,8 @@ impl CodexErr {
             | CodexErr::Spawn
             | CodexErr::SessionConfiguredNotFirstEvent
             | CodexErr::UsageLimitReached(_)
-            | CodexErr::ServerOverloaded => false,
+            | CodexErr::ServerOverloaded
+            | CodexErr::MaxOutputTokens => false,
             CodexErr::Stream(..)
             | CodexErr::Timeout
             | CodexErr::UnexpectedStatus(_)
@@ -32,11 +32,13 @@ use crate::spawn::SpawnChildRequest;
 use crate::spawn::StdioPolicy;
 use crate::spawn::spawn_child_async;
 use crate::text_encoding::bytes_to_string_smart;
+use crate::tool_secrets::SecretStreamScrubber;
 use crate::tools::sandboxing::SandboxablePreference;
 use codex_network_proxy::NetworkProxy;
 use codex_protocol::permissions::FileSystemSandboxKind;
 use codex_protocol::permissions::FileSystemSandboxPolicy;
 use codex_protocol::permissions::NetworkSandboxPolicy;
+use codex_protocol::permissions::ResourceLimits;
 use codex_utils_pty::DEFAULT_OUTPUT_BYTES_CAP;
 use codex_utils_pty::process_group::kill_child_process_group;
 
@@ -175,6 +177,8 @@ pub struct StdoutStream {
     pub sub_id: String,
     pub call_id: String,
     pub tx_event: Sender<Event>,
+    /// Scrubs exposed secrets from the streamed deltas.
+    pub(crate) secret_scrubber: SecretStreamScrubber,
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -186,6 +190,7 @@ pub async fn process_exec_tool_call(
     sandbox_cwd: &Path,
     codex_linux_sandbox_exe: &Option<PathBuf>,
     use_legacy_landlock: bool,
+    resource_limits: ResourceLimits,
     stdout_stream: Option<StdoutStream>,
 ) -> Result<ExecToolCallOutput> {
     let exec_req = build_exec_request(
@@ -196,6 +201,7 @@ pub async fn process_exec_tool_call(
         sandbox_cwd,
         codex_linux_sandbox_exe,
         use_legacy_landlock,
+        resource_limits,
     )?;
 
     // Route through the sandboxing module for a single, unified execution path.
@@ -203,7 +209,8 @@ pub async fn process_exec_tool_call(
 }
 
 /// Transform a portable exec request into the concrete argv/env that should be
-/// spawned under the requested sandbox policy.
+/// spawned under the requested sandbox policy and resource limits.
+#[allow(clippy::too_many_arguments)]
 pub fn build_exec_request(
     params: ExecParams,
     sandbox_policy: &SandboxPolicy,
@@ -212,6 +219,7 @@ pub fn build_exec_request(
     sandbox_cwd: &Path,
     codex_linux_sandbox_exe: &Option<PathBuf>,
     use_legacy_landlock: bool,
+    resource_limits: ResourceLimits,
 ) -> Result<ExecRequest> {
     let windows_sandbox_level = params.windows_sandbox_level;
     let enforce_managed_network = params.network.is_some();
@@ -270,6 +278,9 @@ pub fn build_exec_request(
             macos_seatbelt_profile_extensions: None,
             codex_linux_sandbox_exe: codex_linux_sandbox_exe.as_ref(),
             use_legacy_landlock,
+            resource_limits,
+            overlay_workspace: None,
+            glob_deny_matches: None,
             windows_sandbox_level,
         })
         .map_err(CodexErr::from)?;
@@ -943,7 +954,7 @@ async fn consume_truncated_output(
 
 async fn read_capped<R: AsyncRead + Unpin + Send + 'static>(
     mut reader: R,
-    stream: Option<StdoutStream>,
+    mut stream: Option<StdoutStream>,
     is_stderr: bool,
 ) -> io::Result<StreamOutput<Vec<u8>>> {
     let mut buf = Vec::with_capacity(AGGREGATE_BUFFER_INITIAL_CAPACITY.min(EXEC_OUTPUT_MAX_BYTES));
@@ -956,31 +967,18 @@ async fn read_capped<R: AsyncRead + Unpin + Send + 'static>(
             break;
         }
 
-        if let Some(stream) = &stream
-            && emitted_deltas < MAX_EXEC_OUTPUT_DELTAS_PER_CALL
-        {
-            let chunk = tmp[..n].to_vec();
-            let msg = EventMsg::ExecCommandOutputDelta(ExecCommandOutputDeltaEvent {
-                call_id: stream.call_id.clone(),
-                stream: if is_stderr {
-                    ExecOutputStream::Stderr
-                } else {
-                    ExecOutputStream::Stdout
-                },
-                chunk,
-            });
-            let event = Event {
-                id: stream.sub_id.clone(),
-                msg,
-            };
-            #[allow(clippy::let_unit_value)]
-            let _ = stream.tx_event.send(event).await;
-            emitted_deltas += 1;
+        if let Some(stream) = stream.as_mut() {
+            let chunk = stream.secret_scrubber.push(&tmp[..n]);
+            emit_output_delta(stream, is_stderr, chunk, &mut emitted_deltas).await;
         }
 
         append_capped(&mut buf, &tmp[..n], EXEC_OUTPUT_MAX_BYTES);
         // Continue reading to EOF to avoid back-pressure
     }
+    if let Some(stream) = stream.as_mut() {
+        let chunk = stream.secret_scrubber.finish();
+        emit_output_delta(stream, is_stderr, chunk, &mut emitted_deltas).await;
+    }
 
     Ok(StreamOutput {
         text: buf,
@@ -988,6 +986,33 @@ async fn read_capped<R: AsyncRead + Unpin + Send + 'static>(
     })
 }
 
+async fn emit_output_delta(
+    stream: &StdoutStream,
+    is_stderr: bool,
+    chunk: Vec<u8>,
+    emitted_deltas: &mut usize,
+) {
+    if chunk.is_empty() || *emitted_deltas >= MAX_EXEC_OUTPUT_DELTAS_PER_CALL {
+        return;
+    }
+    let msg = EventMsg::ExecCommandOutputDelta(ExecCommandOutputDeltaEvent {
+        call_id: stream.call_id.clone(),
+        stream: if is_stderr {
+            ExecOutputStream::Stderr
+        } else {
+            ExecOutputStream::Stdout
+        },
+        chunk,
+    });
+    let event = Event {
+        id: stream.sub_id.clone(),
+        msg,
+    };
+    #[allow(clippy::let_unit_value)]
+    let _ = stream.tx_event.send(event).await;
+    *emitted_deltas += 1;
+}
+
 #[cfg(unix)]
 fn synthetic_exit_status(code: i32) -> ExitStatus {
     use std::os::unix::process::ExitStatusExt;
@@ -390,6 +390,7 @@ async fn process_exec_tool_call_respects_cancellation_token() -> Result<()> {
         cwd.as_path(),
         &None,
         false,
+        ResourceLimits::default(),
         None,
     )
     .await;
@@ -93,6 +93,13 @@ pub enum Feature {
     UnifiedExec,
     /// Route shell tool execution through the zsh exec bridge.
     ShellZshFork,
+    /// Save shell output that overflows the model's budget to disk and expose
+    /// the `read_tool_output` tool to page through it.
+    SpillToolOutput,
+    /// Run `[services.<name>]` tables and expose the `service_start`,
+    /// `service_stop` and `service_status` tools for managing long-running
+    /// background services.
+    BackgroundServices,
     /// Include the freeform apply_patch tool.
     ApplyPatchFreeform,
     /// Allow exec tools to request additional permissions while staying sandboxed.
@@ -114,6 +121,9 @@ pub enum Feature {
     /// Use the legacy Landlock Linux sandbox fallback instead of the default
     /// bubblewrap pipeline.
     UseLegacyLandlock,
+    /// Run Linux sandboxed commands against a copy-on-write overlay of the
+    /// workspace and ask the user to accept the changes at the end of a turn.
+    OverlayWorkspace,
     /// Allow the model to request approval and propose exec rules.
     RequestRule,
     /// Enable Windows sandbox (restricted token) on Windows.
@@ -538,6 +548,18 @@ pub const FEATURES: &[FeatureSpec] = &[
         stage: Stage::UnderDevelopment,
         default_enabled: false,
     },
+    FeatureSpec {
+        id: Feature::SpillToolOutput,
+        key: "spill_tool_output",
+        stage: Stage::UnderDevelopment,
+        default_enabled: false,
+    },
+    FeatureSpec {
+        id: Feature::BackgroundServices,
+        key: "background_services",
+        stage: Stage::UnderDevelopment,
+        default_enabled: false,
+    },
     FeatureSpec {
         id: Feature::ShellSnapshot,
         key: "shell_snapshot",
@@ -657,6 +679,12 @@ pub const FEATURES: &[FeatureSpec] = &[
         stage: Stage::Stable,
         default_enabled: false,
     },
+    FeatureSpec {
+        id: Feature::OverlayWorkspace,
+        key: "overlay_workspace",
+        stage: Stage::UnderDevelopment,
+        default_enabled: false,
+    },
     FeatureSpec {
         id: Feature::RequestRule,
         key: "request_rule",
@@ -1,5 +1,5 @@
-//! Watches skill roots for changes and broadcasts coarse-grained
-//! `FileWatcherEvent`s that higher-level components react to on the next turn.
+//! Watches skill roots and repository roots for changes and broadcasts
+//! coarse-grained `FileWatcherEvent`s that higher-level components react to.
 
 use std::collections::HashMap;
 use std::collections::HashSet;
@@ -23,15 +23,18 @@ use tokio::time::sleep_until;
 use tracing::warn;
 
 use crate::config::Config;
+use crate::repo_index_updater::REPO_INDEX_DIR;
 use crate::skills::SkillsManager;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum FileWatcherEvent {
     SkillsChanged { paths: Vec<PathBuf> },
+    RepoFilesChanged { paths: Vec<PathBuf> },
 }
 
 struct WatchState {
     skills_root_ref_counts: HashMap<PathBuf, usize>,
+    repo_root_ref_counts: HashMap<PathBuf, usize>,
 }
 
 struct FileWatcherInner {
@@ -40,18 +43,30 @@ struct FileWatcherInner {
 }
 
 const WATCHER_THROTTLE_INTERVAL: Duration = Duration::from_secs(10);
+const REPO_FILES_THROTTLE_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Repository paths whose changes never affect the repo index. Mirrors the
+/// paths the index scanner skips, including the index's own storage.
+const IGNORED_REPO_PREFIXES: [&str; 4] =
+    [".git", "target", "node_modules", ".codex/repo_hybrid_index"];
 
 /// Coalesces bursts of paths and emits at most once per interval.
 struct ThrottledPaths {
     pending: HashSet<PathBuf>,
     next_allowed_at: Instant,
+    interval: Duration,
 }
 
 impl ThrottledPaths {
     fn new(now: Instant) -> Self {
+        Self::with_interval(now, WATCHER_THROTTLE_INTERVAL)
+    }
+
+    fn with_interval(now: Instant, interval: Duration) -> Self {
         Self {
             pending: HashSet::new(),
             next_allowed_at: now,
+            interval,
         }
     }
 
@@ -80,7 +95,7 @@ impl ThrottledPaths {
     fn take_with_next_allowed(&mut self, now: Instant) -> Vec<PathBuf> {
         let mut paths: Vec<PathBuf> = self.pending.drain().collect();
         paths.sort_unstable_by(|a, b| a.as_os_str().cmp(b.as_os_str()));
-        self.next_allowed_at = now + WATCHER_THROTTLE_INTERVAL;
+        self.next_allowed_at = now + self.interval;
         paths
     }
 }
@@ -94,12 +109,13 @@ pub(crate) struct FileWatcher {
 pub(crate) struct WatchRegistration {
     file_watcher: std::sync::Weak<FileWatcher>,
     roots: Vec<PathBuf>,
+    repo_roots: Vec<PathBuf>,
 }
 
 impl Drop for WatchRegistration {
     fn drop(&mut self) {
         if let Some(file_watcher) = self.file_watcher.upgrade() {
-            file_watcher.unregister_roots(&self.roots);
+            file_watcher.unregister_roots(&self.roots, &self.repo_roots);
         }
     }
 }
@@ -118,6 +134,7 @@ impl FileWatcher {
         let (tx, _) = broadcast::channel(128);
         let state = Arc::new(RwLock::new(WatchState {
             skills_root_ref_counts: HashMap::new(),
+            repo_root_ref_counts: HashMap::new(),
         }));
         let file_watcher = Self {
             inner: Some(Mutex::new(inner)),
@@ -134,6 +151,7 @@ impl FileWatcher {
             inner: None,
             state: Arc::new(RwLock::new(WatchState {
                 skills_root_ref_counts: HashMap::new(),
+                repo_root_ref_counts: HashMap::new(),
             })),
             tx,
         }
@@ -159,9 +177,18 @@ impl FileWatcher {
             self.register_skills_root(root.clone());
         }
 
+        let mut repo_roots = Vec::new();
+        // Only repos with an index get a recursive watch; it feeds the index
+        // auto-update and nothing else.
+        if config.query_project_index.auto_update && config.cwd.join(REPO_INDEX_DIR).is_dir() {
+            self.register_repo_root(config.cwd.clone());
+            repo_roots.push(config.cwd.clone());
+        }
+
         WatchRegistration {
             file_watcher: Arc::downgrade(self),
             roots: registered_roots,
+            repo_roots,
         }
     }
 
@@ -177,10 +204,18 @@ impl FileWatcher {
             handle.spawn(async move {
                 let now = Instant::now();
                 let mut skills = ThrottledPaths::new(now);
+                let mut repo_files =
+                    ThrottledPaths::with_interval(now, REPO_FILES_THROTTLE_INTERVAL);
 
                 loop {
                     let now = Instant::now();
-                    let next_deadline = skills.next_deadline(now);
+                    let next_deadline = match (
+                        skills.next_deadline(now),
+                        repo_files.next_deadline(now),
+                    ) {
+                        (Some(skills), Some(repo_files)) => Some(skills.min(repo_files)),
+                        (skills, repo_files) => skills.or(repo_files),
+                    };
                     let timer_deadline = next_deadline
                         .unwrap_or_else(|| now + Duration::from_secs(60 * 60 * 24 * 365));
                     let timer = sleep_until(timer_deadline);
@@ -191,12 +226,17 @@ impl FileWatcher {
                             match res {
                                 Some(Ok(event)) => {
                                     let skills_paths = classify_event(&event, &state);
+                                    let repo_paths = classify_repo_event(&event, &state);
                                     let now = Instant::now();
                                     skills.add(skills_paths);
+                                    repo_files.add(repo_paths);
 
                                     if let Some(paths) = skills.take_ready(now) {
                                         let _ = tx.send(FileWatcherEvent::SkillsChanged { paths });
                                     }
+                                    if let Some(paths) = repo_files.take_ready(now) {
+                                        let _ = tx.send(FileWatcherEvent::RepoFilesChanged { paths });
+                                    }
                                 }
                                 Some(Err(err)) => {
                                     warn!("file watcher error: {err}");
@@ -208,6 +248,9 @@ impl FileWatcher {
                                     if let Some(paths) = skills.take_pending(now) {
                                         let _ = tx.send(FileWatcherEvent::SkillsChanged { paths });
                                     }
+                                    if let Some(paths) = repo_files.take_pending(now) {
+                                        let _ = tx.send(FileWatcherEvent::RepoFilesChanged { paths });
+                                    }
                                     break;
                                 }
                             }
@@ -217,6 +260,9 @@ impl FileWatcher {
                             if let Some(paths) = skills.take_ready(now) {
                                 let _ = tx.send(FileWatcherEvent::SkillsChanged { paths });
                             }
+                            if let Some(paths) = repo_files.take_ready(now) {
+                                let _ = tx.send(FileWatcherEvent::RepoFilesChanged { paths });
+                            }
                         }
                     }
                 }
@@ -241,25 +287,43 @@ impl FileWatcher {
         }
     }
 
-    fn unregister_roots(&self, roots: &[PathBuf]) {
+    fn register_repo_root(&self, root: PathBuf) {
+        let mut state = self
+            .state
+            .write()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        let count = state.repo_root_ref_counts.entry(root.clone()).or_insert(0);
+        *count += 1;
+        if *count == 1 {
+            self.watch_path(root, RecursiveMode::Recursive);
+        }
+    }
+
+    fn unregister_roots(&self, roots: &[PathBuf], repo_roots: &[PathBuf]) {
         let mut state = self
             .state
             .write()
             .unwrap_or_else(std::sync::PoisonError::into_inner);
         let mut inner_guard: Option<std::sync::MutexGuard<'_, FileWatcherInner>> = None;
 
+        let mut released_roots = Vec::new();
         for root in roots {
-            let mut should_unwatch = false;
-            if let Some(count) = state.skills_root_ref_counts.get_mut(root) {
-                if *count > 1 {
-                    *count -= 1;
-                } else {
-                    state.skills_root_ref_counts.remove(root);
-                    should_unwatch = true;
-                }
+            if release_root(&mut state.skills_root_ref_counts, root) {
+                released_roots.push(root);
+            }
+        }
+        for root in repo_roots {
+            if release_root(&mut state.repo_root_ref_counts, root) {
+                released_roots.push(root);
             }
+        }
 
-            if !should_unwatch {
+        for root in released_roots {
+            // A path can be registered as both a skills root and a repo root;
+            // keep watching it until neither registration remains.
+            if state.skills_root_ref_counts.contains_key(root)
+                || state.repo_root_ref_counts.contains_key(root)
+            {
                 continue;
             }
             let Some(inner) = &self.inner else {
@@ -349,6 +413,62 @@ fn is_skills_path(path: &Path, roots: &HashSet<PathBuf>) -> bool {
     roots.iter().any(|root| path.starts_with(root))
 }
 
+fn classify_repo_event(event: &Event, state: &RwLock<WatchState>) -> Vec<PathBuf> {
+    if !matches!(
+        event.kind,
+        EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)
+    ) {
+        return Vec::new();
+    }
+
+    let repo_roots = match state.read() {
+        Ok(state) => state
+            .repo_root_ref_counts
+            .keys()
+            .cloned()
+            .collect::<Vec<_>>(),
+        Err(err) => err
+            .into_inner()
+            .repo_root_ref_counts
+            .keys()
+            .cloned()
+            .collect::<Vec<_>>(),
+    };
+
+    event
+        .paths
+        .iter()
+        .filter(|path| is_indexable_repo_path(path, &repo_roots))
+        .cloned()
+        .collect()
+}
+
+fn is_indexable_repo_path(path: &Path, roots: &[PathBuf]) -> bool {
+    roots.iter().any(|root| {
+        path.strip_prefix(root).is_ok_and(|relative| {
+            relative.components().next().is_some()
+                && !IGNORED_REPO_PREFIXES
+                    .iter()
+                    .any(|prefix| relative.starts_with(prefix))
+        })
+    })
+}
+
+/// Decrements a root's reference count and reports whether it was the last one.
+fn release_root(ref_counts: &mut HashMap<PathBuf, usize>, root: &Path) -> bool {
+    match ref_counts.get_mut(root) {
+        Some(count) if *count > 1 => {
+            *count -= 1;
+            false
+        }
+        Some(_) => {
+            ref_counts.remove(root);
+            true
+        }
+        None => false,
+    }
+}
+
 #[cfg(test)]
 #[path = "file_watcher_tests.rs"]
 mod tests;
@@ -60,6 +60,7 @@ fn classify_event_filters_to_skills_roots() {
     let root = path("/tmp/skills");
     let state = RwLock::new(WatchState {
         skills_root_ref_counts: HashMap::from([(root.clone(), 1)]),
+        repo_root_ref_counts: HashMap::new(),
     });
     let event = notify_event(
         EventKind::Create(CreateKind::Any),
@@ -79,6 +80,7 @@ fn classify_event_supports_multiple_roots_without_prefix_false_positives() {
     let root_b = path("/tmp/workspace/.codex/skills");
     let state = RwLock::new(WatchState {
         skills_root_ref_counts: HashMap::from([(root_a.clone(), 1), (root_b.clone(), 1)]),
+        repo_root_ref_counts: HashMap::new(),
     });
     let event = notify_event(
         EventKind::Modify(ModifyKind::Any),
@@ -101,6 +103,7 @@ fn classify_event_ignores_non_mutating_event_kinds() {
     let root = path("/tmp/skills");
     let state = RwLock::new(WatchState {
         skills_root_ref_counts: HashMap::from([(root.clone(), 1)]),
+        repo_root_ref_counts: HashMap::new(),
     });
     let path = root.join("demo/SKILL.md");
 
@@ -117,6 +120,36 @@ fn classify_event_ignores_non_mutating_event_kinds() {
     assert_eq!(classify_event(&other_event, &state), Vec::<PathBuf>::new());
 }
 
+#[test]
+fn classify_repo_event_skips_index_storage_and_build_output() {
+    let root = path("/tmp/repo");
+    let state = RwLock::new(WatchState {
+        skills_root_ref_counts: HashMap::new(),
+        repo_root_ref_counts: HashMap::from([(root.clone(), 1)]),
+    });
+    let event = notify_event(
+        EventKind::Modify(ModifyKind::Any),
+        vec![
+            root.join("src/lib.rs"),
+            root.join(".git/index"),
+            root.join(".codex/repo_hybrid_index/index.sqlite"),
+            root.join("target/debug/build.log"),
+            root.join(".codex/prompts/review.md"),
+            path("/tmp/repo-other/src/lib.rs"),
+        ],
+    );
+
+    let classified = classify_repo_event(&event, &state);
+    assert_eq!(
+        classified,
+        vec![
+            root.join("src/lib.rs"),
+            root.join(".codex/prompts/review.md")
+        ]
+    );
+    assert_eq!(classify_event(&event, &state), Vec::<PathBuf>::new());
+}
+
 #[test]
 fn register_skills_root_dedupes_state_entries() {
     let watcher = FileWatcher::noop();
@@ -137,6 +170,7 @@ fn watch_registration_drop_unregisters_roots() {
     let registration = WatchRegistration {
         file_watcher: Arc::downgrade(&watcher),
         roots: vec![root],
+        repo_roots: Vec::new(),
     };
 
     drop(registration);
@@ -145,6 +179,25 @@ fn watch_registration_drop_unregisters_roots() {
     assert_eq!(state.skills_root_ref_counts.len(), 0);
 }
 
+#[test]
+fn watch_registration_drop_keeps_roots_shared_with_other_kinds() {
+    let watcher = Arc::new(FileWatcher::noop());
+    let root = path("/tmp/repo");
+    watcher.register_skills_root(root.clone());
+    watcher.register_repo_root(root.clone());
+    let registration = WatchRegistration {
+        file_watcher: Arc::downgrade(&watcher),
+        roots: Vec::new(),
+        repo_roots: vec![root.clone()],
+    };
+
+    drop(registration);
+
+    let state = watcher.state.read().expect("state lock");
+    assert_eq!(state.repo_root_ref_counts.len(), 0);
+    assert_eq!(state.skills_root_ref_counts.get(&root), Some(&1));
+}
+
 #[test]
 fn unregister_holds_state_lock_until_unwatch_finishes() {
     let temp_dir = tempfile::tempdir().expect("temp dir");
@@ -160,7 +213,7 @@ fn unregister_holds_state_lock_until_unwatch_finishes() {
     let unregister_watcher = Arc::clone(&watcher);
     let unregister_root = root.clone();
     let unregister_thread = std::thread::spawn(move || {
-        unregister_watcher.unregister_roots(&[unregister_root]);
+        unregister_watcher.unregister_roots(&[unregister_root], &[]);
     });
 
     let state_lock_observed = (0..100).any(|_| {
@@ -244,3 +297,34 @@ async fn spawn_event_loop_flushes_pending_changes_on_shutdown() {
         }
     );
 }
+
+#[tokio::test]
+async fn spawn_event_loop_broadcasts_repo_file_changes() {
+    let watcher = FileWatcher::noop();
+    let root = path("/tmp/repo");
+    {
+        let mut state = watcher.state.write().expect("state lock");
+        state.repo_root_ref_counts.insert(root.clone(), 1);
+    }
+
+    let (raw_tx, raw_rx) = mpsc::unbounded_channel();
+    let (tx, mut rx) = broadcast::channel(8);
+    watcher.spawn_event_loop(raw_rx, Arc::clone(&watcher.state), tx);
+
+    raw_tx
+        .send(Ok(notify_event(
+            EventKind::Modify(ModifyKind::Any),
+            vec![root.join("src/main.rs"), root.join(".git/HEAD")],
+        )))
+        .expect("send event");
+    let event = timeout(Duration::from_secs(2), rx.recv())
+        .await
+        .expect("watcher event")
+        .expect("broadcast recv");
+    assert_eq!(
+        event,
+        FileWatcherEvent::RepoFilesChanged {
+            paths: vec![root.join("src/main.rs")]
+        }
+    );
+}
@@ -0,0 +1,268 @@
+//! Core implementation of [`HookModelRunner`], which backs `prompt` and
+//! `agent` handlers from hooks.json.
+//!
+//! Prompt handlers make a single tool-less model request. Agent handlers spawn
+//! a read-only sub-agent through [`crate::agent::AgentControl`] and return its
+//! final message. Either reply is parsed by the hooks engine exactly like
+//! command hook stdout.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use codex_api::ResponseEvent;
+use codex_app_server_protocol::ConfigLayerSource;
+use codex_config::Constrained;
+use codex_hooks::HookModelRequest;
+use codex_hooks::HookModelRunner;
+use codex_protocol::ThreadId;
+use codex_protocol::models::BaseInstructions;
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::protocol::AskForApproval;
+use codex_protocol::protocol::SandboxPolicy;
+use codex_protocol::protocol::SessionSource;
+use codex_protocol::protocol::SubAgentSource;
+use codex_protocol::user_input::UserInput;
+use futures::StreamExt;
+use futures::future::BoxFuture;
+
+use crate::Prompt;
+use crate::agent::AgentControl;
+use crate::agent::AgentStatus;
+use crate::agent::status::is_final;
+use crate::codex::Session;
+use crate::codex::TurnContext;
+use crate::config::Config;
+use crate::config_loader::ConfigLayerStack;
+use crate::features::Feature;
+
+const HOOK_AGENT_SUBAGENT_NAME: &str = "hook_agent";
+
+const PROMPT_HOOK_BASE_INSTRUCTIONS: &str = "You evaluate lifecycle events for a coding agent on behalf of a user-configured hook. Follow the instructions in the user message and reply only with the JSON object it asks for.";
+
+/// Runs model-backed hooks in the context of the turn that triggered them.
+pub(crate) struct SessionHookModelRunner {
+    session: Arc<Session>,
+    turn: Arc<TurnContext>,
+}
+
+impl SessionHookModelRunner {
+    pub(crate) fn new(session: Arc<Session>, turn: Arc<TurnContext>) -> Self {
+        Self { session, turn }
+    }
+
+    async fn run_prompt_hook(&self, request: HookModelRequest) -> Result<String, String> {
+        let (model_info, reasoning_effort) = match request.model.as_deref() {
+            Some(model) if model != self.turn.model_info.slug => {
+                let model_info = self
+                    .session
+                    .services
+                    .models_manager
+                    .get_model_info(model, &self.turn.config)
+                    .await;
+                let reasoning_effort = model_info.default_reasoning_level;
+                (model_info, reasoning_effort)
+            }
+            _ => (self.turn.model_info.clone(), self.turn.reasoning_effort),
+        };
+        let prompt = Prompt {
+            input: vec![ResponseItem::Message {
+                id: None,
+                role: "user".to_string(),
+                content: vec![ContentItem::InputText {
+                    text: request.prompt,
+                }],
+                end_turn: None,
+                phase: None,
+            }],
+            tools: Vec::new(),
+            parallel_tool_calls: false,
+            base_instructions: BaseInstructions {
+                text: PROMPT_HOOK_BASE_INSTRUCTIONS.to_string(),
+            },
+            personality: None,
+            output_schema: None,
+            max_output_tokens: None,
+        };
+
+        let turn_metadata_header = self.turn.turn_metadata_state.current_header_value();
+        let mut client_session = self.session.services.model_client.new_session();
+        let mut stream = client_session
+            .stream(
+                &prompt,
+                &model_info,
+                &self.turn.session_telemetry,
+                reasoning_effort,
+                self.turn.reasoning_summary,
+                self.turn.config.service_tier,
+                turn_metadata_header.as_deref(),
+            )
+            .await
+            .map_err(|err| format!("prompt hook request failed: {err}"))?;
+
+        let mut reply = String::new();
+        while let Some(event) = stream.next().await {
+            match event.map_err(|err| format!("prompt hook request failed: {err}"))? {
+                ResponseEvent::OutputTextDelta(delta) => reply.push_str(&delta),
+                ResponseEvent::OutputItemDone(item) => {
+                    if reply.is_empty()
+                        && let ResponseItem::Message { content, .. } = item
+                        && let Some(text) = crate::compact::content_items_to_text(&content)
+                    {
+                        reply.push_str(&text);
+                    }
+                }
+                ResponseEvent::Completed { .. } => return Ok(reply),
+                _ => {}
+            }
+        }
+        Err("prompt hook stream closed before response.completed".to_string())
+    }
+
+    async fn run_agent_hook(&self, request: HookModelRequest) -> Result<String, String> {
+        let config = build_hook_agent_config(self.turn.config.as_ref(), &request)?;
+        let agent_control = self.session.services.agent_control.clone();
+        let thread_id = agent_control
+            .spawn_agent(
+                config,
+                vec![UserInput::Text {
+                    text: request.prompt,
+                    text_elements: Vec::new(),
+                }],
+                Some(SessionSource::SubAgent(SubAgentSource::Other(
+                    HOOK_AGENT_SUBAGENT_NAME.to_string(),
+                ))),
+            )
+            .await
+            .map_err(|err| format!("failed to spawn hook agent: {err}"))?;
+
+        // Wait on a detached task so the agent is still shut down when the
+        // hooks engine gives up on this future at the handler timeout.
+        let timeout = request.timeout;
+        let status = tokio::spawn(async move {
+            let status =
+                tokio::time::timeout(timeout, wait_for_final_status(&agent_control, thread_id))
+                    .await;
+            let _ = agent_control.shutdown_agent(thread_id).await;
+            status
+        })
+        .await
+        .map_err(|err| format!("hook agent task failed: {err}"))?;
+
+        match status {
+            Ok(AgentStatus::Completed(message)) => Ok(message.unwrap_or_default()),
+            Ok(AgentStatus::Errored(err)) => Err(format!("hook agent failed: {err}")),
+            Ok(status) => Err(format!("hook agent ended without a reply: {status:?}")),
+            Err(_) => Err(format!("hook timed out after {}s", timeout.as_secs())),
+        }
+    }
+}
+
+impl HookModelRunner for SessionHookModelRunner {
+    fn run_prompt(&self, request: HookModelRequest) -> BoxFuture<'_, Result<String, String>> {
+        Box::pin(self.run_prompt_hook(request))
+    }
+
+    fn run_agent(&self, request: HookModelRequest) -> BoxFuture<'_, Result<String, String>> {
+        Box::pin(self.run_agent_hook(request))
+    }
+}
+
+async fn wait_for_final_status(agent_control: &AgentControl, thread_id: ThreadId) -> AgentStatus {
+    let mut status_rx = match agent_control.subscribe_status(thread_id).await {
+        Ok(status_rx) => status_rx,
+        Err(_) => return agent_control.get_status(thread_id).await,
+    };
+    let mut status = status_rx.borrow().clone();
+    while !is_final(&status) {
+        if status_rx.changed().await.is_err() {
+            return agent_control.get_status(thread_id).await;
+        }
+        status = status_rx.borrow().clone();
+    }
+    status
+}
+
+/// Builds the hook agent config from the parent turn config.
+///
+/// The agent can read the workspace but never write, ask for approval, call
+/// MCP tools, spawn further agents, or run hooks of its own, which would
+/// otherwise let an agent hook recursively trigger itself.
+fn build_hook_agent_config(
+    parent_config: &Config,
+    request: &HookModelRequest,
+) -> Result<Config, String> {
+    let mut config = parent_config.clone();
+    config.cwd = request.cwd.clone();
+    if let Some(model) = request.model.as_ref() {
+        config.model = Some(model.clone());
+    }
+    config.permissions.approval_policy = Constrained::allow_only(AskForApproval::Never);
+    config.permissions.sandbox_policy =
+        Constrained::allow_only(SandboxPolicy::new_read_only_policy());
+    // MCP tools can have side effects the sandbox does not cover, so the agent
+    // gets no servers from config, plugins or the apps connector.
+    config.mcp_servers = Constrained::allow_only(HashMap::new());
+    config.config_layer_stack = disable_plugins(&config.config_layer_stack);
+    for feature in [
+        Feature::SpawnCsv,
+        Feature::Collab,
+        Feature::CodexHooks,
+        Feature::Apps,
+    ] {
+        config.features.disable(feature).map_err(|err| {
+            format!(
+                "hook agent could not disable `features.{}`: {err}",
+                feature.key()
+            )
+        })?;
+        if config.features.enabled(feature) {
+            return Err(format!(
+                "hook agent requires `features.{}` to be disabled",
+                feature.key()
+            ));
+        }
+    }
+    Ok(config)
+}
+
+/// Plugins are only enabled from the user config layer, so turning the feature
+/// off there (and in the profile it selects) keeps plugin MCP servers out.
+fn disable_plugins(config_layer_stack: &ConfigLayerStack) -> ConfigLayerStack {
+    let Some(user_layer) = config_layer_stack.get_user_layer() else {
+        return config_layer_stack.clone();
+    };
+    let ConfigLayerSource::User { file } = &user_layer.name else {
+        return config_layer_stack.clone();
+    };
+    let mut user_config = user_layer.config.clone();
+    let profile = user_config
+        .get("profile")
+        .and_then(toml::Value::as_str)
+        .map(str::to_string);
+    if let Some(table) = user_config.as_table_mut() {
+        disable_feature(table, Feature::Plugins);
+        if let Some(profile) = profile
+            && let Some(profile_table) = table
+                .get_mut("profiles")
+                .and_then(|profiles| profiles.get_mut(&profile))
+                .and_then(toml::Value::as_table_mut)
+        {
+            disable_feature(profile_table, Feature::Plugins);
+        }
+    }
+    config_layer_stack.with_user_config(file, user_config)
+}
+
+fn disable_feature(table: &mut toml::map::Map<String, toml::Value>, feature: Feature) {
+    let features = table
+        .entry("features")
+        .or_insert_with(|| toml::Value::Table(toml::map::Map::new()));
+    if let Some(features) = features.as_table_mut() {
+        features.insert(feature.key().to_string(), toml::Value::Boolean(false));
+    }
+}
+
+#[cfg(test)]
+#[path = "hook_model_runner_tests.rs"]
+mod tests;
@@ -0,0 +1,101 @@
+use super::*;
+use crate::config::test_config;
+use crate::config::types::McpServerConfig;
+use codex_protocol::protocol::HookEventName;
+use codex_utils_absolute_path::AbsolutePathBuf;
+use pretty_assertions::assert_eq;
+use std::path::PathBuf;
+use std::time::Duration;
+
+fn hook_model_request(model: Option<&str>) -> HookModelRequest {
+    HookModelRequest {
+        event_name: HookEventName::Stop,
+        prompt: "Is the task complete?".to_string(),
+        model: model.map(str::to_string),
+        cwd: PathBuf::from("/tmp/hook-agent-cwd"),
+        timeout: Duration::from_secs(60),
+    }
+}
+
+#[test]
+fn hook_agent_config_is_read_only_and_cannot_recurse() {
+    let parent_config = test_config();
+
+    let config = build_hook_agent_config(&parent_config, &hook_model_request(None))
+        .expect("hook agent config");
+
+    assert_eq!(config.cwd, PathBuf::from("/tmp/hook-agent-cwd"));
+    assert_eq!(config.model, parent_config.model);
+    assert_eq!(
+        config.permissions.approval_policy,
+        Constrained::allow_only(AskForApproval::Never)
+    );
+    assert_eq!(
+        config.permissions.sandbox_policy,
+        Constrained::allow_only(SandboxPolicy::new_read_only_policy())
+    );
+    assert!(!config.features.enabled(Feature::Collab));
+    assert!(!config.features.enabled(Feature::SpawnCsv));
+    assert!(!config.features.enabled(Feature::CodexHooks));
+    assert!(!config.features.enabled(Feature::Apps));
+}
+
+#[test]
+fn hook_agent_config_has_no_mcp_servers() {
+    let mut parent_config = test_config();
+    let server: McpServerConfig = toml::from_str(r#"command = "echo""#).expect("server config");
+    parent_config
+        .mcp_servers
+        .set(HashMap::from([("docs".to_string(), server)]))
+        .expect("set mcp servers");
+    let user_file =
+        AbsolutePathBuf::from_absolute_path(parent_config.codex_home.join("config.toml"))
+            .expect("absolute path");
+    parent_config.config_layer_stack = parent_config.config_layer_stack.with_user_config(
+        &user_file,
+        toml::from_str(
+            r#"
+                profile = "work"
+
+                [features]
+                plugins = true
+
+                [profiles.work.features]
+                plugins = true
+            "#,
+        )
+        .expect("user config"),
+    );
+
+    let config = build_hook_agent_config(&parent_config, &hook_model_request(None))
+        .expect("hook agent config");
+
+    assert!(config.mcp_servers.get().is_empty());
+    let user_config = &config
+        .config_layer_stack
+        .get_user_layer()
+        .expect("user layer")
+        .config;
+    assert_eq!(
+        user_config
+            .get("features")
+            .and_then(|features| features.get("plugins")),
+        Some(&toml::Value::Boolean(false))
+    );
+    assert_eq!(
+        user_config
+            .get("profiles")
+            .and_then(|profiles| profiles.get("work"))
+            .and_then(|profile| profile.get("features"))
+            .and_then(|features| features.get("plugins")),
+        Some(&toml::Value::Boolean(false))
+    );
+}
+
+#[test]
+fn hook_agent_config_applies_handler_model_override() {
+    let config = build_hook_agent_config(&test_config(), &hook_model_request(Some("hook-model")))
+        .expect("hook agent config");
+
+    assert_eq!(config.model, Some("hook-model".to_string()));
+}
@@ -4,12 +4,18 @@ use crate::spawn::StdioPolicy;
 use crate::spawn::spawn_child_async;
 use codex_network_proxy::NetworkProxy;
 use codex_protocol::permissions::FileSystemSandboxPolicy;
+use codex_protocol::permissions::GlobDenyMatches;
 use codex_protocol::permissions::NetworkSandboxPolicy;
+use codex_protocol::permissions::OverlayWorkspaceLayers;
+use codex_protocol::permissions::ResourceLimits;
 use std::collections::HashMap;
 use std::path::Path;
 use std::path::PathBuf;
 use tokio::process::Child;
 
+/// Largest `--glob-deny-matches` argument passed to the helper.
+const MAX_GLOB_DENY_MATCHES_ARG_BYTES: usize = 64 * 1024;
+
 /// Spawn a shell tool command under the Linux sandbox helper
 /// (codex-linux-sandbox), which defaults to bubblewrap for filesystem
 /// isolation plus seccomp for network restrictions.
@@ -41,6 +47,9 @@ where
         sandbox_policy,
         &file_system_sandbox_policy,
         network_sandbox_policy,
+        ResourceLimits::default(),
+        None,
+        None,
         sandbox_policy_cwd,
         use_legacy_landlock,
         allow_network_for_proxy(false),
@@ -71,14 +80,20 @@ pub(crate) fn allow_network_for_proxy(enforce_managed_network: bool) -> bool {
 ///
 /// The helper performs the actual sandboxing (bubblewrap by default + seccomp) after
 /// parsing these arguments. Policy JSON flags are emitted before helper feature
-/// flags so the argv order matches the helper's CLI shape. See
-/// `docs/linux_sandbox.md` for the Linux semantics.
+/// flags so the argv order matches the helper's CLI shape. Resource limits are
+/// only passed when at least one limit is set, the copy-on-write workspace
+/// layers only when the session has one, and the expanded glob deny matches
+/// only when the turn already expanded them. See `docs/linux_sandbox.md` for
+/// the Linux semantics.
 #[allow(clippy::too_many_arguments)]
 pub(crate) fn create_linux_sandbox_command_args_for_policies(
     command: Vec<String>,
     sandbox_policy: &SandboxPolicy,
     file_system_sandbox_policy: &FileSystemSandboxPolicy,
     network_sandbox_policy: NetworkSandboxPolicy,
+    resource_limits: ResourceLimits,
+    overlay_workspace: Option<&OverlayWorkspaceLayers>,
+    glob_deny_matches: Option<&GlobDenyMatches>,
     sandbox_policy_cwd: &Path,
     use_legacy_landlock: bool,
     allow_network_for_proxy: bool,
@@ -104,6 +119,28 @@ pub(crate) fn create_linux_sandbox_command_args_for_policies(
         "--network-sandbox-policy".to_string(),
         network_policy_json,
     ];
+    if !resource_limits.is_empty() {
+        let resource_limits_json = serde_json::to_string(&resource_limits)
+            .unwrap_or_else(|err| panic!("failed to serialize resource limits: {err}"));
+        linux_cmd.push("--resource-limits".to_string());
+        linux_cmd.push(resource_limits_json);
+    }
+    if let Some(overlay_workspace) = overlay_workspace {
+        let overlay_workspace_json = serde_json::to_string(overlay_workspace)
+            .unwrap_or_else(|err| panic!("failed to serialize workspace overlay: {err}"));
+        linux_cmd.push("--overlay-workspace".to_string());
+        linux_cmd.push(overlay_workspace_json);
+    }
+    if let Some(glob_deny_matches) = glob_deny_matches {
+        let glob_deny_matches_json = serde_json::to_string(glob_deny_matches)
+            .unwrap_or_else(|err| panic!("failed to serialize glob deny matches: {err}"));
+        // The kernel caps each argument at 128 KiB; past that the helper
+        // expands the globs itself.
+        if glob_deny_matches_json.len() <= MAX_GLOB_DENY_MATCHES_ARG_BYTES {
+            linux_cmd.push("--glob-deny-matches".to_string());
+            linux_cmd.push(glob_deny_matches_json);
+        }
+    }
     if use_legacy_landlock {
         linux_cmd.push("--use-legacy-landlock".to_string());
     }
@@ -115,6 +152,31 @@ pub(crate) fn create_linux_sandbox_command_args_for_policies(
     linux_cmd
 }
 
+/// Builds the `codex-linux-sandbox` invocation for a command that otherwise
+/// runs without a sandbox, such as an approved escalated retry. The helper runs
+/// it with full access and only enforces `resource_limits`.
+pub(crate) fn create_linux_resource_limits_command_args(
+    command: Vec<String>,
+    resource_limits: ResourceLimits,
+    sandbox_policy_cwd: &Path,
+) -> Vec<String> {
+    let sandbox_policy = SandboxPolicy::DangerFullAccess;
+    let file_system_sandbox_policy =
+        FileSystemSandboxPolicy::from_legacy_sandbox_policy(&sandbox_policy, sandbox_policy_cwd);
+    create_linux_sandbox_command_args_for_policies(
+        command,
+        &sandbox_policy,
+        &file_system_sandbox_policy,
+        NetworkSandboxPolicy::from(&sandbox_policy),
+        resource_limits,
+        None,
+        None,
+        sandbox_policy_cwd,
+        false,
+        allow_network_for_proxy(false),
+    )
+}
+
 /// Converts the sandbox cwd and execution options into the CLI invocation for
 /// `codex-linux-sandbox`.
 #[cfg(test)]
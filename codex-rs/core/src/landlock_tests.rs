@@ -44,6 +44,9 @@ fn split_policy_flags_are_included() {
         &sandbox_policy,
         &file_system_sandbox_policy,
         network_sandbox_policy,
+        ResourceLimits::default(),
+        None,
+        None,
         cwd,
         true,
         false,
@@ -61,6 +64,131 @@ fn split_policy_flags_are_included() {
     );
 }
 
+#[test]
+fn resource_limits_flag_is_included_only_when_limits_are_set() {
+    let command = vec!["/bin/true".to_string()];
+    let cwd = Path::new("/tmp");
+    let sandbox_policy = SandboxPolicy::new_read_only_policy();
+    let file_system_sandbox_policy = FileSystemSandboxPolicy::from(&sandbox_policy);
+    let network_sandbox_policy = NetworkSandboxPolicy::from(&sandbox_policy);
+    let args_for = |resource_limits| {
+        create_linux_sandbox_command_args_for_policies(
+            command.clone(),
+            &sandbox_policy,
+            &file_system_sandbox_policy,
+            network_sandbox_policy,
+            resource_limits,
+            None,
+            None,
+            cwd,
+            false,
+            false,
+        )
+    };
+
+    assert_eq!(
+        args_for(ResourceLimits::default()).contains(&"--resource-limits".to_string()),
+        false
+    );
+    let args = args_for(ResourceLimits {
+        pids_max: Some(64),
+        ..Default::default()
+    });
+    assert_eq!(
+        args.windows(2)
+            .any(|window| window[0] == "--resource-limits" && window[1] == r#"{"pids_max":64}"#),
+        true
+    );
+}
+
+#[test]
+fn overlay_workspace_flag_carries_the_layers() {
+    let command = vec!["/bin/true".to_string()];
+    let cwd = Path::new("/tmp");
+    let sandbox_policy = SandboxPolicy::new_workspace_write_policy();
+    let file_system_sandbox_policy = FileSystemSandboxPolicy::from(&sandbox_policy);
+    let network_sandbox_policy = NetworkSandboxPolicy::from(&sandbox_policy);
+    let layers = OverlayWorkspaceLayers {
+        lower: PathBuf::from("/repo"),
+        upper: PathBuf::from("/codex/overlays/t/upper"),
+        work: PathBuf::from("/codex/overlays/t/work"),
+    };
+
+    let args = create_linux_sandbox_command_args_for_policies(
+        command,
+        &sandbox_policy,
+        &file_system_sandbox_policy,
+        network_sandbox_policy,
+        ResourceLimits::default(),
+        Some(&layers),
+        None,
+        cwd,
+        false,
+        false,
+    );
+
+    let position = args
+        .iter()
+        .position(|arg| arg == "--overlay-workspace")
+        .expect("overlay flag");
+    assert_eq!(
+        serde_json::from_str::<OverlayWorkspaceLayers>(&args[position + 1]).expect("layers"),
+        layers
+    );
+}
+
+#[test]
+fn glob_deny_matches_flag_is_skipped_when_too_large() {
+    let command = vec!["/bin/true".to_string()];
+    let cwd = Path::new("/tmp");
+    let sandbox_policy = SandboxPolicy::new_workspace_write_policy();
+    let file_system_sandbox_policy = FileSystemSandboxPolicy::from(&sandbox_policy);
+    let network_sandbox_policy = NetworkSandboxPolicy::from(&sandbox_policy);
+    let args_for = |glob_deny_matches: &GlobDenyMatches| {
+        create_linux_sandbox_command_args_for_policies(
+            command.clone(),
+            &sandbox_policy,
+            &file_system_sandbox_policy,
+            network_sandbox_policy,
+            ResourceLimits::default(),
+            None,
+            Some(glob_deny_matches),
+            cwd,
+            false,
+            false,
+        )
+    };
+    let env_path = |index: usize| {
+        codex_utils_absolute_path::AbsolutePathBuf::from_absolute_path(format!(
+            "/repo/pkg-{index}/.env"
+        ))
+        .expect("absolute")
+    };
+
+    let small = GlobDenyMatches {
+        paths: vec![env_path(0)],
+        truncated_under: None,
+    };
+    let args = args_for(&small);
+    let position = args
+        .iter()
+        .position(|arg| arg == "--glob-deny-matches")
+        .expect("glob deny matches flag");
+    assert_eq!(
+        serde_json::from_str::<GlobDenyMatches>(&args[position + 1]).expect("matches"),
+        small
+    );
+
+    let large = GlobDenyMatches {
+        paths: (0..10_000).map(env_path).collect(),
+        truncated_under: None,
+    };
+    assert_eq!(
+        args_for(&large).contains(&"--glob-deny-matches".to_string()),
+        false
+    );
+}
+
 #[test]
 fn proxy_network_requires_managed_requirements() {
     assert_eq!(allow_network_for_proxy(false), false);
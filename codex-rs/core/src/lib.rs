@@ -11,6 +11,8 @@ mod apply_patch;
 mod apps;
 mod arc_monitor;
 pub mod auth;
+mod background_services;
+pub use background_services::BackgroundServiceError;
 mod client;
 mod client_common;
 pub mod codex;
@@ -43,6 +45,7 @@ mod file_watcher;
 mod flags;
 pub mod git_info;
 mod guardian;
+mod hook_model_runner;
 pub mod instructions;
 pub mod landlock;
 pub mod mcp;
@@ -52,6 +55,7 @@ pub mod models_manager;
 mod network_policy_decision;
 pub mod network_proxy_loader;
 mod original_image_detail;
+mod overlay_workspace;
 pub use mcp_connection_manager::MCP_SANDBOX_STATE_CAPABILITY;
 pub use mcp_connection_manager::MCP_SANDBOX_STATE_METHOD;
 pub use mcp_connection_manager::SandboxState;
@@ -65,6 +69,8 @@ mod model_provider_info;
 pub mod path_utils;
 pub mod personality_migration;
 pub mod plugins;
+mod repo_index_updater;
+mod run_budget;
 mod sandbox_tags;
 pub mod sandboxing;
 mod session_prefix;
@@ -73,6 +79,8 @@ mod stream_events_utils;
 pub mod test_support;
 mod text_encoding;
 pub mod token_data;
+mod tool_output_store;
+mod tool_secrets;
 mod truncate;
 mod unified_exec;
 pub mod windows_sandbox;
@@ -134,6 +142,7 @@ pub use rollout::find_thread_path_by_id_str;
 pub use rollout::find_thread_path_by_name_str;
 pub use rollout::list::Cursor;
 pub use rollout::list::ThreadItem;
+pub use rollout::list::ThreadSearchHit;
 pub use rollout::list::ThreadSortKey;
 pub use rollout::list::ThreadsPage;
 pub use rollout::list::parse_cursor;
@@ -170,6 +179,7 @@ pub use exec_policy::format_exec_policy_error_with_source;
 pub use exec_policy::load_exec_policy;
 pub use file_watcher::FileWatcherEvent;
 pub use safety::get_platform_sandbox;
+pub use tool_output_store::remove_thread_tool_outputs;
 pub use tools::spec::parse_tool_input_schema;
 pub use turn_metadata::build_turn_metadata_header;
 pub mod compact;
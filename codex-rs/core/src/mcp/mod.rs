@@ -1,4 +1,9 @@
 pub mod auth;
+pub(crate) mod prompts;
+pub(crate) mod resource_subscriptions;
+pub(crate) mod roots;
+pub(crate) mod sampling;
+pub(crate) mod sandbox;
 mod skill_dependencies;
 pub(crate) use skill_dependencies::maybe_prompt_and_install_mcp_dependencies;
 
@@ -214,6 +219,8 @@ fn codex_apps_mcp_server_config(config: &Config, auth: Option<&CodexAuth>) -> Mc
         disabled_tools: None,
         scopes: None,
         oauth_resource: None,
+        sampling: None,
+        sandbox: None,
     }
 }
 
@@ -302,6 +309,8 @@ fn repo_tools_mcp_server_config_for_launcher(
         disabled_tools: None,
         scopes: None,
         oauth_resource: None,
+        sampling: None,
+        sandbox: None,
     }
 }
 
@@ -972,6 +981,8 @@ mod tests {
                 disabled_tools: None,
                 scopes: None,
                 oauth_resource: None,
+                sampling: None,
+                sandbox: None,
             },
         );
         config
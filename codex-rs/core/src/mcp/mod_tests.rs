@@ -305,6 +305,8 @@ async fn effective_mcp_servers_include_plugins_without_overriding_user_config()
             disabled_tools: None,
             scopes: None,
             oauth_resource: None,
+            sampling: None,
+            sandbox: None,
         },
     );
     config
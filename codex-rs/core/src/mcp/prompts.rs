@@ -0,0 +1,74 @@
+//! Conversions for MCP prompts (`prompts/list` and `prompts/get`).
+//!
+//! Codex submits a fetched prompt as a single user message, so the messages a
+//! server returns are flattened to text here.
+
+use std::collections::HashMap;
+
+use codex_protocol::mcp::Prompt as McpPrompt;
+use rmcp::model::GetPromptResult;
+use rmcp::model::JsonObject;
+use rmcp::model::Prompt;
+use rmcp::model::PromptMessageContent;
+use rmcp::model::ResourceContents;
+
+/// Converts the prompts reported by a server into protocol types, dropping
+/// any that fail to convert.
+pub(crate) fn prompts_from_mcp(server_name: &str, prompts: Vec<Prompt>) -> Vec<McpPrompt> {
+    prompts
+        .into_iter()
+        .filter_map(|prompt| {
+            let name = prompt.name.clone();
+            match serde_json::to_value(prompt).and_then(McpPrompt::from_mcp_value) {
+                Ok(prompt) => Some(prompt),
+                Err(err) => {
+                    tracing::warn!(
+                        "Failed to convert MCP prompt '{name}' from server '{server_name}': {err}"
+                    );
+                    None
+                }
+            }
+        })
+        .collect()
+}
+
+/// Builds the `arguments` object for a `prompts/get` request.
+pub(crate) fn prompt_arguments(arguments: HashMap<String, String>) -> Option<JsonObject> {
+    if arguments.is_empty() {
+        return None;
+    }
+    Some(
+        arguments
+            .into_iter()
+            .map(|(name, value)| (name, serde_json::Value::String(value)))
+            .collect(),
+    )
+}
+
+/// Joins the text of every message in `result`. Embedded text resources are
+/// inlined, resource links are referenced by URI, and images and binary
+/// resources are skipped.
+pub(crate) fn prompt_result_text(result: GetPromptResult) -> Result<String, String> {
+    let parts: Vec<String> = result
+        .messages
+        .into_iter()
+        .filter_map(|message| match message.content {
+            PromptMessageContent::Text { text } => Some(text),
+            PromptMessageContent::Resource { resource } => match &resource.resource {
+                ResourceContents::TextResourceContents { text, .. } => Some(text.clone()),
+                ResourceContents::BlobResourceContents { .. } => None,
+            },
+            PromptMessageContent::ResourceLink { link } => Some(link.uri.clone()),
+            PromptMessageContent::Image { .. } => None,
+        })
+        .filter(|text| !text.trim().is_empty())
+        .collect();
+    if parts.is_empty() {
+        return Err("the prompt did not return any text".to_string());
+    }
+    Ok(parts.join("\n\n"))
+}
+
+#[cfg(test)]
+#[path = "prompts_tests.rs"]
+mod tests;
@@ -0,0 +1,70 @@
+use super::*;
+use pretty_assertions::assert_eq;
+use rmcp::model::AnnotateAble;
+use rmcp::model::PromptArgument;
+use rmcp::model::PromptMessage;
+use rmcp::model::PromptMessageRole;
+use rmcp::model::RawImageContent;
+
+#[test]
+fn prompts_from_mcp_keeps_arguments() {
+    let prompt = Prompt::new(
+        "review",
+        Some("Ask for a review of a file."),
+        Some(vec![PromptArgument {
+            name: "file".to_string(),
+            title: None,
+            description: Some("Path of the file to review.".to_string()),
+            required: Some(true),
+        }]),
+    );
+
+    assert_eq!(
+        prompts_from_mcp("docs", vec![prompt]),
+        vec![McpPrompt {
+            name: "review".to_string(),
+            title: None,
+            description: Some("Ask for a review of a file.".to_string()),
+            arguments: Some(vec![codex_protocol::mcp::PromptArgument {
+                name: "file".to_string(),
+                title: None,
+                description: Some("Path of the file to review.".to_string()),
+                required: Some(true),
+            }]),
+        }]
+    );
+}
+
+#[test]
+fn prompt_result_text_joins_text_and_skips_images() {
+    let result = GetPromptResult {
+        description: None,
+        messages: vec![
+            PromptMessage::new_text(PromptMessageRole::User, "Review this file."),
+            PromptMessage {
+                role: PromptMessageRole::User,
+                content: PromptMessageContent::Image {
+                    image: RawImageContent {
+                        data: "AAAA".to_string(),
+                        mime_type: "image/png".to_string(),
+                        meta: None,
+                    }
+                    .no_annotation(),
+                },
+            },
+            PromptMessage::new_text(PromptMessageRole::Assistant, "Focus on errors."),
+        ],
+    };
+
+    assert_eq!(
+        prompt_result_text(result),
+        Ok("Review this file.\n\nFocus on errors.".to_string())
+    );
+    assert_eq!(
+        prompt_result_text(GetPromptResult {
+            description: None,
+            messages: Vec::new(),
+        }),
+        Err("the prompt did not return any text".to_string())
+    );
+}
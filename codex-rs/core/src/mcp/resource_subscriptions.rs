@@ -0,0 +1,159 @@
+//! MCP resource subscriptions (`resources/subscribe`).
+//!
+//! A server reports a change to a subscribed resource with
+//! `notifications/resources/updated`. The update is remembered until the next
+//! turn starts; the resource is then read again and its contents are added to
+//! the conversation as context. A resource that cannot be read then stays
+//! queued for the turn after.
+
+use std::collections::BTreeSet;
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::PoisonError;
+use std::time::Duration;
+
+use codex_protocol::models::ResponseItem;
+use rmcp::model::ReadResourceResult;
+use rmcp::model::ResourceContents;
+use tokio::sync::broadcast;
+use tokio::sync::broadcast::error::RecvError;
+
+use crate::contextual_user_message::MCP_RESOURCE_UPDATE_FRAGMENT;
+use crate::truncate::TruncationPolicy;
+use crate::truncate::truncate_text;
+
+/// Upper bound on the resource text injected for a single update.
+const MAX_RESOURCE_UPDATE_TOKENS: usize = 2_000;
+
+/// How long the start of a turn waits on reading a single updated resource.
+pub(crate) const RESOURCE_UPDATE_READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A resource on a specific server: `(server, uri)`.
+pub(crate) type ResourceKey = (String, String);
+
+#[derive(Clone, Default)]
+pub(crate) struct ResourceSubscriptions {
+    state: Arc<Mutex<SubscriptionState>>,
+}
+
+#[derive(Default)]
+struct SubscriptionState {
+    subscribed: HashSet<ResourceKey>,
+    updated: BTreeSet<ResourceKey>,
+}
+
+impl ResourceSubscriptions {
+    pub(crate) fn insert(&self, server: &str, uri: &str) {
+        self.lock()
+            .subscribed
+            .insert((server.to_string(), uri.to_string()));
+    }
+
+    /// Forgets a subscription and any update still waiting for the next turn.
+    pub(crate) fn remove(&self, server: &str, uri: &str) -> bool {
+        let key = (server.to_string(), uri.to_string());
+        let mut state = self.lock();
+        state.updated.remove(&key);
+        state.subscribed.remove(&key)
+    }
+
+    /// Records an update. Updates for resources Codex did not subscribe to
+    /// are ignored.
+    pub(crate) fn mark_updated(&self, server: &str, uri: &str) {
+        let key = (server.to_string(), uri.to_string());
+        let mut state = self.lock();
+        if state.subscribed.contains(&key) {
+            state.updated.insert(key);
+        }
+    }
+
+    /// Takes the resources updated since the last call.
+    pub(crate) fn take_updated(&self) -> UpdatedResources {
+        UpdatedResources {
+            subscriptions: self.clone(),
+            pending: std::mem::take(&mut self.lock().updated),
+        }
+    }
+
+    /// Records updates reported by `server_name` until its client goes away.
+    pub(crate) fn watch(&self, server_name: String, mut updates: broadcast::Receiver<String>) {
+        let subscriptions = self.clone();
+        tokio::spawn(async move {
+            loop {
+                match updates.recv().await {
+                    Ok(uri) => subscriptions.mark_updated(&server_name, &uri),
+                    Err(RecvError::Lagged(skipped)) => {
+                        tracing::warn!(
+                            "dropped {skipped} resource updates from MCP server '{server_name}'"
+                        );
+                    }
+                    Err(RecvError::Closed) => break,
+                }
+            }
+        });
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, SubscriptionState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Updated resources taken for a turn. Resources not marked read by the time
+/// this is dropped are queued again, so a read that fails, times out or is
+/// cancelled with the turn is retried at the next one.
+pub(crate) struct UpdatedResources {
+    subscriptions: ResourceSubscriptions,
+    pending: BTreeSet<ResourceKey>,
+}
+
+impl UpdatedResources {
+    /// The resources still to be read, ordered by server and URI.
+    pub(crate) fn keys(&self) -> Vec<ResourceKey> {
+        self.pending.iter().cloned().collect()
+    }
+
+    pub(crate) fn mark_read(&mut self, server: &str, uri: &str) {
+        self.pending.remove(&(server.to_string(), uri.to_string()));
+    }
+}
+
+impl Drop for UpdatedResources {
+    fn drop(&mut self) {
+        for (server, uri) in std::mem::take(&mut self.pending) {
+            self.subscriptions.mark_updated(&server, &uri);
+        }
+    }
+}
+
+/// Builds the context message for an updated resource from its current
+/// contents.
+pub(crate) fn resource_update_message(
+    server: &str,
+    uri: &str,
+    result: ReadResourceResult,
+) -> ResponseItem {
+    let contents: Vec<String> = result
+        .contents
+        .into_iter()
+        .map(|contents| match contents {
+            ResourceContents::TextResourceContents { text, .. } => text,
+            ResourceContents::BlobResourceContents { mime_type, .. } => format!(
+                "[binary content: {}]",
+                mime_type.as_deref().unwrap_or("unknown type")
+            ),
+        })
+        .collect();
+    let body = format!(
+        "The subscribed resource `{uri}` from MCP server `{server}` changed. Its current contents:\n\n{}",
+        truncate_text(
+            &contents.join("\n\n"),
+            TruncationPolicy::Tokens(MAX_RESOURCE_UPDATE_TOKENS)
+        )
+    );
+    MCP_RESOURCE_UPDATE_FRAGMENT.into_message(MCP_RESOURCE_UPDATE_FRAGMENT.wrap(body))
+}
+
+#[cfg(test)]
+#[path = "resource_subscriptions_tests.rs"]
+mod tests;
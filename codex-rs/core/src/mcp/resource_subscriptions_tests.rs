@@ -0,0 +1,94 @@
+use super::*;
+use codex_protocol::models::ContentItem;
+use pretty_assertions::assert_eq;
+
+#[test]
+fn only_subscribed_updates_are_kept_until_taken() {
+    let subscriptions = ResourceSubscriptions::default();
+    subscriptions.insert("docs", "memo://b");
+    subscriptions.insert("docs", "memo://a");
+    subscriptions.insert("wiki", "page://home");
+
+    subscriptions.mark_updated("docs", "memo://b");
+    subscriptions.mark_updated("docs", "memo://a");
+    subscriptions.mark_updated("docs", "memo://a");
+    subscriptions.mark_updated("docs", "memo://unknown");
+    subscriptions.mark_updated("wiki", "page://home");
+    assert!(subscriptions.remove("wiki", "page://home"));
+
+    let mut updated = subscriptions.take_updated();
+    assert_eq!(
+        updated.keys(),
+        vec![
+            ("docs".to_string(), "memo://a".to_string()),
+            ("docs".to_string(), "memo://b".to_string()),
+        ]
+    );
+    updated.mark_read("docs", "memo://a");
+    updated.mark_read("docs", "memo://b");
+    drop(updated);
+    assert_eq!(
+        subscriptions.take_updated().keys(),
+        Vec::<ResourceKey>::new()
+    );
+    assert!(!subscriptions.remove("wiki", "page://home"));
+}
+
+#[test]
+fn unread_updates_are_queued_again() {
+    let subscriptions = ResourceSubscriptions::default();
+    subscriptions.insert("docs", "memo://a");
+    subscriptions.insert("docs", "memo://b");
+    subscriptions.insert("docs", "memo://c");
+    subscriptions.mark_updated("docs", "memo://a");
+    subscriptions.mark_updated("docs", "memo://b");
+    subscriptions.mark_updated("docs", "memo://c");
+
+    let mut updated = subscriptions.take_updated();
+    updated.mark_read("docs", "memo://a");
+    assert!(subscriptions.remove("docs", "memo://c"));
+    drop(updated);
+
+    assert_eq!(
+        subscriptions.take_updated().keys(),
+        vec![("docs".to_string(), "memo://b".to_string())]
+    );
+}
+
+#[test]
+fn resource_update_message_wraps_contents_as_context() {
+    let message = resource_update_message(
+        "docs",
+        "memo://a",
+        ReadResourceResult {
+            contents: vec![
+                ResourceContents::TextResourceContents {
+                    uri: "memo://a".to_string(),
+                    mime_type: Some("text/plain".to_string()),
+                    text: "hello".to_string(),
+                    meta: None,
+                },
+                ResourceContents::BlobResourceContents {
+                    uri: "memo://a".to_string(),
+                    mime_type: Some("image/png".to_string()),
+                    blob: "AAAA".to_string(),
+                    meta: None,
+                },
+            ],
+        },
+    );
+
+    let ResponseItem::Message { role, content, .. } = message else {
+        panic!("expected a message");
+    };
+    assert_eq!(role, "user");
+    assert_eq!(
+        content,
+        vec![ContentItem::InputText {
+            text: "<mcp_resource_update>\nThe subscribed resource `memo://a` from MCP server `docs` changed. Its current contents:\n\nhello\n\n[binary content: image/png]\n</mcp_resource_update>".to_string(),
+        }]
+    );
+    assert!(crate::contextual_user_message::is_contextual_user_fragment(
+        &content[0]
+    ));
+}
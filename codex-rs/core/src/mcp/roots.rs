@@ -0,0 +1,61 @@
+//! Roots reported to MCP servers from `roots/list`.
+//!
+//! Servers learn the session's workspace from the sandbox: the working
+//! directory, any extra writable roots (such as `--add-dir`), and the readable
+//! roots of a restricted read policy. Temporary directories the sandbox also
+//! allows writes to are not part of the workspace and are left out.
+
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+use codex_protocol::protocol::ReadOnlyAccess;
+use codex_protocol::protocol::SandboxPolicy;
+use codex_utils_absolute_path::AbsolutePathBuf;
+use rmcp::model::Root;
+
+use crate::mcp_connection_manager::SandboxState;
+
+pub(crate) fn roots_for_sandbox_state(sandbox_state: &SandboxState) -> Vec<Root> {
+    let mut paths = vec![sandbox_state.sandbox_cwd.clone()];
+    match &sandbox_state.sandbox_policy {
+        SandboxPolicy::WorkspaceWrite {
+            writable_roots,
+            read_only_access,
+            ..
+        } => {
+            paths.extend(writable_roots.iter().map(AbsolutePathBuf::to_path_buf));
+            paths.extend(restricted_readable_roots(read_only_access));
+        }
+        SandboxPolicy::ReadOnly { access, .. } => {
+            paths.extend(restricted_readable_roots(access));
+        }
+        SandboxPolicy::DangerFullAccess | SandboxPolicy::ExternalSandbox { .. } => {}
+    }
+
+    let mut seen = HashSet::new();
+    paths
+        .into_iter()
+        .filter(|path| seen.insert(path.clone()))
+        .filter_map(|path| {
+            let uri = url::Url::from_file_path(&path).ok()?.to_string();
+            let name = path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned());
+            Some(Root { uri, name })
+        })
+        .collect()
+}
+
+fn restricted_readable_roots(access: &ReadOnlyAccess) -> Vec<PathBuf> {
+    match access {
+        ReadOnlyAccess::Restricted { readable_roots, .. } => readable_roots
+            .iter()
+            .map(AbsolutePathBuf::to_path_buf)
+            .collect(),
+        ReadOnlyAccess::FullAccess => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+#[path = "roots_tests.rs"]
+mod tests;
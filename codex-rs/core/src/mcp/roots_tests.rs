@@ -0,0 +1,54 @@
+use super::*;
+use pretty_assertions::assert_eq;
+
+fn sandbox_state(sandbox_policy: SandboxPolicy, cwd: PathBuf) -> SandboxState {
+    SandboxState {
+        sandbox_policy,
+        codex_linux_sandbox_exe: None,
+        sandbox_cwd: cwd,
+        use_legacy_landlock: false,
+    }
+}
+
+fn root(path: &PathBuf, name: &str) -> Root {
+    Root {
+        uri: url::Url::from_file_path(path)
+            .expect("absolute path")
+            .to_string(),
+        name: Some(name.to_string()),
+    }
+}
+
+#[test]
+fn roots_include_cwd_and_extra_workspace_roots_but_not_tmp() {
+    let base = std::env::temp_dir();
+    let cwd = base.join("project");
+    let extra = base.join("shared");
+    let docs = base.join("docs");
+    let policy = SandboxPolicy::WorkspaceWrite {
+        writable_roots: vec![
+            AbsolutePathBuf::from_absolute_path(&extra).expect("absolute"),
+            AbsolutePathBuf::from_absolute_path(&cwd).expect("absolute"),
+        ],
+        read_only_access: ReadOnlyAccess::Restricted {
+            include_platform_defaults: true,
+            readable_roots: vec![AbsolutePathBuf::from_absolute_path(&docs).expect("absolute")],
+        },
+        network_access: false,
+        exclude_tmpdir_env_var: false,
+        exclude_slash_tmp: false,
+    };
+
+    assert_eq!(
+        roots_for_sandbox_state(&sandbox_state(policy, cwd.clone())),
+        vec![
+            root(&cwd, "project"),
+            root(&extra, "shared"),
+            root(&docs, "docs"),
+        ]
+    );
+    assert_eq!(
+        roots_for_sandbox_state(&sandbox_state(SandboxPolicy::DangerFullAccess, cwd.clone())),
+        vec![root(&cwd, "project")]
+    );
+}
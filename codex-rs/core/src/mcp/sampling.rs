@@ -0,0 +1,279 @@
+//! Support for MCP `sampling/createMessage` requests.
+//!
+//! Servers that opt in via `[mcp_servers.<name>.sampling]` may ask Codex for a
+//! model completion. The request is validated and clamped here, surfaced to
+//! the user for approval, and once approved runs as a single tool-less request
+//! against the session's configured provider.
+
+use codex_api::ResponseEvent;
+use codex_protocol::approvals::McpSamplingMessage;
+use codex_protocol::approvals::McpSamplingRole;
+use codex_protocol::models::BaseInstructions;
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::ResponseItem;
+use codex_utils_string::take_bytes_at_char_boundary;
+use futures::Stream;
+use futures::StreamExt;
+use rmcp::model::CreateMessageRequestParams;
+use rmcp::model::CreateMessageResult;
+use rmcp::model::ErrorCode;
+use rmcp::model::ErrorData;
+use rmcp::model::Role;
+use rmcp::model::SamplingMessage;
+use rmcp::model::SamplingMessageContent;
+
+use crate::Prompt;
+use crate::codex::Session;
+use crate::codex::TurnContext;
+use crate::config::types::McpServerSamplingConfig;
+use crate::error::CodexErr;
+use crate::error::Result as CodexResult;
+use crate::truncate::approx_bytes_for_tokens;
+use crate::truncate::approx_token_count;
+
+/// Error code the MCP specification suggests for a request the user declined.
+const SAMPLING_REJECTED_ERROR_CODE: i32 = -1;
+
+const SAMPLING_BASE_INSTRUCTIONS: &str = "You are answering a request from a tool server on behalf of the user. Reply to the conversation below.";
+
+/// A validated sampling request, ready to be shown to the user and run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct SamplingJob {
+    pub(crate) system_prompt: Option<String>,
+    pub(crate) messages: Vec<McpSamplingMessage>,
+    pub(crate) max_tokens: u32,
+}
+
+/// The result of an approved sampling request.
+pub(crate) struct SamplingCompletion {
+    pub(crate) result: CreateMessageResult,
+    pub(crate) output_tokens: u64,
+}
+
+/// Checks that `params` only uses features Codex supports and clamps the
+/// requested output size to the server's limits. `tokens_used` is the number
+/// of sampling tokens the server has already consumed.
+pub(crate) fn sampling_job(
+    params: CreateMessageRequestParams,
+    config: &McpServerSamplingConfig,
+    tokens_used: u64,
+) -> Result<SamplingJob, ErrorData> {
+    if params.tools.is_some() || params.tool_choice.is_some() {
+        return Err(ErrorData::invalid_params(
+            "tool use is not supported in sampling requests",
+            None,
+        ));
+    }
+    if params.messages.is_empty() {
+        return Err(ErrorData::invalid_params(
+            "sampling requests must include at least one message",
+            None,
+        ));
+    }
+
+    let mut messages = Vec::with_capacity(params.messages.len());
+    for message in params.messages {
+        let role = match message.role {
+            Role::User => McpSamplingRole::User,
+            Role::Assistant => McpSamplingRole::Assistant,
+        };
+        let mut parts = Vec::new();
+        for content in message.content.into_vec() {
+            match content {
+                SamplingMessageContent::Text(text) => parts.push(text.text),
+                _ => {
+                    return Err(ErrorData::invalid_params(
+                        "only text content is supported in sampling requests",
+                        None,
+                    ));
+                }
+            }
+        }
+        messages.push(McpSamplingMessage {
+            role,
+            text: parts.join("\n"),
+        });
+    }
+
+    let remaining = match config.max_total_tokens {
+        Some(max_total_tokens) => {
+            let remaining = max_total_tokens.saturating_sub(tokens_used);
+            if remaining == 0 {
+                return Err(ErrorData::invalid_request(
+                    "sampling token budget for this server is exhausted",
+                    None,
+                ));
+            }
+            u32::try_from(remaining).unwrap_or(u32::MAX)
+        }
+        None => u32::MAX,
+    };
+
+    Ok(SamplingJob {
+        system_prompt: params.system_prompt,
+        messages,
+        max_tokens: params.max_tokens.min(config.max_tokens).min(remaining),
+    })
+}
+
+pub(crate) fn sampling_rejected_error() -> ErrorData {
+    ErrorData::new(
+        ErrorCode(SAMPLING_REJECTED_ERROR_CODE),
+        "User rejected sampling request",
+        None,
+    )
+}
+
+fn prompt_for_job(job: &SamplingJob) -> Prompt {
+    let input = job
+        .messages
+        .iter()
+        .map(|message| {
+            let (role, content) = match message.role {
+                McpSamplingRole::User => (
+                    "user",
+                    ContentItem::InputText {
+                        text: message.text.clone(),
+                    },
+                ),
+                McpSamplingRole::Assistant => (
+                    "assistant",
+                    ContentItem::OutputText {
+                        text: message.text.clone(),
+                    },
+                ),
+            };
+            ResponseItem::Message {
+                id: None,
+                role: role.to_string(),
+                content: vec![content],
+                end_turn: None,
+                phase: None,
+            }
+        })
+        .collect();
+    Prompt {
+        input,
+        tools: Vec::new(),
+        parallel_tool_calls: false,
+        base_instructions: BaseInstructions {
+            text: job
+                .system_prompt
+                .clone()
+                .unwrap_or_else(|| SAMPLING_BASE_INSTRUCTIONS.to_string()),
+        },
+        personality: None,
+        output_schema: None,
+        max_output_tokens: Some(u64::from(job.max_tokens)),
+    }
+}
+
+/// Runs an approved sampling request against the session's model provider.
+pub(crate) async fn run_sampling_job(
+    session: &Session,
+    turn: &TurnContext,
+    job: &SamplingJob,
+) -> Result<SamplingCompletion, ErrorData> {
+    let prompt = prompt_for_job(job);
+    let turn_metadata_header = turn.turn_metadata_state.current_header_value();
+    let mut client_session = session.services.model_client.new_session();
+    let stream = client_session
+        .stream(
+            &prompt,
+            &turn.model_info,
+            &turn.session_telemetry,
+            turn.reasoning_effort,
+            turn.reasoning_summary,
+            turn.config.service_tier,
+            turn_metadata_header.as_deref(),
+        )
+        .await
+        .map_err(|err| {
+            ErrorData::internal_error(format!("sampling request failed: {err}"), None)
+        })?;
+    let (message, stop_reason, output_tokens) = collect_reply(stream, job.max_tokens).await?;
+
+    Ok(SamplingCompletion {
+        result: CreateMessageResult {
+            model: turn.model_info.slug.clone(),
+            stop_reason: Some(stop_reason.to_string()),
+            message,
+        },
+        output_tokens,
+    })
+}
+
+/// Reads the model's reply from `stream`, returning it with its stop reason
+/// and output token count.
+///
+/// A reply that the provider stops at the request's output limit ends with a
+/// `maxTokens` stop reason; that limit also covers any reasoning tokens. The
+/// reply is additionally cut off at `max_tokens` in case the provider ignores
+/// the limit.
+async fn collect_reply<S>(
+    mut stream: S,
+    max_tokens: u32,
+) -> Result<(SamplingMessage, &'static str, u64), ErrorData>
+where
+    S: Stream<Item = CodexResult<ResponseEvent>> + Unpin,
+{
+    let max_tokens_usize = usize::try_from(max_tokens).unwrap_or(usize::MAX);
+    let mut reply = String::new();
+    let mut output_tokens = None;
+    let mut stop_reason = CreateMessageResult::STOP_REASON_END_TURN;
+    loop {
+        let Some(event) = stream.next().await else {
+            return Err(ErrorData::internal_error(
+                "sampling stream closed before response.completed",
+                None,
+            ));
+        };
+        match event {
+            Ok(ResponseEvent::OutputTextDelta(delta)) => reply.push_str(&delta),
+            Ok(ResponseEvent::OutputItemDone(item)) => {
+                if reply.is_empty()
+                    && let ResponseItem::Message { content, .. } = item
+                    && let Some(text) = crate::compact::content_items_to_text(&content)
+                {
+                    reply.push_str(&text);
+                }
+            }
+            Ok(ResponseEvent::Completed { token_usage, .. }) => {
+                output_tokens =
+                    token_usage.and_then(|usage| u64::try_from(usage.output_tokens).ok());
+                break;
+            }
+            Ok(_) => {}
+            Err(CodexErr::MaxOutputTokens) => {
+                output_tokens = Some(u64::from(max_tokens));
+                stop_reason = CreateMessageResult::STOP_REASON_END_MAX_TOKEN;
+                break;
+            }
+            Err(err) => {
+                return Err(ErrorData::internal_error(
+                    format!("sampling request failed: {err}"),
+                    None,
+                ));
+            }
+        }
+        if approx_token_count(&reply) > max_tokens_usize {
+            break;
+        }
+    }
+
+    if approx_token_count(&reply) > max_tokens_usize {
+        reply = take_bytes_at_char_boundary(&reply, approx_bytes_for_tokens(max_tokens_usize))
+            .to_string();
+        stop_reason = CreateMessageResult::STOP_REASON_END_MAX_TOKEN;
+    }
+    let output_tokens = output_tokens.unwrap_or_else(|| approx_token_count(&reply) as u64);
+    Ok((
+        SamplingMessage::assistant_text(reply),
+        stop_reason,
+        output_tokens,
+    ))
+}
+
+#[cfg(test)]
+#[path = "sampling_tests.rs"]
+mod tests;
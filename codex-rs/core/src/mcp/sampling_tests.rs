@@ -0,0 +1,150 @@
+use super::*;
+use pretty_assertions::assert_eq;
+use rmcp::model::RawImageContent;
+
+fn request(messages: Vec<SamplingMessage>, max_tokens: u32) -> CreateMessageRequestParams {
+    CreateMessageRequestParams {
+        meta: None,
+        task: None,
+        messages,
+        model_preferences: None,
+        system_prompt: Some("Be brief.".to_string()),
+        include_context: None,
+        temperature: None,
+        max_tokens,
+        stop_sequences: None,
+        metadata: None,
+        tools: None,
+        tool_choice: None,
+    }
+}
+
+fn config(max_tokens: u32, max_total_tokens: Option<u64>) -> McpServerSamplingConfig {
+    McpServerSamplingConfig {
+        enabled: true,
+        max_tokens,
+        max_total_tokens,
+    }
+}
+
+#[test]
+fn sampling_job_converts_text_messages_and_clamps_max_tokens() {
+    let params = request(
+        vec![
+            SamplingMessage::user_text("What is 2 + 2?"),
+            SamplingMessage::assistant_text("4"),
+            SamplingMessage::new_multiple(
+                Role::User,
+                vec![
+                    SamplingMessageContent::text("And 3 + 3?"),
+                    SamplingMessageContent::text("Answer with a digit."),
+                ],
+            ),
+        ],
+        4096,
+    );
+
+    let job = sampling_job(params, &config(256, Some(1000)), 900).expect("valid request");
+
+    assert_eq!(
+        job,
+        SamplingJob {
+            system_prompt: Some("Be brief.".to_string()),
+            messages: vec![
+                McpSamplingMessage {
+                    role: McpSamplingRole::User,
+                    text: "What is 2 + 2?".to_string(),
+                },
+                McpSamplingMessage {
+                    role: McpSamplingRole::Assistant,
+                    text: "4".to_string(),
+                },
+                McpSamplingMessage {
+                    role: McpSamplingRole::User,
+                    text: "And 3 + 3?\nAnswer with a digit.".to_string(),
+                },
+            ],
+            max_tokens: 100,
+        }
+    );
+    let prompt = prompt_for_job(&job);
+    assert_eq!(prompt.base_instructions.text, "Be brief.");
+    assert_eq!(prompt.max_output_tokens, Some(100));
+    let roles: Vec<_> = prompt
+        .input
+        .iter()
+        .filter_map(|item| match item {
+            ResponseItem::Message { role, .. } => Some(role.as_str()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(roles, vec!["user", "assistant", "user"]);
+}
+
+#[test]
+fn sampling_job_rejects_unsupported_requests() {
+    let image = request(
+        vec![SamplingMessage::new(
+            Role::User,
+            SamplingMessageContent::Image(RawImageContent {
+                data: "AAAA".to_string(),
+                mime_type: "image/png".to_string(),
+                meta: None,
+            }),
+        )],
+        10,
+    );
+    assert_eq!(
+        sampling_job(image, &config(100, None), 0)
+            .expect_err("images are not supported")
+            .message,
+        "only text content is supported in sampling requests"
+    );
+
+    let exhausted = request(vec![SamplingMessage::user_text("hi")], 10);
+    assert_eq!(
+        sampling_job(exhausted, &config(100, Some(50)), 50)
+            .expect_err("budget is exhausted")
+            .message,
+        "sampling token budget for this server is exhausted"
+    );
+
+    assert_eq!(
+        sampling_job(request(Vec::new(), 10), &config(100, None), 0)
+            .expect_err("messages are required")
+            .message,
+        "sampling requests must include at least one message"
+    );
+}
+
+#[tokio::test]
+async fn collect_reply_returns_partial_text_when_stopped_at_max_output_tokens() {
+    let events = futures::stream::iter(vec![
+        Ok(ResponseEvent::OutputTextDelta("The answer".to_string())),
+        Ok(ResponseEvent::OutputTextDelta(" is".to_string())),
+        Err(CodexErr::MaxOutputTokens),
+    ]);
+
+    let (message, stop_reason, output_tokens) =
+        collect_reply(events, 100).await.expect("partial reply");
+
+    assert_eq!(message, SamplingMessage::assistant_text("The answer is"));
+    assert_eq!(stop_reason, CreateMessageResult::STOP_REASON_END_MAX_TOKEN);
+    assert_eq!(output_tokens, 100);
+}
+
+#[tokio::test]
+async fn collect_reply_reports_other_stream_errors() {
+    let events = futures::stream::iter(vec![
+        Ok(ResponseEvent::OutputTextDelta("partial".to_string())),
+        Err(CodexErr::Stream("connection reset".to_string(), None)),
+    ]);
+
+    let err = collect_reply(events, 100).await.expect_err("stream error");
+
+    assert_eq!(err.code, ErrorCode::INTERNAL_ERROR);
+    assert_eq!(
+        err.message,
+        "sampling request failed: stream disconnected before completion: connection reset"
+    );
+}
@@ -0,0 +1,221 @@
+//! Sandboxing for stdio MCP servers (`[mcp_servers.<name>.sandbox]`).
+//!
+//! The server command is wrapped by the same platform sandbox that shell
+//! commands use (`codex-linux-sandbox` on Linux, Seatbelt on macOS). The
+//! server may read its working directory, the directory of its executable,
+//! the platform's system directories and any configured `readable_roots`.
+//! When the server may reach any domains, its traffic goes through a
+//! dedicated network proxy that denies every other destination.
+
+use std::collections::HashMap;
+use std::ffi::OsString;
+use std::path::Path;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use anyhow::Result;
+use anyhow::anyhow;
+use codex_network_proxy::NetworkProxyConfig;
+use codex_protocol::config_types::WindowsSandboxLevel;
+use codex_protocol::permissions::FileSystemSandboxPolicy;
+use codex_protocol::permissions::NetworkSandboxPolicy;
+use codex_protocol::permissions::ResourceLimits;
+use codex_protocol::protocol::ReadOnlyAccess;
+use codex_protocol::protocol::SandboxPolicy;
+use codex_utils_absolute_path::AbsolutePathBuf;
+
+use crate::config::NetworkProxyAuditMetadata;
+use crate::config::NetworkProxySpec;
+use crate::config::StartedNetworkProxy;
+use crate::config::types::McpServerSandboxConfig;
+use crate::config::types::McpServerSandboxPermissions;
+use crate::exec::ExecExpiration;
+use crate::mcp_connection_manager::SandboxState;
+use crate::sandboxing::CommandSpec;
+use crate::sandboxing::SandboxManager;
+use crate::sandboxing::SandboxPermissions;
+use crate::sandboxing::SandboxTransformRequest;
+
+/// A stdio server command rewritten to run inside the sandbox.
+pub(crate) struct SandboxedStdioCommand {
+    pub(crate) program: OsString,
+    pub(crate) args: Vec<OsString>,
+    pub(crate) arg0: Option<OsString>,
+    /// Variables the sandbox adds to the server's environment.
+    pub(crate) env: HashMap<String, String>,
+    /// Must outlive the server, whose traffic can only leave through it.
+    pub(crate) network_proxy: Option<StartedNetworkProxy>,
+}
+
+pub(crate) async fn sandbox_stdio_command(
+    command: String,
+    args: Vec<String>,
+    cwd: &Path,
+    config: &McpServerSandboxConfig,
+    sandbox_state: &SandboxState,
+) -> Result<SandboxedStdioCommand> {
+    let sandbox = crate::safety::get_platform_sandbox(false)
+        .ok_or_else(|| anyhow!("sandboxed MCP servers are not supported on this platform"))?;
+    let policy = sandbox_policy(config, program_dirs(&command, cwd));
+    let network_proxy = if config.allowed_domains.is_empty() {
+        None
+    } else {
+        Some(start_network_proxy(&config.allowed_domains, &policy).await?)
+    };
+    let network = network_proxy.as_ref().map(StartedNetworkProxy::proxy);
+
+    let file_system_policy = FileSystemSandboxPolicy::from_legacy_sandbox_policy(&policy, cwd);
+    let exec_request = SandboxManager::new().transform(SandboxTransformRequest {
+        spec: CommandSpec {
+            program: command,
+            args,
+            cwd: cwd.to_path_buf(),
+            env: HashMap::new(),
+            expiration: ExecExpiration::DefaultTimeout,
+            sandbox_permissions: SandboxPermissions::UseDefault,
+            additional_permissions: None,
+            justification: None,
+        },
+        policy: &policy,
+        file_system_policy: &file_system_policy,
+        network_policy: NetworkSandboxPolicy::from(&policy),
+        sandbox,
+        // Only the proxy may be reached, so the allowlist cannot be bypassed.
+        enforce_managed_network: network.is_some(),
+        network: network.as_ref(),
+        sandbox_policy_cwd: cwd,
+        #[cfg(target_os = "macos")]
+        macos_seatbelt_profile_extensions: None,
+        codex_linux_sandbox_exe: sandbox_state.codex_linux_sandbox_exe.as_ref(),
+        use_legacy_landlock: sandbox_state.use_legacy_landlock,
+        resource_limits: ResourceLimits::default(),
+        overlay_workspace: None,
+        glob_deny_matches: None,
+        windows_sandbox_level: WindowsSandboxLevel::Disabled,
+    })?;
+
+    let mut env = exec_request.env;
+    if let Some(network) = network.as_ref() {
+        network.apply_to_env(&mut env);
+    }
+    let mut command = exec_request.command.into_iter().map(OsString::from);
+    let program = command
+        .next()
+        .ok_or_else(|| anyhow!("sandbox produced an empty command"))?;
+    Ok(SandboxedStdioCommand {
+        program,
+        args: command.collect(),
+        arg0: exec_request.arg0.map(OsString::from),
+        env,
+        network_proxy,
+    })
+}
+
+/// Short description of what the sandbox allows, for startup errors.
+pub(crate) fn describe_sandbox(config: &McpServerSandboxConfig) -> String {
+    let permissions = match config.permissions {
+        McpServerSandboxPermissions::ReadOnly => "read-only",
+        McpServerSandboxPermissions::WorkspaceWrite => "workspace-write",
+    };
+    if config.allowed_domains.is_empty() {
+        format!("{permissions}, no network")
+    } else {
+        format!(
+            "{permissions}, network limited to {}",
+            config.allowed_domains.join(", ")
+        )
+    }
+}
+
+/// Builds the server's policy. `program_dirs` are the directories holding
+/// its executable, which it may need to read to load code shipped alongside.
+fn sandbox_policy(
+    config: &McpServerSandboxConfig,
+    program_dirs: Vec<AbsolutePathBuf>,
+) -> SandboxPolicy {
+    // Allowed domains are enforced by the proxy; the sandbox itself only
+    // needs to let traffic out.
+    let network_access = !config.allowed_domains.is_empty();
+    let read_access = if config
+        .readable_roots
+        .iter()
+        .any(|root| root.as_path() == Path::new("/"))
+    {
+        ReadOnlyAccess::FullAccess
+    } else {
+        let mut readable_roots = program_dirs;
+        for root in &config.readable_roots {
+            if !readable_roots.contains(root) {
+                readable_roots.push(root.clone());
+            }
+        }
+        // The working directory is readable under either permission.
+        ReadOnlyAccess::Restricted {
+            include_platform_defaults: true,
+            readable_roots,
+        }
+    };
+    match config.permissions {
+        McpServerSandboxPermissions::ReadOnly => SandboxPolicy::ReadOnly {
+            access: read_access,
+            network_access,
+        },
+        McpServerSandboxPermissions::WorkspaceWrite => SandboxPolicy::WorkspaceWrite {
+            writable_roots: config.writable_roots.clone(),
+            read_only_access: read_access,
+            network_access,
+            exclude_tmpdir_env_var: false,
+            exclude_slash_tmp: false,
+        },
+    }
+}
+
+/// Directories holding the server's executable: the one it is launched from
+/// and, when that is a symlink, the one it resolves to.
+fn program_dirs(program: &str, cwd: &Path) -> Vec<AbsolutePathBuf> {
+    let path = Path::new(program);
+    let resolved: Option<PathBuf> = if path.components().count() > 1 {
+        Some(cwd.join(path))
+    } else {
+        which::which(program).ok()
+    };
+    let Some(resolved) = resolved else {
+        return Vec::new();
+    };
+    let canonical = dunce::canonicalize(&resolved).ok();
+    let mut dirs = Vec::new();
+    for executable in std::iter::once(resolved).chain(canonical) {
+        if let Some(dir) = executable
+            .parent()
+            .and_then(|dir| AbsolutePathBuf::from_absolute_path(dir).ok())
+            && !dirs.contains(&dir)
+        {
+            dirs.push(dir);
+        }
+    }
+    dirs
+}
+
+async fn start_network_proxy(
+    allowed_domains: &[String],
+    policy: &SandboxPolicy,
+) -> Result<StartedNetworkProxy> {
+    let mut proxy_config = NetworkProxyConfig::default();
+    proxy_config.network.enabled = true;
+    proxy_config.network.allowed_domains = allowed_domains.to_vec();
+    let spec = NetworkProxySpec::from_config_and_constraints(proxy_config, None, policy)?;
+    // Without an approval flow, requests outside the allowlist are denied.
+    spec.start_proxy(
+        policy,
+        None,
+        None,
+        false,
+        NetworkProxyAuditMetadata::default(),
+    )
+    .await
+    .context("failed to start the network proxy for the sandbox")
+}
+
+#[cfg(test)]
+#[path = "sandbox_tests.rs"]
+mod tests;
@@ -0,0 +1,158 @@
+use super::*;
+use codex_utils_absolute_path::AbsolutePathBuf;
+use pretty_assertions::assert_eq;
+
+fn workspace_write_config() -> McpServerSandboxConfig {
+    McpServerSandboxConfig {
+        permissions: McpServerSandboxPermissions::WorkspaceWrite,
+        writable_roots: vec![
+            AbsolutePathBuf::from_absolute_path("/var/cache/server").expect("absolute path"),
+        ],
+        readable_roots: vec![
+            AbsolutePathBuf::from_absolute_path("/opt/server").expect("absolute path"),
+            AbsolutePathBuf::from_absolute_path("/usr/local/bin").expect("absolute path"),
+        ],
+        allowed_domains: vec!["api.example.com".to_string(), "example.org".to_string()],
+    }
+}
+
+fn absolute(path: &str) -> AbsolutePathBuf {
+    AbsolutePathBuf::from_absolute_path(path).expect("absolute path")
+}
+
+#[test]
+fn sandbox_policy_follows_permissions_and_allowed_domains() {
+    assert_eq!(
+        sandbox_policy(&McpServerSandboxConfig::default(), Vec::new()),
+        SandboxPolicy::ReadOnly {
+            access: ReadOnlyAccess::Restricted {
+                include_platform_defaults: true,
+                readable_roots: Vec::new(),
+            },
+            network_access: false,
+        }
+    );
+    assert_eq!(
+        sandbox_policy(&workspace_write_config(), vec![absolute("/usr/local/bin")]),
+        SandboxPolicy::WorkspaceWrite {
+            writable_roots: vec![absolute("/var/cache/server")],
+            read_only_access: ReadOnlyAccess::Restricted {
+                include_platform_defaults: true,
+                readable_roots: vec![absolute("/usr/local/bin"), absolute("/opt/server")],
+            },
+            network_access: true,
+            exclude_tmpdir_env_var: false,
+            exclude_slash_tmp: false,
+        }
+    );
+
+    let full_read = McpServerSandboxConfig {
+        readable_roots: vec![absolute("/")],
+        ..McpServerSandboxConfig::default()
+    };
+    assert_eq!(
+        sandbox_policy(&full_read, vec![absolute("/usr/local/bin")]),
+        SandboxPolicy::ReadOnly {
+            access: ReadOnlyAccess::FullAccess,
+            network_access: false,
+        }
+    );
+}
+
+#[test]
+fn program_dirs_resolve_the_server_executable() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let cwd = dunce::canonicalize(dir.path())?;
+    std::fs::create_dir_all(cwd.join("bin"))?;
+    std::fs::write(cwd.join("bin/server"), "")?;
+
+    assert_eq!(
+        program_dirs("bin/server", &cwd),
+        vec![AbsolutePathBuf::from_absolute_path(cwd.join("bin"))?]
+    );
+    assert_eq!(
+        program_dirs(&cwd.join("bin/server").to_string_lossy(), Path::new("/")),
+        vec![AbsolutePathBuf::from_absolute_path(cwd.join("bin"))?]
+    );
+    assert_eq!(
+        program_dirs("codex-test-no-such-server", &cwd),
+        Vec::<AbsolutePathBuf>::new()
+    );
+    Ok(())
+}
+
+#[test]
+fn describe_sandbox_summarizes_access() {
+    assert_eq!(
+        describe_sandbox(&McpServerSandboxConfig::default()),
+        "read-only, no network"
+    );
+    assert_eq!(
+        describe_sandbox(&workspace_write_config()),
+        "workspace-write, network limited to api.example.com, example.org"
+    );
+}
+
+#[cfg(target_os = "linux")]
+fn sandbox_state(codex_linux_sandbox_exe: Option<std::path::PathBuf>) -> SandboxState {
+    SandboxState {
+        sandbox_policy: SandboxPolicy::new_read_only_policy(),
+        codex_linux_sandbox_exe,
+        sandbox_cwd: std::env::temp_dir(),
+        use_legacy_landlock: false,
+    }
+}
+
+#[cfg(target_os = "linux")]
+#[tokio::test]
+async fn linux_server_runs_under_the_sandbox_helper() -> Result<()> {
+    let cwd = std::env::temp_dir();
+    let command = sandbox_stdio_command(
+        "server".to_string(),
+        vec!["--stdio".to_string()],
+        &cwd,
+        &McpServerSandboxConfig::default(),
+        &sandbox_state(Some("/opt/codex/codex".into())),
+    )
+    .await?;
+
+    assert_eq!(command.program, OsString::from("/opt/codex/codex"));
+    assert_eq!(command.arg0, Some(OsString::from("codex-linux-sandbox")));
+    assert_eq!(
+        command.args[command.args.len() - 3..].to_vec(),
+        vec![
+            OsString::from("--"),
+            OsString::from("server"),
+            OsString::from("--stdio"),
+        ]
+    );
+    assert_eq!(
+        command
+            .env
+            .get(crate::spawn::CODEX_SANDBOX_NETWORK_DISABLED_ENV_VAR)
+            .map(String::as_str),
+        Some("1")
+    );
+    assert!(command.network_proxy.is_none());
+    Ok(())
+}
+
+#[cfg(target_os = "linux")]
+#[tokio::test]
+async fn linux_server_fails_without_the_sandbox_helper() {
+    let err = sandbox_stdio_command(
+        "server".to_string(),
+        Vec::new(),
+        &std::env::temp_dir(),
+        &McpServerSandboxConfig::default(),
+        &sandbox_state(None),
+    )
+    .await
+    .err()
+    .expect("missing helper should be an error");
+
+    assert_eq!(
+        err.to_string(),
+        "missing codex-linux-sandbox executable path"
+    );
+}
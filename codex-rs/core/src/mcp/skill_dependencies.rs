@@ -428,6 +428,8 @@ fn mcp_dependency_to_server_config(
             disabled_tools: None,
             scopes: None,
             oauth_resource: None,
+            sampling: None,
+            sandbox: None,
         });
     }
 
@@ -453,6 +455,8 @@ fn mcp_dependency_to_server_config(
             disabled_tools: None,
             scopes: None,
             oauth_resource: None,
+            sampling: None,
+            sandbox: None,
         });
     }
 
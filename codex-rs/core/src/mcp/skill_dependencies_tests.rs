@@ -14,6 +14,7 @@ fn skill_with_tools(tools: Vec<SkillToolDependency>) -> SkillMetadata {
         policy: None,
         permission_profile: None,
         managed_network_override: None,
+        resource_limits: None,
         path_to_skills_md: PathBuf::from("skill"),
         scope: SkillScope::User,
     }
@@ -48,6 +49,8 @@ fn collect_missing_respects_canonical_installed_key() {
             disabled_tools: None,
             scopes: None,
             oauth_resource: None,
+            sampling: None,
+            sandbox: None,
         },
     )]);
 
@@ -97,6 +100,8 @@ fn collect_missing_dedupes_by_canonical_key_but_preserves_original_name() {
             disabled_tools: None,
             scopes: None,
             oauth_resource: None,
+            sampling: None,
+            sandbox: None,
         },
     )]);
 
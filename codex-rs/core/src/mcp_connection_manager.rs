@@ -15,6 +15,7 @@ use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::Mutex as StdMutex;
 use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicU64;
 use std::sync::atomic::Ordering;
 use std::time::Duration;
 use std::time::Instant;
@@ -22,6 +23,15 @@ use std::time::Instant;
 use crate::mcp::CODEX_APPS_MCP_SERVER_NAME;
 use crate::mcp::ToolPluginProvenance;
 use crate::mcp::auth::McpAuthStatusEntry;
+use crate::mcp::resource_subscriptions::ResourceSubscriptions;
+use crate::mcp::resource_subscriptions::UpdatedResources;
+use crate::mcp::roots::roots_for_sandbox_state;
+use crate::mcp::sampling::SamplingCompletion;
+use crate::mcp::sampling::SamplingJob;
+use crate::mcp::sampling::sampling_job;
+use crate::mcp::sampling::sampling_rejected_error;
+use crate::mcp::sandbox::describe_sandbox;
+use crate::mcp::sandbox::sandbox_stdio_command;
 use anyhow::Context;
 use anyhow::Result;
 use anyhow::anyhow;
@@ -31,6 +41,7 @@ use codex_async_utils::OrCancelExt;
 use codex_config::Constrained;
 use codex_protocol::approvals::ElicitationRequest;
 use codex_protocol::approvals::ElicitationRequestEvent;
+use codex_protocol::approvals::McpSamplingRequestEvent;
 use codex_protocol::mcp::CallToolResult;
 use codex_protocol::mcp::RequestId as ProtocolRequestId;
 use codex_protocol::protocol::AskForApproval;
@@ -45,26 +56,36 @@ use codex_rmcp_client::ElicitationResponse;
 use codex_rmcp_client::OAuthCredentialsStoreMode;
 use codex_rmcp_client::RmcpClient;
 use codex_rmcp_client::SendElicitation;
+use codex_rmcp_client::SendSampling;
 use futures::future::BoxFuture;
 use futures::future::FutureExt;
 use futures::future::Shared;
 use rmcp::model::ClientCapabilities;
 use rmcp::model::CreateElicitationRequestParams;
+use rmcp::model::CreateMessageResult;
 use rmcp::model::ElicitationAction;
 use rmcp::model::ElicitationCapability;
 use rmcp::model::FormElicitationCapability;
+use rmcp::model::GetPromptRequestParams;
+use rmcp::model::GetPromptResult;
 use rmcp::model::Implementation;
 use rmcp::model::InitializeRequestParams;
 use rmcp::model::ListResourceTemplatesResult;
 use rmcp::model::ListResourcesResult;
 use rmcp::model::PaginatedRequestParams;
+use rmcp::model::Prompt;
 use rmcp::model::ProtocolVersion;
 use rmcp::model::ReadResourceRequestParams;
 use rmcp::model::ReadResourceResult;
 use rmcp::model::RequestId;
 use rmcp::model::Resource;
 use rmcp::model::ResourceTemplate;
+use rmcp::model::Root;
+use rmcp::model::RootsCapabilities;
+use rmcp::model::SamplingCapability;
+use rmcp::model::SubscribeRequestParams;
 use rmcp::model::Tool;
+use rmcp::model::UnsubscribeRequestParams;
 
 use serde::Deserialize;
 use serde::Serialize;
@@ -79,7 +100,10 @@ use tracing::warn;
 use url::Url;
 
 use crate::codex::INITIAL_SUBMIT_ID;
+use crate::config::StartedNetworkProxy;
 use crate::config::types::McpServerConfig;
+use crate::config::types::McpServerSamplingConfig;
+use crate::config::types::McpServerSandboxConfig;
 use crate::config::types::McpServerTransportConfig;
 use crate::connectors::is_connector_id_allowed;
 use crate::connectors::sanitize_name;
@@ -367,6 +391,121 @@ impl ElicitationRequestManager {
     }
 }
 
+type SamplingResult = std::result::Result<CreateMessageResult, rmcp::ErrorData>;
+
+/// A sampling request that is waiting for the user's decision.
+pub(crate) struct PendingSamplingRequest {
+    pub(crate) job: SamplingJob,
+    tokens_used: Arc<AtomicU64>,
+    responder: oneshot::Sender<SamplingResult>,
+}
+
+impl PendingSamplingRequest {
+    pub(crate) fn decline(self) {
+        let _ = self.responder.send(Err(sampling_rejected_error()));
+    }
+
+    pub(crate) fn complete(
+        self,
+        completion: std::result::Result<SamplingCompletion, rmcp::ErrorData>,
+    ) {
+        let result = completion.map(|completion| {
+            self.tokens_used
+                .fetch_add(completion.output_tokens, Ordering::Relaxed);
+            completion.result
+        });
+        let _ = self.responder.send(result);
+    }
+}
+
+#[derive(Clone)]
+struct SamplingRequestManager {
+    requests: Arc<Mutex<HashMap<(String, RequestId), PendingSamplingRequest>>>,
+    approval_policy: Arc<StdMutex<AskForApproval>>,
+}
+
+impl SamplingRequestManager {
+    fn new(approval_policy: Arc<StdMutex<AskForApproval>>) -> Self {
+        Self {
+            requests: Arc::new(Mutex::new(HashMap::new())),
+            approval_policy,
+        }
+    }
+
+    async fn take(&self, server_name: String, id: RequestId) -> Option<PendingSamplingRequest> {
+        self.requests.lock().await.remove(&(server_name, id))
+    }
+
+    fn make_sender(
+        &self,
+        server_name: String,
+        config: McpServerSamplingConfig,
+        tx_event: Sender<Event>,
+    ) -> SendSampling {
+        let sampling_requests = self.requests.clone();
+        let approval_policy = self.approval_policy.clone();
+        // Shared by every request from this connection to enforce `max_total_tokens`.
+        let tokens_used = Arc::new(AtomicU64::new(0));
+        Box::new(move |id, params| {
+            let sampling_requests = sampling_requests.clone();
+            let approval_policy = approval_policy.clone();
+            let tokens_used = tokens_used.clone();
+            let tx_event = tx_event.clone();
+            let server_name = server_name.clone();
+            async move {
+                if approval_policy
+                    .lock()
+                    .is_ok_and(|policy| elicitation_is_rejected_by_policy(*policy))
+                {
+                    return Err(sampling_rejected_error());
+                }
+
+                let job = sampling_job(params, &config, tokens_used.load(Ordering::Relaxed))?;
+                let event = McpSamplingRequestEvent {
+                    turn_id: None,
+                    server_name: server_name.clone(),
+                    id: match id.clone() {
+                        rmcp::model::NumberOrString::String(value) => {
+                            ProtocolRequestId::String(value.to_string())
+                        }
+                        rmcp::model::NumberOrString::Number(value) => {
+                            ProtocolRequestId::Integer(value)
+                        }
+                    },
+                    system_prompt: job.system_prompt.clone(),
+                    messages: job.messages.clone(),
+                    max_tokens: job.max_tokens,
+                };
+                let (tx, rx) = oneshot::channel();
+                {
+                    let mut lock = sampling_requests.lock().await;
+                    lock.insert(
+                        (server_name, id),
+                        PendingSamplingRequest {
+                            job,
+                            tokens_used,
+                            responder: tx,
+                        },
+                    );
+                }
+                let _ = tx_event
+                    .send(Event {
+                        id: "mcp_sampling_request".to_string(),
+                        msg: EventMsg::McpSamplingRequest(event),
+                    })
+                    .await;
+                rx.await.unwrap_or_else(|_| {
+                    Err(rmcp::ErrorData::internal_error(
+                        "sampling request channel closed unexpectedly",
+                        None,
+                    ))
+                })
+            }
+            .boxed()
+        })
+    }
+}
+
 #[derive(Clone)]
 struct ManagedClient {
     client: Arc<RmcpClient>,
@@ -374,7 +513,11 @@ struct ManagedClient {
     tool_filter: ToolFilter,
     tool_timeout: Option<Duration>,
     server_supports_sandbox_state_capability: bool,
+    server_supports_prompts: bool,
+    server_supports_resource_subscriptions: bool,
     codex_apps_tools_cache_context: Option<CodexAppsToolsCacheContext>,
+    /// Keeps a sandboxed server's network proxy running while the server is.
+    _network_proxy: Option<Arc<StartedNetworkProxy>>,
 }
 
 impl ManagedClient {
@@ -405,6 +548,9 @@ impl ManagedClient {
 
     /// Returns once the server has ack'd the sandbox state update.
     async fn notify_sandbox_state_change(&self, sandbox_state: &SandboxState) -> Result<()> {
+        self.client
+            .set_roots(roots_for_sandbox_state(sandbox_state))
+            .await?;
         if !self.server_supports_sandbox_state_capability {
             return Ok(());
         }
@@ -439,6 +585,9 @@ impl AsyncManagedClient {
         cancel_token: CancellationToken,
         tx_event: Sender<Event>,
         elicitation_requests: ElicitationRequestManager,
+        sampling_requests: SamplingRequestManager,
+        resource_subscriptions: ResourceSubscriptions,
+        sandbox_state: SandboxState,
         codex_apps_tools_cache_context: Option<CodexAppsToolsCacheContext>,
         tool_plugin_provenance: Arc<ToolPluginProvenance>,
     ) -> Self {
@@ -449,6 +598,8 @@ impl AsyncManagedClient {
         )
         .map(|tools| filter_tools(tools, &tool_filter));
         let startup_tool_filter = tool_filter;
+        let sampling = config.sampling.filter(|sampling| sampling.enabled);
+        let sandbox = config.sandbox;
         let startup_complete = Arc::new(AtomicBool::new(false));
         let startup_complete_for_fut = Arc::clone(&startup_complete);
         let fut = async move {
@@ -457,11 +608,17 @@ impl AsyncManagedClient {
                     return Err(error.into());
                 }
 
-                let client =
-                    Arc::new(make_rmcp_client(&server_name, config.transport, store_mode).await?);
-                match start_server_task(
-                    server_name,
-                    client,
+                let (client, network_proxy) = make_rmcp_client(
+                    &server_name,
+                    config.transport,
+                    sandbox.as_ref(),
+                    &sandbox_state,
+                    store_mode,
+                )
+                .await?;
+                let started = start_server_task(
+                    server_name.clone(),
+                    Arc::new(client),
                     StartServerTaskParams {
                         startup_timeout: config
                             .startup_timeout_sec
@@ -470,13 +627,22 @@ impl AsyncManagedClient {
                         tool_filter: startup_tool_filter,
                         tx_event,
                         elicitation_requests,
+                        sampling_requests,
+                        sampling,
+                        resource_subscriptions,
+                        roots: roots_for_sandbox_state(&sandbox_state),
+                        network_proxy,
                         codex_apps_tools_cache_context,
                     },
                 )
                 .or_cancel(&cancel_token)
-                .await
-                {
-                    Ok(result) => result,
+                .await;
+                match started {
+                    Ok(Ok(managed)) => Ok(managed),
+                    Ok(Err(error)) => match sandbox.as_ref() {
+                        Some(sandbox) => Err(sandboxed_startup_error(&server_name, sandbox, error)),
+                        None => Err(error),
+                    },
                     Err(CancelErr::Cancelled) => Err(StartupOutcomeError::Cancelled),
                 }
             }
@@ -599,14 +765,21 @@ pub(crate) struct McpConnectionManager {
     clients: HashMap<String, AsyncManagedClient>,
     server_origins: HashMap<String, String>,
     elicitation_requests: ElicitationRequestManager,
+    sampling_requests: SamplingRequestManager,
+    resource_subscriptions: ResourceSubscriptions,
 }
 
 impl McpConnectionManager {
     pub(crate) fn new_uninitialized(approval_policy: &Constrained<AskForApproval>) -> Self {
+        let elicitation_requests = ElicitationRequestManager::new(approval_policy.value());
+        let sampling_requests =
+            SamplingRequestManager::new(elicitation_requests.approval_policy.clone());
         Self {
             clients: HashMap::new(),
             server_origins: HashMap::new(),
-            elicitation_requests: ElicitationRequestManager::new(approval_policy.value()),
+            elicitation_requests,
+            sampling_requests,
+            resource_subscriptions: ResourceSubscriptions::default(),
         }
     }
 
@@ -648,6 +821,9 @@ impl McpConnectionManager {
         let mut server_origins = HashMap::new();
         let mut join_set = JoinSet::new();
         let elicitation_requests = ElicitationRequestManager::new(approval_policy.value());
+        let sampling_requests =
+            SamplingRequestManager::new(elicitation_requests.approval_policy.clone());
+        let resource_subscriptions = ResourceSubscriptions::default();
         let tool_plugin_provenance = Arc::new(tool_plugin_provenance);
         let mcp_servers = mcp_servers.clone();
         for (server_name, cfg) in mcp_servers.into_iter().filter(|(_, cfg)| cfg.enabled) {
@@ -678,6 +854,9 @@ impl McpConnectionManager {
                 cancel_token.clone(),
                 tx_event.clone(),
                 elicitation_requests.clone(),
+                sampling_requests.clone(),
+                resource_subscriptions.clone(),
+                initial_sandbox_state.clone(),
                 codex_apps_tools_cache_context,
                 Arc::clone(&tool_plugin_provenance),
             );
@@ -729,6 +908,8 @@ impl McpConnectionManager {
             clients,
             server_origins,
             elicitation_requests: elicitation_requests.clone(),
+            sampling_requests,
+            resource_subscriptions,
         };
         tokio::spawn(async move {
             let outcomes = join_set.join_all().await;
@@ -775,6 +956,15 @@ impl McpConnectionManager {
             .await
     }
 
+    /// Removes a sampling request that is waiting for the user's decision.
+    pub(crate) async fn take_sampling_request(
+        &self,
+        server_name: String,
+        id: RequestId,
+    ) -> Option<PendingSamplingRequest> {
+        self.sampling_requests.take(server_name, id).await
+    }
+
     pub(crate) async fn wait_for_server_ready(&self, server_name: &str, timeout: Duration) -> bool {
         let Some(async_managed_client) = self.clients.get(server_name) else {
             return false;
@@ -1008,6 +1198,93 @@ impl McpConnectionManager {
         aggregated
     }
 
+    /// Returns the prompts published by every server that advertises the
+    /// `prompts` capability, keyed by server name.
+    pub async fn list_all_prompts(&self) -> HashMap<String, Vec<Prompt>> {
+        let mut join_set = JoinSet::new();
+
+        for (server_name, async_managed_client) in &self.clients {
+            let server_name = server_name.clone();
+            let Ok(managed_client) = async_managed_client.client().await else {
+                continue;
+            };
+            if !managed_client.server_supports_prompts {
+                continue;
+            }
+            let timeout = managed_client.tool_timeout;
+            let client = managed_client.client.clone();
+
+            join_set.spawn(async move {
+                let mut collected: Vec<Prompt> = Vec::new();
+                let mut cursor: Option<String> = None;
+
+                loop {
+                    let params = cursor.as_ref().map(|next| PaginatedRequestParams {
+                        meta: None,
+                        cursor: Some(next.clone()),
+                    });
+                    let response = match client.list_prompts(params, timeout).await {
+                        Ok(result) => result,
+                        Err(err) => return (server_name, Err(err)),
+                    };
+
+                    collected.extend(response.prompts);
+
+                    match response.next_cursor {
+                        Some(next) => {
+                            if cursor.as_ref() == Some(&next) {
+                                return (
+                                    server_name,
+                                    Err(anyhow!("prompts/list returned duplicate cursor")),
+                                );
+                            }
+                            cursor = Some(next);
+                        }
+                        None => return (server_name, Ok(collected)),
+                    }
+                }
+            });
+        }
+
+        let mut aggregated: HashMap<String, Vec<Prompt>> = HashMap::new();
+
+        while let Some(join_res) = join_set.join_next().await {
+            match join_res {
+                Ok((server_name, Ok(prompts))) => {
+                    aggregated.insert(server_name, prompts);
+                }
+                Ok((server_name, Err(err))) => {
+                    warn!("Failed to list prompts for MCP server '{server_name}': {err:#}");
+                }
+                Err(err) => {
+                    warn!("Task panic when listing prompts for MCP server: {err:#}");
+                }
+            }
+        }
+
+        aggregated
+    }
+
+    /// Fetch a prompt from the specified server.
+    pub async fn get_prompt(
+        &self,
+        server: &str,
+        params: GetPromptRequestParams,
+    ) -> Result<GetPromptResult> {
+        let managed = self.client_by_name(server).await?;
+        if !managed.server_supports_prompts {
+            return Err(anyhow!("MCP server '{server}' does not support prompts"));
+        }
+        let client = managed.client.clone();
+        let timeout = managed.tool_timeout;
+        let name = params.name.clone();
+
+        client
+            .get_prompt(params, timeout)
+            .await
+            .with_context(|| format!("prompts/get failed for `{server}` ({name})"))
+    }
+
     /// Invoke the tool indicated by the (server, tool) pair.
     pub async fn call_tool(
         &self,
@@ -1094,6 +1371,55 @@ impl McpConnectionManager {
             .with_context(|| format!("resources/read failed for `{server}` ({uri})"))
     }
 
+    /// Subscribes to updates of a resource on the specified server.
+    pub async fn subscribe_resource(&self, server: &str, uri: &str) -> Result<()> {
+        let managed = self.client_by_name(server).await?;
+        if !managed.server_supports_resource_subscriptions {
+            return Err(anyhow!(
+                "MCP server '{server}' does not support resource subscriptions"
+            ));
+        }
+        managed
+            .client
+            .subscribe_resource(
+                SubscribeRequestParams {
+                    meta: None,
+                    uri: uri.to_string(),
+                },
+                managed.tool_timeout,
+            )
+            .await
+            .with_context(|| format!("resources/subscribe failed for `{server}` ({uri})"))?;
+        self.resource_subscriptions.insert(server, uri);
+        Ok(())
+    }
+
+    /// Stops following a resource. Fails if it was not subscribed.
+    pub async fn unsubscribe_resource(&self, server: &str, uri: &str) -> Result<()> {
+        if !self.resource_subscriptions.remove(server, uri) {
+            return Err(anyhow!(
+                "not subscribed to `{uri}` on MCP server '{server}'"
+            ));
+        }
+        let managed = self.client_by_name(server).await?;
+        managed
+            .client
+            .unsubscribe_resource(
+                UnsubscribeRequestParams {
+                    meta: None,
+                    uri: uri.to_string(),
+                },
+                managed.tool_timeout,
+            )
+            .await
+            .with_context(|| format!("resources/unsubscribe failed for `{server}` ({uri})"))
+    }
+
+    /// Returns the subscribed resources that changed since the last call.
+    pub(crate) fn take_updated_resources(&self) -> UpdatedResources {
+        self.resource_subscriptions.take_updated()
+    }
+
     pub async fn parse_tool_name(&self, tool_name: &str) -> Option<(String, String)> {
         self.list_all_tools()
             .await
@@ -1342,6 +1668,11 @@ async fn start_server_task(
         tool_filter,
         tx_event,
         elicitation_requests,
+        sampling_requests,
+        sampling,
+        resource_subscriptions,
+        roots,
+        network_proxy,
         codex_apps_tools_cache_context,
     } = params;
     let elicitation = elicitation_capability_for_server(&server_name);
@@ -1350,8 +1681,10 @@ async fn start_server_task(
         capabilities: ClientCapabilities {
             experimental: None,
             extensions: None,
-            roots: None,
-            sampling: None,
+            roots: Some(RootsCapabilities {
+                list_changed: Some(true),
+            }),
+            sampling: sampling.map(|_| SamplingCapability::default()),
             elicitation,
             tasks: None,
         },
@@ -1366,12 +1699,21 @@ async fn start_server_task(
         protocol_version: ProtocolVersion::V_2025_06_18,
     };
 
+    let send_sampling = sampling.map(|sampling| {
+        sampling_requests.make_sender(server_name.clone(), sampling, tx_event.clone())
+    });
     let send_elicitation = elicitation_requests.make_sender(server_name.clone(), tx_event);
 
+    // Set before the handshake so the server can ask for roots while initializing.
+    client
+        .set_roots(roots)
+        .await
+        .map_err(StartupOutcomeError::from)?;
     let initialize_result = client
-        .initialize(params, startup_timeout, send_elicitation)
+        .initialize(params, startup_timeout, send_elicitation, send_sampling)
         .await
         .map_err(StartupOutcomeError::from)?;
+    resource_subscriptions.watch(server_name.clone(), client.resource_updates());
 
     let list_start = Instant::now();
     let fetch_start = Instant::now();
@@ -1403,13 +1745,23 @@ async fn start_server_task(
         .as_ref()
         .and_then(|exp| exp.get(MCP_SANDBOX_STATE_CAPABILITY))
         .is_some();
+    let server_supports_prompts = initialize_result.capabilities.prompts.is_some();
+    let server_supports_resource_subscriptions = initialize_result
+        .capabilities
+        .resources
+        .as_ref()
+        .and_then(|resources| resources.subscribe)
+        .unwrap_or(false);
     let managed = ManagedClient {
         client: Arc::clone(&client),
         tools,
         tool_timeout: Some(tool_timeout),
         tool_filter,
         server_supports_sandbox_state_capability,
+        server_supports_prompts,
+        server_supports_resource_subscriptions,
         codex_apps_tools_cache_context,
+        _network_proxy: network_proxy.map(Arc::new),
     };
 
     Ok(managed)
@@ -1421,14 +1773,23 @@ struct StartServerTaskParams {
     tool_filter: ToolFilter,
     tx_event: Sender<Event>,
     elicitation_requests: ElicitationRequestManager,
+    sampling_requests: SamplingRequestManager,
+    /// Set when the server opted in to `sampling/createMessage`.
+    sampling: Option<McpServerSamplingConfig>,
+    resource_subscriptions: ResourceSubscriptions,
+    /// Initial answer to `roots/list`.
+    roots: Vec<Root>,
+    network_proxy: Option<StartedNetworkProxy>,
     codex_apps_tools_cache_context: Option<CodexAppsToolsCacheContext>,
 }
 
 async fn make_rmcp_client(
     server_name: &str,
     transport: McpServerTransportConfig,
+    sandbox: Option<&McpServerSandboxConfig>,
+    sandbox_state: &SandboxState,
     store_mode: OAuthCredentialsStoreMode,
-) -> Result<RmcpClient, StartupOutcomeError> {
+) -> Result<(RmcpClient, Option<StartedNetworkProxy>), StartupOutcomeError> {
     match transport {
         McpServerTransportConfig::Stdio {
             command,
@@ -1437,11 +1798,37 @@ async fn make_rmcp_client(
             env_vars,
             cwd,
         } => {
-            let command_os: OsString = command.into();
-            let args_os: Vec<OsString> = args.into_iter().map(Into::into).collect();
-            RmcpClient::new_stdio_client(command_os, args_os, env, &env_vars, cwd)
+            let Some(sandbox) = sandbox else {
+                let command_os: OsString = command.into();
+                let args_os: Vec<OsString> = args.into_iter().map(Into::into).collect();
+                let client = RmcpClient::new_stdio_client(command_os, args_os, env, &env_vars, cwd)
+                    .await
+                    .map_err(|err| StartupOutcomeError::from(anyhow!(err)))?;
+                return Ok((client, None));
+            };
+
+            // The sandbox policy is relative to the directory the server runs in.
+            let cwd = cwd.unwrap_or_else(|| sandbox_state.sandbox_cwd.clone());
+            let sandboxed = sandbox_stdio_command(command, args, &cwd, sandbox, sandbox_state)
                 .await
-                .map_err(|err| StartupOutcomeError::from(anyhow!(err)))
+                .map_err(|err| {
+                    StartupOutcomeError::from(anyhow!(
+                        "failed to sandbox MCP server `{server_name}`: {err:#}"
+                    ))
+                })?;
+            let mut env = env.unwrap_or_default();
+            env.extend(sandboxed.env);
+            let client = RmcpClient::new_stdio_client_with_arg0(
+                sandboxed.program,
+                sandboxed.args,
+                sandboxed.arg0,
+                Some(env),
+                &env_vars,
+                Some(cwd),
+            )
+            .await
+            .map_err(|err| StartupOutcomeError::from(anyhow!(err)))?;
+            Ok((client, sandboxed.network_proxy))
         }
         McpServerTransportConfig::StreamableHttp {
             url,
@@ -1454,7 +1841,7 @@ async fn make_rmcp_client(
                     Ok(token) => token,
                     Err(error) => return Err(error.into()),
                 };
-            RmcpClient::new_streamable_http_client(
+            let client = RmcpClient::new_streamable_http_client(
                 server_name,
                 &url,
                 resolved_bearer_token,
@@ -1463,11 +1850,31 @@ async fn make_rmcp_client(
                 store_mode,
             )
             .await
-            .map_err(StartupOutcomeError::from)
+            .map_err(StartupOutcomeError::from)?;
+            Ok((client, None))
         }
     }
 }
 
+/// Points at the sandbox when a sandboxed server fails after it was spawned,
+/// since a denied file or network access usually surfaces as the server
+/// exiting or closing its pipes.
+fn sandboxed_startup_error(
+    server_name: &str,
+    sandbox: &McpServerSandboxConfig,
+    error: StartupOutcomeError,
+) -> StartupOutcomeError {
+    match error {
+        StartupOutcomeError::Failed { error } => StartupOutcomeError::Failed {
+            error: format!(
+                "{error} (the server runs in a sandbox: {}; if it needs more access, adjust [mcp_servers.{server_name}.sandbox] in config.toml)",
+                describe_sandbox(sandbox)
+            ),
+        },
+        StartupOutcomeError::Cancelled => StartupOutcomeError::Cancelled,
+    }
+}
+
 fn write_cached_codex_apps_tools_if_needed(
     server_name: &str,
     cache_context: Option<&CodexAppsToolsCacheContext>,
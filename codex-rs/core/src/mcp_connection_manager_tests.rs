@@ -542,6 +542,8 @@ fn mcp_init_error_display_prompts_for_github_pat() {
             disabled_tools: None,
             scopes: None,
             oauth_resource: None,
+            sampling: None,
+            sandbox: None,
         },
         auth_status: McpAuthStatus::Unsupported,
     };
@@ -590,6 +592,8 @@ fn mcp_init_error_display_reports_generic_errors() {
             disabled_tools: None,
             scopes: None,
             oauth_resource: None,
+            sampling: None,
+            sandbox: None,
         },
         auth_status: McpAuthStatus::Unsupported,
     };
@@ -615,6 +619,18 @@ fn mcp_init_error_display_includes_startup_timeout_hint() {
     );
 }
 
+#[test]
+fn sandboxed_startup_error_points_at_the_sandbox() {
+    let err: StartupOutcomeError = anyhow::anyhow!("connection closed").into();
+
+    let err = sandboxed_startup_error("fetch", &McpServerSandboxConfig::default(), err);
+
+    assert_eq!(
+        "MCP client for `fetch` failed to start: MCP startup failed: connection closed (the server runs in a sandbox: read-only, no network; if it needs more access, adjust [mcp_servers.fetch.sandbox] in config.toml)",
+        mcp_init_error_display("fetch", None, &err)
+    );
+}
+
 #[test]
 fn transport_origin_extracts_http_origin() {
     let transport = McpServerTransportConfig::StreamableHttp {
@@ -0,0 +1,528 @@
+//! Copy-on-write workspace for the Linux sandbox.
+//!
+//! With the `overlay_workspace` feature, sandboxed commands see the session's
+//! workspace through an overlayfs mount whose upper layer lives under
+//! `$CODEX_HOME/overlays/<thread id>`. Nothing reaches the real workspace until
+//! the user accepts the pending changes, which this module reads back out of
+//! the upper layer: regular files and symlinks are additions or
+//! modifications, whiteouts are deletions, and an opaque directory deletes
+//! every lower entry it does not contain.
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::ffi::OsString;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use codex_protocol::ThreadId;
+use codex_protocol::permissions::OverlayWorkspaceLayers;
+use codex_protocol::protocol::FileChange;
+use codex_protocol::protocol::OverlayWorkspaceDecision;
+
+const OVERLAYS_DIR: &str = "overlays";
+
+/// Returned instead of running a command outside the sandbox, because
+/// unsandboxed writes would land in the real workspace instead of the overlay.
+pub(crate) const ESCALATION_REFUSED_MESSAGE: &str =
+    "commands cannot run outside the sandbox while the copy-on-write workspace is active";
+
+#[derive(Debug)]
+pub(crate) struct OverlayWorkspace {
+    layers: OverlayWorkspaceLayers,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PendingKind {
+    Added,
+    Modified,
+    /// `whiteout` is false when the entry is hidden by an opaque directory
+    /// rather than by a whiteout of its own.
+    Deleted {
+        whiteout: bool,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct PendingChange {
+    relative: PathBuf,
+    kind: PendingKind,
+}
+
+impl OverlayWorkspace {
+    /// Creates (or reopens, when resuming a thread) the layers for `workspace`.
+    pub(crate) fn create(
+        codex_home: &Path,
+        thread_id: ThreadId,
+        workspace: &Path,
+    ) -> io::Result<Self> {
+        if !cfg!(target_os = "linux") {
+            return Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "copy-on-write workspaces require the Linux sandbox",
+            ));
+        }
+        let root = codex_home.join(OVERLAYS_DIR).join(thread_id.to_string());
+        if root.starts_with(workspace) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "cannot overlay {} because CODEX_HOME is inside it",
+                    workspace.display()
+                ),
+            ));
+        }
+        let layers = OverlayWorkspaceLayers {
+            lower: workspace.to_path_buf(),
+            upper: root.join("upper"),
+            work: root.join("work"),
+        };
+        fs::create_dir_all(&layers.upper)?;
+        fs::create_dir_all(&layers.work)?;
+        Ok(Self { layers })
+    }
+
+    pub(crate) fn layers(&self) -> &OverlayWorkspaceLayers {
+        &self.layers
+    }
+
+    /// Returns the host path holding the contents of `path` as sandboxed
+    /// commands see it, or `None` when the overlay deleted it.
+    pub(crate) fn read_path(&self, path: &Path) -> Option<PathBuf> {
+        let Ok(relative) = path.strip_prefix(&self.layers.lower) else {
+            return Some(path.to_path_buf());
+        };
+        let mut upper = self.layers.upper.clone();
+        for component in relative.components() {
+            upper.push(component);
+            let Ok(metadata) = upper.symlink_metadata() else {
+                return Some(path.to_path_buf());
+            };
+            if is_whiteout(&metadata) {
+                return None;
+            }
+            if !metadata.is_dir() || is_opaque_dir(&upper) {
+                return Some(self.layers.upper.join(relative));
+            }
+        }
+        Some(path.to_path_buf())
+    }
+
+    /// Lists the entries of `dir` as sandboxed commands see them: upper
+    /// entries shadow lower ones, whiteouts hide them, and an opaque directory
+    /// hides every lower entry.
+    pub(crate) fn read_dir(&self, dir: &Path) -> io::Result<Vec<(OsString, fs::FileType)>> {
+        let host = self
+            .read_path(dir)
+            .ok_or_else(|| io::Error::from(io::ErrorKind::NotFound))?;
+        let Ok(relative) = dir.strip_prefix(&self.layers.lower) else {
+            return host_dir_entries(&host);
+        };
+        if host.starts_with(&self.layers.upper) {
+            let mut entries = host_dir_entries(&host)?;
+            entries.retain(|(name, _)| {
+                !host
+                    .join(name)
+                    .symlink_metadata()
+                    .is_ok_and(|metadata| is_whiteout(&metadata))
+            });
+            return Ok(entries);
+        }
+
+        let upper = self.layers.upper.join(relative);
+        if !upper
+            .symlink_metadata()
+            .is_ok_and(|metadata| metadata.is_dir())
+        {
+            return host_dir_entries(dir);
+        }
+        let mut entries = Vec::new();
+        let mut seen = HashSet::new();
+        for entry in fs::read_dir(&upper)? {
+            let entry = entry?;
+            let metadata = entry.path().symlink_metadata()?;
+            seen.insert(entry.file_name());
+            if !is_whiteout(&metadata) {
+                entries.push((entry.file_name(), metadata.file_type()));
+            }
+        }
+        // The directory may only exist in the upper layer.
+        if dir
+            .symlink_metadata()
+            .is_ok_and(|metadata| metadata.is_dir())
+        {
+            for (name, file_type) in host_dir_entries(dir)? {
+                if !seen.contains(&name) {
+                    entries.push((name, file_type));
+                }
+            }
+        }
+        Ok(entries)
+    }
+
+    /// Lists the pending changes keyed by their path in the real workspace.
+    pub(crate) fn pending_file_changes(&self) -> io::Result<HashMap<PathBuf, FileChange>> {
+        self.pending_changes()?
+            .into_iter()
+            .map(|change| {
+                let lower = self.layers.lower.join(&change.relative);
+                let upper = self.layers.upper.join(&change.relative);
+                let file_change = match change.kind {
+                    PendingKind::Added => FileChange::Add {
+                        content: read_lossy(&upper)?,
+                    },
+                    PendingKind::Modified => {
+                        let old = read_lossy(&lower)?;
+                        let new = read_lossy(&upper)?;
+                        FileChange::Update {
+                            unified_diff: similar::TextDiff::from_lines(&old, &new)
+                                .unified_diff()
+                                .context_radius(3)
+                                .to_string(),
+                            move_path: None,
+                        }
+                    }
+                    PendingKind::Deleted { .. } => FileChange::Delete {
+                        content: read_lossy(&lower)?,
+                    },
+                };
+                Ok((lower, file_change))
+            })
+            .collect()
+    }
+
+    /// Accepts or rejects the pending changes at or beneath `paths` (every
+    /// pending change when `None`) and returns how many were resolved.
+    /// Relative paths are resolved against the workspace.
+    pub(crate) fn resolve(
+        &self,
+        decision: OverlayWorkspaceDecision,
+        paths: Option<&[PathBuf]>,
+    ) -> io::Result<usize> {
+        let selected = self
+            .pending_changes()?
+            .into_iter()
+            .filter(|change| self.is_selected(change, paths))
+            .collect::<Vec<_>>();
+        for change in &selected {
+            match decision {
+                OverlayWorkspaceDecision::Accept => self.accept_change(change)?,
+                OverlayWorkspaceDecision::Reject => self.reject_change(change)?,
+            }
+        }
+        self.prune_empty_dirs(Path::new(""), false)?;
+        Ok(selected.len())
+    }
+
+    fn is_selected(&self, change: &PendingChange, paths: Option<&[PathBuf]>) -> bool {
+        let path = self.layers.lower.join(&change.relative);
+        paths.is_none_or(|paths| {
+            paths
+                .iter()
+                .any(|selected| path.starts_with(self.layers.lower.join(selected)))
+        })
+    }
+
+    fn pending_changes(&self) -> io::Result<Vec<PendingChange>> {
+        let mut changes = Vec::new();
+        self.scan_dir(Path::new(""), false, &mut changes)?;
+        Ok(changes)
+    }
+
+    /// `hides_lower` is set inside opaque directories, where lower entries
+    /// missing from the upper layer are deleted.
+    fn scan_dir(
+        &self,
+        relative: &Path,
+        hides_lower: bool,
+        changes: &mut Vec<PendingChange>,
+    ) -> io::Result<()> {
+        let mut entries =
+            fs::read_dir(self.layers.upper.join(relative))?.collect::<io::Result<Vec<_>>>()?;
+        entries.sort_by_key(fs::DirEntry::file_name);
+
+        let mut seen = HashSet::new();
+        for entry in entries {
+            let name = entry.file_name();
+            let entry_relative = relative.join(&name);
+            seen.insert(name);
+            let upper = entry.path();
+            let metadata = upper.symlink_metadata()?;
+            let lower = self.layers.lower.join(&entry_relative);
+            let lower_metadata = lower.symlink_metadata().ok();
+
+            if is_whiteout(&metadata) {
+                if lower_metadata.is_some() {
+                    changes.push(PendingChange {
+                        relative: entry_relative,
+                        kind: PendingKind::Deleted { whiteout: true },
+                    });
+                }
+                continue;
+            }
+            if metadata.is_dir() {
+                let opaque = hides_lower || is_opaque_dir(&upper);
+                self.scan_dir(&entry_relative, opaque, changes)?;
+                continue;
+            }
+            let kind = match lower_metadata {
+                None => PendingKind::Added,
+                Some(lower_metadata) => {
+                    if same_contents(&upper, &metadata, &lower, &lower_metadata)? {
+                        continue;
+                    }
+                    PendingKind::Modified
+                }
+            };
+            changes.push(PendingChange {
+                relative: entry_relative,
+                kind,
+            });
+        }
+
+        if hides_lower {
+            for name in lower_dir_entries(&self.layers.lower.join(relative))? {
+                if !seen.contains(&name) {
+                    changes.push(PendingChange {
+                        relative: relative.join(name),
+                        kind: PendingKind::Deleted { whiteout: false },
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn accept_change(&self, change: &PendingChange) -> io::Result<()> {
+        let lower = self.layers.lower.join(&change.relative);
+        let upper = self.layers.upper.join(&change.relative);
+        match change.kind {
+            PendingKind::Added | PendingKind::Modified => {
+                prepare_destination(&self.layers.lower, &lower)?;
+                copy_entry(&upper, &lower)?;
+                fs::remove_file(&upper)
+            }
+            PendingKind::Deleted { whiteout } => {
+                remove_entry(&lower)?;
+                if whiteout {
+                    fs::remove_file(&upper)?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    fn reject_change(&self, change: &PendingChange) -> io::Result<()> {
+        let lower = self.layers.lower.join(&change.relative);
+        let upper = self.layers.upper.join(&change.relative);
+        match change.kind {
+            PendingKind::Added
+            | PendingKind::Modified
+            | PendingKind::Deleted { whiteout: true } => remove_entry(&upper),
+            // Restore the entry by copying it back above the opaque directory.
+            PendingKind::Deleted { whiteout: false } => copy_entry(&lower, &upper),
+        }
+    }
+
+    /// Removes upper directories that no longer hold changes. An empty opaque
+    /// directory is kept while it still hides lower entries. Returns whether
+    /// `relative` was removed.
+    fn prune_empty_dirs(&self, relative: &Path, hides_lower: bool) -> io::Result<bool> {
+        let upper = self.layers.upper.join(relative);
+        let hides_lower = hides_lower || is_opaque_dir(&upper);
+        let mut empty = true;
+        for entry in fs::read_dir(&upper)? {
+            let entry = entry?;
+            let is_dir = entry.file_type()?.is_dir();
+            if !is_dir || !self.prune_empty_dirs(&relative.join(entry.file_name()), hides_lower)? {
+                empty = false;
+            }
+        }
+        if !empty || relative.as_os_str().is_empty() {
+            return Ok(false);
+        }
+        if hides_lower && !lower_dir_entries(&self.layers.lower.join(relative))?.is_empty() {
+            return Ok(false);
+        }
+        fs::remove_dir(&upper)?;
+        Ok(true)
+    }
+}
+
+/// Reads the pending changes on the blocking pool.
+pub(crate) async fn pending_file_changes(
+    overlay: Arc<OverlayWorkspace>,
+) -> io::Result<HashMap<PathBuf, FileChange>> {
+    tokio::task::spawn_blocking(move || overlay.pending_file_changes())
+        .await
+        .map_err(io::Error::other)?
+}
+
+fn host_dir_entries(path: &Path) -> io::Result<Vec<(OsString, fs::FileType)>> {
+    fs::read_dir(path)?
+        .map(|entry| {
+            let entry = entry?;
+            Ok((entry.file_name(), entry.file_type()?))
+        })
+        .collect()
+}
+
+fn lower_dir_entries(path: &Path) -> io::Result<Vec<OsString>> {
+    if !path
+        .symlink_metadata()
+        .is_ok_and(|metadata| metadata.is_dir())
+    {
+        return Ok(Vec::new());
+    }
+    let mut names = fs::read_dir(path)?
+        .map(|entry| entry.map(|entry| entry.file_name()))
+        .collect::<io::Result<Vec<_>>>()?;
+    names.sort();
+    Ok(names)
+}
+
+fn same_contents(
+    upper: &Path,
+    upper_metadata: &fs::Metadata,
+    lower: &Path,
+    lower_metadata: &fs::Metadata,
+) -> io::Result<bool> {
+    if upper_metadata.is_symlink() || lower_metadata.is_symlink() {
+        return Ok(upper_metadata.is_symlink()
+            && lower_metadata.is_symlink()
+            && fs::read_link(upper)? == fs::read_link(lower)?);
+    }
+    if !lower_metadata.is_file()
+        || upper_metadata.len() != lower_metadata.len()
+        || upper_metadata.permissions() != lower_metadata.permissions()
+    {
+        return Ok(false);
+    }
+    Ok(fs::read(upper)? == fs::read(lower)?)
+}
+
+/// Text shown for an entry in the change summary. Directories have none and
+/// symlinks show their target.
+fn read_lossy(path: &Path) -> io::Result<String> {
+    let metadata = path.symlink_metadata()?;
+    if metadata.is_symlink() {
+        return Ok(fs::read_link(path)?.display().to_string());
+    }
+    if !metadata.is_file() {
+        return Ok(String::new());
+    }
+    Ok(String::from_utf8_lossy(&fs::read(path)?).into_owned())
+}
+
+/// Makes room for `path` in the real workspace, replacing any file that sits
+/// where the overlay has a directory.
+fn prepare_destination(root: &Path, path: &Path) -> io::Result<()> {
+    let Some(parent) = path.parent() else {
+        return Ok(());
+    };
+    if let Ok(relative) = parent.strip_prefix(root) {
+        let mut ancestor = root.to_path_buf();
+        for component in relative.components() {
+            ancestor.push(component);
+            if ancestor
+                .symlink_metadata()
+                .is_ok_and(|metadata| !metadata.is_dir())
+            {
+                fs::remove_file(&ancestor)?;
+            }
+        }
+    }
+    fs::create_dir_all(parent)?;
+    remove_entry(path)
+}
+
+fn copy_entry(src: &Path, dst: &Path) -> io::Result<()> {
+    let metadata = src.symlink_metadata()?;
+    if metadata.is_symlink() {
+        copy_symlink(src, dst)
+    } else if metadata.is_dir() {
+        fs::create_dir_all(dst)?;
+        fs::set_permissions(dst, metadata.permissions())?;
+        for entry in fs::read_dir(src)? {
+            let entry = entry?;
+            copy_entry(&entry.path(), &dst.join(entry.file_name()))?;
+        }
+        Ok(())
+    } else {
+        fs::copy(src, dst).map(|_| ())
+    }
+}
+
+fn remove_entry(path: &Path) -> io::Result<()> {
+    match path.symlink_metadata() {
+        Ok(metadata) if metadata.is_dir() => fs::remove_dir_all(path),
+        Ok(_) => fs::remove_file(path),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(()),
+        Err(err) => Err(err),
+    }
+}
+
+#[cfg(unix)]
+fn copy_symlink(src: &Path, dst: &Path) -> io::Result<()> {
+    std::os::unix::fs::symlink(fs::read_link(src)?, dst)
+}
+
+#[cfg(not(unix))]
+fn copy_symlink(src: &Path, _dst: &Path) -> io::Result<()> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        format!("cannot copy symlink {}", src.display()),
+    ))
+}
+
+/// overlayfs records a deletion as a 0/0 character device.
+#[cfg(unix)]
+fn is_whiteout(metadata: &fs::Metadata) -> bool {
+    use std::os::unix::fs::FileTypeExt;
+    use std::os::unix::fs::MetadataExt;
+
+    metadata.file_type().is_char_device() && metadata.rdev() == 0
+}
+
+#[cfg(not(unix))]
+fn is_whiteout(_metadata: &fs::Metadata) -> bool {
+    false
+}
+
+/// bubblewrap mounts the overlay with `userxattr`, so the opaque marker is a
+/// `user.` xattr; a privileged mount would use `trusted.` instead.
+#[cfg(target_os = "linux")]
+fn is_opaque_dir(path: &Path) -> bool {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+
+    let Ok(c_path) = CString::new(path.as_os_str().as_bytes()) else {
+        return false;
+    };
+    [c"user.overlay.opaque", c"trusted.overlay.opaque"]
+        .iter()
+        .any(|name| {
+            let mut value = [0u8; 1];
+            // SAFETY: both strings are NUL-terminated and `value` outlives the call.
+            let len = unsafe {
+                libc::lgetxattr(
+                    c_path.as_ptr(),
+                    name.as_ptr(),
+                    value.as_mut_ptr().cast(),
+                    value.len(),
+                )
+            };
+            len == 1 && value[0] == b'y'
+        })
+}
+
+#[cfg(not(target_os = "linux"))]
+fn is_opaque_dir(_path: &Path) -> bool {
+    false
+}
+
+#[cfg(test)]
+#[path = "overlay_workspace_tests.rs"]
+mod tests;
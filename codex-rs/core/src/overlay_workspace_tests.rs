@@ -0,0 +1,223 @@
+use super::*;
+use pretty_assertions::assert_eq;
+use tempfile::TempDir;
+
+struct Fixture {
+    _dir: TempDir,
+    overlay: OverlayWorkspace,
+}
+
+impl Fixture {
+    fn new() -> Self {
+        let dir = tempfile::tempdir().expect("create temp dir");
+        let layers = OverlayWorkspaceLayers {
+            lower: dir.path().join("lower"),
+            upper: dir.path().join("upper"),
+            work: dir.path().join("work"),
+        };
+        for path in [&layers.lower, &layers.upper, &layers.work] {
+            fs::create_dir_all(path).expect("create layer");
+        }
+        Self {
+            _dir: dir,
+            overlay: OverlayWorkspace { layers },
+        }
+    }
+
+    fn write_lower(&self, relative: &str, contents: &str) {
+        write(&self.overlay.layers.lower.join(relative), contents);
+    }
+
+    fn write_upper(&self, relative: &str, contents: &str) {
+        write(&self.overlay.layers.upper.join(relative), contents);
+    }
+
+    fn lower(&self, relative: &str) -> PathBuf {
+        self.overlay.layers.lower.join(relative)
+    }
+
+    fn upper(&self, relative: &str) -> PathBuf {
+        self.overlay.layers.upper.join(relative)
+    }
+}
+
+fn dir_names(overlay: &OverlayWorkspace, dir: &Path) -> Vec<String> {
+    let mut names = overlay
+        .read_dir(dir)
+        .expect("read dir")
+        .into_iter()
+        .map(|(name, _)| name.to_string_lossy().into_owned())
+        .collect::<Vec<_>>();
+    names.sort();
+    names
+}
+
+fn write(path: &Path, contents: &str) {
+    fs::create_dir_all(path.parent().expect("parent")).expect("create parent");
+    fs::write(path, contents).expect("write file");
+}
+
+#[test]
+fn pending_changes_report_additions_and_modifications_but_not_copy_ups() {
+    let fixture = Fixture::new();
+    fixture.write_lower("edited.txt", "one\n");
+    fixture.write_lower("copied.txt", "same\n");
+    fixture.write_upper("edited.txt", "two\n");
+    fixture.write_upper("copied.txt", "same\n");
+    fixture.write_upper("new/file.txt", "hello\n");
+
+    let changes = fixture.overlay.pending_file_changes().expect("changes");
+
+    assert_eq!(
+        changes,
+        HashMap::from([
+            (
+                fixture.lower("edited.txt"),
+                FileChange::Update {
+                    unified_diff: "@@ -1 +1 @@\n-one\n+two\n".to_string(),
+                    move_path: None,
+                },
+            ),
+            (
+                fixture.lower("new/file.txt"),
+                FileChange::Add {
+                    content: "hello\n".to_string(),
+                },
+            ),
+        ])
+    );
+}
+
+#[test]
+fn accepting_selected_paths_leaves_the_rest_pending() {
+    let fixture = Fixture::new();
+    fixture.write_lower("edited.txt", "one\n");
+    fixture.write_upper("edited.txt", "two\n");
+    fixture.write_upper("new/file.txt", "hello\n");
+
+    let resolved = fixture
+        .overlay
+        .resolve(
+            OverlayWorkspaceDecision::Accept,
+            Some(&[PathBuf::from("new")]),
+        )
+        .expect("accept");
+
+    assert_eq!(resolved, 1);
+    assert_eq!(
+        fs::read_to_string(fixture.lower("new/file.txt")).expect("read accepted file"),
+        "hello\n"
+    );
+    assert!(!fixture.upper("new").exists());
+    assert_eq!(
+        fixture
+            .overlay
+            .pending_file_changes()
+            .expect("changes")
+            .into_keys()
+            .collect::<Vec<_>>(),
+        vec![fixture.lower("edited.txt")]
+    );
+}
+
+#[test]
+fn rejecting_everything_discards_the_upper_layer() {
+    let fixture = Fixture::new();
+    fixture.write_lower("edited.txt", "one\n");
+    fixture.write_upper("edited.txt", "two\n");
+    fixture.write_upper("new/file.txt", "hello\n");
+
+    let resolved = fixture
+        .overlay
+        .resolve(OverlayWorkspaceDecision::Reject, None)
+        .expect("reject");
+
+    assert_eq!(resolved, 2);
+    assert_eq!(
+        fs::read_to_string(fixture.lower("edited.txt")).expect("read lower"),
+        "one\n"
+    );
+    assert!(!fixture.lower("new").exists());
+    assert_eq!(
+        fs::read_dir(&fixture.overlay.layers.upper)
+            .expect("read upper")
+            .count(),
+        0
+    );
+}
+
+#[test]
+fn read_dir_merges_upper_entries_over_the_lower_layer() {
+    let fixture = Fixture::new();
+    fixture.write_lower("kept.txt", "lower\n");
+    fixture.write_lower("src/lib.rs", "lower\n");
+    fixture.write_upper("src/lib.rs", "upper\n");
+    fixture.write_upper("new/file.txt", "hello\n");
+
+    assert_eq!(
+        dir_names(&fixture.overlay, &fixture.overlay.layers.lower),
+        vec!["kept.txt".to_string(), "new".to_string(), "src".to_string()]
+    );
+    assert_eq!(
+        dir_names(&fixture.overlay, &fixture.lower("new")),
+        vec!["file.txt".to_string()]
+    );
+    assert_eq!(
+        dir_names(&fixture.overlay, &fixture.lower("src")),
+        vec!["lib.rs".to_string()]
+    );
+    assert!(fixture.overlay.read_dir(&fixture.lower("missing")).is_err());
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn whiteouts_are_deletions_and_hide_the_lower_file() {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+
+    let fixture = Fixture::new();
+    fixture.write_lower("gone.txt", "bye\n");
+    fixture.write_lower("kept.txt", "lower\n");
+    fixture.write_upper("kept.txt", "upper\n");
+    let whiteout = CString::new(fixture.upper("gone.txt").as_os_str().as_bytes()).expect("path");
+    // SAFETY: `whiteout` is a valid NUL-terminated path.
+    if unsafe { libc::mknod(whiteout.as_ptr(), libc::S_IFCHR, 0) } != 0 {
+        // Unprivileged whiteout creation needs Linux 5.8 or newer.
+        return;
+    }
+
+    assert_eq!(fixture.overlay.read_path(&fixture.lower("gone.txt")), None);
+    assert_eq!(
+        fixture.overlay.read_path(&fixture.lower("kept.txt")),
+        Some(fixture.upper("kept.txt"))
+    );
+    assert_eq!(
+        fixture.overlay.read_path(&fixture.lower("other.txt")),
+        Some(fixture.lower("other.txt"))
+    );
+    assert_eq!(
+        dir_names(&fixture.overlay, &fixture.overlay.layers.lower),
+        vec!["kept.txt".to_string()]
+    );
+    assert_eq!(
+        fixture
+            .overlay
+            .pending_file_changes()
+            .expect("changes")
+            .get(&fixture.lower("gone.txt")),
+        Some(&FileChange::Delete {
+            content: "bye\n".to_string(),
+        })
+    );
+
+    fixture
+        .overlay
+        .resolve(
+            OverlayWorkspaceDecision::Accept,
+            Some(&[fixture.lower("gone.txt")]),
+        )
+        .expect("accept");
+
+    assert!(!fixture.lower("gone.txt").exists());
+    assert!(fixture.upper("gone.txt").symlink_metadata().is_err());
+}
@@ -198,6 +198,8 @@ fn load_plugins_loads_default_skills_and_mcp_servers() {
                     disabled_tools: None,
                     scopes: None,
                     oauth_resource: None,
+                    sampling: None,
+                    sandbox: None,
                 },
             )]),
             apps: vec![AppConnectorId("connector_example".to_string())],
@@ -367,6 +369,8 @@ fn load_plugins_uses_manifest_configured_component_paths() {
                 disabled_tools: None,
                 scopes: None,
                 oauth_resource: None,
+                sampling: None,
+                sandbox: None,
             },
         )])
     );
@@ -470,6 +474,8 @@ fn load_plugins_ignores_manifest_component_paths_without_dot_slash() {
                 disabled_tools: None,
                 scopes: None,
                 oauth_resource: None,
+                sampling: None,
+                sandbox: None,
             },
         )])
     );
@@ -617,6 +623,8 @@ fn capability_index_filters_inactive_and_zero_capability_plugins() {
         disabled_tools: None,
         scopes: None,
         oauth_resource: None,
+        sampling: None,
+        sandbox: None,
     };
     let plugin = |config_name: &str, dir_name: &str, manifest_name: &str| LoadedPlugin {
         config_name: config_name.to_string(),
@@ -0,0 +1,233 @@
+//! Keeps an existing repo index in sync with working-tree edits by running
+//! debounced, file-scoped `repo_index_refresh` calls in the background.
+
+use std::collections::HashSet;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use globset::Glob;
+use globset::GlobSet;
+use globset::GlobSetBuilder;
+use tokio::sync::mpsc;
+use tokio::time::Instant;
+use tokio::time::timeout_at;
+use tracing::debug;
+use tracing::warn;
+
+use crate::SandboxState;
+use crate::config::Config;
+use crate::tasks::RepoToolsConnection;
+
+/// Quiet period after the last change before the touched files are refreshed.
+const REPO_INDEX_UPDATE_DEBOUNCE: Duration = Duration::from_secs(2);
+
+/// Longest a change waits for a refresh while edits keep arriving.
+const REPO_INDEX_UPDATE_MAX_WAIT: Duration = Duration::from_secs(10);
+
+/// Where the repo tools server stores the index, relative to the repo root.
+pub(crate) const REPO_INDEX_DIR: &str = ".codex/repo_hybrid_index";
+
+pub(crate) struct RepoIndexUpdater {
+    repo_root: PathBuf,
+    tx: Option<mpsc::UnboundedSender<Vec<PathBuf>>>,
+}
+
+impl RepoIndexUpdater {
+    /// Starts the background update loop for `config.cwd` when
+    /// `query_project_index.auto_update` is enabled.
+    pub(crate) fn new(config: Arc<Config>, sandbox_state: SandboxState) -> Self {
+        let repo_root = config.cwd.clone();
+        if !config.query_project_index.auto_update {
+            return Self::disabled(repo_root);
+        }
+        let include_globs = match build_include_globs(&config.query_project_index.file_globs) {
+            Ok(include_globs) => include_globs,
+            Err(err) => {
+                warn!("repo index auto-update disabled: {err}");
+                return Self::disabled(repo_root);
+            }
+        };
+
+        let (tx, rx) = mpsc::unbounded_channel();
+        tokio::spawn(run_update_loop(
+            rx,
+            repo_root.clone(),
+            include_globs,
+            config,
+            sandbox_state,
+        ));
+        Self {
+            repo_root,
+            tx: Some(tx),
+        }
+    }
+
+    pub(crate) fn disabled(repo_root: PathBuf) -> Self {
+        Self {
+            repo_root,
+            tx: None,
+        }
+    }
+
+    /// Queues changed paths for the next debounced refresh. Paths outside the
+    /// repo root are ignored; relative paths are resolved against it.
+    pub(crate) fn notify_paths(&self, paths: impl IntoIterator<Item = PathBuf>) {
+        let Some(tx) = &self.tx else {
+            return;
+        };
+        let paths: Vec<PathBuf> = paths
+            .into_iter()
+            .map(|path| self.repo_root.join(path))
+            .filter(|path| path.starts_with(&self.repo_root))
+            .collect();
+        if !paths.is_empty() {
+            let _ = tx.send(paths);
+        }
+    }
+}
+
+async fn run_update_loop(
+    mut rx: mpsc::UnboundedReceiver<Vec<PathBuf>>,
+    repo_root: PathBuf,
+    include_globs: Option<GlobSet>,
+    config: Arc<Config>,
+    sandbox_state: SandboxState,
+) {
+    // One repo tools server serves every refresh of this session; it is
+    // started on first use and restarted after a failed call.
+    let mut connection: Option<RepoToolsConnection> = None;
+    while let Some(paths) = next_batch(
+        &mut rx,
+        REPO_INDEX_UPDATE_DEBOUNCE,
+        REPO_INDEX_UPDATE_MAX_WAIT,
+    )
+    .await
+    {
+        refresh_paths(
+            &mut connection,
+            &repo_root,
+            paths,
+            include_globs.as_ref(),
+            &config,
+            &sandbox_state,
+        )
+        .await;
+    }
+}
+
+/// Waits for changed paths and collects them until no change arrives for
+/// `debounce`, `max_wait` after the first one, or the channel closes. Returns
+/// `None` once the channel is closed and nothing is pending.
+async fn next_batch(
+    rx: &mut mpsc::UnboundedReceiver<Vec<PathBuf>>,
+    debounce: Duration,
+    max_wait: Duration,
+) -> Option<HashSet<PathBuf>> {
+    let mut pending: HashSet<PathBuf> = rx.recv().await?.into_iter().collect();
+    let flush_at = Instant::now() + max_wait;
+    loop {
+        let quiet_until = (Instant::now() + debounce).min(flush_at);
+        match timeout_at(quiet_until, rx.recv()).await {
+            Ok(Some(paths)) => pending.extend(paths),
+            // A closed channel still flushes what is pending.
+            Ok(None) | Err(_) => return Some(pending),
+        }
+    }
+}
+
+async fn refresh_paths(
+    connection: &mut Option<RepoToolsConnection>,
+    repo_root: &Path,
+    paths: HashSet<PathBuf>,
+    include_globs: Option<&GlobSet>,
+    config: &Config,
+    sandbox_state: &SandboxState,
+) {
+    // Only keep an index the user already built up to date; never create one.
+    if !repo_root.join(REPO_INDEX_DIR).is_dir() {
+        return;
+    }
+    let file_globs = scoped_refresh_globs(repo_root, paths, include_globs);
+    if file_globs.is_empty() {
+        return;
+    }
+
+    let arguments = serde_json::json!({
+        "repo_root": repo_root.display().to_string(),
+        "file_globs": &file_globs,
+    });
+    if connection.is_none() {
+        match RepoToolsConnection::start(config, sandbox_state.clone()).await {
+            Ok(started) => *connection = Some(started),
+            Err(err) => {
+                warn!("repo index auto-update failed: {err}");
+                return;
+            }
+        }
+    }
+    let Some(active) = connection.as_ref() else {
+        return;
+    };
+    let result = active.call_repo_index_refresh(arguments).await;
+    match result {
+        Ok(result) if result.is_error == Some(true) => {
+            warn!(
+                "repo index auto-update failed: {}",
+                serde_json::Value::Array(result.content)
+            );
+        }
+        Ok(_) => debug!(
+            ?file_globs,
+            "repo index auto-update refreshed changed files"
+        ),
+        Err(err) => {
+            warn!("repo index auto-update failed: {err}");
+            *connection = None;
+        }
+    }
+}
+
+/// Converts changed absolute paths into literal globs for a scoped refresh.
+/// Each path also matches its descendants so removed or renamed directories
+/// drop their files from the index.
+fn scoped_refresh_globs(
+    repo_root: &Path,
+    paths: HashSet<PathBuf>,
+    include_globs: Option<&GlobSet>,
+) -> Vec<String> {
+    let mut relative_paths: Vec<String> = paths
+        .iter()
+        .filter_map(|path| path.strip_prefix(repo_root).ok())
+        .filter_map(Path::to_str)
+        .map(|path| path.replace('\\', "/"))
+        .filter(|path| !path.is_empty())
+        .filter(|path| include_globs.is_none_or(|include_globs| include_globs.is_match(path)))
+        .collect();
+    relative_paths.sort_unstable();
+    relative_paths.dedup();
+
+    relative_paths
+        .into_iter()
+        .flat_map(|path| {
+            let escaped = globset::escape(&path);
+            [format!("{escaped}/**"), escaped]
+        })
+        .collect()
+}
+
+fn build_include_globs(file_globs: &[String]) -> Result<Option<GlobSet>, globset::Error> {
+    if file_globs.is_empty() {
+        return Ok(None);
+    }
+    let mut builder = GlobSetBuilder::new();
+    for file_glob in file_globs {
+        builder.add(Glob::new(file_glob)?);
+    }
+    builder.build().map(Some)
+}
+
+#[cfg(test)]
+#[path = "repo_index_updater_tests.rs"]
+mod tests;
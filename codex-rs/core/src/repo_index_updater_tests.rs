@@ -0,0 +1,114 @@
+use super::*;
+use pretty_assertions::assert_eq;
+
+fn paths(root: &Path, relative: &[&str]) -> HashSet<PathBuf> {
+    relative.iter().map(|path| root.join(path)).collect()
+}
+
+#[test]
+fn scoped_refresh_globs_escape_paths_and_cover_descendants() {
+    let root = Path::new("/tmp/repo");
+    let changed = paths(root, &["src/lib.rs", "docs/[draft].md", "src/lib.rs"]);
+
+    let globs = scoped_refresh_globs(root, changed, None);
+
+    assert_eq!(
+        globs,
+        vec![
+            "docs/[[]draft[]].md/**".to_string(),
+            "docs/[[]draft[]].md".to_string(),
+            "src/lib.rs/**".to_string(),
+            "src/lib.rs".to_string(),
+        ]
+    );
+}
+
+#[test]
+fn scoped_refresh_globs_respect_configured_include_globs() {
+    let root = Path::new("/tmp/repo");
+    let include_globs = build_include_globs(&["src/**/*.rs".to_string()])
+        .expect("valid globs")
+        .expect("non-empty globs");
+    let mut changed = paths(root, &["src/main.rs", "README.md"]);
+    changed.insert(PathBuf::from("/tmp/elsewhere/src/main.rs"));
+
+    let globs = scoped_refresh_globs(root, changed, Some(&include_globs));
+
+    assert_eq!(
+        globs,
+        vec!["src/main.rs/**".to_string(), "src/main.rs".to_string()]
+    );
+}
+
+#[tokio::test]
+async fn notify_paths_resolves_relative_paths_and_drops_outside_paths() {
+    let root = PathBuf::from("/tmp/repo");
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    let updater = RepoIndexUpdater {
+        repo_root: root.clone(),
+        tx: Some(tx),
+    };
+
+    updater.notify_paths([
+        PathBuf::from("src/lib.rs"),
+        root.join("Cargo.toml"),
+        PathBuf::from("/tmp/other/Cargo.toml"),
+    ]);
+    updater.notify_paths([PathBuf::from("/tmp/other/README.md")]);
+    drop(updater);
+
+    assert_eq!(
+        rx.recv().await,
+        Some(vec![root.join("src/lib.rs"), root.join("Cargo.toml")])
+    );
+    assert_eq!(rx.recv().await, None);
+}
+
+#[tokio::test]
+async fn next_batch_flushes_after_max_wait_while_changes_keep_arriving() {
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    let sender = tokio::spawn(async move {
+        for index in 0.. {
+            if tx
+                .send(vec![PathBuf::from(format!("/repo/{index}"))])
+                .is_err()
+            {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    });
+
+    let batch = tokio::time::timeout(
+        Duration::from_secs(5),
+        next_batch(
+            &mut rx,
+            Duration::from_millis(200),
+            Duration::from_millis(300),
+        ),
+    )
+    .await
+    .expect("flushed while changes keep arriving")
+    .expect("a batch");
+    assert!(!batch.is_empty());
+
+    drop(rx);
+    sender.await.expect("sender task");
+}
+
+#[tokio::test]
+async fn next_batch_flushes_pending_paths_when_the_channel_closes() {
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    tx.send(vec![PathBuf::from("/repo/a.rs")]).expect("send");
+    tx.send(vec![PathBuf::from("/repo/b.rs")]).expect("send");
+    drop(tx);
+
+    let batch = next_batch(&mut rx, Duration::from_secs(60), Duration::from_secs(60))
+        .await
+        .expect("pending paths are flushed");
+    assert_eq!(batch, paths(Path::new("/repo"), &["a.rs", "b.rs"]));
+    assert_eq!(
+        next_batch(&mut rx, Duration::from_secs(60), Duration::from_secs(60)).await,
+        None
+    );
+}
@@ -1,4 +1,6 @@
+use anyhow::Context;
 use codex_git::merge_base_with_head;
+use codex_git::resolve_commit_range;
 use codex_protocol::protocol::ReviewRequest;
 use codex_protocol::protocol::ReviewTarget;
 use std::path::Path;
@@ -19,6 +21,14 @@ const COMMIT_PROMPT_WITH_TITLE: &str = "Review the code changes introduced by co
 const COMMIT_PROMPT: &str =
     "Review the code changes introduced by commit {sha}. Provide prioritized, actionable findings.";
 
+const COMMIT_RANGE_PROMPT: &str = "Review the code changes in the commit range {base}..{head} ({commitCount} commits, {baseSha}..{headSha}). Run `git log {baseSha}..{headSha}` and `git diff {baseSha} {headSha}` to inspect them. Do not check out either revision; use `git show <rev>:<path>` to read files at a given revision. Provide prioritized, actionable findings.";
+
+const PATCH_PROMPT: &str = "Review the code changes in the following patch ({source}). The patch has not been applied to the working tree and must not be applied; read the surrounding code in the repository for context where it helps. Provide prioritized, actionable findings.";
+
+/// Patches are inlined into the review prompt, so keep them well inside the
+/// model's context window.
+const MAX_PATCH_BYTES: usize = 512 * 1024;
+
 pub fn resolve_review_request(
     request: ReviewRequest,
     cwd: &Path,
@@ -57,6 +67,29 @@ pub fn review_prompt(target: &ReviewTarget, cwd: &Path) -> anyhow::Result<String
                 Ok(COMMIT_PROMPT.replace("{sha}", sha))
             }
         }
+        ReviewTarget::CommitRange { base, head } => {
+            let Some(range) = resolve_commit_range(cwd, base, head)? else {
+                anyhow::bail!("Could not resolve commit range {base}..{head}");
+            };
+            Ok(COMMIT_RANGE_PROMPT
+                .replace("{base}", base)
+                .replace("{head}", head)
+                .replace("{commitCount}", &range.commit_count.to_string())
+                .replace("{baseSha}", &range.base_sha)
+                .replace("{headSha}", &range.head_sha))
+        }
+        ReviewTarget::PatchFile { path } => {
+            let path = cwd.join(path);
+            let bytes = std::fs::read(&path)
+                .with_context(|| format!("failed to read patch {}", path.display()))?;
+            patch_prompt(
+                &String::from_utf8_lossy(&bytes),
+                &path.display().to_string(),
+            )
+        }
+        ReviewTarget::Diff { diff, title } => {
+            patch_prompt(diff, title.as_deref().unwrap_or("provided diff"))
+        }
         ReviewTarget::Custom { instructions } => {
             let prompt = instructions.trim();
             if prompt.is_empty() {
@@ -67,6 +100,32 @@ pub fn review_prompt(target: &ReviewTarget, cwd: &Path) -> anyhow::Result<String
     }
 }
 
+fn patch_prompt(diff: &str, source: &str) -> anyhow::Result<String> {
+    if diff.trim().is_empty() {
+        anyhow::bail!("Patch to review is empty");
+    }
+    if diff.len() > MAX_PATCH_BYTES {
+        anyhow::bail!(
+            "Patch to review is {} bytes; the limit is {MAX_PATCH_BYTES} bytes",
+            diff.len()
+        );
+    }
+    // Use a fence longer than any backtick run inside the patch so it cannot
+    // close the block early.
+    let mut longest_run = 0;
+    let mut run = 0;
+    for ch in diff.chars() {
+        run = if ch == '`' { run + 1 } else { 0 };
+        longest_run = longest_run.max(run);
+    }
+    let fence = "`".repeat(longest_run.max(2) + 1);
+    Ok(format!(
+        "{}\n\n{fence}diff\n{}\n{fence}",
+        PATCH_PROMPT.replace("{source}", source),
+        diff.trim_end_matches('\n')
+    ))
+}
+
 pub fn user_facing_hint(target: &ReviewTarget) -> String {
     match target {
         ReviewTarget::UncommittedChanges => "current changes".to_string(),
@@ -79,6 +138,14 @@ pub fn user_facing_hint(target: &ReviewTarget) -> String {
                 format!("commit {short_sha}")
             }
         }
+        ReviewTarget::CommitRange { base, head } => format!("commits {base}..{head}"),
+        ReviewTarget::PatchFile { path } => {
+            let name = path.file_name().unwrap_or(path.as_os_str());
+            format!("patch {}", name.to_string_lossy())
+        }
+        ReviewTarget::Diff { title, .. } => {
+            title.clone().unwrap_or_else(|| "provided diff".to_string())
+        }
         ReviewTarget::Custom { instructions } => instructions.trim().to_string(),
     }
 }
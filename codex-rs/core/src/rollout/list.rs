@@ -73,6 +73,17 @@ pub struct ThreadItem {
     pub updated_at: Option<String>,
 }
 
+/// A thread matching a full-text session search.
+#[derive(Debug, PartialEq)]
+pub struct ThreadSearchHit {
+    /// Summary information for the matching thread.
+    pub item: ThreadItem,
+    /// The kind of content that matched best.
+    pub match_kind: codex_state::ThreadSearchDocumentKind,
+    /// A short excerpt around the best match.
+    pub snippet: String,
+}
+
 #[allow(dead_code)]
 #[deprecated(note = "use ThreadItem")]
 pub type ConversationItem = ThreadItem;
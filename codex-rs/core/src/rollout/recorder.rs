@@ -30,6 +30,7 @@ use super::list::Cursor;
 use super::list::ThreadItem;
 use super::list::ThreadListConfig;
 use super::list::ThreadListLayout;
+use super::list::ThreadSearchHit;
 use super::list::ThreadSortKey;
 use super::list::ThreadsPage;
 use super::list::get_threads;
@@ -295,6 +296,33 @@ impl RolloutRecorder {
         Ok(truncate_fs_page(fs_page, page_size, sort_key))
     }
 
+    /// Full-text search user messages, agent messages and command outputs of
+    /// recorded threads. Requires the SQLite state database.
+    pub async fn search_threads(
+        config: &Config,
+        query: &str,
+        limit: usize,
+        allowed_sources: &[SessionSource],
+        model_providers: Option<&[String]>,
+        archived: bool,
+    ) -> std::io::Result<Vec<ThreadSearchHit>> {
+        let state_db_ctx = state_db::get_state_db(config)
+            .await
+            .ok_or_else(|| IoError::other("session search requires the state database"))?;
+        let hits = state_db::search_threads_db(
+            Some(&state_db_ctx),
+            config.codex_home.as_path(),
+            query,
+            limit,
+            allowed_sources,
+            model_providers,
+            archived,
+        )
+        .await
+        .ok_or_else(|| IoError::other("session search failed"))?;
+        Ok(hits.into_iter().map(ThreadSearchHit::from).collect())
+    }
+
     /// Find the newest recorded thread path, optionally filtering to a matching cwd.
     #[allow(clippy::too_many_arguments)]
     pub async fn find_latest_thread_path(
@@ -979,34 +1007,35 @@ impl JsonlWriter {
     }
 }
 
+impl From<codex_state::ThreadMetadata> for ThreadItem {
+    fn from(item: codex_state::ThreadMetadata) -> Self {
+        Self {
+            path: item.rollout_path,
+            thread_id: Some(item.id),
+            first_user_message: item.first_user_message,
+            cwd: Some(item.cwd),
+            git_branch: item.git_branch,
+            git_sha: item.git_sha,
+            git_origin_url: item.git_origin_url,
+            source: Some(
+                serde_json::from_str(item.source.as_str())
+                    .or_else(|_| serde_json::from_value(Value::String(item.source)))
+                    .unwrap_or(SessionSource::Unknown),
+            ),
+            agent_nickname: item.agent_nickname,
+            agent_role: item.agent_role,
+            model_provider: Some(item.model_provider),
+            cli_version: Some(item.cli_version),
+            created_at: Some(item.created_at.to_rfc3339_opts(SecondsFormat::Secs, true)),
+            updated_at: Some(item.updated_at.to_rfc3339_opts(SecondsFormat::Secs, true)),
+        }
+    }
+}
+
 impl From<codex_state::ThreadsPage> for ThreadsPage {
     fn from(db_page: codex_state::ThreadsPage) -> Self {
-        let items = db_page
-            .items
-            .into_iter()
-            .map(|item| ThreadItem {
-                path: item.rollout_path,
-                thread_id: Some(item.id),
-                first_user_message: item.first_user_message,
-                cwd: Some(item.cwd),
-                git_branch: item.git_branch,
-                git_sha: item.git_sha,
-                git_origin_url: item.git_origin_url,
-                source: Some(
-                    serde_json::from_str(item.source.as_str())
-                        .or_else(|_| serde_json::from_value(Value::String(item.source)))
-                        .unwrap_or(SessionSource::Unknown),
-                ),
-                agent_nickname: item.agent_nickname,
-                agent_role: item.agent_role,
-                model_provider: Some(item.model_provider),
-                cli_version: Some(item.cli_version),
-                created_at: Some(item.created_at.to_rfc3339_opts(SecondsFormat::Secs, true)),
-                updated_at: Some(item.updated_at.to_rfc3339_opts(SecondsFormat::Secs, true)),
-            })
-            .collect();
         Self {
-            items,
+            items: db_page.items.into_iter().map(ThreadItem::from).collect(),
             next_cursor: db_page.next_anchor.map(Into::into),
             num_scanned_files: db_page.num_scanned_rows,
             reached_scan_cap: false,
@@ -1014,6 +1043,16 @@ impl From<codex_state::ThreadsPage> for ThreadsPage {
     }
 }
 
+impl From<codex_state::ThreadSearchHit> for ThreadSearchHit {
+    fn from(hit: codex_state::ThreadSearchHit) -> Self {
+        Self {
+            item: hit.metadata.into(),
+            match_kind: hit.match_kind,
+            snippet: hit.snippet,
+        }
+    }
+}
+
 async fn select_resume_path(
     page: &ThreadsPage,
     filter_cwd: Option<&Path>,
@@ -0,0 +1,146 @@
+//! Enforces the `[run_budget]` limits (`codex exec --max-turns` and friends)
+//! across a whole run.
+//!
+//! One [`RunBudget`] is shared by every thread of a user session, so model
+//! requests, tool calls and tokens of sub-agents count against the same limits
+//! as the thread that spawned them. The timeout runs from the first turn of the
+//! run. A budget never interrupts a model request that already finished the
+//! turn: it only stops the agent from doing more work.
+
+use std::sync::OnceLock;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+use std::time::Instant;
+
+use codex_protocol::protocol::CodexErrorInfo;
+use codex_protocol::protocol::ErrorEvent;
+use codex_protocol::protocol::RunBudgetKind;
+use codex_protocol::protocol::TokenUsage;
+
+use crate::config::types::RunBudgetConfig;
+
+#[derive(Debug, Default)]
+pub(crate) struct RunBudget {
+    limits: RunBudgetConfig,
+    started_at: OnceLock<Instant>,
+    sampling_requests: AtomicU64,
+    tool_calls: AtomicU64,
+    output_tokens: AtomicU64,
+    total_tokens: AtomicU64,
+}
+
+impl RunBudget {
+    pub(crate) fn new(limits: RunBudgetConfig) -> Self {
+        Self {
+            limits,
+            ..Self::default()
+        }
+    }
+
+    /// Starts the run's clock when its first turn starts.
+    pub(crate) fn start(&self) {
+        self.started_at.get_or_init(Instant::now);
+    }
+
+    /// When the `timeout` budget runs out, once the run has started.
+    pub(crate) fn deadline(&self) -> Option<Instant> {
+        self.limits
+            .timeout
+            .zip(self.started_at.get())
+            .map(|(timeout, started_at)| *started_at + timeout)
+    }
+
+    pub(crate) fn record_sampling_request(&self) {
+        self.sampling_requests.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Adds the token usage of one model response.
+    pub(crate) fn record_token_usage(&self, usage: &TokenUsage) {
+        let tokens = |count: i64| u64::try_from(count).unwrap_or(0);
+        self.output_tokens
+            .fetch_add(tokens(usage.output_tokens), Ordering::Relaxed);
+        self.total_tokens
+            .fetch_add(tokens(usage.total_tokens), Ordering::Relaxed);
+    }
+
+    /// Counts a tool call and returns `false` when it would exceed
+    /// `max_tool_calls`, in which case the call must not run.
+    pub(crate) fn try_start_tool_call(&self) -> bool {
+        let previous = self.tool_calls.fetch_add(1, Ordering::Relaxed);
+        self.limits
+            .max_tool_calls
+            .is_none_or(|max_tool_calls| previous < max_tool_calls)
+    }
+
+    /// Returns the first budget that prevents another model request.
+    pub(crate) fn exhausted(&self) -> Option<RunBudgetKind> {
+        self.exhausted_after(self.started_at.get().map(Instant::elapsed))
+    }
+
+    fn exhausted_after(&self, elapsed: Option<Duration>) -> Option<RunBudgetKind> {
+        let limits = &self.limits;
+        if limits
+            .timeout
+            .zip(elapsed)
+            .is_some_and(|(timeout, elapsed)| elapsed >= timeout)
+        {
+            return Some(RunBudgetKind::Timeout);
+        }
+        if limits
+            .max_tool_calls
+            .is_some_and(|max_tool_calls| self.tool_calls.load(Ordering::Relaxed) > max_tool_calls)
+        {
+            return Some(RunBudgetKind::MaxToolCalls);
+        }
+        if limits
+            .max_turns
+            .is_some_and(|max_turns| self.sampling_requests.load(Ordering::Relaxed) >= max_turns)
+        {
+            return Some(RunBudgetKind::MaxTurns);
+        }
+        if limits.max_output_tokens.is_some_and(|max_output_tokens| {
+            self.output_tokens.load(Ordering::Relaxed) >= max_output_tokens
+        }) {
+            return Some(RunBudgetKind::MaxOutputTokens);
+        }
+        if limits.max_total_tokens.is_some_and(|max_total_tokens| {
+            self.total_tokens.load(Ordering::Relaxed) >= max_total_tokens
+        }) {
+            return Some(RunBudgetKind::MaxTotalTokens);
+        }
+        None
+    }
+
+    /// Error reported when `kind` stops the turn.
+    pub(crate) fn exhausted_error(&self, kind: RunBudgetKind) -> ErrorEvent {
+        let limits = &self.limits;
+        let limit = match kind {
+            RunBudgetKind::MaxTurns => format!("{} model requests", limits.max_turns.unwrap_or(0)),
+            RunBudgetKind::MaxOutputTokens => {
+                format!("{} output tokens", limits.max_output_tokens.unwrap_or(0))
+            }
+            RunBudgetKind::MaxTotalTokens => {
+                format!("{} total tokens", limits.max_total_tokens.unwrap_or(0))
+            }
+            RunBudgetKind::Timeout => format!(
+                "{}s of wall-clock time",
+                limits.timeout.unwrap_or_default().as_secs()
+            ),
+            RunBudgetKind::MaxToolCalls => {
+                format!("{} tool calls", limits.max_tool_calls.unwrap_or(0))
+            }
+        };
+        ErrorEvent {
+            message: format!(
+                "Run budget `{}` exhausted after {limit}; stopping the turn.",
+                kind.as_str()
+            ),
+            codex_error_info: Some(CodexErrorInfo::RunBudgetExceeded { budget: kind }),
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "run_budget_tests.rs"]
+mod tests;
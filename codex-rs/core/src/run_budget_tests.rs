@@ -0,0 +1,103 @@
+use super::*;
+use pretty_assertions::assert_eq;
+
+fn usage(output_tokens: i64, total_tokens: i64) -> TokenUsage {
+    TokenUsage {
+        output_tokens,
+        total_tokens,
+        ..TokenUsage::default()
+    }
+}
+
+#[test]
+fn unlimited_budget_is_never_exhausted() {
+    let budget = RunBudget::new(RunBudgetConfig::default());
+    budget.start();
+    budget.record_sampling_request();
+    budget.record_token_usage(&usage(1_000_000, 1_000_000));
+    assert!(budget.try_start_tool_call());
+    assert_eq!(budget.deadline(), None);
+    assert_eq!(
+        budget.exhausted_after(Some(Duration::from_secs(86_400))),
+        None
+    );
+}
+
+#[test]
+fn turn_and_tool_call_budgets_count_across_the_run() {
+    let budget = RunBudget::new(RunBudgetConfig {
+        max_turns: Some(2),
+        max_tool_calls: Some(1),
+        ..RunBudgetConfig::default()
+    });
+
+    budget.record_sampling_request();
+    assert!(budget.try_start_tool_call());
+    assert_eq!(budget.exhausted(), None);
+
+    assert!(!budget.try_start_tool_call());
+    assert_eq!(budget.exhausted(), Some(RunBudgetKind::MaxToolCalls));
+
+    let budget = RunBudget::new(RunBudgetConfig {
+        max_turns: Some(2),
+        ..RunBudgetConfig::default()
+    });
+    budget.record_sampling_request();
+    budget.record_sampling_request();
+    assert_eq!(budget.exhausted(), Some(RunBudgetKind::MaxTurns));
+}
+
+#[test]
+fn token_budgets_add_up_every_response() {
+    let budget = RunBudget::new(RunBudgetConfig {
+        max_output_tokens: Some(100),
+        max_total_tokens: Some(1_000),
+        ..RunBudgetConfig::default()
+    });
+
+    budget.record_token_usage(&usage(50, 400));
+    assert_eq!(budget.exhausted(), None);
+    budget.record_token_usage(&usage(49, 500));
+    assert_eq!(budget.exhausted(), None);
+    budget.record_token_usage(&usage(0, 100));
+    assert_eq!(budget.exhausted(), Some(RunBudgetKind::MaxTotalTokens));
+
+    let budget = RunBudget::new(RunBudgetConfig {
+        max_output_tokens: Some(100),
+        ..RunBudgetConfig::default()
+    });
+    budget.record_token_usage(&usage(60, 60));
+    budget.record_token_usage(&usage(40, 40));
+    assert_eq!(budget.exhausted(), Some(RunBudgetKind::MaxOutputTokens));
+}
+
+#[test]
+fn timeout_runs_from_the_first_turn_and_reports_budget_in_error_event() {
+    let budget = RunBudget::new(RunBudgetConfig {
+        timeout: Some(Duration::from_secs(30)),
+        ..RunBudgetConfig::default()
+    });
+    assert_eq!(budget.deadline(), None);
+    budget.start();
+    let deadline = budget.deadline();
+    budget.start();
+    assert_eq!(budget.deadline(), deadline);
+    assert!(deadline.is_some());
+
+    assert_eq!(budget.exhausted_after(Some(Duration::from_secs(29))), None);
+    assert_eq!(
+        budget.exhausted_after(Some(Duration::from_secs(30))),
+        Some(RunBudgetKind::Timeout)
+    );
+    let error = budget.exhausted_error(RunBudgetKind::Timeout);
+    assert_eq!(
+        error.message,
+        "Run budget `timeout` exhausted after 30s of wall-clock time; stopping the turn."
+    );
+    assert_eq!(
+        error.codex_error_info,
+        Some(CodexErrorInfo::RunBudgetExceeded {
+            budget: RunBudgetKind::Timeout,
+        })
+    );
+}
@@ -14,6 +14,7 @@ use crate::exec::SandboxType;
 use crate::exec::StdoutStream;
 use crate::exec::execute_exec_request;
 use crate::landlock::allow_network_for_proxy;
+use crate::landlock::create_linux_resource_limits_command_args;
 use crate::landlock::create_linux_sandbox_command_args_for_policies;
 use crate::protocol::SandboxPolicy;
 #[cfg(target_os = "macos")]
@@ -36,7 +37,10 @@ use codex_protocol::permissions::FileSystemPath;
 use codex_protocol::permissions::FileSystemSandboxEntry;
 use codex_protocol::permissions::FileSystemSandboxKind;
 use codex_protocol::permissions::FileSystemSandboxPolicy;
+use codex_protocol::permissions::GlobDenyMatches;
 use codex_protocol::permissions::NetworkSandboxPolicy;
+use codex_protocol::permissions::OverlayWorkspaceLayers;
+use codex_protocol::permissions::ResourceLimits;
 use codex_protocol::protocol::NetworkAccess;
 use codex_protocol::protocol::ReadOnlyAccess;
 use codex_utils_absolute_path::AbsolutePathBuf;
@@ -95,6 +99,14 @@ pub(crate) struct SandboxTransformRequest<'a> {
     pub macos_seatbelt_profile_extensions: Option<&'a MacOsSeatbeltProfileExtensions>,
     pub codex_linux_sandbox_exe: Option<&'a PathBuf>,
     pub use_legacy_landlock: bool,
+    /// Per-command limits, currently only enforced by the Linux sandbox helper.
+    pub resource_limits: ResourceLimits,
+    /// Copy-on-write layers for the workspace, only honored by the Linux
+    /// bubblewrap pipeline.
+    pub overlay_workspace: Option<&'a OverlayWorkspaceLayers>,
+    /// Glob deny matches the turn already expanded, so the Linux sandbox helper
+    /// does not walk the filesystem again for every command.
+    pub glob_deny_matches: Option<&'a GlobDenyMatches>,
     pub windows_sandbox_level: WindowsSandboxLevel,
 }
 
@@ -592,6 +604,9 @@ impl SandboxManager {
             macos_seatbelt_profile_extensions,
             codex_linux_sandbox_exe,
             use_legacy_landlock,
+            resource_limits,
+            overlay_workspace,
+            glob_deny_matches,
             windows_sandbox_level,
         } = request;
         #[cfg(not(target_os = "macos"))]
@@ -642,7 +657,24 @@ impl SandboxManager {
         command.append(&mut spec.args);
 
         let (command, sandbox_env, arg0_override) = match sandbox {
-            SandboxType::None => (command, HashMap::new(), None),
+            // Unsandboxed commands still get their resource limits from the
+            // Linux helper when it is available.
+            SandboxType::None => match codex_linux_sandbox_exe {
+                Some(exe) if !resource_limits.is_empty() => {
+                    let mut full_command = vec![exe.to_string_lossy().to_string()];
+                    full_command.extend(create_linux_resource_limits_command_args(
+                        command,
+                        resource_limits,
+                        sandbox_policy_cwd,
+                    ));
+                    (
+                        full_command,
+                        HashMap::new(),
+                        Some("codex-linux-sandbox".to_string()),
+                    )
+                }
+                _ => (command, HashMap::new(), None),
+            },
             #[cfg(target_os = "macos")]
             SandboxType::MacosSeatbelt => {
                 let mut seatbelt_env = HashMap::new();
@@ -672,6 +704,9 @@ impl SandboxManager {
                     &effective_policy,
                     &effective_file_system_policy,
                     effective_network_policy,
+                    resource_limits,
+                    overlay_workspace,
+                    glob_deny_matches,
                     sandbox_policy_cwd,
                     use_legacy_landlock,
                     allow_proxy_network,
@@ -31,10 +31,12 @@ use codex_protocol::permissions::FileSystemSandboxEntry;
 use codex_protocol::permissions::FileSystemSandboxPolicy;
 use codex_protocol::permissions::FileSystemSpecialPath;
 use codex_protocol::permissions::NetworkSandboxPolicy;
+use codex_protocol::permissions::ResourceLimits;
 use codex_utils_absolute_path::AbsolutePathBuf;
 use dunce::canonicalize;
 use pretty_assertions::assert_eq;
 use std::collections::HashMap;
+use std::path::PathBuf;
 use tempfile::TempDir;
 
 #[test]
@@ -168,6 +170,9 @@ fn transform_preserves_unrestricted_file_system_policy_for_restricted_network()
             macos_seatbelt_profile_extensions: None,
             codex_linux_sandbox_exe: None,
             use_legacy_landlock: false,
+            resource_limits: ResourceLimits::default(),
+            overlay_workspace: None,
+            glob_deny_matches: None,
             windows_sandbox_level: WindowsSandboxLevel::Disabled,
         })
         .expect("transform");
@@ -182,6 +187,65 @@ fn transform_preserves_unrestricted_file_system_policy_for_restricted_network()
     );
 }
 
+#[test]
+fn transform_keeps_resource_limits_for_unsandboxed_commands() {
+    let manager = SandboxManager::new();
+    let cwd = std::env::current_dir().expect("current dir");
+    let helper = PathBuf::from("/usr/bin/codex-linux-sandbox");
+    let resource_limits = ResourceLimits {
+        memory_max_bytes: Some(1024 * 1024 * 1024),
+        ..ResourceLimits::default()
+    };
+    let transform = |codex_linux_sandbox_exe: Option<&PathBuf>| {
+        manager
+            .transform(super::SandboxTransformRequest {
+                spec: super::CommandSpec {
+                    program: "true".to_string(),
+                    args: Vec::new(),
+                    cwd: cwd.clone(),
+                    env: HashMap::new(),
+                    expiration: crate::exec::ExecExpiration::DefaultTimeout,
+                    sandbox_permissions: super::SandboxPermissions::UseDefault,
+                    additional_permissions: None,
+                    justification: None,
+                },
+                policy: &SandboxPolicy::DangerFullAccess,
+                file_system_policy: &FileSystemSandboxPolicy::unrestricted(),
+                network_policy: NetworkSandboxPolicy::Enabled,
+                sandbox: SandboxType::None,
+                enforce_managed_network: false,
+                network: None,
+                sandbox_policy_cwd: cwd.as_path(),
+                #[cfg(target_os = "macos")]
+                macos_seatbelt_profile_extensions: None,
+                codex_linux_sandbox_exe,
+                use_legacy_landlock: false,
+                resource_limits,
+                overlay_workspace: None,
+                glob_deny_matches: None,
+                windows_sandbox_level: WindowsSandboxLevel::Disabled,
+            })
+            .expect("transform")
+    };
+
+    let exec_request = transform(Some(&helper));
+    assert_eq!(
+        exec_request.command.first(),
+        Some(&helper.display().to_string())
+    );
+    assert!(
+        exec_request
+            .command
+            .contains(&"--resource-limits".to_string())
+    );
+    assert_eq!(exec_request.command.last(), Some(&"true".to_string()));
+    assert_eq!(exec_request.arg0.as_deref(), Some("codex-linux-sandbox"));
+
+    let exec_request = transform(None);
+    assert_eq!(exec_request.command, vec!["true".to_string()]);
+    assert_eq!(exec_request.arg0, None);
+}
+
 #[test]
 fn normalize_additional_permissions_preserves_network() {
     let temp_dir = TempDir::new().expect("create temp dir");
@@ -501,6 +565,9 @@ fn transform_additional_permissions_enable_network_for_external_sandbox() {
             macos_seatbelt_profile_extensions: None,
             codex_linux_sandbox_exe: None,
             use_legacy_landlock: false,
+            resource_limits: ResourceLimits::default(),
+            overlay_workspace: None,
+            glob_deny_matches: None,
             windows_sandbox_level: WindowsSandboxLevel::Disabled,
         })
         .expect("transform");
@@ -573,6 +640,9 @@ fn transform_additional_permissions_preserves_denied_entries() {
             macos_seatbelt_profile_extensions: None,
             codex_linux_sandbox_exe: None,
             use_legacy_landlock: false,
+            resource_limits: ResourceLimits::default(),
+            overlay_workspace: None,
+            glob_deny_matches: None,
             windows_sandbox_level: WindowsSandboxLevel::Disabled,
         })
         .expect("transform");
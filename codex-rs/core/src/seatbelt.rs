@@ -399,6 +399,107 @@ fn build_seatbelt_access_policy(
     }
 }
 
+/// Denies reads and writes of paths matching the policy's glob entries.
+fn build_seatbelt_glob_deny_policy(patterns: &[String]) -> String {
+    if patterns.is_empty() {
+        return String::new();
+    }
+
+    let regexes = patterns
+        .iter()
+        .map(|pattern| format!("(regex #\"{}\")", glob_to_seatbelt_regex(pattern)))
+        .collect::<Vec<_>>()
+        .join("\n");
+    format!("; deny filesystem glob entries\n(deny file-read* file-write*\n{regexes}\n)")
+}
+
+/// Translates a glob into the regex dialect accepted by Seatbelt.
+///
+/// `*` and `?` stay within one path component, `**/` spans any number of
+/// directories, `{a,b}` becomes an alternation and `[...]` classes are kept.
+/// The regex also matches everything below a matching path, mirroring how the
+/// other backends mask a whole directory.
+fn glob_to_seatbelt_regex(pattern: &str) -> String {
+    let chars: Vec<char> = pattern.chars().collect();
+    let mut regex = String::from("^");
+    let mut alternation_depth = 0usize;
+    let mut index = 0;
+    while index < chars.len() {
+        let ch = chars[index];
+        index += 1;
+        match ch {
+            '*' if chars.get(index) == Some(&'*') => {
+                index += 1;
+                if chars.get(index) == Some(&'/') {
+                    index += 1;
+                    regex.push_str("(.*/)?");
+                } else {
+                    regex.push_str(".*");
+                }
+            }
+            '*' => regex.push_str("[^/]*"),
+            '?' => regex.push_str("[^/]"),
+            '[' => {
+                let class_start = index;
+                let mut class_end = class_start;
+                if matches!(chars.get(class_end), Some('!' | '^')) {
+                    class_end += 1;
+                }
+                if chars.get(class_end) == Some(&']') {
+                    class_end += 1;
+                }
+                while class_end < chars.len() && chars[class_end] != ']' {
+                    class_end += 1;
+                }
+                if class_end >= chars.len() {
+                    regex.push_str("\\[");
+                    continue;
+                }
+                regex.push('[');
+                let mut class = &chars[class_start..class_end];
+                if let Some(('!' | '^', rest)) = class.split_first() {
+                    regex.push('^');
+                    class = rest;
+                }
+                regex.extend(class.iter().filter(|ch| **ch != '"'));
+                regex.push(']');
+                index = class_end + 1;
+            }
+            '{' => {
+                alternation_depth += 1;
+                regex.push('(');
+            }
+            '}' if alternation_depth > 0 => {
+                alternation_depth -= 1;
+                regex.push(')');
+            }
+            ',' if alternation_depth > 0 => regex.push('|'),
+            '\\' => {
+                if let Some(escaped) = chars.get(index) {
+                    index += 1;
+                    push_seatbelt_regex_literal(&mut regex, *escaped);
+                }
+            }
+            _ => push_seatbelt_regex_literal(&mut regex, ch),
+        }
+    }
+    regex.push_str("(/.*)?$");
+    regex
+}
+
+fn push_seatbelt_regex_literal(regex: &mut String, ch: char) {
+    match ch {
+        // The policy embeds the regex in a `#"..."` literal, which cannot
+        // contain a quote, so match any character there instead.
+        '"' => regex.push('.'),
+        '.' | '+' | '(' | ')' | '|' | '^' | '$' | '{' | '}' | '[' | ']' | '*' | '?' | '\\' => {
+            regex.push('\\');
+            regex.push(ch);
+        }
+        _ => regex.push(ch),
+    }
+}
+
 #[cfg_attr(not(test), allow(dead_code))]
 pub(crate) fn create_seatbelt_command_args_with_extensions(
     command: Vec<String>,
@@ -511,6 +612,17 @@ pub(crate) fn create_seatbelt_command_args_for_policies_with_extensions(
             }
         };
 
+    // Seatbelt matches canonical paths, so anchor relative globs at the
+    // canonical cwd (for example `/private/var/...` rather than `/var/...`).
+    let glob_deny_cwd = normalize_path_for_sandbox(sandbox_policy_cwd)
+        .map(AbsolutePathBuf::into_path_buf)
+        .unwrap_or_else(|| sandbox_policy_cwd.to_path_buf());
+    let glob_deny_policy = build_seatbelt_glob_deny_policy(
+        file_system_sandbox_policy
+            .glob_denies_with_cwd(&glob_deny_cwd)
+            .patterns(),
+    );
+
     let proxy = proxy_policy_inputs(network);
     let network_policy =
         dynamic_network_policy_for_network(network_sandbox_policy, enforce_managed_network, &proxy);
@@ -535,6 +647,10 @@ pub(crate) fn create_seatbelt_command_args_for_policies_with_extensions(
     if !seatbelt_extensions.policy.is_empty() {
         policy_sections.push(seatbelt_extensions.policy.clone());
     }
+    // Later rules win in Seatbelt, so the glob denies go last.
+    if !glob_deny_policy.is_empty() {
+        policy_sections.push(glob_deny_policy);
+    }
 
     let full_policy = policy_sections.join("\n");
 
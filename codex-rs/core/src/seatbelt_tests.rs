@@ -5,6 +5,7 @@ use super::create_seatbelt_command_args;
 use super::create_seatbelt_command_args_for_policies_with_extensions;
 use super::create_seatbelt_command_args_with_extensions;
 use super::dynamic_network_policy;
+use super::glob_to_seatbelt_regex;
 use super::macos_dir_params;
 use super::normalize_path_for_sandbox;
 use super::unix_socket_dir_params;
@@ -146,6 +147,62 @@ fn explicit_unreadable_paths_are_excluded_from_full_disk_read_and_write_access()
     );
 }
 
+#[test]
+fn glob_to_seatbelt_regex_translates_wildcards() {
+    assert_eq!(
+        glob_to_seatbelt_regex("/repo/**/.env*"),
+        r"^/repo/(.*/)?\.env[^/]*(/.*)?$"
+    );
+    assert_eq!(
+        glob_to_seatbelt_regex("/repo/**/*.{pem,key}"),
+        r"^/repo/(.*/)?[^/]*\.(pem|key)(/.*)?$"
+    );
+    assert_eq!(
+        glob_to_seatbelt_regex("/repo[[]1[]]/secrets/**"),
+        r"^/repo[[]1[]]/secrets/.*(/.*)?$"
+    );
+    assert_eq!(
+        glob_to_seatbelt_regex("/srv/id_?sa[!.]"),
+        r"^/srv/id_[^/]sa[^.](/.*)?$"
+    );
+}
+
+#[test]
+fn glob_entries_become_trailing_deny_rules() {
+    let file_system_policy = FileSystemSandboxPolicy::restricted(vec![
+        FileSystemSandboxEntry {
+            path: FileSystemPath::Special {
+                value: crate::protocol::FileSystemSpecialPath::Root,
+            },
+            access: FileSystemAccessMode::Write,
+        },
+        FileSystemSandboxEntry {
+            path: FileSystemPath::Glob {
+                pattern: "/tmp/codex-glob/**/*.pem".to_string(),
+            },
+            access: FileSystemAccessMode::None,
+        },
+    ]);
+
+    let args = create_seatbelt_command_args_for_policies_with_extensions(
+        vec!["/bin/true".to_string()],
+        &file_system_policy,
+        NetworkSandboxPolicy::Restricted,
+        Path::new("/"),
+        false,
+        None,
+        None,
+    );
+
+    let policy = seatbelt_policy_arg(&args);
+    assert!(
+        policy.ends_with(
+            "(deny file-read* file-write*\n(regex #\"^/tmp/codex-glob/(.*/)?[^/]*\\.pem(/.*)?$\")\n)"
+        ),
+        "expected trailing glob deny in policy:\n{policy}"
+    );
+}
+
 #[test]
 fn explicit_unreadable_paths_are_excluded_from_readable_roots() {
     let root = absolute_path("/tmp/codex-readable");
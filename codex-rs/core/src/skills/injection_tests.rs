@@ -13,6 +13,7 @@ fn make_skill(name: &str, path: &str) -> SkillMetadata {
         policy: None,
         permission_profile: None,
         managed_network_override: None,
+        resource_limits: None,
         path_to_skills_md: PathBuf::from(path),
         scope: codex_protocol::protocol::SkillScope::User,
     }
@@ -20,6 +20,7 @@ fn test_skill_metadata(skill_doc_path: PathBuf) -> SkillMetadata {
         policy: None,
         permission_profile: None,
         managed_network_override: None,
+        resource_limits: None,
         path_to_skills_md: skill_doc_path,
         scope: codex_protocol::protocol::SkillScope::User,
     }
@@ -1,3 +1,4 @@
+use crate::config::ResourceLimitsToml;
 use crate::config_loader::ConfigLayerStack;
 use crate::config_loader::ConfigLayerStackOrdering;
 use crate::config_loader::default_project_root_markers;
@@ -18,6 +19,7 @@ use codex_protocol::models::FileSystemPermissions;
 use codex_protocol::models::MacOsSeatbeltProfileExtensions;
 use codex_protocol::models::NetworkPermissions;
 use codex_protocol::models::PermissionProfile;
+use codex_protocol::permissions::ResourceLimits;
 use codex_protocol::protocol::SkillScope;
 use codex_utils_absolute_path::AbsolutePathBufGuard;
 use dirs::home_dir;
@@ -72,6 +74,7 @@ struct LoadedSkillMetadata {
     policy: Option<SkillPolicy>,
     permission_profile: Option<PermissionProfile>,
     managed_network_override: Option<SkillManagedNetworkOverride>,
+    resource_limits: Option<ResourceLimits>,
 }
 
 #[derive(Debug, Default, Deserialize, PartialEq, Eq)]
@@ -82,6 +85,8 @@ struct SkillPermissionProfile {
     file_system: Option<FileSystemPermissions>,
     #[serde(default)]
     macos: Option<MacOsSeatbeltProfileExtensions>,
+    #[serde(default)]
+    resources: Option<ResourceLimitsToml>,
 }
 
 #[derive(Debug, Default, Deserialize, PartialEq, Eq)]
@@ -551,6 +556,7 @@ fn parse_skill_file(path: &Path, scope: SkillScope) -> Result<SkillMetadata, Ski
         policy,
         permission_profile,
         managed_network_override,
+        resource_limits,
     } = load_skill_metadata(path);
 
     validate_len(&name, MAX_NAME_LEN, "name")?;
@@ -574,6 +580,7 @@ fn parse_skill_file(path: &Path, scope: SkillScope) -> Result<SkillMetadata, Ski
         policy,
         permission_profile,
         managed_network_override,
+        resource_limits,
         path_to_skills_md: resolved_path,
         scope,
     })
@@ -639,13 +646,15 @@ fn load_skill_metadata(skill_path: &Path) -> LoadedSkillMetadata {
         policy,
         permissions,
     } = parsed;
-    let (permission_profile, managed_network_override) = normalize_permissions(permissions);
+    let (permission_profile, managed_network_override, resource_limits) =
+        normalize_permissions(permissions);
     LoadedSkillMetadata {
         interface: resolve_interface(interface, skill_dir),
         dependencies: resolve_dependencies(dependencies),
         policy: resolve_policy(policy),
         permission_profile,
         managed_network_override,
+        resource_limits,
     }
 }
 
@@ -654,10 +663,21 @@ fn normalize_permissions(
 ) -> (
     Option<PermissionProfile>,
     Option<SkillManagedNetworkOverride>,
+    Option<ResourceLimits>,
 ) {
     let Some(permissions) = permissions else {
-        return (None, None);
+        return (None, None, None);
     };
+    let resource_limits = permissions
+        .resources
+        .and_then(|resources| match resources.to_resource_limits() {
+            Ok(resource_limits) => Some(resource_limits),
+            Err(error) => {
+                tracing::warn!("ignoring skill resource limits: {error}");
+                None
+            }
+        })
+        .filter(|resource_limits| !resource_limits.is_empty());
     let managed_network_override = permissions
         .network
         .as_ref()
@@ -682,6 +702,7 @@ fn normalize_permissions(
     (
         (!permission_profile.is_empty()).then_some(permission_profile),
         managed_network_override,
+        resource_limits,
     )
 }
 
@@ -240,6 +240,7 @@ fn loads_skills_from_home_agents_dir_for_user_scope() -> anyhow::Result<()> {
             policy: None,
             permission_profile: None,
             managed_network_override: None,
+            resource_limits: None,
             path_to_skills_md: normalized(&skill_path),
             scope: SkillScope::User,
         }]
@@ -392,6 +393,7 @@ async fn loads_skill_dependencies_metadata_from_yaml() {
             policy: None,
             permission_profile: None,
             managed_network_override: None,
+            resource_limits: None,
             path_to_skills_md: normalized(&skill_path),
             scope: SkillScope::User,
         }]
@@ -449,6 +451,7 @@ interface:
             policy: None,
             permission_profile: None,
             managed_network_override: None,
+            resource_limits: None,
             path_to_skills_md: normalized(skill_path.as_path()),
             scope: SkillScope::User,
         }]
@@ -601,7 +604,7 @@ permissions: {}
 
 #[test]
 fn normalize_permissions_splits_managed_network_overrides() {
-    let (permission_profile, managed_network_override) =
+    let (permission_profile, managed_network_override, _) =
         normalize_permissions(Some(SkillPermissionProfile {
             network: Some(SkillNetworkPermissions {
                 enabled: Some(true),
@@ -610,6 +613,7 @@ fn normalize_permissions_splits_managed_network_overrides() {
             }),
             file_system: None,
             macos: None,
+            resources: None,
         }));
 
     assert_eq!(
@@ -633,7 +637,7 @@ fn normalize_permissions_splits_managed_network_overrides() {
 
 #[test]
 fn normalize_permissions_preserves_network_gate_separately_from_overrides() {
-    let (permission_profile, managed_network_override) =
+    let (permission_profile, managed_network_override, _) =
         normalize_permissions(Some(SkillPermissionProfile {
             network: Some(SkillNetworkPermissions {
                 enabled: Some(false),
@@ -642,6 +646,7 @@ fn normalize_permissions_preserves_network_gate_separately_from_overrides() {
             }),
             file_system: None,
             macos: None,
+            resources: None,
         }));
 
     assert_eq!(
@@ -663,6 +668,47 @@ fn normalize_permissions_preserves_network_gate_separately_from_overrides() {
     );
 }
 
+#[test]
+fn normalize_permissions_converts_resource_limits() {
+    let (permission_profile, managed_network_override, resource_limits) =
+        normalize_permissions(Some(SkillPermissionProfile {
+            network: None,
+            file_system: None,
+            macos: None,
+            resources: Some(ResourceLimitsToml {
+                memory_max_mb: Some(512),
+                pids_max: Some(32),
+                ..Default::default()
+            }),
+        }));
+
+    assert_eq!(permission_profile, None);
+    assert_eq!(managed_network_override, None);
+    assert_eq!(
+        resource_limits,
+        Some(ResourceLimits {
+            memory_max_bytes: Some(512 * 1024 * 1024),
+            pids_max: Some(32),
+            ..Default::default()
+        })
+    );
+}
+
+#[test]
+fn normalize_permissions_ignores_invalid_resource_limits() {
+    let (_, _, resource_limits) = normalize_permissions(Some(SkillPermissionProfile {
+        network: None,
+        file_system: None,
+        macos: None,
+        resources: Some(ResourceLimitsToml {
+            wall_time_seconds: Some(0),
+            ..Default::default()
+        }),
+    }));
+
+    assert_eq!(resource_limits, None);
+}
+
 #[test]
 fn skill_metadata_parses_macos_permissions_yaml() {
     let parsed = serde_yaml::from_str::<SkillMetadataFile>(
@@ -695,6 +741,7 @@ permissions:
                 macos_reminders: false,
                 macos_contacts: MacOsContactsPermission::None,
             }),
+            resources: None,
         })
     );
 }
@@ -724,6 +771,7 @@ permissions:
                 macos_reminders: true,
                 macos_contacts: MacOsContactsPermission::None,
             }),
+            resources: None,
         })
     );
 }
@@ -753,6 +801,7 @@ permissions:
             }),
             file_system: None,
             macos: None,
+            resources: None,
         })
     );
 }
@@ -901,6 +950,7 @@ async fn accepts_icon_paths_under_assets_dir() {
             policy: None,
             permission_profile: None,
             managed_network_override: None,
+            resource_limits: None,
             path_to_skills_md: normalized(&skill_path),
             scope: SkillScope::User,
         }]
@@ -943,6 +993,7 @@ async fn ignores_invalid_brand_color() {
             policy: None,
             permission_profile: None,
             managed_network_override: None,
+            resource_limits: None,
             path_to_skills_md: normalized(&skill_path),
             scope: SkillScope::User,
         }]
@@ -998,6 +1049,7 @@ async fn ignores_default_prompt_over_max_length() {
             policy: None,
             permission_profile: None,
             managed_network_override: None,
+            resource_limits: None,
             path_to_skills_md: normalized(&skill_path),
             scope: SkillScope::User,
         }]
@@ -1041,6 +1093,7 @@ async fn drops_interface_when_icons_are_invalid() {
             policy: None,
             permission_profile: None,
             managed_network_override: None,
+            resource_limits: None,
             path_to_skills_md: normalized(&skill_path),
             scope: SkillScope::User,
         }]
@@ -1087,6 +1140,7 @@ async fn loads_skills_via_symlinked_subdir_for_user_scope() {
             policy: None,
             permission_profile: None,
             managed_network_override: None,
+            resource_limits: None,
             path_to_skills_md: normalized(&shared_skill_path),
             scope: SkillScope::User,
         }]
@@ -1148,6 +1202,7 @@ async fn does_not_loop_on_symlink_cycle_for_user_scope() {
             policy: None,
             permission_profile: None,
             managed_network_override: None,
+            resource_limits: None,
             path_to_skills_md: normalized(&skill_path),
             scope: SkillScope::User,
         }]
@@ -1186,6 +1241,7 @@ fn loads_skills_via_symlinked_subdir_for_admin_scope() {
             policy: None,
             permission_profile: None,
             managed_network_override: None,
+            resource_limits: None,
             path_to_skills_md: normalized(&shared_skill_path),
             scope: SkillScope::Admin,
         }]
@@ -1227,6 +1283,7 @@ async fn loads_skills_via_symlinked_subdir_for_repo_scope() {
             policy: None,
             permission_profile: None,
             managed_network_override: None,
+            resource_limits: None,
             path_to_skills_md: normalized(&linked_skill_path),
             scope: SkillScope::Repo,
         }]
@@ -1296,6 +1353,7 @@ async fn respects_max_scan_depth_for_user_scope() {
             policy: None,
             permission_profile: None,
             managed_network_override: None,
+            resource_limits: None,
             path_to_skills_md: normalized(&within_depth_path),
             scope: SkillScope::User,
         }]
@@ -1325,6 +1383,7 @@ async fn loads_valid_skill() {
             policy: None,
             permission_profile: None,
             managed_network_override: None,
+            resource_limits: None,
             path_to_skills_md: normalized(&skill_path),
             scope: SkillScope::User,
         }]
@@ -1359,6 +1418,7 @@ async fn falls_back_to_directory_name_when_skill_name_is_missing() {
             policy: None,
             permission_profile: None,
             managed_network_override: None,
+            resource_limits: None,
             path_to_skills_md: normalized(&skill_path),
             scope: SkillScope::User,
         }]
@@ -1402,6 +1462,7 @@ async fn namespaces_plugin_skills_using_plugin_name() {
             policy: None,
             permission_profile: None,
             managed_network_override: None,
+            resource_limits: None,
             path_to_skills_md: normalized(&skill_path),
             scope: SkillScope::User,
         }]
@@ -1435,6 +1496,7 @@ async fn loads_short_description_from_metadata() {
             policy: None,
             permission_profile: None,
             managed_network_override: None,
+            resource_limits: None,
             path_to_skills_md: normalized(&skill_path),
             scope: SkillScope::User,
         }]
@@ -1549,6 +1611,7 @@ async fn loads_skills_from_repo_root() {
             policy: None,
             permission_profile: None,
             managed_network_override: None,
+            resource_limits: None,
             path_to_skills_md: normalized(&skill_path),
             scope: SkillScope::Repo,
         }]
@@ -1586,6 +1649,7 @@ async fn loads_skills_from_agents_dir_without_codex_dir() {
             policy: None,
             permission_profile: None,
             managed_network_override: None,
+            resource_limits: None,
             path_to_skills_md: normalized(&skill_path),
             scope: SkillScope::Repo,
         }]
@@ -1641,6 +1705,7 @@ async fn loads_skills_from_all_codex_dirs_under_project_root() {
                 policy: None,
                 permission_profile: None,
                 managed_network_override: None,
+                resource_limits: None,
                 path_to_skills_md: normalized(&nested_skill_path),
                 scope: SkillScope::Repo,
             },
@@ -1653,6 +1718,7 @@ async fn loads_skills_from_all_codex_dirs_under_project_root() {
                 policy: None,
                 permission_profile: None,
                 managed_network_override: None,
+                resource_limits: None,
                 path_to_skills_md: normalized(&root_skill_path),
                 scope: SkillScope::Repo,
             },
@@ -1694,6 +1760,7 @@ async fn loads_skills_from_codex_dir_when_not_git_repo() {
             policy: None,
             permission_profile: None,
             managed_network_override: None,
+            resource_limits: None,
             path_to_skills_md: normalized(&skill_path),
             scope: SkillScope::Repo,
         }]
@@ -1733,6 +1800,7 @@ async fn deduplicates_by_path_preferring_first_root() {
             policy: None,
             permission_profile: None,
             managed_network_override: None,
+            resource_limits: None,
             path_to_skills_md: normalized(&skill_path),
             scope: SkillScope::Repo,
         }]
@@ -1776,6 +1844,7 @@ async fn keeps_duplicate_names_from_repo_and_user() {
                 policy: None,
                 permission_profile: None,
                 managed_network_override: None,
+                resource_limits: None,
                 path_to_skills_md: normalized(&repo_skill_path),
                 scope: SkillScope::Repo,
             },
@@ -1788,6 +1857,7 @@ async fn keeps_duplicate_names_from_repo_and_user() {
                 policy: None,
                 permission_profile: None,
                 managed_network_override: None,
+                resource_limits: None,
                 path_to_skills_md: normalized(&user_skill_path),
                 scope: SkillScope::User,
             },
@@ -1853,6 +1923,7 @@ async fn keeps_duplicate_names_from_nested_codex_dirs() {
                 policy: None,
                 permission_profile: None,
                 managed_network_override: None,
+                resource_limits: None,
                 path_to_skills_md: first_path,
                 scope: SkillScope::Repo,
             },
@@ -1865,6 +1936,7 @@ async fn keeps_duplicate_names_from_nested_codex_dirs() {
                 policy: None,
                 permission_profile: None,
                 managed_network_override: None,
+                resource_limits: None,
                 path_to_skills_md: second_path,
                 scope: SkillScope::Repo,
             },
@@ -1938,6 +2010,7 @@ async fn loads_skills_when_cwd_is_file_in_repo() {
             policy: None,
             permission_profile: None,
             managed_network_override: None,
+            resource_limits: None,
             path_to_skills_md: normalized(&skill_path),
             scope: SkillScope::Repo,
         }]
@@ -1998,6 +2071,7 @@ async fn loads_skills_from_system_cache_when_present() {
             policy: None,
             permission_profile: None,
             managed_network_override: None,
+            resource_limits: None,
             path_to_skills_md: normalized(&skill_path),
             scope: SkillScope::System,
         }]
@@ -4,6 +4,7 @@ use std::path::PathBuf;
 use std::sync::Arc;
 
 use codex_protocol::models::PermissionProfile;
+use codex_protocol::permissions::ResourceLimits;
 use codex_protocol::protocol::SkillScope;
 use serde::Deserialize;
 
@@ -29,6 +30,9 @@ pub struct SkillMetadata {
     pub policy: Option<SkillPolicy>,
     pub permission_profile: Option<PermissionProfile>,
     pub managed_network_override: Option<SkillManagedNetworkOverride>,
+    /// Resource limits applied to this skill's scripts on Linux, on top of
+    /// the turn's own limits.
+    pub resource_limits: Option<ResourceLimits>,
     /// Path to the SKILLS.md file that declares this skill.
     pub path_to_skills_md: PathBuf,
     pub scope: SkillScope,
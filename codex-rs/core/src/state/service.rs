@@ -5,6 +5,7 @@ use crate::AuthManager;
 use crate::RolloutRecorder;
 use crate::agent::AgentControl;
 use crate::analytics_client::AnalyticsEventsClient;
+use crate::background_services::BackgroundServices;
 use crate::client::ModelClient;
 use crate::config::StartedNetworkProxy;
 use crate::exec_policy::ExecPolicyManager;
@@ -12,9 +13,14 @@ use crate::file_watcher::FileWatcher;
 use crate::mcp::McpManager;
 use crate::mcp_connection_manager::McpConnectionManager;
 use crate::models_manager::manager::ModelsManager;
+use crate::overlay_workspace::OverlayWorkspace;
 use crate::plugins::PluginsManager;
+use crate::repo_index_updater::RepoIndexUpdater;
+use crate::run_budget::RunBudget;
 use crate::skills::SkillsManager;
 use crate::state_db::StateDbHandle;
+use crate::tool_output_store::ToolOutputStore;
+use crate::tool_secrets::ToolSecrets;
 use crate::tools::code_mode::CodeModeService;
 use crate::tools::network_approval::NetworkApprovalService;
 use crate::tools::runtimes::ExecveSessionApproval;
@@ -54,11 +60,21 @@ pub(crate) struct SessionServices {
     pub(crate) plugins_manager: Arc<PluginsManager>,
     pub(crate) mcp_manager: Arc<McpManager>,
     pub(crate) file_watcher: Arc<FileWatcher>,
+    pub(crate) repo_index_updater: RepoIndexUpdater,
+    pub(crate) tool_secrets: ToolSecrets,
     pub(crate) agent_control: AgentControl,
+    /// `[run_budget]` shared with the rest of the user session's threads.
+    pub(crate) run_budget: Arc<RunBudget>,
     pub(crate) network_proxy: Option<StartedNetworkProxy>,
     pub(crate) network_approval: Arc<NetworkApprovalService>,
     pub(crate) state_db: Option<StateDbHandle>,
     /// Session-scoped model client shared across turns.
     pub(crate) model_client: ModelClient,
     pub(crate) code_mode_service: CodeModeService,
+    pub(crate) overlay_workspace: Option<Arc<OverlayWorkspace>>,
+    /// Set when the `spill_tool_output` feature is enabled.
+    pub(crate) tool_output_store: Option<ToolOutputStore>,
+    /// This thread's services, shared with the thread manager so they outlive
+    /// the session.
+    pub(crate) background_services: Arc<BackgroundServices>,
 }
@@ -3,6 +3,7 @@ use crate::path_utils::normalize_for_path_comparison;
 use crate::rollout::list::Cursor;
 use crate::rollout::list::ThreadSortKey;
 use crate::rollout::metadata;
+use crate::tool_output_store::remove_thread_tool_outputs;
 use chrono::DateTime;
 use chrono::NaiveDateTime;
 use chrono::Timelike;
@@ -13,16 +14,34 @@ use codex_protocol::protocol::RolloutItem;
 use codex_protocol::protocol::SessionSource;
 pub use codex_state::LogEntry;
 use codex_state::ThreadMetadataBuilder;
+use futures::FutureExt;
+use futures::future::BoxFuture;
+use futures::future::Shared;
 use serde_json::Value;
+use std::collections::HashMap;
 use std::path::Path;
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::sync::LazyLock;
+use std::sync::Mutex;
+use std::time::Duration;
 use tracing::warn;
 use uuid::Uuid;
 
 /// Core-facing handle to the SQLite-backed state runtime.
 pub type StateDbHandle = Arc<codex_state::StateRuntime>;
 
+/// Search index syncs in flight, keyed by state DB home, so repeated searches
+/// wait on the running sync instead of stacking up new ones.
+static SEARCH_INDEX_SYNCS: LazyLock<Mutex<HashMap<PathBuf, SearchIndexSync>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+type SearchIndexSync = Shared<BoxFuture<'static, ()>>;
+
+/// How long a search waits for the index to catch up before querying what is
+/// already indexed.
+const SEARCH_INDEX_SYNC_WAIT: Duration = Duration::from_secs(10);
+
 /// Initialize the state runtime for thread state persistence and backfill checks. To only be used
 /// inside `core`. The initialization should not be done anywhere else.
 pub(crate) async fn init(config: &Config) -> Option<StateDbHandle> {
@@ -58,6 +77,8 @@ pub(crate) async fn init(config: &Config) -> Option<StateDbHandle> {
             metadata::backfill_sessions(runtime_for_backfill.as_ref(), &config).await;
         });
     }
+    // Start catching up now so the first search has less to wait for.
+    drop(spawn_thread_search_index_sync(runtime.clone()));
     Some(runtime)
 }
 
@@ -255,7 +276,7 @@ pub async fn list_threads_db(
                         item.rollout_path.display()
                     );
                     warn!("state db discrepancy during list_threads_db: stale_db_path_dropped");
-                    let _ = ctx.delete_thread(item.id).await;
+                    delete_stale_thread(ctx, codex_home, item.id).await;
                 }
             }
             page.items = valid_items;
@@ -268,6 +289,157 @@ pub async fn list_threads_db(
     }
 }
 
+/// Full-text search thread content in SQLite.
+///
+/// First brings the index up to date with threads updated since the last
+/// sync, waiting up to `SEARCH_INDEX_SYNC_WAIT`. A sync that takes longer
+/// keeps running in the background, and the search covers what is indexed so
+/// far.
+pub async fn search_threads_db(
+    context: Option<&StateDbHandle>,
+    codex_home: &Path,
+    query: &str,
+    limit: usize,
+    allowed_sources: &[SessionSource],
+    model_providers: Option<&[String]>,
+    archived: bool,
+) -> Option<Vec<codex_state::ThreadSearchHit>> {
+    let ctx = context?;
+    let sync = spawn_thread_search_index_sync(Arc::clone(ctx));
+    if tokio::time::timeout(SEARCH_INDEX_SYNC_WAIT, sync)
+        .await
+        .is_err()
+    {
+        warn!("state db search index is still syncing; results may be incomplete");
+    }
+
+    let allowed_sources: Vec<String> = allowed_sources
+        .iter()
+        .map(|value| match serde_json::to_value(value) {
+            Ok(Value::String(s)) => s,
+            Ok(other) => other.to_string(),
+            Err(_) => String::new(),
+        })
+        .collect();
+    match ctx
+        .search_threads(
+            query,
+            limit,
+            allowed_sources.as_slice(),
+            model_providers,
+            archived,
+        )
+        .await
+    {
+        Ok(hits) => {
+            let mut valid_hits = Vec::with_capacity(hits.len());
+            for hit in hits {
+                if tokio::fs::try_exists(&hit.metadata.rollout_path)
+                    .await
+                    .unwrap_or(false)
+                {
+                    valid_hits.push(hit);
+                } else {
+                    warn!(
+                        "state db search_threads returned stale rollout path for thread {}: {}",
+                        hit.metadata.id,
+                        hit.metadata.rollout_path.display()
+                    );
+                    delete_stale_thread(ctx, codex_home, hit.metadata.id).await;
+                }
+            }
+            Some(valid_hits)
+        }
+        Err(err) => {
+            warn!("state db search_threads failed: {err}");
+            None
+        }
+    }
+}
+
+/// Forgets a thread whose rollout file is gone, along with the tool outputs
+/// saved for it.
+async fn delete_stale_thread(
+    ctx: &codex_state::StateRuntime,
+    codex_home: &Path,
+    thread_id: ThreadId,
+) {
+    let _ = ctx.delete_thread(thread_id).await;
+    if let Err(err) = remove_thread_tool_outputs(codex_home, thread_id).await {
+        warn!("failed to remove saved tool outputs for thread {thread_id}: {err}");
+    }
+}
+
+/// Re-extracts search documents in the background for every thread updated
+/// since it was last indexed, and returns a future that resolves once the
+/// sync is done. Joins the sync of the same database if one is already
+/// running.
+fn spawn_thread_search_index_sync(ctx: StateDbHandle) -> SearchIndexSync {
+    let codex_home = ctx.codex_home().to_path_buf();
+    let mut syncs = SEARCH_INDEX_SYNCS
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    if let Some(sync) = syncs.get(&codex_home) {
+        return sync.clone();
+    }
+    let key = codex_home.clone();
+    let handle = tokio::spawn(async move {
+        sync_thread_search_index(ctx.as_ref()).await;
+        SEARCH_INDEX_SYNCS
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .remove(&key);
+    });
+    let sync = async move {
+        let _ = handle.await;
+    }
+    .boxed()
+    .shared();
+    syncs.insert(codex_home, sync.clone());
+    sync
+}
+
+async fn sync_thread_search_index(ctx: &codex_state::StateRuntime) {
+    let targets = match ctx.list_threads_needing_search_index().await {
+        Ok(targets) => targets,
+        Err(err) => {
+            warn!("state db list_threads_needing_search_index failed: {err}");
+            return;
+        }
+    };
+    for target in targets {
+        index_thread_search_documents(ctx, target.id, &target.rollout_path, target.updated_at)
+            .await;
+    }
+}
+
+/// Re-extracts the search documents of one thread from its rollout.
+async fn index_thread_search_documents(
+    ctx: &codex_state::StateRuntime,
+    thread_id: ThreadId,
+    rollout_path: &Path,
+    updated_at: DateTime<Utc>,
+) {
+    // Unreadable rollouts are recorded with no documents so they are not retried on
+    // every sync; a later update to the thread makes them eligible again.
+    let documents = match crate::rollout::RolloutRecorder::load_rollout_items(rollout_path).await {
+        Ok((items, _, _)) => codex_state::thread_search_documents(&items),
+        Err(err) => {
+            warn!(
+                "state db search index failed to read rollout {}: {err}",
+                rollout_path.display()
+            );
+            Vec::new()
+        }
+    };
+    if let Err(err) = ctx
+        .replace_thread_search_documents(thread_id, updated_at, &documents)
+        .await
+    {
+        warn!("state db replace_thread_search_documents failed for thread {thread_id}: {err}");
+    }
+}
+
 /// Look up the rollout path for a thread id using SQLite.
 pub async fn find_rollout_path_by_id(
     context: Option<&codex_state::StateRuntime>,
@@ -388,6 +560,9 @@ pub async fn reconcile_rollout(
         );
         return;
     }
+    // Threads found on disk become searchable as soon as they are repaired,
+    // rather than waiting for the next background sync.
+    index_thread_search_documents(ctx, metadata.id, rollout_path, metadata.updated_at).await;
     if let Err(err) = ctx
         .set_thread_memory_mode(metadata.id, memory_mode.as_str())
         .await
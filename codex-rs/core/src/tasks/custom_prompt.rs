@@ -0,0 +1,205 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use codex_async_utils::OrCancelExt;
+use codex_protocol::protocol::ExpandCustomPromptResponseEvent;
+use codex_protocol::user_input::TextElement;
+use codex_protocol::user_input::UserInput;
+use tokio_util::sync::CancellationToken;
+use uuid::Uuid;
+
+use super::SessionTask;
+use super::SessionTaskContext;
+use super::UserShellCommandMode;
+use crate::codex::Session;
+use crate::codex::TurnContext;
+use crate::custom_prompts::replace_shell_commands;
+use crate::custom_prompts::shell_commands;
+use crate::exec_env::create_env;
+use crate::exec_policy::ExecApprovalRequest;
+use crate::protocol::EventMsg;
+use crate::protocol::TurnStartedEvent;
+use crate::sandboxing::SandboxPermissions;
+use crate::state::TaskKind;
+use crate::tools::orchestrator::ToolOrchestrator;
+use crate::tools::runtimes::shell::ShellRequest;
+use crate::tools::runtimes::shell::ShellRuntime;
+use crate::tools::sandboxing::ToolCtx;
+use crate::tools::sandboxing::ToolError;
+
+const PROMPT_SHELL_TIMEOUT_MS: u64 = 60 * 1000;
+
+/// Runs the `{{shell: cmd}}` directives of an expanded custom prompt.
+#[derive(Clone)]
+pub(crate) struct CustomPromptShellTask {
+    request_id: String,
+    text: String,
+    text_elements: Vec<TextElement>,
+}
+
+impl CustomPromptShellTask {
+    pub(crate) fn new(request_id: String, text: String, text_elements: Vec<TextElement>) -> Self {
+        Self {
+            request_id,
+            text,
+            text_elements,
+        }
+    }
+}
+
+#[async_trait]
+impl SessionTask for CustomPromptShellTask {
+    fn kind(&self) -> TaskKind {
+        TaskKind::Regular
+    }
+
+    fn span_name(&self) -> &'static str {
+        "session_task.custom_prompt_shell"
+    }
+
+    async fn run(
+        self: Arc<Self>,
+        session: Arc<SessionTaskContext>,
+        turn_context: Arc<TurnContext>,
+        _input: Vec<UserInput>,
+        cancellation_token: CancellationToken,
+    ) -> Option<String> {
+        expand_custom_prompt_shell(
+            session.clone_session(),
+            turn_context,
+            self.request_id.clone(),
+            self.text.clone(),
+            self.text_elements.clone(),
+            cancellation_token,
+            UserShellCommandMode::StandaloneTurn,
+        )
+        .await;
+        None
+    }
+}
+
+/// Replaces each `{{shell: cmd}}` in `text` with the command's output, moving
+/// `text_elements` along, and reports the result under `request_id` with
+/// `EventMsg::ExpandCustomPromptResponse`. Every command goes through exec
+/// policy like a model-issued one: forbidden commands fail the expansion and
+/// the others may need the user's approval.
+pub(crate) async fn expand_custom_prompt_shell(
+    session: Arc<Session>,
+    turn_context: Arc<TurnContext>,
+    request_id: String,
+    text: String,
+    text_elements: Vec<TextElement>,
+    cancellation_token: CancellationToken,
+    mode: UserShellCommandMode,
+) {
+    if mode == UserShellCommandMode::StandaloneTurn {
+        let event = EventMsg::TurnStarted(TurnStartedEvent {
+            turn_id: turn_context.sub_id.clone(),
+            model_context_window: turn_context.model_context_window(),
+            collaboration_mode_kind: turn_context.collaboration_mode.mode,
+        });
+        session.send_event(turn_context.as_ref(), event).await;
+    }
+
+    let mut outputs = Vec::new();
+    let mut result = Ok(());
+    for command in shell_commands(&text) {
+        let output = run_prompt_shell_command(&session, &turn_context, &command)
+            .or_cancel(&cancellation_token)
+            .await
+            .unwrap_or_else(|_| Err(format!("`{command}` was cancelled")));
+        match output {
+            Ok(output) => outputs.push(output),
+            Err(err) => {
+                result = Err(err);
+                break;
+            }
+        }
+    }
+    let (result, text_elements) = match result {
+        Ok(()) => {
+            let (text, text_elements) = replace_shell_commands(&text, &text_elements, outputs);
+            (Ok(text), text_elements)
+        }
+        Err(err) => (Err(err), Vec::new()),
+    };
+
+    session
+        .send_event(
+            turn_context.as_ref(),
+            EventMsg::ExpandCustomPromptResponse(ExpandCustomPromptResponseEvent {
+                id: request_id,
+                result,
+                text_elements,
+            }),
+        )
+        .await;
+}
+
+/// Runs one directive the way the model's shell tool would: through the tool
+/// orchestrator, so session approvals are cached, exec policy amendments can be
+/// offered, and sandbox denials may be retried with the user's approval.
+async fn run_prompt_shell_command(
+    session: &Arc<Session>,
+    turn_context: &Arc<TurnContext>,
+    command: &str,
+) -> Result<String, String> {
+    let argv = session.user_shell().derive_exec_args(command, true);
+    let exec_approval_requirement = session
+        .services
+        .exec_policy
+        .create_exec_approval_requirement_for_command(ExecApprovalRequest {
+            command: &argv,
+            approval_policy: turn_context.approval_policy.value(),
+            sandbox_policy: turn_context.sandbox_policy.get(),
+            file_system_sandbox_policy: &turn_context.file_system_sandbox_policy,
+            sandbox_permissions: SandboxPermissions::UseDefault,
+            prefix_rule: None,
+        })
+        .await;
+    let req = ShellRequest {
+        command: argv,
+        cwd: turn_context.cwd.clone(),
+        timeout_ms: Some(PROMPT_SHELL_TIMEOUT_MS),
+        env: create_env(
+            &turn_context.shell_environment_policy,
+            Some(session.conversation_id),
+        ),
+        explicit_env_overrides: turn_context.shell_environment_policy.r#set.clone(),
+        network: turn_context.network.clone(),
+        sandbox_permissions: SandboxPermissions::UseDefault,
+        additional_permissions: None,
+        #[cfg(unix)]
+        additional_permissions_preapproved: false,
+        justification: None,
+        exec_approval_requirement,
+    };
+    let tool_ctx = ToolCtx {
+        session: Arc::clone(session),
+        turn: Arc::clone(turn_context),
+        call_id: Uuid::new_v4().to_string(),
+        tool_name: "custom_prompt_shell".to_string(),
+    };
+    let output = ToolOrchestrator::new()
+        .run(
+            &mut ShellRuntime::new(),
+            &req,
+            &tool_ctx,
+            turn_context,
+            turn_context.approval_policy.value(),
+        )
+        .await
+        .map_err(|err| match err {
+            ToolError::Rejected(reason) => format!("`{command}` was rejected: {reason}"),
+            ToolError::Codex(err) => format!("`{command}` failed: {err}"),
+        })?
+        .output;
+    if output.exit_code != 0 {
+        return Err(format!(
+            "`{command}` exited with code {}: {}",
+            output.exit_code,
+            output.stderr.text.trim()
+        ));
+    }
+    Ok(output.stdout.text.trim_end().to_string())
+}
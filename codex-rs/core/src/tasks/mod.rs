@@ -1,4 +1,5 @@
 mod compact;
+mod custom_prompt;
 mod ghost_snapshot;
 mod regular;
 mod repo_index_refresh;
@@ -27,6 +28,7 @@ use crate::contextual_user_message::TURN_ABORTED_OPEN_TAG;
 use crate::event_mapping::parse_turn_item;
 use crate::models_manager::manager::ModelsManager;
 use crate::protocol::EventMsg;
+use crate::protocol::OverlayWorkspaceChangesEvent;
 use crate::protocol::TokenUsage;
 use crate::protocol::TurnAbortReason;
 use crate::protocol::TurnAbortedEvent;
@@ -48,9 +50,12 @@ use codex_protocol::user_input::UserInput;
 
 use crate::features::Feature;
 pub(crate) use compact::CompactTask;
+pub(crate) use custom_prompt::CustomPromptShellTask;
+pub(crate) use custom_prompt::expand_custom_prompt_shell;
 pub(crate) use ghost_snapshot::GhostSnapshotTask;
 pub(crate) use regular::RegularTask;
 pub(crate) use repo_index_refresh::RepoIndexRefreshTask;
+pub(crate) use repo_index_refresh::RepoToolsConnection;
 pub(crate) use review::ReviewTask;
 pub(crate) use undo::UndoTask;
 pub(crate) use user_shell::UserShellCommandMode;
@@ -362,6 +367,17 @@ impl Session {
                 &[("token_type", "reasoning_output"), tmp_mem],
             );
         }
+        if let Some(overlay_workspace) = turn_context.overlay_workspace.clone() {
+            match crate::overlay_workspace::pending_file_changes(overlay_workspace).await {
+                Ok(changes) if !changes.is_empty() => {
+                    let event =
+                        EventMsg::OverlayWorkspaceChanges(OverlayWorkspaceChangesEvent { changes });
+                    self.send_event(turn_context.as_ref(), event).await;
+                }
+                Ok(_) => {}
+                Err(err) => warn!("failed to read copy-on-write workspace changes: {err}"),
+            }
+        }
         let event = EventMsg::TurnComplete(TurnCompleteEvent {
             turn_id: turn_context.sub_id.clone(),
             last_agent_message,
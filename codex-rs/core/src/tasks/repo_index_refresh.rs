@@ -18,6 +18,7 @@ use super::SessionTask;
 use super::SessionTaskContext;
 use crate::SandboxState;
 use crate::codex::TurnContext;
+use crate::config::Config;
 use crate::mcp::CODEX_REPO_TOOLS_MCP_SERVER_NAME;
 use crate::mcp::ToolPluginProvenance;
 use crate::mcp::auth::compute_auth_statuses;
@@ -95,54 +96,14 @@ impl SessionTask for RepoIndexRefreshTask {
             .await;
 
         let start = Instant::now();
-        let result: Result<CallToolResult, String> = if let Some(repo_tools_server_config) =
-            repo_tools_mcp_server_config()
-        {
-            let server_name = CODEX_REPO_TOOLS_MCP_SERVER_NAME.to_string();
-            let server_config = HashMap::from([(server_name.clone(), repo_tools_server_config)]);
-            let auth_statuses = compute_auth_statuses(
-                server_config.iter(),
-                turn_context.config.mcp_oauth_credentials_store_mode,
-            )
-            .await;
-            let sandbox_state = SandboxState {
-                sandbox_policy: turn_context.sandbox_policy.get().clone(),
-                codex_linux_sandbox_exe: turn_context.codex_linux_sandbox_exe.clone(),
-                sandbox_cwd: turn_context.cwd.clone(),
-                use_legacy_landlock: turn_context.features.use_legacy_landlock(),
-            };
-            let (tx_event, rx_event) = unbounded();
-            drop(rx_event);
-
-            let (manager, cancel_token) = McpConnectionManager::new(
-                &server_config,
-                turn_context.config.mcp_oauth_credentials_store_mode,
-                auth_statuses,
-                &turn_context.config.permissions.approval_policy,
-                tx_event,
-                sandbox_state,
-                turn_context.config.codex_home.clone(),
-                codex_apps_tools_cache_key(None),
-                ToolPluginProvenance::default(),
-            )
-            .await;
-            let result = manager
-                .call_tool(
-                    CODEX_REPO_TOOLS_MCP_SERVER_NAME,
-                    "repo_index_refresh",
-                    Some(arguments),
-                )
-                .await
-                .map_err(|err| format!("tool call error: {err:#}"));
-            cancel_token.cancel();
-            result
-        } else {
-            Err(concat!(
-                "tool call error: could not resolve a local Codex MCP launcher; ",
-                "tried `codex-mcp-server` and `codex mcp-server`."
-            )
-            .to_string())
+        let sandbox_state = SandboxState {
+            sandbox_policy: turn_context.sandbox_policy.get().clone(),
+            codex_linux_sandbox_exe: turn_context.codex_linux_sandbox_exe.clone(),
+            sandbox_cwd: turn_context.cwd.clone(),
+            use_legacy_landlock: turn_context.features.use_legacy_landlock(),
         };
+        let result =
+            call_repo_index_refresh_tool(&turn_context.config, sandbox_state, arguments).await;
 
         let status = if result.is_ok() { "ok" } else { "error" };
         turn_context
@@ -163,3 +124,84 @@ impl SessionTask for RepoIndexRefreshTask {
         None
     }
 }
+
+/// Calls `repo_index_refresh` on a short-lived local repo tools MCP server.
+async fn call_repo_index_refresh_tool(
+    config: &Config,
+    sandbox_state: SandboxState,
+    arguments: serde_json::Value,
+) -> Result<CallToolResult, String> {
+    RepoToolsConnection::start(config, sandbox_state)
+        .await?
+        .call_repo_index_refresh(arguments)
+        .await
+}
+
+/// A local repo tools MCP server that stays up across `repo_index_refresh`
+/// calls. The server is shut down when the connection is dropped.
+pub(crate) struct RepoToolsConnection {
+    manager: McpConnectionManager,
+    cancel_token: CancellationToken,
+}
+
+impl RepoToolsConnection {
+    pub(crate) async fn start(
+        config: &Config,
+        sandbox_state: SandboxState,
+    ) -> Result<Self, String> {
+        let Some(repo_tools_server_config) = repo_tools_mcp_server_config() else {
+            return Err(concat!(
+                "tool call error: could not resolve a local Codex MCP launcher; ",
+                "tried `codex-mcp-server` and `codex mcp-server`."
+            )
+            .to_string());
+        };
+
+        let server_name = CODEX_REPO_TOOLS_MCP_SERVER_NAME.to_string();
+        let server_config = HashMap::from([(server_name, repo_tools_server_config)]);
+        let auth_statuses = compute_auth_statuses(
+            server_config.iter(),
+            config.mcp_oauth_credentials_store_mode,
+        )
+        .await;
+        let (tx_event, rx_event) = unbounded();
+        drop(rx_event);
+
+        let (manager, cancel_token) = McpConnectionManager::new(
+            &server_config,
+            config.mcp_oauth_credentials_store_mode,
+            auth_statuses,
+            &config.permissions.approval_policy,
+            tx_event,
+            sandbox_state,
+            config.codex_home.clone(),
+            codex_apps_tools_cache_key(None),
+            ToolPluginProvenance::default(),
+        )
+        .await;
+        Ok(Self {
+            manager,
+            cancel_token,
+        })
+    }
+
+    pub(crate) async fn call_repo_index_refresh(
+        &self,
+        arguments: serde_json::Value,
+    ) -> Result<CallToolResult, String> {
+        self.manager
+            .call_tool(
+                CODEX_REPO_TOOLS_MCP_SERVER_NAME,
+                "repo_index_refresh",
+                Some(arguments),
+            )
+            .await
+            .map_err(|err| format!("tool call error: {err:#}"))
+    }
+}
+
+impl Drop for RepoToolsConnection {
+    fn drop(&mut self) {
+        self.cancel_token.cancel();
+    }
+}
@@ -179,6 +179,7 @@ pub(crate) async fn execute_user_shell_command(
         sub_id: turn_context.sub_id.clone(),
         call_id: call_id.clone(),
         tx_event: session.get_tx_event(),
+        secret_scrubber: Default::default(),
     });
 
     let exec_result = execute_exec_request(exec_env, &sandbox_policy, stdout_stream, None)
@@ -2,6 +2,8 @@ use crate::AuthManager;
 use crate::CodexAuth;
 use crate::ModelProviderInfo;
 use crate::agent::AgentControl;
+use crate::background_services::BackgroundServiceError;
+use crate::background_services::BackgroundServiceRegistry;
 use crate::codex::Codex;
 use crate::codex::CodexSpawnArgs;
 use crate::codex::CodexSpawnOk;
@@ -27,6 +29,8 @@ use codex_protocol::ThreadId;
 use codex_protocol::config_types::CollaborationModeMask;
 use codex_protocol::openai_models::ModelPreset;
 use codex_protocol::openai_models::ModelsResponse;
+use codex_protocol::protocol::BackgroundServiceAction;
+use codex_protocol::protocol::BackgroundServiceSummary;
 use codex_protocol::protocol::InitialHistory;
 use codex_protocol::protocol::McpServerRefreshConfig;
 use codex_protocol::protocol::Op;
@@ -104,6 +108,7 @@ fn build_file_watcher(codex_home: PathBuf, skills_manager: Arc<SkillsManager>) -
                     Ok(FileWatcherEvent::SkillsChanged { .. }) => {
                         skills_manager.clear_cache();
                     }
+                    Ok(FileWatcherEvent::RepoFilesChanged { .. }) => {}
                     Err(broadcast::error::RecvError::Closed) => break,
                     Err(broadcast::error::RecvError::Lagged(_)) => continue,
                 }
@@ -156,6 +161,7 @@ pub(crate) struct ThreadManagerState {
     plugins_manager: Arc<PluginsManager>,
     mcp_manager: Arc<McpManager>,
     file_watcher: Arc<FileWatcher>,
+    background_service_registry: Arc<BackgroundServiceRegistry>,
     session_source: SessionSource,
     // Captures submitted ops for testing purpose when test mode is enabled.
     ops_log: Option<SharedCapturedOps>,
@@ -192,6 +198,7 @@ impl ThreadManager {
                 plugins_manager,
                 mcp_manager,
                 file_watcher,
+                background_service_registry: Arc::new(BackgroundServiceRegistry::default()),
                 auth_manager,
                 session_source,
                 ops_log: should_use_test_thread_manager_behavior()
@@ -261,6 +268,7 @@ impl ThreadManager {
                 plugins_manager,
                 mcp_manager,
                 file_watcher,
+                background_service_registry: Arc::new(BackgroundServiceRegistry::default()),
                 auth_manager,
                 session_source: SessionSource::Exec,
                 ops_log: should_use_test_thread_manager_behavior()
@@ -290,6 +298,38 @@ impl ThreadManager {
         self.state.file_watcher.subscribe()
     }
 
+    /// Background services of `thread_id`, including ones left running by a
+    /// thread that is no longer loaded.
+    pub fn list_background_services(&self, thread_id: ThreadId) -> Vec<BackgroundServiceSummary> {
+        self.state
+            .background_service_registry
+            .get(thread_id)
+            .map(|services| services.summaries())
+            .unwrap_or_default()
+    }
+
+    pub fn control_background_service(
+        &self,
+        thread_id: ThreadId,
+        name: &str,
+        action: BackgroundServiceAction,
+    ) -> Result<(), BackgroundServiceError> {
+        let services = self
+            .state
+            .background_service_registry
+            .get(thread_id)
+            .ok_or_else(|| BackgroundServiceError::UnknownService(name.to_string()))?;
+        services.control(name, action)
+    }
+
+    /// Stops and forgets every background service of `thread_id`, e.g. when
+    /// the thread is archived.
+    pub fn stop_background_services(&self, thread_id: ThreadId) {
+        self.state
+            .background_service_registry
+            .remove_thread(thread_id);
+    }
+
     pub fn get_models_manager(&self) -> Arc<ModelsManager> {
         self.state.models_manager.clone()
     }
@@ -696,6 +736,7 @@ impl ThreadManagerState {
             plugins_manager: Arc::clone(&self.plugins_manager),
             mcp_manager: Arc::clone(&self.mcp_manager),
             file_watcher: Arc::clone(&self.file_watcher),
+            background_service_registry: Arc::clone(&self.background_service_registry),
             conversation_history: initial_history,
             session_source,
             agent_control,
@@ -0,0 +1,126 @@
+//! Full copies of tool output that was truncated before reaching the model.
+//!
+//! With the `spill_tool_output` feature, shell output that does not fit the
+//! truncation budget is written to `$CODEX_HOME/tool_outputs/<thread id>/<id>.txt`
+//! and the truncation marker names `<id>`, so the model can page or grep the
+//! rest with `read_tool_output`. The thread's directory is removed when the
+//! thread is archived.
+
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+
+use codex_protocol::ThreadId;
+use rand::Rng;
+use rand::rng;
+use tokio::io::AsyncWriteExt;
+use tracing::warn;
+
+use crate::truncate::TruncationPolicy;
+use crate::truncate::exceeds_budget;
+
+const TOOL_OUTPUTS_DIR: &str = "tool_outputs";
+const SPILLED_OUTPUT_ID_LEN: usize = 8;
+
+#[derive(Debug)]
+pub(crate) struct ToolOutputStore {
+    dir: PathBuf,
+}
+
+impl ToolOutputStore {
+    pub(crate) fn new(codex_home: &Path, thread_id: ThreadId) -> Self {
+        Self {
+            dir: thread_tool_outputs_dir(codex_home, thread_id),
+        }
+    }
+
+    /// Saves `content` when `policy` would truncate it and returns the id the
+    /// truncation marker should name. Failing to save is not fatal: the model
+    /// still gets the truncated output, just without a way to read the rest.
+    pub(crate) async fn spill_if_truncated(
+        &self,
+        content: &str,
+        policy: TruncationPolicy,
+    ) -> Option<String> {
+        if !exceeds_budget(content, policy) {
+            return None;
+        }
+        match self.write(content).await {
+            Ok(id) => Some(id),
+            Err(err) => {
+                warn!("failed to save truncated tool output: {err}");
+                None
+            }
+        }
+    }
+
+    async fn write(&self, content: &str) -> io::Result<String> {
+        tokio::fs::create_dir_all(&self.dir).await?;
+        loop {
+            let id = generate_spilled_output_id();
+            let mut file = match tokio::fs::OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(self.path_for(&id))
+                .await
+            {
+                Ok(file) => file,
+                Err(err) if err.kind() == io::ErrorKind::AlreadyExists => continue,
+                Err(err) => return Err(err),
+            };
+            file.write_all(content.as_bytes()).await?;
+            file.flush().await?;
+            return Ok(id);
+        }
+    }
+
+    /// Reads back the output saved under `id`.
+    pub(crate) async fn read(&self, id: &str) -> io::Result<String> {
+        if !is_valid_spilled_output_id(id) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("`{id}` is not a tool output id"),
+            ));
+        }
+        match tokio::fs::read_to_string(self.path_for(id)).await {
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("no saved tool output with id `{id}`"),
+            )),
+            result => result,
+        }
+    }
+
+    fn path_for(&self, id: &str) -> PathBuf {
+        self.dir.join(format!("{id}.txt"))
+    }
+}
+
+/// Deletes every output saved for `thread_id`. Missing directories are fine.
+pub async fn remove_thread_tool_outputs(codex_home: &Path, thread_id: ThreadId) -> io::Result<()> {
+    match tokio::fs::remove_dir_all(thread_tool_outputs_dir(codex_home, thread_id)).await {
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(()),
+        result => result,
+    }
+}
+
+fn thread_tool_outputs_dir(codex_home: &Path, thread_id: ThreadId) -> PathBuf {
+    codex_home
+        .join(TOOL_OUTPUTS_DIR)
+        .join(thread_id.to_string())
+}
+
+fn generate_spilled_output_id() -> String {
+    let mut rng = rng();
+    (0..SPILLED_OUTPUT_ID_LEN)
+        .map(|_| format!("{:x}", rng.random_range(0..16)))
+        .collect()
+}
+
+fn is_valid_spilled_output_id(id: &str) -> bool {
+    id.len() == SPILLED_OUTPUT_ID_LEN && id.bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+#[cfg(test)]
+#[path = "tool_output_store_tests.rs"]
+mod tests;
@@ -0,0 +1,73 @@
+use super::*;
+use pretty_assertions::assert_eq;
+
+#[tokio::test]
+async fn spills_only_output_over_the_budget() -> anyhow::Result<()> {
+    let codex_home = tempfile::tempdir()?;
+    let store = ToolOutputStore::new(codex_home.path(), ThreadId::new());
+
+    assert_eq!(
+        store
+            .spill_if_truncated("short", TruncationPolicy::Bytes(64))
+            .await,
+        None
+    );
+
+    let content = "line\n".repeat(100);
+    let id = store
+        .spill_if_truncated(&content, TruncationPolicy::Bytes(64))
+        .await
+        .expect("expected the output to be saved");
+    assert_eq!(store.read(&id).await?, content);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn read_rejects_ids_that_could_escape_the_thread_dir() -> anyhow::Result<()> {
+    let codex_home = tempfile::tempdir()?;
+    let store = ToolOutputStore::new(codex_home.path(), ThreadId::new());
+
+    let err = store
+        .read("../../config")
+        .await
+        .expect_err("expected an invalid id");
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+
+    let err = store
+        .read("0123abcd")
+        .await
+        .expect_err("expected a missing output");
+    assert_eq!(err.kind(), io::ErrorKind::NotFound);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn remove_thread_tool_outputs_deletes_only_that_thread() -> anyhow::Result<()> {
+    let codex_home = tempfile::tempdir()?;
+    let archived = ThreadId::new();
+    let other = ThreadId::new();
+    let content = "x".repeat(128);
+    let archived_id = ToolOutputStore::new(codex_home.path(), archived)
+        .spill_if_truncated(&content, TruncationPolicy::Bytes(8))
+        .await
+        .expect("expected the output to be saved");
+    let other_store = ToolOutputStore::new(codex_home.path(), other);
+    let other_id = other_store
+        .spill_if_truncated(&content, TruncationPolicy::Bytes(8))
+        .await
+        .expect("expected the output to be saved");
+
+    remove_thread_tool_outputs(codex_home.path(), archived).await?;
+    remove_thread_tool_outputs(codex_home.path(), archived).await?;
+
+    let err = ToolOutputStore::new(codex_home.path(), archived)
+        .read(&archived_id)
+        .await
+        .expect_err("expected the archived thread's output to be gone");
+    assert_eq!(err.kind(), io::ErrorKind::NotFound);
+    assert_eq!(other_store.read(&other_id).await?, content);
+
+    Ok(())
+}
@@ -0,0 +1,205 @@
+//! Exposes the secrets listed in `[secrets].expose` to shell tool runs as
+//! environment variables, and scrubs their values from tool output before it
+//! is recorded in history or the rollout.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use codex_secrets::SecretName;
+use codex_secrets::SecretScope;
+use codex_secrets::SecretsManager;
+use codex_secrets::environment_id_from_cwd;
+use tracing::warn;
+
+use crate::config::Config;
+use crate::exec::ExecToolCallOutput;
+
+/// Replacement for secret values found in tool output.
+const REDACTED_SECRET: &str = "[REDACTED_SECRET]";
+
+/// Values shorter than this would scrub unrelated output, so they are never
+/// injected.
+const MIN_SCRUBBABLE_SECRET_LEN: usize = 4;
+
+#[derive(Default)]
+pub(crate) struct ToolSecrets {
+    /// `(name, value)` pairs, sorted so longer values are scrubbed first.
+    vars: Vec<(String, String)>,
+}
+
+impl ToolSecrets {
+    /// Resolves `config.secrets.expose` for the environment scope of
+    /// `config.cwd`. Failures are logged and leave the secret unset so a broken
+    /// keyring never prevents a session from starting.
+    pub(crate) async fn load(config: &Config) -> Self {
+        if config.secrets.expose.is_empty() {
+            return Self::default();
+        }
+        let manager = SecretsManager::new(config.codex_home.clone(), config.secrets.backend);
+        let cwd = config.cwd.clone();
+        let names = config.secrets.expose.clone();
+        // Decrypting the secrets file is CPU-bound (scrypt), so keep it off the
+        // async runtime.
+        tokio::task::spawn_blocking(move || Self::resolve(&manager, &cwd, &names))
+            .await
+            .unwrap_or_else(|err| {
+                warn!("failed to resolve exposed secrets: {err}");
+                Self::default()
+            })
+    }
+
+    fn resolve(manager: &SecretsManager, cwd: &Path, names: &[String]) -> Self {
+        let environment_scope = match SecretScope::environment(environment_id_from_cwd(cwd)) {
+            Ok(scope) => Some(scope),
+            Err(err) => {
+                warn!("failed to compute secrets environment scope: {err}");
+                None
+            }
+        };
+
+        let mut vars = Vec::new();
+        for raw_name in names {
+            let name = match SecretName::new(raw_name) {
+                Ok(name) => name,
+                Err(err) => {
+                    warn!("ignoring exposed secret `{raw_name}`: {err}");
+                    continue;
+                }
+            };
+            let value = match lookup(manager, environment_scope.as_ref(), &name) {
+                Ok(Some(value)) => value,
+                Ok(None) => {
+                    warn!("exposed secret {name} is not set; run `codex secrets set {name}`");
+                    continue;
+                }
+                Err(err) => {
+                    warn!("failed to read secret {name}: {err:#}");
+                    continue;
+                }
+            };
+            if value.len() < MIN_SCRUBBABLE_SECRET_LEN {
+                warn!(
+                    "not exposing secret {name}: values shorter than {MIN_SCRUBBABLE_SECRET_LEN} bytes cannot be scrubbed from output"
+                );
+                continue;
+            }
+            vars.push((name.as_str().to_string(), value));
+        }
+        vars.sort_by_key(|(_, value)| std::cmp::Reverse(value.len()));
+        Self { vars }
+    }
+
+    /// Adds the exposed secrets to a tool environment, overriding inherited
+    /// variables of the same name.
+    pub(crate) fn inject(&self, env: &mut HashMap<String, String>) {
+        for (name, value) in &self.vars {
+            env.insert(name.clone(), value.clone());
+        }
+    }
+
+    /// Replaces every exposed secret value in `text`.
+    pub(crate) fn scrub(&self, text: &str) -> String {
+        let mut scrubbed = text.to_string();
+        for (_, value) in &self.vars {
+            if scrubbed.contains(value.as_str()) {
+                scrubbed = scrubbed.replace(value.as_str(), REDACTED_SECRET);
+            }
+        }
+        scrubbed
+    }
+
+    /// Starts scrubbing a stream of output chunks, where a secret value may be
+    /// split across chunks.
+    pub(crate) fn stream_scrubber(&self) -> SecretStreamScrubber {
+        SecretStreamScrubber {
+            values: self
+                .vars
+                .iter()
+                .map(|(_, value)| value.as_bytes().to_vec())
+                .collect(),
+            held: Vec::new(),
+        }
+    }
+
+    pub(crate) fn scrub_exec_output(&self, output: &mut ExecToolCallOutput) {
+        if self.vars.is_empty() {
+            return;
+        }
+        output.stdout.text = self.scrub(&output.stdout.text);
+        output.stderr.text = self.scrub(&output.stderr.text);
+        output.aggregated_output.text = self.scrub(&output.aggregated_output.text);
+    }
+}
+
+/// Scrubs output that arrives in chunks. The tail of a chunk that could be the
+/// start of a secret is held back until the next chunk shows whether it is.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct SecretStreamScrubber {
+    /// Secret values, longest first.
+    values: Vec<Vec<u8>>,
+    held: Vec<u8>,
+}
+
+impl SecretStreamScrubber {
+    /// Returns the scrubbed output that is safe to release after `chunk`.
+    pub(crate) fn push(&mut self, chunk: &[u8]) -> Vec<u8> {
+        if self.values.is_empty() {
+            return chunk.to_vec();
+        }
+        let mut bytes = std::mem::take(&mut self.held);
+        bytes.extend_from_slice(chunk);
+        self.scrub(&bytes, /*stream_ended*/ false)
+    }
+
+    /// Releases whatever is still held back once the stream has ended.
+    pub(crate) fn finish(&mut self) -> Vec<u8> {
+        let bytes = std::mem::take(&mut self.held);
+        self.scrub(&bytes, /*stream_ended*/ true)
+    }
+
+    fn scrub(&mut self, bytes: &[u8], stream_ended: bool) -> Vec<u8> {
+        let mut out = Vec::with_capacity(bytes.len());
+        let mut index = 0;
+        while index < bytes.len() {
+            let rest = &bytes[index..];
+            if !stream_ended
+                && self
+                    .values
+                    .iter()
+                    .any(|value| rest.len() < value.len() && value.starts_with(rest))
+            {
+                self.held = rest.to_vec();
+                break;
+            }
+            match self.values.iter().find(|value| rest.starts_with(value)) {
+                Some(value) => {
+                    out.extend_from_slice(REDACTED_SECRET.as_bytes());
+                    index += value.len();
+                }
+                None => {
+                    out.push(bytes[index]);
+                    index += 1;
+                }
+            }
+        }
+        out
+    }
+}
+
+/// Prefers the environment-scoped value and falls back to the global one.
+fn lookup(
+    manager: &SecretsManager,
+    environment_scope: Option<&SecretScope>,
+    name: &SecretName,
+) -> anyhow::Result<Option<String>> {
+    if let Some(scope) = environment_scope
+        && let Some(value) = manager.get(scope, name)?
+    {
+        return Ok(Some(value));
+    }
+    manager.get(&SecretScope::Global, name)
+}
+
+#[cfg(test)]
+#[path = "tool_secrets_tests.rs"]
+mod tests;
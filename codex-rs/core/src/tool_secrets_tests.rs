@@ -0,0 +1,121 @@
+use super::*;
+use codex_keyring_store::tests::MockKeyringStore;
+use codex_secrets::SecretsBackendKind;
+use pretty_assertions::assert_eq;
+use std::sync::Arc;
+
+fn manager(codex_home: &Path) -> SecretsManager {
+    SecretsManager::new_with_keyring_store(
+        codex_home.to_path_buf(),
+        SecretsBackendKind::Local,
+        Arc::new(MockKeyringStore::default()),
+    )
+}
+
+#[test]
+fn resolve_prefers_environment_scope_and_skips_unusable_secrets() -> anyhow::Result<()> {
+    let codex_home = tempfile::tempdir()?;
+    let cwd = tempfile::tempdir()?;
+    let manager = manager(codex_home.path());
+    let environment = SecretScope::environment(environment_id_from_cwd(cwd.path()))?;
+    manager.set(
+        &SecretScope::Global,
+        &SecretName::new("GITHUB_TOKEN")?,
+        "global-token",
+    )?;
+    manager.set(
+        &environment,
+        &SecretName::new("GITHUB_TOKEN")?,
+        "repo-token-value",
+    )?;
+    manager.set(
+        &SecretScope::Global,
+        &SecretName::new("NPM_TOKEN")?,
+        "npm-value",
+    )?;
+    manager.set(&SecretScope::Global, &SecretName::new("PIN")?, "123")?;
+
+    let secrets = ToolSecrets::resolve(
+        &manager,
+        cwd.path(),
+        &[
+            "NPM_TOKEN".to_string(),
+            "GITHUB_TOKEN".to_string(),
+            "PIN".to_string(),
+            "MISSING".to_string(),
+            "not-a-name".to_string(),
+        ],
+    );
+
+    assert_eq!(
+        secrets.vars,
+        vec![
+            ("GITHUB_TOKEN".to_string(), "repo-token-value".to_string()),
+            ("NPM_TOKEN".to_string(), "npm-value".to_string()),
+        ]
+    );
+    let mut env = HashMap::from([("GITHUB_TOKEN".to_string(), "inherited".to_string())]);
+    secrets.inject(&mut env);
+    assert_eq!(
+        env,
+        HashMap::from([
+            ("GITHUB_TOKEN".to_string(), "repo-token-value".to_string()),
+            ("NPM_TOKEN".to_string(), "npm-value".to_string()),
+        ])
+    );
+    Ok(())
+}
+
+#[test]
+fn scrub_replaces_values_in_text_and_raw_output() {
+    let secrets = ToolSecrets {
+        vars: vec![
+            ("LONG".to_string(), "abcd-efgh".to_string()),
+            ("SHORT".to_string(), "abcd".to_string()),
+        ],
+    };
+
+    assert_eq!(
+        secrets.scrub("token=abcd-efgh other=abcd"),
+        "token=[REDACTED_SECRET] other=[REDACTED_SECRET]"
+    );
+    let mut scrubber = secrets.stream_scrubber();
+    let mut bytes = scrubber.push(b"\xffabcd-efgh\xfe");
+    bytes.extend(scrubber.finish());
+    assert_eq!(bytes, b"\xff[REDACTED_SECRET]\xfe".to_vec());
+}
+
+#[test]
+fn stream_scrubber_catches_values_split_across_chunks() {
+    let secrets = ToolSecrets {
+        vars: vec![
+            ("LONG".to_string(), "abcd-efgh".to_string()),
+            ("SHORT".to_string(), "abcd".to_string()),
+        ],
+    };
+    let mut scrubber = secrets.stream_scrubber();
+
+    let mut output = Vec::new();
+    for chunk in [
+        &b"token=ab"[..],
+        b"cd-e",
+        b"fgh other=ab",
+        b"cd",
+        b" end=abc",
+    ] {
+        output.extend(scrubber.push(chunk));
+    }
+    assert_eq!(
+        String::from_utf8_lossy(&output),
+        "token=[REDACTED_SECRET] other=[REDACTED_SECRET] end="
+    );
+    output.extend(scrubber.finish());
+    assert_eq!(
+        String::from_utf8_lossy(&output),
+        "token=[REDACTED_SECRET] other=[REDACTED_SECRET] end=abc"
+    );
+
+    let mut passthrough = ToolSecrets::default().stream_scrubber();
+    assert_eq!(passthrough.push(b"abc"), b"abc".to_vec());
+    assert_eq!(passthrough.finish(), Vec::<u8>::new());
+}
@@ -5,7 +5,7 @@ use crate::tools::TELEMETRY_PREVIEW_MAX_BYTES;
 use crate::tools::TELEMETRY_PREVIEW_MAX_LINES;
 use crate::tools::TELEMETRY_PREVIEW_TRUNCATION_NOTICE;
 use crate::truncate::TruncationPolicy;
-use crate::truncate::formatted_truncate_text;
+use crate::truncate::formatted_truncate_text_with_spill;
 use crate::turn_diff_tracker::TurnDiffTracker;
 use crate::unified_exec::resolve_max_tokens;
 use codex_protocol::mcp::CallToolResult;
@@ -248,6 +248,8 @@ pub struct ExecCommandToolOutput {
     pub exit_code: Option<i32>,
     pub original_token_count: Option<usize>,
     pub session_command: Option<Vec<String>>,
+    /// Id of the saved copy of `raw_output` when it does not fit `max_output_tokens`.
+    pub spilled_output_id: Option<String>,
 }
 
 impl ToolOutput for ExecCommandToolOutput {
@@ -304,7 +306,11 @@ impl ExecCommandToolOutput {
     pub(crate) fn truncated_output(&self) -> String {
         let text = String::from_utf8_lossy(&self.raw_output).to_string();
         let max_tokens = resolve_max_tokens(self.max_output_tokens);
-        formatted_truncate_text(&text, TruncationPolicy::Tokens(max_tokens))
+        formatted_truncate_text_with_spill(
+            &text,
+            TruncationPolicy::Tokens(max_tokens),
+            self.spilled_output_id.as_deref(),
+        )
     }
 
     fn response_text(&self) -> String {
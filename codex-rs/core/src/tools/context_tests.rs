@@ -246,6 +246,7 @@ fn exec_command_tool_output_formats_truncated_response() {
         exit_code: Some(0),
         original_token_count: Some(10),
         session_command: None,
+        spilled_output_id: None,
     }
     .to_response_item("call-42", &payload);
 
@@ -283,16 +283,30 @@ impl ToolEmitter {
         self.emit(ctx, ToolEventStage::Begin).await;
     }
 
-    fn format_exec_output_for_model(
+    async fn format_exec_output_for_model(
         &self,
         output: &ExecToolCallOutput,
         ctx: ToolEventCtx<'_>,
     ) -> String {
-        match self {
-            Self::Shell { freeform: true, .. } => {
-                super::format_exec_output_for_model_freeform(output, ctx.turn.truncation_policy)
+        let truncation_policy = ctx.turn.truncation_policy;
+        let spilled_output_id = match ctx.session.services.tool_output_store.as_ref() {
+            Some(store) => {
+                let content = super::build_content_with_timeout(output);
+                store.spill_if_truncated(&content, truncation_policy).await
             }
-            _ => super::format_exec_output_for_model_structured(output, ctx.turn.truncation_policy),
+            None => None,
+        };
+        match self {
+            Self::Shell { freeform: true, .. } => super::format_exec_output_for_model_freeform(
+                output,
+                truncation_policy,
+                spilled_output_id.as_deref(),
+            ),
+            _ => super::format_exec_output_for_model_structured(
+                output,
+                truncation_policy,
+                spilled_output_id.as_deref(),
+            ),
         }
     }
 
@@ -301,9 +315,11 @@ impl ToolEmitter {
         ctx: ToolEventCtx<'_>,
         out: Result<ExecToolCallOutput, ToolError>,
     ) -> Result<String, FunctionCallError> {
+        let tool_secrets = &ctx.session.services.tool_secrets;
         let (event, result) = match out {
-            Ok(output) => {
-                let content = self.format_exec_output_for_model(&output, ctx);
+            Ok(mut output) => {
+                tool_secrets.scrub_exec_output(&mut output);
+                let content = self.format_exec_output_for_model(&output, ctx).await;
                 let exit_code = output.exit_code;
                 let event = ToolEventStage::Success(output);
                 let result = if exit_code == 0 {
@@ -313,15 +329,16 @@ impl ToolEmitter {
                 };
                 (event, result)
             }
-            Err(ToolError::Codex(CodexErr::Sandbox(SandboxErr::Timeout { output })))
-            | Err(ToolError::Codex(CodexErr::Sandbox(SandboxErr::Denied { output, .. }))) => {
-                let response = self.format_exec_output_for_model(&output, ctx);
+            Err(ToolError::Codex(CodexErr::Sandbox(SandboxErr::Timeout { mut output })))
+            | Err(ToolError::Codex(CodexErr::Sandbox(SandboxErr::Denied { mut output, .. }))) => {
+                tool_secrets.scrub_exec_output(&mut output);
+                let response = self.format_exec_output_for_model(&output, ctx).await;
                 let event = ToolEventStage::Failure(ToolEventFailure::Output(*output));
                 let result = Err(FunctionCallError::RespondToModel(response));
                 (event, result)
             }
             Err(ToolError::Codex(err)) => {
-                let message = format!("execution error: {err:?}");
+                let message = tool_secrets.scrub(&format!("execution error: {err:?}"));
                 let event = ToolEventStage::Failure(ToolEventFailure::Message(message.clone()));
                 let result = Err(FunctionCallError::RespondToModel(message));
                 (event, result)
@@ -413,8 +430,14 @@ async fn emit_exec_stage(
             )
             .await;
         }
-        ToolEventStage::Success(output)
-        | ToolEventStage::Failure(ToolEventFailure::Output(output)) => {
+        ToolEventStage::Success(mut output)
+        | ToolEventStage::Failure(ToolEventFailure::Output(mut output)) => {
+            // Unified exec emits its end event directly, so scrub here as well
+            // as in `finish`.
+            ctx.session
+                .services
+                .tool_secrets
+                .scrub_exec_output(&mut output);
             let exec_result = ExecCommandResult {
                 stdout: output.stdout.text.clone(),
                 stderr: output.stderr.text.clone(),
@@ -496,6 +519,20 @@ async fn emit_patch_end(
     success: bool,
     status: PatchApplyStatus,
 ) {
+    if success {
+        let touched_paths = changes.iter().flat_map(|(path, change)| {
+            let move_path = match change {
+                FileChange::Update { move_path, .. } => move_path.clone(),
+                FileChange::Add { .. } | FileChange::Delete { .. } => None,
+            };
+            std::iter::once(path.clone()).chain(move_path)
+        });
+        ctx.session
+            .services
+            .repo_index_updater
+            .notify_paths(touched_paths);
+    }
+
     ctx.session
         .send_event(
             ctx.turn,
@@ -170,7 +170,7 @@ impl ToolHandler for ApplyPatchHandler {
         // Avoid building temporary ExecParams/command vectors; derive directly from inputs.
         let cwd = turn.cwd.clone();
         let command = vec!["apply_patch".to_string(), patch_input.clone()];
-        match codex_apply_patch::maybe_parse_apply_patch_verified(&command, &cwd) {
+        match parse_apply_patch_verified(turn.as_ref(), &command, &cwd) {
             codex_apply_patch::MaybeApplyPatchVerified::Body(changes) => {
                 let (file_paths, effective_additional_permissions, file_system_sandbox_policy) =
                     effective_patch_permissions(session.as_ref(), turn.as_ref(), &changes).await;
@@ -257,6 +257,25 @@ impl ToolHandler for ApplyPatchHandler {
     }
 }
 
+/// Verifies the patch against the files sandboxed commands see, which are not
+/// the host files when the turn writes into a copy-on-write workspace.
+fn parse_apply_patch_verified(
+    turn: &TurnContext,
+    command: &[String],
+    cwd: &Path,
+) -> codex_apply_patch::MaybeApplyPatchVerified {
+    match turn.overlay_workspace.as_deref() {
+        Some(overlay_workspace) => {
+            codex_apply_patch::maybe_parse_apply_patch_verified_with_read_path(
+                command,
+                cwd,
+                &|path| overlay_workspace.read_path(path),
+            )
+        }
+        None => codex_apply_patch::maybe_parse_apply_patch_verified(command, cwd),
+    }
+}
+
 #[allow(clippy::too_many_arguments)]
 pub(crate) async fn intercept_apply_patch(
     command: &[String],
@@ -268,7 +287,7 @@ pub(crate) async fn intercept_apply_patch(
     call_id: &str,
     tool_name: &str,
 ) -> Result<Option<FunctionToolOutput>, FunctionCallError> {
-    match codex_apply_patch::maybe_parse_apply_patch_verified(command, cwd) {
+    match parse_apply_patch_verified(turn.as_ref(), command, cwd) {
         codex_apply_patch::MaybeApplyPatchVerified::Body(changes) => {
             session
                 .record_model_warning(
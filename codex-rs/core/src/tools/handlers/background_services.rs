@@ -0,0 +1,283 @@
+use async_trait::async_trait;
+use codex_protocol::protocol::BackgroundServiceHealth;
+use codex_protocol::protocol::BackgroundServiceSource;
+use codex_protocol::protocol::BackgroundServiceStatus;
+use codex_protocol::protocol::BackgroundServiceSummary;
+use serde::Deserialize;
+
+use crate::background_services::ServiceSpec;
+use crate::background_services::is_valid_service_name;
+use crate::codex::Session;
+use crate::codex::TurnContext;
+use crate::config::DEFAULT_SERVICE_HEALTH_CHECK_INTERVAL;
+use crate::config::types::ServiceHealthCheck;
+use crate::config::types::ServiceHealthProbe;
+use crate::config::types::ServiceRestartPolicy;
+use crate::exec_env::create_env;
+use crate::exec_policy::ExecApprovalRequest;
+use crate::function_tool::FunctionCallError;
+use crate::sandboxing::SandboxPermissions;
+use crate::tools::context::FunctionToolOutput;
+use crate::tools::context::ToolInvocation;
+use crate::tools::context::ToolPayload;
+use crate::tools::handlers::parse_arguments;
+use crate::tools::handlers::resolve_workdir_base_path;
+use crate::tools::orchestrator::ToolOrchestrator;
+use crate::tools::registry::ToolHandler;
+use crate::tools::registry::ToolKind;
+use crate::tools::runtimes::service::ServiceRequest;
+use crate::tools::runtimes::service::ServiceRuntime;
+use crate::tools::sandboxing::ToolCtx;
+use crate::tools::sandboxing::ToolError;
+use std::sync::Arc;
+
+pub struct BackgroundServicesHandler;
+
+const DEFAULT_STATUS_LOG_LINES: usize = 50;
+
+/// JSON arguments accepted by the `service_start` tool.
+#[derive(Deserialize)]
+struct ServiceStartArgs {
+    name: String,
+    /// Shell command to run. Omit to start a service that is already defined.
+    #[serde(default)]
+    cmd: Option<String>,
+    #[serde(default)]
+    health_check_port: Option<u16>,
+    #[serde(default)]
+    restart: ServiceRestartPolicy,
+}
+
+#[derive(Deserialize)]
+struct ServiceStopArgs {
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct ServiceStatusArgs {
+    #[serde(default)]
+    name: Option<String>,
+    /// Log lines to include when `name` is set.
+    #[serde(default = "default_status_log_lines")]
+    lines: usize,
+}
+
+fn default_status_log_lines() -> usize {
+    DEFAULT_STATUS_LOG_LINES
+}
+
+#[async_trait]
+impl ToolHandler for BackgroundServicesHandler {
+    type Output = FunctionToolOutput;
+
+    fn kind(&self) -> ToolKind {
+        ToolKind::Function
+    }
+
+    async fn is_mutating(&self, invocation: &ToolInvocation) -> bool {
+        matches!(
+            invocation.tool_name.as_str(),
+            "service_start" | "service_stop"
+        )
+    }
+
+    async fn handle(&self, invocation: ToolInvocation) -> Result<Self::Output, FunctionCallError> {
+        let ToolInvocation {
+            session,
+            turn,
+            call_id,
+            tool_name,
+            payload,
+            ..
+        } = invocation;
+
+        let arguments = match payload {
+            ToolPayload::Function { arguments } => arguments,
+            _ => {
+                return Err(FunctionCallError::RespondToModel(format!(
+                    "{tool_name} handler received unsupported payload"
+                )));
+            }
+        };
+
+        let text = match tool_name.as_str() {
+            "service_start" => start(&session, &turn, call_id, &arguments).await?,
+            "service_stop" => {
+                let ServiceStopArgs { name } = parse_arguments(&arguments)?;
+                session
+                    .services
+                    .background_services
+                    .stop(&name)
+                    .map_err(|err| FunctionCallError::RespondToModel(err.to_string()))?;
+                format!("Stopped service `{name}`.")
+            }
+            "service_status" => {
+                let ServiceStatusArgs { name, lines } = parse_arguments(&arguments)?;
+                status(&session, name.as_deref(), lines)?
+            }
+            other => {
+                return Err(FunctionCallError::RespondToModel(format!(
+                    "unsupported background service tool {other}"
+                )));
+            }
+        };
+        Ok(FunctionToolOutput::from_text(text, Some(true)))
+    }
+}
+
+async fn start(
+    session: &Arc<Session>,
+    turn: &Arc<TurnContext>,
+    call_id: String,
+    arguments: &str,
+) -> Result<String, FunctionCallError> {
+    let ServiceStartArgs {
+        name,
+        cmd,
+        health_check_port,
+        restart,
+    } = parse_arguments(arguments)?;
+    let services = &session.services.background_services;
+
+    let Some(cmd) = cmd else {
+        services
+            .start(&name)
+            .map_err(|err| FunctionCallError::RespondToModel(err.to_string()))?;
+        return Ok(format!("Started service `{name}`."));
+    };
+    if !is_valid_service_name(&name) {
+        return Err(FunctionCallError::RespondToModel(
+            "service names may only contain letters, digits, `-` and `_`".to_string(),
+        ));
+    }
+    if cmd.trim().is_empty() {
+        return Err(FunctionCallError::RespondToModel(
+            "cmd must not be empty".to_string(),
+        ));
+    }
+
+    let cwd = resolve_workdir_base_path(arguments, turn.cwd.as_path())?;
+    let command = session.user_shell().derive_exec_args(&cmd, false);
+    let mut env = create_env(
+        &turn.shell_environment_policy,
+        Some(session.conversation_id),
+    );
+    session.services.tool_secrets.inject(&mut env);
+    let exec_approval_requirement = session
+        .services
+        .exec_policy
+        .create_exec_approval_requirement_for_command(ExecApprovalRequest {
+            command: &command,
+            approval_policy: turn.approval_policy.value(),
+            sandbox_policy: turn.sandbox_policy.get(),
+            file_system_sandbox_policy: &turn.file_system_sandbox_policy,
+            sandbox_permissions: SandboxPermissions::UseDefault,
+            prefix_rule: None,
+        })
+        .await;
+    let req = ServiceRequest {
+        command: command.clone(),
+        cwd,
+        env,
+        exec_approval_requirement,
+    };
+    let tool_ctx = ToolCtx {
+        session: session.clone(),
+        turn: turn.clone(),
+        call_id,
+        tool_name: "service_start".to_string(),
+    };
+    let request = ToolOrchestrator::new()
+        .run(
+            &mut ServiceRuntime,
+            &req,
+            &tool_ctx,
+            turn,
+            turn.approval_policy.value(),
+        )
+        .await
+        .map_err(|err| match err {
+            ToolError::Rejected(reason) => FunctionCallError::RespondToModel(reason),
+            ToolError::Codex(err) => {
+                FunctionCallError::RespondToModel(format!("failed to start service: {err}"))
+            }
+        })?
+        .output;
+
+    let health_check = health_check_port.map(|port| ServiceHealthCheck {
+        probe: ServiceHealthProbe::Port(port),
+        interval: DEFAULT_SERVICE_HEALTH_CHECK_INTERVAL,
+    });
+    let spec = ServiceSpec::from_exec_request(command, request, restart, health_check);
+    services
+        .define(&name, spec, BackgroundServiceSource::Model)
+        .and_then(|()| services.start(&name))
+        .map_err(|err| FunctionCallError::RespondToModel(err.to_string()))?;
+    Ok(format!(
+        "Started service `{name}`. Use service_status to check on it."
+    ))
+}
+
+fn status(
+    session: &Session,
+    name: Option<&str>,
+    lines: usize,
+) -> Result<String, FunctionCallError> {
+    let services = &session.services.background_services;
+    let Some(name) = name else {
+        let summaries = services.summaries();
+        if summaries.is_empty() {
+            return Ok("No background services are defined.".to_string());
+        }
+        return Ok(summaries
+            .iter()
+            .map(summary_line)
+            .collect::<Vec<_>>()
+            .join("\n"));
+    };
+
+    let summary = services
+        .summary(name, lines)
+        .map_err(|err| FunctionCallError::RespondToModel(err.to_string()))?;
+    let mut text = summary_line(&summary);
+    text.push_str(&format!("\ncommand: {}", summary.command.join(" ")));
+    if summary.recent_logs.is_empty() {
+        text.push_str("\nNo output yet.");
+    } else {
+        let logs = summary.recent_logs.join("\n");
+        text.push_str("\nRecent output:\n");
+        text.push_str(&session.services.tool_secrets.scrub(&logs));
+    }
+    Ok(text)
+}
+
+fn summary_line(summary: &BackgroundServiceSummary) -> String {
+    let status = match summary.status {
+        BackgroundServiceStatus::Stopped => "stopped".to_string(),
+        BackgroundServiceStatus::Running => "running".to_string(),
+        BackgroundServiceStatus::Restarting => "restarting".to_string(),
+        BackgroundServiceStatus::Exited => match summary.exit_code {
+            Some(code) => format!("exited with code {code}"),
+            None => "exited".to_string(),
+        },
+    };
+    let mut line = format!("{}: {status}", summary.name);
+    if let Some(health) = summary.health {
+        line.push_str(match health {
+            BackgroundServiceHealth::Pending => ", health check pending",
+            BackgroundServiceHealth::Healthy => ", healthy",
+            BackgroundServiceHealth::Unhealthy => ", unhealthy",
+        });
+    }
+    if summary.restarts > 0 {
+        line.push_str(&format!(", restarted {} times", summary.restarts));
+    }
+    if summary.source == BackgroundServiceSource::Config {
+        line.push_str(" (from config)");
+    }
+    line
+}
+
+#[cfg(test)]
+#[path = "background_services_tests.rs"]
+mod tests;
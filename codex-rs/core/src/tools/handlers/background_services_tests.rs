@@ -0,0 +1,36 @@
+use super::*;
+use pretty_assertions::assert_eq;
+
+fn summary(status: BackgroundServiceStatus) -> BackgroundServiceSummary {
+    BackgroundServiceSummary {
+        name: "db".to_string(),
+        command: vec!["postgres".to_string()],
+        source: BackgroundServiceSource::Model,
+        status,
+        health: None,
+        restarts: 0,
+        exit_code: None,
+        recent_logs: Vec::new(),
+    }
+}
+
+#[test]
+fn summary_line_reports_status_health_and_restarts() {
+    assert_eq!(
+        summary_line(&summary(BackgroundServiceStatus::Stopped)),
+        "db: stopped"
+    );
+
+    let mut running = summary(BackgroundServiceStatus::Running);
+    running.health = Some(BackgroundServiceHealth::Healthy);
+    running.restarts = 2;
+    running.source = BackgroundServiceSource::Config;
+    assert_eq!(
+        summary_line(&running),
+        "db: running, healthy, restarted 2 times (from config)"
+    );
+
+    let mut exited = summary(BackgroundServiceStatus::Exited);
+    exited.exit_code = Some(1);
+    assert_eq!(summary_line(&exited), "db: exited with code 1");
+}
@@ -1,15 +1,19 @@
 use std::path::Path;
+use std::path::PathBuf;
 use std::time::Duration;
 
 use async_trait::async_trait;
+use codex_protocol::permissions::FileSystemAccessResolver;
 use serde::Deserialize;
 use tokio::process::Command;
 use tokio::time::timeout;
 
 use crate::function_tool::FunctionCallError;
+use crate::overlay_workspace::OverlayWorkspace;
 use crate::tools::context::FunctionToolOutput;
 use crate::tools::context::ToolInvocation;
 use crate::tools::context::ToolPayload;
+use crate::tools::handlers::ensure_readable_by_policy;
 use crate::tools::handlers::parse_arguments;
 use crate::tools::registry::ToolHandler;
 use crate::tools::registry::ToolKind;
@@ -73,7 +77,18 @@ impl ToolHandler for GrepFilesHandler {
         let limit = args.limit.min(MAX_LIMIT);
         let search_path = turn.resolve_path(args.path.clone());
 
-        verify_path_exists(&search_path).await?;
+        let overlay_workspace = turn.overlay_workspace.as_deref();
+        let host_search_path = match overlay_workspace {
+            Some(overlay_workspace) => overlay_workspace
+                .read_path(&search_path)
+                .unwrap_or_else(|| search_path.clone()),
+            None => search_path.clone(),
+        };
+        verify_path_exists(&host_search_path).await?;
+        let access = turn
+            .file_system_sandbox_policy
+            .access_resolver_with_cwd(&turn.cwd);
+        ensure_readable_by_policy(&access, &search_path)?;
 
         let include = args.include.as_deref().map(str::trim).and_then(|val| {
             if val.is_empty() {
@@ -83,8 +98,33 @@ impl ToolHandler for GrepFilesHandler {
             }
         });
 
-        let search_results =
-            run_rg_search(pattern, include.as_deref(), &search_path, limit, &turn.cwd).await?;
+        // Search what sandboxed commands see when they write into a
+        // copy-on-write workspace.
+        let search_results = match overlay_workspace {
+            Some(overlay_workspace) => {
+                run_overlay_rg_search(
+                    overlay_workspace,
+                    pattern,
+                    include.as_deref(),
+                    &search_path,
+                    limit,
+                    &turn.cwd,
+                    &access,
+                )
+                .await?
+            }
+            None => {
+                run_rg_search(
+                    pattern,
+                    include.as_deref(),
+                    &search_path,
+                    limit,
+                    &turn.cwd,
+                    &access,
+                )
+                .await?
+            }
+        };
 
         if search_results.is_empty() {
             Ok(FunctionToolOutput::from_text(
@@ -113,6 +153,7 @@ async fn run_rg_search(
     search_path: &Path,
     limit: usize,
     cwd: &Path,
+    access: &FileSystemAccessResolver,
 ) -> Result<Vec<String>, FunctionCallError> {
     let mut command = Command::new("rg");
     command
@@ -141,7 +182,7 @@ async fn run_rg_search(
         })?;
 
     match output.status.code() {
-        Some(0) => Ok(parse_results(&output.stdout, limit)),
+        Some(0) => Ok(parse_results(&output.stdout, limit, access)),
         Some(1) => Ok(Vec::new()),
         _ => {
             let stderr = String::from_utf8_lossy(&output.stderr);
@@ -152,14 +193,84 @@ async fn run_rg_search(
     }
 }
 
-fn parse_results(stdout: &[u8], limit: usize) -> Vec<String> {
+/// Searches both layers of a copy-on-write workspace. Lower matches that the
+/// overlay replaced or deleted are dropped, upper matches are reported at
+/// their workspace path, and the merged list keeps rg's newest-first order.
+async fn run_overlay_rg_search(
+    overlay_workspace: &OverlayWorkspace,
+    pattern: &str,
+    include: Option<&str>,
+    search_path: &Path,
+    limit: usize,
+    cwd: &Path,
+    access: &FileSystemAccessResolver,
+) -> Result<Vec<String>, FunctionCallError> {
+    let layers = overlay_workspace.layers();
+    // (workspace path, host path)
+    let mut matches: Vec<(PathBuf, PathBuf)> = Vec::new();
+    if tokio::fs::symlink_metadata(search_path).await.is_ok() {
+        for path in run_rg_search(pattern, include, search_path, usize::MAX, cwd, access).await? {
+            let path = PathBuf::from(path);
+            if overlay_workspace.read_path(&path).as_ref() == Some(&path) {
+                matches.push((path.clone(), path));
+            }
+        }
+    }
+    if let Ok(relative) = search_path.strip_prefix(&layers.lower) {
+        let upper_search_path = layers.upper.join(relative);
+        if tokio::fs::symlink_metadata(&upper_search_path)
+            .await
+            .is_ok()
+        {
+            for host in run_rg_search(
+                pattern,
+                include,
+                &upper_search_path,
+                usize::MAX,
+                cwd,
+                access,
+            )
+            .await?
+            {
+                let host = PathBuf::from(host);
+                let Ok(relative) = host.strip_prefix(&layers.upper) else {
+                    continue;
+                };
+                let path = layers.lower.join(relative);
+                if !access.is_read_denied(&path) {
+                    matches.push((path, host));
+                }
+            }
+        }
+    }
+
+    let mut matches = matches
+        .into_iter()
+        .map(|(path, host)| {
+            let modified = std::fs::metadata(&host)
+                .and_then(|metadata| metadata.modified())
+                .ok();
+            (modified, path)
+        })
+        .collect::<Vec<_>>();
+    matches.sort_by(|a, b| b.0.cmp(&a.0));
+    Ok(matches
+        .into_iter()
+        .take(limit)
+        .map(|(_, path)| path.display().to_string())
+        .collect())
+}
+
+/// Collects up to `limit` matching paths, dropping any the filesystem policy
+/// denies so they neither appear nor count toward the limit.
+fn parse_results(stdout: &[u8], limit: usize, access: &FileSystemAccessResolver) -> Vec<String> {
     let mut results = Vec::new();
     for line in stdout.split(|byte| *byte == b'\n') {
         if line.is_empty() {
             continue;
         }
         if let Ok(text) = std::str::from_utf8(line) {
-            if text.is_empty() {
+            if text.is_empty() || access.is_read_denied(Path::new(text)) {
                 continue;
             }
             results.push(text.to_string());
@@ -1,11 +1,16 @@
 use super::*;
+use codex_protocol::permissions::FileSystemAccessMode;
+use codex_protocol::permissions::FileSystemPath;
+use codex_protocol::permissions::FileSystemSandboxEntry;
+use codex_protocol::permissions::FileSystemSandboxPolicy;
+use codex_protocol::permissions::FileSystemSpecialPath;
 use std::process::Command as StdCommand;
 use tempfile::tempdir;
 
 #[test]
 fn parses_basic_results() {
     let stdout = b"/tmp/file_a.rs\n/tmp/file_b.rs\n";
-    let parsed = parse_results(stdout, 10);
+    let parsed = parse_results(stdout, 10, &unrestricted());
     assert_eq!(
         parsed,
         vec!["/tmp/file_a.rs".to_string(), "/tmp/file_b.rs".to_string()]
@@ -15,7 +20,7 @@ fn parses_basic_results() {
 #[test]
 fn parse_truncates_after_limit() {
     let stdout = b"/tmp/file_a.rs\n/tmp/file_b.rs\n/tmp/file_c.rs\n";
-    let parsed = parse_results(stdout, 2);
+    let parsed = parse_results(stdout, 2, &unrestricted());
     assert_eq!(
         parsed,
         vec!["/tmp/file_a.rs".to_string(), "/tmp/file_b.rs".to_string()]
@@ -33,7 +38,7 @@ async fn run_search_returns_results() -> anyhow::Result<()> {
     std::fs::write(dir.join("match_two.txt"), "alpha delta").unwrap();
     std::fs::write(dir.join("other.txt"), "omega").unwrap();
 
-    let results = run_rg_search("alpha", None, dir, 10, dir).await?;
+    let results = run_rg_search("alpha", None, dir, 10, dir, &unrestricted()).await?;
     assert_eq!(results.len(), 2);
     assert!(results.iter().any(|path| path.ends_with("match_one.txt")));
     assert!(results.iter().any(|path| path.ends_with("match_two.txt")));
@@ -50,7 +55,7 @@ async fn run_search_with_glob_filter() -> anyhow::Result<()> {
     std::fs::write(dir.join("match_one.rs"), "alpha beta gamma").unwrap();
     std::fs::write(dir.join("match_two.txt"), "alpha delta").unwrap();
 
-    let results = run_rg_search("alpha", Some("*.rs"), dir, 10, dir).await?;
+    let results = run_rg_search("alpha", Some("*.rs"), dir, 10, dir, &unrestricted()).await?;
     assert_eq!(results.len(), 1);
     assert!(results.iter().all(|path| path.ends_with("match_one.rs")));
     Ok(())
@@ -67,7 +72,7 @@ async fn run_search_respects_limit() -> anyhow::Result<()> {
     std::fs::write(dir.join("two.txt"), "alpha two").unwrap();
     std::fs::write(dir.join("three.txt"), "alpha three").unwrap();
 
-    let results = run_rg_search("alpha", None, dir, 2, dir).await?;
+    let results = run_rg_search("alpha", None, dir, 2, dir, &unrestricted()).await?;
     assert_eq!(results.len(), 2);
     Ok(())
 }
@@ -81,11 +86,81 @@ async fn run_search_handles_no_matches() -> anyhow::Result<()> {
     let dir = temp.path();
     std::fs::write(dir.join("one.txt"), "omega").unwrap();
 
-    let results = run_rg_search("alpha", None, dir, 5, dir).await?;
+    let results = run_rg_search("alpha", None, dir, 5, dir, &unrestricted()).await?;
     assert!(results.is_empty());
     Ok(())
 }
 
+#[test]
+fn parse_skips_paths_denied_by_policy() {
+    let policy = FileSystemSandboxPolicy::restricted(vec![
+        FileSystemSandboxEntry {
+            path: FileSystemPath::Special {
+                value: FileSystemSpecialPath::Root,
+            },
+            access: FileSystemAccessMode::Read,
+        },
+        FileSystemSandboxEntry {
+            path: FileSystemPath::Glob {
+                pattern: "**/*.pem".to_string(),
+            },
+            access: FileSystemAccessMode::None,
+        },
+    ]);
+    let access = policy.access_resolver_with_cwd(Path::new("/tmp"));
+    let stdout = b"/tmp/keys/server.pem\n/tmp/src/lib.rs\n/tmp/src/main.rs\n";
+    let parsed = parse_results(stdout, 1, &access);
+    assert_eq!(parsed, vec!["/tmp/src/lib.rs".to_string()]);
+}
+
+#[tokio::test]
+async fn overlay_search_reports_what_sandboxed_commands_see() -> anyhow::Result<()> {
+    if !rg_available() {
+        return Ok(());
+    }
+    let temp = tempdir().expect("create temp dir");
+    let workspace = temp.path().join("workspace");
+    std::fs::create_dir_all(&workspace)?;
+    let Ok(overlay) = OverlayWorkspace::create(
+        &temp.path().join("codex_home"),
+        codex_protocol::ThreadId::new(),
+        &workspace,
+    ) else {
+        // Copy-on-write workspaces are Linux only.
+        return Ok(());
+    };
+    let upper = &overlay.layers().upper;
+    std::fs::write(workspace.join("kept.txt"), "alpha")?;
+    std::fs::write(workspace.join("edited.txt"), "alpha")?;
+    std::fs::write(upper.join("edited.txt"), "omega")?;
+    std::fs::write(upper.join("added.txt"), "alpha")?;
+
+    let mut results = run_overlay_rg_search(
+        &overlay,
+        "alpha",
+        None,
+        &workspace,
+        10,
+        &workspace,
+        &unrestricted(),
+    )
+    .await?;
+    results.sort();
+
+    assert_eq!(
+        results,
+        vec![
+            workspace.join("added.txt").display().to_string(),
+            workspace.join("kept.txt").display().to_string(),
+        ]
+    );
+    Ok(())
+}
+
+fn unrestricted() -> FileSystemAccessResolver {
+    FileSystemSandboxPolicy::unrestricted().access_resolver_with_cwd(Path::new("/"))
+}
+
 fn rg_available() -> bool {
     StdCommand::new("rg")
         .arg("--version")
@@ -1,18 +1,22 @@
 use std::collections::VecDeque;
 use std::ffi::OsStr;
+use std::ffi::OsString;
 use std::fs::FileType;
 use std::path::Path;
 use std::path::PathBuf;
 
 use async_trait::async_trait;
+use codex_protocol::permissions::FileSystemAccessResolver;
 use codex_utils_string::take_bytes_at_char_boundary;
 use serde::Deserialize;
 use tokio::fs;
 
 use crate::function_tool::FunctionCallError;
+use crate::overlay_workspace::OverlayWorkspace;
 use crate::tools::context::FunctionToolOutput;
 use crate::tools::context::ToolInvocation;
 use crate::tools::context::ToolPayload;
+use crate::tools::handlers::ensure_readable_by_policy;
 use crate::tools::handlers::parse_arguments;
 use crate::tools::registry::ToolHandler;
 use crate::tools::registry::ToolKind;
@@ -54,7 +58,7 @@ impl ToolHandler for ListDirHandler {
     }
 
     async fn handle(&self, invocation: ToolInvocation) -> Result<Self::Output, FunctionCallError> {
-        let ToolInvocation { payload, .. } = invocation;
+        let ToolInvocation { payload, turn, .. } = invocation;
 
         let arguments = match payload {
             ToolPayload::Function { arguments } => arguments,
@@ -99,7 +103,22 @@ impl ToolHandler for ListDirHandler {
             ));
         }
 
-        let entries = list_dir_slice(&path, offset, limit, depth).await?;
+        let access = turn
+            .file_system_sandbox_policy
+            .access_resolver_with_cwd(&turn.cwd);
+        ensure_readable_by_policy(&access, &path)?;
+
+        // List what sandboxed commands see when they write into a
+        // copy-on-write workspace.
+        let entries = list_dir_slice(
+            &path,
+            offset,
+            limit,
+            depth,
+            &access,
+            turn.overlay_workspace.as_deref(),
+        )
+        .await?;
         let mut output = Vec::with_capacity(entries.len() + 1);
         output.push(format!("Absolute path: {}", path.display()));
         output.extend(entries);
@@ -112,9 +131,19 @@ async fn list_dir_slice(
     offset: usize,
     limit: usize,
     depth: usize,
+    access: &FileSystemAccessResolver,
+    overlay_workspace: Option<&OverlayWorkspace>,
 ) -> Result<Vec<String>, FunctionCallError> {
     let mut entries = Vec::new();
-    collect_entries(path, Path::new(""), depth, &mut entries).await?;
+    collect_entries(
+        path,
+        Path::new(""),
+        depth,
+        access,
+        overlay_workspace,
+        &mut entries,
+    )
+    .await?;
 
     if entries.is_empty() {
         return Ok(Vec::new());
@@ -150,26 +179,23 @@ async fn collect_entries(
     dir_path: &Path,
     relative_prefix: &Path,
     depth: usize,
+    access: &FileSystemAccessResolver,
+    overlay_workspace: Option<&OverlayWorkspace>,
     entries: &mut Vec<DirEntry>,
 ) -> Result<(), FunctionCallError> {
     let mut queue = VecDeque::new();
     queue.push_back((dir_path.to_path_buf(), relative_prefix.to_path_buf(), depth));
 
     while let Some((current_dir, prefix, remaining_depth)) = queue.pop_front() {
-        let mut read_dir = fs::read_dir(&current_dir).await.map_err(|err| {
-            FunctionCallError::RespondToModel(format!("failed to read directory: {err}"))
-        })?;
-
         let mut dir_entries = Vec::new();
 
-        while let Some(entry) = read_dir.next_entry().await.map_err(|err| {
-            FunctionCallError::RespondToModel(format!("failed to read directory: {err}"))
-        })? {
-            let file_type = entry.file_type().await.map_err(|err| {
-                FunctionCallError::RespondToModel(format!("failed to inspect entry: {err}"))
-            })?;
+        for (file_name, file_type) in read_dir_entries(&current_dir, overlay_workspace).await? {
+            let entry_path = current_dir.join(&file_name);
+            // Entries denied by the filesystem policy are hidden, not listed.
+            if access.is_read_denied(&entry_path) {
+                continue;
+            }
 
-            let file_name = entry.file_name();
             let relative_path = if prefix.as_os_str().is_empty() {
                 PathBuf::from(&file_name)
             } else {
@@ -181,7 +207,7 @@ async fn collect_entries(
             let sort_key = format_entry_name(&relative_path);
             let kind = DirEntryKind::from(&file_type);
             dir_entries.push((
-                entry.path(),
+                entry_path,
                 relative_path,
                 kind,
                 DirEntry {
@@ -206,6 +232,31 @@ async fn collect_entries(
     Ok(())
 }
 
+async fn read_dir_entries(
+    dir: &Path,
+    overlay_workspace: Option<&OverlayWorkspace>,
+) -> Result<Vec<(OsString, FileType)>, FunctionCallError> {
+    if let Some(overlay_workspace) = overlay_workspace {
+        return overlay_workspace.read_dir(dir).map_err(|err| {
+            FunctionCallError::RespondToModel(format!("failed to read directory: {err}"))
+        });
+    }
+
+    let mut read_dir = fs::read_dir(dir).await.map_err(|err| {
+        FunctionCallError::RespondToModel(format!("failed to read directory: {err}"))
+    })?;
+    let mut entries = Vec::new();
+    while let Some(entry) = read_dir.next_entry().await.map_err(|err| {
+        FunctionCallError::RespondToModel(format!("failed to read directory: {err}"))
+    })? {
+        let file_type = entry.file_type().await.map_err(|err| {
+            FunctionCallError::RespondToModel(format!("failed to inspect entry: {err}"))
+        })?;
+        entries.push((entry.file_name(), file_type));
+    }
+    Ok(entries)
+}
+
 fn format_entry_name(path: &Path) -> String {
     let normalized = path.to_string_lossy().replace("\\", "/");
     if normalized.len() > MAX_ENTRY_LENGTH {
@@ -1,7 +1,16 @@
 use super::*;
+use codex_protocol::permissions::FileSystemAccessMode;
+use codex_protocol::permissions::FileSystemPath;
+use codex_protocol::permissions::FileSystemSandboxEntry;
+use codex_protocol::permissions::FileSystemSandboxPolicy;
+use codex_protocol::permissions::FileSystemSpecialPath;
 use pretty_assertions::assert_eq;
 use tempfile::tempdir;
 
+fn unrestricted() -> FileSystemAccessResolver {
+    FileSystemSandboxPolicy::unrestricted().access_resolver_with_cwd(Path::new("/"))
+}
+
 #[tokio::test]
 async fn lists_directory_entries() {
     let temp = tempdir().expect("create tempdir");
@@ -34,7 +43,7 @@ async fn lists_directory_entries() {
         symlink(dir_path.join("entry.txt"), &link_path).expect("create symlink");
     }
 
-    let entries = list_dir_slice(dir_path, 1, 20, 3)
+    let entries = list_dir_slice(dir_path, 1, 20, 3, &unrestricted(), None)
         .await
         .expect("list directory");
 
@@ -68,7 +77,7 @@ async fn errors_when_offset_exceeds_entries() {
         .await
         .expect("create sub dir");
 
-    let err = list_dir_slice(dir_path, 10, 1, 2)
+    let err = list_dir_slice(dir_path, 10, 1, 2, &unrestricted(), None)
         .await
         .expect_err("offset exceeds entries");
     assert_eq!(
@@ -95,7 +104,7 @@ async fn respects_depth_parameter() {
         .await
         .expect("write deeper");
 
-    let entries_depth_one = list_dir_slice(dir_path, 1, 10, 1)
+    let entries_depth_one = list_dir_slice(dir_path, 1, 10, 1, &unrestricted(), None)
         .await
         .expect("list depth 1");
     assert_eq!(
@@ -103,7 +112,7 @@ async fn respects_depth_parameter() {
         vec!["nested/".to_string(), "root.txt".to_string(),]
     );
 
-    let entries_depth_two = list_dir_slice(dir_path, 1, 20, 2)
+    let entries_depth_two = list_dir_slice(dir_path, 1, 20, 2, &unrestricted(), None)
         .await
         .expect("list depth 2");
     assert_eq!(
@@ -116,7 +125,7 @@ async fn respects_depth_parameter() {
         ]
     );
 
-    let entries_depth_three = list_dir_slice(dir_path, 1, 30, 3)
+    let entries_depth_three = list_dir_slice(dir_path, 1, 30, 3, &unrestricted(), None)
         .await
         .expect("list depth 3");
     assert_eq!(
@@ -148,7 +157,7 @@ async fn paginates_in_sorted_order() {
         .await
         .expect("write b child");
 
-    let first_page = list_dir_slice(dir_path, 1, 2, 2)
+    let first_page = list_dir_slice(dir_path, 1, 2, 2, &unrestricted(), None)
         .await
         .expect("list page one");
     assert_eq!(
@@ -160,7 +169,7 @@ async fn paginates_in_sorted_order() {
         ]
     );
 
-    let second_page = list_dir_slice(dir_path, 3, 2, 2)
+    let second_page = list_dir_slice(dir_path, 3, 2, 2, &unrestricted(), None)
         .await
         .expect("list page two");
     assert_eq!(
@@ -183,7 +192,7 @@ async fn handles_large_limit_without_overflow() {
         .await
         .expect("write gamma");
 
-    let entries = list_dir_slice(dir_path, 2, usize::MAX, 1)
+    let entries = list_dir_slice(dir_path, 2, usize::MAX, 1, &unrestricted(), None)
         .await
         .expect("list without overflow");
     assert_eq!(
@@ -204,7 +213,7 @@ async fn indicates_truncated_results() {
             .expect("write file");
     }
 
-    let entries = list_dir_slice(dir_path, 1, 25, 1)
+    let entries = list_dir_slice(dir_path, 1, 25, 1, &unrestricted(), None)
         .await
         .expect("list directory");
     assert_eq!(entries.len(), 26);
@@ -226,7 +235,7 @@ async fn truncation_respects_sorted_order() -> anyhow::Result<()> {
     tokio::fs::write(nested.join("child.txt"), b"child").await?;
     tokio::fs::write(deeper.join("grandchild.txt"), b"deep").await?;
 
-    let entries_depth_three = list_dir_slice(dir_path, 1, 3, 3).await?;
+    let entries_depth_three = list_dir_slice(dir_path, 1, 3, 3, &unrestricted(), None).await?;
     assert_eq!(
         entries_depth_three,
         vec![
@@ -239,3 +248,85 @@ async fn truncation_respects_sorted_order() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn hides_entries_denied_by_glob_policy() {
+    let temp = tempdir().expect("create tempdir");
+    let dir_path = temp.path();
+    let nested = dir_path.join("nested");
+    tokio::fs::create_dir(&nested).await.expect("create nested");
+    tokio::fs::write(dir_path.join(".env"), b"SECRET=1")
+        .await
+        .expect("write env");
+    tokio::fs::write(nested.join(".env.local"), b"SECRET=2")
+        .await
+        .expect("write nested env");
+    tokio::fs::write(nested.join("visible.txt"), b"ok")
+        .await
+        .expect("write visible");
+
+    let policy = FileSystemSandboxPolicy::restricted(vec![
+        FileSystemSandboxEntry {
+            path: FileSystemPath::Special {
+                value: FileSystemSpecialPath::Root,
+            },
+            access: FileSystemAccessMode::Read,
+        },
+        FileSystemSandboxEntry {
+            path: FileSystemPath::Glob {
+                pattern: "**/.env*".to_string(),
+            },
+            access: FileSystemAccessMode::None,
+        },
+    ]);
+    let access = policy.access_resolver_with_cwd(dir_path);
+
+    let entries = list_dir_slice(dir_path, 1, 20, 2, &access, None)
+        .await
+        .expect("list directory");
+
+    assert_eq!(
+        entries,
+        vec!["nested/".to_string(), "  visible.txt".to_string()]
+    );
+}
+
+#[tokio::test]
+async fn lists_the_overlay_view_of_the_workspace() {
+    let temp = tempdir().expect("create tempdir");
+    let workspace = temp.path().join("workspace");
+    tokio::fs::create_dir(&workspace)
+        .await
+        .expect("create workspace");
+    let Ok(overlay) = OverlayWorkspace::create(
+        &temp.path().join("codex_home"),
+        codex_protocol::ThreadId::new(),
+        &workspace,
+    ) else {
+        // Copy-on-write workspaces are Linux only.
+        return;
+    };
+    let upper = &overlay.layers().upper;
+    tokio::fs::write(workspace.join("kept.txt"), b"lower")
+        .await
+        .expect("write kept");
+    tokio::fs::create_dir(upper.join("new"))
+        .await
+        .expect("create upper dir");
+    tokio::fs::write(upper.join("new/file.txt"), b"upper")
+        .await
+        .expect("write upper file");
+
+    let entries = list_dir_slice(&workspace, 1, 20, 2, &unrestricted(), Some(&overlay))
+        .await
+        .expect("list directory");
+
+    assert_eq!(
+        entries,
+        vec![
+            "kept.txt".to_string(),
+            "new/".to_string(),
+            "  file.txt".to_string(),
+        ]
+    );
+}
@@ -57,6 +57,14 @@ struct ReadResourceArgs {
     uri: String,
 }
 
+#[derive(Debug, Deserialize)]
+struct SubscribeResourceArgs {
+    server: String,
+    uri: String,
+    #[serde(default)]
+    unsubscribe: bool,
+}
+
 #[derive(Debug, Serialize)]
 struct ResourceWithServer {
     server: String,
@@ -178,6 +186,13 @@ struct ReadResourcePayload {
     result: ReadResourceResult,
 }
 
+#[derive(Debug, Serialize)]
+struct SubscribeResourcePayload {
+    server: String,
+    uri: String,
+    subscribed: bool,
+}
+
 #[async_trait]
 impl ToolHandler for McpResourceHandler {
     type Output = FunctionToolOutput;
@@ -235,6 +250,15 @@ impl ToolHandler for McpResourceHandler {
                 )
                 .await
             }
+            "subscribe_mcp_resource" => {
+                handle_subscribe_resource(
+                    Arc::clone(&session),
+                    Arc::clone(&turn),
+                    call_id,
+                    arguments_value,
+                )
+                .await
+            }
             other => Err(FunctionCallError::RespondToModel(format!(
                 "unsupported MCP resource tool: {other}"
             ))),
@@ -543,6 +567,89 @@ async fn handle_read_resource(
     }
 }
 
+async fn handle_subscribe_resource(
+    session: Arc<Session>,
+    turn: Arc<TurnContext>,
+    call_id: String,
+    arguments: Option<Value>,
+) -> Result<FunctionToolOutput, FunctionCallError> {
+    let args: SubscribeResourceArgs = parse_args(arguments.clone())?;
+    let SubscribeResourceArgs {
+        server,
+        uri,
+        unsubscribe,
+    } = args;
+    let server = normalize_required_string("server", server)?;
+    let uri = normalize_required_string("uri", uri)?;
+
+    let invocation = McpInvocation {
+        server: server.clone(),
+        tool: "subscribe_mcp_resource".to_string(),
+        arguments: arguments.clone(),
+    };
+
+    emit_tool_call_begin(&session, turn.as_ref(), &call_id, invocation.clone()).await;
+    let start = Instant::now();
+
+    let payload_result: Result<SubscribeResourcePayload, FunctionCallError> = async {
+        if unsubscribe {
+            session
+                .unsubscribe_mcp_resource(&server, &uri)
+                .await
+                .map_err(|err| {
+                    FunctionCallError::RespondToModel(format!(
+                        "resources/unsubscribe failed: {err:#}"
+                    ))
+                })?;
+        } else {
+            session
+                .subscribe_mcp_resource(&server, &uri)
+                .await
+                .map_err(|err| {
+                    FunctionCallError::RespondToModel(format!(
+                        "resources/subscribe failed: {err:#}"
+                    ))
+                })?;
+        }
+
+        Ok(SubscribeResourcePayload {
+            server,
+            uri,
+            subscribed: !unsubscribe,
+        })
+    }
+    .await;
+
+    match payload_result.and_then(serialize_function_output) {
+        Ok(output) => {
+            let content =
+                function_call_output_content_items_to_text(&output.body).unwrap_or_default();
+            emit_tool_call_end(
+                &session,
+                turn.as_ref(),
+                &call_id,
+                invocation,
+                start.elapsed(),
+                Ok(call_tool_result_from_content(&content, output.success)),
+            )
+            .await;
+            Ok(output)
+        }
+        Err(err) => {
+            emit_tool_call_end(
+                &session,
+                turn.as_ref(),
+                &call_id,
+                invocation,
+                start.elapsed(),
+                Err(err.to_string()),
+            )
+            .await;
+            Err(err)
+        }
+    }
+}
+
 fn call_tool_result_from_content(content: &str, success: Option<bool>) -> CallToolResult {
     CallToolResult {
         content: vec![serde_json::json!({"type": "text", "text": content})],
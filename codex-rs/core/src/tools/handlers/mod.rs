@@ -1,6 +1,7 @@
 pub(crate) mod agent_jobs;
 pub mod apply_patch;
 mod artifacts;
+mod background_services;
 mod dynamic;
 mod grep_files;
 mod js_repl;
@@ -10,6 +11,7 @@ mod mcp_resource;
 pub(crate) mod multi_agents;
 mod plan;
 mod read_file;
+mod read_tool_output;
 mod request_permissions;
 mod request_user_input;
 mod shell;
@@ -35,7 +37,9 @@ pub(crate) use crate::tools::code_mode::CodeModeExecuteHandler;
 pub(crate) use crate::tools::code_mode::CodeModeWaitHandler;
 pub use apply_patch::ApplyPatchHandler;
 pub use artifacts::ArtifactsHandler;
+pub use background_services::BackgroundServicesHandler;
 use codex_protocol::models::PermissionProfile;
+use codex_protocol::permissions::FileSystemAccessResolver;
 use codex_protocol::protocol::AskForApproval;
 pub use dynamic::DynamicToolHandler;
 pub use grep_files::GrepFilesHandler;
@@ -46,6 +50,7 @@ pub use mcp::McpHandler;
 pub use mcp_resource::McpResourceHandler;
 pub use plan::PlanHandler;
 pub use read_file::ReadFileHandler;
+pub use read_tool_output::ReadToolOutputHandler;
 pub use request_permissions::RequestPermissionsHandler;
 pub(crate) use request_permissions::request_permissions_tool_description;
 pub use request_user_input::RequestUserInputHandler;
@@ -70,6 +75,27 @@ where
     })
 }
 
+/// Rejects `path` when the turn's filesystem policy denies reading it. The
+/// canonical path is checked as well so a symlink cannot route around a deny
+/// entry.
+fn ensure_readable_by_policy(
+    access: &FileSystemAccessResolver,
+    path: &Path,
+) -> Result<(), FunctionCallError> {
+    let canonical_path = std::fs::canonicalize(path).ok();
+    if access.is_read_denied(path)
+        || canonical_path
+            .as_deref()
+            .is_some_and(|canonical_path| access.is_read_denied(canonical_path))
+    {
+        return Err(FunctionCallError::RespondToModel(format!(
+            "access to `{}` is denied by policy",
+            path.display()
+        )));
+    }
+    Ok(())
+}
+
 fn parse_arguments_with_base_path<T>(
     arguments: &str,
     base_path: &Path,
@@ -9,6 +9,7 @@ use crate::function_tool::FunctionCallError;
 use crate::tools::context::FunctionToolOutput;
 use crate::tools::context::ToolInvocation;
 use crate::tools::context::ToolPayload;
+use crate::tools::handlers::ensure_readable_by_policy;
 use crate::tools::handlers::parse_arguments;
 use crate::tools::registry::ToolHandler;
 use crate::tools::registry::ToolKind;
@@ -100,7 +101,7 @@ impl ToolHandler for ReadFileHandler {
     }
 
     async fn handle(&self, invocation: ToolInvocation) -> Result<Self::Output, FunctionCallError> {
-        let ToolInvocation { payload, .. } = invocation;
+        let ToolInvocation { payload, turn, .. } = invocation;
 
         let arguments = match payload {
             ToolPayload::Function { arguments } => arguments,
@@ -139,6 +140,22 @@ impl ToolHandler for ReadFileHandler {
                 "file_path must be an absolute path".to_string(),
             ));
         }
+        ensure_readable_by_policy(
+            &turn
+                .file_system_sandbox_policy
+                .access_resolver_with_cwd(&turn.cwd),
+            &path,
+        )?;
+        // Read what sandboxed commands see when they write into a
+        // copy-on-write workspace.
+        let path = match turn.overlay_workspace.as_deref() {
+            Some(overlay_workspace) => overlay_workspace.read_path(&path).ok_or_else(|| {
+                FunctionCallError::RespondToModel(
+                    "failed to read file: No such file or directory".to_string(),
+                )
+            })?,
+            None => path,
+        };
 
         let collected = match mode {
             ReadMode::Slice => slice::read(&path, offset, limit).await?,
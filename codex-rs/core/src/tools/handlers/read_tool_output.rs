@@ -0,0 +1,140 @@
+use async_trait::async_trait;
+use codex_utils_string::take_bytes_at_char_boundary;
+use regex_lite::Regex;
+use serde::Deserialize;
+
+use crate::function_tool::FunctionCallError;
+use crate::tools::context::FunctionToolOutput;
+use crate::tools::context::ToolInvocation;
+use crate::tools::context::ToolPayload;
+use crate::tools::handlers::parse_arguments;
+use crate::tools::registry::ToolHandler;
+use crate::tools::registry::ToolKind;
+
+pub struct ReadToolOutputHandler;
+
+const MAX_LINE_LENGTH: usize = 500;
+
+/// JSON arguments accepted by the `read_tool_output` tool handler.
+#[derive(Deserialize)]
+struct ReadToolOutputArgs {
+    /// Id named in the truncation marker.
+    id: String,
+    /// 1-indexed line number to start reading from; defaults to 1.
+    #[serde(default = "defaults::offset")]
+    offset: usize,
+    /// Maximum number of lines to return; defaults to 200.
+    #[serde(default = "defaults::limit")]
+    limit: usize,
+    /// Only return lines matching this regular expression.
+    #[serde(default)]
+    pattern: Option<String>,
+}
+
+#[async_trait]
+impl ToolHandler for ReadToolOutputHandler {
+    type Output = FunctionToolOutput;
+
+    fn kind(&self) -> ToolKind {
+        ToolKind::Function
+    }
+
+    async fn handle(&self, invocation: ToolInvocation) -> Result<Self::Output, FunctionCallError> {
+        let ToolInvocation {
+            session, payload, ..
+        } = invocation;
+
+        let arguments = match payload {
+            ToolPayload::Function { arguments } => arguments,
+            _ => {
+                return Err(FunctionCallError::RespondToModel(
+                    "read_tool_output handler received unsupported payload".to_string(),
+                ));
+            }
+        };
+
+        let ReadToolOutputArgs {
+            id,
+            offset,
+            limit,
+            pattern,
+        } = parse_arguments(&arguments)?;
+
+        if offset == 0 {
+            return Err(FunctionCallError::RespondToModel(
+                "offset must be a 1-indexed line number".to_string(),
+            ));
+        }
+
+        if limit == 0 {
+            return Err(FunctionCallError::RespondToModel(
+                "limit must be greater than zero".to_string(),
+            ));
+        }
+
+        let pattern = pattern
+            .as_deref()
+            .map(Regex::new)
+            .transpose()
+            .map_err(|err| FunctionCallError::RespondToModel(format!("invalid pattern: {err}")))?;
+
+        let Some(store) = session.services.tool_output_store.as_ref() else {
+            return Err(FunctionCallError::RespondToModel(
+                "read_tool_output is not available in this session".to_string(),
+            ));
+        };
+        let content = store.read(&id).await.map_err(|err| {
+            FunctionCallError::RespondToModel(format!("failed to read tool output: {err}"))
+        })?;
+
+        Ok(FunctionToolOutput::from_text(
+            page(&content, offset, limit, pattern.as_ref()),
+            Some(true),
+        ))
+    }
+}
+
+/// Returns up to `limit` lines (or matching lines, with `pattern`) starting at
+/// line `offset`, followed by where to continue when more remain.
+fn page(content: &str, offset: usize, limit: usize, pattern: Option<&Regex>) -> String {
+    let mut lines = content
+        .lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, line))
+        .skip(offset - 1)
+        .filter(|(_, line)| pattern.is_none_or(|pattern| pattern.is_match(line)));
+
+    let mut collected: Vec<String> = lines
+        .by_ref()
+        .take(limit)
+        .map(|(number, line)| {
+            let line = take_bytes_at_char_boundary(line, MAX_LINE_LENGTH);
+            format!("L{number}: {line}")
+        })
+        .collect();
+
+    if collected.is_empty() {
+        return match pattern {
+            Some(_) => format!("No lines at or after line {offset} match the pattern."),
+            None => format!("The output has fewer than {offset} lines."),
+        };
+    }
+    if let Some((next, _)) = lines.next() {
+        collected.push(format!("[more lines follow; continue with offset {next}]"));
+    }
+    collected.join("\n")
+}
+
+mod defaults {
+    pub fn offset() -> usize {
+        1
+    }
+
+    pub fn limit() -> usize {
+        200
+    }
+}
+
+#[cfg(test)]
+#[path = "read_tool_output_tests.rs"]
+mod tests;
@@ -0,0 +1,31 @@
+use super::*;
+use pretty_assertions::assert_eq;
+
+const OUTPUT: &str = "alpha\nbeta\ngamma\ndelta\nepsilon\n";
+
+#[test]
+fn pages_from_offset_and_says_where_to_continue() {
+    assert_eq!(
+        page(OUTPUT, 2, 2, None),
+        "L2: beta\nL3: gamma\n[more lines follow; continue with offset 4]"
+    );
+    assert_eq!(page(OUTPUT, 4, 10, None), "L4: delta\nL5: epsilon");
+    assert_eq!(
+        page(OUTPUT, 9, 10, None),
+        "The output has fewer than 9 lines."
+    );
+}
+
+#[test]
+fn pattern_keeps_only_matching_lines_with_their_numbers() {
+    let pattern = Regex::new("a$").expect("valid pattern");
+    assert_eq!(
+        page(OUTPUT, 1, 2, Some(&pattern)),
+        "L1: alpha\nL2: beta\n[more lines follow; continue with offset 3]"
+    );
+    assert_eq!(page(OUTPUT, 3, 10, Some(&pattern)), "L3: gamma\nL4: delta");
+    assert_eq!(
+        page(OUTPUT, 5, 10, Some(&pattern)),
+        "No lines at or after line 5 match the pattern."
+    );
+}
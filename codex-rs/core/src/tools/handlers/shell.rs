@@ -335,6 +335,12 @@ impl ShellHandler {
                 explicit_env_overrides.insert(key.clone(), value.clone());
             }
         }
+        // Exposed secrets win over both inherited and shell-snapshot values.
+        session.services.tool_secrets.inject(&mut exec_params.env);
+        session
+            .services
+            .tool_secrets
+            .inject(&mut explicit_env_overrides);
 
         let exec_permission_approvals_enabled =
             session.features().enabled(Feature::ExecPermissionApprovals);
@@ -19,12 +19,16 @@ use crate::tools::handlers::parse_arguments_with_base_path;
 use crate::tools::handlers::resolve_workdir_base_path;
 use crate::tools::registry::ToolHandler;
 use crate::tools::registry::ToolKind;
+use crate::truncate::TruncationPolicy;
 use crate::unified_exec::ExecCommandRequest;
+use crate::unified_exec::MAX_YIELD_TIME_MS;
 use crate::unified_exec::UnifiedExecContext;
 use crate::unified_exec::UnifiedExecProcessManager;
 use crate::unified_exec::WriteStdinRequest;
+use crate::unified_exec::resolve_max_tokens;
 use async_trait::async_trait;
 use codex_protocol::models::PermissionProfile;
+use regex_lite::Regex;
 use serde::Deserialize;
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -42,11 +46,15 @@ pub(crate) struct ExecCommandArgs {
     login: Option<bool>,
     #[serde(default = "default_tty")]
     tty: bool,
-    #[serde(default = "default_exec_yield_time_ms")]
-    yield_time_ms: u64,
+    #[serde(default)]
+    yield_time_ms: Option<u64>,
     #[serde(default)]
     max_output_tokens: Option<usize>,
     #[serde(default)]
+    wait_for: Option<String>,
+    #[serde(default)]
+    wait_for_exit: bool,
+    #[serde(default)]
     sandbox_permissions: SandboxPermissions,
     #[serde(default)]
     additional_permissions: Option<PermissionProfile>,
@@ -62,10 +70,14 @@ struct WriteStdinArgs {
     session_id: i32,
     #[serde(default)]
     chars: String,
-    #[serde(default = "default_write_stdin_yield_time_ms")]
-    yield_time_ms: u64,
+    #[serde(default)]
+    yield_time_ms: Option<u64>,
     #[serde(default)]
     max_output_tokens: Option<usize>,
+    #[serde(default)]
+    wait_for: Option<String>,
+    #[serde(default)]
+    wait_for_exit: bool,
 }
 
 fn default_exec_yield_time_ms() -> u64 {
@@ -80,6 +92,33 @@ fn default_tty() -> bool {
     false
 }
 
+/// Resolves how long a call may wait for output. When the model waits for a
+/// pattern or for the process to exit without naming a yield time, the wait
+/// runs up to `ceiling_ms` instead of the short default.
+fn resolve_yield_time_ms(
+    yield_time_ms: Option<u64>,
+    waits: bool,
+    default_yield_time_ms: fn() -> u64,
+    ceiling_ms: u64,
+) -> u64 {
+    match yield_time_ms {
+        Some(yield_time_ms) => yield_time_ms,
+        None if waits => ceiling_ms,
+        None => default_yield_time_ms(),
+    }
+}
+
+fn parse_wait_for(wait_for: Option<&str>) -> Result<Option<Regex>, FunctionCallError> {
+    wait_for
+        .filter(|pattern| !pattern.is_empty())
+        .map(|pattern| {
+            Regex::new(pattern).map_err(|err| {
+                FunctionCallError::RespondToModel(format!("invalid wait_for pattern: {err}"))
+            })
+        })
+        .transpose()
+}
+
 #[async_trait]
 impl ToolHandler for UnifiedExecHandler {
     type Output = ExecCommandToolOutput;
@@ -138,7 +177,7 @@ impl ToolHandler for UnifiedExecHandler {
         let manager: &UnifiedExecProcessManager = &session.services.unified_exec_manager;
         let context = UnifiedExecContext::new(session.clone(), turn.clone(), call_id.clone());
 
-        let response = match tool_name.as_str() {
+        let mut response = match tool_name.as_str() {
             "exec_command" => {
                 let cwd = resolve_workdir_base_path(&arguments, context.turn.cwd.as_path())?;
                 let args: ExecCommandArgs =
@@ -150,6 +189,7 @@ impl ToolHandler for UnifiedExecHandler {
                     args.workdir.as_deref(),
                 )
                 .await;
+                let wait_for = parse_wait_for(args.wait_for.as_deref())?;
                 let process_id = manager.allocate_process_id().await;
                 let command = get_command(
                     &args,
@@ -163,12 +203,19 @@ impl ToolHandler for UnifiedExecHandler {
                     tty,
                     yield_time_ms,
                     max_output_tokens,
+                    wait_for_exit,
                     sandbox_permissions,
                     additional_permissions,
                     justification,
                     prefix_rule,
                     ..
                 } = args;
+                let yield_time_ms = resolve_yield_time_ms(
+                    yield_time_ms,
+                    wait_for.is_some() || wait_for_exit,
+                    default_exec_yield_time_ms,
+                    MAX_YIELD_TIME_MS,
+                );
 
                 let exec_permission_approvals_enabled =
                     session.features().enabled(Feature::ExecPermissionApprovals);
@@ -253,6 +300,7 @@ impl ToolHandler for UnifiedExecHandler {
                         exit_code: None,
                         original_token_count: None,
                         session_command: None,
+                        spilled_output_id: None,
                     });
                 }
 
@@ -263,6 +311,7 @@ impl ToolHandler for UnifiedExecHandler {
                             process_id,
                             yield_time_ms,
                             max_output_tokens,
+                            wait_for,
                             workdir,
                             network: context.turn.network.clone(),
                             tty,
@@ -283,12 +332,20 @@ impl ToolHandler for UnifiedExecHandler {
             }
             "write_stdin" => {
                 let args: WriteStdinArgs = parse_arguments(&arguments)?;
+                let wait_for = parse_wait_for(args.wait_for.as_deref())?;
+                let yield_time_ms = resolve_yield_time_ms(
+                    args.yield_time_ms,
+                    wait_for.is_some() || args.wait_for_exit,
+                    default_write_stdin_yield_time_ms,
+                    manager.max_write_stdin_yield_time_ms(&args.chars),
+                );
                 let response = manager
                     .write_stdin(WriteStdinRequest {
                         process_id: args.session_id,
                         input: &args.chars,
-                        yield_time_ms: args.yield_time_ms,
+                        yield_time_ms,
                         max_output_tokens: args.max_output_tokens,
+                        wait_for,
                     })
                     .await
                     .map_err(|err| {
@@ -313,6 +370,11 @@ impl ToolHandler for UnifiedExecHandler {
             }
         };
 
+        if let Some(store) = session.services.tool_output_store.as_ref() {
+            let text = String::from_utf8_lossy(&response.raw_output);
+            let policy = TruncationPolicy::Tokens(resolve_max_tokens(response.max_output_tokens));
+            response.spilled_output_id = store.spill_if_truncated(&text, policy).await;
+        }
         Ok(response)
     }
 }
@@ -124,3 +124,46 @@ fn exec_command_args_resolve_relative_additional_permissions_against_workdir() -
     );
     Ok(())
 }
+
+#[test]
+fn waiting_without_yield_time_uses_the_ceiling() -> anyhow::Result<()> {
+    let args: ExecCommandArgs =
+        parse_arguments(r#"{"cmd": "npm run dev", "wait_for": "ready on"}"#)?;
+
+    let wait_for = parse_wait_for(args.wait_for.as_deref())?;
+
+    assert!(wait_for.is_some_and(|pattern| pattern.is_match("server ready on :3000")));
+    assert_eq!(
+        resolve_yield_time_ms(
+            args.yield_time_ms,
+            true,
+            default_exec_yield_time_ms,
+            MAX_YIELD_TIME_MS
+        ),
+        MAX_YIELD_TIME_MS
+    );
+    assert_eq!(
+        resolve_yield_time_ms(None, false, default_exec_yield_time_ms, MAX_YIELD_TIME_MS),
+        default_exec_yield_time_ms()
+    );
+    assert_eq!(
+        resolve_yield_time_ms(
+            Some(500),
+            true,
+            default_exec_yield_time_ms,
+            MAX_YIELD_TIME_MS
+        ),
+        500
+    );
+    Ok(())
+}
+
+#[test]
+fn invalid_wait_for_pattern_is_reported_to_the_model() {
+    let err = parse_wait_for(Some("(")).expect_err("expected invalid pattern");
+
+    let FunctionCallError::RespondToModel(message) = err else {
+        panic!("expected a model-facing error");
+    };
+    assert!(message.starts_with("invalid wait_for pattern"));
+}
@@ -14,6 +14,7 @@ use codex_protocol::models::FunctionCallOutputContentItem;
 use codex_protocol::models::FunctionCallOutputPayload;
 use codex_protocol::models::ImageDetail;
 use codex_protocol::models::ResponseInputItem;
+use codex_protocol::permissions::ResourceLimits;
 use serde::Deserialize;
 use serde::Serialize;
 use serde_json::Value as JsonValue;
@@ -38,6 +39,7 @@ use crate::exec::ExecExpiration;
 use crate::exec_env::create_env;
 use crate::function_tool::FunctionCallError;
 use crate::original_image_detail::normalize_output_image_detail;
+use crate::overlay_workspace::OverlayWorkspace;
 use crate::sandboxing::CommandSpec;
 use crate::sandboxing::SandboxManager;
 use crate::sandboxing::SandboxPermissions;
@@ -1066,6 +1068,16 @@ impl JsReplManager {
                 macos_seatbelt_profile_extensions: None,
                 codex_linux_sandbox_exe: turn.codex_linux_sandbox_exe.as_ref(),
                 use_legacy_landlock: turn.features.use_legacy_landlock(),
+                // The kernel is long-lived, so only the per-process limits apply.
+                resource_limits: ResourceLimits {
+                    wall_time_seconds: None,
+                    ..turn.config.permissions.resource_limits
+                },
+                overlay_workspace: turn
+                    .overlay_workspace
+                    .as_deref()
+                    .map(OverlayWorkspace::layers),
+                glob_deny_matches: turn.glob_deny_matches().await,
                 windows_sandbox_level: turn.windows_sandbox_level,
             })
             .map_err(|err| format!("failed to configure sandbox for js_repl: {err}"))?;
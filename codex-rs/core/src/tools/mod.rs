@@ -17,7 +17,8 @@ pub mod spec;
 use crate::exec::ExecToolCallOutput;
 use crate::truncate::TruncationPolicy;
 use crate::truncate::formatted_truncate_text;
-use crate::truncate::truncate_text;
+use crate::truncate::formatted_truncate_text_with_spill;
+use crate::truncate::truncate_text_with_spill;
 pub use router::ToolRouter;
 use serde::Serialize;
 
@@ -29,9 +30,11 @@ pub(crate) const TELEMETRY_PREVIEW_TRUNCATION_NOTICE: &str =
 
 /// Format the combined exec output for sending back to the model.
 /// Includes exit code and duration metadata; truncates large bodies safely.
+/// `spilled_output_id` names the saved copy of the full output, if any.
 pub fn format_exec_output_for_model_structured(
     exec_output: &ExecToolCallOutput,
     truncation_policy: TruncationPolicy,
+    spilled_output_id: Option<&str>,
 ) -> String {
     let ExecToolCallOutput {
         exit_code,
@@ -54,7 +57,9 @@ pub fn format_exec_output_for_model_structured(
     // round to 1 decimal place
     let duration_seconds = ((duration.as_secs_f32()) * 10.0).round() / 10.0;
 
-    let formatted_output = format_exec_output_str(exec_output, truncation_policy);
+    let content = build_content_with_timeout(exec_output);
+    let formatted_output =
+        formatted_truncate_text_with_spill(&content, truncation_policy, spilled_output_id);
 
     let payload = ExecOutput {
         output: &formatted_output,
@@ -71,6 +76,7 @@ pub fn format_exec_output_for_model_structured(
 pub fn format_exec_output_for_model_freeform(
     exec_output: &ExecToolCallOutput,
     truncation_policy: TruncationPolicy,
+    spilled_output_id: Option<&str>,
 ) -> String {
     // round to 1 decimal place
     let duration_seconds = ((exec_output.duration.as_secs_f32()) * 10.0).round() / 10.0;
@@ -79,7 +85,7 @@ pub fn format_exec_output_for_model_freeform(
 
     let total_lines = content.lines().count();
 
-    let formatted_output = truncate_text(&content, truncation_policy);
+    let formatted_output = truncate_text_with_spill(&content, truncation_policy, spilled_output_id);
 
     let mut sections = Vec::new();
 
@@ -106,7 +112,7 @@ pub fn format_exec_output_str(
 }
 
 /// Extracts exec output content and prepends a timeout message if the command timed out.
-fn build_content_with_timeout(exec_output: &ExecToolCallOutput) -> String {
+pub(crate) fn build_content_with_timeout(exec_output: &ExecToolCallOutput) -> String {
     if exec_output.timed_out {
         format!(
             "command timed out after {} milliseconds\n{}",
@@ -12,6 +12,8 @@ use crate::exec::ExecToolCallOutput;
 use crate::guardian::GUARDIAN_REJECTION_MESSAGE;
 use crate::guardian::routes_approval_to_guardian;
 use crate::network_policy_decision::network_approval_context_from_payload;
+use crate::overlay_workspace::ESCALATION_REFUSED_MESSAGE;
+use crate::overlay_workspace::OverlayWorkspace;
 use crate::sandboxing::SandboxManager;
 use crate::tools::network_approval::DeferredNetworkApproval;
 use crate::tools::network_approval::NetworkApprovalMode;
@@ -26,6 +28,7 @@ use crate::tools::sandboxing::ToolCtx;
 use crate::tools::sandboxing::ToolError;
 use crate::tools::sandboxing::ToolRuntime;
 use crate::tools::sandboxing::default_exec_approval_requirement;
+use crate::tools::sandboxing::skill_resource_limits;
 use codex_otel::ToolDecisionSource;
 use codex_protocol::protocol::AskForApproval;
 use codex_protocol::protocol::NetworkPolicyRuleAction;
@@ -173,6 +176,9 @@ impl ToolOrchestrator {
             .network
             .is_some();
         let initial_sandbox = match tool.sandbox_mode_for_first_attempt(req) {
+            SandboxOverride::BypassSandboxFirstAttempt if turn_ctx.overlay_workspace.is_some() => {
+                return Err(ToolError::Rejected(ESCALATION_REFUSED_MESSAGE.to_string()));
+            }
             SandboxOverride::BypassSandboxFirstAttempt => crate::exec::SandboxType::None,
             SandboxOverride::NoOverride => self.sandbox.select_initial(
                 &turn_ctx.file_system_sandbox_policy,
@@ -186,6 +192,12 @@ impl ToolOrchestrator {
         // Platform-specific flag gating is handled by SandboxManager::select_initial
         // via crate::safety::get_platform_sandbox(..).
         let use_legacy_landlock = turn_ctx.features.use_legacy_landlock();
+        let skill_resource_limits = skill_resource_limits(tool_ctx).await;
+        let glob_deny_matches = if initial_sandbox == crate::exec::SandboxType::LinuxSeccomp {
+            turn_ctx.glob_deny_matches().await
+        } else {
+            None
+        };
         let initial_attempt = SandboxAttempt {
             sandbox: initial_sandbox,
             policy: &turn_ctx.sandbox_policy,
@@ -196,6 +208,13 @@ impl ToolOrchestrator {
             sandbox_cwd: &turn_ctx.cwd,
             codex_linux_sandbox_exe: turn_ctx.codex_linux_sandbox_exe.as_ref(),
             use_legacy_landlock,
+            resource_limits: turn_ctx.config.permissions.resource_limits,
+            skill_resource_limits: &skill_resource_limits,
+            overlay_workspace: turn_ctx
+                .overlay_workspace
+                .as_deref()
+                .map(OverlayWorkspace::layers),
+            glob_deny_matches,
             windows_sandbox_level: turn_ctx.windows_sandbox_level,
         };
 
@@ -232,7 +251,8 @@ impl ToolOrchestrator {
                         network_policy_decision,
                     })));
                 }
-                if !tool.escalate_on_failure() {
+                // The retry would run outside the copy-on-write workspace.
+                if !tool.escalate_on_failure() || turn_ctx.overlay_workspace.is_some() {
                     return Err(ToolError::Codex(CodexErr::Sandbox(SandboxErr::Denied {
                         output,
                         network_policy_decision,
@@ -316,8 +336,14 @@ impl ToolOrchestrator {
                     enforce_managed_network: has_managed_network_requirements,
                     manager: &self.sandbox,
                     sandbox_cwd: &turn_ctx.cwd,
-                    codex_linux_sandbox_exe: None,
+                    // Only used to keep the resource limits of the command.
+                    codex_linux_sandbox_exe: turn_ctx.codex_linux_sandbox_exe.as_ref(),
                     use_legacy_landlock,
+                    resource_limits: turn_ctx.config.permissions.resource_limits,
+                    skill_resource_limits: &skill_resource_limits,
+                    // Escalation is refused above while an overlay is active.
+                    overlay_workspace: None,
+                    glob_deny_matches: None,
                     windows_sandbox_level: turn_ctx.windows_sandbox_level,
                 };
 
@@ -4,8 +4,13 @@ use std::time::Duration;
 use std::time::Instant;
 
 use crate::client_common::tools::ToolSpec;
+use crate::codex::Session;
+use crate::codex::hook_permission_mode;
 use crate::function_tool::FunctionCallError;
+use crate::hook_model_runner::SessionHookModelRunner;
 use crate::memories::usage::emit_metric_for_tool_read;
+use crate::protocol::EventMsg;
+use crate::protocol::HookStartedEvent;
 use crate::protocol::SandboxPolicy;
 use crate::sandbox_tags::sandbox_tag;
 use crate::tools::context::ToolInvocation;
@@ -19,8 +24,13 @@ use codex_hooks::HookResult;
 use codex_hooks::HookToolInput;
 use codex_hooks::HookToolInputLocalShell;
 use codex_hooks::HookToolKind;
+use codex_hooks::PostToolUseRequest;
+use codex_hooks::PreToolUseRequest;
+use codex_protocol::models::ContentItem;
 use codex_protocol::models::ResponseInputItem;
+use codex_protocol::models::ShellToolCallParams;
 use codex_utils_readiness::Readiness;
+use serde_json::Value as JsonValue;
 use tracing::warn;
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
@@ -159,7 +169,7 @@ impl ToolRegistry {
 
     pub(crate) async fn dispatch_any(
         &self,
-        invocation: ToolInvocation,
+        mut invocation: ToolInvocation,
     ) -> Result<AnyToolResult, FunctionCallError> {
         let tool_name = invocation.tool_name.clone();
         let tool_namespace = invocation.tool_namespace.clone();
@@ -243,6 +253,21 @@ impl ToolRegistry {
             return Err(FunctionCallError::Fatal(message));
         }
 
+        if let Err(err) = run_pre_tool_use_hooks(&mut invocation).await {
+            otel.tool_result_with_tags(
+                tool_name.as_ref(),
+                &call_id_owned,
+                log_payload.as_ref(),
+                Duration::ZERO,
+                false,
+                &err.to_string(),
+                &metric_tags,
+                mcp_server_ref,
+                mcp_server_origin_ref,
+            );
+            return Err(err);
+        }
+
         let is_mutating = handler.is_mutating(&invocation).await;
         let response_cell = tokio::sync::Mutex::new(None);
         let invocation_for_tool = invocation.clone();
@@ -285,6 +310,7 @@ impl ToolRegistry {
             Err(err) => (err.to_string(), false),
         };
         emit_metric_for_tool_read(&invocation, success).await;
+        run_post_tool_use_hooks(&invocation, &output_preview, success).await?;
         let hook_abort_error = dispatch_after_tool_use_hook(AfterToolUseHookDispatch {
             invocation: &invocation,
             output_preview,
@@ -463,6 +489,212 @@ fn hook_tool_kind(tool_input: &HookToolInput) -> HookToolKind {
     }
 }
 
+/// JSON shape of the tool input handed to `PreToolUse`/`PostToolUse` hooks.
+/// Function and MCP arguments are passed as parsed JSON when possible so hook
+/// commands can inspect them without a second decode step.
+fn hook_tool_input_value(payload: &ToolPayload) -> JsonValue {
+    match payload {
+        ToolPayload::Function { arguments } => {
+            serde_json::from_str(arguments).unwrap_or_else(|_| JsonValue::String(arguments.clone()))
+        }
+        ToolPayload::ToolSearch { arguments } => serde_json::json!({
+            "query": arguments.query,
+            "limit": arguments.limit,
+        }),
+        ToolPayload::Custom { input } => JsonValue::String(input.clone()),
+        ToolPayload::LocalShell { .. } => match HookToolInput::from(payload) {
+            HookToolInput::LocalShell { params } => {
+                serde_json::to_value(params).unwrap_or(JsonValue::Null)
+            }
+            _ => JsonValue::Null,
+        },
+        ToolPayload::Mcp { raw_arguments, .. } => serde_json::from_str(raw_arguments)
+            .unwrap_or_else(|_| JsonValue::String(raw_arguments.clone())),
+    }
+}
+
+/// Replaces the tool payload with the `updatedInput` returned by a
+/// `PreToolUse` hook, keeping the payload variant unchanged.
+fn apply_updated_tool_input(
+    payload: &mut ToolPayload,
+    updated_input: JsonValue,
+) -> Result<(), String> {
+    match payload {
+        ToolPayload::Function { arguments } => {
+            *arguments = match updated_input {
+                JsonValue::String(text) => text,
+                other => other.to_string(),
+            };
+        }
+        ToolPayload::Custom { input } => match updated_input {
+            JsonValue::String(text) => *input = text,
+            _ => return Err("expected a string for a freeform tool".to_string()),
+        },
+        ToolPayload::LocalShell { params } => {
+            *params = serde_json::from_value::<ShellToolCallParams>(updated_input)
+                .map_err(|err| err.to_string())?;
+        }
+        ToolPayload::Mcp { raw_arguments, .. } => *raw_arguments = updated_input.to_string(),
+        ToolPayload::ToolSearch { .. } => {
+            return Err("tool_search does not accept updated input".to_string());
+        }
+    }
+    Ok(())
+}
+
+async fn inject_hook_feedback(session: &Session, text: String) {
+    let message = ResponseInputItem::Message {
+        role: "developer".to_string(),
+        content: vec![ContentItem::InputText { text }],
+    };
+    if session.inject_response_items(vec![message]).await.is_err() {
+        warn!("dropping tool hook feedback because no turn is active");
+    }
+}
+
+/// Runs `PreToolUse` hooks from hooks.json. A blocking hook turns into an error
+/// the model sees in place of the tool output; a returned `updatedInput`
+/// rewrites the invocation payload before the handler runs.
+async fn run_pre_tool_use_hooks(invocation: &mut ToolInvocation) -> Result<(), FunctionCallError> {
+    let session = Arc::clone(&invocation.session);
+    let turn = Arc::clone(&invocation.turn);
+    let request = PreToolUseRequest {
+        session_id: session.conversation_id,
+        turn_id: turn.sub_id.clone(),
+        cwd: turn.cwd.clone(),
+        transcript_path: session.current_rollout_path().await,
+        model: turn.model_info.slug.clone(),
+        permission_mode: hook_permission_mode(turn.approval_policy.value()),
+        tool_name: invocation.tool_name.clone(),
+        tool_use_id: invocation.call_id.clone(),
+        tool_input: hook_tool_input_value(&invocation.payload),
+    };
+    let previews = session.hooks().preview_pre_tool_use(&request);
+    if previews.is_empty() {
+        return Ok(());
+    }
+    for run in previews {
+        session
+            .send_event(
+                &turn,
+                EventMsg::HookStarted(HookStartedEvent {
+                    turn_id: Some(turn.sub_id.clone()),
+                    run,
+                }),
+            )
+            .await;
+    }
+    let outcome = session
+        .hooks()
+        .run_pre_tool_use(
+            request,
+            &SessionHookModelRunner::new(Arc::clone(&session), Arc::clone(&turn)),
+        )
+        .await;
+    for completed in outcome.hook_events {
+        session
+            .send_event(&turn, EventMsg::HookCompleted(completed))
+            .await;
+    }
+    if outcome.should_stop {
+        let reason = outcome
+            .stop_reason
+            .unwrap_or_else(|| "no reason given".to_string());
+        return Err(FunctionCallError::Fatal(format!(
+            "PreToolUse hook stopped processing: {reason}"
+        )));
+    }
+    if outcome.should_block {
+        let reason = outcome
+            .block_reason
+            .unwrap_or_else(|| "no reason given".to_string());
+        return Err(FunctionCallError::RespondToModel(format!(
+            "Tool call blocked by PreToolUse hook: {reason}"
+        )));
+    }
+    if let Some(updated_input) = outcome.updated_input
+        && let Err(err) = apply_updated_tool_input(&mut invocation.payload, updated_input)
+    {
+        return Err(FunctionCallError::RespondToModel(format!(
+            "PreToolUse hook returned invalid updatedInput for {}: {err}",
+            invocation.tool_name
+        )));
+    }
+    if let Some(additional_context) = outcome.additional_context {
+        inject_hook_feedback(&session, additional_context).await;
+    }
+    Ok(())
+}
+
+/// Runs `PostToolUse` hooks from hooks.json. The tool has already run, so
+/// blocking feedback and additional context are queued for the model as a
+/// developer message after the tool output.
+async fn run_post_tool_use_hooks(
+    invocation: &ToolInvocation,
+    output_preview: &str,
+    success: bool,
+) -> Result<(), FunctionCallError> {
+    let session = invocation.session.as_ref();
+    let turn = invocation.turn.as_ref();
+    let request = PostToolUseRequest {
+        session_id: session.conversation_id,
+        turn_id: turn.sub_id.clone(),
+        cwd: turn.cwd.clone(),
+        transcript_path: session.current_rollout_path().await,
+        model: turn.model_info.slug.clone(),
+        permission_mode: hook_permission_mode(turn.approval_policy.value()),
+        tool_name: invocation.tool_name.clone(),
+        tool_use_id: invocation.call_id.clone(),
+        tool_input: hook_tool_input_value(&invocation.payload),
+        tool_response: serde_json::json!({
+            "success": success,
+            "output": output_preview,
+        }),
+    };
+    let previews = session.hooks().preview_post_tool_use(&request);
+    if previews.is_empty() {
+        return Ok(());
+    }
+    for run in previews {
+        session
+            .send_event(
+                turn,
+                EventMsg::HookStarted(HookStartedEvent {
+                    turn_id: Some(turn.sub_id.clone()),
+                    run,
+                }),
+            )
+            .await;
+    }
+    let outcome = session
+        .hooks()
+        .run_post_tool_use(
+            request,
+            &SessionHookModelRunner::new(
+                Arc::clone(&invocation.session),
+                Arc::clone(&invocation.turn),
+            ),
+        )
+        .await;
+    for completed in outcome.hook_events {
+        session
+            .send_event(turn, EventMsg::HookCompleted(completed))
+            .await;
+    }
+    if outcome.should_stop {
+        let reason = outcome
+            .stop_reason
+            .unwrap_or_else(|| "no reason given".to_string());
+        return Err(FunctionCallError::Fatal(format!(
+            "PostToolUse hook stopped processing: {reason}"
+        )));
+    }
+    if let Some(feedback) = outcome.feedback_message_for_model {
+        inject_hook_feedback(session, feedback).await;
+    }
+    Ok(())
+}
+
 struct AfterToolUseHookDispatch<'a> {
     invocation: &'a ToolInvocation,
     output_preview: String,
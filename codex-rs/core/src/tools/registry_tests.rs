@@ -48,3 +48,47 @@ fn handler_looks_up_namespaced_aliases_explicitly() {
             .is_some_and(|handler| Arc::ptr_eq(handler, &namespaced_handler))
     );
 }
+
+#[test]
+fn hook_tool_input_value_parses_function_arguments() {
+    let payload = ToolPayload::Function {
+        arguments: r#"{"cmd":"cargo publish"}"#.to_string(),
+    };
+
+    assert_eq!(
+        hook_tool_input_value(&payload),
+        serde_json::json!({"cmd": "cargo publish"})
+    );
+}
+
+#[test]
+fn apply_updated_tool_input_rewrites_function_arguments() {
+    let mut payload = ToolPayload::Function {
+        arguments: r#"{"cmd":"cargo publish"}"#.to_string(),
+    };
+
+    apply_updated_tool_input(
+        &mut payload,
+        serde_json::json!({"cmd": "cargo publish --dry-run"}),
+    )
+    .expect("function payload accepts updated input");
+
+    let ToolPayload::Function { arguments } = payload else {
+        panic!("payload variant should be unchanged");
+    };
+    assert_eq!(arguments, r#"{"cmd":"cargo publish --dry-run"}"#);
+}
+
+#[test]
+fn apply_updated_tool_input_rejects_non_string_custom_input() {
+    let mut payload = ToolPayload::Custom {
+        input: "*** Begin Patch".to_string(),
+    };
+
+    let result = apply_updated_tool_input(&mut payload, serde_json::json!({"input": 1}));
+
+    assert_eq!(
+        result,
+        Err("expected a string for a freeform tool".to_string())
+    );
+}
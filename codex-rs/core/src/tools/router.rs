@@ -259,6 +259,19 @@ impl ToolRouter {
             ));
         }
 
+        if !turn.run_budget.try_start_tool_call() {
+            let err = FunctionCallError::RespondToModel(
+                "this run's --max-tool-calls budget is exhausted; finish without calling more tools"
+                    .to_string(),
+            );
+            return Ok(Self::failure_result(
+                failure_call_id,
+                payload_outputs_custom,
+                payload_outputs_tool_search,
+                err,
+            ));
+        }
+
         let invocation = ToolInvocation {
             session,
             turn,
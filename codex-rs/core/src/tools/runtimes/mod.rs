@@ -16,6 +16,7 @@ use std::collections::HashMap;
 use std::path::Path;
 
 pub mod apply_patch;
+pub mod service;
 pub mod shell;
 pub mod unified_exec;
 
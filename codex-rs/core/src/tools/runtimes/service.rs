@@ -0,0 +1,146 @@
+/*
+Runtime: background services
+
+Handles approval + sandbox selection for `service_start`. Nothing is spawned
+here: the runtime returns the sandbox-wrapped ExecRequest so the background
+service supervisor can start the process, and restart it after a crash, without
+asking for approval again.
+*/
+use crate::command_canonicalization::canonicalize_command_for_approval;
+use crate::exec::ExecExpiration;
+use crate::guardian::GuardianApprovalRequest;
+use crate::guardian::review_approval_request;
+use crate::guardian::routes_approval_to_guardian;
+use crate::sandboxing::ExecRequest;
+use crate::sandboxing::SandboxPermissions;
+use crate::tools::runtimes::build_command_spec;
+use crate::tools::sandboxing::Approvable;
+use crate::tools::sandboxing::ApprovalCtx;
+use crate::tools::sandboxing::ExecApprovalRequirement;
+use crate::tools::sandboxing::SandboxAttempt;
+use crate::tools::sandboxing::Sandboxable;
+use crate::tools::sandboxing::SandboxablePreference;
+use crate::tools::sandboxing::ToolCtx;
+use crate::tools::sandboxing::ToolError;
+use crate::tools::sandboxing::ToolRuntime;
+use crate::tools::sandboxing::with_cached_approval;
+use codex_protocol::protocol::ReviewDecision;
+use futures::future::BoxFuture;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+#[derive(Clone, Debug)]
+pub struct ServiceRequest {
+    pub command: Vec<String>,
+    pub cwd: PathBuf,
+    pub env: HashMap<String, String>,
+    pub exec_approval_requirement: ExecApprovalRequirement,
+}
+
+#[derive(serde::Serialize, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct ServiceApprovalKey {
+    pub command: Vec<String>,
+    pub cwd: PathBuf,
+}
+
+pub struct ServiceRuntime;
+
+impl Sandboxable for ServiceRuntime {
+    fn sandbox_preference(&self) -> SandboxablePreference {
+        SandboxablePreference::Auto
+    }
+
+    // The service has not run yet, so there is no sandbox denial to retry.
+    fn escalate_on_failure(&self) -> bool {
+        false
+    }
+}
+
+impl Approvable<ServiceRequest> for ServiceRuntime {
+    type ApprovalKey = ServiceApprovalKey;
+
+    fn approval_keys(&self, req: &ServiceRequest) -> Vec<Self::ApprovalKey> {
+        vec![ServiceApprovalKey {
+            command: canonicalize_command_for_approval(&req.command),
+            cwd: req.cwd.clone(),
+        }]
+    }
+
+    fn start_approval_async<'b>(
+        &'b mut self,
+        req: &'b ServiceRequest,
+        ctx: ApprovalCtx<'b>,
+    ) -> BoxFuture<'b, ReviewDecision> {
+        let keys = self.approval_keys(req);
+        let session = ctx.session;
+        let turn = ctx.turn;
+        let call_id = ctx.call_id.to_string();
+        let command = req.command.clone();
+        let cwd = req.cwd.clone();
+        let retry_reason = ctx.retry_reason.clone();
+        Box::pin(async move {
+            if routes_approval_to_guardian(turn) {
+                return review_approval_request(
+                    session,
+                    turn,
+                    GuardianApprovalRequest::ExecCommand {
+                        command,
+                        cwd,
+                        sandbox_permissions: SandboxPermissions::UseDefault,
+                        additional_permissions: None,
+                        justification: None,
+                        tty: false,
+                    },
+                    retry_reason,
+                )
+                .await;
+            }
+            with_cached_approval(&session.services, "service_start", keys, || async move {
+                session
+                    .request_command_approval(
+                        turn,
+                        call_id,
+                        None,
+                        command,
+                        cwd,
+                        retry_reason,
+                        ctx.network_approval_context.clone(),
+                        req.exec_approval_requirement
+                            .proposed_execpolicy_amendment()
+                            .cloned(),
+                        None,
+                        None,
+                        None,
+                    )
+                    .await
+            })
+            .await
+        })
+    }
+
+    fn exec_approval_requirement(&self, req: &ServiceRequest) -> Option<ExecApprovalRequirement> {
+        Some(req.exec_approval_requirement.clone())
+    }
+}
+
+impl ToolRuntime<ServiceRequest, ExecRequest> for ServiceRuntime {
+    async fn run(
+        &mut self,
+        req: &ServiceRequest,
+        attempt: &SandboxAttempt<'_>,
+        _ctx: &ToolCtx,
+    ) -> Result<ExecRequest, ToolError> {
+        let spec = build_command_spec(
+            &req.command,
+            &req.cwd,
+            &req.env,
+            ExecExpiration::DefaultTimeout,
+            SandboxPermissions::UseDefault,
+            None,
+            None,
+        )?;
+        attempt
+            .env_for(spec, None)
+            .map_err(|err| ToolError::Codex(err.into()))
+    }
+}
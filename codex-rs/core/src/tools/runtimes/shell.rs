@@ -113,6 +113,7 @@ impl ShellRuntime {
             sub_id: ctx.turn.sub_id.clone(),
             call_id: ctx.call_id.clone(),
             tx_event: ctx.session.get_tx_event(),
+            secret_scrubber: ctx.session.services.tool_secrets.stream_scrubber(),
         })
     }
 }
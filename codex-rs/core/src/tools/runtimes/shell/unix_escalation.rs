@@ -9,6 +9,8 @@ use crate::features::Feature;
 use crate::guardian::GuardianApprovalRequest;
 use crate::guardian::review_approval_request;
 use crate::guardian::routes_approval_to_guardian;
+use crate::landlock::create_linux_resource_limits_command_args;
+use crate::overlay_workspace::ESCALATION_REFUSED_MESSAGE;
 use crate::sandboxing::ExecRequest;
 use crate::sandboxing::SandboxPermissions;
 use crate::shell::ShellType;
@@ -19,6 +21,7 @@ use crate::tools::sandboxing::SandboxAttempt;
 use crate::tools::sandboxing::SandboxablePreference;
 use crate::tools::sandboxing::ToolCtx;
 use crate::tools::sandboxing::ToolError;
+use crate::tools::sandboxing::skill_resource_limits;
 use codex_execpolicy::Decision;
 use codex_execpolicy::Evaluation;
 use codex_execpolicy::MatchOptions;
@@ -28,7 +31,10 @@ use codex_protocol::config_types::WindowsSandboxLevel;
 use codex_protocol::models::MacOsSeatbeltProfileExtensions;
 use codex_protocol::models::PermissionProfile;
 use codex_protocol::permissions::FileSystemSandboxPolicy;
+use codex_protocol::permissions::GlobDenyMatches;
 use codex_protocol::permissions::NetworkSandboxPolicy;
+use codex_protocol::permissions::OverlayWorkspaceLayers;
+use codex_protocol::permissions::ResourceLimits;
 use codex_protocol::protocol::AskForApproval;
 use codex_protocol::protocol::ExecApprovalRequestSkillMetadata;
 use codex_protocol::protocol::NetworkPolicyRuleAction;
@@ -163,6 +169,14 @@ pub(super) async fn try_run_zsh_fork(
             .clone(),
         codex_linux_sandbox_exe: ctx.turn.codex_linux_sandbox_exe.clone(),
         use_legacy_landlock: ctx.turn.features.use_legacy_landlock(),
+        resource_limits: ctx.turn.config.permissions.resource_limits,
+        skill_resource_limits: skill_resource_limits(ctx).await,
+        overlay_workspace: ctx
+            .turn
+            .overlay_workspace
+            .as_ref()
+            .map(|overlay| overlay.layers().clone()),
+        glob_deny_matches: ctx.turn.glob_deny_matches().await.cloned(),
     };
     let main_execve_wrapper_exe = ctx
         .session
@@ -280,6 +294,14 @@ pub(crate) async fn prepare_unified_exec_zsh_fork(
             .clone(),
         codex_linux_sandbox_exe: ctx.turn.codex_linux_sandbox_exe.clone(),
         use_legacy_landlock: ctx.turn.features.use_legacy_landlock(),
+        resource_limits: ctx.turn.config.permissions.resource_limits,
+        skill_resource_limits: skill_resource_limits(ctx).await,
+        overlay_workspace: ctx
+            .turn
+            .overlay_workspace
+            .as_ref()
+            .map(|overlay| overlay.layers().clone()),
+        glob_deny_matches: ctx.turn.glob_deny_matches().await.cloned(),
     };
     let main_execve_wrapper_exe = ctx
         .session
@@ -882,6 +904,12 @@ struct CoreShellCommandExecutor {
     macos_seatbelt_profile_extensions: Option<MacOsSeatbeltProfileExtensions>,
     codex_linux_sandbox_exe: Option<PathBuf>,
     use_legacy_landlock: bool,
+    resource_limits: ResourceLimits,
+    /// Resource limits declared by skills, keyed by each skill's `scripts/`
+    /// directory.
+    skill_resource_limits: Vec<(PathBuf, ResourceLimits)>,
+    overlay_workspace: Option<OverlayWorkspaceLayers>,
+    glob_deny_matches: Option<GlobDenyMatches>,
 }
 
 struct PrepareSandboxedExecParams<'a> {
@@ -892,6 +920,7 @@ struct PrepareSandboxedExecParams<'a> {
     file_system_sandbox_policy: &'a FileSystemSandboxPolicy,
     network_sandbox_policy: NetworkSandboxPolicy,
     additional_permissions: Option<PermissionProfile>,
+    resource_limits: ResourceLimits,
     #[cfg(target_os = "macos")]
     macos_seatbelt_profile_extensions: Option<&'a MacOsSeatbeltProfileExtensions>,
 }
@@ -961,12 +990,33 @@ impl ShellCommandExecutor for CoreShellCommandExecutor {
             ));
         };
 
+        let resource_limits = self.resource_limits_for(program);
         let prepared = match execution {
-            EscalationExecution::Unsandboxed => PreparedExec {
-                command,
-                cwd: workdir.to_path_buf(),
-                env,
-                arg0: Some(first_arg.clone()),
+            EscalationExecution::Unsandboxed if self.overlay_workspace.is_some() => {
+                return Err(anyhow::anyhow!(ESCALATION_REFUSED_MESSAGE));
+            }
+            EscalationExecution::Unsandboxed => match self.codex_linux_sandbox_exe.as_ref() {
+                // Escalation lifts the sandbox but not the resource limits.
+                Some(exe) if !resource_limits.is_empty() => {
+                    let mut wrapped = vec![exe.to_string_lossy().to_string()];
+                    wrapped.extend(create_linux_resource_limits_command_args(
+                        command,
+                        resource_limits,
+                        &self.sandbox_policy_cwd,
+                    ));
+                    PreparedExec {
+                        command: wrapped,
+                        cwd: workdir.to_path_buf(),
+                        env,
+                        arg0: Some("codex-linux-sandbox".to_string()),
+                    }
+                }
+                _ => PreparedExec {
+                    command,
+                    cwd: workdir.to_path_buf(),
+                    env,
+                    arg0: Some(first_arg.clone()),
+                },
             },
             EscalationExecution::TurnDefault => {
                 self.prepare_sandboxed_exec(PrepareSandboxedExecParams {
@@ -977,6 +1027,7 @@ impl ShellCommandExecutor for CoreShellCommandExecutor {
                     file_system_sandbox_policy: &self.file_system_sandbox_policy,
                     network_sandbox_policy: self.network_sandbox_policy,
                     additional_permissions: None,
+                    resource_limits,
                     #[cfg(target_os = "macos")]
                     macos_seatbelt_profile_extensions: self
                         .macos_seatbelt_profile_extensions
@@ -996,6 +1047,7 @@ impl ShellCommandExecutor for CoreShellCommandExecutor {
                     file_system_sandbox_policy: &self.file_system_sandbox_policy,
                     network_sandbox_policy: self.network_sandbox_policy,
                     additional_permissions: Some(permission_profile),
+                    resource_limits,
                     #[cfg(target_os = "macos")]
                     macos_seatbelt_profile_extensions: self
                         .macos_seatbelt_profile_extensions
@@ -1012,6 +1064,7 @@ impl ShellCommandExecutor for CoreShellCommandExecutor {
                     file_system_sandbox_policy: &permissions.file_system_sandbox_policy,
                     network_sandbox_policy: permissions.network_sandbox_policy,
                     additional_permissions: None,
+                    resource_limits,
                     #[cfg(target_os = "macos")]
                     macos_seatbelt_profile_extensions: permissions
                         .macos_seatbelt_profile_extensions
@@ -1025,6 +1078,17 @@ impl ShellCommandExecutor for CoreShellCommandExecutor {
 }
 
 impl CoreShellCommandExecutor {
+    /// Limits for an intercepted exec: the turn's limits, tightened by those of
+    /// the skill whose script is being run.
+    fn resource_limits_for(&self, program: &AbsolutePathBuf) -> ResourceLimits {
+        self.skill_resource_limits
+            .iter()
+            .find(|(scripts_dir, _)| program.as_path().starts_with(scripts_dir))
+            .map_or(self.resource_limits, |(_, skill_resource_limits)| {
+                self.resource_limits.tightened_by(*skill_resource_limits)
+            })
+    }
+
     #[allow(clippy::too_many_arguments)]
     fn prepare_sandboxed_exec(
         &self,
@@ -1038,6 +1102,7 @@ impl CoreShellCommandExecutor {
             file_system_sandbox_policy,
             network_sandbox_policy,
             additional_permissions,
+            resource_limits,
             #[cfg(target_os = "macos")]
             macos_seatbelt_profile_extensions,
         } = params;
@@ -1079,6 +1144,9 @@ impl CoreShellCommandExecutor {
                 macos_seatbelt_profile_extensions,
                 codex_linux_sandbox_exe: self.codex_linux_sandbox_exe.as_ref(),
                 use_legacy_landlock: self.use_legacy_landlock,
+                resource_limits,
+                overlay_workspace: self.overlay_workspace.as_ref(),
+                glob_deny_matches: self.glob_deny_matches.as_ref(),
                 windows_sandbox_level: self.windows_sandbox_level,
             })?;
         if let Some(network) = exec_request.network.as_ref() {
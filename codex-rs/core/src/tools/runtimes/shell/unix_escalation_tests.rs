@@ -39,6 +39,7 @@ use codex_protocol::permissions::FileSystemSandboxEntry;
 use codex_protocol::permissions::FileSystemSandboxPolicy;
 use codex_protocol::permissions::FileSystemSpecialPath;
 use codex_protocol::permissions::NetworkSandboxPolicy;
+use codex_protocol::permissions::ResourceLimits;
 use codex_protocol::protocol::SkillScope;
 use codex_shell_escalation::EscalationExecution;
 use codex_shell_escalation::EscalationPermissions;
@@ -93,6 +94,7 @@ fn test_skill_metadata(permission_profile: Option<PermissionProfile>) -> SkillMe
         policy: None,
         permission_profile,
         managed_network_override: None,
+        resource_limits: None,
         path_to_skills_md: PathBuf::from("/tmp/skill/SKILL.md"),
         scope: SkillScope::User,
     }
@@ -670,6 +672,9 @@ async fn prepare_escalated_exec_turn_default_preserves_macos_seatbelt_extensions
         }),
         codex_linux_sandbox_exe: None,
         use_legacy_landlock: false,
+        resource_limits: ResourceLimits::default(),
+        skill_resource_limits: Vec::new(),
+        overlay_workspace: None,
     };
 
     let prepared = executor
@@ -719,6 +724,9 @@ async fn prepare_escalated_exec_permissions_preserve_macos_seatbelt_extensions()
         macos_seatbelt_profile_extensions: None,
         codex_linux_sandbox_exe: None,
         use_legacy_landlock: false,
+        resource_limits: ResourceLimits::default(),
+        skill_resource_limits: Vec::new(),
+        overlay_workspace: None,
     };
 
     let permissions = Permissions {
@@ -734,6 +742,7 @@ async fn prepare_escalated_exec_permissions_preserve_macos_seatbelt_extensions()
             macos_preferences: MacOsPreferencesPermission::ReadWrite,
             ..Default::default()
         }),
+        resource_limits: ResourceLimits::default(),
     };
 
     let prepared = executor
@@ -796,6 +805,9 @@ async fn prepare_escalated_exec_permission_profile_unions_turn_and_requested_mac
         }),
         codex_linux_sandbox_exe: None,
         use_legacy_landlock: false,
+        resource_limits: ResourceLimits::default(),
+        skill_resource_limits: Vec::new(),
+        overlay_workspace: None,
     };
 
     let prepared = executor
@@ -838,3 +850,57 @@ async fn prepare_escalated_exec_permission_profile_unions_turn_and_requested_mac
         prepared.command
     );
 }
+
+#[test]
+fn skill_scripts_tighten_turn_resource_limits() {
+    let cwd = AbsolutePathBuf::from_absolute_path(std::env::temp_dir()).unwrap();
+    let turn_limits = ResourceLimits {
+        memory_max_bytes: Some(4 << 30),
+        wall_time_seconds: Some(600),
+        ..Default::default()
+    };
+    let executor = CoreShellCommandExecutor {
+        command: vec!["echo".to_string(), "ok".to_string()],
+        cwd: cwd.to_path_buf(),
+        env: HashMap::new(),
+        network: None,
+        sandbox: SandboxType::None,
+        sandbox_policy: SandboxPolicy::new_read_only_policy(),
+        file_system_sandbox_policy: read_only_file_system_sandbox_policy(),
+        network_sandbox_policy: NetworkSandboxPolicy::Restricted,
+        windows_sandbox_level: WindowsSandboxLevel::Disabled,
+        sandbox_permissions: SandboxPermissions::UseDefault,
+        justification: None,
+        arg0: None,
+        sandbox_policy_cwd: cwd.to_path_buf(),
+        macos_seatbelt_profile_extensions: None,
+        codex_linux_sandbox_exe: None,
+        use_legacy_landlock: false,
+        resource_limits: turn_limits,
+        skill_resource_limits: vec![(
+            PathBuf::from("/skills/build/scripts"),
+            ResourceLimits {
+                memory_max_bytes: Some(1 << 30),
+                pids_max: Some(64),
+                ..Default::default()
+            },
+        )],
+        overlay_workspace: None,
+    };
+
+    assert_eq!(
+        executor.resource_limits_for(
+            &AbsolutePathBuf::from_absolute_path("/skills/build/scripts/run.sh").unwrap()
+        ),
+        ResourceLimits {
+            memory_max_bytes: Some(1 << 30),
+            pids_max: Some(64),
+            wall_time_seconds: Some(600),
+            ..Default::default()
+        }
+    );
+    assert_eq!(
+        executor.resource_limits_for(&AbsolutePathBuf::from_absolute_path("/bin/echo").unwrap()),
+        turn_limits
+    );
+}
@@ -20,7 +20,10 @@ use codex_protocol::approvals::ExecPolicyAmendment;
 use codex_protocol::approvals::NetworkApprovalContext;
 use codex_protocol::permissions::FileSystemSandboxKind;
 use codex_protocol::permissions::FileSystemSandboxPolicy;
+use codex_protocol::permissions::GlobDenyMatches;
 use codex_protocol::permissions::NetworkSandboxPolicy;
+use codex_protocol::permissions::OverlayWorkspaceLayers;
+use codex_protocol::permissions::ResourceLimits;
 use codex_protocol::protocol::AskForApproval;
 use codex_protocol::protocol::ReviewDecision;
 use futures::Future;
@@ -30,6 +33,7 @@ use std::collections::HashMap;
 use std::fmt::Debug;
 use std::hash::Hash;
 use std::path::Path;
+use std::path::PathBuf;
 use std::sync::Arc;
 
 #[derive(Clone, Default, Debug)]
@@ -332,6 +336,12 @@ pub(crate) struct SandboxAttempt<'a> {
     pub(crate) sandbox_cwd: &'a Path,
     pub codex_linux_sandbox_exe: Option<&'a std::path::PathBuf>,
     pub use_legacy_landlock: bool,
+    pub resource_limits: ResourceLimits,
+    /// Resource limits declared by skills, keyed by each skill's `scripts/`
+    /// directory.
+    pub skill_resource_limits: &'a [(PathBuf, ResourceLimits)],
+    pub overlay_workspace: Option<&'a OverlayWorkspaceLayers>,
+    pub glob_deny_matches: Option<&'a GlobDenyMatches>,
     pub windows_sandbox_level: codex_protocol::config_types::WindowsSandboxLevel,
 }
 
@@ -341,6 +351,16 @@ impl<'a> SandboxAttempt<'a> {
         spec: CommandSpec,
         network: Option<&NetworkProxy>,
     ) -> Result<crate::sandboxing::ExecRequest, SandboxTransformError> {
+        let command = std::iter::once(&spec.program)
+            .chain(&spec.args)
+            .cloned()
+            .collect::<Vec<_>>();
+        let resource_limits = resource_limits_for_command(
+            self.resource_limits,
+            self.skill_resource_limits,
+            &command,
+            &spec.cwd,
+        );
         self.manager
             .transform(crate::sandboxing::SandboxTransformRequest {
                 spec,
@@ -355,11 +375,56 @@ impl<'a> SandboxAttempt<'a> {
                 macos_seatbelt_profile_extensions: None,
                 codex_linux_sandbox_exe: self.codex_linux_sandbox_exe,
                 use_legacy_landlock: self.use_legacy_landlock,
+                resource_limits,
+                overlay_workspace: self.overlay_workspace,
+                glob_deny_matches: self.glob_deny_matches,
                 windows_sandbox_level: self.windows_sandbox_level,
             })
     }
 }
 
+/// Resource limits declared by skills, keyed by each skill's `scripts/`
+/// directory.
+pub(crate) async fn skill_resource_limits(ctx: &ToolCtx) -> Vec<(PathBuf, ResourceLimits)> {
+    let force_reload = false;
+    ctx.session
+        .services
+        .skills_manager
+        .skills_for_cwd(&ctx.turn.cwd, force_reload)
+        .await
+        .skills
+        .into_iter()
+        .filter_map(|skill| {
+            let resource_limits = skill.resource_limits?;
+            let skill_root = skill.path_to_skills_md.parent()?;
+            Some((skill_root.join("scripts"), resource_limits))
+        })
+        .collect()
+}
+
+/// The turn's limits, tightened by those of every skill whose `scripts/`
+/// directory `command` refers to. Arguments are split into shell words, so a
+/// script run through `bash -lc "python skills/x/scripts/run.py"` counts too.
+pub(crate) fn resource_limits_for_command(
+    resource_limits: ResourceLimits,
+    skill_resource_limits: &[(PathBuf, ResourceLimits)],
+    command: &[String],
+    cwd: &Path,
+) -> ResourceLimits {
+    let paths = command
+        .iter()
+        .flat_map(|arg| arg.split(|c: char| c.is_whitespace() || "'\";&|()".contains(c)))
+        .filter(|word| !word.is_empty())
+        .map(|word| cwd.join(word))
+        .collect::<Vec<_>>();
+    skill_resource_limits
+        .iter()
+        .filter(|(scripts_dir, _)| paths.iter().any(|path| path.starts_with(scripts_dir)))
+        .fold(resource_limits, |limits, (_, skill_resource_limits)| {
+            limits.tightened_by(*skill_resource_limits)
+        })
+}
+
 #[cfg(test)]
 #[path = "sandboxing_tests.rs"]
 mod tests;
@@ -108,3 +108,58 @@ fn guardian_bypasses_sandbox_for_explicit_escalation_on_first_attempt() {
         SandboxOverride::BypassSandboxFirstAttempt
     );
 }
+
+#[test]
+fn skill_scripts_in_shell_commands_tighten_resource_limits() {
+    let cwd = PathBuf::from("/repo");
+    let turn_limits = ResourceLimits {
+        memory_max_bytes: Some(4 * 1024 * 1024 * 1024),
+        pids_max: Some(256),
+        ..ResourceLimits::default()
+    };
+    let skill_limits = ResourceLimits {
+        memory_max_bytes: Some(512 * 1024 * 1024),
+        wall_time_seconds: Some(60),
+        ..ResourceLimits::default()
+    };
+    let skills = vec![(
+        PathBuf::from("/repo/.codex/skills/build/scripts"),
+        skill_limits,
+    )];
+    let tightened = ResourceLimits {
+        memory_max_bytes: Some(512 * 1024 * 1024),
+        pids_max: Some(256),
+        wall_time_seconds: Some(60),
+        ..ResourceLimits::default()
+    };
+
+    let shell = |script: &str| {
+        vec![
+            "/bin/bash".to_string(),
+            "-lc".to_string(),
+            script.to_string(),
+        ]
+    };
+    assert_eq!(
+        resource_limits_for_command(
+            turn_limits,
+            &skills,
+            &shell("cd src && python '.codex/skills/build/scripts/run.py' --fast"),
+            &cwd,
+        ),
+        tightened
+    );
+    assert_eq!(
+        resource_limits_for_command(
+            turn_limits,
+            &skills,
+            &["/repo/.codex/skills/build/scripts/run.sh".to_string()],
+            &cwd,
+        ),
+        tightened
+    );
+    assert_eq!(
+        resource_limits_for_command(turn_limits, &skills, &shell("cargo build"), &cwd),
+        turn_limits
+    );
+}
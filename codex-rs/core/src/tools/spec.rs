@@ -236,6 +236,8 @@ pub(crate) struct ToolsConfig {
     pub experimental_supported_tools: Vec<String>,
     pub agent_jobs_tools: bool,
     pub agent_jobs_worker_tools: bool,
+    pub read_tool_output: bool,
+    pub background_services: bool,
 }
 
 pub(crate) struct ToolsConfigParams<'a> {
@@ -291,6 +293,8 @@ impl ToolsConfig {
             features.enabled(Feature::ImageGeneration) && supports_image_generation(model_info);
         let exec_permission_approvals_enabled = features.enabled(Feature::ExecPermissionApprovals);
         let request_permissions_tool_enabled = features.enabled(Feature::RequestPermissionsTool);
+        let include_read_tool_output = features.enabled(Feature::SpillToolOutput);
+        let include_background_services = features.enabled(Feature::BackgroundServices);
         let shell_command_backend =
             if features.enabled(Feature::ShellTool) && features.enabled(Feature::ShellZshFork) {
                 ShellCommandBackendConfig::ZshFork
@@ -373,6 +377,8 @@ impl ToolsConfig {
             experimental_supported_tools: model_info.experimental_supported_tools.clone(),
             agent_jobs_tools: include_agent_jobs,
             agent_jobs_worker_tools,
+            read_tool_output: include_read_tool_output,
+            background_services: include_background_services,
         }
     }
 
@@ -575,6 +581,29 @@ fn create_approval_parameters(
     properties
 }
 
+fn create_wait_parameters() -> BTreeMap<String, JsonSchema> {
+    BTreeMap::from([
+        (
+            "wait_for".to_string(),
+            JsonSchema::String {
+                description: Some(
+                    "Optional regex. Yield as soon as the output matches it, for example a server's ready line, instead of waiting out the yield time."
+                        .to_string(),
+                ),
+            },
+        ),
+        (
+            "wait_for_exit".to_string(),
+            JsonSchema::Boolean {
+                description: Some(
+                    "Wait for the process to exit. With `wait_for` or `wait_for_exit` and no `yield_time_ms`, the call waits up to the maximum yield time."
+                        .to_string(),
+                ),
+            },
+        ),
+    ])
+}
+
 fn create_exec_command_tool(
     allow_login_shell: bool,
     exec_permission_approvals_enabled: bool,
@@ -638,6 +667,7 @@ fn create_exec_command_tool(
             },
         );
     }
+    properties.extend(create_wait_parameters());
     properties.extend(create_approval_parameters(
         exec_permission_approvals_enabled,
     ));
@@ -659,7 +689,7 @@ fn create_exec_command_tool(
 }
 
 fn create_write_stdin_tool() -> ToolSpec {
-    let properties = BTreeMap::from([
+    let mut properties = BTreeMap::from([
         (
             "session_id".to_string(),
             JsonSchema::Number {
@@ -690,6 +720,7 @@ fn create_write_stdin_tool() -> ToolSpec {
             },
         ),
     ]);
+    properties.extend(create_wait_parameters());
 
     ToolSpec::Function(ResponsesApiTool {
         name: "write_stdin".to_string(),
@@ -1949,6 +1980,178 @@ fn create_list_dir_tool() -> ToolSpec {
     })
 }
 
+fn create_read_tool_output_tool() -> ToolSpec {
+    let properties = BTreeMap::from([
+        (
+            "id".to_string(),
+            JsonSchema::String {
+                description: Some(
+                    "Id named in the truncation marker of the output to read.".to_string(),
+                ),
+            },
+        ),
+        (
+            "offset".to_string(),
+            JsonSchema::Number {
+                description: Some(
+                    "The line number to start reading from. Must be 1 or greater.".to_string(),
+                ),
+            },
+        ),
+        (
+            "limit".to_string(),
+            JsonSchema::Number {
+                description: Some("The maximum number of lines to return.".to_string()),
+            },
+        ),
+        (
+            "pattern".to_string(),
+            JsonSchema::String {
+                description: Some(
+                    "Optional regular expression; only matching lines are returned.".to_string(),
+                ),
+            },
+        ),
+    ]);
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: "read_tool_output".to_string(),
+        description:
+            "Reads the full output of an earlier command whose output was truncated, with 1-indexed line numbers. Use `pattern` to return only the lines that match."
+                .to_string(),
+        strict: false,
+        defer_loading: None,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["id".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+        output_schema: None,
+    })
+}
+
+fn create_service_start_tool() -> ToolSpec {
+    let properties = BTreeMap::from([
+        (
+            "name".to_string(),
+            JsonSchema::String {
+                description: Some(
+                    "Name of the service, using letters, digits, `-` and `_`.".to_string(),
+                ),
+            },
+        ),
+        (
+            "cmd".to_string(),
+            JsonSchema::String {
+                description: Some(
+                    "Shell command that runs the service. Omit to start a service that is already defined, such as one from the user's config."
+                        .to_string(),
+                ),
+            },
+        ),
+        (
+            "workdir".to_string(),
+            JsonSchema::String {
+                description: Some(
+                    "Optional working directory to run the service in; defaults to the turn cwd."
+                        .to_string(),
+                ),
+            },
+        ),
+        (
+            "health_check_port".to_string(),
+            JsonSchema::Number {
+                description: Some(
+                    "Optional TCP port on localhost that accepts connections once the service is healthy."
+                        .to_string(),
+                ),
+            },
+        ),
+        (
+            "restart".to_string(),
+            JsonSchema::String {
+                description: Some(
+                    "When to restart the service after it exits: `on-failure` (default), `always` or `never`."
+                        .to_string(),
+                ),
+            },
+        ),
+    ]);
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: "service_start".to_string(),
+        description: "Starts a long-running background service such as a database, dev server or file watcher. The service keeps running across turns and is restarted according to its restart policy; use service_status to read its output."
+            .to_string(),
+        strict: false,
+        defer_loading: None,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["name".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+        output_schema: None,
+    })
+}
+
+fn create_service_stop_tool() -> ToolSpec {
+    let properties = BTreeMap::from([(
+        "name".to_string(),
+        JsonSchema::String {
+            description: Some("Name of the service to stop.".to_string()),
+        },
+    )]);
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: "service_stop".to_string(),
+        description:
+            "Stops a running background service. It can be started again with service_start."
+                .to_string(),
+        strict: false,
+        defer_loading: None,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["name".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+        output_schema: None,
+    })
+}
+
+fn create_service_status_tool() -> ToolSpec {
+    let properties = BTreeMap::from([
+        (
+            "name".to_string(),
+            JsonSchema::String {
+                description: Some(
+                    "Service to inspect. Omit to list every service without logs.".to_string(),
+                ),
+            },
+        ),
+        (
+            "lines".to_string(),
+            JsonSchema::Number {
+                description: Some(
+                    "Number of recent log lines to return for `name`; defaults to 50.".to_string(),
+                ),
+            },
+        ),
+    ]);
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: "service_status".to_string(),
+        description: "Reports the status, health and restart count of background services, and the recent output of one service."
+            .to_string(),
+        strict: false,
+        defer_loading: None,
+        parameters: JsonSchema::Object {
+            properties,
+            required: None,
+            additional_properties: Some(false.into()),
+        },
+        output_schema: None,
+    })
+}
+
 fn create_js_repl_tool() -> ToolSpec {
     // Keep JS input freeform, but block the most common malformed payload shapes
     // (JSON wrappers, quoted strings, and markdown fences) before they reach the
@@ -2163,6 +2366,52 @@ fn create_read_mcp_resource_tool() -> ToolSpec {
     })
 }
 
+fn create_subscribe_mcp_resource_tool() -> ToolSpec {
+    let properties = BTreeMap::from([
+        (
+            "server".to_string(),
+            JsonSchema::String {
+                description: Some(
+                    "MCP server name exactly as configured. Must match the 'server' field returned by list_mcp_resources."
+                        .to_string(),
+                ),
+            },
+        ),
+        (
+            "uri".to_string(),
+            JsonSchema::String {
+                description: Some(
+                    "Resource URI to follow. Must be one of the URIs returned by list_mcp_resources."
+                        .to_string(),
+                ),
+            },
+        ),
+        (
+            "unsubscribe".to_string(),
+            JsonSchema::Boolean {
+                description: Some(
+                    "Set to true to stop following a resource you subscribed to earlier."
+                        .to_string(),
+                ),
+            },
+        ),
+    ]);
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: "subscribe_mcp_resource".to_string(),
+        description: "Follow changes to a resource on an MCP server that supports subscriptions. When the resource changes, its new contents are added to the conversation at the start of the next turn."
+            .to_string(),
+        strict: false,
+        defer_loading: None,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["server".to_string(), "uri".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+        output_schema: None,
+    })
+}
+
 /// TODO(dylan): deprecate once we get rid of json tool
 #[derive(Serialize, Deserialize)]
 pub(crate) struct ApplyPatchToolArgs {
@@ -2445,6 +2694,7 @@ pub(crate) fn build_specs_with_discoverable_tools(
 ) -> ToolRegistryBuilder {
     use crate::tools::handlers::ApplyPatchHandler;
     use crate::tools::handlers::ArtifactsHandler;
+    use crate::tools::handlers::BackgroundServicesHandler;
     use crate::tools::handlers::CodeModeExecuteHandler;
     use crate::tools::handlers::CodeModeWaitHandler;
     use crate::tools::handlers::DynamicToolHandler;
@@ -2456,6 +2706,7 @@ pub(crate) fn build_specs_with_discoverable_tools(
     use crate::tools::handlers::McpResourceHandler;
     use crate::tools::handlers::PlanHandler;
     use crate::tools::handlers::ReadFileHandler;
+    use crate::tools::handlers::ReadToolOutputHandler;
     use crate::tools::handlers::RequestPermissionsHandler;
     use crate::tools::handlers::RequestUserInputHandler;
     use crate::tools::handlers::ShellCommandHandler;
@@ -2608,9 +2859,16 @@ pub(crate) fn build_specs_with_discoverable_tools(
             true,
             config.code_mode_enabled,
         );
+        push_tool_spec(
+            &mut builder,
+            create_subscribe_mcp_resource_tool(),
+            true,
+            config.code_mode_enabled,
+        );
         builder.register_handler("list_mcp_resources", mcp_resource_handler.clone());
         builder.register_handler("list_mcp_resource_templates", mcp_resource_handler.clone());
-        builder.register_handler("read_mcp_resource", mcp_resource_handler);
+        builder.register_handler("read_mcp_resource", mcp_resource_handler.clone());
+        builder.register_handler("subscribe_mcp_resource", mcp_resource_handler);
     }
 
     push_tool_spec(
@@ -2754,6 +3012,31 @@ pub(crate) fn build_specs_with_discoverable_tools(
         builder.register_handler("list_dir", list_dir_handler);
     }
 
+    if config.read_tool_output {
+        let read_tool_output_handler = Arc::new(ReadToolOutputHandler);
+        push_tool_spec(
+            &mut builder,
+            create_read_tool_output_tool(),
+            true,
+            config.code_mode_enabled,
+        );
+        builder.register_handler("read_tool_output", read_tool_output_handler);
+    }
+
+    if config.background_services {
+        let background_services_handler = Arc::new(BackgroundServicesHandler);
+        for spec in [
+            create_service_start_tool(),
+            create_service_stop_tool(),
+            create_service_status_tool(),
+        ] {
+            push_tool_spec(&mut builder, spec, false, config.code_mode_enabled);
+        }
+        builder.register_handler("service_start", background_services_handler.clone());
+        builder.register_handler("service_stop", background_services_handler.clone());
+        builder.register_handler("service_status", background_services_handler);
+    }
+
     if config
         .experimental_supported_tools
         .contains(&"test_sync_tool".to_string())
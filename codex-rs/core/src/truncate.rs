@@ -77,24 +77,49 @@ impl std::ops::Mul<f64> for TruncationPolicy {
 }
 
 pub(crate) fn formatted_truncate_text(content: &str, policy: TruncationPolicy) -> String {
-    if content.len() <= policy.byte_budget() {
+    formatted_truncate_text_with_spill(content, policy, None)
+}
+
+/// Like [`formatted_truncate_text`], but when the full content was spilled to
+/// a tool output file the marker names it so the model can page through it.
+pub(crate) fn formatted_truncate_text_with_spill(
+    content: &str,
+    policy: TruncationPolicy,
+    spilled_output_id: Option<&str>,
+) -> String {
+    if !exceeds_budget(content, policy) {
         return content.to_string();
     }
     let total_lines = content.lines().count();
-    let result = truncate_text(content, policy);
+    let result = truncate_text_with_spill(content, policy, spilled_output_id);
     format!("Total output lines: {total_lines}\n\n{result}")
 }
 
 pub(crate) fn truncate_text(content: &str, policy: TruncationPolicy) -> String {
+    truncate_text_with_spill(content, policy, None)
+}
+
+pub(crate) fn truncate_text_with_spill(
+    content: &str,
+    policy: TruncationPolicy,
+    spilled_output_id: Option<&str>,
+) -> String {
     match policy {
-        TruncationPolicy::Bytes(_) => truncate_with_byte_estimate(content, policy),
+        TruncationPolicy::Bytes(_) => {
+            truncate_with_byte_estimate(content, policy, spilled_output_id)
+        }
         TruncationPolicy::Tokens(_) => {
-            let (truncated, _) = truncate_with_token_budget(content, policy);
+            let (truncated, _) = truncate_with_token_budget(content, policy, spilled_output_id);
             truncated
         }
     }
 }
 
+/// Whether `content` would be truncated under `policy`.
+pub(crate) fn exceeds_budget(content: &str, policy: TruncationPolicy) -> bool {
+    content.len() > policy.byte_budget()
+}
+
 pub(crate) fn formatted_truncate_text_content_items_with_policy(
     items: &[FunctionCallOutputContentItem],
     policy: TruncationPolicy,
@@ -205,7 +230,11 @@ pub(crate) fn truncate_function_output_items_with_policy(
 /// preserving the beginning and the end. Returns the possibly truncated string
 /// and `Some(original_token_count)` if truncation occurred; otherwise returns
 /// the original string and `None`.
-fn truncate_with_token_budget(s: &str, policy: TruncationPolicy) -> (String, Option<u64>) {
+fn truncate_with_token_budget(
+    s: &str,
+    policy: TruncationPolicy,
+    spilled_output_id: Option<&str>,
+) -> (String, Option<u64>) {
     if s.is_empty() {
         return (String::new(), None);
     }
@@ -216,7 +245,7 @@ fn truncate_with_token_budget(s: &str, policy: TruncationPolicy) -> (String, Opt
         return (s.to_string(), None);
     }
 
-    let truncated = truncate_with_byte_estimate(s, policy);
+    let truncated = truncate_with_byte_estimate(s, policy, spilled_output_id);
     let approx_total_usize = approx_token_count(s);
     let approx_total = u64::try_from(approx_total_usize).unwrap_or(u64::MAX);
     if truncated == s {
@@ -229,7 +258,11 @@ fn truncate_with_token_budget(s: &str, policy: TruncationPolicy) -> (String, Opt
 /// Truncate a string using a byte budget derived from the token budget, without
 /// performing any real tokenization. This keeps the logic purely byte-based and
 /// uses a bytes placeholder in the truncated output.
-fn truncate_with_byte_estimate(s: &str, policy: TruncationPolicy) -> String {
+fn truncate_with_byte_estimate(
+    s: &str,
+    policy: TruncationPolicy,
+    spilled_output_id: Option<&str>,
+) -> String {
     if s.is_empty() {
         return String::new();
     }
@@ -242,6 +275,7 @@ fn truncate_with_byte_estimate(s: &str, policy: TruncationPolicy) -> String {
         let marker = format_truncation_marker(
             policy,
             removed_units_for_source(policy, s.len(), total_chars),
+            spilled_output_id,
         );
         return marker;
     }
@@ -259,6 +293,7 @@ fn truncate_with_byte_estimate(s: &str, policy: TruncationPolicy) -> String {
     let marker = format_truncation_marker(
         policy,
         removed_units_for_source(policy, total_bytes.saturating_sub(max_bytes), removed_chars),
+        spilled_output_id,
     );
 
     assemble_truncated_output(left, right, &marker)
@@ -304,10 +339,20 @@ fn split_string(s: &str, beginning_bytes: usize, end_bytes: usize) -> (usize, &s
     (removed_chars, before, after)
 }
 
-fn format_truncation_marker(policy: TruncationPolicy, removed_count: u64) -> String {
-    match policy {
-        TruncationPolicy::Tokens(_) => format!("…{removed_count} tokens truncated…"),
-        TruncationPolicy::Bytes(_) => format!("…{removed_count} chars truncated…"),
+fn format_truncation_marker(
+    policy: TruncationPolicy,
+    removed_count: u64,
+    spilled_output_id: Option<&str>,
+) -> String {
+    let unit = match policy {
+        TruncationPolicy::Tokens(_) => "tokens",
+        TruncationPolicy::Bytes(_) => "chars",
+    };
+    match spilled_output_id {
+        Some(id) => format!(
+            "…{removed_count} {unit} truncated; read_tool_output with id \"{id}\" pages through the full output…"
+        ),
+        None => format!("…{removed_count} {unit} truncated…"),
     }
 }
 
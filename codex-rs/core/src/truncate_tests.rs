@@ -5,6 +5,7 @@ use super::formatted_truncate_text_content_items_with_policy;
 use super::split_string;
 use super::truncate_function_output_items_with_policy;
 use super::truncate_text;
+use super::truncate_text_with_spill;
 use super::truncate_with_token_budget;
 use codex_protocol::models::FunctionCallOutputContentItem;
 use pretty_assertions::assert_eq;
@@ -130,7 +131,7 @@ fn truncate_tokens_reports_original_line_count_when_truncated() {
 fn truncate_with_token_budget_returns_original_when_under_limit() {
     let s = "short output";
     let limit = 100;
-    let (out, original) = truncate_with_token_budget(s, TruncationPolicy::Tokens(limit));
+    let (out, original) = truncate_with_token_budget(s, TruncationPolicy::Tokens(limit), None);
     assert_eq!(out, s);
     assert_eq!(original, None);
 }
@@ -138,7 +139,7 @@ fn truncate_with_token_budget_returns_original_when_under_limit() {
 #[test]
 fn truncate_with_token_budget_reports_truncation_at_zero_limit() {
     let s = "abcdef";
-    let (out, original) = truncate_with_token_budget(s, TruncationPolicy::Tokens(0));
+    let (out, original) = truncate_with_token_budget(s, TruncationPolicy::Tokens(0), None);
     assert_eq!(out, "…2 tokens truncated…");
     assert_eq!(original, Some(2));
 }
@@ -146,7 +147,7 @@ fn truncate_with_token_budget_reports_truncation_at_zero_limit() {
 #[test]
 fn truncate_middle_tokens_handles_utf8_content() {
     let s = "😀😀😀😀😀😀😀😀😀😀\nsecond line with text\n";
-    let (out, tokens) = truncate_with_token_budget(s, TruncationPolicy::Tokens(8));
+    let (out, tokens) = truncate_with_token_budget(s, TruncationPolicy::Tokens(8), None);
     assert_eq!(out, "😀😀😀😀…8 tokens truncated… line with text\n");
     assert_eq!(tokens, Some(16));
 }
@@ -158,6 +159,16 @@ fn truncate_middle_bytes_handles_utf8_content() {
     assert_eq!(out, "😀😀…21 chars truncated…with text\n");
 }
 
+#[test]
+fn truncation_marker_names_the_spilled_output() {
+    let s = "😀😀😀😀😀😀😀😀😀😀\nsecond line with text\n";
+    let out = truncate_text_with_spill(s, TruncationPolicy::Bytes(20), Some("3fa9c1e2"));
+    assert_eq!(
+        out,
+        "😀😀…21 chars truncated; read_tool_output with id \"3fa9c1e2\" pages through the full output…with text\n"
+    );
+}
+
 #[test]
 fn truncates_across_multiple_under_limit_texts_and_reports_omitted() {
     let chunk = "alpha beta gamma delta epsilon zeta eta theta iota kappa lambda mu nu xi omicron pi rho sigma tau upsilon phi chi psi omega.\n";
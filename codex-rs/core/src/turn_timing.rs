@@ -57,6 +57,10 @@ impl TurnTimingState {
         state.first_message_at = None;
     }
 
+    pub(crate) async fn started_at(&self) -> Option<Instant> {
+        self.state.lock().await.started_at
+    }
+
     pub(crate) async fn record_ttft_for_response_event(
         &self,
         event: &ResponseEvent,
@@ -35,7 +35,7 @@ const UNIFIED_EXEC_OUTPUT_DELTA_MAX_BYTES: usize = 8192;
 
 /// Spawn a background task that continuously reads from the PTY, appends to the
 /// shared transcript, and emits ExecCommandOutputDelta events on UTF‑8
-/// boundaries.
+/// boundaries. Exposed secrets are scrubbed before either sees the output.
 pub(crate) fn start_streaming_output(
     process: &UnifiedExecProcess,
     context: &UnifiedExecContext,
@@ -48,6 +48,7 @@ pub(crate) fn start_streaming_output(
     let session_ref = Arc::clone(&context.session);
     let turn_ref = Arc::clone(&context.turn);
     let call_id = context.call_id.clone();
+    let mut scrubber = context.session.services.tool_secrets.stream_scrubber();
 
     tokio::spawn(async move {
         use tokio::sync::broadcast::error::RecvError;
@@ -69,6 +70,15 @@ pub(crate) fn start_streaming_output(
                         sleep.as_mut().await;
                     }
                 }, if grace_sleep.is_some() => {
+                    process_chunk(
+                        &mut pending,
+                        &transcript,
+                        &call_id,
+                        &session_ref,
+                        &turn_ref,
+                        &mut emitted_deltas,
+                        scrubber.finish(),
+                    ).await;
                     output_drained.notify_one();
                     break;
                 }
@@ -80,6 +90,15 @@ pub(crate) fn start_streaming_output(
                             continue;
                         },
                         Err(RecvError::Closed) => {
+                            process_chunk(
+                                &mut pending,
+                                &transcript,
+                                &call_id,
+                                &session_ref,
+                                &turn_ref,
+                                &mut emitted_deltas,
+                                scrubber.finish(),
+                            ).await;
                             output_drained.notify_one();
                             break;
                         }
@@ -92,7 +111,7 @@ pub(crate) fn start_streaming_output(
                         &session_ref,
                         &turn_ref,
                         &mut emitted_deltas,
-                        chunk,
+                        scrubber.push(&chunk),
                     ).await;
                 }
             }
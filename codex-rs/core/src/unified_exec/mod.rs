@@ -31,11 +31,13 @@ use codex_network_proxy::NetworkProxy;
 use codex_protocol::models::PermissionProfile;
 use rand::Rng;
 use rand::rng;
+use regex_lite::Regex;
 use tokio::sync::Mutex;
 
 use crate::codex::Session;
 use crate::codex::TurnContext;
 use crate::sandboxing::SandboxPermissions;
+use crate::tool_secrets::SecretStreamScrubber;
 
 mod async_watcher;
 mod errors;
@@ -89,6 +91,8 @@ pub(crate) struct ExecCommandRequest {
     pub process_id: i32,
     pub yield_time_ms: u64,
     pub max_output_tokens: Option<usize>,
+    /// Stop waiting as soon as the collected output matches this pattern.
+    pub wait_for: Option<Regex>,
     pub workdir: Option<PathBuf>,
     pub network: Option<NetworkProxy>,
     pub tty: bool,
@@ -105,6 +109,8 @@ pub(crate) struct WriteStdinRequest<'a> {
     pub input: &'a str,
     pub yield_time_ms: u64,
     pub max_output_tokens: Option<usize>,
+    /// Stop waiting as soon as the collected output matches this pattern.
+    pub wait_for: Option<Regex>,
 }
 
 #[derive(Default)]
@@ -150,6 +156,8 @@ struct ProcessEntry {
     network_approval_id: Option<String>,
     session: Weak<Session>,
     last_used: tokio::time::Instant,
+    /// Carries a partially printed secret over from one poll to the next.
+    output_scrubber: SecretStreamScrubber,
 }
 
 pub(crate) fn clamp_yield_time(yield_time_ms: u64) -> u64 {
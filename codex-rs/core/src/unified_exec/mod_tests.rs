@@ -50,6 +50,7 @@ async fn exec_command(
                 process_id,
                 yield_time_ms,
                 max_output_tokens: None,
+                wait_for: None,
                 workdir: None,
                 network: None,
                 tty: true,
@@ -78,6 +79,7 @@ async fn write_stdin(
             input,
             yield_time_ms,
             max_output_tokens: None,
+            wait_for: None,
         })
         .await
 }
@@ -190,6 +192,41 @@ async fn multi_unified_exec_sessions() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn write_stdin_yields_once_wait_for_matches() -> anyhow::Result<()> {
+    skip_if_sandbox!(Ok(()));
+
+    let (session, turn) = test_session_and_turn().await;
+
+    let open_shell = exec_command(&session, &turn, "bash -i", 2_500).await?;
+    let process_id = open_shell.process_id.expect("expected process_id");
+
+    // The terminal echoes the typed line, so match the expanded value only.
+    let output = session
+        .services
+        .unified_exec_manager
+        .write_stdin(WriteStdinRequest {
+            process_id,
+            input: "sleep 1; echo READY_$((40 + 2)); sleep 60\n",
+            yield_time_ms: 20_000,
+            max_output_tokens: None,
+            wait_for: Some(regex_lite::Regex::new("READY_42")?),
+        })
+        .await?;
+
+    assert!(
+        output.truncated_output().contains("READY_42"),
+        "expected the ready line in the output"
+    );
+    assert!(
+        output.wall_time < Duration::from_secs(10),
+        "expected to yield on the match, waited {:?}",
+        output.wall_time
+    );
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn unified_exec_timeouts() -> anyhow::Result<()> {
     skip_if_sandbox!(Ok(()));
@@ -1,4 +1,5 @@
 use rand::Rng;
+use regex_lite::Regex;
 use std::cmp::Reverse;
 use std::collections::HashMap;
 use std::collections::HashSet;
@@ -6,17 +7,16 @@ use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
 use std::sync::atomic::Ordering;
-use tokio::sync::Notify;
 use tokio::sync::mpsc;
 use tokio::sync::watch;
 use tokio::time::Duration;
 use tokio::time::Instant;
-use tokio_util::sync::CancellationToken;
 
 use crate::exec_env::create_env;
 use crate::exec_policy::ExecApprovalRequest;
 use crate::protocol::ExecCommandSource;
 use crate::sandboxing::ExecRequest;
+use crate::tool_secrets::SecretStreamScrubber;
 use crate::tools::context::ExecCommandToolOutput;
 use crate::tools::events::ToolEmitter;
 use crate::tools::events::ToolEventCtx;
@@ -46,7 +46,6 @@ use crate::unified_exec::async_watcher::start_streaming_output;
 use crate::unified_exec::clamp_yield_time;
 use crate::unified_exec::generate_chunk_id;
 use crate::unified_exec::head_tail_buffer::HeadTailBuffer;
-use crate::unified_exec::process::OutputBuffer;
 use crate::unified_exec::process::OutputHandles;
 use crate::unified_exec::process::SpawnLifecycleHandle;
 use crate::unified_exec::process::UnifiedExecProcess;
@@ -91,11 +90,7 @@ fn apply_unified_exec_env(mut env: HashMap<String, String>) -> HashMap<String, S
 
 struct PreparedProcessHandles {
     writer_tx: mpsc::Sender<Vec<u8>>,
-    output_buffer: OutputBuffer,
-    output_notify: Arc<Notify>,
-    output_closed: Arc<AtomicBool>,
-    output_closed_notify: Arc<Notify>,
-    cancellation_token: CancellationToken,
+    output_handles: OutputHandles,
     pause_state: Option<watch::Receiver<bool>>,
     command: Vec<String>,
     process_id: i32,
@@ -130,6 +125,15 @@ impl UnifiedExecProcessManager {
         }
     }
 
+    /// Longest a `write_stdin` call with `input` may wait for output.
+    pub(crate) fn max_write_stdin_yield_time_ms(&self, input: &str) -> u64 {
+        if input.is_empty() {
+            self.max_write_stdin_yield_time_ms
+        } else {
+            MAX_YIELD_TIME_MS
+        }
+    }
+
     pub(crate) async fn release_process_id(&self, process_id: i32) {
         let removed = {
             let mut store = self.process_store.lock().await;
@@ -197,26 +201,16 @@ impl UnifiedExecProcessManager {
         // For the initial exec_command call, we both stream output to events
         // (via start_streaming_output above) and collect a snapshot here for
         // the tool response body.
-        let OutputHandles {
-            output_buffer,
-            output_notify,
-            output_closed,
-            output_closed_notify,
-            cancellation_token,
-        } = process.output_handles();
         let deadline = start + Duration::from_millis(yield_time_ms);
         let collected = Self::collect_output_until_deadline(
-            &output_buffer,
-            &output_notify,
-            &output_closed,
-            &output_closed_notify,
-            &cancellation_token,
+            &process.output_handles(),
             Some(
                 context
                     .session
                     .subscribe_out_of_band_elicitation_pause_state(),
             ),
             deadline,
+            request.wait_for.as_ref(),
         )
         .await;
         let wall_time = Instant::now().saturating_duration_since(start);
@@ -226,8 +220,11 @@ impl UnifiedExecProcessManager {
         let has_exited = process.has_exited() || exit_code.is_some();
         let chunk_id = generate_chunk_id();
         let process_id = request.process_id;
+        let mut output_scrubber = context.session.services.tool_secrets.stream_scrubber();
+        let mut raw_output = output_scrubber.push(&collected);
 
         if has_exited {
+            raw_output.extend(output_scrubber.finish());
             // Short‑lived command: emit ExecCommandEnd immediately using the
             // same helper as the background watcher, so all end events share
             // one implementation.
@@ -271,6 +268,7 @@ impl UnifiedExecProcessManager {
                 request.tty,
                 network_approval_id,
                 Arc::clone(&transcript),
+                output_scrubber,
             )
             .await;
         };
@@ -280,7 +278,7 @@ impl UnifiedExecProcessManager {
             event_call_id: context.call_id.clone(),
             chunk_id,
             wall_time,
-            raw_output: collected,
+            raw_output,
             max_output_tokens: request.max_output_tokens,
             process_id: if has_exited {
                 None
@@ -290,6 +288,7 @@ impl UnifiedExecProcessManager {
             exit_code,
             original_token_count: Some(original_token_count),
             session_command: Some(request.command.clone()),
+            spilled_output_id: None,
         };
 
         Ok(response)
@@ -303,11 +302,7 @@ impl UnifiedExecProcessManager {
 
         let PreparedProcessHandles {
             writer_tx,
-            output_buffer,
-            output_notify,
-            output_closed,
-            output_closed_notify,
-            cancellation_token,
+            output_handles,
             pause_state,
             command: session_command,
             process_id,
@@ -338,13 +333,10 @@ impl UnifiedExecProcessManager {
         let start = Instant::now();
         let deadline = start + Duration::from_millis(yield_time_ms);
         let collected = Self::collect_output_until_deadline(
-            &output_buffer,
-            &output_notify,
-            &output_closed,
-            &output_closed_notify,
-            &cancellation_token,
+            &output_handles,
             pause_state,
             deadline,
+            request.wait_for.as_ref(),
         )
         .await;
         let wall_time = Instant::now().saturating_duration_since(start);
@@ -352,6 +344,7 @@ impl UnifiedExecProcessManager {
         let text = String::from_utf8_lossy(&collected).to_string();
         let original_token_count = approx_token_count(&text);
         let chunk_id = generate_chunk_id();
+        let mut raw_output = self.scrub_poll_output(process_id, &collected).await;
 
         // After polling, refresh_process_state tells us whether the PTY is
         // still alive or has exited and been removed from the store; we thread
@@ -364,7 +357,11 @@ impl UnifiedExecProcessManager {
                 call_id,
                 process_id,
             } => (Some(process_id), exit_code, call_id),
-            ProcessStatus::Exited { exit_code, entry } => {
+            ProcessStatus::Exited {
+                exit_code,
+                mut entry,
+            } => {
+                raw_output.extend(entry.output_scrubber.finish());
                 let call_id = entry.call_id.clone();
                 (None, exit_code, call_id)
             }
@@ -379,17 +376,28 @@ impl UnifiedExecProcessManager {
             event_call_id,
             chunk_id,
             wall_time,
-            raw_output: collected,
+            raw_output,
             max_output_tokens: request.max_output_tokens,
             process_id,
             exit_code,
             original_token_count: Some(original_token_count),
             session_command: Some(session_command.clone()),
+            spilled_output_id: None,
         };
 
         Ok(response)
     }
 
+    /// Scrubs exposed secrets from a poll's output, holding back a possibly
+    /// split secret until the next poll of the same process.
+    async fn scrub_poll_output(&self, process_id: i32, output: &[u8]) -> Vec<u8> {
+        let mut store = self.process_store.lock().await;
+        match store.processes.get_mut(&process_id) {
+            Some(entry) => entry.output_scrubber.push(output),
+            None => output.to_vec(),
+        }
+    }
+
     async fn refresh_process_state(&self, process_id: i32) -> ProcessStatus {
         let status = {
             let mut store = self.process_store.lock().await;
@@ -432,13 +440,6 @@ impl UnifiedExecProcessManager {
             .get_mut(&process_id)
             .ok_or(UnifiedExecError::UnknownProcessId { process_id })?;
         entry.last_used = Instant::now();
-        let OutputHandles {
-            output_buffer,
-            output_notify,
-            output_closed,
-            output_closed_notify,
-            cancellation_token,
-        } = entry.process.output_handles();
         let pause_state = entry
             .session
             .upgrade()
@@ -446,11 +447,7 @@ impl UnifiedExecProcessManager {
 
         Ok(PreparedProcessHandles {
             writer_tx: entry.process.writer_sender(),
-            output_buffer,
-            output_notify,
-            output_closed,
-            output_closed_notify,
-            cancellation_token,
+            output_handles: entry.process.output_handles(),
             pause_state,
             command: entry.command.clone(),
             process_id: entry.process_id,
@@ -480,6 +477,7 @@ impl UnifiedExecProcessManager {
         tty: bool,
         network_approval_id: Option<String>,
         transcript: Arc<tokio::sync::Mutex<HeadTailBuffer>>,
+        output_scrubber: SecretStreamScrubber,
     ) {
         let entry = ProcessEntry {
             process: Arc::clone(&process),
@@ -490,6 +488,7 @@ impl UnifiedExecProcessManager {
             network_approval_id,
             session: Arc::downgrade(&context.session),
             last_used: started_at,
+            output_scrubber,
         };
         let (number_processes, pruned_entry) = {
             let mut store = self.process_store.lock().await;
@@ -570,10 +569,14 @@ impl UnifiedExecProcessManager {
         cwd: PathBuf,
         context: &UnifiedExecContext,
     ) -> Result<(UnifiedExecProcess, Option<DeferredNetworkApproval>), UnifiedExecError> {
-        let env = apply_unified_exec_env(create_env(
+        let mut env = apply_unified_exec_env(create_env(
             &context.turn.shell_environment_policy,
             Some(context.session.conversation_id),
         ));
+        let mut explicit_env_overrides = context.turn.shell_environment_policy.r#set.clone();
+        let tool_secrets = &context.session.services.tool_secrets;
+        tool_secrets.inject(&mut env);
+        tool_secrets.inject(&mut explicit_env_overrides);
         let mut orchestrator = ToolOrchestrator::new();
         let mut runtime =
             UnifiedExecRuntime::new(self, context.turn.tools_config.unified_exec_backend);
@@ -598,7 +601,7 @@ impl UnifiedExecProcessManager {
             command: request.command.clone(),
             cwd,
             env,
-            explicit_env_overrides: context.turn.shell_environment_policy.r#set.clone(),
+            explicit_env_overrides,
             network: request.network.clone(),
             tty: request.tty,
             sandbox_permissions: request.sandbox_permissions,
@@ -628,17 +631,22 @@ impl UnifiedExecProcessManager {
     }
 
     pub(super) async fn collect_output_until_deadline(
-        output_buffer: &OutputBuffer,
-        output_notify: &Arc<Notify>,
-        output_closed: &Arc<AtomicBool>,
-        output_closed_notify: &Arc<Notify>,
-        cancellation_token: &CancellationToken,
+        output_handles: &OutputHandles,
         mut pause_state: Option<watch::Receiver<bool>>,
         mut deadline: Instant,
+        wait_for: Option<&Regex>,
     ) -> Vec<u8> {
         const POST_EXIT_CLOSE_WAIT_CAP: Duration = Duration::from_millis(50);
 
+        let OutputHandles {
+            output_buffer,
+            output_notify,
+            output_closed,
+            output_closed_notify,
+            cancellation_token,
+        } = output_handles;
         let mut collected: Vec<u8> = Vec::with_capacity(4096);
+        let mut wait_for_scanned = 0;
         let mut exit_signal_received = cancellation_token.is_cancelled();
         let mut post_exit_deadline: Option<Instant> = None;
         loop {
@@ -706,6 +714,12 @@ impl UnifiedExecProcessManager {
             for chunk in drained_chunks {
                 collected.extend_from_slice(&chunk);
             }
+            if let Some(pattern) = wait_for {
+                if wait_for_matches(pattern, &collected, wait_for_scanned) {
+                    break;
+                }
+                wait_for_scanned = collected.len();
+            }
 
             exit_signal_received |= cancellation_token.is_cancelled();
             if Instant::now() >= deadline {
@@ -818,6 +832,22 @@ impl UnifiedExecProcessManager {
     }
 }
 
+/// How far back `wait_for` re-scans output it has already checked, so a match
+/// split across chunks is still found without re-scanning the whole buffer.
+const WAIT_FOR_OVERLAP_BYTES: usize = 4 * 1024;
+
+/// Checks `pattern` against the output collected since `scanned`, starting
+/// from the beginning of the last line that was already scanned (at most
+/// [`WAIT_FOR_OVERLAP_BYTES`] back).
+fn wait_for_matches(pattern: &Regex, collected: &[u8], scanned: usize) -> bool {
+    let overlap_start = scanned.saturating_sub(WAIT_FOR_OVERLAP_BYTES);
+    let start = collected[overlap_start..scanned]
+        .iter()
+        .rposition(|byte| *byte == b'\n')
+        .map_or(overlap_start, |newline| overlap_start + newline + 1);
+    pattern.is_match(&String::from_utf8_lossy(&collected[start..]))
+}
+
 enum ProcessStatus {
     Alive {
         exit_code: Option<i32>,
@@ -97,3 +97,20 @@ fn pruning_protects_recent_processes_even_if_exited() {
     // (10) is exited but among the last 8; we should drop the LRU outside that set.
     assert_eq!(candidate, Some(1));
 }
+
+#[test]
+fn wait_for_matches_across_chunks_without_rescanning_old_lines() {
+    let pattern = Regex::new("server ready").expect("valid regex");
+    let mut collected = b"booting\nserver re".to_vec();
+    assert!(!wait_for_matches(&pattern, &collected, 0));
+
+    let scanned = collected.len();
+    collected.extend_from_slice(b"ady on :8080\n");
+    assert!(wait_for_matches(&pattern, &collected, scanned));
+
+    let old_match = b"server ready\n".to_vec();
+    let scanned = old_match.len();
+    let mut collected = old_match;
+    collected.extend_from_slice(b"still running\n");
+    assert!(!wait_for_matches(&pattern, &collected, scanned));
+}
@@ -157,6 +157,8 @@ async fn run_code_mode_turn_with_rmcp(
                     disabled_tools: None,
                     scopes: None,
                     oauth_resource: None,
+                    sampling: None,
+                    sandbox: None,
                 },
             );
             config
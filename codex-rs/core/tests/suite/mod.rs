@@ -113,6 +113,7 @@ mod resume_warning;
 mod review;
 mod rmcp_client;
 mod rollout_list_find;
+mod run_budget;
 mod safety_check_downgrade;
 mod search_tool;
 mod seatbelt;
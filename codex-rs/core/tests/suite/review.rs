@@ -873,6 +873,160 @@ async fn review_uses_overridden_cwd_for_base_branch_merge_base() {
     server.verify().await;
 }
 
+/// Commit range reviews resolve both ends through git without checking either
+/// revision out.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn review_commit_range_resolves_shas_without_checkout() {
+    skip_if_no_network!();
+
+    let sse_raw = r#"[{"type":"response.completed", "response": {"id": "__ID__"}}]"#;
+    let (server, request_log) = start_responses_server_with_sse(sse_raw, 1).await;
+
+    let repo_dir = TempDir::new().unwrap();
+    let repo_path = repo_dir.path().to_path_buf();
+    run_git(&repo_path, &["init", "-b", "main"]);
+    run_git(&repo_path, &["config", "user.email", "test@example.com"]);
+    run_git(&repo_path, &["config", "user.name", "Test User"]);
+    std::fs::write(repo_path.join("file.txt"), "hello\n").unwrap();
+    run_git(&repo_path, &["add", "."]);
+    run_git(&repo_path, &["commit", "-m", "initial"]);
+    let base_sha = git_stdout(&repo_path, &["rev-parse", "HEAD"]);
+    run_git(&repo_path, &["checkout", "-b", "feature"]);
+    std::fs::write(repo_path.join("file.txt"), "hello world\n").unwrap();
+    run_git(&repo_path, &["commit", "-am", "feature"]);
+    let head_sha = git_stdout(&repo_path, &["rev-parse", "HEAD"]);
+    run_git(&repo_path, &["checkout", "main"]);
+
+    let codex_home = Arc::new(TempDir::new().unwrap());
+    let cwd = repo_path.clone();
+    let codex = new_conversation_for_server(&server, codex_home.clone(), move |config| {
+        config.cwd = cwd;
+    })
+    .await;
+
+    codex
+        .submit(Op::Review {
+            review_request: ReviewRequest {
+                target: ReviewTarget::CommitRange {
+                    base: "main".to_string(),
+                    head: "feature".to_string(),
+                },
+                user_facing_hint: None,
+            },
+        })
+        .await
+        .unwrap();
+
+    let entered = wait_for_event(&codex, |ev| matches!(ev, EventMsg::EnteredReviewMode(_))).await;
+    let EventMsg::EnteredReviewMode(entered) = entered else {
+        unreachable!()
+    };
+    assert_eq!(
+        entered.user_facing_hint,
+        Some("commits main..feature".to_string())
+    );
+    let _complete = wait_for_event(&codex, |ev| matches!(ev, EventMsg::TurnComplete(_))).await;
+
+    let requests = request_log.requests();
+    assert_eq!(requests.len(), 1);
+    let body = requests[0].body_json();
+    let input = body["input"].as_array().expect("input array");
+    let saw_range = input
+        .iter()
+        .filter_map(|msg| msg["content"][0]["text"].as_str())
+        .any(|text| text.contains(&format!("{base_sha}..{head_sha}")));
+    assert!(
+        saw_range,
+        "expected review prompt to include {base_sha}..{head_sha}"
+    );
+    assert_eq!(
+        git_stdout(&repo_path, &["rev-parse", "--abbrev-ref", "HEAD"]),
+        "main"
+    );
+
+    let _codex_home_guard = codex_home;
+    server.verify().await;
+}
+
+/// Patch file reviews inline the patch into the prompt instead of applying it.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn review_patch_file_inlines_patch_into_prompt() {
+    skip_if_no_network!();
+
+    let sse_raw = r#"[{"type":"response.completed", "response": {"id": "__ID__"}}]"#;
+    let (server, request_log) = start_responses_server_with_sse(sse_raw, 1).await;
+
+    let cwd_dir = TempDir::new().unwrap();
+    let patch = "--- a/file.txt\n+++ b/file.txt\n@@ -1 +1 @@\n-hello\n+hello world\n";
+    std::fs::write(cwd_dir.path().join("fix.patch"), patch).unwrap();
+
+    let codex_home = Arc::new(TempDir::new().unwrap());
+    let cwd = cwd_dir.path().to_path_buf();
+    let codex = new_conversation_for_server(&server, codex_home.clone(), move |config| {
+        config.cwd = cwd;
+    })
+    .await;
+
+    codex
+        .submit(Op::Review {
+            review_request: ReviewRequest {
+                target: ReviewTarget::PatchFile {
+                    path: PathBuf::from("fix.patch"),
+                },
+                user_facing_hint: None,
+            },
+        })
+        .await
+        .unwrap();
+
+    let _entered = wait_for_event(&codex, |ev| matches!(ev, EventMsg::EnteredReviewMode(_))).await;
+    let _complete = wait_for_event(&codex, |ev| matches!(ev, EventMsg::TurnComplete(_))).await;
+
+    let requests = request_log.requests();
+    assert_eq!(requests.len(), 1);
+    let body = requests[0].body_json();
+    let input = body["input"].as_array().expect("input array");
+    let saw_patch = input
+        .iter()
+        .filter_map(|msg| msg["content"][0]["text"].as_str())
+        .any(|text| text.contains("```diff\n--- a/file.txt") && text.contains("+hello world"));
+    assert!(saw_patch, "expected review prompt to inline the patch");
+
+    let _codex_home_guard = codex_home;
+    server.verify().await;
+}
+
+#[expect(clippy::expect_used)]
+fn run_git(repo_path: &std::path::Path, args: &[&str]) {
+    let output = std::process::Command::new("git")
+        .arg("-C")
+        .arg(repo_path)
+        .args(args)
+        .output()
+        .expect("spawn git");
+    assert!(
+        output.status.success(),
+        "git {:?} failed: stderr={:?}",
+        args,
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+#[expect(clippy::expect_used)]
+fn git_stdout(repo_path: &std::path::Path, args: &[&str]) -> String {
+    let output = std::process::Command::new("git")
+        .arg("-C")
+        .arg(repo_path)
+        .args(args)
+        .output()
+        .expect("spawn git");
+    assert!(output.status.success(), "git {args:?} failed");
+    String::from_utf8(output.stdout)
+        .expect("utf8 output")
+        .trim()
+        .to_string()
+}
+
 /// Start a mock Responses API server and mount the given SSE stream body.
 async fn start_responses_server_with_sse(
     sse_raw: &str,
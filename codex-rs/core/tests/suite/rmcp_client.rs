@@ -108,6 +108,8 @@ async fn stdio_server_round_trip() -> anyhow::Result<()> {
                     disabled_tools: None,
                     scopes: None,
                     oauth_resource: None,
+                    sampling: None,
+                    sandbox: None,
                 },
             );
             config
@@ -252,6 +254,8 @@ async fn stdio_image_responses_round_trip() -> anyhow::Result<()> {
                     disabled_tools: None,
                     scopes: None,
                     oauth_resource: None,
+                    sampling: None,
+                    sandbox: None,
                 },
             );
             config
@@ -475,6 +479,8 @@ async fn stdio_image_responses_are_sanitized_for_text_only_model() -> anyhow::Re
                     disabled_tools: None,
                     scopes: None,
                     oauth_resource: None,
+                    sampling: None,
+                    sandbox: None,
                 },
             );
             config
@@ -595,6 +601,8 @@ async fn stdio_server_propagates_whitelisted_env_vars() -> anyhow::Result<()> {
                     disabled_tools: None,
                     scopes: None,
                     oauth_resource: None,
+                    sampling: None,
+                    sandbox: None,
                 },
             );
             config
@@ -680,6 +688,214 @@ async fn stdio_server_propagates_whitelisted_env_vars() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+#[serial(mcp_test_value)]
+async fn stdio_server_prompts_can_be_listed_and_fetched() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = responses::start_mock_server().await;
+    let server_name = "rmcp_prompts";
+    let rmcp_test_server_bin = stdio_server_bin()?;
+
+    let fixture = test_codex()
+        .with_config(move |config| {
+            let mut servers = config.mcp_servers.get().clone();
+            servers.insert(
+                server_name.to_string(),
+                McpServerConfig {
+                    transport: McpServerTransportConfig::Stdio {
+                        command: rmcp_test_server_bin,
+                        args: Vec::new(),
+                        env: None,
+                        env_vars: Vec::new(),
+                        cwd: None,
+                    },
+                    enabled: true,
+                    required: false,
+                    disabled_reason: None,
+                    startup_timeout_sec: Some(Duration::from_secs(10)),
+                    tool_timeout_sec: None,
+                    enabled_tools: None,
+                    disabled_tools: None,
+                    scopes: None,
+                    oauth_resource: None,
+                    sampling: None,
+                    sandbox: None,
+                },
+            );
+            config
+                .mcp_servers
+                .set(servers)
+                .expect("test mcp servers should accept any configuration");
+        })
+        .build(&server)
+        .await?;
+
+    fixture.codex.submit(Op::ListMcpPrompts).await?;
+    let list_event = wait_for_event_with_timeout(
+        &fixture.codex,
+        |ev| matches!(ev, EventMsg::ListMcpPromptsResponse(_)),
+        Duration::from_secs(10),
+    )
+    .await;
+    let EventMsg::ListMcpPromptsResponse(list) = list_event else {
+        unreachable!("event guard guarantees ListMcpPromptsResponse");
+    };
+    let prompts = list
+        .prompts
+        .get(server_name)
+        .expect("test server should publish prompts");
+    let names: Vec<_> = prompts.iter().map(|prompt| prompt.name.as_str()).collect();
+    assert_eq!(names, vec!["review"]);
+
+    fixture
+        .codex
+        .submit(Op::GetMcpPrompt {
+            server_name: server_name.to_string(),
+            prompt_name: "review".to_string(),
+            arguments: HashMap::from([("file".to_string(), "src/lib.rs".to_string())]),
+        })
+        .await?;
+    let get_event = wait_for_event(&fixture.codex, |ev| {
+        matches!(ev, EventMsg::GetMcpPromptResponse(_))
+    })
+    .await;
+    let EventMsg::GetMcpPromptResponse(prompt) = get_event else {
+        unreachable!("event guard guarantees GetMcpPromptResponse");
+    };
+    assert_eq!(prompt.result, Ok("Please review src/lib.rs.".to_string()));
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+#[serial(mcp_test_value)]
+async fn stdio_server_sees_roots_and_subscribed_resource_updates() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = responses::start_mock_server().await;
+    let server_name = "rmcp_resources";
+    let memo_uri = "memo://codex/example-note";
+    let rmcp_test_server_bin = stdio_server_bin()?;
+
+    mount_sse_once(
+        &server,
+        responses::sse(vec![
+            responses::ev_response_created("resp-1"),
+            responses::ev_function_call(
+                "subscribe-call",
+                "subscribe_mcp_resource",
+                &json!({ "server": server_name, "uri": memo_uri }).to_string(),
+            ),
+            responses::ev_completed("resp-1"),
+        ]),
+    )
+    .await;
+    mount_sse_once(
+        &server,
+        responses::sse(vec![
+            responses::ev_response_created("resp-2"),
+            responses::ev_function_call("roots-call", &format!("mcp__{server_name}__roots"), "{}"),
+            responses::ev_function_call(
+                "touch-call",
+                &format!("mcp__{server_name}__touch_memo"),
+                "{}",
+            ),
+            responses::ev_completed("resp-2"),
+        ]),
+    )
+    .await;
+    let tool_outputs = mount_sse_once(
+        &server,
+        responses::sse(vec![
+            responses::ev_assistant_message("msg-1", "subscribed"),
+            responses::ev_completed("resp-3"),
+        ]),
+    )
+    .await;
+    let next_turn = mount_sse_once(
+        &server,
+        responses::sse(vec![
+            responses::ev_assistant_message("msg-2", "noted"),
+            responses::ev_completed("resp-4"),
+        ]),
+    )
+    .await;
+
+    let fixture = test_codex()
+        .with_config(move |config| {
+            let mut servers = config.mcp_servers.get().clone();
+            servers.insert(
+                server_name.to_string(),
+                McpServerConfig {
+                    transport: McpServerTransportConfig::Stdio {
+                        command: rmcp_test_server_bin,
+                        args: Vec::new(),
+                        env: None,
+                        env_vars: Vec::new(),
+                        cwd: None,
+                    },
+                    enabled: true,
+                    required: false,
+                    disabled_reason: None,
+                    startup_timeout_sec: Some(Duration::from_secs(10)),
+                    tool_timeout_sec: None,
+                    enabled_tools: None,
+                    disabled_tools: None,
+                    scopes: None,
+                    oauth_resource: None,
+                    sampling: None,
+                    sandbox: None,
+                },
+            );
+            config
+                .mcp_servers
+                .set(servers)
+                .expect("test mcp servers should accept any configuration");
+        })
+        .build(&server)
+        .await?;
+
+    fixture.submit_turn("follow the memo").await?;
+    let request = tool_outputs.single_request();
+    let subscribe_output: Value = serde_json::from_str(
+        &request
+            .function_call_output_text("subscribe-call")
+            .expect("subscribe tool output"),
+    )?;
+    assert_eq!(
+        subscribe_output,
+        json!({ "server": server_name, "uri": memo_uri, "subscribed": true })
+    );
+    let roots = request
+        .function_call_output_text("roots-call")
+        .expect("roots tool output");
+    let cwd_uri = url::Url::from_file_path(fixture.cwd_path())
+        .expect("cwd is absolute")
+        .to_string();
+    assert!(roots.contains(&cwd_uri), "unexpected roots: {roots}");
+
+    fixture.submit_turn("anything new?").await?;
+    let updates: Vec<String> = next_turn
+        .single_request()
+        .message_input_texts("user")
+        .into_iter()
+        .filter(|text| text.starts_with("<mcp_resource_update>"))
+        .collect();
+    assert_eq!(
+        updates.len(),
+        1,
+        "expected one resource update: {updates:?}"
+    );
+    assert!(
+        updates[0].contains("This is a sample MCP resource served by the rmcp test server."),
+        "update should include the memo contents: {}",
+        updates[0]
+    );
+
+    Ok(())
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 async fn streamable_http_tool_call_round_trip() -> anyhow::Result<()> {
     skip_if_no_network!(Ok(()));
@@ -756,6 +972,8 @@ async fn streamable_http_tool_call_round_trip() -> anyhow::Result<()> {
                     disabled_tools: None,
                     scopes: None,
                     oauth_resource: None,
+                    sampling: None,
+                    sandbox: None,
                 },
             );
             config
@@ -977,6 +1195,8 @@ async fn streamable_http_with_oauth_round_trip_impl() -> anyhow::Result<()> {
                     disabled_tools: None,
                     scopes: None,
                     oauth_resource: None,
+                    sampling: None,
+                    sandbox: None,
                 },
             );
             config
@@ -0,0 +1,115 @@
+use anyhow::Result;
+use codex_core::config::Config;
+use codex_protocol::protocol::CodexErrorInfo;
+use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::Op;
+use codex_protocol::protocol::RunBudgetKind;
+use codex_protocol::user_input::UserInput;
+use core_test_support::responses::ev_assistant_message;
+use core_test_support::responses::ev_completed;
+use core_test_support::responses::ev_function_call;
+use core_test_support::responses::ev_response_created;
+use core_test_support::responses::mount_sse_sequence;
+use core_test_support::responses::sse;
+use core_test_support::responses::start_mock_server;
+use core_test_support::skip_if_no_network;
+use core_test_support::test_codex::test_codex;
+use core_test_support::wait_for_event;
+use pretty_assertions::assert_eq;
+
+async fn run_turn_collecting_errors(
+    configure: impl FnOnce(&mut Config) + Send + 'static,
+) -> Result<(Vec<Option<CodexErrorInfo>>, usize)> {
+    let server = start_mock_server().await;
+    let responses = mount_sse_sequence(
+        &server,
+        vec![
+            sse(vec![
+                ev_response_created("resp-1"),
+                ev_function_call("call-1", "unknown_tool", "{}"),
+                ev_function_call("call-2", "unknown_tool", "{}"),
+                ev_completed("resp-1"),
+            ]),
+            sse(vec![
+                ev_response_created("resp-2"),
+                ev_assistant_message("msg-1", "done"),
+                ev_completed("resp-2"),
+            ]),
+        ],
+    )
+    .await;
+    let test = test_codex().with_config(configure).build(&server).await?;
+
+    test.codex
+        .submit(Op::UserInput {
+            items: vec![UserInput::Text {
+                text: "work until the budget runs out".into(),
+                text_elements: Vec::new(),
+            }],
+            final_output_json_schema: None,
+        })
+        .await?;
+
+    let mut errors = Vec::new();
+    loop {
+        match wait_for_event(&test.codex, |_| true).await {
+            EventMsg::Error(err) => errors.push(err.codex_error_info),
+            EventMsg::TurnComplete(_) => break,
+            _ => {}
+        }
+    }
+    Ok((errors, responses.requests().len()))
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn max_turns_stops_before_follow_up_request() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let (errors, requests) = run_turn_collecting_errors(|config| {
+        config.run_budget.max_turns = Some(1);
+    })
+    .await?;
+
+    assert_eq!(
+        errors,
+        vec![Some(CodexErrorInfo::RunBudgetExceeded {
+            budget: RunBudgetKind::MaxTurns,
+        })]
+    );
+    assert_eq!(requests, 1);
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn max_tool_calls_refuses_extra_calls_and_stops_turn() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let (errors, requests) = run_turn_collecting_errors(|config| {
+        config.run_budget.max_tool_calls = Some(1);
+    })
+    .await?;
+
+    assert_eq!(
+        errors,
+        vec![Some(CodexErrorInfo::RunBudgetExceeded {
+            budget: RunBudgetKind::MaxToolCalls,
+        })]
+    );
+    assert_eq!(requests, 1);
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn generous_budget_lets_turn_finish() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let (errors, requests) = run_turn_collecting_errors(|config| {
+        config.run_budget.max_turns = Some(5);
+        config.run_budget.max_tool_calls = Some(5);
+    })
+    .await?;
+
+    assert_eq!(errors, Vec::new());
+    assert_eq!(requests, 2);
+    Ok(())
+}
@@ -372,6 +372,8 @@ async fn mcp_call_marks_thread_memory_mode_polluted_when_configured() -> Result<
                 disabled_tools: None,
                 scopes: None,
                 oauth_resource: None,
+                sampling: None,
+                sandbox: None,
             },
         );
         config
@@ -371,6 +371,8 @@ async fn mcp_tool_call_output_exceeds_limit_truncated_for_model() -> Result<()>
                 disabled_tools: None,
                 scopes: None,
                 oauth_resource: None,
+                sampling: None,
+                sandbox: None,
             },
         );
         config
@@ -466,6 +468,8 @@ async fn mcp_image_output_preserves_image_and_no_text_summary() -> Result<()> {
                 disabled_tools: None,
                 scopes: None,
                 oauth_resource: None,
+                sampling: None,
+                sandbox: None,
             },
         );
         config
@@ -733,6 +737,8 @@ async fn mcp_tool_call_output_not_truncated_with_custom_limit() -> Result<()> {
                 disabled_tools: None,
                 scopes: None,
                 oauth_resource: None,
+                sampling: None,
+                sandbox: None,
             },
         );
         config
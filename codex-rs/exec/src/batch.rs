@@ -0,0 +1,637 @@
+//! `codex exec --batch tasks.jsonl`: runs every task in its own thread on the
+//! shared in-process app-server and interleaves their events into a single
+//! JSONL stream on stdout.
+//!
+//! Tasks move through the same states as agent job items (pending, running,
+//! then completed or failed), but are only tracked for the lifetime of the
+//! run. Each task ends with a `task.completed` line carrying its exit status,
+//! and the run ends with a `batch.completed` summary.
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::collections::VecDeque;
+use std::path::Path;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use codex_app_server_client::InProcessAppServerClient;
+use codex_app_server_client::InProcessClientStartArgs;
+use codex_app_server_client::InProcessServerEvent;
+use codex_app_server_protocol::ClientRequest;
+use codex_app_server_protocol::ThreadStartResponse;
+use codex_app_server_protocol::TurnInterruptParams;
+use codex_app_server_protocol::TurnInterruptResponse;
+use codex_app_server_protocol::TurnStartParams;
+use codex_app_server_protocol::TurnStartResponse;
+use codex_core::config::Config;
+use codex_core::git_info::get_git_repo_root;
+use codex_protocol::protocol::CodexErrorInfo;
+use codex_protocol::protocol::ErrorEvent;
+use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::RunBudgetKind;
+use codex_protocol::user_input::UserInput;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::Value;
+use tokio::sync::mpsc;
+use tracing::warn;
+
+use crate::RUN_BUDGET_EXCEEDED_EXIT_CODE;
+use crate::RequestIdSequencer;
+use crate::decode_legacy_notification;
+use crate::event_processor_with_jsonl_output::EventProcessorWithJsonOutput;
+use crate::exec_events::ThreadEvent;
+use crate::handle_server_request;
+use crate::lagged_event_warning_message;
+use crate::request_shutdown;
+use crate::send_request_with_response;
+use crate::thread_start_params_from_config;
+
+/// One line of the tasks file.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct BatchTaskLine {
+    #[serde(default)]
+    id: Option<String>,
+    prompt: String,
+    #[serde(default)]
+    cwd: Option<PathBuf>,
+    /// Inline JSON Schema, or a path to a schema file.
+    #[serde(default)]
+    output_schema: Option<Value>,
+    #[serde(default)]
+    model: Option<String>,
+    /// Config overrides keyed by dotted path, as with `-c key=value`.
+    #[serde(default)]
+    config: HashMap<String, Value>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct BatchTask {
+    pub(crate) id: String,
+    pub(crate) prompt: String,
+    pub(crate) cwd: PathBuf,
+    pub(crate) output_schema: Option<Value>,
+    pub(crate) model: Option<String>,
+    pub(crate) config: HashMap<String, Value>,
+}
+
+pub(crate) struct BatchRunArgs {
+    pub(crate) in_process_start_args: InProcessClientStartArgs,
+    pub(crate) config: Config,
+    pub(crate) tasks: Vec<BatchTask>,
+    pub(crate) concurrency: usize,
+    pub(crate) skip_git_repo_check: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum BatchTaskStatus {
+    Completed,
+    Failed,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct BatchTaskResult {
+    task_id: String,
+    status: BatchTaskStatus,
+    exit_code: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    thread_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    final_message: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reason: Option<RunBudgetKind>,
+}
+
+impl BatchTaskResult {
+    fn failed(task_id: &str, thread_id: Option<String>, error: String) -> Self {
+        Self {
+            task_id: task_id.to_string(),
+            status: BatchTaskStatus::Failed,
+            exit_code: 1,
+            thread_id,
+            final_message: None,
+            error: Some(error),
+            reason: None,
+        }
+    }
+}
+
+/// Lines written to stdout in batch mode.
+#[derive(Debug, Serialize)]
+#[serde(tag = "type")]
+enum BatchEvent<'a> {
+    #[serde(rename = "task.started")]
+    TaskStarted {
+        task_id: &'a str,
+        thread_id: &'a str,
+        cwd: &'a Path,
+    },
+    #[serde(rename = "task.completed")]
+    TaskCompleted(&'a BatchTaskResult),
+    #[serde(rename = "batch.completed")]
+    BatchCompleted {
+        total: usize,
+        completed: usize,
+        failed: usize,
+    },
+}
+
+/// A thread event tagged with the task that produced it.
+#[derive(Debug, Serialize)]
+struct TaskThreadEvent<'a> {
+    task_id: &'a str,
+    #[serde(flatten)]
+    event: &'a ThreadEvent,
+}
+
+struct RunningTask {
+    task: BatchTask,
+    thread_id: String,
+    turn_id: String,
+    processor: EventProcessorWithJsonOutput,
+    last_error: Option<String>,
+    budget_exceeded: Option<RunBudgetKind>,
+}
+
+impl RunningTask {
+    fn finish(self, final_message: Option<String>, aborted: bool) -> BatchTaskResult {
+        let (status, exit_code) = match (self.budget_exceeded, &self.last_error) {
+            (Some(_), _) => (BatchTaskStatus::Failed, RUN_BUDGET_EXCEEDED_EXIT_CODE),
+            (None, Some(_)) => (BatchTaskStatus::Failed, 1),
+            (None, None) if aborted => (BatchTaskStatus::Failed, 1),
+            (None, None) => (BatchTaskStatus::Completed, 0),
+        };
+        let error = self
+            .last_error
+            .or_else(|| aborted.then(|| "turn was interrupted".to_string()));
+        BatchTaskResult {
+            task_id: self.task.id,
+            status,
+            exit_code,
+            thread_id: Some(self.thread_id),
+            final_message,
+            error,
+            reason: self.budget_exceeded,
+        }
+    }
+}
+
+/// Parses a tasks file. Relative `cwd` and schema paths resolve against
+/// `base_cwd`, and tasks without an `output_schema` use `default_output_schema`.
+pub(crate) fn parse_batch_tasks(
+    contents: &str,
+    base_cwd: &Path,
+    default_output_schema: Option<&Value>,
+) -> anyhow::Result<Vec<BatchTask>> {
+    let mut tasks = Vec::new();
+    let mut seen_ids = HashSet::new();
+    for (index, line) in contents.lines().enumerate() {
+        let line_number = index + 1;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let line: BatchTaskLine = serde_json::from_str(line)
+            .with_context(|| format!("invalid batch task on line {line_number}"))?;
+        if line.prompt.trim().is_empty() {
+            anyhow::bail!("batch task on line {line_number} has an empty prompt");
+        }
+        let id = line
+            .id
+            .unwrap_or_else(|| format!("task-{}", tasks.len() + 1));
+        if !seen_ids.insert(id.clone()) {
+            anyhow::bail!("duplicate batch task id `{id}` on line {line_number}");
+        }
+        let cwd = match line.cwd {
+            Some(cwd) => base_cwd.join(cwd),
+            None => base_cwd.to_path_buf(),
+        };
+        let output_schema = match line.output_schema {
+            Some(Value::String(path)) => Some(read_output_schema(&base_cwd.join(path))?),
+            Some(schema @ Value::Object(_)) => Some(schema),
+            Some(_) => anyhow::bail!(
+                "batch task on line {line_number}: output_schema must be an object or a file path"
+            ),
+            None => default_output_schema.cloned(),
+        };
+        tasks.push(BatchTask {
+            id,
+            prompt: line.prompt,
+            cwd,
+            output_schema,
+            model: line.model,
+            config: line.config,
+        });
+    }
+    if tasks.is_empty() {
+        anyhow::bail!("batch file contains no tasks");
+    }
+    Ok(tasks)
+}
+
+fn read_output_schema(path: &Path) -> anyhow::Result<Value> {
+    let contents = std::fs::read_to_string(path)
+        .with_context(|| format!("failed to read output schema file {}", path.display()))?;
+    serde_json::from_str(&contents)
+        .with_context(|| format!("output schema file {} is not valid JSON", path.display()))
+}
+
+pub(crate) async fn run_batch(args: BatchRunArgs) -> anyhow::Result<()> {
+    let BatchRunArgs {
+        in_process_start_args,
+        config,
+        tasks,
+        concurrency,
+        skip_git_repo_check,
+    } = args;
+
+    let mut request_ids = RequestIdSequencer::new();
+    let mut client = InProcessAppServerClient::start(in_process_start_args)
+        .await
+        .map_err(|err| {
+            anyhow::anyhow!("failed to initialize in-process app-server client: {err}")
+        })?;
+
+    let (interrupt_tx, mut interrupt_rx) = mpsc::unbounded_channel::<()>();
+    tokio::spawn(async move {
+        if tokio::signal::ctrl_c().await.is_ok() {
+            tracing::debug!("Keyboard interrupt");
+            let _ = interrupt_tx.send(());
+        }
+    });
+
+    let total = tasks.len();
+    let mut pending: VecDeque<BatchTask> = tasks.into();
+    let mut running: HashMap<String, RunningTask> = HashMap::new();
+    let mut failed = 0;
+    let mut interrupt_channel_open = true;
+
+    loop {
+        while running.len() < concurrency
+            && let Some(task) = pending.pop_front()
+        {
+            match start_task(
+                &client,
+                &mut request_ids,
+                &config,
+                task.clone(),
+                skip_git_repo_check,
+            )
+            .await
+            {
+                Ok(running_task) => {
+                    emit(&BatchEvent::TaskStarted {
+                        task_id: &running_task.task.id,
+                        thread_id: &running_task.thread_id,
+                        cwd: &running_task.task.cwd,
+                    });
+                    running.insert(running_task.thread_id.clone(), running_task);
+                }
+                Err(err) => {
+                    failed += 1;
+                    emit(&BatchEvent::TaskCompleted(&BatchTaskResult::failed(
+                        &task.id, None, err,
+                    )));
+                }
+            }
+        }
+        if running.is_empty() {
+            break;
+        }
+
+        let server_event = tokio::select! {
+            maybe_interrupt = interrupt_rx.recv(), if interrupt_channel_open => {
+                if maybe_interrupt.is_none() {
+                    interrupt_channel_open = false;
+                    continue;
+                }
+                // Stop scheduling new work and interrupt whatever is running;
+                // the aborted turns finish their tasks below.
+                for task in pending.drain(..) {
+                    failed += 1;
+                    emit(&BatchEvent::TaskCompleted(&BatchTaskResult::failed(
+                        &task.id,
+                        None,
+                        "batch was interrupted before the task started".to_string(),
+                    )));
+                }
+                for running_task in running.values() {
+                    if let Err(err) = send_request_with_response::<TurnInterruptResponse>(
+                        &client,
+                        ClientRequest::TurnInterrupt {
+                            request_id: request_ids.next(),
+                            params: TurnInterruptParams {
+                                thread_id: running_task.thread_id.clone(),
+                                turn_id: running_task.turn_id.clone(),
+                            },
+                        },
+                        "turn/interrupt",
+                    )
+                    .await
+                    {
+                        warn!("turn/interrupt failed: {err}");
+                    }
+                }
+                continue;
+            }
+            maybe_event = client.next_event() => maybe_event,
+        };
+        let Some(server_event) = server_event else {
+            break;
+        };
+
+        match server_event {
+            InProcessServerEvent::ServerRequest(request) => {
+                let mut request_failed = false;
+                handle_server_request(&client, request, &config, "", &mut request_failed).await;
+            }
+            InProcessServerEvent::ServerNotification(_) => {}
+            InProcessServerEvent::LegacyNotification(notification) => {
+                let decoded = match decode_legacy_notification(notification) {
+                    Ok(event) => event,
+                    Err(err) => {
+                        warn!("{err}");
+                        continue;
+                    }
+                };
+                let Some(thread_id) = decoded.conversation_id else {
+                    continue;
+                };
+                let Some(running_task) = running.get_mut(&thread_id) else {
+                    continue;
+                };
+                let event = decoded.event;
+                if matches!(event.msg, EventMsg::SessionConfigured(_)) {
+                    continue;
+                }
+                if let EventMsg::Error(ErrorEvent {
+                    message,
+                    codex_error_info,
+                }) = &event.msg
+                {
+                    running_task.last_error = Some(message.clone());
+                    if let Some(CodexErrorInfo::RunBudgetExceeded { budget }) = codex_error_info {
+                        running_task.budget_exceeded = Some(*budget);
+                    }
+                }
+                for thread_event in running_task.processor.collect_thread_events(&event) {
+                    emit(&TaskThreadEvent {
+                        task_id: &running_task.task.id,
+                        event: &thread_event,
+                    });
+                }
+
+                let finished = match &event.msg {
+                    EventMsg::TurnComplete(payload) if payload.turn_id == running_task.turn_id => {
+                        Some((payload.last_agent_message.clone(), false))
+                    }
+                    EventMsg::TurnAborted(payload)
+                        if payload.turn_id.as_deref() == Some(running_task.turn_id.as_str()) =>
+                    {
+                        Some((None, true))
+                    }
+                    _ => None,
+                };
+                if let Some((final_message, aborted)) = finished
+                    && let Some(running_task) = running.remove(&thread_id)
+                {
+                    let result = running_task.finish(final_message, aborted);
+                    if result.status == BatchTaskStatus::Failed {
+                        failed += 1;
+                    }
+                    emit(&BatchEvent::TaskCompleted(&result));
+                    if let Err(err) = request_shutdown(&client, &mut request_ids, &thread_id).await
+                    {
+                        warn!("thread/unsubscribe failed during shutdown: {err}");
+                    }
+                }
+            }
+            InProcessServerEvent::Lagged { skipped } => {
+                warn!("{}", lagged_event_warning_message(skipped));
+            }
+        }
+    }
+
+    // The event stream closed under running tasks: report them as failed.
+    for (thread_id, running_task) in running.drain() {
+        failed += 1;
+        emit(&BatchEvent::TaskCompleted(&BatchTaskResult::failed(
+            &running_task.task.id,
+            Some(thread_id),
+            "app-server event stream closed before the task finished".to_string(),
+        )));
+    }
+    emit(&BatchEvent::BatchCompleted {
+        total,
+        completed: total - failed,
+        failed,
+    });
+
+    if let Err(err) = client.shutdown().await {
+        warn!("in-process app-server shutdown failed: {err}");
+    }
+    if failed > 0 {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+/// Starts a thread in the task's working directory and submits its prompt.
+async fn start_task(
+    client: &InProcessAppServerClient,
+    request_ids: &mut RequestIdSequencer,
+    config: &Config,
+    task: BatchTask,
+    skip_git_repo_check: bool,
+) -> Result<RunningTask, String> {
+    if !task.cwd.is_dir() {
+        return Err(format!("{} is not a directory", task.cwd.display()));
+    }
+    if !skip_git_repo_check && get_git_repo_root(&task.cwd).is_none() {
+        return Err(format!(
+            "{} is not inside a trusted directory and --skip-git-repo-check was not specified",
+            task.cwd.display()
+        ));
+    }
+
+    let mut thread_params = thread_start_params_from_config(config);
+    thread_params.cwd = Some(task.cwd.to_string_lossy().to_string());
+    if let Some(model) = &task.model {
+        thread_params.model = Some(model.clone());
+    }
+    if !task.config.is_empty() {
+        thread_params
+            .config
+            .get_or_insert_with(HashMap::new)
+            .extend(task.config.clone());
+    }
+    let thread: ThreadStartResponse = send_request_with_response(
+        client,
+        ClientRequest::ThreadStart {
+            request_id: request_ids.next(),
+            params: thread_params,
+        },
+        "thread/start",
+    )
+    .await?;
+    let thread_id = thread.thread.id;
+
+    // Approval, sandbox and reasoning settings come from the thread's own
+    // config so per-task overrides apply.
+    let turn: TurnStartResponse = send_request_with_response(
+        client,
+        ClientRequest::TurnStart {
+            request_id: request_ids.next(),
+            params: TurnStartParams {
+                thread_id: thread_id.clone(),
+                input: vec![
+                    UserInput::Text {
+                        text: task.prompt.clone(),
+                        text_elements: Vec::new(),
+                    }
+                    .into(),
+                ],
+                cwd: None,
+                approval_policy: None,
+                sandbox_policy: None,
+                model: None,
+                service_tier: None,
+                effort: None,
+                summary: None,
+                personality: None,
+                output_schema: task.output_schema.clone(),
+                collaboration_mode: None,
+            },
+        },
+        "turn/start",
+    )
+    .await?;
+
+    Ok(RunningTask {
+        task,
+        thread_id,
+        turn_id: turn.turn.id,
+        processor: EventProcessorWithJsonOutput::new(None),
+        last_error: None,
+        budget_exceeded: None,
+    })
+}
+
+fn emit(line: &impl Serialize) {
+    match serde_json::to_string(line) {
+        Ok(line) => {
+            #[allow(clippy::print_stdout)]
+            {
+                println!("{line}");
+            }
+        }
+        Err(err) => warn!("failed to serialize batch event: {err}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exec_events::TurnStartedEvent;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    #[test]
+    fn parses_tasks_with_defaults_and_relative_paths() {
+        let base = tempfile::tempdir().expect("tempdir");
+        std::fs::write(base.path().join("schema.json"), r#"{"type":"object"}"#)
+            .expect("write schema");
+        let contents = r#"
+{"prompt": "fix lint", "cwd": "repo-a", "config": {"model_reasoning_effort": "low"}}
+
+{"id": "b", "prompt": "summarize", "output_schema": "schema.json", "model": "gpt-5.1"}
+"#;
+        let default_schema = json!({"type": "string"});
+
+        let tasks = parse_batch_tasks(contents, base.path(), Some(&default_schema))
+            .expect("tasks should parse");
+
+        assert_eq!(
+            tasks,
+            vec![
+                BatchTask {
+                    id: "task-1".to_string(),
+                    prompt: "fix lint".to_string(),
+                    cwd: base.path().join("repo-a"),
+                    output_schema: Some(default_schema.clone()),
+                    model: None,
+                    config: HashMap::from([("model_reasoning_effort".to_string(), json!("low"))]),
+                },
+                BatchTask {
+                    id: "b".to_string(),
+                    prompt: "summarize".to_string(),
+                    cwd: base.path().to_path_buf(),
+                    output_schema: Some(json!({"type": "object"})),
+                    model: Some("gpt-5.1".to_string()),
+                    config: HashMap::new(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_task_files() {
+        let base = Path::new("/tmp");
+        let err = |contents: &str| {
+            parse_batch_tasks(contents, base, None)
+                .expect_err("tasks should be rejected")
+                .to_string()
+        };
+
+        assert_eq!(err(""), "batch file contains no tasks");
+        assert_eq!(
+            err(r#"{"prompt": "a", "id": "x"}
+{"prompt": "b", "id": "x"}"#),
+            "duplicate batch task id `x` on line 2"
+        );
+        assert_eq!(
+            err(r#"{"prompt": "a", "unknown": true}"#),
+            "invalid batch task on line 1"
+        );
+        assert_eq!(
+            err(r#"{"prompt": " "}"#),
+            "batch task on line 1 has an empty prompt"
+        );
+    }
+
+    #[test]
+    fn task_events_are_tagged_with_task_id() {
+        let event = ThreadEvent::TurnStarted(TurnStartedEvent {});
+        let line = serde_json::to_value(TaskThreadEvent {
+            task_id: "a",
+            event: &event,
+        })
+        .expect("serialize");
+        assert_eq!(line, json!({"task_id": "a", "type": "turn.started"}));
+
+        let result = BatchTaskResult {
+            task_id: "a".to_string(),
+            status: BatchTaskStatus::Failed,
+            exit_code: RUN_BUDGET_EXCEEDED_EXIT_CODE,
+            thread_id: Some("thread-1".to_string()),
+            final_message: None,
+            error: Some("budget".to_string()),
+            reason: Some(RunBudgetKind::Timeout),
+        };
+        assert_eq!(
+            serde_json::to_value(BatchEvent::TaskCompleted(&result)).expect("serialize"),
+            json!({
+                "type": "task.completed",
+                "task_id": "a",
+                "status": "failed",
+                "exit_code": 3,
+                "thread_id": "thread-1",
+                "error": "budget",
+                "reason": "timeout",
+            })
+        );
+    }
+}
@@ -79,6 +79,12 @@ pub struct Cli {
     #[arg(long = "output-schema", value_name = "FILE")]
     pub output_schema: Option<PathBuf>,
 
+    #[command(flatten)]
+    pub run_budget: RunBudgetArgs,
+
+    #[command(flatten)]
+    pub batch: BatchArgs,
+
     #[clap(skip)]
     pub config_overrides: CliConfigOverrides,
 
@@ -114,6 +120,94 @@ pub struct Cli {
     pub prompt: Option<String>,
 }
 
+/// Runs many prompts from a JSONL file instead of a single prompt.
+#[derive(Args, Debug, Default, Clone, PartialEq, Eq)]
+pub struct BatchArgs {
+    /// Run every task in a JSONL file (`-` for stdin), each in its own
+    /// thread, and stream the combined results to stdout as JSONL.
+    #[arg(
+        long = "batch",
+        value_name = "FILE",
+        conflicts_with_all = ["prompt", "images", "last_message_file"]
+    )]
+    pub tasks_file: Option<PathBuf>,
+
+    /// Maximum number of batch tasks that run at the same time [default: 4].
+    #[arg(
+        long = "batch-concurrency",
+        value_name = "N",
+        requires = "tasks_file",
+        value_parser = clap::value_parser!(u64).range(1..)
+    )]
+    pub concurrency: Option<u64>,
+}
+
+/// Limits that stop the run early, sub-agents included. Each flag maps to a `run_budget.*` config
+/// key, so the same limits can also be set in config.toml.
+#[derive(Args, Debug, Default, Clone, PartialEq, Eq)]
+pub struct RunBudgetArgs {
+    /// Stop after this many model requests in the run.
+    #[arg(
+        long = "max-turns",
+        value_name = "N",
+        global = true,
+        value_parser = clap::value_parser!(u64).range(1..)
+    )]
+    pub max_turns: Option<u64>,
+
+    /// Stop once the run has generated this many output tokens.
+    #[arg(
+        long = "max-output-tokens",
+        value_name = "N",
+        global = true,
+        value_parser = clap::value_parser!(u64).range(1..)
+    )]
+    pub max_output_tokens: Option<u64>,
+
+    /// Stop once the run has used this many input and output tokens.
+    #[arg(
+        long = "max-total-tokens",
+        value_name = "N",
+        global = true,
+        value_parser = clap::value_parser!(u64).range(1..)
+    )]
+    pub max_total_tokens: Option<u64>,
+
+    /// Stop the run after this many seconds of wall-clock time.
+    #[arg(
+        long = "timeout",
+        value_name = "SECONDS",
+        global = true,
+        value_parser = clap::value_parser!(u64).range(1..)
+    )]
+    pub timeout_seconds: Option<u64>,
+
+    /// Refuse tool calls beyond this many and stop the run.
+    #[arg(
+        long = "max-tool-calls",
+        value_name = "N",
+        global = true,
+        value_parser = clap::value_parser!(u64).range(1..)
+    )]
+    pub max_tool_calls: Option<u64>,
+}
+
+impl RunBudgetArgs {
+    /// `-c`-style overrides for the limits that were set.
+    pub fn config_overrides(&self) -> Vec<String> {
+        [
+            ("max_turns", self.max_turns),
+            ("max_output_tokens", self.max_output_tokens),
+            ("max_total_tokens", self.max_total_tokens),
+            ("timeout_seconds", self.timeout_seconds),
+            ("max_tool_calls", self.max_tool_calls),
+        ]
+        .into_iter()
+        .filter_map(|(key, value)| value.map(|value| format!("run_budget.{key}={value}")))
+        .collect()
+    }
+}
+
 #[derive(Debug, clap::Subcommand)]
 pub enum Command {
     /// Resume a previous session by id or pick the most recent with --last.
@@ -220,7 +314,7 @@ pub struct ReviewArgs {
     #[arg(
         long = "uncommitted",
         default_value_t = false,
-        conflicts_with_all = ["base", "commit", "prompt"]
+        conflicts_with_all = ["base", "commit", "range", "patch", "prompt"]
     )]
     pub uncommitted: bool,
 
@@ -228,7 +322,7 @@ pub struct ReviewArgs {
     #[arg(
         long = "base",
         value_name = "BRANCH",
-        conflicts_with_all = ["uncommitted", "commit", "prompt"]
+        conflicts_with_all = ["uncommitted", "commit", "range", "patch", "prompt"]
     )]
     pub base: Option<String>,
 
@@ -236,7 +330,7 @@ pub struct ReviewArgs {
     #[arg(
         long = "commit",
         value_name = "SHA",
-        conflicts_with_all = ["uncommitted", "base", "prompt"]
+        conflicts_with_all = ["uncommitted", "base", "range", "patch", "prompt"]
     )]
     pub commit: Option<String>,
 
@@ -244,9 +338,68 @@ pub struct ReviewArgs {
     #[arg(long = "title", value_name = "TITLE", requires = "commit")]
     pub commit_title: Option<String>,
 
+    /// Review the commits in a `BASE..HEAD` range without checking it out.
+    #[arg(
+        long = "range",
+        value_name = "BASE..HEAD",
+        conflicts_with_all = ["uncommitted", "base", "commit", "patch", "prompt"]
+    )]
+    pub range: Option<String>,
+
+    /// Review a `.patch`/`.diff` file without applying it. If `-` is used,
+    /// read the diff from stdin.
+    #[arg(
+        long = "patch",
+        value_name = "FILE",
+        conflicts_with_all = ["uncommitted", "base", "commit", "range", "prompt"]
+    )]
+    pub patch: Option<PathBuf>,
+
     /// Custom review instructions. If `-` is used, read from stdin.
     #[arg(value_name = "PROMPT", value_hint = clap::ValueHint::Other)]
     pub prompt: Option<String>,
+
+    #[command(flatten)]
+    pub report: ReviewReportArgs,
+}
+
+#[derive(Args, Debug, Default)]
+pub struct ReviewReportArgs {
+    /// Format of the review report. `text` keeps the human-readable output;
+    /// the other formats are meant for CI tooling.
+    #[arg(long = "format", value_enum, default_value_t = ReviewReportFormat::Text)]
+    pub format: ReviewReportFormat,
+
+    /// Write the review report to this file instead of stdout.
+    #[arg(long = "output", value_name = "FILE")]
+    pub output: Option<PathBuf>,
+
+    /// Exit with a non-zero status when any P0 or P1 finding has a confidence
+    /// score at or above this value (0.0-1.0).
+    #[arg(long = "fail-on-confidence", value_name = "SCORE", value_parser = parse_confidence)]
+    pub fail_on_confidence: Option<f32>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+#[value(rename_all = "kebab-case")]
+pub enum ReviewReportFormat {
+    #[default]
+    Text,
+    Json,
+    Sarif,
+    Junit,
+}
+
+fn parse_confidence(raw: &str) -> Result<f32, String> {
+    let value: f32 = raw
+        .parse()
+        .map_err(|_| format!("`{raw}` is not a number"))?;
+    if !(0.0..=1.0).contains(&value) {
+        return Err(format!(
+            "confidence must be between 0.0 and 1.0, got {value}"
+        ));
+    }
+    Ok(value)
 }
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
@@ -315,4 +468,25 @@ mod tests {
         assert_eq!(args.session_id.as_deref(), Some("session-123"));
         assert_eq!(args.prompt.as_deref(), Some(PROMPT));
     }
+
+    #[test]
+    fn run_budget_flags_become_config_overrides() {
+        let cli = Cli::parse_from([
+            "codex-exec",
+            "review",
+            "--max-turns",
+            "5",
+            "--timeout",
+            "300",
+        ]);
+
+        assert_eq!(
+            cli.run_budget.config_overrides(),
+            vec![
+                "run_budget.max_turns=5".to_string(),
+                "run_budget.timeout_seconds=300".to_string(),
+            ]
+        );
+        assert!(Cli::try_parse_from(["codex-exec", "--max-tool-calls", "0", "hi"]).is_err());
+    }
 }
@@ -315,6 +315,19 @@ impl EventProcessor for EventProcessorWithHumanOutput {
                     "auto-cancelling (not supported in exec mode)".style(self.dimmed)
                 );
             }
+            EventMsg::McpSamplingRequest(ev) => {
+                ts_msg!(
+                    self,
+                    "{} {}",
+                    "sampling request".style(self.magenta),
+                    ev.server_name.style(self.dimmed)
+                );
+                ts_msg!(
+                    self,
+                    "{}",
+                    "declining (not supported in exec mode)".style(self.dimmed)
+                );
+            }
             EventMsg::TurnComplete(TurnCompleteEvent {
                 last_agent_message, ..
             }) => {
@@ -866,7 +879,10 @@ impl EventProcessor for EventProcessorWithHumanOutput {
             | EventMsg::ExecCommandOutputDelta(_)
             | EventMsg::GetHistoryEntryResponse(_)
             | EventMsg::McpListToolsResponse(_)
+            | EventMsg::ListMcpPromptsResponse(_)
+            | EventMsg::GetMcpPromptResponse(_)
             | EventMsg::ListCustomPromptsResponse(_)
+            | EventMsg::ExpandCustomPromptResponse(_)
             | EventMsg::ListSkillsResponse(_)
             | EventMsg::ListRemoteSkillsResponse(_)
             | EventMsg::RemoteSkillDownloaded(_)
@@ -886,6 +902,8 @@ impl EventProcessor for EventProcessorWithHumanOutput {
             | EventMsg::SkillsUpdateAvailable
             | EventMsg::UndoCompleted(_)
             | EventMsg::UndoStarted(_)
+            | EventMsg::OverlayWorkspaceChanges(_)
+            | EventMsg::BackgroundServicesUpdated(_)
             | EventMsg::ThreadRolledBack(_)
             | EventMsg::RequestUserInput(_)
             | EventMsg::RequestPermissions(_)
@@ -989,6 +1007,9 @@ impl EventProcessorWithHumanOutput {
     fn hook_event_name(event_name: HookEventName) -> &'static str {
         match event_name {
             HookEventName::SessionStart => "SessionStart",
+            HookEventName::UserPromptSubmit => "UserPromptSubmit",
+            HookEventName::PreToolUse => "PreToolUse",
+            HookEventName::PostToolUse => "PostToolUse",
             HookEventName::Stop => "Stop",
         }
     }
@@ -1028,7 +1049,10 @@ impl EventProcessorWithHumanOutput {
                     | EventMsg::ExecCommandOutputDelta(_)
                     | EventMsg::GetHistoryEntryResponse(_)
                     | EventMsg::McpListToolsResponse(_)
+                    | EventMsg::ListMcpPromptsResponse(_)
+                    | EventMsg::GetMcpPromptResponse(_)
                     | EventMsg::ListCustomPromptsResponse(_)
+                    | EventMsg::ExpandCustomPromptResponse(_)
                     | EventMsg::ListSkillsResponse(_)
                     | EventMsg::ListRemoteSkillsResponse(_)
                     | EventMsg::RemoteSkillDownloaded(_)
@@ -1048,6 +1072,8 @@ impl EventProcessorWithHumanOutput {
                     | EventMsg::SkillsUpdateAvailable
                     | EventMsg::UndoCompleted(_)
                     | EventMsg::UndoStarted(_)
+                    | EventMsg::OverlayWorkspaceChanges(_)
+                    | EventMsg::BackgroundServicesUpdated(_)
                     | EventMsg::ThreadRolledBack(_)
                     | EventMsg::RequestUserInput(_)
                     | EventMsg::RequestPermissions(_)
@@ -44,6 +44,7 @@ use codex_protocol::plan_tool::StepStatus;
 use codex_protocol::plan_tool::UpdatePlanArgs;
 use codex_protocol::protocol;
 use codex_protocol::protocol::AgentStatus as CoreAgentStatus;
+use codex_protocol::protocol::CodexErrorInfo;
 use codex_protocol::protocol::CollabAgentInteractionBeginEvent;
 use codex_protocol::protocol::CollabAgentInteractionEndEvent;
 use codex_protocol::protocol::CollabAgentSpawnBeginEvent;
@@ -52,6 +53,7 @@ use codex_protocol::protocol::CollabCloseBeginEvent;
 use codex_protocol::protocol::CollabCloseEndEvent;
 use codex_protocol::protocol::CollabWaitingBeginEvent;
 use codex_protocol::protocol::CollabWaitingEndEvent;
+use codex_protocol::protocol::RunBudgetKind;
 use serde_json::Value as JsonValue;
 use tracing::error;
 use tracing::warn;
@@ -70,6 +72,7 @@ pub struct EventProcessorWithJsonOutput {
     running_collab_tool_calls: HashMap<String, RunningCollabToolCall>,
     running_web_search_calls: HashMap<String, String>,
     last_critical_error: Option<ThreadErrorEvent>,
+    last_run_budget_exceeded: Option<RunBudgetKind>,
 }
 
 #[derive(Debug, Clone)]
@@ -113,6 +116,7 @@ impl EventProcessorWithJsonOutput {
             running_collab_tool_calls: HashMap::new(),
             running_web_search_calls: HashMap::new(),
             last_critical_error: None,
+            last_run_budget_exceeded: None,
         }
     }
 
@@ -166,6 +170,10 @@ impl EventProcessorWithJsonOutput {
                     message: ev.message.clone(),
                 };
                 self.last_critical_error = Some(error.clone());
+                self.last_run_budget_exceeded = match ev.codex_error_info {
+                    Some(CodexErrorInfo::RunBudgetExceeded { budget }) => Some(budget),
+                    _ => None,
+                };
                 vec![ThreadEvent::Error(error)]
             }
             protocol::EventMsg::Warning(ev) => {
@@ -746,6 +754,7 @@ impl EventProcessorWithJsonOutput {
 
     fn handle_task_started(&mut self, _: &protocol::TurnStartedEvent) -> Vec<ThreadEvent> {
         self.last_critical_error = None;
+        self.last_run_budget_exceeded = None;
         vec![ThreadEvent::TurnStarted(TurnStartedEvent {})]
     }
 
@@ -788,7 +797,10 @@ impl EventProcessorWithJsonOutput {
         }
 
         if let Some(error) = self.last_critical_error.take() {
-            items.push(ThreadEvent::TurnFailed(TurnFailedEvent { error }));
+            items.push(ThreadEvent::TurnFailed(TurnFailedEvent {
+                error,
+                reason: self.last_run_budget_exceeded.take(),
+            }));
         } else {
             items.push(ThreadEvent::TurnCompleted(TurnCompletedEvent { usage }));
         }
@@ -4,15 +4,18 @@
 // For both modes, any other output must be written to stderr.
 #![deny(clippy::print_stdout)]
 
+mod batch;
 mod cli;
 mod event_processor;
 mod event_processor_with_human_output;
 pub mod event_processor_with_jsonl_output;
 pub mod exec_events;
+mod review_report;
 
 pub use cli::Cli;
 pub use cli::Command;
 pub use cli::ReviewArgs;
+use cli::ReviewReportFormat;
 use codex_app_server_client::DEFAULT_IN_PROCESS_CHANNEL_CAPACITY;
 use codex_app_server_client::InProcessAppServerClient;
 use codex_app_server_client::InProcessClientStartArgs;
@@ -24,6 +27,8 @@ use codex_app_server_protocol::JSONRPCErrorError;
 use codex_app_server_protocol::JSONRPCNotification;
 use codex_app_server_protocol::McpServerElicitationAction;
 use codex_app_server_protocol::McpServerElicitationRequestResponse;
+use codex_app_server_protocol::McpServerSamplingDecision;
+use codex_app_server_protocol::McpServerSamplingRequestResponse;
 use codex_app_server_protocol::RequestId;
 use codex_app_server_protocol::ReviewStartParams;
 use codex_app_server_protocol::ReviewStartResponse;
@@ -64,6 +69,8 @@ use codex_otel::traceparent_context_from_env;
 use codex_protocol::account::PlanType as AccountPlanType;
 use codex_protocol::config_types::SandboxMode;
 use codex_protocol::protocol::AskForApproval;
+use codex_protocol::protocol::CodexErrorInfo;
+use codex_protocol::protocol::ErrorEvent;
 use codex_protocol::protocol::Event;
 use codex_protocol::protocol::EventMsg;
 use codex_protocol::protocol::ReviewRequest;
@@ -82,6 +89,7 @@ use std::collections::HashSet;
 use std::collections::VecDeque;
 use std::io::IsTerminal;
 use std::io::Read;
+use std::path::Path;
 use std::path::PathBuf;
 use supports_color::Stream;
 use tokio::sync::mpsc;
@@ -105,6 +113,12 @@ use codex_core::find_thread_path_by_name_str;
 
 const DEFAULT_ANALYTICS_ENABLED: bool = true;
 
+/// Exit status when a `--max-*` or `--timeout` run budget stopped the turn,
+/// distinct from the generic failure status `1`.
+const RUN_BUDGET_EXCEEDED_EXIT_CODE: i32 = 3;
+
+const DEFAULT_BATCH_CONCURRENCY: usize = 4;
+
 enum InitialOperation {
     UserTurn {
         items: Vec<UserInput>,
@@ -145,6 +159,7 @@ struct ExecRunArgs {
     oss: bool,
     output_schema_path: Option<PathBuf>,
     prompt: Option<String>,
+    batch: cli::BatchArgs,
     skip_git_repo_check: bool,
     stderr_with_ansi: bool,
 }
@@ -182,7 +197,9 @@ pub async fn run_main(cli: Cli, arg0_paths: Arg0DispatchPaths) -> anyhow::Result
         sandbox_mode: sandbox_mode_cli_arg,
         prompt,
         output_schema: output_schema_path,
-        config_overrides,
+        run_budget,
+        batch,
+        mut config_overrides,
         progress_cursor,
     } = cli;
 
@@ -234,6 +251,11 @@ pub async fn run_main(cli: Cli, arg0_paths: Arg0DispatchPaths) -> anyhow::Result
         sandbox_mode_cli_arg.map(Into::<SandboxMode>::into)
     };
 
+    // Run budget flags are applied after `-c` overrides so they win.
+    config_overrides
+        .raw_overrides
+        .extend(run_budget.config_overrides());
+
     // Parse `-c` overrides from the CLI.
     let cli_kv_overrides = match config_overrides.parse_overrides() {
         Ok(v) => v,
@@ -458,6 +480,7 @@ pub async fn run_main(cli: Cli, arg0_paths: Arg0DispatchPaths) -> anyhow::Result
         oss,
         output_schema_path,
         prompt,
+        batch,
         skip_git_repo_check,
         stderr_with_ansi,
     })
@@ -480,6 +503,7 @@ async fn run_exec_session(args: ExecRunArgs) -> anyhow::Result<()> {
         oss,
         output_schema_path,
         prompt,
+        batch,
         skip_git_repo_check,
         stderr_with_ansi,
     } = args;
@@ -518,6 +542,41 @@ async fn run_exec_session(args: ExecRunArgs) -> anyhow::Result<()> {
             .map_err(|e| anyhow::anyhow!("OSS setup failed: {e}"))?;
     }
 
+    if let Some(tasks_file) = batch.tasks_file {
+        if command.is_some() {
+            anyhow::bail!("--batch cannot be combined with a subcommand");
+        }
+        let contents = if tasks_file.as_os_str() == "-" {
+            let mut contents = String::new();
+            std::io::stdin()
+                .read_to_string(&mut contents)
+                .map_err(|err| anyhow::anyhow!("failed to read batch tasks from stdin: {err}"))?;
+            contents
+        } else {
+            std::fs::read_to_string(&tasks_file).map_err(|err| {
+                anyhow::anyhow!(
+                    "failed to read batch tasks file {}: {err}",
+                    tasks_file.display()
+                )
+            })?
+        };
+        let default_output_schema = load_output_schema(output_schema_path);
+        let tasks =
+            batch::parse_batch_tasks(&contents, &config.cwd, default_output_schema.as_ref())?;
+        return batch::run_batch(batch::BatchRunArgs {
+            in_process_start_args,
+            config,
+            tasks,
+            concurrency: batch
+                .concurrency
+                .map_or(DEFAULT_BATCH_CONCURRENCY, |concurrency| {
+                    usize::try_from(concurrency).unwrap_or(usize::MAX)
+                }),
+            skip_git_repo_check: skip_git_repo_check || dangerously_bypass_approvals_and_sandbox,
+        })
+        .await;
+    }
+
     let default_cwd = config.cwd.to_path_buf();
     let default_approval_policy = config.permissions.approval_policy.value();
     let default_sandbox_policy = config.permissions.sandbox_policy.get();
@@ -601,6 +660,12 @@ async fn run_exec_session(args: ExecRunArgs) -> anyhow::Result<()> {
 
     let (initial_operation, prompt_summary) = match (command.as_ref(), prompt, images) {
         (Some(ExecCommand::Review(review_cli)), _, _) => {
+            if json_mode
+                && review_cli.report.format != ReviewReportFormat::Text
+                && review_cli.report.output.is_none()
+            {
+                anyhow::bail!("--format requires --output when --json is set");
+            }
             let review_request = build_review_request(review_cli)?;
             let summary = codex_core::review_prompts::user_facing_hint(&review_request.target);
             (InitialOperation::Review { review_request }, summary)
@@ -731,6 +796,8 @@ async fn run_exec_session(args: ExecRunArgs) -> anyhow::Result<()> {
     // Track whether a fatal error was reported by the server so we can
     // exit with a non-zero status for automation-friendly signaling.
     let mut error_seen = false;
+    let mut run_budget_exceeded = false;
+    let mut review_output = None;
     let mut interrupt_channel_open = true;
     let primary_thread_id_for_requests = primary_thread_id.to_string();
     loop {
@@ -812,6 +879,13 @@ async fn run_exec_session(args: ExecRunArgs) -> anyhow::Result<()> {
                     // path is fully replaced by typed server notifications.
                     error_seen = true;
                 }
+                if let EventMsg::Error(ErrorEvent {
+                    codex_error_info: Some(CodexErrorInfo::RunBudgetExceeded { .. }),
+                    ..
+                }) = &event.msg
+                {
+                    run_budget_exceeded = true;
+                }
                 match &event.msg {
                     EventMsg::TurnComplete(payload) => {
                         if payload.turn_id != task_id {
@@ -823,6 +897,9 @@ async fn run_exec_session(args: ExecRunArgs) -> anyhow::Result<()> {
                             continue;
                         }
                     }
+                    EventMsg::ExitedReviewMode(payload) => {
+                        review_output = payload.review_output.clone();
+                    }
                     EventMsg::McpStartupUpdate(update) => {
                         if required_mcp_servers.contains(&update.server)
                             && let codex_protocol::protocol::McpStartupStatus::Failed { error } =
@@ -883,7 +960,24 @@ async fn run_exec_session(args: ExecRunArgs) -> anyhow::Result<()> {
     if let Err(err) = client.shutdown().await {
         warn!("in-process app-server shutdown failed: {err}");
     }
-    event_processor.print_final_output();
+    let review_report = match command.as_ref() {
+        Some(ExecCommand::Review(review_cli)) => Some(&review_cli.report),
+        _ => None,
+    };
+    // A machine-readable report on stdout replaces the final message.
+    let report_on_stdout = review_report
+        .is_some_and(|report| report.format != ReviewReportFormat::Text && report.output.is_none());
+    if !report_on_stdout {
+        event_processor.print_final_output();
+    }
+    if let Some(report) = review_report
+        && review_report::write_review_report(report, review_output.as_ref(), &config.cwd)
+    {
+        error_seen = true;
+    }
+    if run_budget_exceeded {
+        std::process::exit(RUN_BUDGET_EXCEEDED_EXIT_CODE);
+    }
     if error_seen {
         std::process::exit(1);
     }
@@ -1046,6 +1140,9 @@ fn review_target_to_api(target: ReviewTarget) -> ApiReviewTarget {
         ReviewTarget::UncommittedChanges => ApiReviewTarget::UncommittedChanges,
         ReviewTarget::BaseBranch { branch } => ApiReviewTarget::BaseBranch { branch },
         ReviewTarget::Commit { sha, title } => ApiReviewTarget::Commit { sha, title },
+        ReviewTarget::CommitRange { base, head } => ApiReviewTarget::CommitRange { base, head },
+        ReviewTarget::PatchFile { path } => ApiReviewTarget::PatchFile { path },
+        ReviewTarget::Diff { diff, title } => ApiReviewTarget::Diff { diff, title },
         ReviewTarget::Custom { instructions } => ApiReviewTarget::Custom { instructions },
     }
 }
@@ -1117,6 +1214,13 @@ fn canceled_mcp_server_elicitation_response() -> Result<Value, String> {
     .map_err(|err| format!("failed to encode mcp elicitation response: {err}"))
 }
 
+fn declined_mcp_server_sampling_response() -> Result<Value, String> {
+    serde_json::to_value(McpServerSamplingRequestResponse {
+        decision: McpServerSamplingDecision::Decline,
+    })
+    .map_err(|err| format!("failed to encode mcp sampling response: {err}"))
+}
+
 async fn request_shutdown(
     client: &InProcessAppServerClient,
     request_ids: &mut RequestIdSequencer,
@@ -1330,6 +1434,17 @@ async fn handle_server_request(
             )
             .await
         }
+        ServerRequest::McpServerSamplingRequest { request_id, .. } => {
+            // There is no one to approve the completion, so decline it the
+            // same way an interactive user would.
+            match declined_mcp_server_sampling_response() {
+                Ok(value) => {
+                    resolve_server_request(client, request_id, value, "mcpServer/sampling/request")
+                        .await
+                }
+                Err(err) => Err(err),
+            }
+        }
     };
 
     if let Err(err) = handle_result {
@@ -1572,6 +1687,26 @@ fn build_review_request(args: &ReviewArgs) -> anyhow::Result<ReviewRequest> {
             sha,
             title: args.commit_title.clone(),
         }
+    } else if let Some(range) = args.range.as_deref() {
+        let (base, head) = parse_commit_range(range)?;
+        ReviewTarget::CommitRange { base, head }
+    } else if let Some(path) = args.patch.as_deref() {
+        if path == Path::new("-") {
+            let mut bytes = Vec::new();
+            std::io::stdin()
+                .read_to_end(&mut bytes)
+                .map_err(|err| anyhow::anyhow!("failed to read diff from stdin: {err}"))?;
+            ReviewTarget::Diff {
+                diff: decode_prompt_bytes(&bytes)
+                    .map_err(|err| anyhow::anyhow!("failed to read diff from stdin: {err}"))?,
+                title: Some("diff from stdin".to_string()),
+            }
+        } else {
+            // Resolve against the invocation directory rather than `--cd`.
+            ReviewTarget::PatchFile {
+                path: std::path::absolute(path)?,
+            }
+        }
     } else if let Some(prompt_arg) = args.prompt.clone() {
         let prompt = resolve_prompt(Some(prompt_arg)).trim().to_string();
         if prompt.is_empty() {
@@ -1582,7 +1717,7 @@ fn build_review_request(args: &ReviewArgs) -> anyhow::Result<ReviewRequest> {
         }
     } else {
         anyhow::bail!(
-            "Specify --uncommitted, --base, --commit, or provide custom review instructions"
+            "Specify --uncommitted, --base, --commit, --range, --patch, or provide custom review instructions"
         );
     };
 
@@ -1592,6 +1727,24 @@ fn build_review_request(args: &ReviewArgs) -> anyhow::Result<ReviewRequest> {
     })
 }
 
+/// Splits `BASE..HEAD`; an omitted end defaults to `HEAD`, as in git.
+fn parse_commit_range(range: &str) -> anyhow::Result<(String, String)> {
+    let Some((base, head)) = range.trim().split_once("..") else {
+        anyhow::bail!("Expected a commit range like BASE..HEAD, got `{range}`");
+    };
+    if head.starts_with('.') {
+        anyhow::bail!("Symmetric ranges (BASE...HEAD) are not supported; use BASE..HEAD");
+    }
+    let or_head = |rev: &str| {
+        if rev.is_empty() {
+            "HEAD".to_string()
+        } else {
+            rev.to_string()
+        }
+    };
+    Ok((or_head(base), or_head(head)))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1640,7 +1793,10 @@ mod tests {
             base: None,
             commit: None,
             commit_title: None,
+            range: None,
+            patch: None,
             prompt: None,
+            report: ReviewReportArgs::default(),
         };
         let request = build_review_request(&args).expect("builds uncommitted review request");
 
@@ -1659,7 +1815,10 @@ mod tests {
             base: None,
             commit: Some("123456789".to_string()),
             commit_title: Some("Add review command".to_string()),
+            range: None,
+            patch: None,
             prompt: None,
+            report: ReviewReportArgs::default(),
         };
         let request = build_review_request(&args).expect("builds commit review request");
 
@@ -1681,7 +1840,10 @@ mod tests {
             base: None,
             commit: None,
             commit_title: None,
+            range: None,
+            patch: None,
             prompt: Some("  custom review instructions  ".to_string()),
+            report: ReviewReportArgs::default(),
         };
         let request = build_review_request(&args).expect("builds custom review request");
 
@@ -1695,6 +1857,59 @@ mod tests {
         assert_eq!(request, expected);
     }
 
+    #[test]
+    fn builds_commit_range_review_request() {
+        let args = ReviewArgs {
+            uncommitted: false,
+            base: None,
+            commit: None,
+            commit_title: None,
+            range: Some("origin/main..".to_string()),
+            patch: None,
+            prompt: None,
+            report: ReviewReportArgs::default(),
+        };
+        let request = build_review_request(&args).expect("builds commit range review request");
+
+        let expected = ReviewRequest {
+            target: ReviewTarget::CommitRange {
+                base: "origin/main".to_string(),
+                head: "HEAD".to_string(),
+            },
+            user_facing_hint: None,
+        };
+
+        assert_eq!(request, expected);
+        assert!(parse_commit_range("main").is_err());
+        assert!(parse_commit_range("main...feature").is_err());
+    }
+
+    #[test]
+    fn builds_patch_file_review_request_with_absolute_path() {
+        let args = ReviewArgs {
+            uncommitted: false,
+            base: None,
+            commit: None,
+            commit_title: None,
+            range: None,
+            patch: Some(PathBuf::from("fix.patch")),
+            prompt: None,
+            report: ReviewReportArgs::default(),
+        };
+        let request = build_review_request(&args).expect("builds patch review request");
+
+        let expected = ReviewRequest {
+            target: ReviewTarget::PatchFile {
+                path: std::env::current_dir()
+                    .expect("current dir")
+                    .join("fix.patch"),
+            },
+            user_facing_hint: None,
+        };
+
+        assert_eq!(request, expected);
+    }
+
     #[test]
     fn decode_prompt_bytes_strips_utf8_bom() {
         let input = [0xEF, 0xBB, 0xBF, b'h', b'i', b'\n'];
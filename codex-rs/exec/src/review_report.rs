@@ -0,0 +1,341 @@
+//! Machine-readable reports for `codex review --format json|sarif|junit`.
+
+use std::path::Path;
+
+use codex_core::review_format::render_review_output_text;
+use codex_protocol::protocol::ReviewFinding;
+use codex_protocol::protocol::ReviewOutputEvent;
+use serde_json::json;
+
+use crate::cli::ReviewReportArgs;
+use crate::cli::ReviewReportFormat;
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const SARIF_VERSION: &str = "2.1.0";
+const TOOL_NAME: &str = "codex-review";
+
+/// Findings at or below this priority (P0 and P1) can fail the build.
+const BLOCKING_PRIORITY: i32 = 1;
+
+/// Writes the report requested by `args` and returns `true` when the review
+/// should fail the run, either because a blocking finding was reported or
+/// because the report could not be produced.
+pub(crate) fn write_review_report(
+    args: &ReviewReportArgs,
+    output: Option<&ReviewOutputEvent>,
+    cwd: &Path,
+) -> bool {
+    let wants_report = args.format != ReviewReportFormat::Text || args.output.is_some();
+    let Some(output) = output else {
+        if wants_report || args.fail_on_confidence.is_some() {
+            eprintln!("Review finished without structured output; no report was written.");
+            return true;
+        }
+        return false;
+    };
+
+    if wants_report {
+        let report = match render_review_report(args.format, output, cwd) {
+            Ok(report) => report,
+            Err(err) => {
+                eprintln!("Failed to render review report: {err}");
+                return true;
+            }
+        };
+        match &args.output {
+            Some(path) => {
+                if let Err(err) = std::fs::write(path, report) {
+                    eprintln!("Failed to write review report {}: {err}", path.display());
+                    return true;
+                }
+            }
+            None => {
+                #[allow(clippy::print_stdout)]
+                {
+                    println!("{report}");
+                }
+            }
+        }
+    }
+
+    let Some(threshold) = args.fail_on_confidence else {
+        return false;
+    };
+    let blocking = blocking_findings(output, threshold);
+    if blocking.is_empty() {
+        return false;
+    }
+    eprintln!(
+        "{} P0/P1 review finding(s) at or above confidence {threshold}:",
+        blocking.len()
+    );
+    for finding in blocking {
+        eprintln!(
+            "  [{}] {} ({:.2})",
+            priority_label(finding.priority),
+            finding.title,
+            finding.confidence_score
+        );
+    }
+    true
+}
+
+fn render_review_report(
+    format: ReviewReportFormat,
+    output: &ReviewOutputEvent,
+    cwd: &Path,
+) -> anyhow::Result<String> {
+    Ok(match format {
+        ReviewReportFormat::Text => render_review_output_text(output),
+        ReviewReportFormat::Json => serde_json::to_string_pretty(output)?,
+        ReviewReportFormat::Sarif => serde_json::to_string_pretty(&render_sarif(output, cwd))?,
+        ReviewReportFormat::Junit => render_junit(output, cwd),
+    })
+}
+
+/// P0/P1 findings whose confidence score is at or above `threshold`.
+fn blocking_findings(output: &ReviewOutputEvent, threshold: f32) -> Vec<&ReviewFinding> {
+    output
+        .findings
+        .iter()
+        .filter(|finding| {
+            finding.priority <= BLOCKING_PRIORITY && finding.confidence_score >= threshold
+        })
+        .collect()
+}
+
+fn sarif_level(priority: i32) -> &'static str {
+    match priority {
+        i32::MIN..=1 => "error",
+        2 => "warning",
+        _ => "note",
+    }
+}
+
+fn priority_label(priority: i32) -> String {
+    format!("P{}", priority.max(0))
+}
+
+fn render_sarif(output: &ReviewOutputEvent, cwd: &Path) -> serde_json::Value {
+    let mut priorities = output
+        .findings
+        .iter()
+        .map(|finding| finding.priority.max(0))
+        .collect::<Vec<_>>();
+    priorities.sort_unstable();
+    priorities.dedup();
+    let rules = priorities
+        .into_iter()
+        .map(|priority| {
+            json!({
+                "id": priority_label(priority),
+                "shortDescription": { "text": format!("Priority {priority} review finding") },
+                "defaultConfiguration": { "level": sarif_level(priority) },
+            })
+        })
+        .collect::<Vec<_>>();
+    let results = output
+        .findings
+        .iter()
+        .map(|finding| {
+            let line_range = &finding.code_location.line_range;
+            json!({
+                "ruleId": priority_label(finding.priority),
+                "level": sarif_level(finding.priority),
+                "message": { "text": format!("{}\n\n{}", finding.title, finding.body) },
+                "locations": [{
+                    "physicalLocation": {
+                        "artifactLocation": {
+                            "uri": relative_uri(&finding.code_location.absolute_file_path, cwd),
+                            "uriBaseId": "%SRCROOT%",
+                        },
+                        "region": {
+                            "startLine": line_range.start.max(1),
+                            "endLine": line_range.end.max(line_range.start).max(1),
+                        },
+                    },
+                }],
+                "properties": {
+                    "priority": finding.priority,
+                    "confidence": finding.confidence_score,
+                },
+            })
+        })
+        .collect::<Vec<_>>();
+
+    json!({
+        "$schema": SARIF_SCHEMA,
+        "version": SARIF_VERSION,
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": TOOL_NAME,
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "rules": rules,
+                },
+            },
+            "originalUriBaseIds": {
+                "%SRCROOT%": { "uri": directory_uri(cwd) },
+            },
+            "results": results,
+            "properties": {
+                "overallCorrectness": output.overall_correctness,
+                "overallExplanation": output.overall_explanation,
+                "overallConfidence": output.overall_confidence_score,
+            },
+        }],
+    })
+}
+
+/// Every finding becomes a failed test case; a review without findings
+/// reports a single passing case so the suite is never empty.
+fn render_junit(output: &ReviewOutputEvent, cwd: &Path) -> String {
+    let mut cases = Vec::new();
+    for finding in &output.findings {
+        let location = format!(
+            "{}:{}-{}",
+            relative_uri(&finding.code_location.absolute_file_path, cwd),
+            finding.code_location.line_range.start,
+            finding.code_location.line_range.end
+        );
+        cases.push(format!(
+            "    <testcase classname=\"{TOOL_NAME}.{}\" name=\"{}\" file=\"{}\">\n      <failure type=\"{}\" message=\"{}\">{}</failure>\n    </testcase>",
+            priority_label(finding.priority),
+            xml_escape(&finding.title),
+            xml_escape(&relative_uri(&finding.code_location.absolute_file_path, cwd)),
+            priority_label(finding.priority),
+            xml_escape(&format!(
+                "{} (confidence {:.2})",
+                finding.title, finding.confidence_score
+            )),
+            xml_escape(&format!("{location}\n\n{}", finding.body)),
+        ));
+    }
+    if cases.is_empty() {
+        cases.push(format!(
+            "    <testcase classname=\"{TOOL_NAME}\" name=\"no findings\">\n      <system-out>{}</system-out>\n    </testcase>",
+            xml_escape(output.overall_explanation.trim())
+        ));
+    }
+
+    let tests = cases.len();
+    let failures = output.findings.len();
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<testsuites name=\"{TOOL_NAME}\" tests=\"{tests}\" failures=\"{failures}\">\n  <testsuite name=\"{TOOL_NAME}\" tests=\"{tests}\" failures=\"{failures}\">\n{}\n  </testsuite>\n</testsuites>\n",
+        cases.join("\n")
+    )
+}
+
+/// Forward-slash path relative to `cwd` when the finding is inside it.
+fn relative_uri(path: &Path, cwd: &Path) -> String {
+    let path = path.strip_prefix(cwd).unwrap_or(path);
+    path.to_string_lossy().replace('\\', "/")
+}
+
+fn directory_uri(dir: &Path) -> String {
+    let mut uri = format!("file://{}", dir.to_string_lossy().replace('\\', "/"));
+    if !uri.ends_with('/') {
+        uri.push('/');
+    }
+    uri
+}
+
+fn xml_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            // XML 1.0 forbids most control characters, even when escaped.
+            ch if ch.is_control() && !matches!(ch, '\n' | '\r' | '\t') => {}
+            ch => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::protocol::ReviewCodeLocation;
+    use codex_protocol::protocol::ReviewLineRange;
+    use pretty_assertions::assert_eq;
+    use std::path::PathBuf;
+
+    fn finding(title: &str, priority: i32, confidence_score: f32) -> ReviewFinding {
+        ReviewFinding {
+            title: title.to_string(),
+            body: "Details & <context>".to_string(),
+            confidence_score,
+            priority,
+            code_location: ReviewCodeLocation {
+                absolute_file_path: PathBuf::from("/repo/src/lib.rs"),
+                line_range: ReviewLineRange { start: 3, end: 5 },
+            },
+        }
+    }
+
+    fn review_output() -> ReviewOutputEvent {
+        ReviewOutputEvent {
+            findings: vec![
+                finding("Null deref", 0, 0.9),
+                finding("Style nit", 3, 0.95),
+                finding("Unchecked error", 1, 0.4),
+            ],
+            overall_correctness: "patch is incorrect".to_string(),
+            overall_explanation: "One crash.".to_string(),
+            overall_confidence_score: 0.8,
+        }
+    }
+
+    #[test]
+    fn blocking_findings_use_priority_and_confidence() {
+        let output = review_output();
+        let titles = blocking_findings(&output, 0.5)
+            .into_iter()
+            .map(|finding| finding.title.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(titles, vec!["Null deref"]);
+        assert_eq!(blocking_findings(&output, 0.4).len(), 2);
+    }
+
+    #[test]
+    fn sarif_maps_priority_to_level_and_relativizes_paths() {
+        let sarif = render_sarif(&review_output(), Path::new("/repo"));
+        let results = &sarif["runs"][0]["results"];
+        assert_eq!(results[0]["level"], "error");
+        assert_eq!(results[1]["level"], "note");
+        assert_eq!(results[2]["ruleId"], "P1");
+        assert_eq!(
+            results[0]["locations"][0]["physicalLocation"]["artifactLocation"]["uri"],
+            "src/lib.rs"
+        );
+        assert_eq!(
+            results[0]["locations"][0]["physicalLocation"]["region"],
+            json!({ "startLine": 3, "endLine": 5 })
+        );
+        let rule_ids = sarif["runs"][0]["tool"]["driver"]["rules"]
+            .as_array()
+            .expect("rules")
+            .iter()
+            .map(|rule| rule["id"].as_str().expect("rule id"))
+            .collect::<Vec<_>>();
+        assert_eq!(rule_ids, vec!["P0", "P1", "P3"]);
+    }
+
+    #[test]
+    fn junit_reports_each_finding_as_failure() {
+        let junit = render_junit(&review_output(), Path::new("/repo"));
+        assert!(junit.contains(r#"<testsuite name="codex-review" tests="3" failures="3">"#));
+        assert!(junit.contains(
+            r#"<testcase classname="codex-review.P0" name="Null deref" file="src/lib.rs">"#
+        ));
+        assert!(junit.contains("Details &amp; &lt;context&gt;"));
+
+        let empty = render_junit(&ReviewOutputEvent::default(), Path::new("/repo"));
+        assert!(empty.contains(r#"tests="1" failures="0""#));
+    }
+}
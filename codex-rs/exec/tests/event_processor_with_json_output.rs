@@ -62,6 +62,7 @@ use codex_protocol::protocol::McpToolCallEndEvent;
 use codex_protocol::protocol::PatchApplyBeginEvent;
 use codex_protocol::protocol::PatchApplyEndEvent;
 use codex_protocol::protocol::PatchApplyStatus as CorePatchApplyStatus;
+use codex_protocol::protocol::RunBudgetKind;
 use codex_protocol::protocol::SandboxPolicy;
 use codex_protocol::protocol::SessionConfiguredEvent;
 use codex_protocol::protocol::WarningEvent;
@@ -854,10 +855,48 @@ fn error_followed_by_task_complete_produces_turn_failed() {
             error: ThreadErrorEvent {
                 message: "boom".to_string(),
             },
+            reason: None,
         })]
     );
 }
 
+#[test]
+fn run_budget_error_sets_turn_failed_reason() {
+    let mut ep = EventProcessorWithJsonOutput::new(None);
+    let message = "Run budget `max_turns` exhausted after 3 model requests; stopping the turn.";
+
+    ep.collect_thread_events(&event(
+        "e1",
+        EventMsg::Error(ErrorEvent {
+            message: message.to_string(),
+            codex_error_info: Some(CodexErrorInfo::RunBudgetExceeded {
+                budget: RunBudgetKind::MaxTurns,
+            }),
+        }),
+    ));
+    let turn_failed = ep.collect_thread_events(&event(
+        "e2",
+        EventMsg::TurnComplete(codex_protocol::protocol::TurnCompleteEvent {
+            turn_id: "turn-1".to_string(),
+            last_agent_message: None,
+        }),
+    ));
+
+    assert_eq!(
+        turn_failed,
+        vec![ThreadEvent::TurnFailed(TurnFailedEvent {
+            error: ThreadErrorEvent {
+                message: message.to_string(),
+            },
+            reason: Some(RunBudgetKind::MaxTurns),
+        })]
+    );
+    assert_eq!(
+        serde_json::to_value(&turn_failed[0]).expect("serialize turn.failed")["reason"],
+        "max_turns"
+    );
+}
+
 #[test]
 fn exec_command_end_success_produces_completed_command_item() {
     let mut ep = EventProcessorWithJsonOutput::new(None);
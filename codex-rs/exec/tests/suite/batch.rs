@@ -0,0 +1,145 @@
+#![cfg(not(target_os = "windows"))]
+#![allow(clippy::expect_used, clippy::unwrap_used)]
+
+use core_test_support::responses;
+use core_test_support::test_codex_exec::test_codex_exec;
+use pretty_assertions::assert_eq;
+use serde_json::Value;
+use serde_json::json;
+
+fn stdout_lines(output: &[u8]) -> Vec<Value> {
+    String::from_utf8_lossy(output)
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("stdout line should be JSON"))
+        .collect()
+}
+
+fn lines_of_type<'a>(lines: &'a [Value], event_type: &str) -> Vec<&'a Value> {
+    lines
+        .iter()
+        .filter(|line| line["type"] == event_type)
+        .collect()
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn batch_runs_each_task_in_its_own_thread() -> anyhow::Result<()> {
+    let test = test_codex_exec();
+    std::fs::create_dir(test.cwd_path().join("repo-b"))?;
+    let tasks_path = test.cwd_path().join("tasks.jsonl");
+    std::fs::write(
+        &tasks_path,
+        [
+            json!({"id": "a", "prompt": "first task"}).to_string(),
+            json!({"id": "b", "prompt": "second task", "cwd": "repo-b"}).to_string(),
+        ]
+        .join("\n"),
+    )?;
+
+    let server = responses::start_mock_server().await;
+    let body = responses::sse(vec![
+        responses::ev_response_created("resp1"),
+        responses::ev_assistant_message("m1", "fixture hello"),
+        responses::ev_completed("resp1"),
+    ]);
+    let response_mock = responses::mount_sse_sequence(&server, vec![body.clone(), body]).await;
+
+    let output = test
+        .cmd_with_server(&server)
+        .arg("--skip-git-repo-check")
+        .arg("--batch")
+        .arg(&tasks_path)
+        .arg("--batch-concurrency")
+        .arg("2")
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+
+    let lines = stdout_lines(&output);
+    let started = lines_of_type(&lines, "task.started");
+    let mut thread_ids = started
+        .iter()
+        .map(|line| line["thread_id"].as_str().expect("thread id"))
+        .collect::<Vec<_>>();
+    thread_ids.sort_unstable();
+    thread_ids.dedup();
+    assert_eq!(thread_ids.len(), 2);
+
+    let mut completed = lines_of_type(&lines, "task.completed")
+        .into_iter()
+        .map(|line| {
+            (
+                line["task_id"].clone(),
+                line["status"].clone(),
+                line["exit_code"].clone(),
+                line["final_message"].clone(),
+            )
+        })
+        .collect::<Vec<_>>();
+    completed.sort_by_key(|(task_id, ..)| task_id.to_string());
+    assert_eq!(
+        completed,
+        vec![
+            (
+                json!("a"),
+                json!("completed"),
+                json!(0),
+                json!("fixture hello")
+            ),
+            (
+                json!("b"),
+                json!("completed"),
+                json!(0),
+                json!("fixture hello")
+            ),
+        ]
+    );
+    assert!(
+        lines
+            .iter()
+            .any(|line| line["task_id"] == "b" && line["type"] == "turn.completed")
+    );
+    assert_eq!(
+        lines.last(),
+        Some(&json!({"type": "batch.completed", "total": 2, "completed": 2, "failed": 0}))
+    );
+    assert_eq!(response_mock.requests().len(), 2);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn batch_reports_failed_task_and_exits_non_zero() -> anyhow::Result<()> {
+    let test = test_codex_exec();
+    let tasks_path = test.cwd_path().join("tasks.jsonl");
+    std::fs::write(
+        &tasks_path,
+        json!({"id": "missing", "prompt": "hi", "cwd": "does-not-exist"}).to_string(),
+    )?;
+    let server = responses::start_mock_server().await;
+
+    let output = test
+        .cmd_with_server(&server)
+        .arg("--skip-git-repo-check")
+        .arg("--batch")
+        .arg(&tasks_path)
+        .assert()
+        .code(1)
+        .get_output()
+        .stdout
+        .clone();
+
+    let lines = stdout_lines(&output);
+    let completed = lines_of_type(&lines, "task.completed");
+    assert_eq!(completed.len(), 1);
+    assert_eq!(completed[0]["task_id"], "missing");
+    assert_eq!(completed[0]["status"], "failed");
+    assert_eq!(completed[0]["exit_code"], 1);
+    assert_eq!(
+        lines.last(),
+        Some(&json!({"type": "batch.completed", "total": 1, "completed": 0, "failed": 1}))
+    );
+
+    Ok(())
+}
@@ -3,11 +3,13 @@ use std::path::Path;
 
 use codex_config::ConfigLayerStack;
 use codex_config::ConfigLayerStackOrdering;
+use codex_protocol::protocol::HookHandlerType;
 use regex::Regex;
 
 use super::ConfiguredHandler;
 use super::config::HookHandlerConfig;
 use super::config::HooksFile;
+use super::dispatcher::is_match_all_matcher;
 
 pub(crate) struct DiscoveryResult {
     pub handlers: Vec<ConfiguredHandler>,
@@ -80,6 +82,42 @@ pub(crate) fn discover_handlers(config_layer_stack: Option<&ConfigLayerStack>) -
             );
         }
 
+        for group in parsed.hooks.user_prompt_submit {
+            append_group_handlers(
+                &mut handlers,
+                &mut warnings,
+                &mut display_order,
+                source_path.as_path(),
+                codex_protocol::protocol::HookEventName::UserPromptSubmit,
+                None,
+                group.hooks,
+            );
+        }
+
+        for group in parsed.hooks.pre_tool_use {
+            append_group_handlers(
+                &mut handlers,
+                &mut warnings,
+                &mut display_order,
+                source_path.as_path(),
+                codex_protocol::protocol::HookEventName::PreToolUse,
+                group.matcher.as_deref(),
+                group.hooks,
+            );
+        }
+
+        for group in parsed.hooks.post_tool_use {
+            append_group_handlers(
+                &mut handlers,
+                &mut warnings,
+                &mut display_order,
+                source_path.as_path(),
+                codex_protocol::protocol::HookEventName::PostToolUse,
+                group.matcher.as_deref(),
+                group.hooks,
+            );
+        }
+
         for group in parsed.hooks.stop {
             append_group_handlers(
                 &mut handlers,
@@ -106,6 +144,7 @@ fn append_group_handlers(
     group_handlers: Vec<HookHandlerConfig>,
 ) {
     if let Some(matcher) = matcher
+        && !is_match_all_matcher(matcher)
         && let Err(err) = Regex::new(matcher)
     {
         warnings.push(format!(
@@ -140,8 +179,10 @@ fn append_group_handlers(
                 let timeout_sec = timeout_sec.unwrap_or(600).max(1);
                 handlers.push(ConfiguredHandler {
                     event_name,
+                    handler_type: HookHandlerType::Command,
                     matcher: matcher.map(ToOwned::to_owned),
                     command,
+                    model: None,
                     timeout_sec,
                     status_message,
                     source_path: source_path.to_path_buf(),
@@ -149,14 +190,58 @@ fn append_group_handlers(
                 });
                 *display_order += 1;
             }
-            HookHandlerConfig::Prompt {} => warnings.push(format!(
-                "skipping prompt hook in {}: prompt hooks are not supported yet",
-                source_path.display()
-            )),
-            HookHandlerConfig::Agent {} => warnings.push(format!(
-                "skipping agent hook in {}: agent hooks are not supported yet",
-                source_path.display()
-            )),
+            HookHandlerConfig::Prompt {
+                prompt,
+                timeout_sec,
+                model,
+                status_message,
+            } => {
+                if prompt.trim().is_empty() {
+                    warnings.push(format!(
+                        "skipping empty hook prompt in {}",
+                        source_path.display()
+                    ));
+                    continue;
+                }
+                handlers.push(ConfiguredHandler {
+                    event_name,
+                    handler_type: HookHandlerType::Prompt,
+                    matcher: matcher.map(ToOwned::to_owned),
+                    command: prompt,
+                    model,
+                    timeout_sec: timeout_sec.unwrap_or(30).max(1),
+                    status_message,
+                    source_path: source_path.to_path_buf(),
+                    display_order: *display_order,
+                });
+                *display_order += 1;
+            }
+            HookHandlerConfig::Agent {
+                prompt,
+                timeout_sec,
+                model,
+                status_message,
+            } => {
+                if prompt.trim().is_empty() {
+                    warnings.push(format!(
+                        "skipping empty hook prompt in {}",
+                        source_path.display()
+                    ));
+                    continue;
+                }
+                handlers.push(ConfiguredHandler {
+                    event_name,
+                    handler_type: HookHandlerType::Agent,
+                    matcher: matcher.map(ToOwned::to_owned),
+                    command: prompt,
+                    model,
+                    timeout_sec: timeout_sec.unwrap_or(60).max(1),
+                    status_message,
+                    source_path: source_path.to_path_buf(),
+                    display_order: *display_order,
+                });
+                *display_order += 1;
+            }
         }
     }
 }
@@ -14,6 +14,8 @@ use super::CommandShell;
 use super::ConfiguredHandler;
 use super::command_runner::CommandRunResult;
 use super::command_runner::run_command;
+use super::model_handler::run_model_handler;
+use crate::model_runner::HookModelRunner;
 
 #[derive(Debug)]
 pub(crate) struct ParsedHandler<T> {
@@ -21,33 +23,48 @@ pub(crate) struct ParsedHandler<T> {
     pub data: T,
 }
 
+/// Selects the handlers configured for `event_name`.
+///
+/// `matcher_input` is the value matched against each handler's matcher: the
+/// session start source for `SessionStart`, and the tool name for
+/// `PreToolUse`/`PostToolUse`. `UserPromptSubmit` and `Stop` handlers ignore
+/// matchers.
 pub(crate) fn select_handlers(
     handlers: &[ConfiguredHandler],
     event_name: HookEventName,
-    session_start_source: Option<&str>,
+    matcher_input: Option<&str>,
 ) -> Vec<ConfiguredHandler> {
     handlers
         .iter()
         .filter(|handler| handler.event_name == event_name)
         .filter(|handler| match event_name {
-            HookEventName::SessionStart => match (&handler.matcher, session_start_source) {
-                (Some(matcher), Some(source)) => regex::Regex::new(matcher)
-                    .map(|regex| regex.is_match(source))
-                    .unwrap_or(false),
+            HookEventName::SessionStart
+            | HookEventName::PreToolUse
+            | HookEventName::PostToolUse => match (handler.matcher.as_deref(), matcher_input) {
                 (None, _) => true,
-                _ => false,
+                (Some(matcher), _) if is_match_all_matcher(matcher) => true,
+                (Some(matcher), Some(input)) => regex::Regex::new(matcher)
+                    .map(|regex| regex.is_match(input))
+                    .unwrap_or(false),
+                (Some(_), None) => false,
             },
-            HookEventName::Stop => true,
+            HookEventName::UserPromptSubmit | HookEventName::Stop => true,
         })
         .cloned()
         .collect()
 }
 
+/// `*` is accepted as a match-everything matcher even though it is not a
+/// valid regex, mirroring the Claude hooks configuration format.
+pub(crate) fn is_match_all_matcher(matcher: &str) -> bool {
+    matcher == "*"
+}
+
 pub(crate) fn running_summary(handler: &ConfiguredHandler) -> HookRunSummary {
     HookRunSummary {
         id: handler.run_id(),
         event_name: handler.event_name,
-        handler_type: HookHandlerType::Command,
+        handler_type: handler.handler_type,
         execution_mode: HookExecutionMode::Sync,
         scope: scope_for_event(handler.event_name),
         source_path: handler.source_path.clone(),
@@ -61,19 +78,29 @@ pub(crate) fn running_summary(handler: &ConfiguredHandler) -> HookRunSummary {
     }
 }
 
+/// Runs every handler concurrently. `prompt` and `agent` handlers go through
+/// `model_runner`; their reply is parsed with the same `parse` function as
+/// command stdout.
 pub(crate) async fn execute_handlers<T>(
     shell: &CommandShell,
+    model_runner: &dyn HookModelRunner,
     handlers: Vec<ConfiguredHandler>,
     input_json: String,
     cwd: &Path,
     turn_id: Option<String>,
     parse: fn(&ConfiguredHandler, CommandRunResult, Option<String>) -> ParsedHandler<T>,
 ) -> Vec<ParsedHandler<T>> {
-    let results = join_all(
-        handlers
-            .iter()
-            .map(|handler| run_command(shell, handler, &input_json, cwd)),
-    )
+    let results = join_all(handlers.iter().map(|handler| {
+        let input_json = input_json.as_str();
+        async move {
+            match handler.handler_type {
+                HookHandlerType::Command => run_command(shell, handler, input_json, cwd).await,
+                HookHandlerType::Prompt | HookHandlerType::Agent => {
+                    run_model_handler(model_runner, handler, input_json, cwd).await
+                }
+            }
+        }
+    }))
     .await;
 
     handlers
@@ -92,7 +119,7 @@ pub(crate) fn completed_summary(
     HookRunSummary {
         id: handler.run_id(),
         event_name: handler.event_name,
-        handler_type: HookHandlerType::Command,
+        handler_type: handler.handler_type,
         execution_mode: HookExecutionMode::Sync,
         scope: scope_for_event(handler.event_name),
         source_path: handler.source_path.clone(),
@@ -109,7 +136,10 @@ pub(crate) fn completed_summary(
 fn scope_for_event(event_name: HookEventName) -> HookScope {
     match event_name {
         HookEventName::SessionStart => HookScope::Thread,
-        HookEventName::Stop => HookScope::Turn,
+        HookEventName::UserPromptSubmit
+        | HookEventName::PreToolUse
+        | HookEventName::PostToolUse
+        | HookEventName::Stop => HookScope::Turn,
     }
 }
 
@@ -118,6 +148,7 @@ mod tests {
     use std::path::PathBuf;
 
     use codex_protocol::protocol::HookEventName;
+    use codex_protocol::protocol::HookHandlerType;
 
     use super::ConfiguredHandler;
     use super::select_handlers;
@@ -130,8 +161,10 @@ mod tests {
     ) -> ConfiguredHandler {
         ConfiguredHandler {
             event_name,
+            handler_type: HookHandlerType::Command,
             matcher: matcher.map(str::to_owned),
             command: command.to_string(),
+            model: None,
             timeout_sec: 5,
             status_message: None,
             source_path: PathBuf::from("/tmp/hooks.json"),
@@ -172,6 +205,32 @@ mod tests {
         assert_eq!(selected[1].display_order, 1);
     }
 
+    #[test]
+    fn select_handlers_matches_tool_name_for_tool_events() {
+        let handlers = vec![
+            make_handler(HookEventName::PreToolUse, Some("^shell$"), "shell only", 0),
+            make_handler(
+                HookEventName::PreToolUse,
+                Some("apply_patch|shell"),
+                "either",
+                1,
+            ),
+            make_handler(HookEventName::PreToolUse, Some("*"), "wildcard", 2),
+            make_handler(HookEventName::PreToolUse, None, "no matcher", 3),
+            make_handler(HookEventName::PostToolUse, None, "post", 4),
+        ];
+
+        let selected = select_handlers(&handlers, HookEventName::PreToolUse, Some("apply_patch"));
+
+        assert_eq!(
+            selected
+                .iter()
+                .map(|handler| handler.command.as_str())
+                .collect::<Vec<_>>(),
+            vec!["either", "wildcard", "no matcher"]
+        );
+    }
+
     #[test]
     fn select_handlers_preserves_declaration_order() {
         let handlers = vec![
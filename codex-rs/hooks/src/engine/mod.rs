@@ -2,6 +2,7 @@ pub(crate) mod command_runner;
 pub(crate) mod config;
 pub(crate) mod discovery;
 pub(crate) mod dispatcher;
+pub(crate) mod model_handler;
 pub(crate) mod output_parser;
 pub(crate) mod schema_loader;
 
@@ -10,10 +11,17 @@ use std::path::PathBuf;
 use codex_config::ConfigLayerStack;
 use codex_protocol::protocol::HookRunSummary;
 
+use crate::events::post_tool_use::PostToolUseOutcome;
+use crate::events::post_tool_use::PostToolUseRequest;
+use crate::events::pre_tool_use::PreToolUseOutcome;
+use crate::events::pre_tool_use::PreToolUseRequest;
 use crate::events::session_start::SessionStartOutcome;
 use crate::events::session_start::SessionStartRequest;
 use crate::events::stop::StopOutcome;
 use crate::events::stop::StopRequest;
+use crate::events::user_prompt_submit::UserPromptSubmitOutcome;
+use crate::events::user_prompt_submit::UserPromptSubmitRequest;
+use crate::model_runner::HookModelRunner;
 
 #[derive(Debug, Clone)]
 pub(crate) struct CommandShell {
@@ -24,8 +32,13 @@ pub(crate) struct CommandShell {
 #[derive(Debug, Clone)]
 pub(crate) struct ConfiguredHandler {
     pub event_name: codex_protocol::protocol::HookEventName,
+    pub handler_type: codex_protocol::protocol::HookHandlerType,
     pub matcher: Option<String>,
+    /// Shell command for `command` handlers, prompt text for `prompt` and
+    /// `agent` handlers.
     pub command: String,
+    /// Model override for `prompt` and `agent` handlers.
+    pub model: Option<String>,
     pub timeout_sec: u64,
     pub status_message: Option<String>,
     pub source_path: PathBuf,
@@ -45,6 +58,9 @@ impl ConfiguredHandler {
     fn event_name_label(&self) -> &'static str {
         match self.event_name {
             codex_protocol::protocol::HookEventName::SessionStart => "session-start",
+            codex_protocol::protocol::HookEventName::UserPromptSubmit => "user-prompt-submit",
+            codex_protocol::protocol::HookEventName::PreToolUse => "pre-tool-use",
+            codex_protocol::protocol::HookEventName::PostToolUse => "post-tool-use",
             codex_protocol::protocol::HookEventName::Stop => "stop",
         }
     }
@@ -95,15 +111,70 @@ impl ClaudeHooksEngine {
         &self,
         request: SessionStartRequest,
         turn_id: Option<String>,
+        model_runner: &dyn HookModelRunner,
     ) -> SessionStartOutcome {
-        crate::events::session_start::run(&self.handlers, &self.shell, request, turn_id).await
+        crate::events::session_start::run(
+            &self.handlers,
+            &self.shell,
+            model_runner,
+            request,
+            turn_id,
+        )
+        .await
+    }
+
+    pub(crate) fn preview_user_prompt_submit(
+        &self,
+        request: &UserPromptSubmitRequest,
+    ) -> Vec<HookRunSummary> {
+        crate::events::user_prompt_submit::preview(&self.handlers, request)
+    }
+
+    pub(crate) async fn run_user_prompt_submit(
+        &self,
+        request: UserPromptSubmitRequest,
+        model_runner: &dyn HookModelRunner,
+    ) -> UserPromptSubmitOutcome {
+        crate::events::user_prompt_submit::run(&self.handlers, &self.shell, model_runner, request)
+            .await
+    }
+
+    pub(crate) fn preview_pre_tool_use(&self, request: &PreToolUseRequest) -> Vec<HookRunSummary> {
+        crate::events::pre_tool_use::preview(&self.handlers, request)
+    }
+
+    pub(crate) async fn run_pre_tool_use(
+        &self,
+        request: PreToolUseRequest,
+        model_runner: &dyn HookModelRunner,
+    ) -> PreToolUseOutcome {
+        crate::events::pre_tool_use::run(&self.handlers, &self.shell, model_runner, request).await
+    }
+
+    pub(crate) fn preview_post_tool_use(
+        &self,
+        request: &PostToolUseRequest,
+    ) -> Vec<HookRunSummary> {
+        crate::events::post_tool_use::preview(&self.handlers, request)
+    }
+
+    pub(crate) async fn run_post_tool_use(
+        &self,
+        request: PostToolUseRequest,
+        model_runner: &dyn HookModelRunner,
+    ) -> PostToolUseOutcome {
+        crate::events::post_tool_use::run(&self.handlers, &self.shell, model_runner, request).await
     }
 
     pub(crate) fn preview_stop(&self, request: &StopRequest) -> Vec<HookRunSummary> {
         crate::events::stop::preview(&self.handlers, request)
     }
 
-    pub(crate) async fn run_stop(&self, request: StopRequest) -> StopOutcome {
-        crate::events::stop::run(&self.handlers, &self.shell, request).await
+    pub(crate) async fn run_stop(
+        &self,
+        request: StopRequest,
+        model_runner: &dyn HookModelRunner,
+    ) -> StopOutcome {
+        crate::events::stop::run(&self.handlers, &self.shell, model_runner, request).await
     }
 }
@@ -0,0 +1,197 @@
+use std::path::Path;
+use std::time::Duration;
+use std::time::Instant;
+
+use codex_protocol::protocol::HookEventName;
+use codex_protocol::protocol::HookHandlerType;
+use serde_json::Value;
+use tokio::time::timeout;
+
+use super::ConfiguredHandler;
+use super::command_runner::CommandRunResult;
+use super::schema_loader::generated_hook_schemas;
+use crate::model_runner::HookModelRequest;
+use crate::model_runner::HookModelRunner;
+
+const ARGUMENTS_PLACEHOLDER: &str = "$ARGUMENTS";
+
+/// Runs a `prompt` or `agent` handler and reports the model reply as if it
+/// were command stdout, so each event's parser can interpret it unchanged.
+pub(crate) async fn run_model_handler(
+    model_runner: &dyn HookModelRunner,
+    handler: &ConfiguredHandler,
+    input_json: &str,
+    cwd: &Path,
+) -> CommandRunResult {
+    let started_at = chrono::Utc::now().timestamp();
+    let started = Instant::now();
+
+    let timeout_duration = Duration::from_secs(handler.timeout_sec);
+    let request = HookModelRequest {
+        event_name: handler.event_name,
+        prompt: render_prompt(handler, input_json),
+        model: handler.model.clone(),
+        cwd: cwd.to_path_buf(),
+        timeout: timeout_duration,
+    };
+    let reply = match handler.handler_type {
+        HookHandlerType::Prompt => {
+            timeout(timeout_duration, model_runner.run_prompt(request)).await
+        }
+        HookHandlerType::Agent => timeout(timeout_duration, model_runner.run_agent(request)).await,
+        HookHandlerType::Command => {
+            Ok(Err("command hooks cannot be run as model hooks".to_string()))
+        }
+    };
+
+    let (stdout, error) = match reply {
+        Ok(Ok(reply)) => (extract_json_object(&reply).to_string(), None),
+        Ok(Err(err)) => (String::new(), Some(err)),
+        Err(_) => (
+            String::new(),
+            Some(format!("hook timed out after {}s", handler.timeout_sec)),
+        ),
+    };
+    CommandRunResult {
+        started_at,
+        completed_at: chrono::Utc::now().timestamp(),
+        duration_ms: started.elapsed().as_millis().try_into().unwrap_or(i64::MAX),
+        exit_code: error.is_none().then_some(0),
+        stdout,
+        stderr: String::new(),
+        error,
+    }
+}
+
+fn render_prompt(handler: &ConfiguredHandler, input_json: &str) -> String {
+    let instructions = if handler.command.contains(ARGUMENTS_PLACEHOLDER) {
+        handler.command.replace(ARGUMENTS_PLACEHOLDER, input_json)
+    } else {
+        format!("{}\n\nHook input:\n{input_json}", handler.command)
+    };
+    let output_schema = serde_json::to_string_pretty(output_schema_for_event(handler.event_name))
+        .unwrap_or_default();
+    format!(
+        "{instructions}\n\n\
+         Reply with a single JSON object and nothing else. Reply with {{}} to let the \
+         action proceed unchanged. The object must match this JSON schema:\n{output_schema}"
+    )
+}
+
+fn output_schema_for_event(event_name: HookEventName) -> &'static Value {
+    let schemas = generated_hook_schemas();
+    match event_name {
+        HookEventName::SessionStart => &schemas.session_start_command_output,
+        HookEventName::UserPromptSubmit => &schemas.user_prompt_submit_command_output,
+        HookEventName::PreToolUse => &schemas.pre_tool_use_command_output,
+        HookEventName::PostToolUse => &schemas.post_tool_use_command_output,
+        HookEventName::Stop => &schemas.stop_command_output,
+    }
+}
+
+/// Models often wrap JSON in prose or code fences; keep the outermost object.
+fn extract_json_object(reply: &str) -> &str {
+    let trimmed = reply.trim();
+    match (trimmed.find('{'), trimmed.rfind('}')) {
+        (Some(start), Some(end)) if start < end => &trimmed[start..=end],
+        _ => trimmed,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use codex_protocol::protocol::HookEventName;
+    use codex_protocol::protocol::HookHandlerType;
+    use futures::future::BoxFuture;
+    use pretty_assertions::assert_eq;
+
+    use super::extract_json_object;
+    use super::render_prompt;
+    use super::run_model_handler;
+    use crate::engine::ConfiguredHandler;
+    use crate::model_runner::HookModelRequest;
+    use crate::model_runner::HookModelRunner;
+
+    struct LabeledReply;
+
+    impl HookModelRunner for LabeledReply {
+        fn run_prompt(&self, _request: HookModelRequest) -> BoxFuture<'_, Result<String, String>> {
+            Box::pin(async { Ok(r#"Done. {"reason":"prompt"}"#.to_string()) })
+        }
+
+        fn run_agent(&self, _request: HookModelRequest) -> BoxFuture<'_, Result<String, String>> {
+            Box::pin(async { Ok(r#"{"reason":"agent"}"#.to_string()) })
+        }
+    }
+
+    fn handler(handler_type: HookHandlerType, prompt: &str) -> ConfiguredHandler {
+        ConfiguredHandler {
+            event_name: HookEventName::Stop,
+            handler_type,
+            matcher: None,
+            command: prompt.to_string(),
+            model: None,
+            timeout_sec: 5,
+            status_message: None,
+            source_path: PathBuf::from("/tmp/hooks.json"),
+            display_order: 0,
+        }
+    }
+
+    #[test]
+    fn render_prompt_substitutes_arguments_placeholder() {
+        let rendered = render_prompt(
+            &handler(HookHandlerType::Prompt, "Check this: $ARGUMENTS"),
+            r#"{"hook_event_name":"Stop"}"#,
+        );
+
+        assert!(rendered.starts_with(r#"Check this: {"hook_event_name":"Stop"}"#));
+        assert!(rendered.contains("\"decision\""));
+    }
+
+    #[test]
+    fn render_prompt_appends_input_without_placeholder() {
+        let rendered = render_prompt(
+            &handler(HookHandlerType::Prompt, "Is the task done?"),
+            r#"{"hook_event_name":"Stop"}"#,
+        );
+
+        assert!(rendered.starts_with("Is the task done?\n\nHook input:\n{\"hook_event_name\""));
+    }
+
+    #[test]
+    fn extract_json_object_strips_surrounding_prose() {
+        assert_eq!(
+            extract_json_object("Sure:\n```json\n{\"decision\":\"block\"}\n```"),
+            "{\"decision\":\"block\"}"
+        );
+        assert_eq!(extract_json_object("  no json here "), "no json here");
+    }
+
+    #[tokio::test]
+    async fn run_model_handler_routes_by_handler_type() {
+        let runner = LabeledReply;
+
+        let prompt = run_model_handler(
+            &runner,
+            &handler(HookHandlerType::Prompt, "p"),
+            "{}",
+            PathBuf::from("/tmp").as_path(),
+        )
+        .await;
+        let agent = run_model_handler(
+            &runner,
+            &handler(HookHandlerType::Agent, "p"),
+            "{}",
+            PathBuf::from("/tmp").as_path(),
+        )
+        .await;
+
+        assert_eq!(prompt.exit_code, Some(0));
+        assert_eq!(prompt.stdout, r#"{"reason":"prompt"}"#);
+        assert_eq!(agent.exit_code, Some(0));
+        assert_eq!(agent.stdout, r#"{"reason":"agent"}"#);
+    }
+}
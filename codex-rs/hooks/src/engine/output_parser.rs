@@ -12,6 +12,32 @@ pub(crate) struct SessionStartOutput {
     pub additional_context: Option<String>,
 }
 
+#[derive(Debug, Clone)]
+pub(crate) struct UserPromptSubmitOutput {
+    pub universal: UniversalOutput,
+    pub should_block: bool,
+    pub reason: Option<String>,
+    pub additional_context: Option<String>,
+    pub updated_prompt: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct PreToolUseOutput {
+    pub universal: UniversalOutput,
+    pub should_block: bool,
+    pub reason: Option<String>,
+    pub updated_input: Option<serde_json::Value>,
+    pub additional_context: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct PostToolUseOutput {
+    pub universal: UniversalOutput,
+    pub should_block: bool,
+    pub reason: Option<String>,
+    pub additional_context: Option<String>,
+}
+
 #[derive(Debug, Clone)]
 pub(crate) struct StopOutput {
     pub universal: UniversalOutput,
@@ -20,9 +46,16 @@ pub(crate) struct StopOutput {
 }
 
 use crate::schema::HookUniversalOutputWire;
+use crate::schema::PostToolUseCommandOutputWire;
+use crate::schema::PostToolUseDecisionWire;
+use crate::schema::PreToolUseCommandOutputWire;
+use crate::schema::PreToolUseDecisionWire;
+use crate::schema::PreToolUsePermissionDecisionWire;
 use crate::schema::SessionStartCommandOutputWire;
 use crate::schema::StopCommandOutputWire;
 use crate::schema::StopDecisionWire;
+use crate::schema::UserPromptSubmitCommandOutputWire;
+use crate::schema::UserPromptSubmitDecisionWire;
 
 pub(crate) fn parse_session_start(stdout: &str) -> Option<SessionStartOutput> {
     let wire: SessionStartCommandOutputWire = parse_json(stdout)?;
@@ -35,6 +68,65 @@ pub(crate) fn parse_session_start(stdout: &str) -> Option<SessionStartOutput> {
     })
 }
 
+pub(crate) fn parse_user_prompt_submit(stdout: &str) -> Option<UserPromptSubmitOutput> {
+    let wire: UserPromptSubmitCommandOutputWire = parse_json(stdout)?;
+    let (additional_context, updated_prompt) = wire
+        .hook_specific_output
+        .map(|output| (output.additional_context, output.updated_prompt))
+        .unwrap_or_default();
+    Some(UserPromptSubmitOutput {
+        universal: UniversalOutput::from(wire.universal),
+        should_block: matches!(wire.decision, Some(UserPromptSubmitDecisionWire::Block)),
+        reason: wire.reason,
+        additional_context,
+        updated_prompt,
+    })
+}
+
+/// Accepts both the top-level `decision: "block"` form used by `Stop` and the
+/// `hookSpecificOutput.permissionDecision: "deny"` form. The
+/// `permissionDecisionReason` takes precedence over the top-level `reason`.
+pub(crate) fn parse_pre_tool_use(stdout: &str) -> Option<PreToolUseOutput> {
+    let wire: PreToolUseCommandOutputWire = parse_json(stdout)?;
+    let mut should_block = matches!(wire.decision, Some(PreToolUseDecisionWire::Block));
+    let mut reason = wire.reason;
+    let mut updated_input = None;
+    let mut additional_context = None;
+    if let Some(hook_specific_output) = wire.hook_specific_output {
+        if matches!(
+            hook_specific_output.permission_decision,
+            Some(PreToolUsePermissionDecisionWire::Deny)
+        ) {
+            should_block = true;
+        }
+        if hook_specific_output.permission_decision_reason.is_some() {
+            reason = hook_specific_output.permission_decision_reason;
+        }
+        updated_input = hook_specific_output.updated_input;
+        additional_context = hook_specific_output.additional_context;
+    }
+    Some(PreToolUseOutput {
+        universal: UniversalOutput::from(wire.universal),
+        should_block,
+        reason,
+        updated_input,
+        additional_context,
+    })
+}
+
+pub(crate) fn parse_post_tool_use(stdout: &str) -> Option<PostToolUseOutput> {
+    let wire: PostToolUseCommandOutputWire = parse_json(stdout)?;
+    let additional_context = wire
+        .hook_specific_output
+        .and_then(|output| output.additional_context);
+    Some(PostToolUseOutput {
+        universal: UniversalOutput::from(wire.universal),
+        should_block: matches!(wire.decision, Some(PostToolUseDecisionWire::Block)),
+        reason: wire.reason,
+        additional_context,
+    })
+}
+
 pub(crate) fn parse_stop(stdout: &str) -> Option<StopOutput> {
     let wire: StopCommandOutputWire = parse_json(stdout)?;
     Some(StopOutput {
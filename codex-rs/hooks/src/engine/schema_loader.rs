@@ -6,6 +6,12 @@ use serde_json::Value;
 pub(crate) struct GeneratedHookSchemas {
     pub session_start_command_input: Value,
     pub session_start_command_output: Value,
+    pub user_prompt_submit_command_input: Value,
+    pub user_prompt_submit_command_output: Value,
+    pub pre_tool_use_command_input: Value,
+    pub pre_tool_use_command_output: Value,
+    pub post_tool_use_command_input: Value,
+    pub post_tool_use_command_output: Value,
     pub stop_command_input: Value,
     pub stop_command_output: Value,
 }
@@ -21,6 +27,30 @@ pub(crate) fn generated_hook_schemas() -> &'static GeneratedHookSchemas {
             "session-start.command.output",
             include_str!("../../schema/generated/session-start.command.output.schema.json"),
         ),
+        user_prompt_submit_command_input: parse_json_schema(
+            "user-prompt-submit.command.input",
+            include_str!("../../schema/generated/user-prompt-submit.command.input.schema.json"),
+        ),
+        user_prompt_submit_command_output: parse_json_schema(
+            "user-prompt-submit.command.output",
+            include_str!("../../schema/generated/user-prompt-submit.command.output.schema.json"),
+        ),
+        pre_tool_use_command_input: parse_json_schema(
+            "pre-tool-use.command.input",
+            include_str!("../../schema/generated/pre-tool-use.command.input.schema.json"),
+        ),
+        pre_tool_use_command_output: parse_json_schema(
+            "pre-tool-use.command.output",
+            include_str!("../../schema/generated/pre-tool-use.command.output.schema.json"),
+        ),
+        post_tool_use_command_input: parse_json_schema(
+            "post-tool-use.command.input",
+            include_str!("../../schema/generated/post-tool-use.command.input.schema.json"),
+        ),
+        post_tool_use_command_output: parse_json_schema(
+            "post-tool-use.command.output",
+            include_str!("../../schema/generated/post-tool-use.command.output.schema.json"),
+        ),
         stop_command_input: parse_json_schema(
             "stop.command.input",
             include_str!("../../schema/generated/stop.command.input.schema.json"),
@@ -48,6 +78,12 @@ mod tests {
 
         assert_eq!(schemas.session_start_command_input["type"], "object");
         assert_eq!(schemas.session_start_command_output["type"], "object");
+        assert_eq!(schemas.user_prompt_submit_command_input["type"], "object");
+        assert_eq!(schemas.user_prompt_submit_command_output["type"], "object");
+        assert_eq!(schemas.pre_tool_use_command_input["type"], "object");
+        assert_eq!(schemas.pre_tool_use_command_output["type"], "object");
+        assert_eq!(schemas.post_tool_use_command_input["type"], "object");
+        assert_eq!(schemas.post_tool_use_command_output["type"], "object");
         assert_eq!(schemas.stop_command_input["type"], "object");
         assert_eq!(schemas.stop_command_output["type"], "object");
     }
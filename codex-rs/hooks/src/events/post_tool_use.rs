@@ -0,0 +1,450 @@
+use std::path::PathBuf;
+
+use codex_protocol::ThreadId;
+use codex_protocol::protocol::HookCompletedEvent;
+use codex_protocol::protocol::HookEventName;
+use codex_protocol::protocol::HookOutputEntry;
+use codex_protocol::protocol::HookOutputEntryKind;
+use codex_protocol::protocol::HookRunStatus;
+use codex_protocol::protocol::HookRunSummary;
+use serde_json::Value;
+
+use crate::engine::CommandShell;
+use crate::engine::ConfiguredHandler;
+use crate::engine::command_runner::CommandRunResult;
+use crate::engine::dispatcher;
+use crate::engine::output_parser;
+use crate::model_runner::HookModelRunner;
+use crate::schema::PostToolUseCommandInput;
+
+#[derive(Debug, Clone)]
+pub struct PostToolUseRequest {
+    pub session_id: ThreadId,
+    pub turn_id: String,
+    pub cwd: PathBuf,
+    pub transcript_path: Option<PathBuf>,
+    pub model: String,
+    pub permission_mode: String,
+    pub tool_name: String,
+    pub tool_use_id: String,
+    pub tool_input: Value,
+    pub tool_response: Value,
+}
+
+#[derive(Debug)]
+pub struct PostToolUseOutcome {
+    pub hook_events: Vec<HookCompletedEvent>,
+    pub should_stop: bool,
+    pub stop_reason: Option<String>,
+    pub should_block: bool,
+    pub block_reason: Option<String>,
+    /// Text to surface to the model after the tool output: block feedback
+    /// followed by any additional context returned by hooks.
+    pub feedback_message_for_model: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct PostToolUseHandlerData {
+    should_stop: bool,
+    stop_reason: Option<String>,
+    should_block: bool,
+    block_reason: Option<String>,
+    additional_context_for_model: Option<String>,
+}
+
+pub(crate) fn preview(
+    handlers: &[ConfiguredHandler],
+    request: &PostToolUseRequest,
+) -> Vec<HookRunSummary> {
+    dispatcher::select_handlers(
+        handlers,
+        HookEventName::PostToolUse,
+        Some(request.tool_name.as_str()),
+    )
+    .into_iter()
+    .map(|handler| dispatcher::running_summary(&handler))
+    .collect()
+}
+
+pub(crate) async fn run(
+    handlers: &[ConfiguredHandler],
+    shell: &CommandShell,
+    model_runner: &dyn HookModelRunner,
+    request: PostToolUseRequest,
+) -> PostToolUseOutcome {
+    let matched = dispatcher::select_handlers(
+        handlers,
+        HookEventName::PostToolUse,
+        Some(request.tool_name.as_str()),
+    );
+    if matched.is_empty() {
+        return PostToolUseOutcome {
+            hook_events: Vec::new(),
+            should_stop: false,
+            stop_reason: None,
+            should_block: false,
+            block_reason: None,
+            feedback_message_for_model: None,
+        };
+    }
+
+    let input_json = match serde_json::to_string(&PostToolUseCommandInput::new(
+        request.session_id.to_string(),
+        request.transcript_path.clone(),
+        request.cwd.display().to_string(),
+        request.model.clone(),
+        request.permission_mode.clone(),
+        request.tool_name.clone(),
+        request.tool_input.clone(),
+        request.tool_response.clone(),
+        request.tool_use_id.clone(),
+    )) {
+        Ok(input_json) => input_json,
+        Err(error) => {
+            return serialization_failure_outcome(
+                matched,
+                Some(request.turn_id),
+                format!("failed to serialize post tool use hook input: {error}"),
+            );
+        }
+    };
+
+    let results = dispatcher::execute_handlers(
+        shell,
+        model_runner,
+        matched,
+        input_json,
+        request.cwd.as_path(),
+        Some(request.turn_id),
+        parse_completed,
+    )
+    .await;
+
+    let should_stop = results.iter().any(|result| result.data.should_stop);
+    let stop_reason = results
+        .iter()
+        .find_map(|result| result.data.stop_reason.clone());
+
+    let should_block = !should_stop && results.iter().any(|result| result.data.should_block);
+    let block_reasons = if should_block {
+        results
+            .iter()
+            .filter_map(|result| result.data.block_reason.clone())
+            .collect::<Vec<_>>()
+    } else {
+        Vec::new()
+    };
+    let block_reason = block_reasons.first().cloned();
+    let feedback_chunks = block_reasons
+        .into_iter()
+        .chain(
+            results
+                .iter()
+                .filter_map(|result| result.data.additional_context_for_model.clone()),
+        )
+        .collect::<Vec<_>>();
+
+    PostToolUseOutcome {
+        hook_events: results.into_iter().map(|result| result.completed).collect(),
+        should_stop,
+        stop_reason,
+        should_block,
+        block_reason,
+        feedback_message_for_model: join_text_chunks(feedback_chunks),
+    }
+}
+
+fn parse_completed(
+    handler: &ConfiguredHandler,
+    run_result: CommandRunResult,
+    turn_id: Option<String>,
+) -> dispatcher::ParsedHandler<PostToolUseHandlerData> {
+    let mut entries = Vec::new();
+    let mut status = HookRunStatus::Completed;
+    let mut should_stop = false;
+    let mut stop_reason = None;
+    let mut should_block = false;
+    let mut block_reason = None;
+    let mut additional_context_for_model = None;
+
+    match run_result.error.as_deref() {
+        Some(error) => {
+            status = HookRunStatus::Failed;
+            entries.push(HookOutputEntry {
+                kind: HookOutputEntryKind::Error,
+                text: error.to_string(),
+            });
+        }
+        None => match run_result.exit_code {
+            Some(0) => {
+                let trimmed_stdout = run_result.stdout.trim();
+                if trimmed_stdout.is_empty() {
+                } else if let Some(parsed) = output_parser::parse_post_tool_use(&run_result.stdout)
+                {
+                    if let Some(system_message) = parsed.universal.system_message {
+                        entries.push(HookOutputEntry {
+                            kind: HookOutputEntryKind::Warning,
+                            text: system_message,
+                        });
+                    }
+                    if let Some(additional_context) = parsed.additional_context {
+                        entries.push(HookOutputEntry {
+                            kind: HookOutputEntryKind::Context,
+                            text: additional_context.clone(),
+                        });
+                        if parsed.universal.continue_processing {
+                            additional_context_for_model = Some(additional_context);
+                        }
+                    }
+                    let _ = parsed.universal.suppress_output;
+                    if !parsed.universal.continue_processing {
+                        status = HookRunStatus::Stopped;
+                        should_stop = true;
+                        stop_reason = parsed.universal.stop_reason.clone();
+                        if let Some(stop_reason_text) = parsed.universal.stop_reason {
+                            entries.push(HookOutputEntry {
+                                kind: HookOutputEntryKind::Stop,
+                                text: stop_reason_text,
+                            });
+                        }
+                    } else if parsed.should_block {
+                        if let Some(reason) = parsed.reason.as_deref().and_then(trimmed_non_empty) {
+                            status = HookRunStatus::Blocked;
+                            should_block = true;
+                            block_reason = Some(reason.clone());
+                            entries.push(HookOutputEntry {
+                                kind: HookOutputEntryKind::Feedback,
+                                text: reason,
+                            });
+                        } else {
+                            status = HookRunStatus::Failed;
+                            entries.push(HookOutputEntry {
+                                kind: HookOutputEntryKind::Error,
+                                text: "hook returned decision \"block\" without a non-empty reason"
+                                    .to_string(),
+                            });
+                        }
+                    }
+                } else {
+                    status = HookRunStatus::Failed;
+                    entries.push(HookOutputEntry {
+                        kind: HookOutputEntryKind::Error,
+                        text: "hook returned invalid post tool use hook JSON output".to_string(),
+                    });
+                }
+            }
+            Some(2) => {
+                if let Some(reason) = trimmed_non_empty(&run_result.stderr) {
+                    status = HookRunStatus::Blocked;
+                    should_block = true;
+                    block_reason = Some(reason.clone());
+                    entries.push(HookOutputEntry {
+                        kind: HookOutputEntryKind::Feedback,
+                        text: reason,
+                    });
+                } else {
+                    status = HookRunStatus::Failed;
+                    entries.push(HookOutputEntry {
+                        kind: HookOutputEntryKind::Error,
+                        text: "hook exited with code 2 without stderr feedback".to_string(),
+                    });
+                }
+            }
+            Some(exit_code) => {
+                status = HookRunStatus::Failed;
+                entries.push(HookOutputEntry {
+                    kind: HookOutputEntryKind::Error,
+                    text: format!("hook exited with code {exit_code}"),
+                });
+            }
+            None => {
+                status = HookRunStatus::Failed;
+                entries.push(HookOutputEntry {
+                    kind: HookOutputEntryKind::Error,
+                    text: "hook exited without a status code".to_string(),
+                });
+            }
+        },
+    }
+
+    let completed = HookCompletedEvent {
+        turn_id,
+        run: dispatcher::completed_summary(handler, &run_result, status, entries),
+    };
+
+    dispatcher::ParsedHandler {
+        completed,
+        data: PostToolUseHandlerData {
+            should_stop,
+            stop_reason,
+            should_block,
+            block_reason,
+            additional_context_for_model,
+        },
+    }
+}
+
+fn trimmed_non_empty(text: &str) -> Option<String> {
+    let trimmed = text.trim();
+    if !trimmed.is_empty() {
+        return Some(trimmed.to_string());
+    }
+    None
+}
+
+fn join_text_chunks(chunks: Vec<String>) -> Option<String> {
+    if chunks.is_empty() {
+        None
+    } else {
+        Some(chunks.join("\n\n"))
+    }
+}
+
+fn serialization_failure_outcome(
+    handlers: Vec<ConfiguredHandler>,
+    turn_id: Option<String>,
+    error_message: String,
+) -> PostToolUseOutcome {
+    let hook_events = handlers
+        .into_iter()
+        .map(|handler| {
+            let mut run = dispatcher::running_summary(&handler);
+            run.status = HookRunStatus::Failed;
+            run.completed_at = Some(run.started_at);
+            run.duration_ms = Some(0);
+            run.entries = vec![HookOutputEntry {
+                kind: HookOutputEntryKind::Error,
+                text: error_message.clone(),
+            }];
+            HookCompletedEvent {
+                turn_id: turn_id.clone(),
+                run,
+            }
+        })
+        .collect();
+
+    PostToolUseOutcome {
+        hook_events,
+        should_stop: false,
+        stop_reason: None,
+        should_block: false,
+        block_reason: None,
+        feedback_message_for_model: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use codex_protocol::protocol::HookEventName;
+    use codex_protocol::protocol::HookHandlerType;
+    use codex_protocol::protocol::HookRunStatus;
+    use pretty_assertions::assert_eq;
+
+    use super::PostToolUseHandlerData;
+    use super::parse_completed;
+    use crate::engine::ConfiguredHandler;
+    use crate::engine::command_runner::CommandRunResult;
+
+    #[test]
+    fn block_decision_becomes_feedback_for_model() {
+        let parsed = parse_completed(
+            &handler(),
+            run_result(
+                Some(0),
+                r#"{"decision":"block","reason":"rustfmt reformatted src/lib.rs"}"#,
+                "",
+            ),
+            Some("turn-1".to_string()),
+        );
+
+        assert_eq!(
+            parsed.data,
+            PostToolUseHandlerData {
+                should_stop: false,
+                stop_reason: None,
+                should_block: true,
+                block_reason: Some("rustfmt reformatted src/lib.rs".to_string()),
+                additional_context_for_model: None,
+            }
+        );
+        assert_eq!(parsed.completed.run.status, HookRunStatus::Blocked);
+    }
+
+    #[test]
+    fn additional_context_is_forwarded() {
+        let parsed = parse_completed(
+            &handler(),
+            run_result(
+                Some(0),
+                r#"{"hookSpecificOutput":{"hookEventName":"PostToolUse","additionalContext":"ran rustfmt"}}"#,
+                "",
+            ),
+            Some("turn-1".to_string()),
+        );
+
+        assert_eq!(
+            parsed.data,
+            PostToolUseHandlerData {
+                should_stop: false,
+                stop_reason: None,
+                should_block: false,
+                block_reason: None,
+                additional_context_for_model: Some("ran rustfmt".to_string()),
+            }
+        );
+        assert_eq!(parsed.completed.run.status, HookRunStatus::Completed);
+    }
+
+    #[test]
+    fn continue_false_stops_processing() {
+        let parsed = parse_completed(
+            &handler(),
+            run_result(
+                Some(0),
+                r#"{"continue":false,"stopReason":"tests are red","decision":"block","reason":"fix tests"}"#,
+                "",
+            ),
+            Some("turn-1".to_string()),
+        );
+
+        assert_eq!(
+            parsed.data,
+            PostToolUseHandlerData {
+                should_stop: true,
+                stop_reason: Some("tests are red".to_string()),
+                should_block: false,
+                block_reason: None,
+                additional_context_for_model: None,
+            }
+        );
+        assert_eq!(parsed.completed.run.status, HookRunStatus::Stopped);
+    }
+
+    fn handler() -> ConfiguredHandler {
+        ConfiguredHandler {
+            event_name: HookEventName::PostToolUse,
+            handler_type: HookHandlerType::Command,
+            matcher: Some("^apply_patch$".to_string()),
+            command: "echo hook".to_string(),
+            model: None,
+            timeout_sec: 600,
+            status_message: None,
+            source_path: PathBuf::from("/tmp/hooks.json"),
+            display_order: 0,
+        }
+    }
+
+    fn run_result(exit_code: Option<i32>, stdout: &str, stderr: &str) -> CommandRunResult {
+        CommandRunResult {
+            started_at: 1,
+            completed_at: 2,
+            duration_ms: 1,
+            exit_code,
+            stdout: stdout.to_string(),
+            stderr: stderr.to_string(),
+            error: None,
+        }
+    }
+}
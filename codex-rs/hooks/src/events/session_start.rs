@@ -13,6 +13,7 @@ use crate::engine::ConfiguredHandler;
 use crate::engine::command_runner::CommandRunResult;
 use crate::engine::dispatcher;
 use crate::engine::output_parser;
+use crate::model_runner::HookModelRunner;
 use crate::schema::SessionStartCommandInput;
 
 #[derive(Debug, Clone, Copy)]
@@ -72,6 +73,7 @@ pub(crate) fn preview(
 pub(crate) async fn run(
     handlers: &[ConfiguredHandler],
     shell: &CommandShell,
+    model_runner: &dyn HookModelRunner,
     request: SessionStartRequest,
     turn_id: Option<String>,
 ) -> SessionStartOutcome {
@@ -109,6 +111,7 @@ pub(crate) async fn run(
 
     let results = dispatcher::execute_handlers(
         shell,
+        model_runner,
         matched,
         input_json,
         request.cwd.as_path(),
@@ -278,6 +281,7 @@ mod tests {
     use std::path::PathBuf;
 
     use codex_protocol::protocol::HookEventName;
+    use codex_protocol::protocol::HookHandlerType;
     use codex_protocol::protocol::HookOutputEntry;
     use codex_protocol::protocol::HookOutputEntryKind;
     use codex_protocol::protocol::HookRunStatus;
@@ -370,8 +374,10 @@ mod tests {
     fn handler() -> ConfiguredHandler {
         ConfiguredHandler {
             event_name: HookEventName::SessionStart,
+            handler_type: HookHandlerType::Command,
             matcher: None,
             command: "echo hook".to_string(),
+            model: None,
             timeout_sec: 600,
             status_message: None,
             source_path: PathBuf::from("/tmp/hooks.json"),
@@ -13,6 +13,7 @@ use crate::engine::ConfiguredHandler;
 use crate::engine::command_runner::CommandRunResult;
 use crate::engine::dispatcher;
 use crate::engine::output_parser;
+use crate::model_runner::HookModelRunner;
 use crate::schema::StopCommandInput;
 
 #[derive(Debug, Clone)]
@@ -59,6 +60,7 @@ pub(crate) fn preview(
 pub(crate) async fn run(
     handlers: &[ConfiguredHandler],
     shell: &CommandShell,
+    model_runner: &dyn HookModelRunner,
     request: StopRequest,
 ) -> StopOutcome {
     let matched = dispatcher::select_handlers(handlers, HookEventName::Stop, None);
@@ -94,6 +96,7 @@ pub(crate) async fn run(
 
     let results = dispatcher::execute_handlers(
         shell,
+        model_runner,
         matched,
         input_json,
         request.cwd.as_path(),
@@ -300,16 +303,24 @@ fn serialization_failure_outcome(
 mod tests {
     use std::path::PathBuf;
 
+    use codex_protocol::ThreadId;
     use codex_protocol::protocol::HookEventName;
+    use codex_protocol::protocol::HookHandlerType;
     use codex_protocol::protocol::HookOutputEntry;
     use codex_protocol::protocol::HookOutputEntryKind;
     use codex_protocol::protocol::HookRunStatus;
+    use futures::future::BoxFuture;
     use pretty_assertions::assert_eq;
 
     use super::StopHandlerData;
+    use super::StopRequest;
     use super::parse_completed;
+    use super::run;
+    use crate::engine::CommandShell;
     use crate::engine::ConfiguredHandler;
     use crate::engine::command_runner::CommandRunResult;
+    use crate::model_runner::HookModelRequest;
+    use crate::model_runner::HookModelRunner;
 
     #[test]
     fn continue_false_overrides_block_decision() {
@@ -469,11 +480,67 @@ mod tests {
         );
     }
 
+    struct BlockingPromptRunner;
+
+    impl HookModelRunner for BlockingPromptRunner {
+        fn run_prompt(&self, request: HookModelRequest) -> BoxFuture<'_, Result<String, String>> {
+            Box::pin(async move {
+                assert!(request.prompt.starts_with("Are the tests passing?"));
+                Ok(r#"{"decision":"block","reason":"run the tests first"}"#.to_string())
+            })
+        }
+
+        fn run_agent(&self, _request: HookModelRequest) -> BoxFuture<'_, Result<String, String>> {
+            Box::pin(async { Err("unexpected agent hook".to_string()) })
+        }
+    }
+
+    #[tokio::test]
+    async fn prompt_handler_block_decision_blocks_stop() {
+        let prompt_handler = ConfiguredHandler {
+            handler_type: HookHandlerType::Prompt,
+            command: "Are the tests passing?".to_string(),
+            ..handler()
+        };
+
+        let outcome = run(
+            &[prompt_handler],
+            &CommandShell {
+                program: String::new(),
+                args: Vec::new(),
+            },
+            &BlockingPromptRunner,
+            StopRequest {
+                session_id: ThreadId::new(),
+                turn_id: "turn-1".to_string(),
+                cwd: PathBuf::from("/tmp"),
+                transcript_path: None,
+                model: "gpt-test".to_string(),
+                permission_mode: "default".to_string(),
+                stop_hook_active: false,
+                last_assistant_message: None,
+            },
+        )
+        .await;
+
+        assert!(outcome.should_block);
+        assert_eq!(
+            outcome.block_message_for_model,
+            Some("run the tests first".to_string())
+        );
+        assert_eq!(
+            outcome.hook_events[0].run.handler_type,
+            HookHandlerType::Prompt
+        );
+    }
+
     fn handler() -> ConfiguredHandler {
         ConfiguredHandler {
             event_name: HookEventName::Stop,
+            handler_type: HookHandlerType::Command,
             matcher: None,
             command: "echo hook".to_string(),
+            model: None,
             timeout_sec: 600,
             status_message: None,
             source_path: PathBuf::from("/tmp/hooks.json"),
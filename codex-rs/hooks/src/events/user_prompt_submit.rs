@@ -0,0 +1,528 @@
+use std::path::PathBuf;
+
+use codex_protocol::ThreadId;
+use codex_protocol::protocol::HookCompletedEvent;
+use codex_protocol::protocol::HookEventName;
+use codex_protocol::protocol::HookOutputEntry;
+use codex_protocol::protocol::HookOutputEntryKind;
+use codex_protocol::protocol::HookRunStatus;
+use codex_protocol::protocol::HookRunSummary;
+use codex_protocol::user_input::UserInput;
+
+use crate::engine::CommandShell;
+use crate::engine::ConfiguredHandler;
+use crate::engine::command_runner::CommandRunResult;
+use crate::engine::dispatcher;
+use crate::engine::output_parser;
+use crate::model_runner::HookModelRunner;
+use crate::schema::UserPromptSubmitCommandInput;
+
+#[derive(Debug, Clone)]
+pub struct UserPromptSubmitRequest {
+    pub session_id: ThreadId,
+    pub turn_id: String,
+    pub cwd: PathBuf,
+    pub transcript_path: Option<PathBuf>,
+    pub model: String,
+    pub permission_mode: String,
+    pub input: Vec<UserInput>,
+}
+
+#[derive(Debug)]
+pub struct UserPromptSubmitOutcome {
+    pub hook_events: Vec<HookCompletedEvent>,
+    pub should_stop: bool,
+    pub stop_reason: Option<String>,
+    pub should_block: bool,
+    pub block_reason: Option<String>,
+    /// Input with the hook's `updatedPrompt` applied. Never set when the
+    /// prompt is blocked or stopped.
+    pub updated_input: Option<Vec<UserInput>>,
+    pub additional_context: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct UserPromptSubmitHandlerData {
+    should_stop: bool,
+    stop_reason: Option<String>,
+    should_block: bool,
+    block_reason: Option<String>,
+    updated_prompt: Option<String>,
+    additional_context_for_model: Option<String>,
+}
+
+pub(crate) fn preview(
+    handlers: &[ConfiguredHandler],
+    _request: &UserPromptSubmitRequest,
+) -> Vec<HookRunSummary> {
+    dispatcher::select_handlers(handlers, HookEventName::UserPromptSubmit, None)
+        .into_iter()
+        .map(|handler| dispatcher::running_summary(&handler))
+        .collect()
+}
+
+pub(crate) async fn run(
+    handlers: &[ConfiguredHandler],
+    shell: &CommandShell,
+    model_runner: &dyn HookModelRunner,
+    request: UserPromptSubmitRequest,
+) -> UserPromptSubmitOutcome {
+    let matched = dispatcher::select_handlers(handlers, HookEventName::UserPromptSubmit, None);
+    if matched.is_empty() {
+        return UserPromptSubmitOutcome {
+            hook_events: Vec::new(),
+            should_stop: false,
+            stop_reason: None,
+            should_block: false,
+            block_reason: None,
+            updated_input: None,
+            additional_context: None,
+        };
+    }
+
+    let input_items = match request
+        .input
+        .iter()
+        .map(serde_json::to_value)
+        .collect::<Result<Vec<_>, _>>()
+    {
+        Ok(input_items) => input_items,
+        Err(error) => {
+            return serialization_failure_outcome(
+                matched,
+                Some(request.turn_id),
+                format!("failed to serialize user prompt submit hook input: {error}"),
+            );
+        }
+    };
+    let input_json = match serde_json::to_string(&UserPromptSubmitCommandInput::new(
+        request.session_id.to_string(),
+        request.transcript_path.clone(),
+        request.cwd.display().to_string(),
+        request.model.clone(),
+        request.permission_mode.clone(),
+        prompt_text(&request.input),
+        input_items,
+    )) {
+        Ok(input_json) => input_json,
+        Err(error) => {
+            return serialization_failure_outcome(
+                matched,
+                Some(request.turn_id),
+                format!("failed to serialize user prompt submit hook input: {error}"),
+            );
+        }
+    };
+
+    let results = dispatcher::execute_handlers(
+        shell,
+        model_runner,
+        matched,
+        input_json,
+        request.cwd.as_path(),
+        Some(request.turn_id),
+        parse_completed,
+    )
+    .await;
+
+    let should_stop = results.iter().any(|result| result.data.should_stop);
+    let stop_reason = results
+        .iter()
+        .find_map(|result| result.data.stop_reason.clone());
+
+    let should_block = !should_stop && results.iter().any(|result| result.data.should_block);
+    let block_reason = if should_block {
+        results
+            .iter()
+            .find_map(|result| result.data.block_reason.clone())
+    } else {
+        None
+    };
+    let updated_input = if should_stop || should_block {
+        None
+    } else {
+        results
+            .iter()
+            .find_map(|result| result.data.updated_prompt.clone())
+            .map(|updated_prompt| apply_updated_prompt(request.input, updated_prompt))
+    };
+    let additional_contexts = results
+        .iter()
+        .filter_map(|result| result.data.additional_context_for_model.clone())
+        .collect::<Vec<_>>();
+
+    UserPromptSubmitOutcome {
+        hook_events: results.into_iter().map(|result| result.completed).collect(),
+        should_stop,
+        stop_reason,
+        should_block,
+        block_reason,
+        updated_input,
+        additional_context: join_text_chunks(additional_contexts),
+    }
+}
+
+/// The text the user typed, with non-text items (images, skills, mentions)
+/// left out.
+fn prompt_text(input: &[UserInput]) -> String {
+    input
+        .iter()
+        .filter_map(|item| match item {
+            UserInput::Text { text, .. } => Some(text.as_str()),
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Replaces the text items with a single item holding `updated_prompt`, at
+/// the position of the first text item. Non-text items are kept as-is. Text
+/// elements are dropped because their byte ranges point into the old text.
+fn apply_updated_prompt(input: Vec<UserInput>, updated_prompt: String) -> Vec<UserInput> {
+    let mut updated_prompt = Some(updated_prompt);
+    let mut updated_input = Vec::with_capacity(input.len());
+    for item in input {
+        match item {
+            UserInput::Text { .. } => {
+                if let Some(text) = updated_prompt.take() {
+                    updated_input.push(UserInput::Text {
+                        text,
+                        text_elements: Vec::new(),
+                    });
+                }
+            }
+            item => updated_input.push(item),
+        }
+    }
+    if let Some(text) = updated_prompt {
+        updated_input.insert(
+            0,
+            UserInput::Text {
+                text,
+                text_elements: Vec::new(),
+            },
+        );
+    }
+    updated_input
+}
+
+fn parse_completed(
+    handler: &ConfiguredHandler,
+    run_result: CommandRunResult,
+    turn_id: Option<String>,
+) -> dispatcher::ParsedHandler<UserPromptSubmitHandlerData> {
+    let mut entries = Vec::new();
+    let mut status = HookRunStatus::Completed;
+    let mut should_stop = false;
+    let mut stop_reason = None;
+    let mut should_block = false;
+    let mut block_reason = None;
+    let mut updated_prompt = None;
+    let mut additional_context_for_model = None;
+
+    match run_result.error.as_deref() {
+        Some(error) => {
+            status = HookRunStatus::Failed;
+            entries.push(HookOutputEntry {
+                kind: HookOutputEntryKind::Error,
+                text: error.to_string(),
+            });
+        }
+        None => match run_result.exit_code {
+            Some(0) => {
+                let trimmed_stdout = run_result.stdout.trim();
+                if trimmed_stdout.is_empty() {
+                } else if let Some(parsed) =
+                    output_parser::parse_user_prompt_submit(&run_result.stdout)
+                {
+                    if let Some(system_message) = parsed.universal.system_message {
+                        entries.push(HookOutputEntry {
+                            kind: HookOutputEntryKind::Warning,
+                            text: system_message,
+                        });
+                    }
+                    if let Some(additional_context) = parsed.additional_context {
+                        entries.push(HookOutputEntry {
+                            kind: HookOutputEntryKind::Context,
+                            text: additional_context.clone(),
+                        });
+                        if parsed.universal.continue_processing {
+                            additional_context_for_model = Some(additional_context);
+                        }
+                    }
+                    let _ = parsed.universal.suppress_output;
+                    if !parsed.universal.continue_processing {
+                        status = HookRunStatus::Stopped;
+                        should_stop = true;
+                        stop_reason = parsed.universal.stop_reason.clone();
+                        if let Some(stop_reason_text) = parsed.universal.stop_reason {
+                            entries.push(HookOutputEntry {
+                                kind: HookOutputEntryKind::Stop,
+                                text: stop_reason_text,
+                            });
+                        }
+                    } else if parsed.should_block {
+                        if let Some(reason) = parsed.reason.as_deref().and_then(trimmed_non_empty) {
+                            status = HookRunStatus::Blocked;
+                            should_block = true;
+                            block_reason = Some(reason.clone());
+                            entries.push(HookOutputEntry {
+                                kind: HookOutputEntryKind::Feedback,
+                                text: reason,
+                            });
+                        } else {
+                            status = HookRunStatus::Failed;
+                            entries.push(HookOutputEntry {
+                                kind: HookOutputEntryKind::Error,
+                                text: "hook blocked the prompt without a non-empty reason"
+                                    .to_string(),
+                            });
+                        }
+                    } else {
+                        updated_prompt = parsed.updated_prompt;
+                    }
+                // Preserve plain-text context support without treating malformed JSON as context.
+                } else if trimmed_stdout.starts_with('{') || trimmed_stdout.starts_with('[') {
+                    status = HookRunStatus::Failed;
+                    entries.push(HookOutputEntry {
+                        kind: HookOutputEntryKind::Error,
+                        text: "hook returned invalid user prompt submit JSON output".to_string(),
+                    });
+                } else {
+                    let additional_context = trimmed_stdout.to_string();
+                    entries.push(HookOutputEntry {
+                        kind: HookOutputEntryKind::Context,
+                        text: additional_context.clone(),
+                    });
+                    additional_context_for_model = Some(additional_context);
+                }
+            }
+            Some(2) => {
+                if let Some(reason) = trimmed_non_empty(&run_result.stderr) {
+                    status = HookRunStatus::Blocked;
+                    should_block = true;
+                    block_reason = Some(reason.clone());
+                    entries.push(HookOutputEntry {
+                        kind: HookOutputEntryKind::Feedback,
+                        text: reason,
+                    });
+                } else {
+                    status = HookRunStatus::Failed;
+                    entries.push(HookOutputEntry {
+                        kind: HookOutputEntryKind::Error,
+                        text: "hook exited with code 2 without stderr feedback".to_string(),
+                    });
+                }
+            }
+            Some(exit_code) => {
+                status = HookRunStatus::Failed;
+                entries.push(HookOutputEntry {
+                    kind: HookOutputEntryKind::Error,
+                    text: format!("hook exited with code {exit_code}"),
+                });
+            }
+            None => {
+                status = HookRunStatus::Failed;
+                entries.push(HookOutputEntry {
+                    kind: HookOutputEntryKind::Error,
+                    text: "hook exited without a status code".to_string(),
+                });
+            }
+        },
+    }
+
+    let completed = HookCompletedEvent {
+        turn_id,
+        run: dispatcher::completed_summary(handler, &run_result, status, entries),
+    };
+
+    dispatcher::ParsedHandler {
+        completed,
+        data: UserPromptSubmitHandlerData {
+            should_stop,
+            stop_reason,
+            should_block,
+            block_reason,
+            updated_prompt,
+            additional_context_for_model,
+        },
+    }
+}
+
+fn trimmed_non_empty(text: &str) -> Option<String> {
+    let trimmed = text.trim();
+    if !trimmed.is_empty() {
+        return Some(trimmed.to_string());
+    }
+    None
+}
+
+fn join_text_chunks(chunks: Vec<String>) -> Option<String> {
+    if chunks.is_empty() {
+        None
+    } else {
+        Some(chunks.join("\n\n"))
+    }
+}
+
+fn serialization_failure_outcome(
+    handlers: Vec<ConfiguredHandler>,
+    turn_id: Option<String>,
+    error_message: String,
+) -> UserPromptSubmitOutcome {
+    let hook_events = handlers
+        .into_iter()
+        .map(|handler| {
+            let mut run = dispatcher::running_summary(&handler);
+            run.status = HookRunStatus::Failed;
+            run.completed_at = Some(run.started_at);
+            run.duration_ms = Some(0);
+            run.entries = vec![HookOutputEntry {
+                kind: HookOutputEntryKind::Error,
+                text: error_message.clone(),
+            }];
+            HookCompletedEvent {
+                turn_id: turn_id.clone(),
+                run,
+            }
+        })
+        .collect();
+
+    UserPromptSubmitOutcome {
+        hook_events,
+        should_stop: false,
+        stop_reason: None,
+        should_block: false,
+        block_reason: None,
+        updated_input: None,
+        additional_context: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use codex_protocol::protocol::HookEventName;
+    use codex_protocol::protocol::HookHandlerType;
+    use codex_protocol::protocol::HookRunStatus;
+    use codex_protocol::user_input::UserInput;
+    use pretty_assertions::assert_eq;
+
+    use super::UserPromptSubmitHandlerData;
+    use super::apply_updated_prompt;
+    use super::parse_completed;
+    use super::prompt_text;
+    use crate::engine::ConfiguredHandler;
+    use crate::engine::command_runner::CommandRunResult;
+
+    #[test]
+    fn exit_code_two_blocks_with_stderr_reason() {
+        let parsed = parse_completed(
+            &handler(),
+            run_result(Some(2), "", "prompt looks like it contains an AWS key\n"),
+            Some("turn-1".to_string()),
+        );
+
+        assert_eq!(
+            parsed.data,
+            UserPromptSubmitHandlerData {
+                should_stop: false,
+                stop_reason: None,
+                should_block: true,
+                block_reason: Some("prompt looks like it contains an AWS key".to_string()),
+                updated_prompt: None,
+                additional_context_for_model: None,
+            }
+        );
+        assert_eq!(parsed.completed.run.status, HookRunStatus::Blocked);
+    }
+
+    #[test]
+    fn plain_stdout_becomes_model_context() {
+        let parsed = parse_completed(
+            &handler(),
+            run_result(Some(0), "Ticket ABC-123: fix the login redirect\n", ""),
+            Some("turn-1".to_string()),
+        );
+
+        assert_eq!(
+            parsed.data.additional_context_for_model,
+            Some("Ticket ABC-123: fix the login redirect".to_string())
+        );
+        assert_eq!(parsed.completed.run.status, HookRunStatus::Completed);
+    }
+
+    #[test]
+    fn updated_prompt_is_ignored_when_blocked() {
+        let parsed = parse_completed(
+            &handler(),
+            run_result(
+                Some(0),
+                r#"{"decision":"block","reason":"no secrets","hookSpecificOutput":{"hookEventName":"UserPromptSubmit","updatedPrompt":"redacted"}}"#,
+                "",
+            ),
+            Some("turn-1".to_string()),
+        );
+
+        assert_eq!(parsed.data.should_block, true);
+        assert_eq!(parsed.data.updated_prompt, None);
+    }
+
+    #[test]
+    fn updated_prompt_replaces_text_items_and_keeps_others() {
+        let input = vec![
+            UserInput::Image {
+                image_url: "data:image/png;base64,AAAA".to_string(),
+            },
+            UserInput::Text {
+                text: "first".to_string(),
+                text_elements: Vec::new(),
+            },
+            UserInput::Text {
+                text: "second".to_string(),
+                text_elements: Vec::new(),
+            },
+        ];
+        assert_eq!(prompt_text(&input), "first\nsecond");
+
+        assert_eq!(
+            apply_updated_prompt(input, "rewritten".to_string()),
+            vec![
+                UserInput::Image {
+                    image_url: "data:image/png;base64,AAAA".to_string(),
+                },
+                UserInput::Text {
+                    text: "rewritten".to_string(),
+                    text_elements: Vec::new(),
+                },
+            ]
+        );
+    }
+
+    fn handler() -> ConfiguredHandler {
+        ConfiguredHandler {
+            event_name: HookEventName::UserPromptSubmit,
+            handler_type: HookHandlerType::Command,
+            matcher: None,
+            command: "echo hook".to_string(),
+            model: None,
+            timeout_sec: 600,
+            status_message: None,
+            source_path: PathBuf::from("/tmp/hooks.json"),
+            display_order: 0,
+        }
+    }
+
+    fn run_result(exit_code: Option<i32>, stdout: &str, stderr: &str) -> CommandRunResult {
+        CommandRunResult {
+            started_at: 1,
+            completed_at: 2,
+            duration_ms: 1,
+            exit_code,
+            stdout: stdout.to_string(),
+            stderr: stderr.to_string(),
+            error: None,
+        }
+    }
+}
@@ -1,17 +1,26 @@
 mod engine;
 pub mod events;
 mod legacy_notify;
+mod model_runner;
 mod registry;
 mod schema;
 mod types;
 
+pub use events::post_tool_use::PostToolUseOutcome;
+pub use events::post_tool_use::PostToolUseRequest;
+pub use events::pre_tool_use::PreToolUseOutcome;
+pub use events::pre_tool_use::PreToolUseRequest;
 pub use events::session_start::SessionStartOutcome;
 pub use events::session_start::SessionStartRequest;
 pub use events::session_start::SessionStartSource;
 pub use events::stop::StopOutcome;
 pub use events::stop::StopRequest;
+pub use events::user_prompt_submit::UserPromptSubmitOutcome;
+pub use events::user_prompt_submit::UserPromptSubmitRequest;
 pub use legacy_notify::legacy_notify_json;
 pub use legacy_notify::notify_hook;
+pub use model_runner::HookModelRequest;
+pub use model_runner::HookModelRunner;
 pub use registry::Hooks;
 pub use registry::HooksConfig;
 pub use registry::command_from_argv;
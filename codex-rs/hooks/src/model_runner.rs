@@ -0,0 +1,32 @@
+use std::path::PathBuf;
+use std::time::Duration;
+
+use codex_protocol::protocol::HookEventName;
+use futures::future::BoxFuture;
+
+/// A request to evaluate a `prompt` or `agent` hook handler with a model.
+#[derive(Debug, Clone)]
+pub struct HookModelRequest {
+    pub event_name: HookEventName,
+    /// Fully rendered instructions: the configured prompt, the hook input, and
+    /// the JSON shape the reply must follow.
+    pub prompt: String,
+    /// Model override from the handler config. `None` uses the session model.
+    pub model: Option<String>,
+    pub cwd: PathBuf,
+    pub timeout: Duration,
+}
+
+/// Runs model-backed hook handlers on behalf of the hooks engine.
+///
+/// The hooks crate cannot start model turns itself, so the host session
+/// provides an implementation per invocation. Both methods return the model's
+/// final reply text, which is parsed exactly like command hook stdout.
+pub trait HookModelRunner: Send + Sync {
+    /// Sends the prompt to a model in a single request without tools.
+    fn run_prompt(&self, request: HookModelRequest) -> BoxFuture<'_, Result<String, String>>;
+
+    /// Runs the prompt as a read-only sub-agent that may inspect the workspace
+    /// before answering.
+    fn run_agent(&self, request: HookModelRequest) -> BoxFuture<'_, Result<String, String>>;
+}
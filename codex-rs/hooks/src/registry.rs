@@ -3,10 +3,17 @@ use tokio::process::Command;
 
 use crate::engine::ClaudeHooksEngine;
 use crate::engine::CommandShell;
+use crate::events::post_tool_use::PostToolUseOutcome;
+use crate::events::post_tool_use::PostToolUseRequest;
+use crate::events::pre_tool_use::PreToolUseOutcome;
+use crate::events::pre_tool_use::PreToolUseRequest;
 use crate::events::session_start::SessionStartOutcome;
 use crate::events::session_start::SessionStartRequest;
 use crate::events::stop::StopOutcome;
 use crate::events::stop::StopRequest;
+use crate::events::user_prompt_submit::UserPromptSubmitOutcome;
+use crate::events::user_prompt_submit::UserPromptSubmitRequest;
+use crate::model_runner::HookModelRunner;
 use crate::types::Hook;
 use crate::types::HookEvent;
 use crate::types::HookPayload;
@@ -94,8 +101,58 @@ impl Hooks {
         &self,
         request: SessionStartRequest,
         turn_id: Option<String>,
+        model_runner: &dyn HookModelRunner,
     ) -> SessionStartOutcome {
-        self.engine.run_session_start(request, turn_id).await
+        self.engine
+            .run_session_start(request, turn_id, model_runner)
+            .await
+    }
+
+    pub fn preview_user_prompt_submit(
+        &self,
+        request: &UserPromptSubmitRequest,
+    ) -> Vec<codex_protocol::protocol::HookRunSummary> {
+        self.engine.preview_user_prompt_submit(request)
+    }
+
+    pub async fn run_user_prompt_submit(
+        &self,
+        request: UserPromptSubmitRequest,
+        model_runner: &dyn HookModelRunner,
+    ) -> UserPromptSubmitOutcome {
+        self.engine
+            .run_user_prompt_submit(request, model_runner)
+            .await
+    }
+
+    pub fn preview_pre_tool_use(
+        &self,
+        request: &PreToolUseRequest,
+    ) -> Vec<codex_protocol::protocol::HookRunSummary> {
+        self.engine.preview_pre_tool_use(request)
+    }
+
+    pub async fn run_pre_tool_use(
+        &self,
+        request: PreToolUseRequest,
+        model_runner: &dyn HookModelRunner,
+    ) -> PreToolUseOutcome {
+        self.engine.run_pre_tool_use(request, model_runner).await
+    }
+
+    pub fn preview_post_tool_use(
+        &self,
+        request: &PostToolUseRequest,
+    ) -> Vec<codex_protocol::protocol::HookRunSummary> {
+        self.engine.preview_post_tool_use(request)
+    }
+
+    pub async fn run_post_tool_use(
+        &self,
+        request: PostToolUseRequest,
+        model_runner: &dyn HookModelRunner,
+    ) -> PostToolUseOutcome {
+        self.engine.run_post_tool_use(request, model_runner).await
     }
 
     pub fn preview_stop(
@@ -105,8 +162,12 @@ impl Hooks {
         self.engine.preview_stop(request)
     }
 
-    pub async fn run_stop(&self, request: StopRequest) -> StopOutcome {
-        self.engine.run_stop(request).await
+    pub async fn run_stop(
+        &self,
+        request: StopRequest,
+        model_runner: &dyn HookModelRunner,
+    ) -> StopOutcome {
+        self.engine.run_stop(request, model_runner).await
     }
 }
 
@@ -18,6 +18,8 @@ use std::path::PathBuf;
 
 use codex_core::error::CodexErr;
 use codex_core::error::Result;
+use codex_protocol::permissions::GlobDenyMatches;
+use codex_protocol::permissions::OverlayWorkspaceLayers;
 use codex_protocol::protocol::FileSystemSandboxPolicy;
 use codex_protocol::protocol::WritableRoot;
 use codex_utils_absolute_path::AbsolutePathBuf;
@@ -40,7 +42,7 @@ const LINUX_PLATFORM_DEFAULT_READ_ROOTS: &[&str] = &[
 
 /// Options that control how bubblewrap is invoked.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub(crate) struct BwrapOptions {
+pub(crate) struct BwrapOptions<'a> {
     /// Whether to mount a fresh `/proc` inside the PID namespace.
     ///
     /// This is the secure default, but some restrictive container environments
@@ -48,13 +50,21 @@ pub(crate) struct BwrapOptions {
     pub mount_proc: bool,
     /// How networking should be configured inside the bubblewrap sandbox.
     pub network_mode: BwrapNetworkMode,
+    /// Copy-on-write layers mounted over the workspace instead of binding it
+    /// writable, so writes land in the upper layer.
+    pub overlay_workspace: Option<&'a OverlayWorkspaceLayers>,
+    /// Glob deny matches expanded by the caller. When absent they are
+    /// expanded from the policy.
+    pub glob_deny_matches: Option<&'a GlobDenyMatches>,
 }
 
-impl Default for BwrapOptions {
+impl Default for BwrapOptions<'_> {
     fn default() -> Self {
         Self {
             mount_proc: true,
             network_mode: BwrapNetworkMode::FullAccess,
+            overlay_workspace: None,
+            glob_deny_matches: None,
         }
     }
 }
@@ -150,7 +160,12 @@ fn create_bwrap_flags(
     let BwrapArgs {
         args: filesystem_args,
         preserved_files,
-    } = create_filesystem_args(file_system_sandbox_policy, cwd)?;
+    } = create_filesystem_args(
+        file_system_sandbox_policy,
+        cwd,
+        options.overlay_workspace,
+        options.glob_deny_matches,
+    )?;
     let mut args = Vec::new();
     args.push("--new-session".to_string());
     args.push("--die-with-parent".to_string());
@@ -187,17 +202,46 @@ fn create_bwrap_flags(
 /// 3. Unreadable ancestors of writable roots are masked before their child
 ///    mounts are rebound so nested writable carveouts can be reopened safely.
 /// 4. `--bind <root> <root>` re-enables writes for allowed roots, including
-///    writable subpaths under `/dev` (for example, `/dev/shm`).
+///    writable subpaths under `/dev` (for example, `/dev/shm`). An overlay
+///    workspace is mounted with `--overlay` right after the deepest writable
+///    root that contains it (or in place of that root's bind), and writable
+///    roots nested inside the workspace are left to the overlay.
 /// 5. `--ro-bind <subpath> <subpath>` re-applies read-only protections under
 ///    those writable roots so protected subpaths win.
 /// 6. Nested unreadable carveouts under a writable root are masked after that
 ///    root is bound, and unrelated unreadable roots are masked afterward.
+///
+/// Glob entries are expanded into the paths that exist when they are expanded,
+/// either here or once per turn by the caller, and masked like explicit
+/// unreadable roots. Files created later are not covered.
 fn create_filesystem_args(
     file_system_sandbox_policy: &FileSystemSandboxPolicy,
     cwd: &Path,
+    overlay_workspace: Option<&OverlayWorkspaceLayers>,
+    glob_deny_matches: Option<&GlobDenyMatches>,
 ) -> Result<BwrapArgs> {
     let writable_roots = file_system_sandbox_policy.get_writable_roots_with_cwd(cwd);
-    let unreadable_roots = file_system_sandbox_policy.get_unreadable_roots_with_cwd(cwd);
+    let mut unreadable_roots = file_system_sandbox_policy.get_unreadable_roots_with_cwd(cwd);
+    let glob_matches = match glob_deny_matches {
+        Some(glob_deny_matches) => glob_deny_matches.paths.clone(),
+        None => {
+            file_system_sandbox_policy
+                .glob_denies_with_cwd(cwd)
+                .expand_existing_matches()
+                .paths
+        }
+    };
+    // Paths inside an already masked root do not exist in the sandbox, so
+    // they cannot be mount targets.
+    let glob_matches: Vec<AbsolutePathBuf> = glob_matches
+        .into_iter()
+        .filter(|path| {
+            !unreadable_roots
+                .iter()
+                .any(|root| path.as_path().starts_with(root.as_path()))
+        })
+        .collect();
+    unreadable_roots.extend(glob_matches);
     ensure_mount_targets_exist(&writable_roots)?;
 
     let mut args = if file_system_sandbox_policy.has_full_disk_read_access() {
@@ -289,6 +333,15 @@ fn create_filesystem_args(
         .map(|path| path.as_path().to_path_buf())
         .collect();
     unreadable_ancestors_of_writable_roots.sort_by_key(|path| path_depth(path));
+    // The overlay only applies when the workspace is writable in the first
+    // place; it is mounted after the deepest writable root containing it.
+    let overlay_workspace = overlay_workspace.and_then(|overlay| {
+        allowed_write_paths
+            .iter()
+            .filter(|root| overlay.lower.starts_with(root))
+            .max_by_key(|root| path_depth(root))
+            .map(|parent_root| (overlay, parent_root.clone()))
+    });
 
     for unreadable_root in &unreadable_ancestors_of_writable_roots {
         append_unreadable_root_args(
@@ -312,9 +365,25 @@ fn create_filesystem_args(
             append_mount_target_parent_dir_args(&mut args, root, masking_root);
         }
 
-        args.push("--bind".to_string());
-        args.push(path_to_string(root));
-        args.push(path_to_string(root));
+        match &overlay_workspace {
+            Some((overlay, _)) if root != overlay.lower && root.starts_with(&overlay.lower) => {
+                // Already writable through the overlay; binding the host path
+                // here would bypass the upper layer.
+            }
+            Some((overlay, parent_root)) if root == parent_root.as_path() => {
+                if root != overlay.lower {
+                    args.push("--bind".to_string());
+                    args.push(path_to_string(root));
+                    args.push(path_to_string(root));
+                }
+                append_overlay_workspace_args(&mut args, overlay);
+            }
+            _ => {
+                args.push("--bind".to_string());
+                args.push(path_to_string(root));
+                args.push(path_to_string(root));
+            }
+        }
 
         let mut read_only_subpaths: Vec<PathBuf> = writable_root
             .read_only_subpaths
@@ -368,6 +437,16 @@ fn create_filesystem_args(
     })
 }
 
+/// Mount `overlay.lower` as an overlayfs whose writes go to `overlay.upper`.
+fn append_overlay_workspace_args(args: &mut Vec<String>, overlay: &OverlayWorkspaceLayers) {
+    args.push("--overlay-src".to_string());
+    args.push(path_to_string(&overlay.lower));
+    args.push("--overlay".to_string());
+    args.push(path_to_string(&overlay.upper));
+    args.push(path_to_string(&overlay.work));
+    args.push(path_to_string(&overlay.lower));
+}
+
 /// Validate that writable roots exist before constructing mounts.
 ///
 /// Bubblewrap requires bind mount targets to exist. We fail fast with a clear
@@ -610,6 +689,8 @@ mod tests {
             BwrapOptions {
                 mount_proc: true,
                 network_mode: BwrapNetworkMode::FullAccess,
+                overlay_workspace: None,
+                glob_deny_matches: None,
             },
         )
         .expect("create bwrap args");
@@ -627,6 +708,8 @@ mod tests {
             BwrapOptions {
                 mount_proc: true,
                 network_mode: BwrapNetworkMode::ProxyOnly,
+                overlay_workspace: None,
+                glob_deny_matches: None,
             },
         )
         .expect("create bwrap args");
@@ -663,6 +746,8 @@ mod tests {
         let args = create_filesystem_args(
             &FileSystemSandboxPolicy::from(&sandbox_policy),
             Path::new("/"),
+            None,
+            None,
         )
         .expect("bwrap fs args");
         assert_eq!(
@@ -700,8 +785,13 @@ mod tests {
             network_access: false,
         };
 
-        let args = create_filesystem_args(&FileSystemSandboxPolicy::from(&policy), temp_dir.path())
-            .expect("filesystem args");
+        let args = create_filesystem_args(
+            &FileSystemSandboxPolicy::from(&policy),
+            temp_dir.path(),
+            None,
+            None,
+        )
+        .expect("filesystem args");
 
         assert_eq!(args.args[0..4], ["--tmpfs", "/", "--dev", "/dev"]);
 
@@ -730,8 +820,13 @@ mod tests {
         // `ReadOnlyAccess::Restricted` always includes `cwd` as a readable
         // root. Using `"/"` here would intentionally collapse to broad read
         // access, so use a non-root cwd to exercise the restricted path.
-        let args = create_filesystem_args(&FileSystemSandboxPolicy::from(&policy), temp_dir.path())
-            .expect("filesystem args");
+        let args = create_filesystem_args(
+            &FileSystemSandboxPolicy::from(&policy),
+            temp_dir.path(),
+            None,
+            None,
+        )
+        .expect("filesystem args");
 
         assert!(
             args.args
@@ -771,7 +866,8 @@ mod tests {
             },
         ]);
 
-        let args = create_filesystem_args(&policy, temp_dir.path()).expect("filesystem args");
+        let args =
+            create_filesystem_args(&policy, temp_dir.path(), None, None).expect("filesystem args");
 
         assert!(args.args.windows(3).any(|window| {
             window
@@ -817,6 +913,65 @@ mod tests {
         );
     }
 
+    #[test]
+    fn overlay_workspace_replaces_the_workspace_bind() {
+        let temp_dir = TempDir::new().expect("temp dir");
+        let workspace = temp_dir.path().join("workspace");
+        let nested = workspace.join("target");
+        std::fs::create_dir_all(&nested).expect("create nested writable root");
+        let overlay = OverlayWorkspaceLayers {
+            lower: workspace.clone(),
+            upper: temp_dir.path().join("upper"),
+            work: temp_dir.path().join("work"),
+        };
+        let workspace_str = path_to_string(&workspace);
+        let nested_str = path_to_string(&nested);
+        let policy = FileSystemSandboxPolicy::restricted(vec![
+            FileSystemSandboxEntry {
+                path: FileSystemPath::Special {
+                    value: FileSystemSpecialPath::Root,
+                },
+                access: FileSystemAccessMode::Read,
+            },
+            FileSystemSandboxEntry {
+                path: FileSystemPath::Path {
+                    path: AbsolutePathBuf::from_absolute_path(&workspace)
+                        .expect("absolute workspace"),
+                },
+                access: FileSystemAccessMode::Write,
+            },
+            FileSystemSandboxEntry {
+                path: FileSystemPath::Path {
+                    path: AbsolutePathBuf::from_absolute_path(&nested).expect("absolute nested"),
+                },
+                access: FileSystemAccessMode::Write,
+            },
+        ]);
+
+        let args = create_filesystem_args(&policy, temp_dir.path(), Some(&overlay), None)
+            .expect("filesystem args");
+
+        let upper_str = path_to_string(&overlay.upper);
+        let work_str = path_to_string(&overlay.work);
+        assert!(args.args.windows(6).any(|window| {
+            window
+                == [
+                    "--overlay-src",
+                    workspace_str.as_str(),
+                    "--overlay",
+                    upper_str.as_str(),
+                    work_str.as_str(),
+                    workspace_str.as_str(),
+                ]
+        }));
+        assert!(
+            !args.args.iter().any(|arg| arg == "--bind"),
+            "writable roots inside the overlay must not be bound from the host: {:#?}",
+            args.args
+        );
+        assert!(!args.args.contains(&nested_str));
+    }
+
     #[test]
     fn split_policy_reenables_nested_writable_subpaths_after_read_only_parent() {
         let temp_dir = TempDir::new().expect("temp dir");
@@ -848,7 +1003,8 @@ mod tests {
             },
         ]);
 
-        let args = create_filesystem_args(&policy, temp_dir.path()).expect("filesystem args");
+        let args =
+            create_filesystem_args(&policy, temp_dir.path(), None, None).expect("filesystem args");
         let docs_str = path_to_string(docs.as_path());
         let docs_public_str = path_to_string(docs_public.as_path());
         let docs_ro_index = args
@@ -900,7 +1056,8 @@ mod tests {
             },
         ]);
 
-        let args = create_filesystem_args(&policy, temp_dir.path()).expect("filesystem args");
+        let args =
+            create_filesystem_args(&policy, temp_dir.path(), None, None).expect("filesystem args");
         let blocked_str = path_to_string(blocked.as_path());
         let allowed_str = path_to_string(allowed.as_path());
         let blocked_none_index = args
@@ -967,7 +1124,8 @@ mod tests {
             },
         ]);
 
-        let args = create_filesystem_args(&policy, temp_dir.path()).expect("filesystem args");
+        let args =
+            create_filesystem_args(&policy, temp_dir.path(), None, None).expect("filesystem args");
         let blocked_str = path_to_string(blocked.as_path());
         let allowed_dir_str = path_to_string(allowed_dir.as_path());
         let allowed_file_str = path_to_string(allowed_file.as_path());
@@ -1042,7 +1200,8 @@ mod tests {
             },
         ]);
 
-        let args = create_filesystem_args(&policy, temp_dir.path()).expect("filesystem args");
+        let args =
+            create_filesystem_args(&policy, temp_dir.path(), None, None).expect("filesystem args");
         let blocked_none_index = args
             .args
             .windows(4)
@@ -1087,7 +1246,8 @@ mod tests {
             },
         ]);
 
-        let args = create_filesystem_args(&policy, temp_dir.path()).expect("filesystem args");
+        let args =
+            create_filesystem_args(&policy, temp_dir.path(), None, None).expect("filesystem args");
         let blocked_str = path_to_string(blocked.as_path());
 
         assert!(
@@ -1129,7 +1289,8 @@ mod tests {
             },
         ]);
 
-        let args = create_filesystem_args(&policy, temp_dir.path()).expect("filesystem args");
+        let args =
+            create_filesystem_args(&policy, temp_dir.path(), None, None).expect("filesystem args");
         let blocked_file_str = path_to_string(blocked_file.as_path());
 
         assert_eq!(args.preserved_files.len(), 1);
@@ -1140,4 +1301,109 @@ mod tests {
                 && window[4] == blocked_file_str
         }));
     }
+
+    #[test]
+    fn glob_denies_mask_existing_matches_under_writable_root() {
+        let temp_dir = TempDir::new().expect("temp dir");
+        let cwd = temp_dir.path().join("workspace");
+        let secrets = cwd.join("deploy/secrets");
+        std::fs::create_dir_all(secrets.join("nested")).expect("create secrets dir");
+        std::fs::write(cwd.join(".env"), "TOKEN=1").expect("write .env");
+        std::fs::write(cwd.join("deploy/.env.prod"), "TOKEN=2").expect("write .env.prod");
+        std::fs::write(secrets.join("nested/.env"), "TOKEN=3").expect("write nested .env");
+        std::fs::write(cwd.join("README.md"), "readme").expect("write readme");
+        let env_str = path_to_string(&cwd.join(".env"));
+        let env_prod_str = path_to_string(&cwd.join("deploy/.env.prod"));
+        let secrets_str = path_to_string(&secrets);
+        let policy = FileSystemSandboxPolicy::restricted(vec![
+            FileSystemSandboxEntry {
+                path: FileSystemPath::Special {
+                    value: FileSystemSpecialPath::Root,
+                },
+                access: FileSystemAccessMode::Read,
+            },
+            FileSystemSandboxEntry {
+                path: FileSystemPath::Special {
+                    value: FileSystemSpecialPath::CurrentWorkingDirectory,
+                },
+                access: FileSystemAccessMode::Write,
+            },
+            FileSystemSandboxEntry {
+                path: FileSystemPath::Glob {
+                    pattern: "**/.env*".to_string(),
+                },
+                access: FileSystemAccessMode::None,
+            },
+            FileSystemSandboxEntry {
+                path: FileSystemPath::Glob {
+                    pattern: "**/secrets".to_string(),
+                },
+                access: FileSystemAccessMode::None,
+            },
+        ]);
+
+        let args = create_filesystem_args(&policy, &cwd, None, None).expect("filesystem args");
+
+        for masked_file in [&env_str, &env_prod_str] {
+            assert!(
+                args.args.windows(5).any(|window| {
+                    window[0] == "--perms"
+                        && window[1] == "000"
+                        && window[2] == "--ro-bind-data"
+                        && window[4] == *masked_file
+                }),
+                "expected {masked_file} to be masked: {:#?}",
+                args.args
+            );
+        }
+        assert!(
+            args.args
+                .windows(4)
+                .any(|window| window == ["--perms", "000", "--tmpfs", secrets_str.as_str()]),
+            "expected secrets dir to be masked: {:#?}",
+            args.args
+        );
+        let nested_env_str = path_to_string(&secrets.join("nested/.env"));
+        assert!(
+            !args.args.contains(&nested_env_str),
+            "files inside a masked directory are not mount targets: {:#?}",
+            args.args
+        );
+    }
+
+    #[test]
+    fn glob_denies_use_matches_expanded_by_caller() {
+        let temp_dir = TempDir::new().expect("temp dir");
+        let cwd = temp_dir.path().join("workspace");
+        std::fs::create_dir_all(&cwd).expect("create cwd");
+        std::fs::write(cwd.join(".env"), "TOKEN=1").expect("write .env");
+        std::fs::write(cwd.join(".env.local"), "TOKEN=2").expect("write .env.local");
+        let env_str = path_to_string(&cwd.join(".env"));
+        let env_local_str = path_to_string(&cwd.join(".env.local"));
+        let policy = FileSystemSandboxPolicy::restricted(vec![
+            FileSystemSandboxEntry {
+                path: FileSystemPath::Special {
+                    value: FileSystemSpecialPath::Root,
+                },
+                access: FileSystemAccessMode::Read,
+            },
+            FileSystemSandboxEntry {
+                path: FileSystemPath::Glob {
+                    pattern: "**/.env*".to_string(),
+                },
+                access: FileSystemAccessMode::None,
+            },
+        ]);
+        // Expanded before `.env.local` was created.
+        let glob_deny_matches = GlobDenyMatches {
+            paths: vec![AbsolutePathBuf::from_absolute_path(cwd.join(".env")).expect("absolute")],
+            truncated_under: None,
+        };
+
+        let args = create_filesystem_args(&policy, &cwd, None, Some(&glob_deny_matches))
+            .expect("filesystem args");
+
+        assert!(args.args.contains(&env_str), "{:#?}", args.args);
+        assert!(!args.args.contains(&env_local_str), "{:#?}", args.args);
+    }
 }
@@ -1,8 +1,9 @@
 //! Linux sandbox helper entry point.
 //!
 //! On Linux, `codex-linux-sandbox` applies:
-//! - in-process restrictions (`no_new_privs` + seccomp), and
-//! - bubblewrap for filesystem isolation.
+//! - in-process restrictions (`no_new_privs` + seccomp),
+//! - bubblewrap for filesystem isolation, and
+//! - optional cgroup v2 resource limits.
 #[cfg(target_os = "linux")]
 mod bwrap;
 #[cfg(target_os = "linux")]
@@ -12,6 +13,8 @@ mod linux_run_main;
 #[cfg(target_os = "linux")]
 mod proxy_routing;
 #[cfg(target_os = "linux")]
+mod resource_limits;
+#[cfg(target_os = "linux")]
 mod vendored_bwrap;
 
 #[cfg(target_os = "linux")]
@@ -13,10 +13,15 @@ use crate::bwrap::create_bwrap_command_args;
 use crate::landlock::apply_sandbox_policy_to_current_thread;
 use crate::proxy_routing::activate_proxy_routes_in_netns;
 use crate::proxy_routing::prepare_host_proxy_route_spec;
+use crate::resource_limits::enforce_resource_limits;
 use crate::vendored_bwrap::exec_vendored_bwrap;
 use crate::vendored_bwrap::run_vendored_bwrap_main;
+use codex_protocol::permissions::GLOB_DENY_MAX_VISITED_ENTRIES;
 use codex_protocol::protocol::FileSystemSandboxPolicy;
+use codex_protocol::protocol::GlobDenyMatches;
 use codex_protocol::protocol::NetworkSandboxPolicy;
+use codex_protocol::protocol::OverlayWorkspaceLayers;
+use codex_protocol::protocol::ResourceLimits;
 use codex_protocol::protocol::SandboxPolicy;
 
 #[derive(Debug, Parser)]
@@ -44,6 +49,25 @@ pub struct LandlockCommand {
     #[arg(long = "network-sandbox-policy", hide = true)]
     pub network_sandbox_policy: Option<NetworkSandboxPolicy>,
 
+    /// Memory, CPU, process-count and wall-time limits for the command.
+    ///
+    /// Only the outer stage receives this flag; it supervises the whole
+    /// bubblewrap/seccomp pipeline from a transient cgroup.
+    #[arg(long = "resource-limits", hide = true)]
+    pub resource_limits: Option<ResourceLimits>,
+
+    /// Copy-on-write layers to mount over the workspace.
+    ///
+    /// Writes under `lower` land in `upper` instead of the real workspace.
+    /// Only the bubblewrap pipeline supports this.
+    #[arg(long = "overlay-workspace", hide = true)]
+    pub overlay_workspace: Option<OverlayWorkspaceLayers>,
+
+    /// Paths matched by the filesystem glob denies, expanded once per turn by
+    /// the caller. When absent the helper expands the globs itself.
+    #[arg(long = "glob-deny-matches", hide = true)]
+    pub glob_deny_matches: Option<GlobDenyMatches>,
+
     /// Opt-in: use the legacy Landlock Linux sandbox fallback.
     ///
     /// When not set, the helper uses the default bubblewrap pipeline.
@@ -84,6 +108,8 @@ pub struct LandlockCommand {
 /// Entry point for the Linux sandbox helper.
 ///
 /// The sequence is:
+/// 0. When resource limits are set, fork a supervisor and move the command
+///    into a transient cgroup.
 /// 1. When needed, wrap the command with bubblewrap to construct the
 ///    filesystem view.
 /// 2. Apply in-process restrictions (no_new_privs + seccomp).
@@ -94,6 +120,9 @@ pub fn run_main() -> ! {
         sandbox_policy,
         file_system_sandbox_policy,
         network_sandbox_policy,
+        resource_limits,
+        overlay_workspace,
+        glob_deny_matches,
         use_legacy_landlock,
         apply_seccomp_then_exec,
         allow_network_for_proxy,
@@ -106,6 +135,7 @@ pub fn run_main() -> ! {
         panic!("No command specified to execute.");
     }
     ensure_inner_stage_mode_is_valid(apply_seccomp_then_exec, use_legacy_landlock);
+    ensure_overlay_workspace_mode_is_valid(overlay_workspace.as_ref(), use_legacy_landlock);
     let EffectiveSandboxPolicies {
         sandbox_policy,
         file_system_sandbox_policy,
@@ -123,6 +153,9 @@ pub fn run_main() -> ! {
         network_sandbox_policy,
         &sandbox_policy_cwd,
     );
+    if let Some(resource_limits) = resource_limits {
+        enforce_resource_limits(resource_limits);
+    }
 
     // Inner stage: apply seccomp/no_new_privs after bubblewrap has already
     // established the filesystem view.
@@ -184,13 +217,24 @@ pub fn run_main() -> ! {
             proxy_route_spec,
             command,
         });
+        let glob_deny_matches = glob_deny_matches.unwrap_or_else(|| {
+            file_system_sandbox_policy
+                .glob_denies_with_cwd(&sandbox_policy_cwd)
+                .expand_existing_matches()
+        });
+        if let Some(message) = glob_deny_truncation_message(&glob_deny_matches) {
+            eprintln!("{message}");
+        }
         run_bwrap_with_proc_fallback(
             &sandbox_policy_cwd,
             &file_system_sandbox_policy,
-            network_sandbox_policy,
             inner,
-            !no_proc,
-            allow_network_for_proxy,
+            BwrapOptions {
+                mount_proc: !no_proc,
+                network_mode: bwrap_network_mode(network_sandbox_policy, allow_network_for_proxy),
+                overlay_workspace: overlay_workspace.as_ref(),
+                glob_deny_matches: Some(&glob_deny_matches),
+            },
         );
     }
 
@@ -369,6 +413,15 @@ fn ensure_inner_stage_mode_is_valid(apply_seccomp_then_exec: bool, use_legacy_la
     }
 }
 
+fn ensure_overlay_workspace_mode_is_valid(
+    overlay_workspace: Option<&OverlayWorkspaceLayers>,
+    use_legacy_landlock: bool,
+) {
+    if overlay_workspace.is_some() && use_legacy_landlock {
+        panic!("--overlay-workspace is incompatible with --use-legacy-landlock");
+    }
+}
+
 fn ensure_legacy_landlock_mode_supports_policy(
     use_legacy_landlock: bool,
     file_system_sandbox_policy: &FileSystemSandboxPolicy,
@@ -385,33 +438,35 @@ fn ensure_legacy_landlock_mode_supports_policy(
     }
 }
 
+/// Warns that glob denies were only partly expanded, so matches the walk did
+/// not reach stay readable to the command.
+fn glob_deny_truncation_message(glob_deny_matches: &GlobDenyMatches) -> Option<String> {
+    let dir = glob_deny_matches.truncated_under.as_ref()?;
+    Some(format!(
+        "codex: filesystem glob denies were only expanded for the first {GLOB_DENY_MAX_VISITED_ENTRIES} entries (stopped under {dir}); files matched past that point are not masked for this command. Use a narrower pattern.",
+        dir = dir.display()
+    ))
+}
+
 fn run_bwrap_with_proc_fallback(
     sandbox_policy_cwd: &Path,
     file_system_sandbox_policy: &FileSystemSandboxPolicy,
-    network_sandbox_policy: NetworkSandboxPolicy,
     inner: Vec<String>,
-    mount_proc: bool,
-    allow_network_for_proxy: bool,
+    mut options: BwrapOptions,
 ) -> ! {
-    let network_mode = bwrap_network_mode(network_sandbox_policy, allow_network_for_proxy);
-    let mut mount_proc = mount_proc;
-
-    if mount_proc
+    if options.mount_proc
         && !preflight_proc_mount_support(
             sandbox_policy_cwd,
             file_system_sandbox_policy,
-            network_mode,
+            options.network_mode,
+            options.glob_deny_matches,
         )
     {
         // Keep the retry silent so sandbox-internal diagnostics do not leak into the
         // child process stderr stream.
-        mount_proc = false;
+        options.mount_proc = false;
     }
 
-    let options = BwrapOptions {
-        mount_proc,
-        network_mode,
-    };
     let bwrap_args = build_bwrap_argv(
         inner,
         file_system_sandbox_policy,
@@ -470,9 +525,14 @@ fn preflight_proc_mount_support(
     sandbox_policy_cwd: &Path,
     file_system_sandbox_policy: &FileSystemSandboxPolicy,
     network_mode: BwrapNetworkMode,
+    glob_deny_matches: Option<&GlobDenyMatches>,
 ) -> bool {
-    let preflight_argv =
-        build_preflight_bwrap_argv(sandbox_policy_cwd, file_system_sandbox_policy, network_mode);
+    let preflight_argv = build_preflight_bwrap_argv(
+        sandbox_policy_cwd,
+        file_system_sandbox_policy,
+        network_mode,
+        glob_deny_matches,
+    );
     let stderr = run_bwrap_in_child_capture_stderr(preflight_argv);
     !is_proc_mount_failure(stderr.as_str())
 }
@@ -481,6 +541,7 @@ fn build_preflight_bwrap_argv(
     sandbox_policy_cwd: &Path,
     file_system_sandbox_policy: &FileSystemSandboxPolicy,
     network_mode: BwrapNetworkMode,
+    glob_deny_matches: Option<&GlobDenyMatches>,
 ) -> crate::bwrap::BwrapArgs {
     let preflight_command = vec![resolve_true_command()];
     build_bwrap_argv(
@@ -490,6 +551,8 @@ fn build_preflight_bwrap_argv(
         BwrapOptions {
             mount_proc: true,
             network_mode,
+            overlay_workspace: None,
+            glob_deny_matches,
         },
     )
 }
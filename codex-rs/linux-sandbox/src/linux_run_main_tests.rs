@@ -47,6 +47,8 @@ fn inserts_bwrap_argv0_before_command_separator() {
         BwrapOptions {
             mount_proc: true,
             network_mode: BwrapNetworkMode::FullAccess,
+            overlay_workspace: None,
+            glob_deny_matches: None,
         },
     )
     .args;
@@ -83,6 +85,8 @@ fn inserts_unshare_net_when_network_isolation_requested() {
         BwrapOptions {
             mount_proc: true,
             network_mode: BwrapNetworkMode::Isolated,
+            overlay_workspace: None,
+            glob_deny_matches: None,
         },
     )
     .args;
@@ -99,6 +103,8 @@ fn inserts_unshare_net_when_proxy_only_network_mode_requested() {
         BwrapOptions {
             mount_proc: true,
             network_mode: BwrapNetworkMode::ProxyOnly,
+            overlay_workspace: None,
+            glob_deny_matches: None,
         },
     )
     .args;
@@ -166,6 +172,7 @@ fn managed_proxy_preflight_argv_is_wrapped_for_full_access_policy() {
         Path::new("/"),
         &FileSystemSandboxPolicy::from(&SandboxPolicy::DangerFullAccess),
         mode,
+        None,
     )
     .args;
     assert!(argv.iter().any(|arg| arg == "--"));
@@ -390,6 +397,21 @@ fn apply_seccomp_then_exec_with_legacy_landlock_panics() {
     assert!(result.is_err());
 }
 
+#[test]
+fn overlay_workspace_with_legacy_landlock_panics() {
+    let overlay = OverlayWorkspaceLayers {
+        lower: PathBuf::from("/workspace"),
+        upper: PathBuf::from("/codex/overlays/upper"),
+        work: PathBuf::from("/codex/overlays/work"),
+    };
+    let result = std::panic::catch_unwind(|| {
+        ensure_overlay_workspace_mode_is_valid(Some(&overlay), true);
+    });
+    assert!(result.is_err());
+    ensure_overlay_workspace_mode_is_valid(Some(&overlay), false);
+    ensure_overlay_workspace_mode_is_valid(None, true);
+}
+
 #[test]
 fn legacy_landlock_rejects_split_only_filesystem_policies() {
     let temp_dir = tempfile::TempDir::new().expect("tempdir");
@@ -427,3 +449,19 @@ fn valid_inner_stage_modes_do_not_panic() {
     ensure_inner_stage_mode_is_valid(false, true);
     ensure_inner_stage_mode_is_valid(true, false);
 }
+
+#[test]
+fn glob_deny_truncation_is_reported() {
+    assert_eq!(
+        glob_deny_truncation_message(&GlobDenyMatches::default()),
+        None
+    );
+
+    let message = glob_deny_truncation_message(&GlobDenyMatches {
+        paths: Vec::new(),
+        truncated_under: Some(AbsolutePathBuf::from_absolute_path("/repo/vendor").expect("abs")),
+    })
+    .expect("truncation message");
+    assert!(message.contains("/repo/vendor"), "{message}");
+    assert!(!message.contains("sandbox"), "{message}");
+}
@@ -0,0 +1,537 @@
+//! Per-command resource limits (`--resource-limits`).
+//!
+//! When limits are requested, the outer helper process forks before any
+//! sandbox setup. The child joins a transient cgroup v2 (or applies rlimits
+//! when no cgroup can be created) and continues into the usual
+//! bubblewrap/seccomp pipeline. The parent stays behind as a supervisor: it
+//! enforces the wall-time limit, reports on stderr which limit stopped the
+//! command, removes the cgroup and exits with the command's status. Limits
+//! that cannot be enforced on the host are reported on stderr too, so they
+//! show up in the command's output rather than being dropped silently.
+//!
+//! Messages written here must not mention the sandbox: core treats such
+//! output as a sandbox denial and may offer to rerun the command unsandboxed,
+//! which would also drop the limits.
+
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+use std::time::Instant;
+
+use codex_protocol::protocol::ResourceLimits;
+
+const CGROUP_ROOT: &str = "/sys/fs/cgroup";
+const CPU_PERIOD_MICROS: u64 = 100_000;
+const WAIT_POLL_INTERVAL: Duration = Duration::from_millis(50);
+const CGROUP_REMOVE_ATTEMPTS: usize = 20;
+/// Exit status used when the limits themselves could not be set up.
+const SETUP_FAILURE_EXIT_CODE: i32 = 1;
+
+/// Forks a supervisor for the command when `limits` is non-empty.
+///
+/// Returns in the child, which must go on to exec the command. The parent
+/// never returns.
+pub(crate) fn enforce_resource_limits(limits: ResourceLimits) {
+    if limits.is_empty() {
+        return;
+    }
+
+    // Creating the cgroup fails when cgroup v2 is not mounted or not
+    // delegated to this user; rlimits are the fallback in that case.
+    let cgroup = TransientCgroup::create(&limits).ok();
+
+    let supervisor_pid = unsafe { libc::getpid() };
+    let pid = unsafe { libc::fork() };
+    if pid < 0 {
+        let err = io::Error::last_os_error();
+        exit_with_setup_failure(&format!(
+            "failed to start the resource limit supervisor: {err}"
+        ));
+    }
+
+    if pid == 0 {
+        // Child: die with the supervisor so an interrupted supervisor never
+        // leaves an unlimited command behind, and lead a process group so the
+        // supervisor can kill whatever the command forks.
+        unsafe {
+            libc::prctl(libc::PR_SET_PDEATHSIG, libc::SIGKILL);
+            if libc::getppid() != supervisor_pid {
+                libc::_exit(1);
+            }
+            libc::setpgid(0, 0);
+        }
+        let joined = cgroup.as_ref().is_some_and(|cgroup| cgroup.join().is_ok());
+        if !joined {
+            if let Err(err) = apply_rlimits(&limits) {
+                exit_with_setup_failure(&format!("failed to apply resource limits: {err}"));
+            }
+            for message in unenforced_limit_messages(&limits) {
+                eprintln!("{message}");
+            }
+        }
+        return;
+    }
+
+    // Also set the group from this side so a kill cannot race the child's
+    // own `setpgid`.
+    unsafe {
+        libc::setpgid(pid, pid);
+    }
+    let status = match wait_for_command(pid, &limits, cgroup.as_ref()) {
+        Ok(status) => status,
+        Err(err) => {
+            kill_command(pid, cgroup.as_ref());
+            if let Some(cgroup) = cgroup {
+                cgroup.remove();
+            }
+            exit_with_setup_failure(&format!(
+                "failed to wait for the resource-limited command: {err}"
+            ));
+        }
+    };
+    let events = cgroup
+        .as_ref()
+        .map(TransientCgroup::events)
+        .unwrap_or_default();
+    for message in limit_messages(&limits, status.timed_out, events) {
+        eprintln!("{message}");
+    }
+    if let Some(cgroup) = cgroup {
+        cgroup.remove();
+    }
+    std::process::exit(status.exit_code);
+}
+
+/// Reports why the limits could not be set up and exits, instead of panicking
+/// with a backtrace in the command's output.
+fn exit_with_setup_failure(message: &str) -> ! {
+    eprintln!("codex: {message}");
+    std::process::exit(SETUP_FAILURE_EXIT_CODE);
+}
+
+struct CommandStatus {
+    exit_code: i32,
+    timed_out: bool,
+}
+
+/// Waits for the command, killing it once the wall-time limit passes.
+///
+/// A command killed by a signal is reported as `128 + signal` rather than by
+/// dying from the same signal, so the caller still receives its output.
+fn wait_for_command(
+    pid: libc::pid_t,
+    limits: &ResourceLimits,
+    cgroup: Option<&TransientCgroup>,
+) -> io::Result<CommandStatus> {
+    let deadline = limits
+        .wall_time_seconds
+        .map(|seconds| Instant::now() + Duration::from_secs(seconds));
+    let mut timed_out = false;
+    loop {
+        let mut status: libc::c_int = 0;
+        let options = if deadline.is_some() { libc::WNOHANG } else { 0 };
+        let wait_res = unsafe { libc::waitpid(pid, &mut status as *mut libc::c_int, options) };
+        if wait_res == pid {
+            let exit_code = if libc::WIFEXITED(status) {
+                libc::WEXITSTATUS(status)
+            } else if libc::WIFSIGNALED(status) {
+                128 + libc::WTERMSIG(status)
+            } else {
+                1
+            };
+            return Ok(CommandStatus {
+                exit_code,
+                timed_out,
+            });
+        }
+        if wait_res < 0 {
+            let err = io::Error::last_os_error();
+            if err.kind() == io::ErrorKind::Interrupted {
+                continue;
+            }
+            return Err(err);
+        }
+
+        if !timed_out && deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            timed_out = true;
+            kill_command(pid, cgroup);
+        }
+        std::thread::sleep(WAIT_POLL_INTERVAL);
+    }
+}
+
+/// Kills the command along with everything it started: the whole cgroup when
+/// the command joined one, and its process group otherwise.
+fn kill_command(pid: libc::pid_t, cgroup: Option<&TransientCgroup>) {
+    if let Some(cgroup) = cgroup {
+        cgroup.kill();
+    }
+    // The child may have failed to join the cgroup, so the group kill runs
+    // either way.
+    unsafe {
+        libc::killpg(pid, libc::SIGKILL);
+    }
+}
+
+/// Counters read from the cgroup after the command exits.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+struct CgroupEvents {
+    oom_kills: u64,
+    pids_max_hits: u64,
+}
+
+fn limit_messages(limits: &ResourceLimits, timed_out: bool, events: CgroupEvents) -> Vec<String> {
+    let mut messages = Vec::new();
+    if timed_out {
+        messages.push(format!(
+            "codex: command killed after exceeding its wall-time limit of {}s",
+            limits.wall_time_seconds.unwrap_or_default()
+        ));
+    }
+    if events.oom_kills > 0 {
+        messages.push(format!(
+            "codex: command killed after exceeding its memory limit of {}",
+            format_bytes(limits.memory_max_bytes.unwrap_or_default())
+        ));
+    }
+    if events.pids_max_hits > 0 {
+        messages.push(format!(
+            "codex: command reached its limit of {} processes; further forks were refused",
+            limits.pids_max.unwrap_or_default()
+        ));
+    }
+    messages
+}
+
+fn format_bytes(bytes: u64) -> String {
+    const MIB: u64 = 1024 * 1024;
+    const GIB: u64 = 1024 * MIB;
+    if bytes >= GIB && bytes.is_multiple_of(GIB) {
+        format!("{} GiB", bytes / GIB)
+    } else if bytes >= MIB && bytes.is_multiple_of(MIB) {
+        format!("{} MiB", bytes / MIB)
+    } else {
+        format!("{bytes} bytes")
+    }
+}
+
+/// Fallback for hosts without a writable cgroup v2 hierarchy.
+///
+/// Only the memory limit has an rlimit counterpart, and `RLIMIT_AS` caps
+/// address space rather than resident memory. `RLIMIT_NPROC` counts every
+/// process of the user rather than those of the command, and there is no
+/// rlimit equivalent of a CPU quota, so those limits are left to
+/// [`unenforced_limit_messages`].
+fn apply_rlimits(limits: &ResourceLimits) -> io::Result<()> {
+    // The resource argument type differs between glibc and musl, so call
+    // `setrlimit` directly with the libc constants.
+    if let Some(memory_max_bytes) = limits.memory_max_bytes {
+        let limit = rlimit(memory_max_bytes);
+        check_rlimit(unsafe { libc::setrlimit(libc::RLIMIT_AS, &limit) })?;
+    }
+    Ok(())
+}
+
+/// Limits that [`apply_rlimits`] cannot enforce.
+fn unenforced_limit_messages(limits: &ResourceLimits) -> Vec<String> {
+    let mut messages = Vec::new();
+    if let Some(cpu_max_percent) = limits.cpu_max_percent {
+        messages.push(format!(
+            "codex: CPU limit of {cpu_max_percent}% not enforced: this host has no writable cgroup v2 hierarchy"
+        ));
+    }
+    if let Some(pids_max) = limits.pids_max {
+        messages.push(format!(
+            "codex: process limit of {pids_max} not enforced: this host has no writable cgroup v2 hierarchy"
+        ));
+    }
+    messages
+}
+
+fn rlimit(value: u64) -> libc::rlimit {
+    libc::rlimit {
+        rlim_cur: value as libc::rlim_t,
+        rlim_max: value as libc::rlim_t,
+    }
+}
+
+fn check_rlimit(ret: libc::c_int) -> io::Result<()> {
+    if ret < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+/// A cgroup v2 created for a single command and removed once it exits.
+struct TransientCgroup {
+    path: PathBuf,
+}
+
+impl TransientCgroup {
+    /// Creates the cgroup under the helper's own cgroup or, when that cgroup
+    /// cannot delegate controllers because it holds processes, next to it.
+    fn create(limits: &ResourceLimits) -> io::Result<Self> {
+        let proc_self_cgroup = fs::read_to_string("/proc/self/cgroup")?;
+        let current = parse_cgroup_v2_path(&proc_self_cgroup)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::Unsupported, "cgroup v2 is not in use"))?;
+        let current = Path::new(CGROUP_ROOT).join(current.strip_prefix("/").unwrap_or(&current));
+        let name = format!("codex-command-{}", std::process::id());
+
+        let mut last_err = io::Error::new(io::ErrorKind::NotFound, "no cgroup parent found");
+        for parent in [Some(current.as_path()), current.parent()]
+            .into_iter()
+            .flatten()
+            .filter(|parent| parent.starts_with(CGROUP_ROOT))
+        {
+            match Self::create_under(parent, &name, limits) {
+                Ok(cgroup) => return Ok(cgroup),
+                Err(err) => last_err = err,
+            }
+        }
+        Err(last_err)
+    }
+
+    fn create_under(parent: &Path, name: &str, limits: &ResourceLimits) -> io::Result<Self> {
+        let subtree_control = fs::read_to_string(parent.join("cgroup.subtree_control"))?;
+        for controller in required_controllers(limits) {
+            if !subtree_control
+                .split_whitespace()
+                .any(|enabled| enabled == controller)
+            {
+                fs::write(
+                    parent.join("cgroup.subtree_control"),
+                    format!("+{controller}"),
+                )?;
+            }
+        }
+
+        let path = parent.join(name);
+        fs::create_dir(&path)?;
+        let cgroup = Self { path };
+        if let Err(err) = cgroup.write_limits(limits) {
+            cgroup.remove();
+            return Err(err);
+        }
+        Ok(cgroup)
+    }
+
+    fn write_limits(&self, limits: &ResourceLimits) -> io::Result<()> {
+        if let Some(memory_max_bytes) = limits.memory_max_bytes {
+            fs::write(self.path.join("memory.max"), memory_max_bytes.to_string())?;
+            // Without this the kernel swaps instead of enforcing the limit.
+            // Not every kernel exposes swap accounting, so this is best effort.
+            let _ = fs::write(self.path.join("memory.swap.max"), "0");
+        }
+        if let Some(cpu_max_percent) = limits.cpu_max_percent {
+            fs::write(self.path.join("cpu.max"), cpu_max_value(cpu_max_percent))?;
+        }
+        if let Some(pids_max) = limits.pids_max {
+            fs::write(self.path.join("pids.max"), pids_max.to_string())?;
+        }
+        Ok(())
+    }
+
+    /// Moves the calling process into the cgroup.
+    fn join(&self) -> io::Result<()> {
+        fs::write(self.path.join("cgroup.procs"), "0")
+    }
+
+    fn kill(&self) {
+        if fs::write(self.path.join("cgroup.kill"), "1").is_ok() {
+            return;
+        }
+        // `cgroup.kill` needs Linux 5.14; signal each member instead.
+        if let Ok(procs) = fs::read_to_string(self.path.join("cgroup.procs")) {
+            for pid in procs
+                .lines()
+                .filter_map(|line| line.trim().parse::<libc::pid_t>().ok())
+            {
+                unsafe {
+                    libc::kill(pid, libc::SIGKILL);
+                }
+            }
+        }
+    }
+
+    fn events(&self) -> CgroupEvents {
+        let read_count = |file: &str, key: &str| {
+            fs::read_to_string(self.path.join(file))
+                .ok()
+                .and_then(|events| event_count(&events, key))
+                .unwrap_or(0)
+        };
+        CgroupEvents {
+            oom_kills: read_count("memory.events", "oom_kill"),
+            pids_max_hits: read_count("pids.events", "max"),
+        }
+    }
+
+    /// Removes the cgroup, retrying briefly while killed members are reaped.
+    fn remove(&self) {
+        for _ in 0..CGROUP_REMOVE_ATTEMPTS {
+            match fs::remove_dir(&self.path) {
+                Ok(()) => return,
+                Err(err) if err.kind() == io::ErrorKind::NotFound => return,
+                Err(_) => {
+                    self.kill();
+                    std::thread::sleep(WAIT_POLL_INTERVAL);
+                }
+            }
+        }
+    }
+}
+
+fn required_controllers(limits: &ResourceLimits) -> Vec<&'static str> {
+    let mut controllers = Vec::new();
+    if limits.memory_max_bytes.is_some() {
+        controllers.push("memory");
+    }
+    if limits.cpu_max_percent.is_some() {
+        controllers.push("cpu");
+    }
+    if limits.pids_max.is_some() {
+        controllers.push("pids");
+    }
+    controllers
+}
+
+/// Returns the unified-hierarchy entry (`0::/path`) of `/proc/self/cgroup`.
+fn parse_cgroup_v2_path(proc_self_cgroup: &str) -> Option<PathBuf> {
+    proc_self_cgroup
+        .lines()
+        .find_map(|line| line.strip_prefix("0::"))
+        .map(PathBuf::from)
+}
+
+fn event_count(events: &str, key: &str) -> Option<u64> {
+    events.lines().find_map(|line| {
+        let (name, count) = line.split_once(' ')?;
+        (name == key).then(|| count.trim().parse().ok()).flatten()
+    })
+}
+
+fn cpu_max_value(cpu_max_percent: u64) -> String {
+    let quota = cpu_max_percent.saturating_mul(CPU_PERIOD_MICROS) / 100;
+    format!("{quota} {CPU_PERIOD_MICROS}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn parses_unified_cgroup_path() {
+        let proc_self_cgroup = "0::/user.slice/user-1000.slice/session-2.scope\n";
+        assert_eq!(
+            parse_cgroup_v2_path(proc_self_cgroup),
+            Some(PathBuf::from("/user.slice/user-1000.slice/session-2.scope"))
+        );
+        assert_eq!(parse_cgroup_v2_path("12:pids:/user.slice\n"), None);
+    }
+
+    #[test]
+    fn reads_event_counters() {
+        let memory_events = "low 0\nhigh 0\nmax 12\noom 1\noom_kill 1\noom_group_kill 0\n";
+        assert_eq!(event_count(memory_events, "oom_kill"), Some(1));
+        assert_eq!(event_count(memory_events, "max"), Some(12));
+        assert_eq!(event_count(memory_events, "missing"), None);
+    }
+
+    #[test]
+    fn cpu_quota_is_relative_to_one_core() {
+        assert_eq!(cpu_max_value(50), "50000 100000");
+        assert_eq!(cpu_max_value(200), "200000 100000");
+    }
+
+    #[test]
+    fn messages_name_each_exceeded_limit() {
+        let limits = ResourceLimits {
+            memory_max_bytes: Some(2 * 1024 * 1024 * 1024),
+            cpu_max_percent: None,
+            pids_max: Some(64),
+            wall_time_seconds: Some(30),
+        };
+
+        assert_eq!(
+            limit_messages(
+                &limits,
+                true,
+                CgroupEvents {
+                    oom_kills: 1,
+                    pids_max_hits: 3,
+                },
+            ),
+            vec![
+                "codex: command killed after exceeding its wall-time limit of 30s".to_string(),
+                "codex: command killed after exceeding its memory limit of 2 GiB".to_string(),
+                "codex: command reached its limit of 64 processes; further forks were refused"
+                    .to_string(),
+            ]
+        );
+        assert_eq!(
+            limit_messages(&limits, false, CgroupEvents::default()),
+            Vec::<String>::new()
+        );
+    }
+
+    #[test]
+    fn rlimit_fallback_reports_cpu_and_process_limits() {
+        let limits = ResourceLimits {
+            memory_max_bytes: Some(512 * 1024 * 1024),
+            cpu_max_percent: Some(50),
+            pids_max: Some(8),
+            wall_time_seconds: None,
+        };
+
+        assert_eq!(
+            unenforced_limit_messages(&limits),
+            vec![
+                "codex: CPU limit of 50% not enforced: this host has no writable cgroup v2 hierarchy"
+                    .to_string(),
+                "codex: process limit of 8 not enforced: this host has no writable cgroup v2 hierarchy"
+                    .to_string(),
+            ]
+        );
+        assert_eq!(
+            unenforced_limit_messages(&ResourceLimits {
+                memory_max_bytes: Some(512 * 1024 * 1024),
+                ..ResourceLimits::default()
+            }),
+            Vec::<String>::new()
+        );
+    }
+
+    #[test]
+    fn messages_do_not_look_like_sandbox_denials() {
+        let limits = ResourceLimits {
+            memory_max_bytes: Some(512 * 1024 * 1024),
+            cpu_max_percent: None,
+            pids_max: Some(8),
+            wall_time_seconds: Some(1),
+        };
+        let mut messages = limit_messages(
+            &limits,
+            true,
+            CgroupEvents {
+                oom_kills: 1,
+                pids_max_hits: 1,
+            },
+        );
+        messages.extend(unenforced_limit_messages(&ResourceLimits {
+            cpu_max_percent: Some(50),
+            ..limits
+        }));
+        for message in messages {
+            let lower = message.to_lowercase();
+            assert!(
+                ["sandbox", "permission denied", "operation not permitted"]
+                    .iter()
+                    .all(|needle| !lower.contains(needle)),
+                "{message}"
+            );
+        }
+    }
+}
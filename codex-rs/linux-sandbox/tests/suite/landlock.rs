@@ -15,6 +15,7 @@ use codex_protocol::permissions::FileSystemSandboxEntry;
 use codex_protocol::permissions::FileSystemSandboxPolicy;
 use codex_protocol::permissions::FileSystemSpecialPath;
 use codex_protocol::permissions::NetworkSandboxPolicy;
+use codex_protocol::permissions::ResourceLimits;
 use codex_protocol::protocol::ReadOnlyAccess;
 use codex_protocol::protocol::SandboxPolicy;
 use codex_utils_absolute_path::AbsolutePathBuf;
@@ -134,6 +135,7 @@ async fn run_cmd_result_with_policies(
         sandbox_cwd.as_path(),
         &codex_linux_sandbox_exe,
         use_legacy_landlock,
+        ResourceLimits::default(),
         None,
     )
     .await
@@ -367,6 +369,7 @@ async fn assert_network_blocked(cmd: &[&str]) {
         sandbox_cwd.as_path(),
         &codex_linux_sandbox_exe,
         false,
+        ResourceLimits::default(),
         None,
     )
     .await;
@@ -14,6 +14,7 @@ use codex_core::NewThread;
 use codex_core::ThreadManager;
 use codex_core::config::Config as CodexConfig;
 use codex_protocol::ThreadId;
+use codex_protocol::approvals::McpSamplingDecision;
 use codex_protocol::protocol::AgentMessageEvent;
 use codex_protocol::protocol::ApplyPatchApprovalRequestEvent;
 use codex_protocol::protocol::Event;
@@ -267,6 +268,21 @@ async fn run_codex_tool_session_inner(
                         // TODO: forward elicitation requests to the client?
                         continue;
                     }
+                    EventMsg::McpSamplingRequest(request) => {
+                        // Nobody can approve the completion here, so decline it
+                        // rather than leave the requesting server waiting.
+                        if let Err(e) = thread
+                            .submit(Op::ResolveMcpSampling {
+                                server_name: request.server_name,
+                                request_id: request.id,
+                                decision: McpSamplingDecision::Decline,
+                            })
+                            .await
+                        {
+                            tracing::error!("Failed to decline MCP sampling request: {e}");
+                        }
+                        continue;
+                    }
                     EventMsg::ApplyPatchApprovalRequest(ApplyPatchApprovalRequestEvent {
                         call_id,
                         turn_id: _,
@@ -332,7 +348,10 @@ async fn run_codex_tool_session_inner(
                     | EventMsg::McpToolCallBegin(_)
                     | EventMsg::McpToolCallEnd(_)
                     | EventMsg::McpListToolsResponse(_)
+                    | EventMsg::ListMcpPromptsResponse(_)
+                    | EventMsg::GetMcpPromptResponse(_)
                     | EventMsg::ListCustomPromptsResponse(_)
+                    | EventMsg::ExpandCustomPromptResponse(_)
                     | EventMsg::ListSkillsResponse(_)
                     | EventMsg::ListRemoteSkillsResponse(_)
                     | EventMsg::RemoteSkillDownloaded(_)
@@ -345,6 +364,8 @@ async fn run_codex_tool_session_inner(
                     | EventMsg::PatchApplyBegin(_)
                     | EventMsg::PatchApplyEnd(_)
                     | EventMsg::TurnDiff(_)
+                    | EventMsg::OverlayWorkspaceChanges(_)
+                    | EventMsg::BackgroundServicesUpdated(_)
                     | EventMsg::WebSearchBegin(_)
                     | EventMsg::WebSearchEnd(_)
                     | EventMsg::GetHistoryEntryResponse(_)
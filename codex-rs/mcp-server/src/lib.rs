@@ -30,6 +30,7 @@ pub(crate) mod message_processor;
 mod outgoing_message;
 mod patch_approval;
 mod query_project;
+mod syntax_chunker;
 
 use crate::message_processor::MessageProcessor;
 use crate::outgoing_message::OutgoingJsonRpcMessage;
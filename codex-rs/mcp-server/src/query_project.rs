@@ -1,7 +1,14 @@
+use crate::syntax_chunker::ChunkSymbol;
+use crate::syntax_chunker::SymbolKind;
+use crate::syntax_chunker::has_meaningful_text;
+use crate::syntax_chunker::symbol_spans;
 use anyhow::Context;
+use codex_core::DEFAULT_OLLAMA_PORT;
+use codex_core::OLLAMA_OSS_PROVIDER_ID;
 use codex_core::config::Config;
 use codex_core::config::types::QueryProjectIndex;
 use codex_core::config::types::QueryProjectIndexBackend;
+use codex_ollama::OllamaClient;
 use futures::TryStreamExt;
 use globset::Glob;
 use globset::GlobSet;
@@ -64,10 +71,13 @@ const DEFAULT_VOYAGE_EMBEDDINGS_URL: &str = "https://api.voyageai.com/v1/embeddi
 const OPENAI_API_KEY_ENV_VAR: &str = "OPENAI_API_KEY";
 const OPENAI_BASE_URL_ENV_VAR: &str = "OPENAI_BASE_URL";
 const VOYAGE_API_KEY_ENV_VAR: &str = "VOYAGE_API_KEY";
+const OLLAMA_MODEL_PREFIX: &str = "ollama/";
 const INDEX_DIR: &str = ".codex/repo_hybrid_index";
 const DB_FILE_NAME: &str = "index.sqlite";
 const CHUNK_LINE_COUNT: usize = 40;
 const CHUNK_LINE_OVERLAP: usize = 8;
+const MAX_SYMBOL_CHUNK_LINES: usize = 120;
+const SYMBOL_MATCH_BOOST: f32 = 0.25;
 const SNIPPET_LINE_COUNT: usize = 6;
 const MAX_FILE_SIZE_BYTES: u64 = 1_500_000;
 const EMBED_BATCH_SIZE: usize = 64;
@@ -115,8 +125,18 @@ pub(crate) struct RepoHybridSearchParams {
     #[serde(default = "default_alpha")]
     pub alpha: f32,
     /// Optional embedding model override. Defaults to `text-embedding-3-small`.
+    /// Use `ollama/<model>` (for example `ollama/nomic-embed-text`) to embed
+    /// with a local Ollama server instead of a hosted API.
     #[serde(default)]
     pub embedding_model: Option<String>,
+    /// Optional symbol-name filter. Only chunks inside a definition whose
+    /// name contains this text (case-insensitive) are returned, and matching
+    /// definitions are considered even when the query text misses them.
+    #[serde(default)]
+    pub symbol: Option<String>,
+    /// Optional symbol-kind filter (for example: ["function", "method"]).
+    #[serde(default)]
+    pub symbol_kinds: Option<Vec<SymbolKind>>,
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
@@ -141,6 +161,8 @@ pub(crate) struct RepoHybridSearchResultItem {
     pub line_range: LineRange,
     pub snippet: String,
     pub score: f32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub symbol: Option<ChunkSymbol>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -238,31 +260,38 @@ impl SearchBlendMode {
 enum EmbeddingProvider {
     OpenAiCompatible,
     Voyage,
+    /// Local Ollama server, selected with an `ollama/` model prefix. The
+    /// prefixed name is what gets stored as `METADATA_EMBEDDING_MODEL`, so
+    /// indexes built with Ollama never mix with hosted-provider vectors.
+    Ollama,
 }
 
 impl EmbeddingProvider {
     fn from_model(model: &str) -> Self {
-        if model
-            .trim_start()
-            .to_ascii_lowercase()
-            .starts_with("voyage-")
-        {
+        let model = model.trim_start().to_ascii_lowercase();
+        if model.starts_with(OLLAMA_MODEL_PREFIX) {
+            return Self::Ollama;
+        }
+        if model.starts_with("voyage-") {
             return Self::Voyage;
         }
         Self::OpenAiCompatible
     }
 
-    fn api_key_env_var(self) -> &'static str {
+    /// Environment variable holding the API key, or `None` for local
+    /// providers that need no credentials.
+    fn api_key_env_var(self) -> Option<&'static str> {
         match self {
-            Self::OpenAiCompatible => OPENAI_API_KEY_ENV_VAR,
-            Self::Voyage => VOYAGE_API_KEY_ENV_VAR,
+            Self::OpenAiCompatible => Some(OPENAI_API_KEY_ENV_VAR),
+            Self::Voyage => Some(VOYAGE_API_KEY_ENV_VAR),
+            Self::Ollama => None,
         }
     }
 
-    fn embeddings_url(self, openai_base_url_override: Option<&str>) -> String {
+    fn embeddings_url(self, base_url_override: Option<&str>) -> String {
         match self {
             Self::OpenAiCompatible => {
-                let base_url = openai_base_url_override.map_or_else(
+                let base_url = base_url_override.map_or_else(
                     || {
                         std::env::var(OPENAI_BASE_URL_ENV_VAR)
                             .unwrap_or_else(|_| DEFAULT_OPENAI_BASE_URL.to_string())
@@ -280,6 +309,10 @@ impl EmbeddingProvider {
                 }
             }
             Self::Voyage => DEFAULT_VOYAGE_EMBEDDINGS_URL.to_string(),
+            Self::Ollama => base_url_override.map_or_else(
+                || format!("http://localhost:{DEFAULT_OLLAMA_PORT}"),
+                str::to_owned,
+            ),
         }
     }
 }
@@ -290,6 +323,7 @@ struct ChunkDraft {
     end_line: usize,
     content: String,
     snippet: String,
+    symbol: Option<ChunkSymbol>,
 }
 
 #[derive(Debug, Clone)]
@@ -298,6 +332,39 @@ struct ChunkRecord {
     start_line: usize,
     end_line: usize,
     snippet: String,
+    symbol: Option<ChunkSymbol>,
+}
+
+/// Symbol constraints for a search; empty means unrestricted.
+#[derive(Debug, Clone, Default)]
+struct SymbolFilter {
+    name: Option<String>,
+    kinds: Vec<SymbolKind>,
+}
+
+impl SymbolFilter {
+    fn new(name: Option<String>, kinds: Option<Vec<SymbolKind>>) -> Self {
+        Self {
+            name: name
+                .map(|name| name.trim().to_lowercase())
+                .filter(|name| !name.is_empty()),
+            kinds: kinds.unwrap_or_default(),
+        }
+    }
+
+    fn matches(&self, symbol: Option<&ChunkSymbol>) -> bool {
+        if self.name.is_none() && self.kinds.is_empty() {
+            return true;
+        }
+        let Some(symbol) = symbol else {
+            return false;
+        };
+        let name_matches = self
+            .name
+            .as_deref()
+            .is_none_or(|name| symbol.name.to_lowercase().contains(name));
+        name_matches && (self.kinds.is_empty() || self.kinds.contains(&symbol.kind))
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -433,7 +500,7 @@ pub(crate) async fn handle_repo_index_refresh(
         embedding_model,
         params.force_full,
         require_embeddings,
-        &config.query_project_index,
+        config,
     )
     .await
     {
@@ -459,7 +526,7 @@ pub(crate) async fn auto_warm_query_project_index(
         config.query_project_index.embedding_model.clone(),
         false,
         config.query_project_index.require_embeddings,
-        &config.query_project_index,
+        config,
     )
     .await
 }
@@ -470,11 +537,12 @@ async fn refresh_repo_index(
     embedding_model: Option<String>,
     force_full: bool,
     require_embeddings: bool,
-    index_config: &QueryProjectIndex,
+    config: &Config,
 ) -> anyhow::Result<RepoIndexWarmOutcome> {
-    let index = RepoHybridIndex::open(&repo_root, index_config)
+    let index = RepoHybridIndex::open(&repo_root, &config.query_project_index)
         .await
-        .with_context(|| format!("failed to initialize index at `{}`", repo_root.display()))?;
+        .with_context(|| format!("failed to initialize index at `{}`", repo_root.display()))?
+        .with_ollama_base_url(ollama_base_url(config));
     let embedding_model = embedding_model_or_default(embedding_model);
     let mut embedding_mode = resolve_embedding_mode(require_embeddings, &embedding_model)?;
     let refresh_outcome = refresh_index(
@@ -587,7 +655,7 @@ pub(crate) async fn handle_query_project(
     };
 
     let index = match RepoHybridIndex::open(&repo_root, &config.query_project_index).await {
-        Ok(index) => index,
+        Ok(index) => index.with_ollama_base_url(ollama_base_url(config)),
         Err(err) => {
             return query_project_failure(
                 &call_start,
@@ -622,12 +690,14 @@ pub(crate) async fn handle_query_project(
         }
     };
 
+    let symbol_filter = SymbolFilter::new(params.symbol.clone(), params.symbol_kinds.clone());
     let search_outcome = match index
         .search(
             query,
             limit,
             blend_mode,
             &file_globs,
+            &symbol_filter,
             embedding_model.clone(),
             config.query_project_index.require_embeddings,
         )
@@ -708,6 +778,15 @@ fn query_project_failure(
     call_tool_error(message)
 }
 
+/// Base URL of the configured `ollama` provider, honoring user overrides in
+/// `model_providers` and `CODEX_OSS_BASE_URL`.
+fn ollama_base_url(config: &Config) -> Option<String> {
+    config
+        .model_providers
+        .get(OLLAMA_OSS_PROVIDER_ID)
+        .and_then(|provider| provider.base_url.clone())
+}
+
 fn default_alpha() -> f32 {
     DEFAULT_ALPHA
 }
@@ -724,12 +803,17 @@ fn resolve_embedding_mode(
     model: &str,
 ) -> anyhow::Result<SelectedEmbeddingMode> {
     let provider = EmbeddingProvider::from_model(model);
-    let api_key = std::env::var(provider.api_key_env_var()).ok();
-    resolve_embedding_mode_from_api_key(
-        require_embeddings,
-        api_key.as_deref(),
-        provider.api_key_env_var(),
-    )
+    let Some(api_key_env_var) = provider.api_key_env_var() else {
+        // Local providers need no key; an unreachable server surfaces as a
+        // refresh failure, which falls back to lexical search when allowed.
+        return Ok(SelectedEmbeddingMode {
+            mode: EmbeddingMode::Required,
+            reason: None,
+            require_embeddings,
+        });
+    };
+    let api_key = std::env::var(api_key_env_var).ok();
+    resolve_embedding_mode_from_api_key(require_embeddings, api_key.as_deref(), api_key_env_var)
 }
 
 fn resolve_embedding_mode_from_api_key(
@@ -1026,6 +1110,7 @@ struct RepoHybridIndex {
     embeddings_client: reqwest::Client,
     embeddings_base_url_override: Option<String>,
     embedding_api_key_override: Option<String>,
+    ollama_base_url: Option<String>,
     vector_backend: VectorBackend,
 }
 
@@ -1660,12 +1745,18 @@ impl RepoHybridIndex {
             embeddings_client,
             embeddings_base_url_override: None,
             embedding_api_key_override: None,
+            ollama_base_url: None,
             vector_backend: VectorBackend::from_config(repo_root, index_config)?,
         };
         index.ensure_schema().await?;
         Ok(index)
     }
 
+    fn with_ollama_base_url(mut self, ollama_base_url: Option<String>) -> Self {
+        self.ollama_base_url = ollama_base_url;
+        self
+    }
+
     async fn ensure_schema(&self) -> anyhow::Result<()> {
         sqlx::query(
             r#"
@@ -1708,7 +1799,9 @@ impl RepoHybridIndex {
                 end_line INTEGER NOT NULL,
                 snippet TEXT NOT NULL,
                 content TEXT NOT NULL,
-                embedding TEXT NOT NULL
+                embedding TEXT NOT NULL,
+                symbol_name TEXT,
+                symbol_kind TEXT
             )
             "#,
         )
@@ -1736,6 +1829,41 @@ impl RepoHybridIndex {
         .execute(&self.pool)
         .await?;
 
+        let chunk_columns = sqlx::query("PRAGMA table_info(chunks)")
+            .fetch_all(&self.pool)
+            .await?;
+        let mut has_symbol_columns = false;
+        for row in &chunk_columns {
+            let name: String = row.try_get("name")?;
+            if name == "symbol_name" {
+                has_symbol_columns = true;
+                break;
+            }
+        }
+        if !has_symbol_columns {
+            // Indexes built before syntax-aware chunking use line windows with
+            // no symbols; drop them so the next refresh re-chunks every file.
+            let mut tx = self.pool.begin().await?;
+            sqlx::query("ALTER TABLE chunks ADD COLUMN symbol_name TEXT")
+                .execute(&mut *tx)
+                .await?;
+            sqlx::query("ALTER TABLE chunks ADD COLUMN symbol_kind TEXT")
+                .execute(&mut *tx)
+                .await?;
+            sqlx::query("DELETE FROM chunks").execute(&mut *tx).await?;
+            sqlx::query("DELETE FROM chunks_fts")
+                .execute(&mut *tx)
+                .await?;
+            sqlx::query("DELETE FROM indexed_files")
+                .execute(&mut *tx)
+                .await?;
+            sqlx::query("INSERT OR REPLACE INTO index_metadata(key, value) VALUES (?, 'false')")
+                .bind(METADATA_EMBEDDING_READY)
+                .execute(&mut *tx)
+                .await?;
+            tx.commit().await?;
+        }
+
         Ok(())
     }
 
@@ -1988,7 +2116,7 @@ impl RepoHybridIndex {
                 }
             };
 
-            let chunks = chunk_text(&file_text);
+            let chunks = chunk_file(path, &file_text);
             if chunks.is_empty() {
                 let mut tx = self.pool.begin().await?;
                 remove_file_from_index(&mut tx, path).await?;
@@ -2042,8 +2170,12 @@ impl RepoHybridIndex {
             for (chunk, embedding) in chunks.into_iter().zip(embeddings.iter()) {
                 let embedding_json = serde_json::to_string(&embedding)?;
                 let content = chunk.content;
+                let (symbol_name, symbol_kind) = chunk
+                    .symbol
+                    .map(|symbol| (symbol.name, symbol.kind.as_str()))
+                    .unzip();
                 let insert_result = sqlx::query(
-                    "INSERT INTO chunks(path, start_line, end_line, snippet, content, embedding) VALUES (?, ?, ?, ?, ?, ?)",
+                    "INSERT INTO chunks(path, start_line, end_line, snippet, content, embedding, symbol_name, symbol_kind) VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
                 )
                 .bind(path)
                 .bind(chunk.start_line as i64)
@@ -2051,6 +2183,8 @@ impl RepoHybridIndex {
                 .bind(chunk.snippet)
                 .bind(&content)
                 .bind(embedding_json)
+                .bind(symbol_name)
+                .bind(symbol_kind)
                 .execute(&mut *tx)
                 .await?;
                 let chunk_id = insert_result.last_insert_rowid();
@@ -2189,6 +2323,7 @@ impl RepoHybridIndex {
         limit: usize,
         blend_mode: SearchBlendMode,
         file_globs: &[String],
+        symbol_filter: &SymbolFilter,
         embedding_model: String,
         require_embeddings: bool,
     ) -> anyhow::Result<SearchOutcome> {
@@ -2203,6 +2338,13 @@ impl RepoHybridIndex {
         } else {
             HashMap::new()
         };
+        let symbol_candidate_ids = self
+            .symbol_candidate_ids(
+                symbol_filter,
+                limit.saturating_mul(LEXICAL_CANDIDATE_MULTIPLIER),
+                glob_set.as_ref(),
+            )
+            .await?;
         let lexical_candidate_ids = lexical_scores.keys().copied().collect::<Vec<_>>();
 
         let mut vector_scores = Vec::new();
@@ -2302,7 +2444,8 @@ impl RepoHybridIndex {
             }
         }
 
-        if vector_scores.is_empty() && lexical_scores.is_empty() {
+        if vector_scores.is_empty() && lexical_scores.is_empty() && symbol_candidate_ids.is_empty()
+        {
             return Ok(SearchOutcome {
                 results: Vec::new(),
                 embedding_fallback_reason,
@@ -2326,13 +2469,21 @@ impl RepoHybridIndex {
                 candidate_ids.extend(normalized_vector.keys().copied());
             }
         }
+        candidate_ids.extend(symbol_candidate_ids);
         let candidate_ids = candidate_ids.into_iter().collect::<Vec<_>>();
         let chunks = self.load_chunks_by_ids(&candidate_ids).await?;
+        let mut query_terms = symbol_query_terms(query);
+        query_terms.extend(symbol_filter.name.clone());
 
         let mut merged = candidate_ids
             .into_iter()
-            .filter(|chunk_id| chunks.contains_key(chunk_id))
-            .map(|chunk_id| {
+            .filter_map(|chunk_id| {
+                let chunk = chunks.get(&chunk_id)?;
+                symbol_filter
+                    .matches(chunk.symbol.as_ref())
+                    .then_some((chunk_id, chunk))
+            })
+            .map(|(chunk_id, chunk)| {
                 let vector_score = normalized_vector
                     .get(&chunk_id)
                     .copied()
@@ -2341,7 +2492,14 @@ impl RepoHybridIndex {
                     .get(&chunk_id)
                     .copied()
                     .unwrap_or_default();
-                let score = blend_mode.score(vector_score, lexical_score);
+                let mut score = blend_mode.score(vector_score, lexical_score);
+                if chunk
+                    .symbol
+                    .as_ref()
+                    .is_some_and(|symbol| query_terms.contains(&symbol.name.to_lowercase()))
+                {
+                    score += SYMBOL_MATCH_BOOST * (1.0 - score);
+                }
                 (chunk_id, score)
             })
             .collect::<Vec<_>>();
@@ -2365,6 +2523,7 @@ impl RepoHybridIndex {
                     },
                     snippet: chunk.snippet.clone(),
                     score: round_score(score),
+                    symbol: chunk.symbol.clone(),
                 });
             }
         }
@@ -2379,7 +2538,9 @@ impl RepoHybridIndex {
             return Ok(Vec::new());
         }
         let provider = EmbeddingProvider::from_model(model);
-        let api_key_env_var = provider.api_key_env_var();
+        let Some(api_key_env_var) = provider.api_key_env_var() else {
+            return self.embed_texts_with_ollama(model, inputs).await;
+        };
         let api_key = self
             .embedding_api_key_override
             .as_deref()
@@ -2397,24 +2558,8 @@ impl RepoHybridIndex {
 
         let mut all_embeddings = Vec::<Vec<f32>>::with_capacity(inputs.len());
         for batch in inputs.chunks(EMBED_BATCH_SIZE) {
-            let clamped_batch: Vec<String> = batch
-                .iter()
-                .map(|s| {
-                    if s.trim().is_empty() {
-                        // Azure rejects empty inputs; use a single space as a
-                        // minimal placeholder so the batch size stays aligned.
-                        " ".to_string()
-                    } else if s.len() <= EMBEDDING_MAX_INPUT_BYTES {
-                        s.clone()
-                    } else {
-                        let mut end = EMBEDDING_MAX_INPUT_BYTES;
-                        while !s.is_char_boundary(end) && end > 0 {
-                            end -= 1;
-                        }
-                        s[..end].to_string()
-                    }
-                })
-                .collect();
+            let clamped_batch: Vec<String> =
+                batch.iter().map(|s| clamp_embedding_input(s)).collect();
             let request_body = EmbeddingsRequestBody {
                 model: model.to_string(),
                 input: clamped_batch,
@@ -2471,6 +2616,34 @@ impl RepoHybridIndex {
         Ok(all_embeddings)
     }
 
+    /// Embeds `inputs` one at a time through Ollama's `/api/embeddings`,
+    /// which accepts a single prompt per request.
+    async fn embed_texts_with_ollama(
+        &self,
+        model: &str,
+        inputs: &[String],
+    ) -> anyhow::Result<Vec<Vec<f32>>> {
+        let model_name = model.trim_start()[OLLAMA_MODEL_PREFIX.len()..].trim();
+        if model_name.is_empty() {
+            anyhow::bail!("embedding model `{model}` is missing an Ollama model name");
+        }
+        let base_url = EmbeddingProvider::Ollama.embeddings_url(
+            self.embeddings_base_url_override
+                .as_deref()
+                .or(self.ollama_base_url.as_deref()),
+        );
+        let client = OllamaClient::from_base_url(&base_url);
+        let mut embeddings = Vec::with_capacity(inputs.len());
+        for input in inputs {
+            let embedding = client
+                .embed(model_name, &clamp_embedding_input(input))
+                .await
+                .with_context(|| format!("Ollama embedding request to `{base_url}` failed"))?;
+            embeddings.push(embedding);
+        }
+        Ok(embeddings)
+    }
+
     async fn vector_scores(
         &self,
         query_embedding: &[f32],
@@ -2556,6 +2729,37 @@ impl RepoHybridIndex {
         Ok(scores)
     }
 
+    /// Chunks whose symbol name contains the filter name, so definitions are
+    /// found even when neither lexical nor vector retrieval ranks them.
+    async fn symbol_candidate_ids(
+        &self,
+        symbol_filter: &SymbolFilter,
+        limit: usize,
+        glob_set: Option<&GlobSet>,
+    ) -> anyhow::Result<Vec<i64>> {
+        let Some(name) = symbol_filter.name.as_deref() else {
+            return Ok(Vec::new());
+        };
+        let rows = sqlx::query(
+            "SELECT id, path FROM chunks WHERE symbol_name IS NOT NULL AND instr(lower(symbol_name), ?) > 0 ORDER BY id",
+        )
+        .bind(name)
+        .fetch_all(&self.pool)
+        .await?;
+        let mut ids = Vec::new();
+        for row in rows {
+            let path: String = row.try_get("path")?;
+            if glob_set.is_some_and(|glob_set| !glob_set.is_match(path.as_str())) {
+                continue;
+            }
+            ids.push(row.try_get("id")?);
+            if ids.len() >= limit {
+                break;
+            }
+        }
+        Ok(ids)
+    }
+
     async fn load_chunks_by_ids(&self, ids: &[i64]) -> anyhow::Result<HashMap<i64, ChunkRecord>> {
         if ids.is_empty() {
             return Ok(HashMap::new());
@@ -2563,7 +2767,7 @@ impl RepoHybridIndex {
         let mut chunks = HashMap::new();
         for id_chunk in ids.chunks(SQLITE_BIND_CHUNK_SIZE) {
             let mut builder = QueryBuilder::new(
-                "SELECT id, path, start_line, end_line, snippet FROM chunks WHERE id IN (",
+                "SELECT id, path, start_line, end_line, snippet, symbol_name, symbol_kind FROM chunks WHERE id IN (",
             );
             let mut separated = builder.separated(", ");
             for id in id_chunk {
@@ -2577,6 +2781,11 @@ impl RepoHybridIndex {
                 let start_line: i64 = row.try_get("start_line")?;
                 let end_line: i64 = row.try_get("end_line")?;
                 let snippet: String = row.try_get("snippet")?;
+                let symbol_name: Option<String> = row.try_get("symbol_name")?;
+                let symbol_kind: Option<String> = row.try_get("symbol_kind")?;
+                let symbol = symbol_name
+                    .zip(symbol_kind.as_deref().and_then(SymbolKind::parse))
+                    .map(|(name, kind)| ChunkSymbol { name, kind });
                 chunks.insert(
                     id,
                     ChunkRecord {
@@ -2584,6 +2793,7 @@ impl RepoHybridIndex {
                         start_line: start_line as usize,
                         end_line: end_line as usize,
                         snippet,
+                        symbol,
                     },
                 );
             }
@@ -2853,39 +3063,137 @@ async fn read_text_file(path: &Path) -> anyhow::Result<Option<String>> {
     Ok(Some(String::from_utf8_lossy(&bytes).to_string()))
 }
 
-fn chunk_text(file_text: &str) -> Vec<ChunkDraft> {
+/// Chunks a file on definition boundaries when its language is supported,
+/// falling back to fixed line windows otherwise. Definitions longer than
+/// `MAX_SYMBOL_CHUNK_LINES` are windowed but keep their symbol.
+fn chunk_file(path: &str, file_text: &str) -> Vec<ChunkDraft> {
+    let spans = match symbol_spans(path, file_text) {
+        Some(spans) if !spans.is_empty() => spans,
+        _ => return chunk_text(file_text),
+    };
     let lines = file_text.lines().collect::<Vec<_>>();
-    if lines.is_empty() {
-        return Vec::new();
+    let mut chunks = Vec::new();
+    let mut next_line = 1;
+    for span in spans {
+        if span.start_line > next_line {
+            push_gap_chunks(&lines, next_line - 1, span.start_line - 1, &mut chunks);
+        }
+        let (start_index, end_index) = (span.start_line - 1, span.end_line);
+        if end_index - start_index <= MAX_SYMBOL_CHUNK_LINES {
+            chunks.push(chunk_draft(
+                &lines,
+                start_index,
+                end_index,
+                Some(span.symbol),
+            ));
+        } else {
+            push_window_chunks(
+                &lines,
+                start_index,
+                end_index,
+                Some(&span.symbol),
+                &mut chunks,
+            );
+        }
+        next_line = span.end_line + 1;
+    }
+    if next_line <= lines.len() {
+        push_gap_chunks(&lines, next_line - 1, lines.len(), &mut chunks);
     }
+    chunks
+}
 
-    let step = CHUNK_LINE_COUNT.saturating_sub(CHUNK_LINE_OVERLAP).max(1);
-    let mut start_index = 0;
+/// Windows lines outside any definition, dropping blank or brace-only runs.
+fn push_gap_chunks(
+    lines: &[&str],
+    start_index: usize,
+    end_index: usize,
+    chunks: &mut Vec<ChunkDraft>,
+) {
+    if has_meaningful_text(&lines[start_index..end_index]) {
+        push_window_chunks(lines, start_index, end_index, None, chunks);
+    }
+}
+
+fn chunk_text(file_text: &str) -> Vec<ChunkDraft> {
+    let lines = file_text.lines().collect::<Vec<_>>();
     let mut chunks = Vec::new();
+    push_window_chunks(&lines, 0, lines.len(), None, &mut chunks);
+    chunks
+}
 
-    while start_index < lines.len() {
-        let end_index = (start_index + CHUNK_LINE_COUNT).min(lines.len());
-        let chunk_lines = &lines[start_index..end_index];
-        let snippet = chunk_lines
-            .iter()
-            .take(SNIPPET_LINE_COUNT)
-            .copied()
-            .collect::<Vec<_>>()
-            .join("\n");
-        let content = chunk_lines.join("\n");
-        chunks.push(ChunkDraft {
-            start_line: start_index + 1,
-            end_line: end_index,
-            content,
-            snippet,
-        });
-        if end_index == lines.len() {
+/// Splits `lines[start_index..end_index]` into overlapping windows of
+/// `CHUNK_LINE_COUNT` lines.
+fn push_window_chunks(
+    lines: &[&str],
+    start_index: usize,
+    end_index: usize,
+    symbol: Option<&ChunkSymbol>,
+    chunks: &mut Vec<ChunkDraft>,
+) {
+    let step = CHUNK_LINE_COUNT.saturating_sub(CHUNK_LINE_OVERLAP).max(1);
+    let mut window_start = start_index;
+    while window_start < end_index {
+        let window_end = (window_start + CHUNK_LINE_COUNT).min(end_index);
+        chunks.push(chunk_draft(
+            lines,
+            window_start,
+            window_end,
+            symbol.cloned(),
+        ));
+        if window_end == end_index {
             break;
         }
-        start_index += step;
+        window_start += step;
     }
+}
 
-    chunks
+fn chunk_draft(
+    lines: &[&str],
+    start_index: usize,
+    end_index: usize,
+    symbol: Option<ChunkSymbol>,
+) -> ChunkDraft {
+    let chunk_lines = &lines[start_index..end_index];
+    let snippet = chunk_lines
+        .iter()
+        .take(SNIPPET_LINE_COUNT)
+        .copied()
+        .collect::<Vec<_>>()
+        .join("\n");
+    ChunkDraft {
+        start_line: start_index + 1,
+        end_line: end_index,
+        content: chunk_lines.join("\n"),
+        snippet,
+        symbol,
+    }
+}
+
+fn clamp_embedding_input(input: &str) -> String {
+    if input.trim().is_empty() {
+        // Azure rejects empty inputs; use a single space as a minimal
+        // placeholder so the batch size stays aligned.
+        " ".to_string()
+    } else if input.len() <= EMBEDDING_MAX_INPUT_BYTES {
+        input.to_string()
+    } else {
+        let mut end = EMBEDDING_MAX_INPUT_BYTES;
+        while !input.is_char_boundary(end) && end > 0 {
+            end -= 1;
+        }
+        input[..end].to_string()
+    }
+}
+
+/// Lowercased identifier-like tokens of the query, used to boost chunks whose
+/// symbol name is mentioned verbatim.
+fn symbol_query_terms(query: &str) -> HashSet<String> {
+    query
+        .split(|ch: char| !(ch.is_alphanumeric() || ch == '_'))
+        .filter(|term| !term.is_empty())
+        .map(str::to_lowercase)
+        .collect()
 }
 
 fn normalize_rel_path(path: &str) -> String {
@@ -2993,6 +3301,7 @@ mod tests {
     use wiremock::MockServer;
     use wiremock::Respond;
     use wiremock::ResponseTemplate;
+    use wiremock::matchers::body_partial_json;
     use wiremock::matchers::method;
     use wiremock::matchers::path;
 
@@ -3083,6 +3392,59 @@ mod tests {
         assert_eq!(chunks[1].end_line, 65);
     }
 
+    #[test]
+    fn chunk_file_splits_on_definitions_and_windows_long_symbols() {
+        let mut lines = vec![
+            "use std::io;".to_string(),
+            String::new(),
+            "fn alpha() {".to_string(),
+            "    one();".to_string(),
+            "}".to_string(),
+            String::new(),
+            "fn beta() {".to_string(),
+        ];
+        lines.extend((1..=130).map(|step| format!("    step_{step}();")));
+        lines.push("}".to_string());
+        let chunks = chunk_file("src/lib.rs", &lines.join("\n"));
+
+        let summary = chunks
+            .iter()
+            .map(|chunk| {
+                (
+                    chunk.start_line,
+                    chunk.end_line,
+                    chunk.symbol.as_ref().map(|symbol| symbol.name.as_str()),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            summary,
+            vec![
+                (1, 2, None),
+                (3, 5, Some("alpha")),
+                (7, 46, Some("beta")),
+                (39, 78, Some("beta")),
+                (71, 110, Some("beta")),
+                (103, 138, Some("beta")),
+            ]
+        );
+        assert_eq!(
+            chunks[1].symbol.as_ref().map(|symbol| symbol.kind),
+            Some(SymbolKind::Function)
+        );
+    }
+
+    #[test]
+    fn chunk_file_falls_back_to_line_windows_for_unsupported_files() {
+        let file_text = (1..=65)
+            .map(|line| format!("line {line}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let chunks = chunk_file("notes.txt", &file_text);
+        assert_eq!(chunks.len(), 2);
+        assert!(chunks.iter().all(|chunk| chunk.symbol.is_none()));
+    }
+
     #[test]
     fn normalize_rel_path_strips_dot_prefix_and_backslashes() {
         assert_eq!(normalize_rel_path("./src\\main.rs"), "src/main.rs");
@@ -3473,6 +3835,20 @@ mod tests {
         );
     }
 
+    #[test]
+    fn embedding_provider_uses_ollama_for_prefixed_models_without_api_key() {
+        assert_eq!(
+            EmbeddingProvider::from_model("ollama/nomic-embed-text"),
+            EmbeddingProvider::Ollama
+        );
+        assert_eq!(EmbeddingProvider::Ollama.api_key_env_var(), None);
+
+        let selected =
+            resolve_embedding_mode(true, "ollama/nomic-embed-text").expect("resolve ollama mode");
+        assert_eq!(selected.mode, EmbeddingMode::Required);
+        assert_eq!(selected.reason, None);
+    }
+
     #[test]
     fn resolve_embedding_mode_requires_voyage_api_key_in_strict_mode() {
         let err = resolve_embedding_mode_from_api_key(true, None, VOYAGE_API_KEY_ENV_VAR)
@@ -3553,6 +3929,134 @@ mod tests {
         assert!(!second.ready);
     }
 
+    #[tokio::test]
+    async fn ollama_refresh_embeds_via_native_endpoint_and_records_prefixed_model() {
+        let temp = tempdir().expect("tempdir");
+        let repo_root = temp.path();
+        std::fs::write(repo_root.join("README.md"), "needle").expect("write README");
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api/embeddings"))
+            .and(body_partial_json(json!({"model": "nomic-embed-text"})))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "embedding": [1.0_f32, 0.0_f32],
+            })))
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        let index = RepoHybridIndex::open(repo_root, &QueryProjectIndex::default())
+            .await
+            .expect("open index")
+            .with_ollama_base_url(Some(format!("{}/v1", server.uri())));
+
+        let outcome = index
+            .refresh(
+                &[],
+                false,
+                "ollama/nomic-embed-text".to_string(),
+                EmbeddingMode::Required,
+                true,
+            )
+            .await
+            .expect("ollama refresh");
+        assert!(outcome.ready);
+        assert_eq!(
+            index
+                .load_metadata(METADATA_EMBEDDING_MODEL)
+                .await
+                .expect("load embedding model"),
+            Some("ollama/nomic-embed-text".to_string())
+        );
+
+        let search = index
+            .search(
+                "needle",
+                1,
+                SearchBlendMode::VectorOnly,
+                &[],
+                &SymbolFilter::default(),
+                "ollama/nomic-embed-text".to_string(),
+                false,
+            )
+            .await
+            .expect("search");
+        assert_eq!(search.embedding_fallback_reason, None);
+        assert_eq!(search.results[0].path, "README.md");
+    }
+
+    #[tokio::test]
+    async fn search_filters_by_symbol_and_surfaces_named_definitions() {
+        let temp = tempdir().expect("tempdir");
+        let repo_root = temp.path();
+        std::fs::create_dir_all(repo_root.join("src")).expect("create src dir");
+        std::fs::write(
+            repo_root.join("src/lib.rs"),
+            "fn parse_config() {\n    let needle = 1;\n}\n\nfn render() {\n    let needle = 2;\n}\n",
+        )
+        .expect("write lib.rs");
+
+        let index = RepoHybridIndex::open(repo_root, &QueryProjectIndex::default())
+            .await
+            .expect("open index");
+        index
+            .refresh(&[], false, "model".to_string(), EmbeddingMode::Skip, false)
+            .await
+            .expect("refresh");
+
+        let filtered = index
+            .search(
+                "needle",
+                5,
+                SearchBlendMode::LexicalOnly,
+                &[],
+                &SymbolFilter::new(Some("Parse".to_string()), Some(vec![SymbolKind::Function])),
+                "model".to_string(),
+                false,
+            )
+            .await
+            .expect("filtered search");
+        assert_eq!(filtered.results.len(), 1);
+        assert_eq!(
+            filtered.results[0].symbol,
+            Some(ChunkSymbol {
+                name: "parse_config".to_string(),
+                kind: SymbolKind::Function,
+            })
+        );
+
+        let wrong_kind = index
+            .search(
+                "needle",
+                5,
+                SearchBlendMode::LexicalOnly,
+                &[],
+                &SymbolFilter::new(None, Some(vec![SymbolKind::Method])),
+                "model".to_string(),
+                false,
+            )
+            .await
+            .expect("kind-filtered search");
+        assert!(wrong_kind.results.is_empty());
+
+        let by_name = index
+            .search(
+                "unrelatedword",
+                5,
+                SearchBlendMode::LexicalOnly,
+                &[],
+                &SymbolFilter::new(Some("render".to_string()), None),
+                "model".to_string(),
+                false,
+            )
+            .await
+            .expect("symbol-only search");
+        assert_eq!(by_name.results.len(), 1);
+        assert_eq!(by_name.results[0].line_range.start, 5);
+        assert_eq!(by_name.results[0].score, SYMBOL_MATCH_BOOST);
+    }
+
     #[tokio::test]
     async fn skip_refresh_preserves_ready_metadata_when_index_is_unchanged() {
         let temp = tempdir().expect("tempdir");
@@ -3987,6 +4491,7 @@ mod tests {
                 5,
                 SearchBlendMode::LexicalOnly,
                 &[],
+                &SymbolFilter::default(),
                 "model".to_string(),
                 true,
             )
@@ -4076,6 +4581,7 @@ mod tests {
                 1,
                 SearchBlendMode::VectorOnly,
                 &[],
+                &SymbolFilter::default(),
                 "model".to_string(),
                 false,
             )
@@ -4176,6 +4682,7 @@ mod tests {
                 2,
                 SearchBlendMode::Hybrid(0.6),
                 &[],
+                &SymbolFilter::default(),
                 "model".to_string(),
                 false,
             )
@@ -4273,6 +4780,7 @@ mod tests {
                 1,
                 SearchBlendMode::Hybrid(0.6),
                 &[],
+                &SymbolFilter::default(),
                 "model".to_string(),
                 false,
             )
@@ -4303,6 +4811,7 @@ mod tests {
                 5,
                 SearchBlendMode::Hybrid(0.6),
                 &[],
+                &SymbolFilter::default(),
                 "model".to_string(),
                 false,
             )
@@ -4339,6 +4848,7 @@ mod tests {
                 5,
                 SearchBlendMode::Hybrid(0.6),
                 &[],
+                &SymbolFilter::default(),
                 "model".to_string(),
                 false,
             )
@@ -4358,6 +4868,7 @@ mod tests {
                 5,
                 SearchBlendMode::Hybrid(0.6),
                 &[],
+                &SymbolFilter::default(),
                 "model".to_string(),
                 true,
             )
@@ -4408,6 +4919,7 @@ mod tests {
                 5,
                 SearchBlendMode::Hybrid(0.6),
                 &[],
+                &SymbolFilter::default(),
                 "model".to_string(),
                 false,
             )
@@ -4427,6 +4939,7 @@ mod tests {
                 5,
                 SearchBlendMode::Hybrid(0.6),
                 &[],
+                &SymbolFilter::default(),
                 "model".to_string(),
                 true,
             )
@@ -4463,6 +4976,7 @@ mod tests {
                 5,
                 SearchBlendMode::Hybrid(0.6),
                 &[],
+                &SymbolFilter::default(),
                 "model".to_string(),
                 false,
             )
@@ -4486,6 +5000,7 @@ mod tests {
                 5,
                 SearchBlendMode::Hybrid(0.6),
                 &[],
+                &SymbolFilter::default(),
                 "model".to_string(),
                 true,
             )
@@ -4518,6 +5033,7 @@ mod tests {
                 5,
                 SearchBlendMode::Hybrid(0.5),
                 &[],
+                &SymbolFilter::default(),
                 "model".to_string(),
                 true,
             )
@@ -4589,6 +5105,7 @@ mod tests {
                 1,
                 SearchBlendMode::VectorOnly,
                 &[],
+                &SymbolFilter::default(),
                 "model".to_string(),
                 false,
             )
@@ -4677,6 +5194,7 @@ mod tests {
                 1,
                 SearchBlendMode::VectorOnly,
                 &["src/*.rs".to_string()],
+                &SymbolFilter::default(),
                 "model".to_string(),
                 false,
             )
@@ -4814,6 +5332,7 @@ mod tests {
                 1,
                 SearchBlendMode::LexicalOnly,
                 &[],
+                &SymbolFilter::default(),
                 "model".to_string(),
                 false,
             )
@@ -5008,6 +5527,7 @@ mod tests {
                 1,
                 SearchBlendMode::VectorOnly,
                 &[],
+                &SymbolFilter::default(),
                 "model".to_string(),
                 false,
             )
@@ -5065,6 +5585,7 @@ mod tests {
         let index_config = QueryProjectIndex {
             backend: QueryProjectIndexBackend::Qdrant,
             auto_warm: true,
+            auto_update: true,
             require_embeddings: true,
             embedding_model: Some(embedding_model.to_string()),
             file_globs: vec![],
@@ -5154,6 +5675,7 @@ mod tests {
                 5,
                 SearchBlendMode::Hybrid(0.6),
                 &[],
+                &SymbolFilter::default(),
                 embedding_model.to_string(),
                 true,
             )
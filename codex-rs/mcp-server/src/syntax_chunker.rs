@@ -0,0 +1,510 @@
+//! Syntax-aware chunk boundaries for the `query_project` index.
+//!
+//! Files in a supported language are parsed with tree-sitter and split on
+//! definition boundaries (functions, methods, impls, classes, modules and
+//! type declarations). Each returned span carries the name and kind of the
+//! definition it belongs to. Lines outside any definition are left for the
+//! caller to chunk by line windows.
+
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde::Serialize;
+use tree_sitter::Language;
+use tree_sitter::Node;
+use tree_sitter::Parser;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum SymbolKind {
+    Function,
+    Method,
+    Class,
+    Impl,
+    Trait,
+    Module,
+    Type,
+}
+
+impl SymbolKind {
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            Self::Function => "function",
+            Self::Method => "method",
+            Self::Class => "class",
+            Self::Impl => "impl",
+            Self::Trait => "trait",
+            Self::Module => "module",
+            Self::Type => "type",
+        }
+    }
+
+    pub(crate) fn parse(value: &str) -> Option<Self> {
+        match value {
+            "function" => Some(Self::Function),
+            "method" => Some(Self::Method),
+            "class" => Some(Self::Class),
+            "impl" => Some(Self::Impl),
+            "trait" => Some(Self::Trait),
+            "module" => Some(Self::Module),
+            "type" => Some(Self::Type),
+            _ => None,
+        }
+    }
+
+    /// Functions nested in these containers are reported as methods.
+    fn owns_methods(self) -> bool {
+        matches!(self, Self::Class | Self::Impl | Self::Trait)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) struct ChunkSymbol {
+    pub name: String,
+    pub kind: SymbolKind,
+}
+
+/// A 1-based, inclusive line range attributed to a single definition.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct SymbolSpan {
+    pub start_line: usize,
+    pub end_line: usize,
+    pub symbol: ChunkSymbol,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SyntaxLanguage {
+    Rust,
+    Python,
+    JavaScript,
+    TypeScript,
+    Tsx,
+    Go,
+    Bash,
+}
+
+impl SyntaxLanguage {
+    fn from_path(path: &str) -> Option<Self> {
+        let extension = path.rsplit_once('.')?.1.to_ascii_lowercase();
+        match extension.as_str() {
+            "rs" => Some(Self::Rust),
+            "py" | "pyi" => Some(Self::Python),
+            "js" | "jsx" | "mjs" | "cjs" => Some(Self::JavaScript),
+            "ts" | "mts" | "cts" => Some(Self::TypeScript),
+            "tsx" => Some(Self::Tsx),
+            "go" => Some(Self::Go),
+            "sh" | "bash" => Some(Self::Bash),
+            _ => None,
+        }
+    }
+
+    fn grammar(self) -> Language {
+        match self {
+            Self::Rust => tree_sitter_rust::LANGUAGE.into(),
+            Self::Python => tree_sitter_python::LANGUAGE.into(),
+            Self::JavaScript => tree_sitter_javascript::LANGUAGE.into(),
+            Self::TypeScript => tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into(),
+            Self::Tsx => tree_sitter_typescript::LANGUAGE_TSX.into(),
+            Self::Go => tree_sitter_go::LANGUAGE.into(),
+            Self::Bash => tree_sitter_bash::LANGUAGE.into(),
+        }
+    }
+}
+
+/// A definition recognized at some node, plus the node whose children hold
+/// its members (for containers such as impls and classes).
+struct Definition<'tree> {
+    symbol: ChunkSymbol,
+    body: Option<Node<'tree>>,
+}
+
+/// Returns definition spans for `file_text`, sorted and non-overlapping, or
+/// `None` when the language is unsupported or the file fails to parse.
+pub(crate) fn symbol_spans(path: &str, file_text: &str) -> Option<Vec<SymbolSpan>> {
+    let language = SyntaxLanguage::from_path(path)?;
+    let mut parser = Parser::new();
+    parser.set_language(&language.grammar()).ok()?;
+    let tree = parser.parse(file_text, None)?;
+    let lines = file_text.lines().collect::<Vec<_>>();
+    let mut spans = Vec::new();
+    collect_spans(
+        language,
+        tree.root_node(),
+        file_text.as_bytes(),
+        &lines,
+        None,
+        &mut spans,
+    );
+    Some(spans)
+}
+
+fn collect_spans(
+    language: SyntaxLanguage,
+    parent: Node<'_>,
+    source: &[u8],
+    lines: &[&str],
+    container: Option<SymbolKind>,
+    out: &mut Vec<SymbolSpan>,
+) {
+    let mut cursor = parent.walk();
+    let children = parent.named_children(&mut cursor).collect::<Vec<_>>();
+    for child in children {
+        let Some(definition) = describe(language, child, source, container) else {
+            continue;
+        };
+        let Some((start_line, end_line)) = line_span(child, lines.len()) else {
+            continue;
+        };
+        let mut members = Vec::new();
+        if let Some(body) = definition.body {
+            collect_spans(
+                language,
+                body,
+                source,
+                lines,
+                Some(definition.symbol.kind),
+                &mut members,
+            );
+        }
+        if members.is_empty() {
+            out.push(SymbolSpan {
+                start_line,
+                end_line,
+                symbol: definition.symbol,
+            });
+            continue;
+        }
+
+        // Lines of the container not covered by a member (the header, fields,
+        // the closing brace) stay attributed to the container itself.
+        let mut next_line = start_line;
+        for member in members {
+            push_container_gap(
+                next_line,
+                member.start_line.saturating_sub(1),
+                &definition.symbol,
+                lines,
+                out,
+            );
+            next_line = member.end_line + 1;
+            out.push(member);
+        }
+        push_container_gap(next_line, end_line, &definition.symbol, lines, out);
+    }
+}
+
+fn push_container_gap(
+    start_line: usize,
+    end_line: usize,
+    symbol: &ChunkSymbol,
+    lines: &[&str],
+    out: &mut Vec<SymbolSpan>,
+) {
+    if start_line > end_line || !has_meaningful_text(&lines[start_line - 1..end_line]) {
+        return;
+    }
+    out.push(SymbolSpan {
+        start_line,
+        end_line,
+        symbol: symbol.clone(),
+    });
+}
+
+/// True when the lines contain more than whitespace and punctuation, so a
+/// lone closing brace does not become its own chunk.
+pub(crate) fn has_meaningful_text(lines: &[&str]) -> bool {
+    lines
+        .iter()
+        .any(|line| line.chars().any(char::is_alphanumeric))
+}
+
+/// Converts a node to a 1-based inclusive line range, extending it upward
+/// over directly attached doc comments, attributes and decorators.
+fn line_span(node: Node<'_>, line_count: usize) -> Option<(usize, usize)> {
+    if line_count == 0 {
+        return None;
+    }
+    let mut start_row = node.start_position().row;
+    let mut previous = node.prev_named_sibling();
+    while let Some(sibling) = previous {
+        let kind = sibling.kind();
+        let attached = kind.contains("comment") || kind == "attribute_item" || kind == "decorator";
+        // A trailing comment such as `} // end` belongs to the previous item.
+        let starts_own_line = sibling
+            .prev_sibling()
+            .is_none_or(|prior| prior.end_position().row < sibling.start_position().row);
+        if !attached || !starts_own_line || sibling.end_position().row + 1 < start_row {
+            break;
+        }
+        start_row = sibling.start_position().row;
+        previous = sibling.prev_named_sibling();
+    }
+
+    let end = node.end_position();
+    let mut end_row = end.row;
+    if end.column == 0 && end_row > start_row {
+        end_row -= 1;
+    }
+    let start_line = (start_row + 1).min(line_count);
+    let end_line = (end_row + 1).clamp(start_line, line_count);
+    Some((start_line, end_line))
+}
+
+fn describe<'tree>(
+    language: SyntaxLanguage,
+    node: Node<'tree>,
+    source: &[u8],
+    container: Option<SymbolKind>,
+) -> Option<Definition<'tree>> {
+    let function_kind = if container.is_some_and(SymbolKind::owns_methods) {
+        SymbolKind::Method
+    } else {
+        SymbolKind::Function
+    };
+    match language {
+        SyntaxLanguage::Rust => match node.kind() {
+            "function_item" | "function_signature_item" => {
+                leaf(node, "name", function_kind, source)
+            }
+            "impl_item" => container_definition(node, "type", SymbolKind::Impl, source),
+            "trait_item" => container_definition(node, "name", SymbolKind::Trait, source),
+            "mod_item" => container_definition(node, "name", SymbolKind::Module, source),
+            "struct_item" | "enum_item" | "union_item" | "type_item" => {
+                leaf(node, "name", SymbolKind::Type, source)
+            }
+            _ => None,
+        },
+        SyntaxLanguage::Python => match node.kind() {
+            "function_definition" => leaf(node, "name", function_kind, source),
+            "class_definition" => container_definition(node, "name", SymbolKind::Class, source),
+            "decorated_definition" => describe(
+                language,
+                node.child_by_field_name("definition")?,
+                source,
+                container,
+            ),
+            _ => None,
+        },
+        SyntaxLanguage::JavaScript | SyntaxLanguage::TypeScript | SyntaxLanguage::Tsx => {
+            match node.kind() {
+                "function_declaration" | "generator_function_declaration" => {
+                    leaf(node, "name", function_kind, source)
+                }
+                "method_definition" | "abstract_method_signature" => {
+                    leaf(node, "name", SymbolKind::Method, source)
+                }
+                "class_declaration" | "abstract_class_declaration" | "class" => {
+                    container_definition(node, "name", SymbolKind::Class, source)
+                }
+                "interface_declaration" | "type_alias_declaration" | "enum_declaration" => {
+                    leaf(node, "name", SymbolKind::Type, source)
+                }
+                "internal_module" | "module" => {
+                    container_definition(node, "name", SymbolKind::Module, source)
+                }
+                "export_statement" => describe(
+                    language,
+                    node.child_by_field_name("declaration")?,
+                    source,
+                    container,
+                ),
+                "lexical_declaration" | "variable_declaration" => {
+                    function_variable(node, function_kind, source)
+                }
+                _ => None,
+            }
+        }
+        SyntaxLanguage::Go => match node.kind() {
+            "function_declaration" => leaf(node, "name", SymbolKind::Function, source),
+            "method_declaration" => leaf(node, "name", SymbolKind::Method, source),
+            "type_declaration" => {
+                let mut cursor = node.walk();
+                let spec = node
+                    .named_children(&mut cursor)
+                    .find(|child| matches!(child.kind(), "type_spec" | "type_alias"))?;
+                Some(Definition {
+                    symbol: ChunkSymbol {
+                        name: field_text(spec, "name", source)?,
+                        kind: SymbolKind::Type,
+                    },
+                    body: None,
+                })
+            }
+            _ => None,
+        },
+        SyntaxLanguage::Bash => match node.kind() {
+            "function_definition" => leaf(node, "name", SymbolKind::Function, source),
+            _ => None,
+        },
+    }
+}
+
+fn leaf<'tree>(
+    node: Node<'tree>,
+    name_field: &str,
+    kind: SymbolKind,
+    source: &[u8],
+) -> Option<Definition<'tree>> {
+    Some(Definition {
+        symbol: ChunkSymbol {
+            name: field_text(node, name_field, source)?,
+            kind,
+        },
+        body: None,
+    })
+}
+
+fn container_definition<'tree>(
+    node: Node<'tree>,
+    name_field: &str,
+    kind: SymbolKind,
+    source: &[u8],
+) -> Option<Definition<'tree>> {
+    Some(Definition {
+        symbol: ChunkSymbol {
+            name: field_text(node, name_field, source)?,
+            kind,
+        },
+        body: node.child_by_field_name("body"),
+    })
+}
+
+/// `const handler = (req) => {...}` and `let f = function () {...}`.
+fn function_variable<'tree>(
+    node: Node<'tree>,
+    kind: SymbolKind,
+    source: &[u8],
+) -> Option<Definition<'tree>> {
+    let mut cursor = node.walk();
+    let mut declarators = node
+        .named_children(&mut cursor)
+        .filter(|child| child.kind() == "variable_declarator");
+    let declarator = declarators.next()?;
+    if declarators.next().is_some() {
+        return None;
+    }
+    let value = declarator.child_by_field_name("value")?;
+    if !matches!(
+        value.kind(),
+        "arrow_function" | "function_expression" | "function" | "generator_function"
+    ) {
+        return None;
+    }
+    leaf(declarator, "name", kind, source)
+}
+
+fn field_text(node: Node<'_>, field: &str, source: &[u8]) -> Option<String> {
+    let text = node.child_by_field_name(field)?.utf8_text(source).ok()?;
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    (!text.is_empty()).then_some(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn summarize(spans: &[SymbolSpan]) -> Vec<(usize, usize, &str, SymbolKind)> {
+        spans
+            .iter()
+            .map(|span| {
+                (
+                    span.start_line,
+                    span.end_line,
+                    span.symbol.name.as_str(),
+                    span.symbol.kind,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn rust_splits_impl_into_methods_and_keeps_doc_comments() {
+        let source = "\
+use std::fmt;
+
+/// A point.
+struct Point {
+    x: i32,
+}
+
+impl Point {
+    /// Creates a point.
+    fn new(x: i32) -> Self {
+        Self { x }
+    }
+
+    fn x(&self) -> i32 {
+        self.x
+    }
+}
+
+fn main() {}
+";
+        let spans = symbol_spans("src/point.rs", source).expect("rust is supported");
+        assert_eq!(
+            summarize(&spans),
+            vec![
+                (3, 6, "Point", SymbolKind::Type),
+                (8, 8, "Point", SymbolKind::Impl),
+                (9, 12, "new", SymbolKind::Method),
+                (14, 16, "x", SymbolKind::Method),
+                (19, 19, "main", SymbolKind::Function),
+            ]
+        );
+    }
+
+    #[test]
+    fn python_reports_decorated_methods_inside_classes() {
+        let source = "\
+import os
+
+
+class Loader:
+    \"\"\"Loads things.\"\"\"
+
+    @staticmethod
+    def load(path):
+        return open(path).read()
+
+
+def helper():
+    pass
+";
+        let spans = symbol_spans("pkg/loader.py", source).expect("python is supported");
+        assert_eq!(
+            summarize(&spans),
+            vec![
+                (4, 6, "Loader", SymbolKind::Class),
+                (7, 9, "load", SymbolKind::Method),
+                (12, 13, "helper", SymbolKind::Function),
+            ]
+        );
+    }
+
+    #[test]
+    fn typescript_handles_exports_and_arrow_functions() {
+        let source = "\
+export interface Options {
+  verbose: boolean;
+}
+
+export const run = (options: Options) => {
+  return options.verbose;
+};
+";
+        let spans = symbol_spans("src/run.ts", source).expect("typescript is supported");
+        assert_eq!(
+            summarize(&spans),
+            vec![
+                (1, 3, "Options", SymbolKind::Type),
+                (5, 7, "run", SymbolKind::Function),
+            ]
+        );
+    }
+
+    #[test]
+    fn unsupported_extensions_return_none() {
+        assert_eq!(symbol_spans("README.md", "# Title\n"), None);
+    }
+}
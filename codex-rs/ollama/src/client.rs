@@ -74,6 +74,21 @@ impl OllamaClient {
         Ok(client)
     }
 
+    /// Build a client from a provider base URL without probing the server.
+    /// Both native (`http://host:11434`) and OpenAI-compatible
+    /// (`http://host:11434/v1`) base URLs are accepted.
+    pub fn from_base_url(base_url: &str) -> Self {
+        let client = reqwest::Client::builder()
+            .connect_timeout(std::time::Duration::from_secs(5))
+            .build()
+            .unwrap_or_else(|_| reqwest::Client::new());
+        Self {
+            client,
+            host_root: base_url_to_host_root(base_url),
+            uses_openai_compat: is_openai_compatible_base_url(base_url),
+        }
+    }
+
     /// Probe whether the server is reachable by hitting the appropriate health endpoint.
     async fn probe_server(&self) -> io::Result<()> {
         let url = if self.uses_openai_compat {
@@ -149,6 +164,46 @@ impl OllamaClient {
         }
     }
 
+    /// Compute an embedding for `prompt` via the native `/api/embeddings` endpoint.
+    pub async fn embed(&self, model: &str, prompt: &str) -> io::Result<Vec<f32>> {
+        let url = format!("{}/api/embeddings", self.host_root.trim_end_matches('/'));
+        let resp = self
+            .client
+            .post(url)
+            .json(&serde_json::json!({"model": model, "prompt": prompt}))
+            .send()
+            .await
+            .map_err(|err| {
+                tracing::warn!("Failed to connect to Ollama server: {err:?}");
+                io::Error::other(OLLAMA_CONNECTION_ERROR)
+            })?;
+        let status = resp.status();
+        if !status.is_success() {
+            let body = resp.text().await.unwrap_or_default();
+            return Err(io::Error::other(format!(
+                "Ollama embeddings request failed: HTTP {status}: {body}"
+            )));
+        }
+        let val = resp.json::<JsonValue>().await.map_err(io::Error::other)?;
+        let embedding = val
+            .get("embedding")
+            .and_then(|v| v.as_array())
+            .ok_or_else(|| io::Error::other("Ollama embeddings response missing `embedding`"))?
+            .iter()
+            .map(|v| {
+                v.as_f64()
+                    .map(|value| value as f32)
+                    .ok_or_else(|| io::Error::other("Ollama embedding contains a non-number"))
+            })
+            .collect::<io::Result<Vec<f32>>>()?;
+        if embedding.is_empty() {
+            return Err(io::Error::other(format!(
+                "Ollama returned an empty embedding; is `{model}` an embedding model?"
+            )));
+        }
+        Ok(embedding)
+    }
+
     /// Start a model pull and emit streaming events. The returned stream ends when
     /// a Success event is observed or the server closes the connection.
     pub async fn pull_model_stream(
@@ -294,6 +349,40 @@ mod tests {
         assert!(models.contains(&"mistral".to_string()));
     }
 
+    #[tokio::test]
+    async fn test_embed_posts_to_native_embeddings_endpoint() {
+        if std::env::var(codex_core::spawn::CODEX_SANDBOX_NETWORK_DISABLED_ENV_VAR).is_ok() {
+            tracing::info!(
+                "{} is set; skipping test_embed_posts_to_native_embeddings_endpoint",
+                codex_core::spawn::CODEX_SANDBOX_NETWORK_DISABLED_ENV_VAR
+            );
+            return;
+        }
+
+        let server = wiremock::MockServer::start().await;
+        wiremock::Mock::given(wiremock::matchers::method("POST"))
+            .and(wiremock::matchers::path("/api/embeddings"))
+            .and(wiremock::matchers::body_json(serde_json::json!({
+                "model": "nomic-embed-text",
+                "prompt": "fn main() {}",
+            })))
+            .respond_with(
+                wiremock::ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({ "embedding": [0.5, -0.25] })),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        // OpenAI-compatible base URLs still use the native endpoint.
+        let client = OllamaClient::from_base_url(&format!("{}/v1", server.uri()));
+        let embedding = client
+            .embed("nomic-embed-text", "fn main() {}")
+            .await
+            .expect("embed");
+        assert_eq!(embedding, vec![0.5, -0.25]);
+    }
+
     #[tokio::test]
     async fn test_fetch_version() {
         if std::env::var(codex_core::spawn::CODEX_SANDBOX_NETWORK_DISABLED_ENV_VAR).is_ok() {
@@ -255,6 +255,46 @@ pub enum ElicitationAction {
     Cancel,
 }
 
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
+#[serde(rename_all = "lowercase")]
+pub enum McpSamplingRole {
+    User,
+    Assistant,
+}
+
+/// Text of one message in an MCP `sampling/createMessage` request.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
+pub struct McpSamplingMessage {
+    pub role: McpSamplingRole,
+    pub text: String,
+}
+
+/// An MCP server asked Codex to run a model completion on its behalf.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
+pub struct McpSamplingRequestEvent {
+    /// Turn ID that this request belongs to, when known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub turn_id: Option<String>,
+    pub server_name: String,
+    #[ts(type = "string | number")]
+    pub id: RequestId,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub system_prompt: Option<String>,
+    pub messages: Vec<McpSamplingMessage>,
+    /// Output token cap for the completion, after applying the server's
+    /// configured limits.
+    pub max_tokens: u32,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
+#[serde(rename_all = "lowercase")]
+pub enum McpSamplingDecision {
+    Approve,
+    Decline,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
 pub struct ApplyPatchApprovalRequestEvent {
     /// Responses API call id for the associated patch apply call, if available.
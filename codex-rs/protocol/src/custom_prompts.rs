@@ -17,4 +17,14 @@ pub struct CustomPrompt {
     pub content: String,
     pub description: Option<String>,
     pub argument_hint: Option<String>,
+    /// Named arguments declared in the prompt's front matter.
+    #[serde(default)]
+    pub arguments: Vec<CustomPromptArgument>,
+}
+
+/// A named argument (`$NAME`) declared by a custom prompt.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema, TS)]
+pub struct CustomPromptArgument {
+    pub name: String,
+    pub required: bool,
 }
@@ -1,10 +1,14 @@
 use std::collections::HashSet;
+use std::collections::VecDeque;
 use std::ffi::OsStr;
 use std::io;
 use std::path::Path;
 use std::path::PathBuf;
 
 use codex_utils_absolute_path::AbsolutePathBuf;
+use globset::GlobBuilder;
+use globset::GlobSet;
+use globset::GlobSetBuilder;
 use schemars::JsonSchema;
 use serde::Deserialize;
 use serde::Serialize;
@@ -34,6 +38,61 @@ impl NetworkSandboxPolicy {
     }
 }
 
+/// Per-command resource limits enforced by the Linux sandbox helper.
+///
+/// Unset fields are unlimited.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResourceLimits {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memory_max_bytes: Option<u64>,
+    /// CPU quota as a percentage of one core, so `200` allows two full cores.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cpu_max_percent: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pids_max: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wall_time_seconds: Option<u64>,
+}
+
+impl ResourceLimits {
+    pub fn is_empty(&self) -> bool {
+        self.memory_max_bytes.is_none()
+            && self.cpu_max_percent.is_none()
+            && self.pids_max.is_none()
+            && self.wall_time_seconds.is_none()
+    }
+
+    /// Combines two sets of limits, keeping the stricter value for each
+    /// resource.
+    pub fn tightened_by(self, other: ResourceLimits) -> Self {
+        fn stricter(left: Option<u64>, right: Option<u64>) -> Option<u64> {
+            match (left, right) {
+                (Some(left), Some(right)) => Some(left.min(right)),
+                (left, right) => left.or(right),
+            }
+        }
+
+        Self {
+            memory_max_bytes: stricter(self.memory_max_bytes, other.memory_max_bytes),
+            cpu_max_percent: stricter(self.cpu_max_percent, other.cpu_max_percent),
+            pids_max: stricter(self.pids_max, other.pids_max),
+            wall_time_seconds: stricter(self.wall_time_seconds, other.wall_time_seconds),
+        }
+    }
+}
+
+/// Host directories backing a copy-on-write workspace.
+///
+/// The Linux sandbox mounts `lower` as an overlay so that commands see the
+/// real workspace while their writes land in `upper`. `work` is the scratch
+/// directory overlayfs requires on the same filesystem as `upper`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OverlayWorkspaceLayers {
+    pub lower: PathBuf,
+    pub upper: PathBuf,
+    pub work: PathBuf,
+}
+
 /// Access mode for a filesystem entry.
 ///
 /// When two equally specific entries target the same path, we compare these by
@@ -153,14 +212,25 @@ struct FileSystemSemanticSignature {
     readable_roots: Vec<AbsolutePathBuf>,
     writable_roots: Vec<WritableRoot>,
     unreadable_roots: Vec<AbsolutePathBuf>,
+    unreadable_globs: Vec<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema, TS)]
 #[serde(tag = "type", rename_all = "snake_case")]
 #[ts(tag = "type")]
 pub enum FileSystemPath {
-    Path { path: AbsolutePathBuf },
-    Special { value: FileSystemSpecialPath },
+    Path {
+        path: AbsolutePathBuf,
+    },
+    Special {
+        value: FileSystemSpecialPath,
+    },
+    /// Glob such as `**/.env*` or `/srv/**/*.pem`. Relative patterns are
+    /// resolved against the cwd. Only `none` access is supported, and a
+    /// matching glob denies the path regardless of other entries.
+    Glob {
+        pattern: String,
+    },
 }
 
 impl Default for FileSystemSandboxPolicy {
@@ -213,6 +283,7 @@ impl FileSystemSandboxPolicy {
 
                 match &entry.path {
                     FileSystemPath::Path { .. } => !self.has_same_target_write_override(entry),
+                    FileSystemPath::Glob { .. } => true,
                     FileSystemPath::Special { value } => match value {
                         FileSystemSpecialPath::Root => entry.access == FileSystemAccessMode::None,
                         FileSystemSpecialPath::Minimal | FileSystemSpecialPath::Unknown { .. } => {
@@ -275,7 +346,7 @@ impl FileSystemSandboxPolicy {
                     FileSystemPath::Path { path } => !legacy_writable_roots
                         .iter()
                         .any(|root| root.is_path_writable(path.as_path())),
-                    FileSystemPath::Special { .. } => true,
+                    FileSystemPath::Special { .. } | FileSystemPath::Glob { .. } => true,
                 }
             });
         }
@@ -320,23 +391,35 @@ impl FileSystemSandboxPolicy {
     }
 
     pub fn resolve_access_with_cwd(&self, path: &Path, cwd: &Path) -> FileSystemAccessMode {
-        match self.kind {
-            FileSystemSandboxKind::Unrestricted | FileSystemSandboxKind::ExternalSandbox => {
-                return FileSystemAccessMode::Write;
-            }
-            FileSystemSandboxKind::Restricted => {}
+        self.access_resolver_with_cwd(cwd).resolve(path)
+    }
+
+    /// Resolves entries and compiles glob entries once so callers checking
+    /// many paths, such as directory listings, do not redo that work per path.
+    pub fn access_resolver_with_cwd(&self, cwd: &Path) -> FileSystemAccessResolver {
+        FileSystemAccessResolver {
+            kind: self.kind,
+            cwd: cwd.to_path_buf(),
+            entries: self.resolved_entries_with_cwd(cwd),
+            glob_denies: self.glob_denies_with_cwd(cwd),
         }
+    }
 
-        let Some(path) = resolve_candidate_path(path, cwd) else {
-            return FileSystemAccessMode::None;
-        };
+    /// Returns the glob entries anchored at the provided cwd.
+    pub fn glob_denies_with_cwd(&self, cwd: &Path) -> FileSystemGlobDenies {
+        if !matches!(self.kind, FileSystemSandboxKind::Restricted) {
+            return FileSystemGlobDenies::default();
+        }
 
-        self.resolved_entries_with_cwd(cwd)
-            .into_iter()
-            .filter(|entry| path.as_path().starts_with(entry.path.as_path()))
-            .max_by_key(resolved_entry_precedence)
-            .map(|entry| entry.access)
-            .unwrap_or(FileSystemAccessMode::None)
+        FileSystemGlobDenies::new(
+            self.entries
+                .iter()
+                .filter_map(|entry| match &entry.path {
+                    FileSystemPath::Glob { pattern } => anchor_glob_pattern(pattern, cwd),
+                    FileSystemPath::Path { .. } | FileSystemPath::Special { .. } => None,
+                })
+                .collect(),
+        )
     }
 
     pub fn can_read_path_with_cwd(&self, path: &Path, cwd: &Path) -> bool {
@@ -562,6 +645,10 @@ impl FileSystemSandboxPolicy {
                             }
                             FileSystemSpecialPath::Unknown { .. } => {}
                         },
+                        // Legacy policies cannot express globs. The semantic
+                        // signature includes them, so such policies always
+                        // need direct runtime enforcement.
+                        FileSystemPath::Glob { .. } => {}
                     }
                 }
 
@@ -630,8 +717,266 @@ impl FileSystemSandboxPolicy {
             readable_roots: self.get_readable_roots_with_cwd(cwd),
             writable_roots: self.get_writable_roots_with_cwd(cwd),
             unreadable_roots: self.get_unreadable_roots_with_cwd(cwd),
+            unreadable_globs: self.glob_denies_with_cwd(cwd).patterns().to_vec(),
+        }
+    }
+}
+
+/// Answers access queries for a [`FileSystemSandboxPolicy`] at a fixed cwd.
+#[derive(Debug, Clone)]
+pub struct FileSystemAccessResolver {
+    kind: FileSystemSandboxKind,
+    cwd: PathBuf,
+    entries: Vec<ResolvedFileSystemEntry>,
+    glob_denies: FileSystemGlobDenies,
+}
+
+impl FileSystemAccessResolver {
+    pub fn resolve(&self, path: &Path) -> FileSystemAccessMode {
+        match self.kind {
+            FileSystemSandboxKind::Unrestricted | FileSystemSandboxKind::ExternalSandbox => {
+                return FileSystemAccessMode::Write;
+            }
+            FileSystemSandboxKind::Restricted => {}
+        }
+
+        let Some(path) = resolve_candidate_path(path, &self.cwd) else {
+            return FileSystemAccessMode::None;
+        };
+        if self.glob_denies.matches_path_or_ancestor(path.as_path()) {
+            return FileSystemAccessMode::None;
+        }
+
+        self.most_specific_entry(&path)
+            .map(|entry| entry.access)
+            .unwrap_or(FileSystemAccessMode::None)
+    }
+
+    /// Returns true when a glob or an explicit `none` entry denies reading
+    /// `path`.
+    ///
+    /// Unlike [`Self::resolve`], paths outside every entry are not reported
+    /// as denied: platform defaults such as `:minimal` are not modelled as
+    /// entries, so "not granted" cannot be told apart from "readable by
+    /// default" here.
+    pub fn is_read_denied(&self, path: &Path) -> bool {
+        if !matches!(self.kind, FileSystemSandboxKind::Restricted) {
+            return false;
+        }
+
+        let Some(path) = resolve_candidate_path(path, &self.cwd) else {
+            return false;
+        };
+        self.glob_denies.matches_path_or_ancestor(path.as_path())
+            || self
+                .most_specific_entry(&path)
+                .is_some_and(|entry| !entry.access.can_read())
+    }
+
+    fn most_specific_entry(&self, path: &AbsolutePathBuf) -> Option<&ResolvedFileSystemEntry> {
+        self.entries
+            .iter()
+            .filter(|entry| path.as_path().starts_with(entry.path.as_path()))
+            .max_by_key(|entry| resolved_entry_precedence(entry))
+    }
+}
+
+/// Compiled glob entries of a [`FileSystemSandboxPolicy`], anchored at a cwd.
+///
+/// Sandboxes that cannot match globs at runtime expand these into concrete
+/// paths when a command is spawned.
+#[derive(Debug, Clone, Default)]
+pub struct FileSystemGlobDenies {
+    patterns: Vec<String>,
+    search_roots: Vec<AbsolutePathBuf>,
+    set: Option<GlobSet>,
+}
+
+impl FileSystemGlobDenies {
+    fn new(anchored: Vec<AnchoredGlob>) -> Self {
+        let mut builder = GlobSetBuilder::new();
+        let mut valid_patterns = Vec::with_capacity(anchored.len());
+        let mut search_roots = Vec::with_capacity(anchored.len());
+        for AnchoredGlob {
+            pattern,
+            search_root,
+        } in anchored
+        {
+            match GlobBuilder::new(&pattern).literal_separator(true).build() {
+                Ok(glob) => {
+                    builder.add(glob);
+                    valid_patterns.push(pattern);
+                    search_roots.push(search_root);
+                }
+                // Config loading validates globs, so this only drops patterns
+                // from hand-built policies.
+                Err(err) => error!("ignoring invalid filesystem glob `{pattern}`: {err}"),
+            }
+        }
+        let set = if valid_patterns.is_empty() {
+            None
+        } else {
+            builder
+                .build()
+                .inspect_err(|err| error!("failed to compile filesystem globs: {err}"))
+                .ok()
+        };
+        Self {
+            patterns: valid_patterns,
+            search_roots: dedup_absolute_paths(search_roots),
+            set,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.set.is_none()
+    }
+
+    /// Absolute glob patterns, with the cwd prefix escaped.
+    pub fn patterns(&self) -> &[String] {
+        &self.patterns
+    }
+
+    /// Returns true when `path` itself matches one of the globs.
+    pub fn matches(&self, path: &Path) -> bool {
+        self.set.as_ref().is_some_and(|set| set.is_match(path))
+    }
+
+    /// Returns true when `path` or a directory containing it matches one of
+    /// the globs, so `**/secrets` also denies everything below `secrets/`.
+    pub fn matches_path_or_ancestor(&self, path: &Path) -> bool {
+        self.set.as_ref().is_some_and(|set| {
+            path.ancestors()
+                .take_while(|ancestor| ancestor.parent().is_some())
+                .any(|ancestor| set.is_match(ancestor))
+        })
+    }
+
+    /// Returns the directories that can contain matches, i.e. the literal
+    /// prefix of each pattern before its first wildcard component.
+    pub fn search_roots(&self) -> &[AbsolutePathBuf] {
+        &self.search_roots
+    }
+
+    /// Walks the search roots and returns the existing paths that match.
+    ///
+    /// Matching directories are not descended into because masking them
+    /// already hides their contents, and symlinked directories are not
+    /// followed. Dependency and VCS directories such as `node_modules` or
+    /// `.git` are skipped unless a pattern names them. The walk stops after
+    /// [`GLOB_DENY_MAX_VISITED_ENTRIES`] entries and records where it stopped.
+    pub fn expand_existing_matches(&self) -> GlobDenyMatches {
+        let mut expanded = GlobDenyMatches::default();
+        if self.is_empty() {
+            return expanded;
+        }
+
+        let descended_skip_dirs: Vec<&str> = GLOB_DENY_SKIPPED_DIRS
+            .iter()
+            .copied()
+            .filter(|name| {
+                self.patterns
+                    .iter()
+                    .any(|pattern| pattern.split('/').any(|component| component == *name))
+            })
+            .collect();
+        let mut visited_dirs = HashSet::new();
+        let mut visited_entries = 0usize;
+        let mut queue: VecDeque<PathBuf> = self
+            .search_roots
+            .iter()
+            .map(|root| root.as_path().to_path_buf())
+            .collect();
+        while let Some(dir) = queue.pop_front() {
+            if !visited_dirs.insert(dir.clone()) {
+                continue;
+            }
+            let Ok(read_dir) = std::fs::read_dir(&dir) else {
+                continue;
+            };
+            for entry in read_dir.flatten() {
+                visited_entries += 1;
+                if visited_entries > GLOB_DENY_MAX_VISITED_ENTRIES {
+                    expanded.truncated_under = AbsolutePathBuf::from_absolute_path(&dir).ok();
+                    return expanded;
+                }
+                let path = entry.path();
+                if self.matches(&path) {
+                    if let Ok(path) = AbsolutePathBuf::from_absolute_path(&path) {
+                        expanded.paths.push(path);
+                    }
+                    continue;
+                }
+                if !entry.file_type().is_ok_and(|file_type| file_type.is_dir()) {
+                    continue;
+                }
+                let skipped = entry.file_name().to_str().is_some_and(|name| {
+                    GLOB_DENY_SKIPPED_DIRS.contains(&name) && !descended_skip_dirs.contains(&name)
+                });
+                if !skipped {
+                    queue.push_back(path);
+                }
+            }
         }
+        expanded
+    }
+}
+
+/// Upper bound on directory entries visited while expanding glob denies, so a
+/// pattern over a huge tree does not stall every command.
+pub const GLOB_DENY_MAX_VISITED_ENTRIES: usize = 200_000;
+
+/// Directory names that glob deny expansion does not descend into unless a
+/// pattern names them, because they are large and rarely hold user secrets.
+const GLOB_DENY_SKIPPED_DIRS: &[&str] = &[
+    ".git",
+    ".hg",
+    ".svn",
+    ".venv",
+    "__pycache__",
+    "node_modules",
+    "target",
+];
+
+/// Existing paths matched by [`FileSystemGlobDenies`] when they were expanded.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GlobDenyMatches {
+    pub paths: Vec<AbsolutePathBuf>,
+    /// Directory the expansion was walking when it hit
+    /// [`GLOB_DENY_MAX_VISITED_ENTRIES`]. Matches past that point are missing
+    /// and only enforced by tools that check the globs in-process.
+    pub truncated_under: Option<AbsolutePathBuf>,
+}
+
+#[derive(Debug)]
+struct AnchoredGlob {
+    pattern: String,
+    search_root: AbsolutePathBuf,
+}
+
+/// Returns true when `path` contains glob syntax and should be compiled as a
+/// [`FileSystemPath::Glob`] entry.
+pub fn is_glob_pattern(path: &str) -> bool {
+    path.contains(['*', '?', '[', '{'])
+}
+
+fn anchor_glob_pattern(pattern: &str, cwd: &Path) -> Option<AnchoredGlob> {
+    let literal_prefix: PathBuf = Path::new(pattern)
+        .components()
+        .take_while(|component| !is_glob_pattern(&component.as_os_str().to_string_lossy()))
+        .collect();
+    if Path::new(pattern).is_absolute() {
+        return Some(AnchoredGlob {
+            pattern: pattern.to_string(),
+            search_root: AbsolutePathBuf::from_absolute_path(literal_prefix).ok()?,
+        });
     }
+
+    let escaped_cwd = globset::escape(&cwd.to_string_lossy());
+    Some(AnchoredGlob {
+        pattern: format!("{}/{pattern}", escaped_cwd.trim_end_matches('/')),
+        search_root: AbsolutePathBuf::resolve_path_against_base(literal_prefix, cwd).ok()?,
+    })
 }
 
 impl From<&SandboxPolicy> for NetworkSandboxPolicy {
@@ -766,6 +1111,7 @@ fn resolve_file_system_path(
     match path {
         FileSystemPath::Path { path } => Some(path.clone()),
         FileSystemPath::Special { value } => resolve_file_system_special_path(value, cwd),
+        FileSystemPath::Glob { .. } => None,
     }
 }
 
@@ -807,6 +1153,10 @@ fn file_system_paths_share_target(left: &FileSystemPath, right: &FileSystemPath)
         | (FileSystemPath::Special { value }, FileSystemPath::Path { path }) => {
             special_path_matches_absolute_path(value, path)
         }
+        (FileSystemPath::Glob { pattern: left }, FileSystemPath::Glob { pattern: right }) => {
+            left == right
+        }
+        (FileSystemPath::Glob { .. }, _) | (_, FileSystemPath::Glob { .. }) => false,
     }
 }
 
@@ -1040,6 +1390,33 @@ mod tests {
     use pretty_assertions::assert_eq;
     use tempfile::TempDir;
 
+    #[test]
+    fn resource_limits_keep_the_stricter_value_per_resource() {
+        let profile = ResourceLimits {
+            memory_max_bytes: Some(4 << 30),
+            cpu_max_percent: Some(200),
+            pids_max: None,
+            wall_time_seconds: Some(600),
+        };
+        let skill = ResourceLimits {
+            memory_max_bytes: Some(1 << 30),
+            cpu_max_percent: None,
+            pids_max: Some(64),
+            wall_time_seconds: Some(900),
+        };
+
+        assert_eq!(
+            profile.tightened_by(skill),
+            ResourceLimits {
+                memory_max_bytes: Some(1 << 30),
+                cpu_max_percent: Some(200),
+                pids_max: Some(64),
+                wall_time_seconds: Some(600),
+            }
+        );
+        assert!(ResourceLimits::default().is_empty());
+    }
+
     #[test]
     fn unknown_special_paths_are_ignored_by_legacy_bridge() -> std::io::Result<()> {
         let policy = FileSystemSandboxPolicy::restricted(vec![FileSystemSandboxEntry {
@@ -1120,6 +1497,158 @@ mod tests {
         );
     }
 
+    #[test]
+    fn glob_entries_deny_matches_and_their_descendants() {
+        let cwd = TempDir::new().expect("tempdir");
+        let policy = FileSystemSandboxPolicy::restricted(vec![
+            FileSystemSandboxEntry {
+                path: FileSystemPath::Special {
+                    value: FileSystemSpecialPath::Root,
+                },
+                access: FileSystemAccessMode::Read,
+            },
+            FileSystemSandboxEntry {
+                path: FileSystemPath::Special {
+                    value: FileSystemSpecialPath::CurrentWorkingDirectory,
+                },
+                access: FileSystemAccessMode::Write,
+            },
+            FileSystemSandboxEntry {
+                path: FileSystemPath::Glob {
+                    pattern: "**/.env*".to_string(),
+                },
+                access: FileSystemAccessMode::None,
+            },
+            FileSystemSandboxEntry {
+                path: FileSystemPath::Glob {
+                    pattern: "**/secrets".to_string(),
+                },
+                access: FileSystemAccessMode::None,
+            },
+        ]);
+        let resolver = policy.access_resolver_with_cwd(cwd.path());
+
+        for denied in [
+            ".env",
+            "app/.env.local",
+            "deploy/secrets",
+            "deploy/secrets/key",
+        ] {
+            let path = cwd.path().join(denied);
+            assert_eq!(
+                resolver.resolve(&path),
+                FileSystemAccessMode::None,
+                "{denied}"
+            );
+            assert!(resolver.is_read_denied(&path), "{denied}");
+        }
+        assert_eq!(
+            resolver.resolve(&cwd.path().join("app/env.rs")),
+            FileSystemAccessMode::Write
+        );
+        assert_eq!(
+            resolver.resolve(Path::new("/etc/hosts")),
+            FileSystemAccessMode::Read
+        );
+        assert!(!resolver.is_read_denied(&cwd.path().join("app/env.rs")));
+        assert!(!policy.has_full_disk_read_access());
+        assert!(
+            policy.needs_direct_runtime_enforcement(NetworkSandboxPolicy::Restricted, cwd.path())
+        );
+    }
+
+    #[test]
+    fn glob_denies_anchor_relative_patterns_at_escaped_cwd() {
+        let parent = TempDir::new().expect("tempdir");
+        let cwd = parent.path().join("repo[1]");
+        let policy = FileSystemSandboxPolicy::restricted(vec![
+            FileSystemSandboxEntry {
+                path: FileSystemPath::Glob {
+                    pattern: "config/**/*.pem".to_string(),
+                },
+                access: FileSystemAccessMode::None,
+            },
+            FileSystemSandboxEntry {
+                path: FileSystemPath::Glob {
+                    pattern: "/srv/*/id_rsa".to_string(),
+                },
+                access: FileSystemAccessMode::None,
+            },
+        ]);
+        let glob_denies = policy.glob_denies_with_cwd(&cwd);
+
+        assert_eq!(
+            glob_denies.search_roots(),
+            [
+                AbsolutePathBuf::from_absolute_path(cwd.join("config")).expect("absolute"),
+                AbsolutePathBuf::from_absolute_path("/srv").expect("absolute"),
+            ]
+        );
+        assert!(glob_denies.matches(&cwd.join("config/tls/server.pem")));
+        assert!(!glob_denies.matches(&parent.path().join("repo1/config/server.pem")));
+        assert!(glob_denies.matches(Path::new("/srv/app/id_rsa")));
+        assert!(!glob_denies.matches(Path::new("/srv/app/nested/id_rsa")));
+        assert!(
+            FileSystemSandboxPolicy::unrestricted()
+                .glob_denies_with_cwd(&cwd)
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn glob_deny_expansion_skips_dependency_dirs_unless_named() {
+        let cwd = TempDir::new().expect("tempdir");
+        for file in [
+            ".env",
+            "app/.env",
+            "node_modules/pkg/.env",
+            "target/debug/.env",
+        ] {
+            let path = cwd.path().join(file);
+            std::fs::create_dir_all(path.parent().expect("parent")).expect("create dir");
+            std::fs::write(&path, "TOKEN=1").expect("write env");
+        }
+        let glob_policy = |patterns: &[&str]| {
+            FileSystemSandboxPolicy::restricted(
+                patterns
+                    .iter()
+                    .map(|pattern| FileSystemSandboxEntry {
+                        path: FileSystemPath::Glob {
+                            pattern: pattern.to_string(),
+                        },
+                        access: FileSystemAccessMode::None,
+                    })
+                    .collect(),
+            )
+        };
+        let expanded_paths = |policy: FileSystemSandboxPolicy| {
+            let expanded = policy
+                .glob_denies_with_cwd(cwd.path())
+                .expand_existing_matches();
+            assert_eq!(expanded.truncated_under, None);
+            let mut paths: Vec<PathBuf> = expanded
+                .paths
+                .into_iter()
+                .map(AbsolutePathBuf::into_path_buf)
+                .collect();
+            paths.sort();
+            paths
+        };
+
+        assert_eq!(
+            expanded_paths(glob_policy(&["**/.env"])),
+            vec![cwd.path().join(".env"), cwd.path().join("app/.env")]
+        );
+        assert_eq!(
+            expanded_paths(glob_policy(&["**/.env", "**/node_modules/**/.env"])),
+            vec![
+                cwd.path().join(".env"),
+                cwd.path().join("app/.env"),
+                cwd.path().join("node_modules/pkg/.env"),
+            ]
+        );
+    }
+
     #[test]
     fn split_only_nested_carveouts_need_direct_runtime_enforcement() {
         let cwd = TempDir::new().expect("tempdir");
@@ -14,6 +14,8 @@ use std::time::Duration;
 
 use crate::ThreadId;
 use crate::approvals::ElicitationRequestEvent;
+use crate::approvals::McpSamplingDecision;
+use crate::approvals::McpSamplingRequestEvent;
 use crate::config_types::CollaborationMode;
 use crate::config_types::ModeKind;
 use crate::config_types::Personality;
@@ -27,6 +29,7 @@ use crate::dynamic_tools::DynamicToolResponse;
 use crate::dynamic_tools::DynamicToolSpec;
 use crate::items::TurnItem;
 use crate::mcp::CallToolResult;
+use crate::mcp::Prompt as McpPrompt;
 use crate::mcp::RequestId;
 use crate::mcp::Resource as McpResource;
 use crate::mcp::ResourceTemplate as McpResourceTemplate;
@@ -70,7 +73,10 @@ pub use crate::permissions::FileSystemSandboxEntry;
 pub use crate::permissions::FileSystemSandboxKind;
 pub use crate::permissions::FileSystemSandboxPolicy;
 pub use crate::permissions::FileSystemSpecialPath;
+pub use crate::permissions::GlobDenyMatches;
 pub use crate::permissions::NetworkSandboxPolicy;
+pub use crate::permissions::OverlayWorkspaceLayers;
+pub use crate::permissions::ResourceLimits;
 pub use crate::request_permissions::RequestPermissionsArgs;
 pub use crate::request_user_input::RequestUserInputEvent;
 
@@ -190,6 +196,12 @@ pub enum Op {
     /// Terminate all running background terminal processes for this thread.
     CleanBackgroundTerminals,
 
+    /// Start, stop or restart one of this thread's background services.
+    ControlBackgroundService {
+        name: String,
+        action: BackgroundServiceAction,
+    },
+
     /// Start a realtime conversation stream.
     RealtimeConversationStart(ConversationStartParams),
 
@@ -357,6 +369,16 @@ pub enum Op {
         meta: Option<Value>,
     },
 
+    /// Resolve an MCP sampling request.
+    ResolveMcpSampling {
+        /// Name of the MCP server that issued the request.
+        server_name: String,
+        /// Request identifier from the MCP server.
+        request_id: RequestId,
+        /// Whether Codex should run the requested completion.
+        decision: McpSamplingDecision,
+    },
+
     /// Resolve a request_user_input tool call.
     #[serde(rename = "user_input_answer", alias = "request_user_input_response")]
     UserInputAnswer {
@@ -398,6 +420,19 @@ pub enum Op {
     /// Reply is delivered via `EventMsg::McpListToolsResponse`.
     ListMcpTools,
 
+    /// Request the prompts published by MCP servers that support them.
+    /// Reply is delivered via `EventMsg::ListMcpPromptsResponse`.
+    ListMcpPrompts,
+
+    /// Fetch a prompt from an MCP server with the given arguments.
+    /// Reply is delivered via `EventMsg::GetMcpPromptResponse`.
+    GetMcpPrompt {
+        server_name: String,
+        prompt_name: String,
+        #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+        arguments: HashMap<String, String>,
+    },
+
     /// Request MCP servers to reinitialize and refresh cached tool lists.
     RefreshMcpServers { config: McpServerRefreshConfig },
 
@@ -410,6 +445,18 @@ pub enum Op {
     /// Request the list of available custom prompts.
     ListCustomPrompts,
 
+    /// Run the `{{shell: cmd}}` directives in an expanded custom prompt, each
+    /// after exec policy approval, and substitute their output.
+    /// Reply is delivered via `EventMsg::ExpandCustomPromptResponse`.
+    ExpandCustomPrompt {
+        /// Client-chosen id echoed back on the response event.
+        id: String,
+        text: String,
+        /// UI-defined spans within `text`, moved along with the substitution.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        text_elements: Vec<crate::user_input::TextElement>,
+    },
+
     /// Request the list of skills for the provided `cwd` values or the session default.
     ListSkills {
         /// Working directories to scope repo skills discovery.
@@ -480,10 +527,38 @@ pub enum Op {
         force_full: bool,
     },
 
+    /// Accept or reject pending changes in the copy-on-write workspace.
+    ResolveOverlayWorkspace {
+        decision: OverlayWorkspaceDecision,
+        /// Workspace paths to resolve. Directories cover everything beneath
+        /// them, and `None` resolves every pending change.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        paths: Option<Vec<PathBuf>>,
+    },
+
     /// Request the list of available models.
     ListModels,
 }
 
+/// What to do with pending copy-on-write workspace changes.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum OverlayWorkspaceDecision {
+    /// Write the changes into the real workspace.
+    Accept,
+    /// Discard the changes.
+    Reject,
+}
+
+/// Lifecycle actions for a background service.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum BackgroundServiceAction {
+    Start,
+    Stop,
+    Restart,
+}
+
 /// Determines the conditions under which the user is consulted to approve
 /// running the command proposed by Codex.
 #[derive(
@@ -785,6 +860,30 @@ impl FromStr for NetworkSandboxPolicy {
     }
 }
 
+impl FromStr for ResourceLimits {
+    type Err = serde_json::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        serde_json::from_str(s)
+    }
+}
+
+impl FromStr for GlobDenyMatches {
+    type Err = serde_json::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        serde_json::from_str(s)
+    }
+}
+
+impl FromStr for OverlayWorkspaceLayers {
+    type Err = serde_json::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        serde_json::from_str(s)
+    }
+}
+
 impl SandboxPolicy {
     /// Returns a policy with read-only disk access and no network.
     pub fn new_read_only_policy() -> Self {
@@ -1195,6 +1294,8 @@ pub enum EventMsg {
 
     ElicitationRequest(ElicitationRequestEvent),
 
+    McpSamplingRequest(McpSamplingRequestEvent),
+
     ApplyPatchApprovalRequest(ApplyPatchApprovalRequestEvent),
 
     /// Notification advising the user that something they are using has been
@@ -1220,15 +1321,32 @@ pub enum EventMsg {
 
     TurnDiff(TurnDiffEvent),
 
+    /// Changes pending in the copy-on-write workspace, sent at the end of a
+    /// turn and after each `Op::ResolveOverlayWorkspace`.
+    OverlayWorkspaceChanges(OverlayWorkspaceChangesEvent),
+
+    /// Current state of the thread's background services, sent when the
+    /// session starts and whenever a service changes state.
+    BackgroundServicesUpdated(BackgroundServicesUpdatedEvent),
+
     /// Response to GetHistoryEntryRequest.
     GetHistoryEntryResponse(GetHistoryEntryResponseEvent),
 
     /// List of MCP tools available to the agent.
     McpListToolsResponse(McpListToolsResponseEvent),
 
+    /// Prompts published by MCP servers, in response to `Op::ListMcpPrompts`.
+    ListMcpPromptsResponse(ListMcpPromptsResponseEvent),
+
+    /// Prompt text fetched in response to `Op::GetMcpPrompt`.
+    GetMcpPromptResponse(GetMcpPromptResponseEvent),
+
     /// List of custom prompts available to the agent.
     ListCustomPromptsResponse(ListCustomPromptsResponseEvent),
 
+    /// Prompt text produced in response to `Op::ExpandCustomPrompt`.
+    ExpandCustomPromptResponse(ExpandCustomPromptResponseEvent),
+
     /// List of skills available to the agent.
     ListSkillsResponse(ListSkillsResponseEvent),
 
@@ -1292,6 +1410,9 @@ pub enum EventMsg {
 #[serde(rename_all = "snake_case")]
 pub enum HookEventName {
     SessionStart,
+    UserPromptSubmit,
+    PreToolUse,
+    PostToolUse,
     Stop,
 }
 
@@ -1503,9 +1624,38 @@ pub enum CodexErrorInfo {
         http_status_code: Option<u16>,
     },
     ThreadRollbackFailed,
+    /// A run budget (`[run_budget]` in config.toml) ran out and the turn was
+    /// stopped before the agent finished.
+    RunBudgetExceeded {
+        budget: RunBudgetKind,
+    },
     Other,
 }
 
+/// The run budget that stopped a turn.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, JsonSchema, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(rename_all = "snake_case")]
+pub enum RunBudgetKind {
+    MaxTurns,
+    MaxOutputTokens,
+    MaxTotalTokens,
+    Timeout,
+    MaxToolCalls,
+}
+
+impl RunBudgetKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::MaxTurns => "max_turns",
+            Self::MaxOutputTokens => "max_output_tokens",
+            Self::MaxTotalTokens => "max_total_tokens",
+            Self::Timeout => "timeout",
+            Self::MaxToolCalls => "max_tool_calls",
+        }
+    }
+}
+
 impl CodexErrorInfo {
     /// Whether this error should mark the current turn as failed when replaying history.
     pub fn affects_turn_status(&self) -> bool {
@@ -1522,6 +1672,7 @@ impl CodexErrorInfo {
             | Self::SandboxError
             | Self::ResponseStreamDisconnected { .. }
             | Self::ResponseTooManyFailedAttempts { .. }
+            | Self::RunBudgetExceeded { .. }
             | Self::Other => true,
         }
     }
@@ -2480,6 +2631,26 @@ pub enum ReviewTarget {
         title: Option<String>,
     },
 
+    /// Review the changes in `base..head` without checking either revision out.
+    #[serde(rename_all = "camelCase")]
+    #[ts(rename_all = "camelCase")]
+    CommitRange { base: String, head: String },
+
+    /// Review a `.patch`/`.diff` file. Relative paths resolve against the
+    /// session cwd; the patch is never applied.
+    #[serde(rename_all = "camelCase")]
+    #[ts(rename_all = "camelCase")]
+    PatchFile { path: PathBuf },
+
+    /// Review an inline unified diff, e.g. one piped on stdin.
+    #[serde(rename_all = "camelCase")]
+    #[ts(rename_all = "camelCase")]
+    Diff {
+        diff: String,
+        /// Optional human-readable label for UIs.
+        title: Option<String>,
+    },
+
     /// Arbitrary instructions provided by the user.
     #[serde(rename_all = "camelCase")]
     #[ts(rename_all = "camelCase")]
@@ -2762,6 +2933,67 @@ pub struct TurnDiffEvent {
     pub unified_diff: String,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct OverlayWorkspaceChangesEvent {
+    /// Pending changes keyed by their path in the real workspace.
+    pub changes: HashMap<PathBuf, FileChange>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct BackgroundServicesUpdatedEvent {
+    pub services: Vec<BackgroundServiceSummary>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, JsonSchema, TS)]
+pub struct BackgroundServiceSummary {
+    pub name: String,
+    pub command: Vec<String>,
+    pub source: BackgroundServiceSource,
+    pub status: BackgroundServiceStatus,
+    /// Result of the latest health check; `None` when the service has no
+    /// health check.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub health: Option<BackgroundServiceHealth>,
+    /// Times the service was restarted after exiting on its own.
+    pub restarts: u32,
+    /// Exit code of the last run, once it has exited.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exit_code: Option<i32>,
+    /// Most recent log lines, oldest first.
+    pub recent_logs: Vec<String>,
+}
+
+/// Where a background service was declared.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, JsonSchema, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum BackgroundServiceSource {
+    /// A `[services.<name>]` table in config.
+    Config,
+    /// Started by the model with the `service_start` tool.
+    Model,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, JsonSchema, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum BackgroundServiceStatus {
+    /// Not running: never started, or stopped on request.
+    Stopped,
+    Running,
+    /// Waiting to start again after the process exited.
+    Restarting,
+    /// Exited and will not be restarted.
+    Exited,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, JsonSchema, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum BackgroundServiceHealth {
+    /// No health check has finished since the service started.
+    Pending,
+    Healthy,
+    Unhealthy,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
 pub struct GetHistoryEntryResponseEvent {
     pub offset: usize,
@@ -2783,6 +3015,22 @@ pub struct McpListToolsResponseEvent {
     pub auth_statuses: std::collections::HashMap<String, McpAuthStatus>,
 }
 
+/// Response payload for `Op::ListMcpPrompts`.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct ListMcpPromptsResponseEvent {
+    /// Prompts grouped by server name.
+    pub prompts: HashMap<String, Vec<McpPrompt>>,
+}
+
+/// Response payload for `Op::GetMcpPrompt`.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct GetMcpPromptResponseEvent {
+    pub server_name: String,
+    pub prompt_name: String,
+    /// The prompt's messages flattened to text, or an error message.
+    pub result: Result<String, String>,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
 pub struct McpStartupUpdateEvent {
     /// Server name being started.
@@ -2842,6 +3090,19 @@ pub struct ListCustomPromptsResponseEvent {
     pub custom_prompts: Vec<CustomPrompt>,
 }
 
+/// Response payload for `Op::ExpandCustomPrompt`.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct ExpandCustomPromptResponseEvent {
+    /// The `id` of the `Op::ExpandCustomPrompt` this answers.
+    pub id: String,
+    /// The prompt with its shell directives replaced by their output, or an
+    /// error message.
+    pub result: Result<String, String>,
+    /// The request's `text_elements` with byte ranges in the expanded prompt.
+    #[serde(default)]
+    pub text_elements: Vec<crate::user_input::TextElement>,
+}
+
 /// Response payload for `Op::ListSkills`.
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
 pub struct ListSkillsResponseEvent {
@@ -1,5 +1,6 @@
 use std::borrow::Cow;
 use std::collections::HashMap;
+use std::collections::HashSet;
 use std::sync::Arc;
 
 use rmcp::ErrorData as McpError;
@@ -7,11 +8,19 @@ use rmcp::ServiceExt;
 use rmcp::handler::server::ServerHandler;
 use rmcp::model::CallToolRequestParams;
 use rmcp::model::CallToolResult;
+use rmcp::model::CreateMessageRequestParams;
+use rmcp::model::GetPromptRequestParams;
+use rmcp::model::GetPromptResult;
 use rmcp::model::JsonObject;
+use rmcp::model::ListPromptsResult;
 use rmcp::model::ListResourceTemplatesResult;
 use rmcp::model::ListResourcesResult;
 use rmcp::model::ListToolsResult;
 use rmcp::model::PaginatedRequestParams;
+use rmcp::model::Prompt;
+use rmcp::model::PromptArgument;
+use rmcp::model::PromptMessage;
+use rmcp::model::PromptMessageRole;
 use rmcp::model::RawResource;
 use rmcp::model::RawResourceTemplate;
 use rmcp::model::ReadResourceRequestParams;
@@ -19,11 +28,17 @@ use rmcp::model::ReadResourceResult;
 use rmcp::model::Resource;
 use rmcp::model::ResourceContents;
 use rmcp::model::ResourceTemplate;
+use rmcp::model::ResourceUpdatedNotificationParam;
+use rmcp::model::SamplingMessage;
+use rmcp::model::SamplingMessageContent;
 use rmcp::model::ServerCapabilities;
 use rmcp::model::ServerInfo;
+use rmcp::model::SubscribeRequestParams;
 use rmcp::model::Tool;
+use rmcp::model::UnsubscribeRequestParams;
 use serde::Deserialize;
 use serde_json::json;
+use tokio::sync::Mutex;
 use tokio::task;
 
 #[derive(Clone)]
@@ -31,6 +46,8 @@ struct TestToolServer {
     tools: Arc<Vec<Tool>>,
     resources: Arc<Vec<Resource>>,
     resource_templates: Arc<Vec<ResourceTemplate>>,
+    prompts: Arc<Vec<Prompt>>,
+    subscriptions: Arc<Mutex<HashSet<String>>>,
 }
 
 const MEMO_URI: &str = "memo://codex/example-note";
@@ -48,13 +65,22 @@ impl TestToolServer {
             Self::echo_dash_tool(),
             Self::image_tool(),
             Self::image_scenario_tool(),
+            Self::sample_tool(),
+            Self::build_no_args_tool("roots", "List the roots the client reports."),
+            Self::build_no_args_tool(
+                "touch_memo",
+                "Report the memo resource as updated to subscribed clients.",
+            ),
         ];
         let resources = vec![Self::memo_resource()];
         let resource_templates = vec![Self::memo_template()];
+        let prompts = vec![Self::review_prompt()];
         Self {
             tools: Arc::new(tools),
             resources: Arc::new(resources),
             resource_templates: Arc::new(resource_templates),
+            prompts: Arc::new(prompts),
+            subscriptions: Arc::new(Mutex::new(HashSet::new())),
         }
     }
 
@@ -72,6 +98,25 @@ impl TestToolServer {
         )
     }
 
+    fn sample_tool() -> Tool {
+        #[expect(clippy::expect_used)]
+        let schema: JsonObject = serde_json::from_value(json!({
+            "type": "object",
+            "properties": {
+                "message": { "type": "string" }
+            },
+            "required": ["message"],
+            "additionalProperties": false
+        }))
+        .expect("sample tool schema should deserialize");
+
+        Tool::new(
+            Cow::Borrowed("sample"),
+            Cow::Borrowed("Ask the client to sample a completion for the provided message."),
+            Arc::new(schema),
+        )
+    }
+
     fn build_echo_tool(name: &'static str, description: &'static str) -> Tool {
         #[expect(clippy::expect_used)]
         let schema: JsonObject = serde_json::from_value(json!({
@@ -92,6 +137,22 @@ impl TestToolServer {
         )
     }
 
+    fn build_no_args_tool(name: &'static str, description: &'static str) -> Tool {
+        #[expect(clippy::expect_used)]
+        let schema: JsonObject = serde_json::from_value(json!({
+            "type": "object",
+            "properties": {},
+            "additionalProperties": false
+        }))
+        .expect("tool schema should deserialize");
+
+        Tool::new(
+            Cow::Borrowed(name),
+            Cow::Borrowed(description),
+            Arc::new(schema),
+        )
+    }
+
     fn image_tool() -> Tool {
         #[expect(clippy::expect_used)]
         let schema: JsonObject = serde_json::from_value(serde_json::json!({
@@ -194,6 +255,27 @@ impl TestToolServer {
     fn memo_text() -> &'static str {
         MEMO_CONTENT
     }
+
+    fn review_prompt() -> Prompt {
+        Prompt::new(
+            "review",
+            Some("Ask for a review of a file."),
+            Some(vec![
+                PromptArgument {
+                    name: "file".to_string(),
+                    title: None,
+                    description: Some("Path of the file to review.".to_string()),
+                    required: Some(true),
+                },
+                PromptArgument {
+                    name: "focus".to_string(),
+                    title: None,
+                    description: Some("What the review should focus on.".to_string()),
+                    required: Some(false),
+                },
+            ]),
+        )
+    }
 }
 
 #[derive(Deserialize)]
@@ -203,6 +285,11 @@ struct EchoArgs {
     env_var: Option<String>,
 }
 
+#[derive(Deserialize)]
+struct SampleArgs {
+    message: String,
+}
+
 #[derive(Deserialize, Debug)]
 #[serde(rename_all = "snake_case")]
 /// Scenarios for `image_scenario`, intended to exercise Codex TUI handling of MCP image outputs.
@@ -236,6 +323,8 @@ impl ServerHandler for TestToolServer {
                 .enable_tools()
                 .enable_tool_list_changed()
                 .enable_resources()
+                .enable_resources_subscribe()
+                .enable_prompts()
                 .build(),
             ..ServerInfo::default()
         }
@@ -283,6 +372,51 @@ impl ServerHandler for TestToolServer {
         })
     }
 
+    async fn list_prompts(
+        &self,
+        _request: Option<PaginatedRequestParams>,
+        _context: rmcp::service::RequestContext<rmcp::service::RoleServer>,
+    ) -> Result<ListPromptsResult, McpError> {
+        Ok(ListPromptsResult {
+            prompts: (*self.prompts).clone(),
+            next_cursor: None,
+            meta: None,
+        })
+    }
+
+    async fn get_prompt(
+        &self,
+        GetPromptRequestParams {
+            name, arguments, ..
+        }: GetPromptRequestParams,
+        _context: rmcp::service::RequestContext<rmcp::service::RoleServer>,
+    ) -> Result<GetPromptResult, McpError> {
+        if name != "review" {
+            return Err(McpError::invalid_params(
+                format!("unknown prompt: {name}"),
+                None,
+            ));
+        }
+        let argument = |key: &str| {
+            arguments
+                .as_ref()
+                .and_then(|arguments| arguments.get(key))
+                .and_then(|value| value.as_str())
+                .map(str::to_string)
+        };
+        let Some(file) = argument("file") else {
+            return Err(McpError::invalid_params("missing argument: file", None));
+        };
+        let mut text = format!("Please review {file}.");
+        if let Some(focus) = argument("focus") {
+            text.push_str(&format!(" Focus on {focus}."));
+        }
+        Ok(GetPromptResult {
+            description: Some("Ask for a review of a file.".to_string()),
+            messages: vec![PromptMessage::new_text(PromptMessageRole::User, text)],
+        })
+    }
+
     async fn read_resource(
         &self,
         ReadResourceRequestParams { uri, .. }: ReadResourceRequestParams,
@@ -305,10 +439,34 @@ impl ServerHandler for TestToolServer {
         }
     }
 
+    async fn subscribe(
+        &self,
+        SubscribeRequestParams { uri, .. }: SubscribeRequestParams,
+        _context: rmcp::service::RequestContext<rmcp::service::RoleServer>,
+    ) -> Result<(), McpError> {
+        if uri != MEMO_URI {
+            return Err(McpError::resource_not_found(
+                "resource_not_found",
+                Some(json!({ "uri": uri })),
+            ));
+        }
+        self.subscriptions.lock().await.insert(uri);
+        Ok(())
+    }
+
+    async fn unsubscribe(
+        &self,
+        UnsubscribeRequestParams { uri, .. }: UnsubscribeRequestParams,
+        _context: rmcp::service::RequestContext<rmcp::service::RoleServer>,
+    ) -> Result<(), McpError> {
+        self.subscriptions.lock().await.remove(&uri);
+        Ok(())
+    }
+
     async fn call_tool(
         &self,
         request: CallToolRequestParams,
-        _context: rmcp::service::RequestContext<rmcp::service::RoleServer>,
+        context: rmcp::service::RequestContext<rmcp::service::RoleServer>,
     ) -> Result<CallToolResult, McpError> {
         match request.name.as_ref() {
             "echo" | "echo-tool" => {
@@ -363,6 +521,63 @@ impl ServerHandler for TestToolServer {
                 let args = Self::parse_call_args::<ImageScenarioArgs>(&request, "image_scenario")?;
                 Self::image_scenario_result(args)
             }
+            "sample" => {
+                let args = Self::parse_call_args::<SampleArgs>(&request, "sample")?;
+                let result = context
+                    .peer
+                    .create_message(CreateMessageRequestParams {
+                        meta: None,
+                        task: None,
+                        messages: vec![SamplingMessage::user_text(args.message)],
+                        model_preferences: None,
+                        system_prompt: None,
+                        include_context: None,
+                        temperature: None,
+                        max_tokens: 64,
+                        stop_sequences: None,
+                        metadata: None,
+                        tools: None,
+                        tool_choice: None,
+                    })
+                    .await
+                    .map_err(|err| McpError::internal_error(err.to_string(), None))?;
+                let text = result
+                    .message
+                    .content
+                    .first()
+                    .and_then(SamplingMessageContent::as_text)
+                    .map(|content| content.text.clone())
+                    .unwrap_or_default();
+                Ok(CallToolResult::success(vec![rmcp::model::Content::text(
+                    format!("{}: {text}", result.model),
+                )]))
+            }
+            "roots" => {
+                let result = context
+                    .peer
+                    .list_roots()
+                    .await
+                    .map_err(|err| McpError::internal_error(err.to_string(), None))?;
+                let uris: Vec<String> = result.roots.into_iter().map(|root| root.uri).collect();
+                Ok(CallToolResult::success(vec![rmcp::model::Content::text(
+                    uris.join("\n"),
+                )]))
+            }
+            "touch_memo" => {
+                let subscribed = self.subscriptions.lock().await.contains(MEMO_URI);
+                if subscribed {
+                    context
+                        .peer
+                        .notify_resource_updated(ResourceUpdatedNotificationParam {
+                            uri: MEMO_URI.to_string(),
+                        })
+                        .await
+                        .map_err(|err| McpError::internal_error(err.to_string(), None))?;
+                }
+                Ok(CallToolResult::success(vec![rmcp::model::Content::text(
+                    format!("subscribed: {subscribed}"),
+                )]))
+            }
             other => Err(McpError::invalid_params(
                 format!("unknown tool: {other}"),
                 None,
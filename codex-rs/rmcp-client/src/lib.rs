@@ -26,5 +26,7 @@ pub use rmcp_client::Elicitation;
 pub use rmcp_client::ElicitationResponse;
 pub use rmcp_client::ListToolsWithConnectorIdResult;
 pub use rmcp_client::RmcpClient;
+pub use rmcp_client::Sampling;
 pub use rmcp_client::SendElicitation;
+pub use rmcp_client::SendSampling;
 pub use rmcp_client::ToolWithConnectorId;
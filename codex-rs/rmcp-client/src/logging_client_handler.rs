@@ -6,30 +6,50 @@ use rmcp::model::CancelledNotificationParam;
 use rmcp::model::ClientInfo;
 use rmcp::model::CreateElicitationRequestParams;
 use rmcp::model::CreateElicitationResult;
+use rmcp::model::CreateMessageRequestMethod;
+use rmcp::model::CreateMessageRequestParams;
+use rmcp::model::CreateMessageResult;
+use rmcp::model::ListRootsResult;
 use rmcp::model::LoggingLevel;
 use rmcp::model::LoggingMessageNotificationParam;
 use rmcp::model::ProgressNotificationParam;
 use rmcp::model::ResourceUpdatedNotificationParam;
+use rmcp::model::Root;
 use rmcp::service::NotificationContext;
 use rmcp::service::RequestContext;
+use tokio::sync::Mutex;
+use tokio::sync::broadcast;
 use tracing::debug;
 use tracing::error;
 use tracing::info;
 use tracing::warn;
 
 use crate::rmcp_client::SendElicitation;
+use crate::rmcp_client::SendSampling;
 
 #[derive(Clone)]
 pub(crate) struct LoggingClientHandler {
     client_info: ClientInfo,
     send_elicitation: Arc<SendElicitation>,
+    send_sampling: Option<Arc<SendSampling>>,
+    roots: Arc<Mutex<Vec<Root>>>,
+    resource_updates: broadcast::Sender<String>,
 }
 
 impl LoggingClientHandler {
-    pub(crate) fn new(client_info: ClientInfo, send_elicitation: SendElicitation) -> Self {
+    pub(crate) fn new(
+        client_info: ClientInfo,
+        send_elicitation: SendElicitation,
+        send_sampling: Option<SendSampling>,
+        roots: Arc<Mutex<Vec<Root>>>,
+        resource_updates: broadcast::Sender<String>,
+    ) -> Self {
         Self {
             client_info,
             send_elicitation: Arc::new(send_elicitation),
+            send_sampling: send_sampling.map(Arc::new),
+            roots,
+            resource_updates,
         }
     }
 }
@@ -46,6 +66,28 @@ impl ClientHandler for LoggingClientHandler {
             .map_err(|err| rmcp::ErrorData::internal_error(err.to_string(), None))
     }
 
+    async fn create_message(
+        &self,
+        params: CreateMessageRequestParams,
+        context: RequestContext<RoleClient>,
+    ) -> Result<CreateMessageResult, rmcp::ErrorData> {
+        match &self.send_sampling {
+            Some(send_sampling) => send_sampling(context.id, params).await,
+            None => Err(rmcp::ErrorData::method_not_found::<
+                CreateMessageRequestMethod,
+            >()),
+        }
+    }
+
+    async fn list_roots(
+        &self,
+        _context: RequestContext<RoleClient>,
+    ) -> Result<ListRootsResult, rmcp::ErrorData> {
+        Ok(ListRootsResult {
+            roots: self.roots.lock().await.clone(),
+        })
+    }
+
     async fn on_cancelled(
         &self,
         params: CancelledNotificationParam,
@@ -74,6 +116,8 @@ impl ClientHandler for LoggingClientHandler {
         _context: NotificationContext<RoleClient>,
     ) {
         info!("MCP server resource updated (uri: {})", params.uri);
+        // Nobody may be listening for updates; dropping them is fine.
+        let _ = self.resource_updates.send(params.uri);
     }
 
     async fn on_resource_list_changed(&self, _context: NotificationContext<RoleClient>) {
@@ -26,12 +26,17 @@ use rmcp::model::ClientNotification;
 use rmcp::model::ClientRequest;
 use rmcp::model::CreateElicitationRequestParams;
 use rmcp::model::CreateElicitationResult;
+use rmcp::model::CreateMessageRequestParams;
+use rmcp::model::CreateMessageResult;
 use rmcp::model::CustomNotification;
 use rmcp::model::CustomRequest;
 use rmcp::model::ElicitationAction;
 use rmcp::model::Extensions;
+use rmcp::model::GetPromptRequestParams;
+use rmcp::model::GetPromptResult;
 use rmcp::model::InitializeRequestParams;
 use rmcp::model::InitializeResult;
+use rmcp::model::ListPromptsResult;
 use rmcp::model::ListResourceTemplatesResult;
 use rmcp::model::ListResourcesResult;
 use rmcp::model::ListToolsResult;
@@ -39,8 +44,11 @@ use rmcp::model::PaginatedRequestParams;
 use rmcp::model::ReadResourceRequestParams;
 use rmcp::model::ReadResourceResult;
 use rmcp::model::RequestId;
+use rmcp::model::Root;
 use rmcp::model::ServerResult;
+use rmcp::model::SubscribeRequestParams;
 use rmcp::model::Tool;
+use rmcp::model::UnsubscribeRequestParams;
 use rmcp::service::RoleClient;
 use rmcp::service::RunningService;
 use rmcp::service::{self};
@@ -63,6 +71,7 @@ use tokio::io::AsyncBufReadExt;
 use tokio::io::BufReader;
 use tokio::process::Command;
 use tokio::sync::Mutex;
+use tokio::sync::broadcast;
 use tokio::time;
 use tracing::info;
 use tracing::warn;
@@ -82,6 +91,7 @@ const JSON_MIME_TYPE: &str = "application/json";
 const HEADER_LAST_EVENT_ID: &str = "Last-Event-Id";
 const HEADER_SESSION_ID: &str = "Mcp-Session-Id";
 const NON_JSON_RESPONSE_BODY_PREVIEW_BYTES: usize = 8_192;
+const RESOURCE_UPDATES_CAPACITY: usize = 64;
 
 #[derive(Clone)]
 struct StreamableHttpResponseClient {
@@ -390,6 +400,7 @@ enum TransportRecipe {
     Stdio {
         program: OsString,
         args: Vec<OsString>,
+        arg0: Option<OsString>,
         env: Option<HashMap<String, String>>,
         env_vars: Vec<String>,
         cwd: Option<PathBuf>,
@@ -453,6 +464,17 @@ pub type SendElicitation = Box<
     dyn Fn(RequestId, Elicitation) -> BoxFuture<'static, Result<ElicitationResponse>> + Send + Sync,
 >;
 
+pub type Sampling = CreateMessageRequestParams;
+
+/// Interface for answering `sampling/createMessage` requests from the server.
+/// Errors are returned to the server as-is, so a declined request should use a
+/// JSON-RPC error code the server can act on.
+pub type SendSampling = Box<
+    dyn Fn(RequestId, Sampling) -> BoxFuture<'static, Result<CreateMessageResult, rmcp::ErrorData>>
+        + Send
+        + Sync,
+>;
+
 pub struct ToolWithConnectorId {
     pub tool: Tool,
     pub connector_id: Option<String>,
@@ -472,6 +494,10 @@ pub struct RmcpClient {
     transport_recipe: TransportRecipe,
     initialize_context: Mutex<Option<InitializeContext>>,
     session_recovery_lock: Mutex<()>,
+    /// Roots reported to the server from `roots/list`.
+    roots: Arc<Mutex<Vec<Root>>>,
+    /// URIs from `notifications/resources/updated`.
+    resource_updates: broadcast::Sender<String>,
 }
 
 impl RmcpClient {
@@ -481,10 +507,25 @@ impl RmcpClient {
         env: Option<HashMap<String, String>>,
         env_vars: &[String],
         cwd: Option<PathBuf>,
+    ) -> io::Result<Self> {
+        Self::new_stdio_client_with_arg0(program, args, None, env, env_vars, cwd).await
+    }
+
+    /// Like [`Self::new_stdio_client`], but spawns the server with a custom
+    /// `argv[0]`. Launchers that dispatch on their invocation name, such as
+    /// `codex-linux-sandbox`, need this.
+    pub async fn new_stdio_client_with_arg0(
+        program: OsString,
+        args: Vec<OsString>,
+        arg0: Option<OsString>,
+        env: Option<HashMap<String, String>>,
+        env_vars: &[String],
+        cwd: Option<PathBuf>,
     ) -> io::Result<Self> {
         let transport_recipe = TransportRecipe::Stdio {
             program,
             args,
+            arg0,
             env,
             env_vars: env_vars.to_vec(),
             cwd,
@@ -500,6 +541,8 @@ impl RmcpClient {
             transport_recipe,
             initialize_context: Mutex::new(None),
             session_recovery_lock: Mutex::new(()),
+            roots: Arc::new(Mutex::new(Vec::new())),
+            resource_updates: broadcast::channel(RESOURCE_UPDATES_CAPACITY).0,
         })
     }
 
@@ -528,6 +571,8 @@ impl RmcpClient {
             transport_recipe,
             initialize_context: Mutex::new(None),
             session_recovery_lock: Mutex::new(()),
+            roots: Arc::new(Mutex::new(Vec::new())),
+            resource_updates: broadcast::channel(RESOURCE_UPDATES_CAPACITY).0,
         })
     }
 
@@ -538,8 +583,15 @@ impl RmcpClient {
         params: InitializeRequestParams,
         timeout: Option<Duration>,
         send_elicitation: SendElicitation,
+        send_sampling: Option<SendSampling>,
     ) -> Result<InitializeResult> {
-        let client_handler = LoggingClientHandler::new(params.clone(), send_elicitation);
+        let client_handler = LoggingClientHandler::new(
+            params.clone(),
+            send_elicitation,
+            send_sampling,
+            Arc::clone(&self.roots),
+            self.resource_updates.clone(),
+        );
         let pending_transport = {
             let mut guard = self.state.lock().await;
             match &mut *guard {
@@ -680,6 +732,38 @@ impl RmcpClient {
         Ok(result)
     }
 
+    pub async fn list_prompts(
+        &self,
+        params: Option<PaginatedRequestParams>,
+        timeout: Option<Duration>,
+    ) -> Result<ListPromptsResult> {
+        self.refresh_oauth_if_needed().await;
+        let result = self
+            .run_service_operation("prompts/list", timeout, move |service| {
+                let params = params.clone();
+                async move { service.list_prompts(params).await }.boxed()
+            })
+            .await?;
+        self.persist_oauth_tokens().await;
+        Ok(result)
+    }
+
+    pub async fn get_prompt(
+        &self,
+        params: GetPromptRequestParams,
+        timeout: Option<Duration>,
+    ) -> Result<GetPromptResult> {
+        self.refresh_oauth_if_needed().await;
+        let result = self
+            .run_service_operation("prompts/get", timeout, move |service| {
+                let params = params.clone();
+                async move { service.get_prompt(params).await }.boxed()
+            })
+            .await?;
+        self.persist_oauth_tokens().await;
+        Ok(result)
+    }
+
     pub async fn read_resource(
         &self,
         params: ReadResourceRequestParams,
@@ -696,6 +780,63 @@ impl RmcpClient {
         Ok(result)
     }
 
+    /// Replaces the roots reported from `roots/list`. Once the client is
+    /// initialized, a change is announced with `notifications/roots/list_changed`.
+    pub async fn set_roots(&self, roots: Vec<Root>) -> Result<()> {
+        {
+            let mut current = self.roots.lock().await;
+            if *current == roots {
+                return Ok(());
+            }
+            *current = roots;
+        }
+        if matches!(&*self.state.lock().await, ClientState::Connecting { .. }) {
+            return Ok(());
+        }
+        self.run_service_operation("notifications/roots/list_changed", None, move |service| {
+            async move { service.notify_roots_list_changed().await }.boxed()
+        })
+        .await?;
+        Ok(())
+    }
+
+    pub async fn subscribe_resource(
+        &self,
+        params: SubscribeRequestParams,
+        timeout: Option<Duration>,
+    ) -> Result<()> {
+        self.refresh_oauth_if_needed().await;
+        self.run_service_operation("resources/subscribe", timeout, move |service| {
+            let params = params.clone();
+            async move { service.subscribe(params).await }.boxed()
+        })
+        .await?;
+        self.persist_oauth_tokens().await;
+        Ok(())
+    }
+
+    pub async fn unsubscribe_resource(
+        &self,
+        params: UnsubscribeRequestParams,
+        timeout: Option<Duration>,
+    ) -> Result<()> {
+        self.refresh_oauth_if_needed().await;
+        self.run_service_operation("resources/unsubscribe", timeout, move |service| {
+            let params = params.clone();
+            async move { service.unsubscribe(params).await }.boxed()
+        })
+        .await?;
+        self.persist_oauth_tokens().await;
+        Ok(())
+    }
+
+    /// Returns a receiver for the URIs of resources the server reports as
+    /// updated. Only resources subscribed with [`Self::subscribe_resource`]
+    /// are reported.
+    pub fn resource_updates(&self) -> broadcast::Receiver<String> {
+        self.resource_updates.subscribe()
+    }
+
     pub async fn call_tool(
         &self,
         name: String,
@@ -819,6 +960,7 @@ impl RmcpClient {
             TransportRecipe::Stdio {
                 program,
                 args,
+                arg0,
                 env,
                 env_vars,
                 cwd,
@@ -837,6 +979,12 @@ impl RmcpClient {
                     .args(args);
                 #[cfg(unix)]
                 command.process_group(0);
+                #[cfg(unix)]
+                if let Some(arg0) = arg0 {
+                    command.arg0(arg0);
+                }
+                #[cfg(not(unix))]
+                let _ = arg0;
                 if let Some(cwd) = cwd {
                     command.current_dir(cwd);
                 }
@@ -0,0 +1,155 @@
+use std::ffi::OsString;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use codex_rmcp_client::ElicitationAction;
+use codex_rmcp_client::ElicitationResponse;
+use codex_rmcp_client::RmcpClient;
+use codex_utils_cargo_bin::CargoBinError;
+use futures::FutureExt as _;
+use pretty_assertions::assert_eq;
+use rmcp::model::ClientCapabilities;
+use rmcp::model::Implementation;
+use rmcp::model::InitializeRequestParams;
+use rmcp::model::ProtocolVersion;
+use rmcp::model::RawContent;
+use rmcp::model::Root;
+use rmcp::model::RootsCapabilities;
+use rmcp::model::SubscribeRequestParams;
+use rmcp::model::UnsubscribeRequestParams;
+
+const MEMO_URI: &str = "memo://codex/example-note";
+
+fn stdio_server_bin() -> Result<PathBuf, CargoBinError> {
+    codex_utils_cargo_bin::cargo_bin("test_stdio_server")
+}
+
+fn init_params() -> InitializeRequestParams {
+    InitializeRequestParams {
+        meta: None,
+        capabilities: ClientCapabilities {
+            experimental: None,
+            extensions: None,
+            roots: Some(RootsCapabilities {
+                list_changed: Some(true),
+            }),
+            sampling: None,
+            elicitation: None,
+            tasks: None,
+        },
+        client_info: Implementation {
+            name: "codex-test".into(),
+            version: "0.0.0-test".into(),
+            title: Some("Codex rmcp roots test".into()),
+            description: None,
+            icons: None,
+            website_url: None,
+        },
+        protocol_version: ProtocolVersion::V_2025_06_18,
+    }
+}
+
+async fn start_client(roots: Vec<Root>) -> anyhow::Result<RmcpClient> {
+    let client = RmcpClient::new_stdio_client(
+        stdio_server_bin()?.into(),
+        Vec::<OsString>::new(),
+        None,
+        &[],
+        None,
+    )
+    .await?;
+    client.set_roots(roots).await?;
+    client
+        .initialize(
+            init_params(),
+            Some(Duration::from_secs(5)),
+            Box::new(|_, _| {
+                async {
+                    Ok(ElicitationResponse {
+                        action: ElicitationAction::Decline,
+                        content: None,
+                        meta: None,
+                    })
+                }
+                .boxed()
+            }),
+            None,
+        )
+        .await?;
+    Ok(client)
+}
+
+async fn call_text_tool(client: &RmcpClient, name: &str) -> anyhow::Result<String> {
+    let result = client
+        .call_tool(name.to_string(), None, Some(Duration::from_secs(5)))
+        .await?;
+    match &result.content.first().map(|content| &content.raw) {
+        Some(RawContent::Text(text)) => Ok(text.text.clone()),
+        other => panic!("expected text content, got {other:?}"),
+    }
+}
+
+fn root(uri: &str) -> Root {
+    Root {
+        uri: uri.to_string(),
+        name: None,
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn server_sees_the_current_roots() -> anyhow::Result<()> {
+    let client = start_client(vec![root("file:///workspace")]).await?;
+    assert_eq!(call_text_tool(&client, "roots").await?, "file:///workspace");
+
+    client
+        .set_roots(vec![root("file:///workspace"), root("file:///extra")])
+        .await?;
+    assert_eq!(
+        call_text_tool(&client, "roots").await?,
+        "file:///workspace\nfile:///extra"
+    );
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn subscribed_resource_updates_are_reported() -> anyhow::Result<()> {
+    let client = start_client(Vec::new()).await?;
+    let mut updates = client.resource_updates();
+
+    assert_eq!(
+        call_text_tool(&client, "touch_memo").await?,
+        "subscribed: false"
+    );
+    client
+        .subscribe_resource(
+            SubscribeRequestParams {
+                meta: None,
+                uri: MEMO_URI.to_string(),
+            },
+            Some(Duration::from_secs(5)),
+        )
+        .await?;
+    assert_eq!(
+        call_text_tool(&client, "touch_memo").await?,
+        "subscribed: true"
+    );
+    let uri = tokio::time::timeout(Duration::from_secs(5), updates.recv()).await??;
+    assert_eq!(uri, MEMO_URI);
+
+    client
+        .unsubscribe_resource(
+            UnsubscribeRequestParams {
+                meta: None,
+                uri: MEMO_URI.to_string(),
+            },
+            Some(Duration::from_secs(5)),
+        )
+        .await?;
+    assert_eq!(
+        call_text_tool(&client, "touch_memo").await?,
+        "subscribed: false"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,138 @@
+use std::ffi::OsString;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use codex_rmcp_client::ElicitationAction;
+use codex_rmcp_client::ElicitationResponse;
+use codex_rmcp_client::RmcpClient;
+use codex_rmcp_client::SendElicitation;
+use codex_rmcp_client::SendSampling;
+use codex_utils_cargo_bin::CargoBinError;
+use futures::FutureExt as _;
+use pretty_assertions::assert_eq;
+use rmcp::model::ClientCapabilities;
+use rmcp::model::CreateMessageResult;
+use rmcp::model::Implementation;
+use rmcp::model::InitializeRequestParams;
+use rmcp::model::ProtocolVersion;
+use rmcp::model::RawContent;
+use rmcp::model::SamplingCapability;
+use rmcp::model::SamplingMessage;
+use rmcp::model::SamplingMessageContent;
+use serde_json::json;
+
+fn stdio_server_bin() -> Result<PathBuf, CargoBinError> {
+    codex_utils_cargo_bin::cargo_bin("test_stdio_server")
+}
+
+fn init_params(sampling: Option<SamplingCapability>) -> InitializeRequestParams {
+    InitializeRequestParams {
+        meta: None,
+        capabilities: ClientCapabilities {
+            experimental: None,
+            extensions: None,
+            roots: None,
+            sampling,
+            elicitation: None,
+            tasks: None,
+        },
+        client_info: Implementation {
+            name: "codex-test".into(),
+            version: "0.0.0-test".into(),
+            title: Some("Codex rmcp sampling test".into()),
+            description: None,
+            icons: None,
+            website_url: None,
+        },
+        protocol_version: ProtocolVersion::V_2025_06_18,
+    }
+}
+
+fn decline_elicitation() -> SendElicitation {
+    Box::new(|_, _| {
+        async {
+            Ok(ElicitationResponse {
+                action: ElicitationAction::Decline,
+                content: None,
+                meta: None,
+            })
+        }
+        .boxed()
+    })
+}
+
+async fn start_client(send_sampling: Option<SendSampling>) -> anyhow::Result<RmcpClient> {
+    let client = RmcpClient::new_stdio_client(
+        stdio_server_bin()?.into(),
+        Vec::<OsString>::new(),
+        None,
+        &[],
+        None,
+    )
+    .await?;
+    let sampling = send_sampling
+        .as_ref()
+        .map(|_| SamplingCapability::default());
+    client
+        .initialize(
+            init_params(sampling),
+            Some(Duration::from_secs(5)),
+            decline_elicitation(),
+            send_sampling,
+        )
+        .await?;
+    Ok(client)
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn sampling_requests_are_forwarded_to_the_handler() -> anyhow::Result<()> {
+    let client = start_client(Some(Box::new(|_, params| {
+        async move {
+            let prompt = params
+                .messages
+                .first()
+                .and_then(|message| message.content.first())
+                .and_then(SamplingMessageContent::as_text)
+                .map(|content| content.text.clone())
+                .unwrap_or_default();
+            Ok(CreateMessageResult {
+                model: "test-model".to_string(),
+                stop_reason: Some(CreateMessageResult::STOP_REASON_END_TURN.to_string()),
+                message: SamplingMessage::assistant_text(format!("reply to {prompt}")),
+            })
+        }
+        .boxed()
+    })))
+    .await?;
+
+    let result = client
+        .call_tool(
+            "sample".to_string(),
+            Some(json!({ "message": "hello" })),
+            Some(Duration::from_secs(5)),
+        )
+        .await?;
+    let text = match &result.content.first().map(|content| &content.raw) {
+        Some(RawContent::Text(text)) => text.text.clone(),
+        other => panic!("expected text content, got {other:?}"),
+    };
+    assert_eq!(text, "test-model: reply to hello");
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn sampling_requests_fail_without_a_handler() -> anyhow::Result<()> {
+    let client = start_client(None).await?;
+
+    let result = client
+        .call_tool(
+            "sample".to_string(),
+            Some(json!({ "message": "hello" })),
+            Some(Duration::from_secs(5)),
+        )
+        .await;
+    assert!(result.is_err(), "expected sampling to fail: {result:?}");
+
+    Ok(())
+}
@@ -0,0 +1,8 @@
+CREATE VIRTUAL TABLE thread_search_fts USING fts5(
+    thread_id UNINDEXED,
+    kind UNINDEXED,
+    content,
+    tokenize = 'unicode61 remove_diacritics 2'
+);
+
+ALTER TABLE threads ADD COLUMN search_indexed_at INTEGER;
@@ -1,4 +1,6 @@
 use crate::model::ThreadMetadata;
+use crate::model::ThreadSearchDocument;
+use crate::model::ThreadSearchDocumentKind;
 use codex_protocol::models::ResponseItem;
 use codex_protocol::protocol::EventMsg;
 use codex_protocol::protocol::RolloutItem;
@@ -8,9 +10,22 @@ use codex_protocol::protocol::USER_MESSAGE_BEGIN;
 use codex_protocol::protocol::UserMessageEvent;
 use serde::Serialize;
 use serde_json::Value;
+use std::collections::HashSet;
 
 const IMAGE_ONLY_USER_MESSAGE_PLACEHOLDER: &str = "[Image]";
 
+/// Tools whose outputs are indexed as command output for session search.
+const COMMAND_TOOL_NAMES: &[&str] = &[
+    "shell",
+    "shell_command",
+    "container.exec",
+    "exec_command",
+    "write_stdin",
+];
+
+/// Upper bound on the text kept for a single search document.
+const MAX_SEARCH_DOCUMENT_BYTES: usize = 32 * 1024;
+
 /// Apply a rollout item to the metadata structure.
 pub fn apply_rollout_item(
     metadata: &mut ThreadMetadata,
@@ -40,6 +55,69 @@ pub fn rollout_item_affects_thread_metadata(item: &RolloutItem) -> bool {
     }
 }
 
+/// Extract the user messages, agent messages and command outputs of a rollout
+/// as full-text search documents.
+pub fn thread_search_documents(items: &[RolloutItem]) -> Vec<ThreadSearchDocument> {
+    let command_call_ids = items
+        .iter()
+        .filter_map(|item| match item {
+            RolloutItem::ResponseItem(ResponseItem::FunctionCall { name, call_id, .. })
+                if COMMAND_TOOL_NAMES.contains(&name.as_str()) =>
+            {
+                Some(call_id.as_str())
+            }
+            RolloutItem::ResponseItem(ResponseItem::LocalShellCall {
+                call_id: Some(call_id),
+                ..
+            }) => Some(call_id.as_str()),
+            _ => None,
+        })
+        .collect::<HashSet<_>>();
+
+    items
+        .iter()
+        .filter_map(|item| {
+            let (kind, content) = match item {
+                RolloutItem::EventMsg(EventMsg::UserMessage(user)) => (
+                    ThreadSearchDocumentKind::UserMessage,
+                    strip_user_message_prefix(user.message.as_str()).to_string(),
+                ),
+                RolloutItem::EventMsg(EventMsg::AgentMessage(agent)) => (
+                    ThreadSearchDocumentKind::AgentMessage,
+                    agent.message.trim().to_string(),
+                ),
+                RolloutItem::ResponseItem(ResponseItem::FunctionCallOutput { call_id, output })
+                    if command_call_ids.contains(call_id.as_str()) =>
+                {
+                    (
+                        ThreadSearchDocumentKind::CommandOutput,
+                        output.body.to_text()?.trim().to_string(),
+                    )
+                }
+                _ => return None,
+            };
+            if content.is_empty() {
+                return None;
+            }
+            Some(ThreadSearchDocument {
+                kind,
+                content: truncate_search_document(content),
+            })
+        })
+        .collect()
+}
+
+fn truncate_search_document(mut content: String) -> String {
+    if content.len() > MAX_SEARCH_DOCUMENT_BYTES {
+        let mut end = MAX_SEARCH_DOCUMENT_BYTES;
+        while !content.is_char_boundary(end) {
+            end -= 1;
+        }
+        content.truncate(end);
+    }
+    content
+}
+
 fn apply_session_meta_from_item(metadata: &mut ThreadMetadata, meta_line: &SessionMetaLine) {
     if metadata.id != meta_line.meta.id {
         // Ignore session_meta lines that don't match the canonical thread ID,
@@ -134,13 +212,18 @@ pub(crate) fn enum_to_string<T: Serialize>(value: &T) -> String {
 #[cfg(test)]
 mod tests {
     use super::apply_rollout_item;
+    use super::thread_search_documents;
     use crate::model::ThreadMetadata;
+    use crate::model::ThreadSearchDocument;
+    use crate::model::ThreadSearchDocumentKind;
     use chrono::DateTime;
     use chrono::Utc;
     use codex_protocol::ThreadId;
     use codex_protocol::config_types::ReasoningSummary;
     use codex_protocol::models::ContentItem;
+    use codex_protocol::models::FunctionCallOutputPayload;
     use codex_protocol::models::ResponseItem;
+    use codex_protocol::protocol::AgentMessageEvent;
     use codex_protocol::protocol::AskForApproval;
     use codex_protocol::protocol::EventMsg;
     use codex_protocol::protocol::RolloutItem;
@@ -351,6 +434,63 @@ mod tests {
         }
     }
 
+    #[test]
+    fn thread_search_documents_index_messages_and_command_outputs_only() {
+        let function_call = |name: &str, call_id: &str| {
+            RolloutItem::ResponseItem(ResponseItem::FunctionCall {
+                id: None,
+                name: name.to_string(),
+                namespace: None,
+                arguments: "{}".to_string(),
+                call_id: call_id.to_string(),
+            })
+        };
+        let function_output = |call_id: &str, text: &str| {
+            RolloutItem::ResponseItem(ResponseItem::FunctionCallOutput {
+                call_id: call_id.to_string(),
+                output: FunctionCallOutputPayload::from_text(text.to_string()),
+            })
+        };
+        let items = vec![
+            RolloutItem::EventMsg(EventMsg::UserMessage(UserMessageEvent {
+                message: format!("{USER_MESSAGE_BEGIN} fix the flaky migration test"),
+                images: None,
+                local_images: vec![],
+                text_elements: vec![],
+            })),
+            function_call("exec_command", "call-1"),
+            function_output("call-1", "thread 'migrations' panicked at 0019"),
+            function_call("update_plan", "call-2"),
+            function_output("call-2", "Plan updated"),
+            RolloutItem::EventMsg(EventMsg::AgentMessage(AgentMessageEvent {
+                message: "  The migration now runs in a transaction.  ".to_string(),
+                phase: None,
+            })),
+            RolloutItem::EventMsg(EventMsg::AgentMessage(AgentMessageEvent {
+                message: "   ".to_string(),
+                phase: None,
+            })),
+        ];
+
+        assert_eq!(
+            thread_search_documents(&items),
+            vec![
+                ThreadSearchDocument {
+                    kind: ThreadSearchDocumentKind::UserMessage,
+                    content: "fix the flaky migration test".to_string(),
+                },
+                ThreadSearchDocument {
+                    kind: ThreadSearchDocumentKind::CommandOutput,
+                    content: "thread 'migrations' panicked at 0019".to_string(),
+                },
+                ThreadSearchDocument {
+                    kind: ThreadSearchDocumentKind::AgentMessage,
+                    content: "The migration now runs in a transaction.".to_string(),
+                },
+            ]
+        );
+    }
+
     #[test]
     fn diff_fields_detects_changes() {
         let mut base = metadata_for_test();
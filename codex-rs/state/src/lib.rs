@@ -24,6 +24,7 @@ pub use runtime::StateRuntime;
 /// Most consumers should prefer [`StateRuntime`].
 pub use extract::apply_rollout_item;
 pub use extract::rollout_item_affects_thread_metadata;
+pub use extract::thread_search_documents;
 pub use model::AgentJob;
 pub use model::AgentJobCreateParams;
 pub use model::AgentJobItem;
@@ -44,6 +45,10 @@ pub use model::Stage1OutputRef;
 pub use model::Stage1StartupClaimParams;
 pub use model::ThreadMetadata;
 pub use model::ThreadMetadataBuilder;
+pub use model::ThreadSearchDocument;
+pub use model::ThreadSearchDocumentKind;
+pub use model::ThreadSearchHit;
+pub use model::ThreadSearchIndexTarget;
 pub use model::ThreadsPage;
 pub use runtime::logs_db_filename;
 pub use runtime::logs_db_path;
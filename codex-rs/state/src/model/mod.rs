@@ -3,6 +3,7 @@ mod backfill_state;
 mod log;
 mod memories;
 mod thread_metadata;
+mod thread_search;
 
 pub use agent_job::AgentJob;
 pub use agent_job::AgentJobCreateParams;
@@ -30,6 +31,10 @@ pub use thread_metadata::SortKey;
 pub use thread_metadata::ThreadMetadata;
 pub use thread_metadata::ThreadMetadataBuilder;
 pub use thread_metadata::ThreadsPage;
+pub use thread_search::ThreadSearchDocument;
+pub use thread_search::ThreadSearchDocumentKind;
+pub use thread_search::ThreadSearchHit;
+pub use thread_search::ThreadSearchIndexTarget;
 
 pub(crate) use agent_job::AgentJobItemRow;
 pub(crate) use agent_job::AgentJobRow;
@@ -38,3 +43,4 @@ pub(crate) use memories::stage1_output_ref_from_parts;
 pub(crate) use thread_metadata::ThreadRow;
 pub(crate) use thread_metadata::anchor_from_item;
 pub(crate) use thread_metadata::datetime_to_epoch_seconds;
+pub(crate) use thread_metadata::epoch_seconds_to_datetime;
@@ -0,0 +1,70 @@
+use anyhow::Result;
+use chrono::DateTime;
+use chrono::Utc;
+use codex_protocol::ThreadId;
+use std::path::PathBuf;
+
+use super::ThreadMetadata;
+
+/// The part of a rollout a full-text search document was extracted from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ThreadSearchDocumentKind {
+    /// A message typed by the user.
+    UserMessage,
+    /// A message produced by the agent.
+    AgentMessage,
+    /// The output of a shell command run by the agent.
+    CommandOutput,
+}
+
+impl ThreadSearchDocumentKind {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            ThreadSearchDocumentKind::UserMessage => "user_message",
+            ThreadSearchDocumentKind::AgentMessage => "agent_message",
+            ThreadSearchDocumentKind::CommandOutput => "command_output",
+        }
+    }
+
+    pub fn parse(value: &str) -> Result<Self> {
+        match value {
+            "user_message" => Ok(Self::UserMessage),
+            "agent_message" => Ok(Self::AgentMessage),
+            "command_output" => Ok(Self::CommandOutput),
+            _ => Err(anyhow::anyhow!(
+                "invalid thread search document kind: {value}"
+            )),
+        }
+    }
+}
+
+/// A single searchable chunk of text extracted from a rollout.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ThreadSearchDocument {
+    /// Where in the rollout the text came from.
+    pub kind: ThreadSearchDocumentKind,
+    /// The searchable text.
+    pub content: String,
+}
+
+/// A thread whose full-text search documents are missing or stale.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ThreadSearchIndexTarget {
+    /// The thread id.
+    pub id: ThreadId,
+    /// The rollout to extract documents from.
+    pub rollout_path: PathBuf,
+    /// The thread's `updated_at` when it was selected for indexing.
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A thread matching a full-text search query.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ThreadSearchHit {
+    /// Metadata for the matching thread.
+    pub metadata: ThreadMetadata,
+    /// The kind of document that matched best.
+    pub match_kind: ThreadSearchDocumentKind,
+    /// A short excerpt around the best match.
+    pub snippet: String,
+}
@@ -55,6 +55,7 @@ mod logs;
 mod memories;
 #[cfg(test)]
 mod test_support;
+mod thread_search;
 mod threads;
 
 // "Partition" is the retention bucket we cap at 10 MiB:
@@ -0,0 +1,284 @@
+use super::threads::push_thread_filters;
+use super::*;
+use crate::ThreadSearchDocument;
+use crate::ThreadSearchDocumentKind;
+use crate::ThreadSearchHit;
+use crate::ThreadSearchIndexTarget;
+use crate::model::epoch_seconds_to_datetime;
+
+impl StateRuntime {
+    /// List threads whose full-text search documents are missing or older than
+    /// the thread's last update.
+    pub async fn list_threads_needing_search_index(
+        &self,
+    ) -> anyhow::Result<Vec<ThreadSearchIndexTarget>> {
+        let rows = sqlx::query(
+            r#"
+SELECT id, rollout_path, updated_at
+FROM threads
+WHERE search_indexed_at IS NULL OR search_indexed_at < updated_at
+ORDER BY updated_at DESC, id DESC
+            "#,
+        )
+        .fetch_all(self.pool.as_ref())
+        .await?;
+        rows.into_iter()
+            .map(|row| {
+                let id: String = row.try_get("id")?;
+                let rollout_path: String = row.try_get("rollout_path")?;
+                let updated_at: i64 = row.try_get("updated_at")?;
+                Ok(ThreadSearchIndexTarget {
+                    id: ThreadId::try_from(id)?,
+                    rollout_path: PathBuf::from(rollout_path),
+                    updated_at: epoch_seconds_to_datetime(updated_at)?,
+                })
+            })
+            .collect()
+    }
+
+    /// Replace the full-text search documents for a thread and record the
+    /// `updated_at` they were extracted at.
+    pub async fn replace_thread_search_documents(
+        &self,
+        thread_id: ThreadId,
+        indexed_at: DateTime<Utc>,
+        documents: &[ThreadSearchDocument],
+    ) -> anyhow::Result<()> {
+        let thread_id = thread_id.to_string();
+        let mut tx = self.pool.begin().await?;
+        sqlx::query("DELETE FROM thread_search_fts WHERE thread_id = ?")
+            .bind(thread_id.as_str())
+            .execute(&mut *tx)
+            .await?;
+        for document in documents {
+            sqlx::query(
+                "INSERT INTO thread_search_fts (thread_id, kind, content) VALUES (?, ?, ?)",
+            )
+            .bind(thread_id.as_str())
+            .bind(document.kind.as_str())
+            .bind(document.content.as_str())
+            .execute(&mut *tx)
+            .await?;
+        }
+        sqlx::query("UPDATE threads SET search_indexed_at = ? WHERE id = ?")
+            .bind(datetime_to_epoch_seconds(indexed_at))
+            .bind(thread_id.as_str())
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Search indexed thread content, returning at most one hit per thread
+    /// ordered by relevance.
+    pub async fn search_threads(
+        &self,
+        query: &str,
+        limit: usize,
+        allowed_sources: &[String],
+        model_providers: Option<&[String]>,
+        archived_only: bool,
+    ) -> anyhow::Result<Vec<ThreadSearchHit>> {
+        let Some(match_expression) = fts_match_expression(query) else {
+            return Ok(Vec::new());
+        };
+
+        let mut builder = QueryBuilder::<Sqlite>::new(
+            r#"
+SELECT
+    id,
+    rollout_path,
+    created_at,
+    updated_at,
+    source,
+    agent_nickname,
+    agent_role,
+    model_provider,
+    cwd,
+    cli_version,
+    title,
+    sandbox_policy,
+    approval_mode,
+    tokens_used,
+    first_user_message,
+    archived_at,
+    git_sha,
+    git_branch,
+    git_origin_url,
+    best.kind AS match_kind,
+    best.snippet AS match_snippet
+FROM (
+    SELECT thread_id, kind, snippet, MIN(rank) AS rank
+    FROM (
+        SELECT
+            thread_id,
+            kind,
+            snippet(thread_search_fts, 2, '', '', '…', 24) AS snippet,
+            bm25(thread_search_fts) AS rank
+        FROM thread_search_fts
+        WHERE thread_search_fts MATCH "#,
+        );
+        builder.push_bind(match_expression);
+        builder.push(
+            r#"
+    )
+    GROUP BY thread_id
+) AS best
+JOIN threads ON threads.id = best.thread_id
+            "#,
+        );
+        push_thread_filters(
+            &mut builder,
+            archived_only,
+            allowed_sources,
+            model_providers,
+            None,
+            SortKey::UpdatedAt,
+            None,
+        );
+        builder.push(" ORDER BY best.rank ASC, updated_at DESC, id DESC LIMIT ");
+        builder.push_bind(limit as i64);
+
+        let rows = builder.build().fetch_all(self.pool.as_ref()).await?;
+        rows.into_iter()
+            .map(|row| {
+                let metadata = ThreadRow::try_from_row(&row).and_then(ThreadMetadata::try_from)?;
+                let match_kind: String = row.try_get("match_kind")?;
+                Ok(ThreadSearchHit {
+                    metadata,
+                    match_kind: ThreadSearchDocumentKind::parse(match_kind.as_str())?,
+                    snippet: row.try_get("match_snippet")?,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Turn free-form user input into an FTS5 expression that matches every term
+/// literally, so punctuation in the input is never parsed as query syntax.
+fn fts_match_expression(query: &str) -> Option<String> {
+    let terms = query
+        .split_whitespace()
+        .map(|term| format!("\"{}\"", term.replace('"', "\"\"")))
+        .collect::<Vec<_>>();
+    (!terms.is_empty()).then(|| terms.join(" "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runtime::test_support::test_thread_metadata;
+    use crate::runtime::test_support::unique_temp_dir;
+    use pretty_assertions::assert_eq;
+
+    fn document(kind: ThreadSearchDocumentKind, content: &str) -> ThreadSearchDocument {
+        ThreadSearchDocument {
+            kind,
+            content: content.to_string(),
+        }
+    }
+
+    #[test]
+    fn fts_match_expression_quotes_terms() {
+        assert_eq!(
+            fts_match_expression(r#"  cargo "test" OR-fail* "#),
+            Some(r#""cargo" """test""" "OR-fail*""#.to_string())
+        );
+        assert_eq!(fts_match_expression("   "), None);
+    }
+
+    #[tokio::test]
+    async fn search_threads_ranks_matches_and_reindexes_stale_threads() {
+        let codex_home = unique_temp_dir();
+        let runtime = StateRuntime::init(codex_home.clone(), "test-provider".to_string())
+            .await
+            .expect("state db should initialize");
+        let first_id =
+            ThreadId::from_string("00000000-0000-0000-0000-000000000201").expect("valid thread id");
+        let second_id =
+            ThreadId::from_string("00000000-0000-0000-0000-000000000202").expect("valid thread id");
+        let first = test_thread_metadata(&codex_home, first_id, codex_home.clone());
+        let second = test_thread_metadata(&codex_home, second_id, codex_home.clone());
+        runtime.upsert_thread(&first).await.expect("upsert first");
+        runtime.upsert_thread(&second).await.expect("upsert second");
+
+        let pending = runtime
+            .list_threads_needing_search_index()
+            .await
+            .expect("list pending threads");
+        assert_eq!(pending.len(), 2);
+
+        runtime
+            .replace_thread_search_documents(
+                first_id,
+                first.updated_at,
+                &[
+                    document(
+                        ThreadSearchDocumentKind::UserMessage,
+                        "why does the migration fail",
+                    ),
+                    document(
+                        ThreadSearchDocumentKind::CommandOutput,
+                        "error: migration 0019 failed: no such table",
+                    ),
+                ],
+            )
+            .await
+            .expect("index first");
+        runtime
+            .replace_thread_search_documents(
+                second_id,
+                second.updated_at,
+                &[document(
+                    ThreadSearchDocumentKind::AgentMessage,
+                    "Renamed the config loader.",
+                )],
+            )
+            .await
+            .expect("index second");
+        assert_eq!(
+            runtime
+                .list_threads_needing_search_index()
+                .await
+                .expect("list pending threads"),
+            Vec::new()
+        );
+
+        let hits = runtime
+            .search_threads("no such table", 10, &[], None, false)
+            .await
+            .expect("search");
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].metadata.id, first_id);
+        assert_eq!(hits[0].match_kind, ThreadSearchDocumentKind::CommandOutput);
+        assert_eq!(
+            hits[0].snippet,
+            "error: migration 0019 failed: no such table"
+        );
+
+        let hits = runtime
+            .search_threads("config", 10, &[], None, true)
+            .await
+            .expect("search archived");
+        assert_eq!(hits, Vec::new());
+
+        let mut updated = second.clone();
+        updated.updated_at = second.updated_at + chrono::Duration::seconds(5);
+        runtime.upsert_thread(&updated).await.expect("touch second");
+        let pending = runtime
+            .list_threads_needing_search_index()
+            .await
+            .expect("list pending threads");
+        assert_eq!(
+            pending.iter().map(|target| target.id).collect::<Vec<_>>(),
+            vec![second_id]
+        );
+
+        runtime.delete_thread(first_id).await.expect("delete first");
+        let hits = runtime
+            .search_threads("migration", 10, &[], None, false)
+            .await
+            .expect("search after delete");
+        assert_eq!(hits, Vec::new());
+    }
+}
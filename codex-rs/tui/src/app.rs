@@ -1213,6 +1213,17 @@ impl App {
                     ))
                 }
             }
+            EventMsg::McpSamplingRequest(ev) => Some(ThreadInteractiveRequest::Approval(
+                ApprovalRequest::McpSampling {
+                    thread_id,
+                    thread_label,
+                    server_name: ev.server_name.clone(),
+                    request_id: ev.id.clone(),
+                    system_prompt: ev.system_prompt.clone(),
+                    messages: ev.messages.clone(),
+                    max_tokens: ev.max_tokens,
+                },
+            )),
             EventMsg::RequestPermissions(ev) => Some(ThreadInteractiveRequest::Approval(
                 ApprovalRequest::Permissions {
                     thread_id,
@@ -2162,7 +2173,9 @@ impl App {
                 self.start_fresh_session_with_summary_hint(tui).await;
             }
             AppEvent::OpenResumePicker => {
-                match crate::resume_picker::run_resume_picker(tui, &self.config, false).await? {
+                match crate::resume_picker::run_resume_picker(tui, &self.config, false, None)
+                    .await?
+                {
                     SessionSelection::Resume(target_session) => {
                         let current_cwd = self.config.cwd.clone();
                         let resume_cwd = match crate::resolve_cwd_for_resume_or_fork(
@@ -3433,6 +3446,29 @@ impl App {
                         "E L I C I T A T I O N".to_string(),
                     ));
                 }
+                ApprovalRequest::McpSampling {
+                    server_name,
+                    system_prompt,
+                    messages,
+                    max_tokens,
+                    ..
+                } => {
+                    let _ = tui.enter_alt_screen();
+                    let mut lines = vec![
+                        Line::from(vec!["Server: ".into(), server_name.bold()]),
+                        Line::from(vec!["Max tokens: ".into(), max_tokens.to_string().into()]),
+                        Line::from(""),
+                    ];
+                    if let Some(system_prompt) = system_prompt {
+                        lines.push(Line::from(vec!["System: ".into(), system_prompt.italic()]));
+                        lines.push(Line::from(""));
+                    }
+                    lines.extend(crate::bottom_pane::sampling_message_lines(&messages));
+                    self.overlay = Some(Overlay::new_static_with_renderables(
+                        vec![Box::new(Paragraph::new(lines).wrap(Wrap { trim: false }))],
+                        "S A M P L I N G".to_string(),
+                    ));
+                }
             },
             #[cfg(not(target_os = "linux"))]
             AppEvent::TranscriptionComplete { id, text } => {
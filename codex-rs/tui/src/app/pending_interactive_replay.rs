@@ -23,8 +23,8 @@ impl ElicitationRequestKey {
 // Tracks which interactive prompts are still unresolved in the thread-event buffer.
 //
 // Thread snapshots are replayed when switching threads/agents. Most events should replay
-// verbatim, but interactive prompts (approvals, request_user_input, MCP elicitations) must
-// only replay if they are still pending. This state is updated from:
+// verbatim, but interactive prompts (approvals, request_user_input, MCP elicitations and
+// sampling requests) must only replay if they are still pending. This state is updated from:
 // - inbound events (`note_event`)
 // - outbound ops that resolve a prompt (`note_outbound_op`)
 // - buffer eviction (`note_evicted_event`)
@@ -39,6 +39,7 @@ pub(super) struct PendingInteractiveReplayState {
     patch_approval_call_ids: HashSet<String>,
     patch_approval_call_ids_by_turn_id: HashMap<String, Vec<String>>,
     elicitation_requests: HashSet<ElicitationRequestKey>,
+    mcp_sampling_requests: HashSet<ElicitationRequestKey>,
     request_permissions_call_ids: HashSet<String>,
     request_permissions_call_ids_by_turn_id: HashMap<String, Vec<String>>,
     request_user_input_call_ids: HashSet<String>,
@@ -52,6 +53,7 @@ impl PendingInteractiveReplayState {
             EventMsg::ExecApprovalRequest(_)
                 | EventMsg::ApplyPatchApprovalRequest(_)
                 | EventMsg::ElicitationRequest(_)
+                | EventMsg::McpSamplingRequest(_)
                 | EventMsg::RequestPermissions(_)
                 | EventMsg::ExecCommandBegin(_)
                 | EventMsg::PatchApplyBegin(_)
@@ -67,6 +69,7 @@ impl PendingInteractiveReplayState {
             Op::ExecApproval { .. }
                 | Op::PatchApproval { .. }
                 | Op::ResolveElicitation { .. }
+                | Op::ResolveMcpSampling { .. }
                 | Op::RequestPermissionsResponse { .. }
                 | Op::UserInputAnswer { .. }
                 | Op::Shutdown
@@ -103,6 +106,17 @@ impl PendingInteractiveReplayState {
                         request_id.clone(),
                     ));
             }
+            Op::ResolveMcpSampling {
+                server_name,
+                request_id,
+                ..
+            } => {
+                self.mcp_sampling_requests
+                    .remove(&ElicitationRequestKey::new(
+                        server_name.clone(),
+                        request_id.clone(),
+                    ));
+            }
             Op::RequestPermissionsResponse { id, .. } => {
                 self.request_permissions_call_ids.remove(id);
                 Self::remove_call_id_from_turn_map(
@@ -170,6 +184,13 @@ impl PendingInteractiveReplayState {
                     ev.id.clone(),
                 ));
             }
+            EventMsg::McpSamplingRequest(ev) => {
+                self.mcp_sampling_requests
+                    .insert(ElicitationRequestKey::new(
+                        ev.server_name.clone(),
+                        ev.id.clone(),
+                    ));
+            }
             EventMsg::RequestUserInput(ev) => {
                 self.request_user_input_call_ids.insert(ev.call_id.clone());
                 self.request_user_input_call_ids_by_turn_id
@@ -231,6 +252,13 @@ impl PendingInteractiveReplayState {
                         ev.id.clone(),
                     ));
             }
+            EventMsg::McpSamplingRequest(ev) => {
+                self.mcp_sampling_requests
+                    .remove(&ElicitationRequestKey::new(
+                        ev.server_name.clone(),
+                        ev.id.clone(),
+                    ));
+            }
             EventMsg::RequestUserInput(ev) => {
                 self.request_user_input_call_ids.remove(&ev.call_id);
                 let mut remove_turn_entry = false;
@@ -284,6 +312,13 @@ impl PendingInteractiveReplayState {
                         ev.id.clone(),
                     ))
             }
+            EventMsg::McpSamplingRequest(ev) => {
+                self.mcp_sampling_requests
+                    .contains(&ElicitationRequestKey::new(
+                        ev.server_name.clone(),
+                        ev.id.clone(),
+                    ))
+            }
             EventMsg::RequestUserInput(ev) => {
                 self.request_user_input_call_ids.contains(&ev.call_id)
             }
@@ -298,6 +333,7 @@ impl PendingInteractiveReplayState {
         !self.exec_approval_call_ids.is_empty()
             || !self.patch_approval_call_ids.is_empty()
             || !self.elicitation_requests.is_empty()
+            || !self.mcp_sampling_requests.is_empty()
             || !self.request_permissions_call_ids.is_empty()
     }
 
@@ -366,6 +402,7 @@ impl PendingInteractiveReplayState {
         self.patch_approval_call_ids.clear();
         self.patch_approval_call_ids_by_turn_id.clear();
         self.elicitation_requests.clear();
+        self.mcp_sampling_requests.clear();
         self.request_permissions_call_ids.clear();
         self.request_permissions_call_ids_by_turn_id.clear();
         self.request_user_input_call_ids.clear();
@@ -668,6 +705,39 @@ mod tests {
         );
     }
 
+    #[test]
+    fn thread_event_snapshot_drops_resolved_mcp_sampling_request() {
+        let mut store = ThreadEventStore::new(8);
+        let request_id = codex_protocol::mcp::RequestId::Integer(3);
+        store.push_event(Event {
+            id: "ev-1".to_string(),
+            msg: EventMsg::McpSamplingRequest(codex_protocol::approvals::McpSamplingRequestEvent {
+                turn_id: None,
+                server_name: "server-1".to_string(),
+                id: request_id.clone(),
+                system_prompt: None,
+                messages: vec![codex_protocol::approvals::McpSamplingMessage {
+                    role: codex_protocol::approvals::McpSamplingRole::User,
+                    text: "hello".to_string(),
+                }],
+                max_tokens: 64,
+            }),
+        });
+        assert_eq!(store.has_pending_thread_approvals(), true);
+
+        store.note_outbound_op(&Op::ResolveMcpSampling {
+            server_name: "server-1".to_string(),
+            request_id,
+            decision: codex_protocol::approvals::McpSamplingDecision::Decline,
+        });
+
+        assert_eq!(store.has_pending_thread_approvals(), false);
+        assert!(
+            store.snapshot().events.is_empty(),
+            "resolved sampling request should not replay on thread switch"
+        );
+    }
+
     #[test]
     fn thread_event_store_reports_pending_thread_approvals() {
         let mut store = ThreadEventStore::new(8);
@@ -18,6 +18,9 @@ use crate::render::renderable::ColumnRenderable;
 use crate::render::renderable::Renderable;
 use codex_core::features::Features;
 use codex_protocol::ThreadId;
+use codex_protocol::approvals::McpSamplingDecision;
+use codex_protocol::approvals::McpSamplingMessage;
+use codex_protocol::approvals::McpSamplingRole;
 use codex_protocol::mcp::RequestId;
 use codex_protocol::models::MacOsAutomationPermission;
 use codex_protocol::models::MacOsContactsPermission;
@@ -78,6 +81,15 @@ pub(crate) enum ApprovalRequest {
         request_id: RequestId,
         message: String,
     },
+    McpSampling {
+        thread_id: ThreadId,
+        thread_label: Option<String>,
+        server_name: String,
+        request_id: RequestId,
+        system_prompt: Option<String>,
+        messages: Vec<McpSamplingMessage>,
+        max_tokens: u32,
+    },
 }
 
 impl ApprovalRequest {
@@ -86,7 +98,8 @@ impl ApprovalRequest {
             ApprovalRequest::Exec { thread_id, .. }
             | ApprovalRequest::Permissions { thread_id, .. }
             | ApprovalRequest::ApplyPatch { thread_id, .. }
-            | ApprovalRequest::McpElicitation { thread_id, .. } => *thread_id,
+            | ApprovalRequest::McpElicitation { thread_id, .. }
+            | ApprovalRequest::McpSampling { thread_id, .. } => *thread_id,
         }
     }
 
@@ -95,7 +108,8 @@ impl ApprovalRequest {
             ApprovalRequest::Exec { thread_label, .. }
             | ApprovalRequest::Permissions { thread_label, .. }
             | ApprovalRequest::ApplyPatch { thread_label, .. }
-            | ApprovalRequest::McpElicitation { thread_label, .. } => thread_label.as_deref(),
+            | ApprovalRequest::McpElicitation { thread_label, .. }
+            | ApprovalRequest::McpSampling { thread_label, .. } => thread_label.as_deref(),
         }
     }
 }
@@ -180,6 +194,10 @@ impl ApprovalOverlay {
                 elicitation_options(),
                 format!("{server_name} needs your approval."),
             ),
+            ApprovalRequest::McpSampling { server_name, .. } => (
+                sampling_options(),
+                format!("{server_name} wants to use the model."),
+            ),
         };
 
         let header = Box::new(ColumnRenderable::with([
@@ -243,6 +261,16 @@ impl ApprovalOverlay {
                 ) => {
                     self.handle_elicitation_decision(server_name, request_id, *decision);
                 }
+                (
+                    ApprovalRequest::McpSampling {
+                        server_name,
+                        request_id,
+                        ..
+                    },
+                    ApprovalDecision::McpSampling(decision),
+                ) => {
+                    self.handle_sampling_decision(server_name, request_id, *decision);
+                }
                 _ => {}
             }
         }
@@ -357,6 +385,29 @@ impl ApprovalOverlay {
         });
     }
 
+    fn handle_sampling_decision(
+        &self,
+        server_name: &str,
+        request_id: &RequestId,
+        decision: McpSamplingDecision,
+    ) {
+        let Some(thread_id) = self
+            .current_request
+            .as_ref()
+            .map(ApprovalRequest::thread_id)
+        else {
+            return;
+        };
+        self.app_event_tx.send(AppEvent::SubmitThreadOp {
+            thread_id,
+            op: Op::ResolveMcpSampling {
+                server_name: server_name.to_string(),
+                request_id: request_id.clone(),
+                decision,
+            },
+        });
+    }
+
     fn advance_queue(&mut self) {
         if let Some(next) = self.queue.pop() {
             self.set_current(next);
@@ -457,6 +508,17 @@ impl BottomPaneView for ApprovalOverlay {
                         ElicitationAction::Cancel,
                     );
                 }
+                ApprovalRequest::McpSampling {
+                    server_name,
+                    request_id,
+                    ..
+                } => {
+                    self.handle_sampling_decision(
+                        server_name,
+                        request_id,
+                        McpSamplingDecision::Decline,
+                    );
+                }
             }
         }
         self.queue.clear();
@@ -628,13 +690,59 @@ fn build_header(request: &ApprovalRequest) -> Box<dyn Renderable> {
             let header = Paragraph::new(lines).wrap(Wrap { trim: false });
             Box::new(header)
         }
+        ApprovalRequest::McpSampling {
+            thread_label,
+            server_name,
+            system_prompt,
+            messages,
+            max_tokens,
+            ..
+        } => {
+            let mut lines = Vec::new();
+            if let Some(thread_label) = thread_label {
+                lines.push(Line::from(vec![
+                    "Thread: ".into(),
+                    thread_label.clone().bold(),
+                ]));
+                lines.push(Line::from(""));
+            }
+            lines.push(Line::from(vec![
+                "Server: ".into(),
+                server_name.clone().bold(),
+                format!(" (up to {max_tokens} tokens)").dim(),
+            ]));
+            lines.push(Line::from(""));
+            if let Some(system_prompt) = system_prompt {
+                lines.push(Line::from(vec![
+                    "System: ".into(),
+                    system_prompt.clone().italic(),
+                ]));
+            }
+            lines.extend(sampling_message_lines(messages));
+            let header = Paragraph::new(lines).wrap(Wrap { trim: false });
+            Box::new(header)
+        }
     }
 }
 
+pub(crate) fn sampling_message_lines(messages: &[McpSamplingMessage]) -> Vec<Line<'static>> {
+    messages
+        .iter()
+        .map(|message| {
+            let role = match message.role {
+                McpSamplingRole::User => "User: ",
+                McpSamplingRole::Assistant => "Assistant: ",
+            };
+            Line::from(vec![role.bold(), message.text.clone().into()])
+        })
+        .collect()
+}
+
 #[derive(Clone)]
 enum ApprovalDecision {
     Review(ReviewDecision),
     McpElicitation(ElicitationAction),
+    McpSampling(McpSamplingDecision),
 }
 
 #[derive(Clone)]
@@ -897,6 +1005,23 @@ fn elicitation_options() -> Vec<ApprovalOption> {
     ]
 }
 
+fn sampling_options() -> Vec<ApprovalOption> {
+    vec![
+        ApprovalOption {
+            label: "Yes, run this request".to_string(),
+            decision: ApprovalDecision::McpSampling(McpSamplingDecision::Approve),
+            display_shortcut: None,
+            additional_shortcuts: vec![key_hint::plain(KeyCode::Char('y'))],
+        },
+        ApprovalOption {
+            label: "No, decline".to_string(),
+            decision: ApprovalDecision::McpSampling(McpSamplingDecision::Decline),
+            display_shortcut: Some(key_hint::plain(KeyCode::Esc)),
+            additional_shortcuts: vec![key_hint::plain(KeyCode::Char('n'))],
+        },
+    ]
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1310,6 +1435,46 @@ mod tests {
         );
     }
 
+    #[test]
+    fn sampling_prompt_shows_conversation_and_ctrl_c_declines() {
+        let (tx, mut rx) = unbounded_channel::<AppEvent>();
+        let tx = AppEventSender::new(tx);
+        let mut view = ApprovalOverlay::new(
+            ApprovalRequest::McpSampling {
+                thread_id: ThreadId::new(),
+                thread_label: None,
+                server_name: "docs".to_string(),
+                request_id: RequestId::Integer(7),
+                system_prompt: Some("Be brief.".to_string()),
+                messages: vec![McpSamplingMessage {
+                    role: McpSamplingRole::User,
+                    text: "Summarize the README".to_string(),
+                }],
+                max_tokens: 256,
+            },
+            tx,
+            Features::with_defaults(),
+        );
+
+        let rendered = render_overlay_lines(&view, 80);
+        assert!(rendered.contains("docs wants to use the model."));
+        assert!(rendered.contains("User: Summarize the README"));
+        assert!(rendered.contains("(up to 256 tokens)"));
+
+        assert_eq!(CancellationEvent::Handled, view.on_ctrl_c());
+        let mut decision = None;
+        while let Ok(ev) = rx.try_recv() {
+            if let AppEvent::SubmitThreadOp {
+                op: Op::ResolveMcpSampling { decision: d, .. },
+                ..
+            } = ev
+            {
+                decision = Some(d);
+            }
+        }
+        assert_eq!(decision, Some(McpSamplingDecision::Decline));
+    }
+
     #[test]
     fn additional_permissions_prompt_shows_permission_rule_line() {
         let (tx, _rx) = unbounded_channel::<AppEvent>();
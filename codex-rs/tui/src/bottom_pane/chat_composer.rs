@@ -153,6 +153,7 @@ use super::chat_composer_history::HistoryEntry;
 use super::command_popup::CommandItem;
 use super::command_popup::CommandPopup;
 use super::command_popup::CommandPopupFlags;
+use super::command_popup::McpPromptCommand;
 use super::file_search_popup::FileSearchPopup;
 use super::footer::CollaborationModeIndicator;
 use super::footer::FooterMode;
@@ -182,10 +183,12 @@ use super::skill_popup::SkillPopup;
 use super::slash_commands;
 use super::slash_commands::BuiltinCommandFlags;
 use crate::bottom_pane::paste_burst::FlushResult;
+use crate::bottom_pane::prompt_args::command_with_arg_placeholders;
+use crate::bottom_pane::prompt_args::custom_prompt_argument_names;
 use crate::bottom_pane::prompt_args::expand_custom_prompt;
 use crate::bottom_pane::prompt_args::expand_if_numeric_with_positional_args;
+use crate::bottom_pane::prompt_args::parse_mcp_prompt_arguments;
 use crate::bottom_pane::prompt_args::parse_slash_name;
-use crate::bottom_pane::prompt_args::prompt_argument_names;
 use crate::bottom_pane::prompt_args::prompt_command_with_arg_placeholders;
 use crate::bottom_pane::prompt_args::prompt_has_numeric_placeholders;
 use crate::render::Insets;
@@ -215,6 +218,7 @@ use crate::tui::FrameRequester;
 use crate::ui_consts::LIVE_PREFIX_COLS;
 use codex_chatgpt::connectors;
 use codex_chatgpt::connectors::AppInfo;
+use codex_core::custom_prompts::shell_commands;
 use codex_core::plugins::PluginCapabilitySummary;
 use codex_core::skills::model::SkillMetadata;
 use codex_file_search::FileMatch;
@@ -258,6 +262,18 @@ pub enum InputResult {
     },
     Command(SlashCommand),
     CommandWithArgs(SlashCommand, String, Vec<TextElement>),
+    /// The user invoked a prompt published by an MCP server.
+    McpPrompt {
+        server: String,
+        name: String,
+        arguments: HashMap<String, String>,
+    },
+    /// The user invoked a custom prompt with `{{shell: cmd}}` directives,
+    /// which Codex runs before the prompt is sent.
+    CustomPromptShell {
+        text: String,
+        text_elements: Vec<TextElement>,
+    },
     None,
 }
 
@@ -381,6 +397,7 @@ pub(crate) struct ChatComposer {
     // When true, disables paste-burst logic and inserts characters immediately.
     disable_paste_burst: bool,
     custom_prompts: Vec<CustomPrompt>,
+    mcp_prompts: Vec<McpPromptCommand>,
     footer_mode: FooterMode,
     footer_hint_override: Option<Vec<(String, String)>>,
     remote_image_urls: Vec<String>,
@@ -506,6 +523,7 @@ impl ChatComposer {
             paste_burst: PasteBurst::default(),
             disable_paste_burst: false,
             custom_prompts: Vec::new(),
+            mcp_prompts: Vec::new(),
             footer_mode: FooterMode::ComposerEmpty,
             footer_hint_override: None,
             remote_image_urls: Vec::new(),
@@ -1440,6 +1458,16 @@ impl ChatComposer {
                                 }
                             }
                         }
+                        CommandItem::McpPrompt(idx) => {
+                            if let Some(prompt) = popup.mcp_prompt(idx) {
+                                let (text, cursor) = command_with_arg_placeholders(
+                                    &prompt.command_name(),
+                                    &prompt.argument_names(),
+                                );
+                                self.textarea.set_text_clearing_elements(&text);
+                                cursor_target = Some(cursor);
+                            }
+                        }
                     }
                     if let Some(pos) = cursor_target {
                         self.textarea.set_cursor(pos);
@@ -1477,10 +1505,7 @@ impl ChatComposer {
                     self.pending_pastes.clear();
                     self.textarea.set_text_clearing_elements("");
                     return (
-                        InputResult::Submitted {
-                            text: expanded.text,
-                            text_elements: expanded.text_elements,
-                        },
+                        custom_prompt_result(expanded.text, expanded.text_elements),
                         true,
                     );
                 }
@@ -1508,13 +1533,7 @@ impl ChatComposer {
                                             &text_elements,
                                         );
                                         self.textarea.set_text_clearing_elements("");
-                                        return (
-                                            InputResult::Submitted {
-                                                text,
-                                                text_elements,
-                                            },
-                                            true,
-                                        );
+                                        return (custom_prompt_result(text, text_elements), true);
                                     }
                                     PromptSelectionAction::Insert { text, cursor } => {
                                         let target = cursor.unwrap_or(text.len());
@@ -1527,6 +1546,28 @@ impl ChatComposer {
                             }
                             return (InputResult::None, true);
                         }
+                        CommandItem::McpPrompt(idx) => {
+                            if let Some(prompt) = popup.mcp_prompt(idx) {
+                                let argument_names = prompt.argument_names();
+                                if argument_names.is_empty() {
+                                    let result = InputResult::McpPrompt {
+                                        server: prompt.server.clone(),
+                                        name: prompt.prompt.name.clone(),
+                                        arguments: HashMap::new(),
+                                    };
+                                    self.textarea.set_text_clearing_elements("");
+                                    return (result, true);
+                                }
+                                // Let the user fill in arguments before sending.
+                                let (text, cursor) = command_with_arg_placeholders(
+                                    &prompt.command_name(),
+                                    &argument_names,
+                                );
+                                self.textarea.set_text_clearing_elements(&text);
+                                self.textarea.set_cursor(cursor);
+                            }
+                            return (InputResult::None, true);
+                        }
                     }
                 }
                 // Fallback to default newline handling if no command selected.
@@ -2324,7 +2365,8 @@ impl ChatComposer {
                             .any(|prompt| prompt.name == prompt_name)
                     })
                     .unwrap_or(false);
-                if !is_builtin && !is_known_prompt {
+                let is_mcp_prompt = self.find_mcp_prompt(name).is_some();
+                if !is_builtin && !is_known_prompt && !is_mcp_prompt {
                     let message = format!(
                         r#"Unrecognized command '/{name}'. Type "/" for a list of supported commands."#
                     );
@@ -2480,8 +2522,22 @@ impl ChatComposer {
         if let Some(result) = self.try_dispatch_slash_command_with_args() {
             return (result, true);
         }
+        if let Some(result) = self.try_dispatch_mcp_prompt() {
+            return (result, true);
+        }
 
         if let Some((text, text_elements)) = self.prepare_submission_text(true) {
+            if self.is_custom_prompt_invocation(&original_input)
+                && !shell_commands(&text).is_empty()
+            {
+                return (
+                    InputResult::CustomPromptShell {
+                        text,
+                        text_elements,
+                    },
+                    true,
+                );
+            }
             if should_queue {
                 (
                     InputResult::Queued {
@@ -2571,6 +2627,57 @@ impl ChatComposer {
         ))
     }
 
+    /// Whether `text` starts with `/prompts:<name>` for a known custom prompt.
+    fn is_custom_prompt_invocation(&self, text: &str) -> bool {
+        let first_line = text.lines().next().unwrap_or("");
+        parse_slash_name(first_line)
+            .and_then(|(name, _, _)| name.strip_prefix(&format!("{PROMPTS_CMD_PREFIX}:")))
+            .is_some_and(|prompt_name| {
+                self.custom_prompts
+                    .iter()
+                    .any(|prompt| prompt.name == prompt_name)
+            })
+    }
+
+    /// Check if the input invokes an MCP prompt (`/server:prompt key=value ...`).
+    /// Returns Some(InputResult) if the name matches a known MCP prompt, None otherwise.
+    fn try_dispatch_mcp_prompt(&mut self) -> Option<InputResult> {
+        if !self.slash_commands_enabled() {
+            return None;
+        }
+        let (text, _) = Self::expand_pending_pastes(
+            self.textarea.text(),
+            self.textarea.text_elements(),
+            &self.pending_pastes,
+        );
+        if text.starts_with(' ') {
+            return None;
+        }
+        let (name, rest, _rest_offset) = parse_slash_name(&text)?;
+        let prompt = self.find_mcp_prompt(name)?;
+        let declared = prompt.prompt.arguments.as_deref().unwrap_or_default();
+        match parse_mcp_prompt_arguments(name, rest, declared) {
+            Ok(arguments) => {
+                let result = InputResult::McpPrompt {
+                    server: prompt.server.clone(),
+                    name: prompt.prompt.name.clone(),
+                    arguments,
+                };
+                self.history
+                    .record_local_submission(HistoryEntry::new(text.trim_end().to_string()));
+                self.pending_pastes.clear();
+                self.textarea.set_text_clearing_elements("");
+                Some(result)
+            }
+            Err(err) => {
+                self.app_event_tx.send(AppEvent::InsertHistoryCell(Box::new(
+                    history_cell::new_error_event(err.user_message()),
+                )));
+                Some(InputResult::None)
+            }
+        }
+    }
+
     /// Expand pending placeholders and extract normalized inline-command args.
     ///
     /// Inline-arg commands are initially dispatched using the raw draft so command rejection does
@@ -3391,7 +3498,7 @@ impl ChatComposer {
                 .iter()
                 .any(|prompt| prompt.name == prompt_name);
         }
-        false
+        self.find_mcp_prompt(name).is_some()
     }
 
     /// If the cursor is currently within a slash command on the first line,
@@ -3439,7 +3546,10 @@ impl ChatComposer {
 
         self.custom_prompts.iter().any(|prompt| {
             fuzzy_match(&format!("{PROMPTS_CMD_PREFIX}:{}", prompt.name), name).is_some()
-        })
+        }) || self
+            .mcp_prompts
+            .iter()
+            .any(|prompt| fuzzy_match(&prompt.command_name(), name).is_some())
     }
 
     /// Synchronize `self.command_popup` with the current text in the
@@ -3500,6 +3610,7 @@ impl ChatComposer {
                             windows_degraded_sandbox_active: self.windows_degraded_sandbox_active,
                         },
                     );
+                    command_popup.set_mcp_prompts(self.mcp_prompts.clone());
                     command_popup.on_composer_text_change(first_line.to_string());
                     self.active_popup = ActivePopup::Command(command_popup);
                 }
@@ -3513,6 +3624,25 @@ impl ChatComposer {
         }
     }
 
+    pub(crate) fn set_mcp_prompts(&mut self, prompts: Vec<McpPromptCommand>) {
+        self.mcp_prompts = prompts.clone();
+        if let ActivePopup::Command(popup) = &mut self.active_popup {
+            popup.set_mcp_prompts(prompts);
+        }
+    }
+
+    /// Finds the MCP prompt invoked as `/{name}`. Custom prompts own the
+    /// `prompts:` namespace, so it never resolves to an MCP prompt.
+    fn find_mcp_prompt(&self, name: &str) -> Option<&McpPromptCommand> {
+        let (server, prompt_name) = name.split_once(':')?;
+        if server == PROMPTS_CMD_PREFIX {
+            return None;
+        }
+        self.mcp_prompts
+            .iter()
+            .find(|prompt| prompt.server == server && prompt.prompt.name == prompt_name)
+    }
+
     /// Synchronize `self.file_search_popup` with the current text in the textarea.
     /// Note this is only called when self.active_popup is NOT Command.
     fn sync_file_search_popup(&mut self, query: String) {
@@ -4440,13 +4570,29 @@ impl ChatComposer {
     }
 }
 
+/// Result for an expanded custom prompt: prompts with `{{shell: cmd}}`
+/// directives go to Codex to run them before the prompt is sent.
+fn custom_prompt_result(text: String, text_elements: Vec<TextElement>) -> InputResult {
+    if shell_commands(&text).is_empty() {
+        InputResult::Submitted {
+            text,
+            text_elements,
+        }
+    } else {
+        InputResult::CustomPromptShell {
+            text,
+            text_elements,
+        }
+    }
+}
+
 fn prompt_selection_action(
     prompt: &CustomPrompt,
     first_line: &str,
     mode: PromptSelectionMode,
     text_elements: &[TextElement],
 ) -> PromptSelectionAction {
-    let named_args = prompt_argument_names(&prompt.content);
+    let named_args = custom_prompt_argument_names(prompt);
     let has_numeric = prompt_has_numeric_placeholders(&prompt.content);
 
     match mode {
@@ -4508,6 +4654,7 @@ impl Drop for ChatComposer {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use codex_protocol::custom_prompts::CustomPromptArgument;
     use image::ImageBuffer;
     use image::Rgba;
     use pretty_assertions::assert_eq;
@@ -5381,6 +5528,7 @@ mod tests {
                 policy: None,
                 permission_profile: None,
                 managed_network_override: None,
+                resource_limits: None,
                 path_to_skills_md: PathBuf::from("/tmp/repo/google-calendar/SKILL.md"),
                 scope: codex_protocol::protocol::SkillScope::Repo,
             }]));
@@ -8320,6 +8468,64 @@ mod tests {
         assert_eq!(imgs, vec![tmp_path]);
     }
 
+    #[test]
+    fn mcp_prompt_completion_inserts_placeholders_and_dispatches_arguments() {
+        use codex_protocol::mcp::Prompt as McpPrompt;
+        use codex_protocol::mcp::PromptArgument as McpPromptArgument;
+
+        let (tx, _rx) = unbounded_channel::<AppEvent>();
+        let sender = AppEventSender::new(tx);
+        let mut composer = ChatComposer::new(
+            true,
+            sender,
+            false,
+            "Ask Codex to do anything".to_string(),
+            false,
+        );
+        let argument = |name: &str, required: bool| McpPromptArgument {
+            name: name.to_string(),
+            title: None,
+            description: None,
+            required: Some(required),
+        };
+        composer.set_mcp_prompts(vec![McpPromptCommand {
+            server: "docs".to_string(),
+            prompt: McpPrompt {
+                name: "review".to_string(),
+                title: None,
+                description: None,
+                arguments: Some(vec![argument("file", true), argument("focus", false)]),
+            },
+        }]);
+
+        type_chars_humanlike(
+            &mut composer,
+            &['/', 'd', 'o', 'c', 's', ':', 'r', 'e', 'v'],
+        );
+        composer.handle_key_event(KeyEvent::new(KeyCode::Tab, KeyModifiers::NONE));
+        assert_eq!(composer.textarea.text(), r#"/docs:review file="" focus="""#);
+
+        // The required argument is still empty, so the draft is kept.
+        let (result, _needs_redraw) =
+            composer.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        assert_eq!(result, InputResult::None);
+        assert_eq!(composer.textarea.text(), r#"/docs:review file="" focus="""#);
+
+        composer.textarea.set_cursor(r#"/docs:review file=""#.len());
+        type_chars_humanlike(&mut composer, &['a', '.', 'r', 's']);
+        let (result, _needs_redraw) =
+            composer.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        assert_eq!(
+            result,
+            InputResult::McpPrompt {
+                server: "docs".to_string(),
+                name: "review".to_string(),
+                arguments: HashMap::from([("file".to_string(), "a.rs".to_string())]),
+            }
+        );
+        assert!(composer.textarea.is_empty());
+    }
+
     #[test]
     fn selecting_custom_prompt_without_args_submits_content() {
         let prompt_text = "Hello from saved prompt";
@@ -8341,6 +8547,7 @@ mod tests {
             content: prompt_text.to_string(),
             description: None,
             argument_hint: None,
+            arguments: Vec::new(),
         }]);
 
         type_chars_humanlike(
@@ -8379,6 +8586,7 @@ mod tests {
             content: "Review $USER changes on $BRANCH".to_string(),
             description: None,
             argument_hint: None,
+            arguments: Vec::new(),
         }]);
 
         composer
@@ -8396,6 +8604,79 @@ mod tests {
         assert!(composer.textarea.is_empty());
     }
 
+    #[test]
+    fn custom_prompt_submission_allows_missing_optional_arguments() {
+        let (tx, _rx) = unbounded_channel::<AppEvent>();
+        let sender = AppEventSender::new(tx);
+        let mut composer = ChatComposer::new(
+            true,
+            sender,
+            false,
+            "Ask Codex to do anything".to_string(),
+            false,
+        );
+
+        composer.set_custom_prompts(vec![CustomPrompt {
+            name: "my-prompt".to_string(),
+            path: "/tmp/my-prompt.md".to_string().into(),
+            content: "Review $FILE.$FOCUS".to_string(),
+            description: None,
+            argument_hint: None,
+            arguments: vec![CustomPromptArgument {
+                name: "FOCUS".to_string(),
+                required: false,
+            }],
+        }]);
+
+        composer
+            .textarea
+            .set_text_clearing_elements("/prompts:my-prompt FILE=lib.rs");
+
+        let (result, _needs_redraw) =
+            composer.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+
+        assert!(matches!(
+            result,
+            InputResult::Submitted { text, .. } if text == "Review lib.rs."
+        ));
+    }
+
+    #[test]
+    fn custom_prompt_with_shell_directive_is_expanded_by_codex() {
+        let (tx, _rx) = unbounded_channel::<AppEvent>();
+        let sender = AppEventSender::new(tx);
+        let mut composer = ChatComposer::new(
+            true,
+            sender,
+            false,
+            "Ask Codex to do anything".to_string(),
+            false,
+        );
+
+        composer.set_custom_prompts(vec![CustomPrompt {
+            name: "status".to_string(),
+            path: "/tmp/status.md".to_string().into(),
+            content: "Summarize on $BRANCH:\n{{shell: git status --short}}".to_string(),
+            description: None,
+            argument_hint: None,
+            arguments: Vec::new(),
+        }]);
+
+        composer
+            .textarea
+            .set_text_clearing_elements("/prompts:status BRANCH=main");
+
+        let (result, _needs_redraw) =
+            composer.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+
+        assert!(matches!(
+            result,
+            InputResult::CustomPromptShell { text, .. }
+                if text == "Summarize on main:\n{{shell: git status --short}}"
+        ));
+        assert!(composer.textarea.is_empty());
+    }
+
     #[test]
     fn custom_prompt_submission_accepts_quoted_values() {
         let (tx, _rx) = unbounded_channel::<AppEvent>();
@@ -8414,6 +8695,7 @@ mod tests {
             content: "Pair $USER with $BRANCH".to_string(),
             description: None,
             argument_hint: None,
+            arguments: Vec::new(),
         }]);
 
         composer
@@ -8453,6 +8735,7 @@ mod tests {
             content: "Review $IMG".to_string(),
             description: None,
             argument_hint: None,
+            arguments: Vec::new(),
         }]);
 
         composer
@@ -8508,6 +8791,7 @@ mod tests {
             content: "Review $IMG".to_string(),
             description: None,
             argument_hint: None,
+            arguments: Vec::new(),
         }]);
 
         composer
@@ -8564,6 +8848,7 @@ mod tests {
             content: "Review changes".to_string(),
             description: None,
             argument_hint: None,
+            arguments: Vec::new(),
         }]);
 
         composer
@@ -8613,6 +8898,7 @@ mod tests {
             content: "Please review the following code:\n\n$1".to_string(),
             description: None,
             argument_hint: None,
+            arguments: Vec::new(),
         }]);
 
         // Type the slash command
@@ -8676,6 +8962,7 @@ mod tests {
             content: "Review $IMG\n\n$CODE".to_string(),
             description: None,
             argument_hint: None,
+            arguments: Vec::new(),
         }]);
 
         composer
@@ -8802,6 +9089,7 @@ mod tests {
             content: "Review $USER changes".to_string(),
             description: None,
             argument_hint: None,
+            arguments: Vec::new(),
         }]);
 
         composer
@@ -8852,6 +9140,7 @@ mod tests {
             content: "Review $USER changes on $BRANCH".to_string(),
             description: None,
             argument_hint: None,
+            arguments: Vec::new(),
         }]);
 
         // Provide only one of the required args
@@ -8907,6 +9196,7 @@ mod tests {
             content: prompt_text.to_string(),
             description: None,
             argument_hint: None,
+            arguments: Vec::new(),
         }]);
 
         // Type the slash command with two args and hit Enter to submit.
@@ -8945,6 +9235,7 @@ mod tests {
             content: "Hello".to_string(),
             description: None,
             argument_hint: None,
+            arguments: Vec::new(),
         }]);
 
         composer.attach_image(PathBuf::from("/tmp/unused.png"));
@@ -8983,6 +9274,7 @@ mod tests {
             content: "Hello $1".to_string(),
             description: None,
             argument_hint: None,
+            arguments: Vec::new(),
         }]);
 
         type_chars_humanlike(
@@ -9026,6 +9318,7 @@ mod tests {
             content: "Echo: $1".to_string(),
             description: None,
             argument_hint: None,
+            arguments: Vec::new(),
         }]);
 
         composer
@@ -9066,6 +9359,7 @@ mod tests {
             content: "Hello $1".to_string(),
             description: None,
             argument_hint: None,
+            arguments: Vec::new(),
         }]);
 
         composer
@@ -9108,6 +9402,7 @@ mod tests {
             content: "Echo: $1".to_string(),
             description: None,
             argument_hint: None,
+            arguments: Vec::new(),
         }]);
 
         let oversized_arg = "x".repeat(MAX_USER_INPUT_TEXT_CHARS);
@@ -9150,6 +9445,7 @@ mod tests {
             content: prompt_text.to_string(),
             description: None,
             argument_hint: None,
+            arguments: Vec::new(),
         };
 
         let action = prompt_selection_action(
@@ -9190,6 +9486,7 @@ mod tests {
             content: "Echo: $ARGUMENTS".to_string(),
             description: None,
             argument_hint: None,
+            arguments: Vec::new(),
         }]);
 
         // Type positional args; should submit with numeric expansion, no errors.
@@ -9226,6 +9523,7 @@ mod tests {
             content: prompt_text.to_string(),
             description: None,
             argument_hint: None,
+            arguments: Vec::new(),
         }]);
 
         type_chars_humanlike(
@@ -9262,6 +9560,7 @@ mod tests {
             content: prompt_text.to_string(),
             description: None,
             argument_hint: None,
+            arguments: Vec::new(),
         }]);
 
         type_chars_humanlike(
@@ -9300,6 +9599,7 @@ mod tests {
             content: prompt_text.to_string(),
             description: None,
             argument_hint: None,
+            arguments: Vec::new(),
         }]);
 
         type_chars_humanlike(
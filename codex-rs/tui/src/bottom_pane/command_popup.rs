@@ -12,6 +12,7 @@ use crate::render::RectExt;
 use crate::slash_command::SlashCommand;
 use codex_protocol::custom_prompts::CustomPrompt;
 use codex_protocol::custom_prompts::PROMPTS_CMD_PREFIX;
+use codex_protocol::mcp::Prompt as McpPrompt;
 use std::collections::HashSet;
 
 // Hide alias commands in the default popup list so each unique action appears once.
@@ -24,18 +25,45 @@ const ALIAS_COMMANDS: &[SlashCommand] = &[
     SlashCommand::RepoIndexRefresh,
 ];
 
-/// A selectable item in the popup: either a built-in command or a user prompt.
+/// A selectable item in the popup: a built-in command, a user prompt, or a
+/// prompt published by an MCP server.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub(crate) enum CommandItem {
     Builtin(SlashCommand),
     // Index into `prompts`
     UserPrompt(usize),
+    // Index into `mcp_prompts`
+    McpPrompt(usize),
+}
+
+/// A prompt published by an MCP server, invoked as `/server:prompt`.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct McpPromptCommand {
+    pub(crate) server: String,
+    pub(crate) prompt: McpPrompt,
+}
+
+impl McpPromptCommand {
+    /// The slash command name, without the leading `/`.
+    pub(crate) fn command_name(&self) -> String {
+        format!("{}:{}", self.server, self.prompt.name)
+    }
+
+    pub(crate) fn argument_names(&self) -> Vec<String> {
+        self.prompt
+            .arguments
+            .iter()
+            .flatten()
+            .map(|argument| argument.name.clone())
+            .collect()
+    }
 }
 
 pub(crate) struct CommandPopup {
     command_filter: String,
     builtins: Vec<(&'static str, SlashCommand)>,
     prompts: Vec<CustomPrompt>,
+    mcp_prompts: Vec<McpPromptCommand>,
     state: ScrollState,
 }
 
@@ -80,6 +108,7 @@ impl CommandPopup {
             command_filter: String::new(),
             builtins,
             prompts,
+            mcp_prompts: Vec::new(),
             state: ScrollState::new(),
         }
     }
@@ -99,6 +128,18 @@ impl CommandPopup {
         self.prompts.get(idx)
     }
 
+    /// Servers named like the custom prompt prefix would shadow `/prompts:*`,
+    /// so their prompts are not offered.
+    pub(crate) fn set_mcp_prompts(&mut self, mut prompts: Vec<McpPromptCommand>) {
+        prompts.retain(|p| p.server != PROMPTS_CMD_PREFIX);
+        prompts.sort_by_key(McpPromptCommand::command_name);
+        self.mcp_prompts = prompts;
+    }
+
+    pub(crate) fn mcp_prompt(&self, idx: usize) -> Option<&McpPromptCommand> {
+        self.mcp_prompts.get(idx)
+    }
+
     /// Update the filter string based on the current composer text. The text
     /// passed in is expected to start with a leading '/'. Everything after the
     /// *first* '/' on the *first* line becomes the active filter that is used
@@ -157,6 +198,9 @@ impl CommandPopup {
             for idx in 0..self.prompts.len() {
                 out.push((CommandItem::UserPrompt(idx), None));
             }
+            for idx in 0..self.mcp_prompts.len() {
+                out.push((CommandItem::McpPrompt(idx), None));
+            }
             return out;
         }
 
@@ -203,6 +247,15 @@ impl CommandPopup {
                 prompt_prefix_len,
             );
         }
+        // MCP prompts match on either "server:prompt" or the bare prompt name.
+        for (idx, p) in self.mcp_prompts.iter().enumerate() {
+            push_match(
+                CommandItem::McpPrompt(idx),
+                &p.command_name(),
+                Some(&p.prompt.name),
+                p.server.chars().count() + 1,
+            );
+        }
 
         out.extend(exact);
         out.extend(prefix);
@@ -235,6 +288,13 @@ impl CommandPopup {
                             description,
                         )
                     }
+                    CommandItem::McpPrompt(i) => {
+                        let prompt = &self.mcp_prompts[i];
+                        let description = prompt.prompt.description.clone().unwrap_or_else(|| {
+                            format!("send prompt from the {} MCP server", prompt.server)
+                        });
+                        (format!("/{}", prompt.command_name()), description)
+                    }
                 };
                 GenericDisplayRow {
                     name,
@@ -306,7 +366,7 @@ mod tests {
         let matches = popup.filtered_items();
         let has_init = matches.iter().any(|item| match item {
             CommandItem::Builtin(cmd) => cmd.command() == "init",
-            CommandItem::UserPrompt(_) => false,
+            CommandItem::UserPrompt(_) | CommandItem::McpPrompt(_) => false,
         });
         assert!(
             has_init,
@@ -324,7 +384,9 @@ mod tests {
         let selected = popup.selected_item();
         match selected {
             Some(CommandItem::Builtin(cmd)) => assert_eq!(cmd.command(), "init"),
-            Some(CommandItem::UserPrompt(_)) => panic!("unexpected prompt selected for '/init'"),
+            Some(CommandItem::UserPrompt(_) | CommandItem::McpPrompt(_)) => {
+                panic!("unexpected prompt selected for '/init'")
+            }
             None => panic!("expected a selected command for exact match"),
         }
     }
@@ -336,7 +398,7 @@ mod tests {
         let matches = popup.filtered_items();
         match matches.first() {
             Some(CommandItem::Builtin(cmd)) => assert_eq!(cmd.command(), "model"),
-            Some(CommandItem::UserPrompt(_)) => {
+            Some(CommandItem::UserPrompt(_) | CommandItem::McpPrompt(_)) => {
                 panic!("unexpected prompt ranked before '/model' for '/mo'")
             }
             None => panic!("expected at least one match for '/mo'"),
@@ -353,7 +415,7 @@ mod tests {
             .into_iter()
             .filter_map(|item| match item {
                 CommandItem::Builtin(cmd) => Some(cmd.command()),
-                CommandItem::UserPrompt(_) => None,
+                CommandItem::UserPrompt(_) | CommandItem::McpPrompt(_) => None,
             })
             .collect();
         assert_eq!(cmds, vec!["model", "mention", "mcp", "multi-agents"]);
@@ -368,6 +430,7 @@ mod tests {
                 content: "hello from foo".to_string(),
                 description: None,
                 argument_hint: None,
+                arguments: Vec::new(),
             },
             CustomPrompt {
                 name: "bar".to_string(),
@@ -375,6 +438,7 @@ mod tests {
                 content: "hello from bar".to_string(),
                 description: None,
                 argument_hint: None,
+                arguments: Vec::new(),
             },
         ];
         let popup = CommandPopup::new(prompts, CommandPopupFlags::default());
@@ -400,6 +464,7 @@ mod tests {
                 content: "should be ignored".to_string(),
                 description: None,
                 argument_hint: None,
+                arguments: Vec::new(),
             }],
             CommandPopupFlags::default(),
         );
@@ -423,6 +488,7 @@ mod tests {
                 content: "body".to_string(),
                 description: Some("Create feature branch, commit and open draft PR.".to_string()),
                 argument_hint: None,
+                arguments: Vec::new(),
             }],
             CommandPopupFlags::default(),
         );
@@ -443,6 +509,7 @@ mod tests {
                 content: "body".to_string(),
                 description: None,
                 argument_hint: None,
+                arguments: Vec::new(),
             }],
             CommandPopupFlags::default(),
         );
@@ -461,7 +528,7 @@ mod tests {
             .into_iter()
             .filter_map(|item| match item {
                 CommandItem::Builtin(cmd) => Some(cmd.command()),
-                CommandItem::UserPrompt(_) => None,
+                CommandItem::UserPrompt(_) | CommandItem::McpPrompt(_) => None,
             })
             .collect();
         assert!(
@@ -504,7 +571,7 @@ mod tests {
             .into_iter()
             .filter_map(|item| match item {
                 CommandItem::Builtin(cmd) => Some(cmd.command()),
-                CommandItem::UserPrompt(_) => None,
+                CommandItem::UserPrompt(_) | CommandItem::McpPrompt(_) => None,
             })
             .collect();
         assert!(
@@ -582,7 +649,7 @@ mod tests {
             .into_iter()
             .filter_map(|item| match item {
                 CommandItem::Builtin(cmd) => Some(cmd.command()),
-                CommandItem::UserPrompt(_) => None,
+                CommandItem::UserPrompt(_) | CommandItem::McpPrompt(_) => None,
             })
             .collect();
         assert!(
@@ -634,7 +701,7 @@ mod tests {
             .into_iter()
             .filter_map(|item| match item {
                 CommandItem::Builtin(cmd) => Some(cmd.command()),
-                CommandItem::UserPrompt(_) => None,
+                CommandItem::UserPrompt(_) | CommandItem::McpPrompt(_) => None,
             })
             .collect();
 
@@ -652,7 +719,7 @@ mod tests {
             .into_iter()
             .filter_map(|item| match item {
                 CommandItem::Builtin(cmd) => Some(cmd.command()),
-                CommandItem::UserPrompt(_) => None,
+                CommandItem::UserPrompt(_) | CommandItem::McpPrompt(_) => None,
             })
             .collect();
 
@@ -661,4 +728,47 @@ mod tests {
             "expected no /debug* command in popup menu, got {cmds:?}"
         );
     }
+
+    #[test]
+    fn mcp_prompts_match_by_bare_prompt_name() {
+        let mut popup = CommandPopup::new(Vec::new(), CommandPopupFlags::default());
+        popup.set_mcp_prompts(vec![
+            McpPromptCommand {
+                server: "docs".to_string(),
+                prompt: McpPrompt {
+                    name: "review".to_string(),
+                    title: None,
+                    description: Some("Ask for a review of a file.".to_string()),
+                    arguments: None,
+                },
+            },
+            McpPromptCommand {
+                server: PROMPTS_CMD_PREFIX.to_string(),
+                prompt: McpPrompt {
+                    name: "review".to_string(),
+                    title: None,
+                    description: None,
+                    arguments: None,
+                },
+            },
+        ]);
+        popup.on_composer_text_change("/revi".to_string());
+
+        let rows = popup.rows_from_matches(popup.filtered());
+        let row = rows
+            .iter()
+            .find(|row| row.name == "/docs:review")
+            .expect("expected /docs:review to match");
+        assert_eq!(
+            row.description.as_deref(),
+            Some("Ask for a review of a file.")
+        );
+        assert_eq!(row.match_indices, Some(vec![6, 7, 8, 9]));
+        assert!(
+            !rows
+                .iter()
+                .any(|row| row.name == format!("/{PROMPTS_CMD_PREFIX}:review")),
+            "prompts from a server named like the prompts prefix should be hidden"
+        );
+    }
 }
@@ -1,5 +1,6 @@
 use codex_protocol::custom_prompts::CustomPrompt;
 use codex_protocol::custom_prompts::PROMPTS_CMD_PREFIX;
+use codex_protocol::mcp::PromptArgument as McpPromptArgument;
 use codex_protocol::user_input::ByteRange;
 use codex_protocol::user_input::TextElement;
 use lazy_static::lazy_static;
@@ -129,6 +130,36 @@ pub fn prompt_argument_names(content: &str) -> Vec<String> {
     names
 }
 
+/// Names of a custom prompt's named arguments: those declared in its
+/// frontmatter, in order, followed by any other `$NAME` in its content.
+pub fn custom_prompt_argument_names(prompt: &CustomPrompt) -> Vec<String> {
+    let mut names: Vec<String> = prompt
+        .arguments
+        .iter()
+        .map(|arg| arg.name.clone())
+        .collect();
+    for name in prompt_argument_names(&prompt.content) {
+        if !names.contains(&name) {
+            names.push(name);
+        }
+    }
+    names
+}
+
+/// Named arguments that must be given: every argument that is not declared
+/// optional in the frontmatter.
+fn required_prompt_argument_names(prompt: &CustomPrompt) -> Vec<String> {
+    custom_prompt_argument_names(prompt)
+        .into_iter()
+        .filter(|name| {
+            !prompt
+                .arguments
+                .iter()
+                .any(|arg| !arg.required && arg.name == *name)
+        })
+        .collect()
+}
+
 /// Shift a text element's byte range left by `offset`, returning `None` if empty.
 ///
 /// `offset` is the byte length of the prefix removed from the original text.
@@ -206,8 +237,8 @@ pub fn expand_custom_prompt(
         Some(prompt) => prompt,
         None => return Ok(None),
     };
-    // If there are named placeholders, expect key=value inputs.
-    let required = prompt_argument_names(&prompt.content);
+    // If there are named arguments, expect key=value inputs.
+    let named = custom_prompt_argument_names(prompt);
     let local_elements: Vec<TextElement> = text_elements
         .iter()
         .filter_map(|elem| {
@@ -220,14 +251,14 @@ pub fn expand_custom_prompt(
             (shifted.byte_range.start < shifted.byte_range.end).then_some(shifted)
         })
         .collect();
-    if !required.is_empty() {
-        let inputs = parse_prompt_inputs(rest, &local_elements).map_err(|error| {
+    if !named.is_empty() {
+        let mut inputs = parse_prompt_inputs(rest, &local_elements).map_err(|error| {
             PromptExpansionError::Args {
                 command: format!("/{name}"),
                 error,
             }
         })?;
-        let missing: Vec<String> = required
+        let missing: Vec<String> = required_prompt_argument_names(prompt)
             .into_iter()
             .filter(|k| !inputs.contains_key(k))
             .collect();
@@ -237,6 +268,13 @@ pub fn expand_custom_prompt(
                 missing,
             });
         }
+        // Optional arguments that were left out expand to nothing.
+        for name in named {
+            inputs.entry(name).or_insert_with(|| PromptArg {
+                text: String::new(),
+                text_elements: Vec::new(),
+            });
+        }
         let (text, elements) = expand_named_placeholders_with_elements(&prompt.content, &inputs);
         return Ok(Some(PromptExpansion {
             text,
@@ -318,7 +356,7 @@ pub fn expand_if_numeric_with_positional_args(
     first_line: &str,
     text_elements: &[TextElement],
 ) -> Option<PromptExpansion> {
-    if !prompt_argument_names(&prompt.content).is_empty() {
+    if !custom_prompt_argument_names(prompt).is_empty() {
         return None;
     }
     if !prompt_has_numeric_placeholders(&prompt.content) {
@@ -548,7 +586,14 @@ fn append_joined_args_with_elements(
 /// Constructs a command text for a custom prompt with arguments.
 /// Returns the text and the cursor position (inside the first double quote).
 pub fn prompt_command_with_arg_placeholders(name: &str, args: &[String]) -> (String, usize) {
-    let mut text = format!("/{PROMPTS_CMD_PREFIX}:{name}");
+    command_with_arg_placeholders(&format!("{PROMPTS_CMD_PREFIX}:{name}"), args)
+}
+
+/// Constructs `/command key="" ...` for any slash command that takes named
+/// arguments. Returns the text and the cursor position (inside the first
+/// double quote).
+pub fn command_with_arg_placeholders(command: &str, args: &[String]) -> (String, usize) {
+    let mut text = format!("/{command}");
     let mut cursor: usize = text.len();
     for (i, arg) in args.iter().enumerate() {
         text.push_str(format!(" {arg}=\"\"").as_str());
@@ -559,6 +604,39 @@ pub fn prompt_command_with_arg_placeholders(name: &str, args: &[String]) -> (Str
     (text, cursor)
 }
 
+/// Parses the `key=value` arguments typed after an MCP prompt command and
+/// checks them against the prompt's declared arguments.
+///
+/// Empty values are treated as absent so untouched `key=""` placeholders do
+/// not satisfy a required argument.
+pub fn parse_mcp_prompt_arguments(
+    command: &str,
+    rest: &str,
+    arguments: &[McpPromptArgument],
+) -> Result<HashMap<String, String>, PromptExpansionError> {
+    let inputs: HashMap<String, String> = parse_prompt_inputs(rest, &[])
+        .map_err(|error| PromptExpansionError::Args {
+            command: format!("/{command}"),
+            error,
+        })?
+        .into_iter()
+        .filter(|(_, value)| !value.text.is_empty())
+        .map(|(key, value)| (key, value.text))
+        .collect();
+    let missing: Vec<String> = arguments
+        .iter()
+        .filter(|argument| argument.required == Some(true) && !inputs.contains_key(&argument.name))
+        .map(|argument| argument.name.clone())
+        .collect();
+    if !missing.is_empty() {
+        return Err(PromptExpansionError::MissingArgs {
+            command: format!("/{command}"),
+            missing,
+        });
+    }
+    Ok(inputs)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -572,6 +650,7 @@ mod tests {
             content: "Review $USER changes on $BRANCH".to_string(),
             description: None,
             argument_hint: None,
+            arguments: Vec::new(),
         }];
 
         let out = expand_custom_prompt("/prompts:my-prompt USER=Alice BRANCH=main", &[], &prompts)
@@ -593,6 +672,7 @@ mod tests {
             content: "Pair $USER with $BRANCH".to_string(),
             description: None,
             argument_hint: None,
+            arguments: Vec::new(),
         }];
 
         let out = expand_custom_prompt(
@@ -618,6 +698,7 @@ mod tests {
             content: "Review $USER changes".to_string(),
             description: None,
             argument_hint: None,
+            arguments: Vec::new(),
         }];
         let err = expand_custom_prompt("/prompts:my-prompt USER=Alice stray", &[], &prompts)
             .unwrap_err()
@@ -633,6 +714,7 @@ mod tests {
             content: "Review $USER changes on $BRANCH".to_string(),
             description: None,
             argument_hint: None,
+            arguments: Vec::new(),
         }];
         let err = expand_custom_prompt("/prompts:my-prompt USER=Alice", &[], &prompts)
             .unwrap_err()
@@ -661,6 +743,7 @@ mod tests {
             content: "literal $$USER".to_string(),
             description: None,
             argument_hint: None,
+            arguments: Vec::new(),
         }];
 
         let out = expand_custom_prompt("/prompts:my-prompt", &[], &prompts).unwrap();
@@ -851,4 +934,38 @@ mod tests {
             })
         );
     }
+
+    #[test]
+    fn mcp_prompt_arguments_require_non_empty_required_values() {
+        let arguments = vec![
+            McpPromptArgument {
+                name: "file".to_string(),
+                title: None,
+                description: None,
+                required: Some(true),
+            },
+            McpPromptArgument {
+                name: "focus".to_string(),
+                title: None,
+                description: None,
+                required: None,
+            },
+        ];
+
+        let parsed =
+            parse_mcp_prompt_arguments("docs:review", r#"file="src/main.rs" focus="""#, &arguments)
+                .unwrap();
+        assert_eq!(
+            parsed,
+            HashMap::from([("file".to_string(), "src/main.rs".to_string())])
+        );
+
+        let err = parse_mcp_prompt_arguments("docs:review", r#"file="" focus=errors"#, &arguments)
+            .unwrap_err()
+            .user_message();
+        assert_eq!(
+            err,
+            "Missing required args for /docs:review: file. Provide as key=value (quote values with spaces)."
+        );
+    }
 }
@@ -80,6 +80,7 @@ use codex_otel::SessionTelemetry;
 use codex_protocol::ThreadId;
 use codex_protocol::account::PlanType;
 use codex_protocol::approvals::ElicitationRequestEvent;
+use codex_protocol::approvals::McpSamplingRequestEvent;
 use codex_protocol::config_types::CollaborationMode;
 use codex_protocol::config_types::CollaborationModeMask;
 use codex_protocol::config_types::ModeKind;
@@ -101,6 +102,8 @@ use codex_protocol::protocol::AgentReasoningRawContentDeltaEvent;
 use codex_protocol::protocol::AgentReasoningRawContentEvent;
 use codex_protocol::protocol::ApplyPatchApprovalRequestEvent;
 use codex_protocol::protocol::BackgroundEventEvent;
+use codex_protocol::protocol::BackgroundServiceAction;
+use codex_protocol::protocol::BackgroundServiceSummary;
 use codex_protocol::protocol::CodexErrorInfo;
 use codex_protocol::protocol::CollabAgentSpawnBeginEvent;
 use codex_protocol::protocol::CreditsSnapshot;
@@ -114,9 +117,12 @@ use codex_protocol::protocol::ExecCommandEndEvent;
 use codex_protocol::protocol::ExecCommandOutputDeltaEvent;
 use codex_protocol::protocol::ExecCommandSource;
 use codex_protocol::protocol::ExitedReviewModeEvent;
+use codex_protocol::protocol::ExpandCustomPromptResponseEvent;
+use codex_protocol::protocol::GetMcpPromptResponseEvent;
 use codex_protocol::protocol::ImageGenerationBeginEvent;
 use codex_protocol::protocol::ImageGenerationEndEvent;
 use codex_protocol::protocol::ListCustomPromptsResponseEvent;
+use codex_protocol::protocol::ListMcpPromptsResponseEvent;
 use codex_protocol::protocol::ListSkillsResponseEvent;
 use codex_protocol::protocol::McpListToolsResponseEvent;
 use codex_protocol::protocol::McpStartupCompleteEvent;
@@ -125,6 +131,8 @@ use codex_protocol::protocol::McpStartupUpdateEvent;
 use codex_protocol::protocol::McpToolCallBeginEvent;
 use codex_protocol::protocol::McpToolCallEndEvent;
 use codex_protocol::protocol::Op;
+use codex_protocol::protocol::OverlayWorkspaceChangesEvent;
+use codex_protocol::protocol::OverlayWorkspaceDecision;
 use codex_protocol::protocol::PatchApplyBeginEvent;
 use codex_protocol::protocol::RateLimitSnapshot;
 use codex_protocol::protocol::ReviewRequest;
@@ -167,6 +175,7 @@ use tokio::sync::mpsc::UnboundedSender;
 use tokio::task::JoinHandle;
 use tracing::debug;
 use tracing::warn;
+use uuid::Uuid;
 
 const DEFAULT_MODEL_DISPLAY_NAME: &str = "loading";
 const PLAN_IMPLEMENTATION_TITLE: &str = "Implement this plan?";
@@ -228,6 +237,7 @@ use crate::bottom_pane::ExperimentalFeaturesView;
 use crate::bottom_pane::FeedbackAudience;
 use crate::bottom_pane::InputResult;
 use crate::bottom_pane::LocalImageAttachment;
+use crate::bottom_pane::McpPromptCommand;
 use crate::bottom_pane::McpServerElicitationFormRequest;
 use crate::bottom_pane::MentionBinding;
 use crate::bottom_pane::QUIT_SHORTCUT_TIMEOUT;
@@ -580,6 +590,8 @@ pub(crate) struct ChatWidget {
     plan_stream_controller: Option<PlanStreamController>,
     // Latest completed user-visible Codex output that `/copy` should place on the clipboard.
     last_copyable_output: Option<String>,
+    // Pending copy-on-write workspace changes that `/overlay` shows again.
+    overlay_workspace_changes: HashMap<PathBuf, codex_protocol::protocol::FileChange>,
     running_commands: HashMap<String, RunningCommand>,
     pending_collab_spawn_requests: HashMap<String, multi_agents::SpawnRequestSummary>,
     suppressed_exec_calls: HashSet<String>,
@@ -590,6 +602,8 @@ pub(crate) struct ChatWidget {
     turn_sleep_inhibitor: SleepInhibitor,
     task_complete_pending: bool,
     unified_exec_processes: Vec<UnifiedExecProcessSummary>,
+    // Latest background services of the thread, listed by `/ps`.
+    background_services: Vec<BackgroundServiceSummary>,
     /// Tracks whether codex-core currently considers an agent turn to be in progress.
     ///
     /// This is kept separate from `mcp_startup_status` so that MCP startup progress (or completion)
@@ -618,6 +632,10 @@ pub(crate) struct ChatWidget {
     // Set when commentary output completes; once stream queues go idle we restore the status row.
     pending_status_indicator_restore: bool,
     suppress_queue_autosend: bool,
+    // Custom prompt waiting for Codex to run its shell directives, keyed by
+    // the request id; keeps the attachments and mentions to send once the
+    // expanded text comes back.
+    pending_custom_prompt: Option<(String, UserMessage)>,
     thread_id: Option<ThreadId>,
     thread_name: Option<String>,
     forked_from: Option<ThreadId>,
@@ -2042,6 +2060,8 @@ impl ChatWidget {
         self.mcp_startup_status = None;
         self.update_task_running_state();
         self.maybe_send_next_queued_input();
+        // Servers are ready, so their prompts can be offered in the slash popup.
+        self.submit_op(Op::ListMcpPrompts);
         self.request_redraw();
     }
 
@@ -2256,6 +2276,14 @@ impl ChatWidget {
         );
     }
 
+    fn on_mcp_sampling_request(&mut self, ev: McpSamplingRequestEvent) {
+        let ev2 = ev.clone();
+        self.defer_or_handle(
+            |q| q.push_mcp_sampling(ev),
+            |s| s.handle_mcp_sampling_request_now(ev2),
+        );
+    }
+
     fn on_request_user_input(&mut self, ev: RequestUserInputEvent) {
         let ev2 = ev.clone();
         self.defer_or_handle(
@@ -2368,6 +2396,33 @@ impl ChatWidget {
         ));
     }
 
+    fn on_overlay_workspace_changes(&mut self, event: OverlayWorkspaceChangesEvent) {
+        self.overlay_workspace_changes = event.changes;
+        self.add_overlay_workspace_changes_output();
+    }
+
+    fn add_overlay_workspace_changes_output(&mut self) {
+        if self.overlay_workspace_changes.is_empty() {
+            self.add_info_message(
+                "No pending changes in the copy-on-write workspace.".to_string(),
+                None,
+            );
+            return;
+        }
+        self.add_to_history(history_cell::new_patch_event(
+            self.overlay_workspace_changes.clone(),
+            &self.config.cwd,
+        ));
+        let count = self.overlay_workspace_changes.len();
+        let noun = if count == 1 { "file" } else { "files" };
+        self.add_info_message(
+            format!("{count} {noun} pending in the copy-on-write workspace."),
+            Some(
+                "Use /overlay accept or /overlay reject, optionally followed by paths.".to_string(),
+            ),
+        );
+    }
+
     fn on_view_image_tool_call(&mut self, event: ViewImageToolCallEvent) {
         self.flush_answer_stream_with_separator();
         self.add_to_history(history_cell::new_view_image_tool_call(
@@ -3019,6 +3074,27 @@ impl ChatWidget {
         self.request_redraw();
     }
 
+    pub(crate) fn handle_mcp_sampling_request_now(&mut self, ev: McpSamplingRequestEvent) {
+        self.flush_answer_stream_with_separator();
+
+        self.notify(Notification::ElicitationRequested {
+            server_name: ev.server_name.clone(),
+        });
+
+        let request = ApprovalRequest::McpSampling {
+            thread_id: self.thread_id.unwrap_or_default(),
+            thread_label: None,
+            server_name: ev.server_name,
+            request_id: ev.id,
+            system_prompt: ev.system_prompt,
+            messages: ev.messages,
+            max_tokens: ev.max_tokens,
+        };
+        self.bottom_pane
+            .push_approval_request(request, &self.config.features);
+        self.request_redraw();
+    }
+
     pub(crate) fn push_approval_request(&mut self, request: ApprovalRequest) {
         self.bottom_pane
             .push_approval_request(request, &self.config.features);
@@ -3257,6 +3333,7 @@ impl ChatWidget {
             stream_controller: None,
             plan_stream_controller: None,
             last_copyable_output: None,
+            overlay_workspace_changes: HashMap::new(),
             running_commands: HashMap::new(),
             pending_collab_spawn_requests: HashMap::new(),
             suppressed_exec_calls: HashSet::new(),
@@ -3265,6 +3342,7 @@ impl ChatWidget {
             turn_sleep_inhibitor: SleepInhibitor::new(prevent_idle_sleep),
             task_complete_pending: false,
             unified_exec_processes: Vec::new(),
+            background_services: Vec::new(),
             agent_turn_running: false,
             mcp_startup_status: None,
             connectors_cache: ConnectorsCacheState::default(),
@@ -3278,6 +3356,7 @@ impl ChatWidget {
             retry_status_header: None,
             pending_status_indicator_restore: false,
             suppress_queue_autosend: false,
+            pending_custom_prompt: None,
             thread_id: None,
             thread_name: None,
             forked_from: None,
@@ -3442,6 +3521,7 @@ impl ChatWidget {
             stream_controller: None,
             plan_stream_controller: None,
             last_copyable_output: None,
+            overlay_workspace_changes: HashMap::new(),
             running_commands: HashMap::new(),
             pending_collab_spawn_requests: HashMap::new(),
             suppressed_exec_calls: HashSet::new(),
@@ -3450,6 +3530,7 @@ impl ChatWidget {
             turn_sleep_inhibitor: SleepInhibitor::new(prevent_idle_sleep),
             task_complete_pending: false,
             unified_exec_processes: Vec::new(),
+            background_services: Vec::new(),
             agent_turn_running: false,
             mcp_startup_status: None,
             connectors_cache: ConnectorsCacheState::default(),
@@ -3463,6 +3544,7 @@ impl ChatWidget {
             retry_status_header: None,
             pending_status_indicator_restore: false,
             suppress_queue_autosend: false,
+            pending_custom_prompt: None,
             thread_id: None,
             thread_name: None,
             forked_from: None,
@@ -3619,6 +3701,7 @@ impl ChatWidget {
             stream_controller: None,
             plan_stream_controller: None,
             last_copyable_output: None,
+            overlay_workspace_changes: HashMap::new(),
             running_commands: HashMap::new(),
             pending_collab_spawn_requests: HashMap::new(),
             suppressed_exec_calls: HashSet::new(),
@@ -3627,6 +3710,7 @@ impl ChatWidget {
             turn_sleep_inhibitor: SleepInhibitor::new(prevent_idle_sleep),
             task_complete_pending: false,
             unified_exec_processes: Vec::new(),
+            background_services: Vec::new(),
             agent_turn_running: false,
             mcp_startup_status: None,
             connectors_cache: ConnectorsCacheState::default(),
@@ -3640,6 +3724,7 @@ impl ChatWidget {
             retry_status_header: None,
             pending_status_indicator_restore: false,
             suppress_queue_autosend: false,
+            pending_custom_prompt: None,
             thread_id: None,
             thread_name: None,
             forked_from: None,
@@ -3881,6 +3966,42 @@ impl ChatWidget {
                 InputResult::CommandWithArgs(cmd, args, text_elements) => {
                     self.dispatch_command_with_args(cmd, args, text_elements);
                 }
+                InputResult::McpPrompt {
+                    server,
+                    name,
+                    arguments,
+                } => {
+                    self.submit_op(Op::GetMcpPrompt {
+                        server_name: server,
+                        prompt_name: name,
+                        arguments,
+                    });
+                }
+                InputResult::CustomPromptShell {
+                    text,
+                    text_elements,
+                } => {
+                    let local_images = self
+                        .bottom_pane
+                        .take_recent_submission_images_with_placeholders();
+                    let remote_image_urls = self.take_remote_image_urls();
+                    let id = Uuid::new_v4().to_string();
+                    let user_message = UserMessage {
+                        text: String::new(),
+                        local_images,
+                        remote_image_urls,
+                        text_elements: Vec::new(),
+                        mention_bindings: self
+                            .bottom_pane
+                            .take_recent_submission_mention_bindings(),
+                    };
+                    self.pending_custom_prompt = Some((id.clone(), user_message));
+                    self.submit_op(Op::ExpandCustomPrompt {
+                        id,
+                        text,
+                        text_elements,
+                    });
+                }
                 InputResult::None => {}
             },
         }
@@ -4164,6 +4285,9 @@ impl ChatWidget {
                     tx.send(AppEvent::DiffResult(text));
                 });
             }
+            SlashCommand::Overlay => {
+                self.add_overlay_workspace_changes_output();
+            }
             SlashCommand::Copy => {
                 let Some(text) = self.last_copyable_output.as_deref() else {
                     self.add_info_message(
@@ -4411,6 +4535,24 @@ impl ChatWidget {
                 self.submit_repo_index_refresh(force_full);
                 self.bottom_pane.drain_pending_submission_state();
             }
+            SlashCommand::Overlay if !trimmed.is_empty() => {
+                let Some((decision, paths)) = parse_overlay_workspace_args(trimmed) else {
+                    self.add_error_message(
+                        "Usage: /overlay [accept|reject] [path ...]".to_string(),
+                    );
+                    return;
+                };
+                self.submit_op(Op::ResolveOverlayWorkspace { decision, paths });
+                self.bottom_pane.drain_pending_submission_state();
+            }
+            SlashCommand::Ps if !trimmed.is_empty() => {
+                let Some((action, name)) = parse_background_service_args(trimmed) else {
+                    self.add_error_message("Usage: /ps [start|stop|restart <name>]".to_string());
+                    return;
+                };
+                self.submit_op(Op::ControlBackgroundService { name, action });
+                self.bottom_pane.drain_pending_submission_state();
+            }
             SlashCommand::SandboxReadRoot if !trimmed.is_empty() => {
                 let Some((prepared_args, _prepared_elements)) =
                     self.bottom_pane.prepare_inline_args_submission(false)
@@ -5101,6 +5243,9 @@ impl ChatWidget {
             EventMsg::ElicitationRequest(ev) => {
                 self.on_elicitation_request(ev);
             }
+            EventMsg::McpSamplingRequest(ev) => {
+                self.on_mcp_sampling_request(ev);
+            }
             EventMsg::RequestUserInput(ev) => {
                 self.on_request_user_input(ev);
             }
@@ -5123,6 +5268,9 @@ impl ChatWidget {
             EventMsg::GetHistoryEntryResponse(ev) => self.on_get_history_entry_response(ev),
             EventMsg::McpListToolsResponse(ev) => self.on_list_mcp_tools(ev),
             EventMsg::ListCustomPromptsResponse(ev) => self.on_list_custom_prompts(ev),
+            EventMsg::ExpandCustomPromptResponse(ev) => self.on_expand_custom_prompt(ev),
+            EventMsg::ListMcpPromptsResponse(ev) => self.on_list_mcp_prompts(ev),
+            EventMsg::GetMcpPromptResponse(ev) => self.on_get_mcp_prompt(ev),
             EventMsg::ListSkillsResponse(ev) => self.on_list_skills(ev),
             EventMsg::ListRemoteSkillsResponse(_) | EventMsg::RemoteSkillDownloaded(_) => {}
             EventMsg::SkillsUpdateAvailable => {
@@ -5133,6 +5281,8 @@ impl ChatWidget {
             }
             EventMsg::ShutdownComplete => self.on_shutdown_complete(),
             EventMsg::TurnDiff(TurnDiffEvent { unified_diff }) => self.on_turn_diff(unified_diff),
+            EventMsg::OverlayWorkspaceChanges(ev) => self.on_overlay_workspace_changes(ev),
+            EventMsg::BackgroundServicesUpdated(ev) => self.background_services = ev.services,
             EventMsg::DeprecationNotice(ev) => self.on_deprecation_notice(ev),
             EventMsg::BackgroundEvent(BackgroundEventEvent { message }) => {
                 self.on_background_event(message)
@@ -5944,7 +6094,10 @@ impl ChatWidget {
                 recent_chunks: process.recent_chunks.clone(),
             })
             .collect();
-        self.add_to_history(history_cell::new_unified_exec_processes_output(processes));
+        self.add_to_history(history_cell::new_unified_exec_processes_output(
+            processes,
+            self.background_services.clone(),
+        ));
     }
 
     fn clean_background_terminals(&mut self) {
@@ -8698,6 +8851,73 @@ impl ChatWidget {
         self.bottom_pane.set_custom_prompts(ev.custom_prompts);
     }
 
+    fn on_expand_custom_prompt(&mut self, ev: ExpandCustomPromptResponseEvent) {
+        // Replies to requests this widget did not send, or that a newer
+        // prompt replaced, are ignored.
+        let Some((_, pending)) = self.pending_custom_prompt.take_if(|(id, _)| *id == ev.id) else {
+            return;
+        };
+        let text = match ev.result {
+            Ok(text) => text,
+            Err(err) => {
+                self.add_to_history(history_cell::new_error_event(format!(
+                    "Failed to run the prompt's shell commands: {err}"
+                )));
+                self.request_redraw();
+                return;
+            }
+        };
+        let user_message = UserMessage {
+            text,
+            text_elements: ev.text_elements,
+            ..pending
+        };
+        if self.is_session_configured() && !self.is_plan_streaming_in_tui() {
+            self.submit_user_message(user_message);
+        } else {
+            self.queue_user_message(user_message);
+        }
+    }
+
+    fn on_list_mcp_prompts(&mut self, ev: ListMcpPromptsResponseEvent) {
+        let prompts: Vec<McpPromptCommand> = ev
+            .prompts
+            .into_iter()
+            .flat_map(|(server, prompts)| {
+                prompts.into_iter().map(move |prompt| McpPromptCommand {
+                    server: server.clone(),
+                    prompt,
+                })
+            })
+            .collect();
+        debug!("received {} MCP prompts", prompts.len());
+        self.bottom_pane.set_mcp_prompts(prompts);
+    }
+
+    fn on_get_mcp_prompt(&mut self, ev: GetMcpPromptResponseEvent) {
+        let GetMcpPromptResponseEvent {
+            server_name,
+            prompt_name,
+            result,
+        } = ev;
+        let text = match result {
+            Ok(text) => text,
+            Err(err) => {
+                self.add_to_history(history_cell::new_error_event(format!(
+                    "Failed to load /{server_name}:{prompt_name}: {err}"
+                )));
+                self.request_redraw();
+                return;
+            }
+        };
+        let user_message = UserMessage::from(text);
+        if self.is_session_configured() && !self.is_plan_streaming_in_tui() {
+            self.submit_user_message(user_message);
+        } else {
+            self.queue_user_message(user_message);
+        }
+    }
+
     fn on_list_skills(&mut self, ev: ListSkillsResponseEvent) {
         self.set_skills_from_response(&ev);
         self.refresh_plugin_mentions();
@@ -9297,9 +9517,37 @@ fn parse_repo_index_refresh_mode(value: &str) -> Option<bool> {
     None
 }
 
+fn parse_overlay_workspace_args(
+    value: &str,
+) -> Option<(OverlayWorkspaceDecision, Option<Vec<PathBuf>>)> {
+    let mut parts = value.split_whitespace();
+    let decision = match parts.next()?.to_ascii_lowercase().as_str() {
+        "accept" => OverlayWorkspaceDecision::Accept,
+        "reject" => OverlayWorkspaceDecision::Reject,
+        _ => return None,
+    };
+    let paths: Vec<PathBuf> = parts.map(PathBuf::from).collect();
+    Some((decision, (!paths.is_empty()).then_some(paths)))
+}
+
+fn parse_background_service_args(value: &str) -> Option<(BackgroundServiceAction, String)> {
+    let mut parts = value.split_whitespace();
+    let action = match parts.next()?.to_ascii_lowercase().as_str() {
+        "start" => BackgroundServiceAction::Start,
+        "stop" => BackgroundServiceAction::Stop,
+        "restart" => BackgroundServiceAction::Restart,
+        _ => return None,
+    };
+    let name = parts.next()?.to_string();
+    parts.next().is_none().then_some((action, name))
+}
+
 fn hook_event_label(event_name: codex_protocol::protocol::HookEventName) -> &'static str {
     match event_name {
         codex_protocol::protocol::HookEventName::SessionStart => "SessionStart",
+        codex_protocol::protocol::HookEventName::UserPromptSubmit => "UserPromptSubmit",
+        codex_protocol::protocol::HookEventName::PreToolUse => "PreToolUse",
+        codex_protocol::protocol::HookEventName::PostToolUse => "PostToolUse",
         codex_protocol::protocol::HookEventName::Stop => "Stop",
     }
 }
@@ -1,6 +1,7 @@
 use std::collections::VecDeque;
 
 use codex_protocol::approvals::ElicitationRequestEvent;
+use codex_protocol::approvals::McpSamplingRequestEvent;
 use codex_protocol::protocol::ApplyPatchApprovalRequestEvent;
 use codex_protocol::protocol::ExecApprovalRequestEvent;
 use codex_protocol::protocol::ExecCommandBeginEvent;
@@ -18,6 +19,7 @@ pub(crate) enum QueuedInterrupt {
     ExecApproval(ExecApprovalRequestEvent),
     ApplyPatchApproval(ApplyPatchApprovalRequestEvent),
     Elicitation(ElicitationRequestEvent),
+    McpSampling(McpSamplingRequestEvent),
     RequestPermissions(RequestPermissionsEvent),
     RequestUserInput(RequestUserInputEvent),
     ExecBegin(ExecCommandBeginEvent),
@@ -57,6 +59,10 @@ impl InterruptManager {
         self.queue.push_back(QueuedInterrupt::Elicitation(ev));
     }
 
+    pub(crate) fn push_mcp_sampling(&mut self, ev: McpSamplingRequestEvent) {
+        self.queue.push_back(QueuedInterrupt::McpSampling(ev));
+    }
+
     pub(crate) fn push_request_permissions(&mut self, ev: RequestPermissionsEvent) {
         self.queue
             .push_back(QueuedInterrupt::RequestPermissions(ev));
@@ -92,6 +98,7 @@ impl InterruptManager {
                 QueuedInterrupt::ExecApproval(ev) => chat.handle_exec_approval_now(ev),
                 QueuedInterrupt::ApplyPatchApproval(ev) => chat.handle_apply_patch_approval_now(ev),
                 QueuedInterrupt::Elicitation(ev) => chat.handle_elicitation_request_now(ev),
+                QueuedInterrupt::McpSampling(ev) => chat.handle_mcp_sampling_request_now(ev),
                 QueuedInterrupt::RequestPermissions(ev) => chat.handle_request_permissions_now(ev),
                 QueuedInterrupt::RequestUserInput(ev) => chat.handle_request_user_input_now(ev),
                 QueuedInterrupt::ExecBegin(ev) => chat.handle_exec_begin_now(ev),
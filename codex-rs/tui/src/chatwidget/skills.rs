@@ -193,6 +193,7 @@ fn protocol_skill_to_core(skill: &ProtocolSkillMetadata) -> SkillMetadata {
         policy: None,
         permission_profile: None,
         managed_network_override: None,
+        resource_limits: None,
         path_to_skills_md: skill.path.clone(),
         scope: skill.scope,
     }
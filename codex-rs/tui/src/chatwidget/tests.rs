@@ -74,7 +74,9 @@ use codex_protocol::protocol::ExecCommandSource;
 use codex_protocol::protocol::ExecCommandStatus as CoreExecCommandStatus;
 use codex_protocol::protocol::ExecPolicyAmendment;
 use codex_protocol::protocol::ExitedReviewModeEvent;
+use codex_protocol::protocol::ExpandCustomPromptResponseEvent;
 use codex_protocol::protocol::FileChange;
+use codex_protocol::protocol::GetMcpPromptResponseEvent;
 use codex_protocol::protocol::ImageGenerationEndEvent;
 use codex_protocol::protocol::ItemCompletedEvent;
 use codex_protocol::protocol::McpStartupCompleteEvent;
@@ -1001,6 +1003,7 @@ async fn submission_prefers_selected_duplicate_skill_path() {
             policy: None,
             permission_profile: None,
             managed_network_override: None,
+            resource_limits: None,
             path_to_skills_md: repo_skill_path,
             scope: SkillScope::Repo,
         },
@@ -1013,6 +1016,7 @@ async fn submission_prefers_selected_duplicate_skill_path() {
             policy: None,
             permission_profile: None,
             managed_network_override: None,
+            resource_limits: None,
             path_to_skills_md: user_skill_path.clone(),
             scope: SkillScope::User,
         },
@@ -1844,6 +1848,7 @@ async fn make_chatwidget_manual(
         stream_controller: None,
         plan_stream_controller: None,
         last_copyable_output: None,
+        overlay_workspace_changes: HashMap::new(),
         running_commands: HashMap::new(),
         pending_collab_spawn_requests: HashMap::new(),
         suppressed_exec_calls: HashSet::new(),
@@ -1854,6 +1859,7 @@ async fn make_chatwidget_manual(
         turn_sleep_inhibitor: SleepInhibitor::new(prevent_idle_sleep),
         task_complete_pending: false,
         unified_exec_processes: Vec::new(),
+        background_services: Vec::new(),
         agent_turn_running: false,
         mcp_startup_status: None,
         connectors_cache: ConnectorsCacheState::default(),
@@ -1867,6 +1873,7 @@ async fn make_chatwidget_manual(
         retry_status_header: None,
         pending_status_indicator_restore: false,
         suppress_queue_autosend: false,
+        pending_custom_prompt: None,
         thread_id: None,
         thread_name: None,
         forked_from: None,
@@ -5868,6 +5875,50 @@ async fn slash_repo_index_refresh_submits_incremental_refresh_op() {
     );
 }
 
+#[tokio::test]
+async fn slash_overlay_accept_submits_resolve_op_for_paths() {
+    let (mut chat, _rx, mut op_rx) = make_chatwidget_manual(None).await;
+
+    chat.dispatch_command_with_args(
+        SlashCommand::Overlay,
+        "accept src/lib.rs".to_string(),
+        Vec::new(),
+    );
+
+    assert_matches!(
+        op_rx.try_recv(),
+        Ok(Op::ResolveOverlayWorkspace {
+            decision: OverlayWorkspaceDecision::Accept,
+            paths: Some(paths),
+        }) if paths == vec![PathBuf::from("src/lib.rs")]
+    );
+}
+
+#[tokio::test]
+async fn slash_ps_controls_background_services() {
+    let (mut chat, mut rx, mut op_rx) = make_chatwidget_manual(None).await;
+
+    chat.dispatch_command_with_args(SlashCommand::Ps, "restart db".to_string(), Vec::new());
+    assert_matches!(
+        op_rx.try_recv(),
+        Ok(Op::ControlBackgroundService {
+            name,
+            action: BackgroundServiceAction::Restart,
+        }) if name == "db"
+    );
+
+    chat.dispatch_command_with_args(SlashCommand::Ps, "restart".to_string(), Vec::new());
+    assert_matches!(op_rx.try_recv(), Err(TryRecvError::Empty));
+    let rendered = drain_insert_history(&mut rx)
+        .iter()
+        .map(|lines| lines_to_single_string(lines))
+        .collect::<String>();
+    assert!(
+        rendered.contains("Usage: /ps [start|stop|restart <name>]"),
+        "expected usage error; got {rendered:?}"
+    );
+}
+
 #[tokio::test]
 async fn slash_index_shows_qdrant_collection_root_for_noncanonical_cwd() -> color_eyre::Result<()> {
     let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
@@ -9210,6 +9261,163 @@ async fn mcp_startup_complete_does_not_clear_running_task() {
     assert!(chat.bottom_pane.status_indicator_visible());
 }
 
+#[tokio::test]
+async fn mcp_prompt_response_is_submitted_as_user_message() {
+    let (mut chat, mut rx, mut op_rx) = make_chatwidget_manual(None).await;
+    chat.thread_id = Some(ThreadId::new());
+
+    chat.handle_codex_event(Event {
+        id: "prompt-1".into(),
+        msg: EventMsg::GetMcpPromptResponse(GetMcpPromptResponseEvent {
+            server_name: "docs".to_string(),
+            prompt_name: "review".to_string(),
+            result: Ok("Please review src/lib.rs.".to_string()),
+        }),
+    });
+    let Op::UserTurn { items, .. } = next_submit_op(&mut op_rx) else {
+        panic!("expected Op::UserTurn");
+    };
+    assert_eq!(
+        items,
+        vec![UserInput::Text {
+            text: "Please review src/lib.rs.".to_string(),
+            text_elements: Vec::new(),
+        }]
+    );
+    drain_insert_history(&mut rx);
+
+    chat.handle_codex_event(Event {
+        id: "prompt-2".into(),
+        msg: EventMsg::GetMcpPromptResponse(GetMcpPromptResponseEvent {
+            server_name: "docs".to_string(),
+            prompt_name: "review".to_string(),
+            result: Err("missing argument: file".to_string()),
+        }),
+    });
+    let cells = drain_insert_history(&mut rx);
+    let rendered = cells
+        .iter()
+        .map(|lines| lines_to_single_string(lines))
+        .collect::<String>();
+    assert!(
+        rendered.contains("Failed to load /docs:review: missing argument: file"),
+        "expected error cell, got {rendered:?}"
+    );
+}
+
+#[tokio::test]
+async fn expanded_custom_prompt_is_submitted_as_user_message() {
+    let (mut chat, mut rx, mut op_rx) = make_chatwidget_manual(None).await;
+    chat.thread_id = Some(ThreadId::new());
+
+    chat.pending_custom_prompt = Some(("prompt-1".to_string(), UserMessage::from("")));
+    chat.handle_codex_event(Event {
+        id: "expand-0".into(),
+        msg: EventMsg::ExpandCustomPromptResponse(ExpandCustomPromptResponseEvent {
+            id: "prompt-0".to_string(),
+            result: Ok("stale".to_string()),
+            text_elements: Vec::new(),
+        }),
+    });
+    assert_no_submit_op(&mut op_rx);
+
+    chat.handle_codex_event(Event {
+        id: "expand-1".into(),
+        msg: EventMsg::ExpandCustomPromptResponse(ExpandCustomPromptResponseEvent {
+            id: "prompt-1".to_string(),
+            result: Ok("Summarize:\nM src/lib.rs".to_string()),
+            text_elements: Vec::new(),
+        }),
+    });
+    let Op::UserTurn { items, .. } = next_submit_op(&mut op_rx) else {
+        panic!("expected Op::UserTurn");
+    };
+    assert_eq!(
+        items,
+        vec![UserInput::Text {
+            text: "Summarize:\nM src/lib.rs".to_string(),
+            text_elements: Vec::new(),
+        }]
+    );
+    drain_insert_history(&mut rx);
+
+    chat.pending_custom_prompt = Some(("prompt-2".to_string(), UserMessage::from("")));
+    chat.handle_codex_event(Event {
+        id: "expand-2".into(),
+        msg: EventMsg::ExpandCustomPromptResponse(ExpandCustomPromptResponseEvent {
+            id: "prompt-2".to_string(),
+            result: Err("`git status` was not approved".to_string()),
+            text_elements: Vec::new(),
+        }),
+    });
+    let cells = drain_insert_history(&mut rx);
+    let rendered = cells
+        .iter()
+        .map(|lines| lines_to_single_string(lines))
+        .collect::<String>();
+    assert!(
+        rendered
+            .contains("Failed to run the prompt's shell commands: `git status` was not approved"),
+        "expected error cell, got {rendered:?}"
+    );
+}
+
+#[tokio::test]
+async fn custom_prompt_with_shell_directive_keeps_attachments() {
+    let (mut chat, mut rx, mut op_rx) = make_chatwidget_manual(None).await;
+    chat.thread_id = Some(ThreadId::new());
+    chat.bottom_pane
+        .set_custom_prompts(vec![codex_protocol::custom_prompts::CustomPrompt {
+            name: "status".to_string(),
+            path: "/tmp/status.md".to_string().into(),
+            content: "Summarize:\n{{shell: git status --short}}".to_string(),
+            description: None,
+            argument_hint: None,
+            arguments: Vec::new(),
+        }]);
+    let remote_url = "https://example.com/screenshot.png".to_string();
+    chat.set_remote_image_urls(vec![remote_url.clone()]);
+    chat.set_composer_text("/prompts:status".to_string(), Vec::new(), Vec::new());
+
+    chat.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+
+    let id = loop {
+        match op_rx.try_recv() {
+            Ok(Op::ExpandCustomPrompt { id, text, .. }) => {
+                assert_eq!(text, "Summarize:\n{{shell: git status --short}}");
+                break id;
+            }
+            Ok(_) => continue,
+            Err(err) => panic!("expected Op::ExpandCustomPrompt, got {err:?}"),
+        }
+    };
+
+    chat.handle_codex_event(Event {
+        id: "expand-1".into(),
+        msg: EventMsg::ExpandCustomPromptResponse(ExpandCustomPromptResponseEvent {
+            id,
+            result: Ok("Summarize:\nM src/lib.rs".to_string()),
+            text_elements: Vec::new(),
+        }),
+    });
+    let Op::UserTurn { items, .. } = next_submit_op(&mut op_rx) else {
+        panic!("expected Op::UserTurn");
+    };
+    assert_eq!(
+        items,
+        vec![
+            UserInput::Image {
+                image_url: remote_url,
+            },
+            UserInput::Text {
+                text: "Summarize:\nM src/lib.rs".to_string(),
+                text_elements: Vec::new(),
+            },
+        ]
+    );
+    drain_insert_history(&mut rx);
+}
+
 #[tokio::test]
 async fn background_event_updates_status_header() {
     let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
@@ -32,6 +32,11 @@ pub struct Cli {
     #[clap(skip)]
     pub resume_show_all: bool,
 
+    /// Internal: open the resume picker with a full-text search of session
+    /// contents for this query. Set by `codex resume --search <QUERY>`.
+    #[clap(skip)]
+    pub resume_search: Option<String>,
+
     // Internal controls set by the top-level `codex fork` subcommand.
     // These are not exposed as user flags on the base `codex` command.
     #[clap(skip)]
@@ -54,6 +54,9 @@ use codex_protocol::openai_models::ReasoningEffort as ReasoningEffortConfig;
 use codex_protocol::plan_tool::PlanItemArg;
 use codex_protocol::plan_tool::StepStatus;
 use codex_protocol::plan_tool::UpdatePlanArgs;
+use codex_protocol::protocol::BackgroundServiceHealth;
+use codex_protocol::protocol::BackgroundServiceStatus;
+use codex_protocol::protocol::BackgroundServiceSummary;
 use codex_protocol::protocol::FileChange;
 use codex_protocol::protocol::McpAuthStatus;
 use codex_protocol::protocol::McpInvocation;
@@ -776,10 +779,88 @@ impl HistoryCell for UnifiedExecProcessesCell {
 
 pub(crate) fn new_unified_exec_processes_output(
     processes: Vec<UnifiedExecProcessDetails>,
+    services: Vec<BackgroundServiceSummary>,
 ) -> CompositeHistoryCell {
     let command = PlainHistoryCell::new(vec!["/ps".magenta().into()]);
     let summary = UnifiedExecProcessesCell::new(processes);
-    CompositeHistoryCell::new(vec![Box::new(command), Box::new(summary)])
+    let mut parts: Vec<Box<dyn HistoryCell>> = vec![Box::new(command), Box::new(summary)];
+    if !services.is_empty() {
+        parts.push(Box::new(BackgroundServicesCell { services }));
+    }
+    CompositeHistoryCell::new(parts)
+}
+
+/// Long-running services of the thread, shown by `/ps` below the background
+/// terminals.
+#[derive(Debug)]
+struct BackgroundServicesCell {
+    services: Vec<BackgroundServiceSummary>,
+}
+
+impl BackgroundServicesCell {
+    /// Log lines shown under each service.
+    const LOG_LINES: usize = 3;
+}
+
+impl HistoryCell for BackgroundServicesCell {
+    fn display_lines(&self, width: u16) -> Vec<Line<'static>> {
+        let wrap_width = width as usize;
+        let mut out: Vec<Line<'static>> =
+            vec![vec!["Background services".bold()].into(), "".into()];
+        for service in &self.services {
+            let mut status = match service.status {
+                BackgroundServiceStatus::Stopped => "stopped".to_string(),
+                BackgroundServiceStatus::Running => "running".to_string(),
+                BackgroundServiceStatus::Restarting => "restarting".to_string(),
+                BackgroundServiceStatus::Exited => match service.exit_code {
+                    Some(code) => format!("exited ({code})"),
+                    None => "exited".to_string(),
+                },
+            };
+            match service.health {
+                Some(BackgroundServiceHealth::Pending) => status.push_str(" · starting"),
+                Some(BackgroundServiceHealth::Healthy) => status.push_str(" · healthy"),
+                Some(BackgroundServiceHealth::Unhealthy) => status.push_str(" · unhealthy"),
+                None => {}
+            }
+            if service.restarts > 0 {
+                status.push_str(&format!(" · {} restarts", service.restarts));
+            }
+            let status = match service.status {
+                BackgroundServiceStatus::Running => status.green(),
+                BackgroundServiceStatus::Restarting => status.magenta(),
+                BackgroundServiceStatus::Stopped | BackgroundServiceStatus::Exited => status.dim(),
+            };
+            out.push(
+                vec![
+                    "  • ".dim(),
+                    service.name.clone().cyan(),
+                    "  ".into(),
+                    status,
+                ]
+                .into(),
+            );
+
+            let log_prefix = "    ↳ ";
+            let budget = wrap_width.saturating_sub(UnicodeWidthStr::width(log_prefix));
+            let skip = service.recent_logs.len().saturating_sub(Self::LOG_LINES);
+            for line in service.recent_logs.iter().skip(skip) {
+                let (truncated, _, _) = take_prefix_by_width(line, budget);
+                out.push(vec![log_prefix.dim(), truncated.dim()].into());
+            }
+        }
+        out.push("".into());
+        out.push(
+            "  Use /ps start|stop|restart <name> to control a service."
+                .dim()
+                .into(),
+        );
+        out
+    }
+
+    fn desired_height(&self, width: u16) -> u16 {
+        self.display_lines(width).len() as u16
+    }
 }
 
 fn truncate_exec_snippet(full_cmd: &str) -> String {
@@ -2984,6 +3065,7 @@ mod tests {
     use codex_protocol::models::WebSearchAction;
     use codex_protocol::parse_command::ParsedCommand;
     use codex_protocol::protocol::AskForApproval;
+    use codex_protocol::protocol::BackgroundServiceSource;
     use codex_protocol::protocol::McpAuthStatus;
     use codex_protocol::protocol::SandboxPolicy;
     use codex_protocol::protocol::SessionConfiguredEvent;
@@ -3159,7 +3241,7 @@ mod tests {
 
     #[test]
     fn ps_output_empty_snapshot() {
-        let cell = new_unified_exec_processes_output(Vec::new());
+        let cell = new_unified_exec_processes_output(Vec::new(), Vec::new());
         let rendered = render_lines(&cell.display_lines(60)).join("\n");
         insta::assert_snapshot!(rendered);
     }
@@ -3237,28 +3319,34 @@ mod tests {
 
     #[test]
     fn ps_output_multiline_snapshot() {
-        let cell = new_unified_exec_processes_output(vec![
-            UnifiedExecProcessDetails {
-                command_display: "echo hello\nand then some extra text".to_string(),
-                recent_chunks: vec!["hello".to_string(), "done".to_string()],
-            },
-            UnifiedExecProcessDetails {
-                command_display: "rg \"foo\" src".to_string(),
-                recent_chunks: vec!["src/main.rs:12:foo".to_string()],
-            },
-        ]);
+        let cell = new_unified_exec_processes_output(
+            vec![
+                UnifiedExecProcessDetails {
+                    command_display: "echo hello\nand then some extra text".to_string(),
+                    recent_chunks: vec!["hello".to_string(), "done".to_string()],
+                },
+                UnifiedExecProcessDetails {
+                    command_display: "rg \"foo\" src".to_string(),
+                    recent_chunks: vec!["src/main.rs:12:foo".to_string()],
+                },
+            ],
+            Vec::new(),
+        );
         let rendered = render_lines(&cell.display_lines(40)).join("\n");
         insta::assert_snapshot!(rendered);
     }
 
     #[test]
     fn ps_output_long_command_snapshot() {
-        let cell = new_unified_exec_processes_output(vec![UnifiedExecProcessDetails {
-            command_display: String::from(
-                "rg \"foo\" src --glob '**/*.rs' --max-count 1000 --no-ignore --hidden --follow --glob '!target/**'",
-            ),
-            recent_chunks: vec!["searching...".to_string()],
-        }]);
+        let cell = new_unified_exec_processes_output(
+            vec![UnifiedExecProcessDetails {
+                command_display: String::from(
+                    "rg \"foo\" src --glob '**/*.rs' --max-count 1000 --no-ignore --hidden --follow --glob '!target/**'",
+                ),
+                recent_chunks: vec!["searching...".to_string()],
+            }],
+            Vec::new(),
+        );
         let rendered = render_lines(&cell.display_lines(36)).join("\n");
         insta::assert_snapshot!(rendered);
     }
@@ -3272,6 +3360,7 @@ mod tests {
                     recent_chunks: Vec::new(),
                 })
                 .collect(),
+            Vec::new(),
         );
         let rendered = render_lines(&cell.display_lines(32)).join("\n");
         insta::assert_snapshot!(rendered);
@@ -3279,17 +3368,69 @@ mod tests {
 
     #[test]
     fn ps_output_chunk_leading_whitespace_snapshot() {
-        let cell = new_unified_exec_processes_output(vec![UnifiedExecProcessDetails {
-            command_display: "just fix".to_string(),
-            recent_chunks: vec![
-                "  indented first".to_string(),
-                "    more indented".to_string(),
-            ],
-        }]);
+        let cell = new_unified_exec_processes_output(
+            vec![UnifiedExecProcessDetails {
+                command_display: "just fix".to_string(),
+                recent_chunks: vec![
+                    "  indented first".to_string(),
+                    "    more indented".to_string(),
+                ],
+            }],
+            Vec::new(),
+        );
         let rendered = render_lines(&cell.display_lines(60)).join("\n");
         insta::assert_snapshot!(rendered);
     }
 
+    #[test]
+    fn ps_output_lists_background_services() {
+        let service = |name: &str, status, health, logs: &[&str]| BackgroundServiceSummary {
+            name: name.to_string(),
+            command: vec!["true".to_string()],
+            source: BackgroundServiceSource::Config,
+            status,
+            health,
+            restarts: 0,
+            exit_code: None,
+            recent_logs: logs.iter().map(ToString::to_string).collect(),
+        };
+        let mut web = service("web", BackgroundServiceStatus::Exited, None, &[]);
+        web.exit_code = Some(1);
+        web.restarts = 5;
+        let cell = new_unified_exec_processes_output(
+            Vec::new(),
+            vec![
+                service(
+                    "db",
+                    BackgroundServiceStatus::Running,
+                    Some(BackgroundServiceHealth::Healthy),
+                    &["one", "two", "three", "ready to accept connections"],
+                ),
+                web,
+            ],
+        );
+        let rendered = render_lines(&cell.display_lines(60));
+        let services = rendered
+            .iter()
+            .skip_while(|line| line.as_str() != "Background services")
+            .map(String::as_str)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            services,
+            vec![
+                "Background services",
+                "",
+                "  • db  running · healthy",
+                "    ↳ two",
+                "    ↳ three",
+                "    ↳ ready to accept connections",
+                "  • web  exited (1) · 5 restarts",
+                "",
+                "  Use /ps start|stop|restart <name> to control a service.",
+            ]
+        );
+    }
+
     #[test]
     fn error_event_oversized_input_snapshot() {
         let cell = new_error_event(
@@ -3322,6 +3463,8 @@ mod tests {
             disabled_tools: None,
             scopes: None,
             oauth_resource: None,
+            sampling: None,
+            sandbox: None,
         };
         let mut servers = config.mcp_servers.get().clone();
         servers.insert("docs".to_string(), stdio_config);
@@ -3346,6 +3489,8 @@ mod tests {
             disabled_tools: None,
             scopes: None,
             oauth_resource: None,
+            sampling: None,
+            sandbox: None,
         };
         servers.insert("http".to_string(), http_config);
         config
@@ -821,7 +821,14 @@ async fn run_ratatui_app(
             _ => resume_picker::SessionSelection::StartFresh,
         }
     } else if cli.resume_picker {
-        match resume_picker::run_resume_picker(&mut tui, &config, cli.resume_show_all).await? {
+        match resume_picker::run_resume_picker(
+            &mut tui,
+            &config,
+            cli.resume_show_all,
+            cli.resume_search.clone(),
+        )
+        .await?
+        {
             resume_picker::SessionSelection::Exit => {
                 restore();
                 session_log::log_session_end();
@@ -16,6 +16,7 @@ use codex_core::Cursor;
 use codex_core::INTERACTIVE_SESSION_SOURCES;
 use codex_core::RolloutRecorder;
 use codex_core::ThreadItem;
+use codex_core::ThreadSearchHit;
 use codex_core::ThreadSortKey;
 use codex_core::ThreadsPage;
 use codex_core::config::Config;
@@ -39,6 +40,7 @@ use unicode_width::UnicodeWidthStr;
 
 const PAGE_SIZE: usize = 25;
 const LOAD_NEAR_THRESHOLD: usize = 5;
+const CONTENT_SEARCH_LIMIT: usize = 50;
 
 #[derive(Debug, Clone)]
 pub struct SessionTarget {
@@ -95,12 +97,24 @@ struct PageLoadRequest {
 
 type PageLoader = Arc<dyn Fn(PageLoadRequest) + Send + Sync>;
 
+#[derive(Clone)]
+struct ContentSearchRequest {
+    query: String,
+    request_token: usize,
+}
+
+type ContentSearchLoader = Arc<dyn Fn(ContentSearchRequest) + Send + Sync>;
+
 enum BackgroundEvent {
     PageLoaded {
         request_token: usize,
         search_token: Option<usize>,
         page: std::io::Result<ThreadsPage>,
     },
+    ContentSearchLoaded {
+        request_token: usize,
+        hits: std::io::Result<Vec<ThreadSearchHit>>,
+    },
 }
 
 /// Interactive session picker that lists recorded rollout files with simple
@@ -119,12 +133,24 @@ enum BackgroundEvent {
 /// 1. Provider and source filtering at the backend (only interactive CLI sessions
 ///    for the current model provider).
 /// 2. Working-directory filtering at the picker (unless `--all` is passed).
+///
+/// Ctrl+F switches the search box from matching titles to full-text search over
+/// session contents. Passing `initial_content_search` opens the picker in that
+/// mode with the query pre-filled.
 pub async fn run_resume_picker(
     tui: &mut Tui,
     config: &Config,
     show_all: bool,
+    initial_content_search: Option<String>,
 ) -> Result<SessionSelection> {
-    run_session_picker(tui, config, show_all, SessionPickerAction::Resume).await
+    run_session_picker(
+        tui,
+        config,
+        show_all,
+        SessionPickerAction::Resume,
+        initial_content_search,
+    )
+    .await
 }
 
 pub async fn run_fork_picker(
@@ -132,7 +158,7 @@ pub async fn run_fork_picker(
     config: &Config,
     show_all: bool,
 ) -> Result<SessionSelection> {
-    run_session_picker(tui, config, show_all, SessionPickerAction::Fork).await
+    run_session_picker(tui, config, show_all, SessionPickerAction::Fork, None).await
 }
 
 async fn run_session_picker(
@@ -140,6 +166,7 @@ async fn run_session_picker(
     config: &Config,
     show_all: bool,
     action: SessionPickerAction,
+    initial_content_search: Option<String>,
 ) -> Result<SessionSelection> {
     let alt = AltScreenGuard::enter(tui);
     let (bg_tx, bg_rx) = mpsc::unbounded_channel();
@@ -152,6 +179,30 @@ async fn run_session_picker(
         std::env::current_dir().ok()
     };
 
+    let search_config = config.clone();
+    let search_tx = bg_tx.clone();
+    let content_search_loader: ContentSearchLoader =
+        Arc::new(move |request: ContentSearchRequest| {
+            let tx = search_tx.clone();
+            let config = search_config.clone();
+            tokio::spawn(async move {
+                let provider_filter = vec![config.model_provider_id.clone()];
+                let hits = RolloutRecorder::search_threads(
+                    &config,
+                    request.query.as_str(),
+                    CONTENT_SEARCH_LIMIT,
+                    INTERACTIVE_SESSION_SOURCES,
+                    Some(provider_filter.as_slice()),
+                    false,
+                )
+                .await;
+                let _ = tx.send(BackgroundEvent::ContentSearchLoaded {
+                    request_token: request.request_token,
+                    hits,
+                });
+            });
+        });
+
     let config = config.clone();
     let loader_tx = bg_tx.clone();
     let page_loader: PageLoader = Arc::new(move |request: PageLoadRequest| {
@@ -187,7 +238,12 @@ async fn run_session_picker(
         filter_cwd,
         action,
     );
+    state.content_search_loader = Some(content_search_loader);
     state.start_initial_load();
+    if let Some(query) = initial_content_search {
+        state.toggle_content_search();
+        state.set_query(query);
+    }
     state.request_frame();
 
     let mut tui_events = alt.tui.event_stream().fuse();
@@ -275,6 +331,34 @@ struct PickerState {
     sort_key: ThreadSortKey,
     thread_name_cache: HashMap<ThreadId, Option<String>>,
     inline_error: Option<String>,
+    content_search: ContentSearchState,
+    content_search_loader: Option<ContentSearchLoader>,
+}
+
+/// Full-text search over session contents, used instead of title matching
+/// while enabled.
+enum ContentSearchState {
+    Disabled,
+    Enabled {
+        rows: Vec<Row>,
+        pending_request: Option<usize>,
+    },
+}
+
+impl ContentSearchState {
+    fn is_enabled(&self) -> bool {
+        matches!(self, ContentSearchState::Enabled { .. })
+    }
+
+    fn is_pending(&self) -> bool {
+        matches!(
+            self,
+            ContentSearchState::Enabled {
+                pending_request: Some(_),
+                ..
+            }
+        )
+    }
 }
 
 struct PaginationState {
@@ -336,6 +420,8 @@ struct Row {
     updated_at: Option<DateTime<Utc>>,
     cwd: Option<PathBuf>,
     git_branch: Option<String>,
+    /// Excerpt of the content that matched a full-text search.
+    snippet: Option<String>,
 }
 
 impl Row {
@@ -393,6 +479,8 @@ impl PickerState {
             sort_key: ThreadSortKey::UpdatedAt,
             thread_name_cache: HashMap::new(),
             inline_error: None,
+            content_search: ContentSearchState::Disabled,
+            content_search_loader: None,
         }
     }
 
@@ -461,6 +549,13 @@ impl PickerState {
                     self.request_frame();
                 }
             }
+            KeyCode::Char('f')
+                if key
+                    .modifiers
+                    .contains(crossterm::event::KeyModifiers::CONTROL) =>
+            {
+                self.toggle_content_search();
+            }
             KeyCode::Tab => {
                 self.toggle_sort_key();
                 self.request_frame();
@@ -540,6 +635,33 @@ impl PickerState {
                 let completed_token = pending.search_token.or(search_token);
                 self.continue_search_if_token_matches(completed_token);
             }
+            BackgroundEvent::ContentSearchLoaded {
+                request_token,
+                hits,
+            } => {
+                let ContentSearchState::Enabled {
+                    rows,
+                    pending_request,
+                } = &mut self.content_search
+                else {
+                    return Ok(());
+                };
+                if *pending_request != Some(request_token) {
+                    return Ok(());
+                }
+                *pending_request = None;
+                match hits {
+                    Ok(hits) => {
+                        *rows = hits.into_iter().map(|hit| hit_to_row(&hit)).collect();
+                    }
+                    Err(err) => {
+                        rows.clear();
+                        self.inline_error = Some(format!("Session search failed: {err}"));
+                    }
+                }
+                self.update_thread_names().await;
+                self.apply_filter();
+            }
         }
         Ok(())
     }
@@ -577,7 +699,7 @@ impl PickerState {
 
     async fn update_thread_names(&mut self) {
         let mut missing_ids = HashSet::new();
-        for row in &self.all_rows {
+        for row in self.all_rows.iter().chain(self.content_search_rows()) {
             let Some(thread_id) = row.thread_id else {
                 continue;
             };
@@ -599,8 +721,16 @@ impl PickerState {
             self.thread_name_cache.insert(thread_id, thread_name);
         }
 
+        let content_search_rows = match &mut self.content_search {
+            ContentSearchState::Enabled { rows, .. } => Some(rows),
+            ContentSearchState::Disabled => None,
+        };
         let mut updated = false;
-        for row in self.all_rows.iter_mut() {
+        for row in self
+            .all_rows
+            .iter_mut()
+            .chain(content_search_rows.into_iter().flatten())
+        {
             let Some(thread_id) = row.thread_id else {
                 continue;
             };
@@ -622,7 +752,13 @@ impl PickerState {
             .all_rows
             .iter()
             .filter(|row| self.row_matches_filter(row));
-        if self.query.is_empty() {
+        if self.content_search.is_enabled() {
+            self.filtered_rows = self
+                .content_search_rows()
+                .filter(|row| self.row_matches_filter(row))
+                .cloned()
+                .collect();
+        } else if self.query.is_empty() {
             self.filtered_rows = base_iter.cloned().collect();
         } else {
             let q = self.query.to_lowercase();
@@ -651,12 +787,70 @@ impl PickerState {
         paths_match(row_cwd, filter_cwd)
     }
 
+    fn content_search_rows(&self) -> impl Iterator<Item = &Row> {
+        let rows: &[Row] = match &self.content_search {
+            ContentSearchState::Enabled { rows, .. } => rows.as_slice(),
+            ContentSearchState::Disabled => &[],
+        };
+        rows.iter()
+    }
+
+    /// Switches the search box between title matching and full-text search
+    /// over session contents, keeping the current query.
+    fn toggle_content_search(&mut self) {
+        if self.content_search_loader.is_none() {
+            return;
+        }
+        self.selected = 0;
+        if self.content_search.is_enabled() {
+            self.content_search = ContentSearchState::Disabled;
+            self.apply_filter();
+            let query = std::mem::take(&mut self.query);
+            self.set_query(query);
+            return;
+        }
+        self.search_state = SearchState::Idle;
+        self.content_search = ContentSearchState::Enabled {
+            rows: Vec::new(),
+            pending_request: None,
+        };
+        self.start_content_search();
+    }
+
+    fn start_content_search(&mut self) {
+        let request_token = self.allocate_request_token();
+        let query = self.query.trim().to_string();
+        let ContentSearchState::Enabled {
+            rows,
+            pending_request,
+        } = &mut self.content_search
+        else {
+            return;
+        };
+        rows.clear();
+        *pending_request = None;
+        if !query.is_empty()
+            && let Some(loader) = self.content_search_loader.as_ref()
+        {
+            *pending_request = Some(request_token);
+            loader(ContentSearchRequest {
+                query,
+                request_token,
+            });
+        }
+        self.apply_filter();
+    }
+
     fn set_query(&mut self, new_query: String) {
         if self.query == new_query {
             return;
         }
         self.query = new_query;
         self.selected = 0;
+        if self.content_search.is_enabled() {
+            self.start_content_search();
+            return;
+        }
         self.apply_filter();
         if self.query.is_empty() {
             self.search_state = SearchState::Idle;
@@ -725,7 +919,7 @@ impl PickerState {
     }
 
     fn ensure_minimum_rows_for_view(&mut self, minimum_rows: usize) {
-        if minimum_rows == 0 {
+        if minimum_rows == 0 || self.content_search.is_enabled() {
             return;
         }
         if self.filtered_rows.len() >= minimum_rows {
@@ -747,7 +941,7 @@ impl PickerState {
     }
 
     fn maybe_load_more_for_scroll(&mut self) {
-        if self.pagination.loading.is_pending() {
+        if self.pagination.loading.is_pending() || self.content_search.is_enabled() {
             return;
         }
         if self.pagination.next_cursor.is_none() {
@@ -844,6 +1038,14 @@ fn head_to_row(item: &ThreadItem) -> Row {
         updated_at,
         cwd: item.cwd.clone(),
         git_branch: item.git_branch.clone(),
+        snippet: None,
+    }
+}
+
+fn hit_to_row(hit: &ThreadSearchHit) -> Row {
+    Row {
+        snippet: Some(hit.snippet.split_whitespace().collect::<Vec<_>>().join(" ")),
+        ..head_to_row(&hit.item)
     }
 }
 
@@ -912,6 +1114,9 @@ fn draw_picker(tui: &mut Tui, state: &PickerState) -> std::io::Result<()> {
             key_hint::plain(KeyCode::Tab).into(),
             " to toggle sort ".dim(),
             "    ".dim(),
+            key_hint::ctrl(KeyCode::Char('f')).into(),
+            " to search contents ".dim(),
+            "    ".dim(),
             key_hint::plain(KeyCode::Up).into(),
             "/".dim(),
             key_hint::plain(KeyCode::Down).into(),
@@ -926,6 +1131,12 @@ fn search_line(state: &PickerState) -> Line<'_> {
     if let Some(error) = state.inline_error.as_deref() {
         return Line::from(error.red());
     }
+    if state.content_search.is_enabled() {
+        if state.query.is_empty() {
+            return Line::from("Type to search session contents".dim());
+        }
+        return Line::from(format!("Search contents: {}", state.query));
+    }
     if state.query.is_empty() {
         return Line::from("Type to search".dim());
     }
@@ -1029,7 +1240,10 @@ fn render_list(
         if add_leading_gap {
             preview_width = preview_width.saturating_sub(2);
         }
-        let preview = truncate_text(row.display_preview(), preview_width);
+        let preview = truncate_text(
+            row.snippet.as_deref().unwrap_or(row.display_preview()),
+            preview_width,
+        );
         let mut spans: Vec<Span> = vec![marker];
         if let Some(created) = created_span {
             spans.push(created);
@@ -1066,6 +1280,15 @@ fn render_list(
 }
 
 fn render_empty_state_line(state: &PickerState) -> Line<'static> {
+    if state.content_search.is_enabled() {
+        if state.query.trim().is_empty() {
+            return vec!["Type to search session contents".italic().dim()].into();
+        }
+        if state.content_search.is_pending() {
+            return vec!["Searching…".italic().dim()].into();
+        }
+        return vec!["No sessions match your search".italic().dim()].into();
+    }
     if !state.query.is_empty() {
         if state.search_state.is_active()
             || (state.pagination.loading.is_pending() && state.pagination.next_cursor.is_some())
@@ -1550,6 +1773,7 @@ mod tests {
             updated_at: None,
             cwd: None,
             git_branch: None,
+            snippet: None,
         };
 
         assert_eq!(row.display_preview(), "My session");
@@ -1584,6 +1808,7 @@ mod tests {
                 updated_at: Some(now - Duration::seconds(42)),
                 cwd: None,
                 git_branch: None,
+                snippet: None,
             },
             Row {
                 path: PathBuf::from("/tmp/b.jsonl"),
@@ -1594,6 +1819,7 @@ mod tests {
                 updated_at: Some(now - Duration::minutes(35)),
                 cwd: None,
                 git_branch: None,
+                snippet: None,
             },
             Row {
                 path: PathBuf::from("/tmp/c.jsonl"),
@@ -1604,6 +1830,7 @@ mod tests {
                 updated_at: Some(now - Duration::hours(2)),
                 cwd: None,
                 git_branch: None,
+                snippet: None,
             },
         ];
         state.all_rows = rows.clone();
@@ -1898,6 +2125,7 @@ mod tests {
                 updated_at: Some(now - Duration::days(2)),
                 cwd: None,
                 git_branch: None,
+                snippet: None,
             },
             Row {
                 path: PathBuf::from("/tmp/b.jsonl"),
@@ -1908,6 +2136,7 @@ mod tests {
                 updated_at: Some(now - Duration::days(3)),
                 cwd: None,
                 git_branch: None,
+                snippet: None,
             },
         ];
         state.all_rows = rows.clone();
@@ -2188,6 +2417,7 @@ mod tests {
             updated_at: None,
             cwd: None,
             git_branch: None,
+            snippet: None,
         };
         state.all_rows = vec![row.clone()];
         state.filtered_rows = vec![row];
@@ -36,6 +36,7 @@ pub enum SlashCommand {
     Agent,
     // Undo,
     Diff,
+    Overlay,
     Copy,
     Mention,
     Status,
@@ -83,6 +84,9 @@ impl SlashCommand {
             // SlashCommand::Undo => "ask Codex to undo a turn",
             SlashCommand::Quit | SlashCommand::Exit => "exit Codex",
             SlashCommand::Diff => "show git diff (including untracked files)",
+            SlashCommand::Overlay => {
+                "review copy-on-write workspace changes: /overlay [accept|reject] [path ...]"
+            }
             SlashCommand::Copy => "copy the latest Codex output to your clipboard",
             SlashCommand::Mention => "mention a file",
             SlashCommand::Skills => "use skills to improve how Codex performs specific tasks",
@@ -94,7 +98,9 @@ impl SlashCommand {
             SlashCommand::DebugConfig => "show config layers and requirement sources for debugging",
             SlashCommand::Statusline => "configure which items appear in the status line",
             SlashCommand::Theme => "choose a syntax highlighting theme",
-            SlashCommand::Ps => "list background terminals",
+            SlashCommand::Ps => {
+                "list background terminals and services: /ps [start|stop|restart <name>]"
+            }
             SlashCommand::Clean => "stop all background terminals",
             SlashCommand::MemoryDrop => "DO NOT USE",
             SlashCommand::MemoryUpdate => "DO NOT USE",
@@ -138,6 +144,8 @@ impl SlashCommand {
                 | SlashCommand::RepoIndexRefresh
                 | SlashCommand::Fast
                 | SlashCommand::SandboxReadRoot
+                | SlashCommand::Overlay
+                | SlashCommand::Ps
         )
     }
 
@@ -163,6 +171,7 @@ impl SlashCommand {
             | SlashCommand::Plan
             | SlashCommand::Clear
             | SlashCommand::Logout
+            | SlashCommand::Overlay
             | SlashCommand::MemoryDrop
             | SlashCommand::MemoryUpdate => false,
             SlashCommand::Diff
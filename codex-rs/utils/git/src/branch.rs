@@ -47,6 +47,53 @@ pub fn merge_base_with_head(
     Ok(Some(merge_base))
 }
 
+/// Commits resolved from a `base..head` revision range.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommitRange {
+    pub base_sha: String,
+    pub head_sha: String,
+    /// Number of commits reachable from `head` but not from `base`.
+    pub commit_count: usize,
+}
+
+/// Resolves both ends of `base..head` to commit SHAs without touching the
+/// worktree, so ranges can be reviewed without checking anything out.
+///
+/// Returns `Ok(None)` when either revision does not name a commit.
+pub fn resolve_commit_range(
+    repo_path: &Path,
+    base: &str,
+    head: &str,
+) -> Result<Option<CommitRange>, GitToolingError> {
+    ensure_git_repository(repo_path)?;
+    let repo_root = resolve_repository_root(repo_path)?;
+
+    let Some(base_sha) = resolve_branch_ref(repo_root.as_path(), &format!("{base}^{{commit}}"))?
+    else {
+        return Ok(None);
+    };
+    let Some(head_sha) = resolve_branch_ref(repo_root.as_path(), &format!("{head}^{{commit}}"))?
+    else {
+        return Ok(None);
+    };
+
+    let count = run_git_for_stdout(
+        repo_root.as_path(),
+        vec![
+            OsString::from("rev-list"),
+            OsString::from("--count"),
+            OsString::from(format!("{base_sha}..{head_sha}")),
+        ],
+        None,
+    )?;
+
+    Ok(Some(CommitRange {
+        base_sha,
+        head_sha,
+        commit_count: count.trim().parse().unwrap_or(0),
+    }))
+}
+
 fn resolve_branch_ref(repo_root: &Path, branch: &str) -> Result<Option<String>, GitToolingError> {
     let rev = run_git_for_stdout(
         repo_root,
@@ -118,7 +165,9 @@ fn resolve_upstream_if_remote_ahead(
 
 #[cfg(test)]
 mod tests {
+    use super::CommitRange;
     use super::merge_base_with_head;
+    use super::resolve_commit_range;
     use crate::GitToolingError;
     use pretty_assertions::assert_eq;
     use std::path::Path;
@@ -253,4 +302,43 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn resolve_commit_range_returns_endpoints_and_count() -> Result<(), GitToolingError> {
+        let temp = tempdir()?;
+        let repo = temp.path();
+        init_test_repo(repo);
+
+        std::fs::write(repo.join("base.txt"), "base\n")?;
+        run_git_in(repo, &["add", "base.txt"]);
+        commit(repo, "base commit");
+        let base_sha = run_git_stdout(repo, &["rev-parse", "HEAD"]);
+
+        run_git_in(repo, &["checkout", "-b", "feature"]);
+        for name in ["one.txt", "two.txt"] {
+            std::fs::write(repo.join(name), "change\n")?;
+            run_git_in(repo, &["add", name]);
+            commit(repo, name);
+        }
+        let head_sha = run_git_stdout(repo, &["rev-parse", "HEAD"]);
+        run_git_in(repo, &["checkout", "main"]);
+
+        let range = resolve_commit_range(repo, "main", "feature")?;
+        assert_eq!(
+            range,
+            Some(CommitRange {
+                base_sha,
+                head_sha,
+                commit_count: 2,
+            })
+        );
+        assert_eq!(
+            run_git_stdout(repo, &["rev-parse", "--abbrev-ref", "HEAD"]),
+            "main"
+        );
+
+        assert_eq!(resolve_commit_range(repo, "main", "missing")?, None);
+
+        Ok(())
+    }
 }
@@ -37,8 +37,18 @@ export type TurnCompletedEvent = {
 export type TurnFailedEvent = {
   type: "turn.failed";
   error: ThreadError;
+  /** Set when the turn was stopped by a run budget such as `--max-turns` or `--timeout`. */
+  reason?: RunBudgetKind;
 };
 
+/** The run budget that stopped a turn. */
+export type RunBudgetKind =
+  | "max_turns"
+  | "max_output_tokens"
+  | "max_total_tokens"
+  | "timeout"
+  | "max_tool_calls";
+
 /** Emitted when a new item is added to the thread. Typically the item is initially "in progress". */
 export type ItemStartedEvent = {
   type: "item.started";
@@ -4,6 +4,7 @@ export type {
   TurnStartedEvent,
   TurnCompletedEvent,
   TurnFailedEvent,
+  RunBudgetKind,
   ItemStartedEvent,
   ItemUpdatedEvent,
   ItemCompletedEvent,